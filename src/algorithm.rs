@@ -0,0 +1,696 @@
+use crate::grid::Grid2;
+use alloc::vec::Vec;
+use core::ops::Range;
+
+// -----------------------------------------------------------------------------
+// Binary search
+// -----------------------------------------------------------------------------
+
+/// Index of the first element of a sorted slice which is not less than `x`.
+///
+/// # Arguments
+///
+/// * `s` - Ascending sorted slice
+/// * `x` - Searched value
+///
+/// # Examples
+///
+/// ```
+/// use ctl::algorithm::lower_bound;
+/// let i = lower_bound(&[1, 2, 2, 2, 5], &2); // 1
+/// ```
+pub fn lower_bound<T: Ord>(s: &[T], x: &T) -> usize {
+    s.partition_point(|e| e < x)
+}
+
+/// Index of the first element of a slice sorted by `f` whose key is not less than `key`.
+///
+/// # Arguments
+///
+/// * `s` - Slice sorted ascending by key
+/// * `key` - Searched key
+/// * `f` - Key extraction function
+///
+/// # Examples
+///
+/// ```
+/// use ctl::algorithm::lower_bound_by_key;
+/// let s = [(1, 'a'), (3, 'b'), (3, 'c')];
+/// let i = lower_bound_by_key(&s, &3, |p| p.0); // 1
+/// ```
+pub fn lower_bound_by_key<T, K: Ord, F: FnMut(&T) -> K>(s: &[T], key: &K, mut f: F) -> usize {
+    s.partition_point(|e| f(e) < *key)
+}
+
+/// Index of the first element of a sorted slice which is greater than `x`.
+///
+/// # Arguments
+///
+/// * `s` - Ascending sorted slice
+/// * `x` - Searched value
+///
+/// # Examples
+///
+/// ```
+/// use ctl::algorithm::upper_bound;
+/// let i = upper_bound(&[1, 2, 2, 2, 5], &2); // 4
+/// ```
+pub fn upper_bound<T: Ord>(s: &[T], x: &T) -> usize {
+    s.partition_point(|e| e <= x)
+}
+
+/// Index of the first element of a slice sorted by `f` whose key is greater than `key`.
+///
+/// # Arguments
+///
+/// * `s` - Slice sorted ascending by key
+/// * `key` - Searched key
+/// * `f` - Key extraction function
+///
+/// # Examples
+///
+/// ```
+/// use ctl::algorithm::upper_bound_by_key;
+/// let s = [(1, 'a'), (3, 'b'), (3, 'c')];
+/// let i = upper_bound_by_key(&s, &3, |p| p.0); // 3
+/// ```
+pub fn upper_bound_by_key<T, K: Ord, F: FnMut(&T) -> K>(s: &[T], key: &K, mut f: F) -> usize {
+    s.partition_point(|e| f(e) <= *key)
+}
+
+/// Index range of all elements of a sorted slice which are equal to `x`.
+///
+/// # Arguments
+///
+/// * `s` - Ascending sorted slice
+/// * `x` - Searched value
+///
+/// # Examples
+///
+/// ```
+/// use ctl::algorithm::equal_range;
+/// let r = equal_range(&[1, 2, 2, 2, 5], &2); // 1..4
+/// ```
+pub fn equal_range<T: Ord>(s: &[T], x: &T) -> Range<usize> {
+    lower_bound(s, x)..upper_bound(s, x)
+}
+
+/// Index range of all elements of a slice sorted by `f` whose key equals `key`.
+///
+/// # Arguments
+///
+/// * `s` - Slice sorted ascending by key
+/// * `key` - Searched key
+/// * `f` - Key extraction function
+///
+/// # Examples
+///
+/// ```
+/// use ctl::algorithm::equal_range_by_key;
+/// let s = [(1, 'a'), (3, 'b'), (3, 'c')];
+/// let r = equal_range_by_key(&s, &3, |p| p.0); // 1..3
+/// ```
+pub fn equal_range_by_key<T, K: Ord, F: FnMut(&T) -> K>(
+    s: &[T],
+    key: &K,
+    mut f: F,
+) -> Range<usize> {
+    lower_bound_by_key(s, key, &mut f)..upper_bound_by_key(s, key, &mut f)
+}
+
+// -----------------------------------------------------------------------------
+// Modifying operations
+// -----------------------------------------------------------------------------
+
+/// Moves the first element of every run of equal consecutive elements to the front of the
+/// slice and returns their count. Elements behind the returned count are left in
+/// unspecified order.
+///
+/// # Arguments
+///
+/// * `s` - Slice to deduplicate
+///
+/// # Examples
+///
+/// ```
+/// use ctl::algorithm::unique;
+/// let mut s = [1, 1, 2, 3, 3, 3, 1];
+/// let n = unique(&mut s); // 4, s[..n] == [1, 2, 3, 1]
+/// ```
+pub fn unique<T: PartialEq>(s: &mut [T]) -> usize {
+    unique_by(s, |a, b| a == b)
+}
+
+/// [`unique`] using a custom equality predicate.
+///
+/// # Arguments
+///
+/// * `s` - Slice to deduplicate
+/// * `eq` - Returns true when 2 elements are considered equal
+///
+/// # Examples
+///
+/// ```
+/// use ctl::algorithm::unique_by;
+/// let mut s = [1, -1, 2, -2, 3];
+/// let n = unique_by(&mut s, |a: &i32, b: &i32| a.abs() == b.abs()); // 3
+/// ```
+pub fn unique_by<T, F: FnMut(&T, &T) -> bool>(s: &mut [T], mut eq: F) -> usize {
+    if s.is_empty() {
+        return 0;
+    }
+
+    let mut n = 1;
+
+    for i in 1..s.len() {
+        if !eq(&s[n - 1], &s[i]) {
+            s.swap(n, i);
+            n += 1;
+        }
+    }
+
+    n
+}
+
+/// [`unique`] comparing the keys extracted by `f`.
+///
+/// # Arguments
+///
+/// * `s` - Slice to deduplicate
+/// * `f` - Key extraction function
+///
+/// # Examples
+///
+/// ```
+/// use ctl::algorithm::unique_by_key;
+/// let mut s = [(1, 'a'), (1, 'b'), (2, 'c')];
+/// let n = unique_by_key(&mut s, |p| p.0); // 2, s[..n] == [(1, 'a'), (2, 'c')]
+/// ```
+pub fn unique_by_key<T, K: PartialEq, F: FnMut(&T) -> K>(s: &mut [T], mut f: F) -> usize {
+    unique_by(s, |a, b| f(a) == f(b))
+}
+
+/// Rotates the slice so that the element at `mid` becomes the first one and returns the new
+/// index of the previously first element.
+///
+/// # Arguments
+///
+/// * `s` - Slice to rotate
+/// * `mid` - Index of the new first element
+///
+/// # Examples
+///
+/// ```
+/// use ctl::algorithm::rotate;
+/// let mut s = [1, 2, 3, 4, 5];
+/// let i = rotate(&mut s, 2); // 3, s == [3, 4, 5, 1, 2]
+/// ```
+pub fn rotate<T>(s: &mut [T], mid: usize) -> usize {
+    s.rotate_left(mid);
+    s.len() - mid
+}
+
+/// Moves the elements inside `range` so that they start at index `to` while keeping the order
+/// of all other elements. Returns the new range of the moved elements.
+///
+/// # Arguments
+///
+/// * `s` - Slice to modify
+/// * `range` - Elements to move
+/// * `to` - Target position of the moved elements, relative to the slice without them
+///
+/// # Examples
+///
+/// ```
+/// use ctl::algorithm::slide;
+/// let mut s = [1, 2, 3, 4, 5];
+/// let r = slide(&mut s, 3..5, 0); // 0..2, s == [4, 5, 1, 2, 3]
+/// ```
+pub fn slide<T>(s: &mut [T], range: Range<usize>, to: usize) -> Range<usize> {
+    let len = range.end - range.start;
+    assert!(range.end <= s.len() && to + len <= s.len());
+
+    if to < range.start {
+        s[to..range.end].rotate_right(len);
+    } else {
+        s[range.start..to + len].rotate_left(len);
+    }
+
+    to..to + len
+}
+
+// -----------------------------------------------------------------------------
+// Assignment
+// -----------------------------------------------------------------------------
+
+/// Marks a row of [`assignment`] that got no column.
+pub const UNASSIGNED: usize = usize::MAX;
+
+/// Solves the linear assignment problem with the Hungarian method (Jonker-Volgenant style
+/// shortest augmenting paths) in O(n^2 m). Row `y` of the matrix holds the costs of assigning
+/// worker `y` to the jobs `x`. Rectangular matrices assign every row when there are at least as
+/// many columns, otherwise every column gets a row and the remaining rows are [`UNASSIGNED`].
+///
+/// Returns the column of every row and the total cost.
+///
+/// # Arguments
+///
+/// * `cost` - Cost matrix indexed by `(column, row)`
+///
+/// # Examples
+///
+/// ```
+/// use ctl::algorithm::assignment;
+/// use ctl::grid::Grid2;
+/// let cost = Grid2::from_vec(3, 3, vec![4.0, 1.0, 3.0, 2.0, 0.0, 5.0, 3.0, 2.0, 2.0]);
+/// let (cols, total) = assignment(&cost); // ([1, 0, 2], 5.0)
+/// ```
+pub fn assignment(cost: &Grid2<f64>) -> (Vec<usize>, f64) {
+    let (rows, cols) = (cost.height(), cost.width());
+
+    if rows > cols {
+        // Solve the transposed problem and invert the mapping
+        let (by_col, total) = assignment(&cost.transpose());
+        let mut by_row = vec![UNASSIGNED; rows];
+
+        for (c, &r) in by_col.iter().enumerate() {
+            by_row[r] = c;
+        }
+
+        return (by_row, total);
+    }
+
+    // 1-based potentials with column 0 as the virtual source of every augmenting path
+    let (n, m) = (rows, cols);
+    let mut u = vec![0.0; n + 1];
+    let mut v = vec![0.0; m + 1];
+    let mut owner = vec![0; m + 1];
+    let mut way = vec![0; m + 1];
+
+    for row in 1..=n {
+        owner[0] = row;
+        let mut j0 = 0;
+        let mut min_v = vec![f64::INFINITY; m + 1];
+        let mut used = vec![false; m + 1];
+
+        loop {
+            used[j0] = true;
+            let i0 = owner[j0];
+            let mut delta = f64::INFINITY;
+            let mut j1 = 0;
+
+            for j in 1..=m {
+                if !used[j] {
+                    let reduced = cost[(j - 1, i0 - 1)] - u[i0] - v[j];
+
+                    if reduced < min_v[j] {
+                        min_v[j] = reduced;
+                        way[j] = j0;
+                    }
+                    if min_v[j] < delta {
+                        delta = min_v[j];
+                        j1 = j;
+                    }
+                }
+            }
+
+            for j in 0..=m {
+                if used[j] {
+                    u[owner[j]] += delta;
+                    v[j] -= delta;
+                } else {
+                    min_v[j] -= delta;
+                }
+            }
+
+            j0 = j1;
+            if owner[j0] == 0 {
+                break;
+            }
+        }
+
+        // Flip the augmenting path
+        while j0 != 0 {
+            let j1 = way[j0];
+            owner[j0] = owner[j1];
+            j0 = j1;
+        }
+    }
+
+    let mut by_row = vec![UNASSIGNED; n];
+    let mut total = 0.0;
+
+    for j in 1..=m {
+        if owner[j] != 0 {
+            by_row[owner[j] - 1] = j - 1;
+            total += cost[(j - 1, owner[j] - 1)];
+        }
+    }
+
+    (by_row, total)
+}
+
+// -----------------------------------------------------------------------------
+// Knapsack
+// -----------------------------------------------------------------------------
+
+/// Solves the 0/1 knapsack problem in O(n * capacity). Returns the highest reachable value and
+/// the indices of the chosen items in ascending order.
+///
+/// # Arguments
+///
+/// * `weights` - Weight of every item
+/// * `values` - Value of every item
+/// * `capacity` - Maximal total weight
+///
+/// # Examples
+///
+/// ```
+/// use ctl::algorithm::knapsack;
+/// let (v, items) = knapsack(&[1, 3, 4, 5], &[1, 4, 5, 7], 7); // (9, [1, 2])
+/// ```
+pub fn knapsack(weights: &[usize], values: &[u64], capacity: usize) -> (u64, Vec<usize>) {
+    assert_eq!(
+        weights.len(),
+        values.len(),
+        "every item needs a weight and value"
+    );
+
+    let n = weights.len();
+    let mut best = vec![0u64; capacity + 1];
+    let mut take = vec![false; n * (capacity + 1)];
+
+    for i in 0..n {
+        let w = weights[i];
+
+        for c in (w..=capacity).rev() {
+            let v = best[c - w] + values[i];
+
+            if v > best[c] {
+                best[c] = v;
+                take[i * (capacity + 1) + c] = true;
+            }
+        }
+    }
+
+    let mut items = Vec::new();
+    let mut c = capacity;
+
+    for i in (0..n).rev() {
+        if take[i * (capacity + 1) + c] {
+            items.push(i);
+            c -= weights[i];
+        }
+    }
+
+    items.reverse();
+    (best[capacity], items)
+}
+
+/// Splits every item into 0/1 items of 1, 2, 4, ... copies so that any count up to the bound
+/// can be combined. Returns the original index and number of copies of every part.
+fn split_bounded(counts: &[usize]) -> Vec<(usize, usize)> {
+    let mut parts = Vec::new();
+
+    for (i, &count) in counts.iter().enumerate() {
+        let (mut left, mut k) = (count, 1);
+
+        while left > 0 {
+            let take = k.min(left);
+            parts.push((i, take));
+            left -= take;
+            k *= 2;
+        }
+    }
+
+    parts
+}
+
+/// Expands the chosen split parts back into item indices, one entry per copy.
+fn join_bounded(parts: &[(usize, usize)], chosen: &[usize]) -> Vec<usize> {
+    let mut items: Vec<usize> = chosen
+        .iter()
+        .flat_map(|&p| core::iter::repeat_n(parts[p].0, parts[p].1))
+        .collect();
+    items.sort_unstable();
+    items
+}
+
+/// Solves the bounded knapsack problem, where item `i` may be taken up to `counts[i]` times, in
+/// O(capacity * sum(log counts)). Returns the highest reachable value and the indices of the
+/// chosen items in ascending order, repeated once per taken copy.
+///
+/// # Arguments
+///
+/// * `weights` - Weight of every item
+/// * `values` - Value of every item
+/// * `counts` - Available copies of every item
+/// * `capacity` - Maximal total weight
+///
+/// # Examples
+///
+/// ```
+/// use ctl::algorithm::bounded_knapsack;
+/// let (v, items) = bounded_knapsack(&[2, 3], &[3, 4], &[3, 1], 7); // (10, [0, 0, 1])
+/// ```
+pub fn bounded_knapsack(
+    weights: &[usize],
+    values: &[u64],
+    counts: &[usize],
+    capacity: usize,
+) -> (u64, Vec<usize>) {
+    assert_eq!(
+        weights.len(),
+        values.len(),
+        "every item needs a weight and value"
+    );
+    assert_eq!(weights.len(), counts.len(), "every item needs a count");
+
+    let parts = split_bounded(counts);
+    let w: Vec<usize> = parts.iter().map(|&(i, k)| weights[i] * k).collect();
+    let v: Vec<u64> = parts.iter().map(|&(i, k)| values[i] * k as u64).collect();
+
+    let (value, chosen) = knapsack(&w, &v, capacity);
+    (value, join_bounded(&parts, &chosen))
+}
+
+/// Reachable sums of a subset sum problem stored as a bitset.
+#[derive(Clone)]
+struct SumSet {
+    words: Vec<u64>,
+}
+
+impl SumSet {
+    fn new(max: usize) -> Self {
+        let mut words = vec![0; max / 64 + 1];
+        words[0] = 1;
+        SumSet { words }
+    }
+
+    fn contains(&self, i: usize) -> bool {
+        self.words[i / 64] >> (i % 64) & 1 == 1
+    }
+
+    /// Adds every reachable sum increased by `shift`, sums beyond the capacity get dropped.
+    fn add_shifted(&mut self, shift: usize) {
+        let (word, bit) = (shift / 64, shift % 64);
+        let len = self.words.len();
+
+        for i in (word..len).rev() {
+            let mut v = self.words[i - word] << bit;
+            if bit > 0 && i > word {
+                v |= self.words[i - word - 1] >> (64 - bit);
+            }
+            self.words[i] |= v;
+        }
+    }
+}
+
+/// Shared backtracking of the subset sum variants. Keeps the reachable sums after every item so
+/// the chosen items can be reconstructed.
+fn subset_sum_parts(weights: &[usize], target: usize) -> Option<Vec<usize>> {
+    let mut reach = vec![SumSet::new(target)];
+
+    for &w in weights {
+        let mut next = reach.last().unwrap().clone();
+        if w <= target {
+            next.add_shifted(w);
+        }
+        reach.push(next);
+    }
+
+    if !reach.last().unwrap().contains(target) {
+        return None;
+    }
+
+    let mut items = Vec::new();
+    let mut t = target;
+
+    for i in (0..weights.len()).rev() {
+        if !reach[i].contains(t) {
+            items.push(i);
+            t -= weights[i];
+        }
+    }
+
+    items.reverse();
+    Some(items)
+}
+
+/// Finds a subset of the weights summing to exactly `target` using a bitset DP in
+/// O(n * target / 64). Returns the indices of the chosen items in ascending order or `None` if
+/// the target can't be reached.
+///
+/// # Arguments
+///
+/// * `weights` - Weight of every item
+/// * `target` - Required sum
+///
+/// # Examples
+///
+/// ```
+/// use ctl::algorithm::subset_sum;
+/// let s = subset_sum(&[3, 34, 4, 12, 5, 2], 9); // Some([2, 4])
+/// ```
+pub fn subset_sum(weights: &[usize], target: usize) -> Option<Vec<usize>> {
+    subset_sum_parts(weights, target)
+}
+
+/// Bounded version of [`subset_sum`], where item `i` may be taken up to `counts[i]` times. The
+/// chosen indices are repeated once per taken copy.
+///
+/// # Arguments
+///
+/// * `weights` - Weight of every item
+/// * `counts` - Available copies of every item
+/// * `target` - Required sum
+///
+/// # Examples
+///
+/// ```
+/// use ctl::algorithm::bounded_subset_sum;
+/// let s = bounded_subset_sum(&[5, 3], &[1, 4], 11); // Some([0, 1, 1])
+/// ```
+pub fn bounded_subset_sum(
+    weights: &[usize],
+    counts: &[usize],
+    target: usize,
+) -> Option<Vec<usize>> {
+    assert_eq!(weights.len(), counts.len(), "every item needs a count");
+
+    let parts = split_bounded(counts);
+    let w: Vec<usize> = parts.iter().map(|&(i, k)| weights[i] * k).collect();
+
+    subset_sum_parts(&w, target).map(|chosen| join_bounded(&parts, &chosen))
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bounds() {
+        let s = [1, 2, 2, 2, 5, 7];
+
+        assert_eq!(lower_bound(&s, &0), 0);
+        assert_eq!(lower_bound(&s, &2), 1);
+        assert_eq!(lower_bound(&s, &3), 4);
+        assert_eq!(lower_bound(&s, &8), 6);
+        assert_eq!(upper_bound(&s, &2), 4);
+        assert_eq!(upper_bound(&s, &7), 6);
+        assert_eq!(equal_range(&s, &2), 1..4);
+        assert_eq!(equal_range(&s, &4), 4..4);
+
+        let p = [(1, 'a'), (3, 'b'), (3, 'c'), (4, 'd')];
+        assert_eq!(lower_bound_by_key(&p, &3, |p| p.0), 1);
+        assert_eq!(upper_bound_by_key(&p, &3, |p| p.0), 3);
+        assert_eq!(equal_range_by_key(&p, &2, |p| p.0), 1..1);
+    }
+
+    #[test]
+    fn test_unique() {
+        let mut s = [1, 1, 2, 3, 3, 3, 1];
+        let n = unique(&mut s);
+        assert_eq!(&s[..n], &[1, 2, 3, 1]);
+
+        let mut e: [i32; 0] = [];
+        assert_eq!(unique(&mut e), 0);
+
+        let mut p = [(1, 'a'), (1, 'b'), (2, 'c'), (2, 'd')];
+        let n = unique_by_key(&mut p, |p| p.0);
+        assert_eq!(&p[..n], &[(1, 'a'), (2, 'c')]);
+    }
+
+    #[test]
+    fn test_rotate() {
+        let mut s = [1, 2, 3, 4, 5];
+        assert_eq!(rotate(&mut s, 2), 3);
+        assert_eq!(s, [3, 4, 5, 1, 2]);
+
+        let mut s = [1, 2, 3, 4, 5];
+        assert_eq!(slide(&mut s, 3..5, 0), 0..2);
+        assert_eq!(s, [4, 5, 1, 2, 3]);
+
+        let mut s = [1, 2, 3, 4, 5];
+        assert_eq!(slide(&mut s, 0..2, 2), 2..4);
+        assert_eq!(s, [3, 4, 1, 2, 5]);
+    }
+
+    #[test]
+    fn test_assignment() {
+        #[rustfmt::skip]
+        let cost = Grid2::from_vec(4, 4, vec![
+            9.0, 2.0, 7.0, 8.0,
+            6.0, 4.0, 3.0, 7.0,
+            5.0, 8.0, 1.0, 8.0,
+            7.0, 6.0, 9.0, 4.0,
+        ]);
+        assert_eq!(assignment(&cost), (vec![1, 0, 2, 3], 13.0));
+
+        // 2 workers, 3 jobs and the transposed case
+        let wide = Grid2::from_vec(3, 2, vec![1.0, 5.0, 2.0, 1.0, 6.0, 9.0]);
+        assert_eq!(assignment(&wide), (vec![2, 0], 3.0));
+        assert_eq!(assignment(&wide.transpose()), (vec![1, UNASSIGNED, 0], 3.0));
+
+        assert_eq!(assignment(&Grid2::new(0, 0, 0.0)), (vec![], 0.0));
+    }
+
+    #[test]
+    fn test_knapsack() {
+        assert_eq!(knapsack(&[1, 3, 4, 5], &[1, 4, 5, 7], 7), (9, vec![1, 2]));
+        assert_eq!(knapsack(&[10, 20], &[5, 6], 5), (0, vec![]));
+        assert_eq!(knapsack(&[], &[], 3), (0, vec![]));
+
+        assert_eq!(
+            bounded_knapsack(&[2, 3], &[3, 4], &[3, 1], 7),
+            (10, vec![0, 0, 1])
+        );
+        assert_eq!(
+            bounded_knapsack(&[1, 4], &[1, 5], &[10, 2], 10),
+            (12, vec![0, 0, 1, 1])
+        );
+    }
+
+    #[test]
+    fn test_subset_sum() {
+        let w = [3, 34, 4, 12, 5, 2];
+        let s = subset_sum(&w, 9).unwrap();
+        assert_eq!(s.iter().map(|&i| w[i]).sum::<usize>(), 9);
+        assert_eq!(subset_sum(&w, 30), None);
+        assert_eq!(subset_sum(&w, 0), Some(vec![]));
+
+        // Shifts across word boundaries
+        let w = [70, 100, 130, 1];
+        assert_eq!(subset_sum(&w, 201), Some(vec![0, 2, 3]));
+
+        assert_eq!(
+            bounded_subset_sum(&[5, 3], &[1, 4], 11),
+            Some(vec![0, 1, 1])
+        );
+        assert_eq!(bounded_subset_sum(&[5, 3], &[1, 4], 10), None);
+    }
+}
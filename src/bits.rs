@@ -0,0 +1,312 @@
+// -----------------------------------------------------------------------------
+// Powers of two
+// -----------------------------------------------------------------------------
+
+/// Checks whether exactly one bit is set. 0 isn't a power of two.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::bits::is_power_of_two;
+/// let x = is_power_of_two(64); // true
+/// let y = is_power_of_two(0); // false
+/// ```
+pub fn is_power_of_two(x: u64) -> bool {
+    x != 0 && x & (x - 1) == 0
+}
+
+/// Smallest power of two greater or equal to x, 1 for 0. `None` if it doesn't fit into a
+/// `u64`, which is the case above 2^63.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::bits::next_power_of_two;
+/// let x = next_power_of_two(100); // Some(128)
+/// let y = next_power_of_two(128); // Some(128)
+/// let z = next_power_of_two((1 << 63) + 1); // None
+/// ```
+pub fn next_power_of_two(x: u64) -> Option<u64> {
+    if x <= 1 {
+        return Some(1);
+    }
+    1u64.checked_shl(64 - (x - 1).leading_zeros())
+}
+
+/// Rounds x up to the next multiple of m, `None` if it doesn't fit into a `u64`. Uses a mask
+/// when m is a power of two. Panics if m is 0.
+///
+/// # Arguments
+///
+/// * `x` - Value to round
+/// * `m` - Step to round to
+///
+/// # Examples
+///
+/// ```
+/// use ctl::bits::round_up_to_multiple;
+/// let x = round_up_to_multiple(13, 8); // Some(16)
+/// let y = round_up_to_multiple(14, 7); // Some(14)
+/// let z = round_up_to_multiple(u64::MAX, 2); // None
+/// ```
+pub fn round_up_to_multiple(x: u64, m: u64) -> Option<u64> {
+    assert!(m != 0, "multiple of 0");
+
+    if is_power_of_two(m) {
+        return x.checked_add(m - 1).map(|y| y & !(m - 1));
+    }
+    match x % m {
+        0 => Some(x),
+        r => x.checked_add(m - r),
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Set bits
+// -----------------------------------------------------------------------------
+
+/// Iterator over the indices of the set bits of a mask, lowest first, by repeatedly clearing
+/// the lowest bit.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::bits::set_bits;
+/// let v: Vec<_> = set_bits(0b1010_0110).collect(); // [1, 2, 5, 7]
+/// let h = set_bits(0b1010_0110).next_back(); // Some(7)
+/// ```
+pub fn set_bits(mask: u64) -> SetBits {
+    SetBits { mask }
+}
+
+/// Iterator created by [`set_bits`].
+#[derive(Debug, Clone)]
+pub struct SetBits {
+    /// Bits not yet returned
+    mask: u64,
+}
+
+impl Iterator for SetBits {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32> {
+        if self.mask == 0 {
+            return None;
+        }
+
+        let i = self.mask.trailing_zeros();
+        self.mask &= self.mask - 1;
+        Some(i)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.mask.count_ones() as usize;
+        (n, Some(n))
+    }
+}
+
+impl DoubleEndedIterator for SetBits {
+    fn next_back(&mut self) -> Option<u32> {
+        if self.mask == 0 {
+            return None;
+        }
+
+        let i = 63 - self.mask.leading_zeros();
+        self.mask ^= 1 << i;
+        Some(i)
+    }
+}
+
+impl ExactSizeIterator for SetBits {}
+
+// -----------------------------------------------------------------------------
+// Submasks
+// -----------------------------------------------------------------------------
+
+/// Iterator over all submasks of a mask in descending order, from the mask itself down to 0.
+/// A mask with k set bits has 2^k submasks, and going over the submasks of every mask below
+/// 2^n takes O(3^n) in total.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::bits::submasks;
+/// let v: Vec<_> = submasks(0b101).collect(); // [0b101, 0b100, 0b001, 0]
+/// ```
+pub fn submasks(mask: u64) -> Submasks {
+    Submasks {
+        mask,
+        next: Some(mask),
+    }
+}
+
+/// Iterator created by [`submasks`].
+#[derive(Debug, Clone)]
+pub struct Submasks {
+    mask: u64,
+    /// Submask to be returned next, `None` after 0
+    next: Option<u64>,
+}
+
+impl Iterator for Submasks {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        let cur = self.next?;
+
+        // Subtracting 1 clears the lowest bit and sets all below, the mask keeps those in it
+        self.next = cur.checked_sub(1).map(|s| s & self.mask);
+        Some(cur)
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Gray code
+// -----------------------------------------------------------------------------
+
+/// Converts n to its reflected binary Gray code, in which n and n + 1 differ in exactly one
+/// bit.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::bits::gray;
+/// let g = gray(5); // 0b111
+/// ```
+pub fn gray(n: u64) -> u64 {
+    n ^ (n >> 1)
+}
+
+/// Recovers n from its Gray code g, the inverse of [`gray`], by XOR-ing all shifts of g in
+/// O(log 64) steps.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::bits::gray_inverse;
+/// let n = gray_inverse(0b111); // 5
+/// ```
+pub fn gray_inverse(mut g: u64) -> u64 {
+    let mut shift = 1;
+    while shift < 64 {
+        g ^= g >> shift;
+        shift <<= 1;
+    }
+    g
+}
+
+/// Iterator over the 2^n codes of the n bit Gray code, from 0 on, each differing from the
+/// previous in a single bit. The last code differs from 0 in a single bit too.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::bits::gray_code;
+/// let v: Vec<_> = gray_code(2).collect(); // [0b00, 0b01, 0b11, 0b10]
+/// ```
+pub fn gray_code(n: u32) -> GrayCode {
+    assert!(n <= 64, "more than 64 bits");
+    GrayCode {
+        next: Some(0),
+        last: if n == 0 { 0 } else { u64::MAX >> (64 - n) },
+    }
+}
+
+/// Iterator created by [`gray_code`].
+#[derive(Debug, Clone)]
+pub struct GrayCode {
+    /// Index of the next code, `None` when done
+    next: Option<u64>,
+    /// Index of the last code, 2^n - 1
+    last: u64,
+}
+
+impl Iterator for GrayCode {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        let i = self.next?;
+        self.next = if i == self.last { None } else { Some(i + 1) };
+        Some(gray(i))
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_powers_and_multiples() {
+        for x in 0..=1025u64 {
+            assert_eq!(is_power_of_two(x), x.is_power_of_two());
+            assert_eq!(next_power_of_two(x), Some(x.next_power_of_two()));
+            for m in 1..20 {
+                assert_eq!(round_up_to_multiple(x, m), Some(x.div_ceil(m) * m));
+            }
+        }
+        assert_eq!(next_power_of_two(1 << 63), Some(1 << 63));
+        assert_eq!(next_power_of_two(u64::MAX), None);
+        assert_eq!(round_up_to_multiple(u64::MAX, 1), Some(u64::MAX));
+        assert_eq!(round_up_to_multiple(u64::MAX - 1, 4), None);
+        assert_eq!(round_up_to_multiple(u64::MAX, 7), None);
+    }
+
+    #[test]
+    fn test_bit_iterators() {
+        let mask = 0x8000_0000_0000_0105u64;
+        let bits: Vec<_> = set_bits(mask).collect();
+        assert_eq!(bits, [0, 2, 8, 63]);
+        assert_eq!(set_bits(mask).len(), 4);
+        assert_eq!(set_bits(mask).rev().collect::<Vec<_>>(), [63, 8, 2, 0]);
+        assert_eq!(set_bits(0).next(), None);
+
+        // Mixed ends meet in the middle
+        let mut it = set_bits(mask);
+        assert_eq!(
+            (it.next(), it.next_back(), it.len()),
+            (Some(0), Some(63), 2)
+        );
+
+        let subs: Vec<_> = submasks(0b1011).collect();
+        assert_eq!(subs, [11, 10, 9, 8, 3, 2, 1, 0]);
+        assert_eq!(submasks(0).collect::<Vec<_>>(), [0]);
+        assert_eq!(
+            submasks(u64::MAX).take(3).collect::<Vec<_>>(),
+            [u64::MAX, u64::MAX - 1, u64::MAX - 2]
+        );
+
+        // 3^n pairs of a mask and its submask
+        let total: usize = (0..1u64 << 8).map(|m| submasks(m).count()).sum();
+        assert_eq!(total, 3usize.pow(8));
+    }
+
+    #[test]
+    fn test_gray_code() {
+        let codes: Vec<_> = gray_code(4).collect();
+        assert_eq!(codes.len(), 16);
+        for (i, &g) in codes.iter().enumerate() {
+            assert_eq!(g, gray(i as u64));
+            assert_eq!(gray_inverse(g), i as u64);
+            let next = codes[(i + 1) % codes.len()];
+            assert_eq!((g ^ next).count_ones(), 1);
+        }
+
+        // Every code appears once
+        let mut sorted = codes.clone();
+        sorted.sort_unstable();
+        assert_eq!(sorted, (0..16).collect::<Vec<_>>());
+
+        assert_eq!(gray_code(0).collect::<Vec<_>>(), [0]);
+        assert_eq!(gray_code(1).collect::<Vec<_>>(), [0, 1]);
+        for x in [u64::MAX, 1 << 63, 0x1234_5678_9ABC_DEF0] {
+            assert_eq!(gray_inverse(gray(x)), x);
+        }
+        let mut all = gray_code(64);
+        assert_eq!(all.nth(3), Some(0b10));
+    }
+}
@@ -0,0 +1,306 @@
+use crate::geometry::Point;
+use crate::random::Rng;
+use alloc::vec::Vec;
+
+// -----------------------------------------------------------------------------
+// Common
+// -----------------------------------------------------------------------------
+
+/// Label of points that belong to no cluster.
+pub const NOISE: usize = usize::MAX;
+
+/// Result of a clustering.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Clusters {
+    /// Cluster index of every input point, [`NOISE`] for outliers
+    pub labels: Vec<usize>,
+    /// Mean of the points of every cluster
+    pub centroids: Vec<Point<f64>>,
+}
+
+fn dist2(a: Point<f64>, b: Point<f64>) -> f64 {
+    let d = a - b;
+    d.x * d.x + d.y * d.y
+}
+
+fn centroids(points: &[Point<f64>], labels: &[usize], k: usize) -> Vec<Point<f64>> {
+    let mut sums = vec![(Point { x: 0.0, y: 0.0 }, 0usize); k];
+
+    for (&p, &l) in points.iter().zip(labels) {
+        if l != NOISE {
+            sums[l].0 = sums[l].0 + p;
+            sums[l].1 += 1;
+        }
+    }
+
+    sums.into_iter()
+        .map(|(s, n)| Point {
+            x: s.x / n as f64,
+            y: s.y / n as f64,
+        })
+        .collect()
+}
+
+// -----------------------------------------------------------------------------
+// K-means
+// -----------------------------------------------------------------------------
+
+/// Picks `k` start centroids with k-means++ seeding, every next one chosen with a probability
+/// proportional to its squared distance to the closest centroid so far.
+fn seed_plus_plus(points: &[Point<f64>], k: usize, rng: &mut Rng) -> Vec<Point<f64>> {
+    let mut centers = vec![points[rng.below(points.len() as u64) as usize]];
+    let mut d: Vec<f64> = points.iter().map(|&p| dist2(p, centers[0])).collect();
+
+    while centers.len() < k {
+        let total: f64 = d.iter().sum();
+
+        // All points coincide with centroids, any choice is as good as another
+        let next = if total > 0.0 {
+            let mut r = rng.next_f64() * total;
+            let mut idx = points.len() - 1;
+
+            for (i, &di) in d.iter().enumerate() {
+                if r < di {
+                    idx = i;
+                    break;
+                }
+                r -= di;
+            }
+
+            idx
+        } else {
+            rng.below(points.len() as u64) as usize
+        };
+
+        let c = points[next];
+        centers.push(c);
+        d.iter_mut()
+            .zip(points)
+            .for_each(|(d, &p)| *d = d.min(dist2(p, c)));
+    }
+
+    centers
+}
+
+/// Partitions the points into `k` clusters with Lloyd's algorithm and k-means++ seeding. Stops
+/// once no label changes or after `max_iterations` rounds. Clusters that run empty keep their
+/// previous centroid.
+///
+/// # Arguments
+///
+/// * `points` - Clustered points, must hold at least `k` of them
+/// * `k` - Number of clusters
+/// * `max_iterations` - Limit of assignment rounds
+/// * `rng` - Random source of the seeding
+///
+/// # Examples
+///
+/// ```
+/// use ctl::cluster::kmeans;
+/// use ctl::geometry::Point;
+/// use ctl::random::Rng;
+/// let p = |x, y| Point { x, y };
+/// let pts = [p(0.0, 0.0), p(0.0, 1.0), p(10.0, 0.0), p(10.0, 1.0)];
+/// let c = kmeans(&pts, 2, 100, &mut Rng::new(1)); // labels [a, a, b, b]
+/// ```
+pub fn kmeans(points: &[Point<f64>], k: usize, max_iterations: usize, rng: &mut Rng) -> Clusters {
+    assert!(k > 0 && k <= points.len(), "k must be in 1..=points.len()");
+
+    let mut centers = seed_plus_plus(points, k, rng);
+    let mut labels = vec![NOISE; points.len()];
+
+    for _ in 0..max_iterations {
+        let mut changed = false;
+
+        for (l, &p) in labels.iter_mut().zip(points) {
+            let nearest = (0..k)
+                .min_by(|&a, &b| {
+                    dist2(p, centers[a])
+                        .partial_cmp(&dist2(p, centers[b]))
+                        .unwrap_or(core::cmp::Ordering::Equal)
+                })
+                .unwrap();
+
+            if *l != nearest {
+                *l = nearest;
+                changed = true;
+            }
+        }
+
+        if !changed {
+            break;
+        }
+
+        for (c, new) in centers.iter_mut().zip(centroids(points, &labels, k)) {
+            if new.x.is_finite() {
+                *c = new;
+            }
+        }
+    }
+
+    Clusters {
+        labels,
+        centroids: centers,
+    }
+}
+
+// -----------------------------------------------------------------------------
+// DBSCAN
+// -----------------------------------------------------------------------------
+
+/// Density based clustering. Points with at least `min_pts` points (themselves included) within
+/// distance `eps` are core points; clusters are the connected groups of core points plus the
+/// points in their reach. Everything else is labeled [`NOISE`].
+///
+/// Neighbourhoods are found by sweeping over the points sorted by x, so each query only looks
+/// at the band `|dx| <= eps`.
+///
+/// # Arguments
+///
+/// * `points` - Clustered points
+/// * `eps` - Neighbourhood radius
+/// * `min_pts` - Neighbours needed for a core point
+///
+/// # Examples
+///
+/// ```
+/// use ctl::cluster::{dbscan, NOISE};
+/// use ctl::geometry::Point;
+/// let p = |x, y| Point { x, y };
+/// let pts = [p(0.0, 0.0), p(0.5, 0.0), p(1.0, 0.0), p(9.0, 9.0)];
+/// let c = dbscan(&pts, 0.6, 2); // labels [0, 0, 0, NOISE]
+/// ```
+pub fn dbscan(points: &[Point<f64>], eps: f64, min_pts: usize) -> Clusters {
+    let mut order: Vec<usize> = (0..points.len()).collect();
+    order.sort_by(|&a, &b| {
+        points[a]
+            .x
+            .partial_cmp(&points[b].x)
+            .unwrap_or(core::cmp::Ordering::Equal)
+    });
+
+    let eps2 = eps * eps;
+    let neighbours = |i: usize, out: &mut Vec<usize>| {
+        let p = points[i];
+        let lo = order.partition_point(|&j| points[j].x < p.x - eps);
+
+        out.clear();
+        out.extend(
+            order[lo..]
+                .iter()
+                .take_while(|&&j| points[j].x <= p.x + eps)
+                .filter(|&&j| dist2(points[j], p) <= eps2),
+        );
+    };
+
+    const UNVISITED: usize = usize::MAX - 1;
+
+    let mut labels = vec![UNVISITED; points.len()];
+    let mut k = 0;
+    let (mut near, mut stack) = (Vec::new(), Vec::new());
+
+    for i in 0..points.len() {
+        if labels[i] != UNVISITED {
+            continue;
+        }
+
+        neighbours(i, &mut near);
+        if near.len() < min_pts {
+            labels[i] = NOISE;
+            continue;
+        }
+
+        labels[i] = k;
+        stack.extend_from_slice(&near);
+
+        while let Some(j) = stack.pop() {
+            if labels[j] == NOISE {
+                // Border point reached from a core point
+                labels[j] = k;
+            }
+            if labels[j] != UNVISITED {
+                continue;
+            }
+
+            labels[j] = k;
+            neighbours(j, &mut near);
+
+            if near.len() >= min_pts {
+                stack.extend_from_slice(&near);
+            }
+        }
+
+        k += 1;
+    }
+
+    let centroids = centroids(points, &labels, k);
+    Clusters { labels, centroids }
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn blobs(rng: &mut Rng) -> Vec<Point<f64>> {
+        let centers = [(0.0, 0.0), (20.0, 0.0), (0.0, 20.0)];
+
+        (0..90)
+            .map(|i| {
+                let (cx, cy) = centers[i % 3];
+                Point {
+                    x: cx + rng.range_f64(-1.0, 1.0),
+                    y: cy + rng.range_f64(-1.0, 1.0),
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_kmeans() {
+        let mut rng = Rng::new(3);
+        let pts = blobs(&mut rng);
+        let c = kmeans(&pts, 3, 100, &mut rng);
+
+        for i in 3..pts.len() {
+            assert_eq!(c.labels[i], c.labels[i % 3]);
+        }
+
+        let c0 = c.centroids[c.labels[0]];
+        assert!(c0.x.abs() < 0.5 && c0.y.abs() < 0.5);
+
+        let same = [Point { x: 1.0, y: 1.0 }; 4];
+        let c = kmeans(&same, 2, 10, &mut rng);
+        assert_eq!(c.labels, vec![0; 4]);
+    }
+
+    #[test]
+    fn test_dbscan() {
+        let mut rng = Rng::new(4);
+        let mut pts = blobs(&mut rng);
+        pts.push(Point { x: 50.0, y: 50.0 });
+
+        let c = dbscan(&pts, 1.5, 4);
+
+        assert_eq!(c.centroids.len(), 3);
+        assert_eq!(c.labels[90], NOISE);
+        for i in 3..90 {
+            assert_eq!(c.labels[i], c.labels[i % 3]);
+        }
+
+        // The end of the chain is a border point only
+        let line: Vec<_> = (0..4)
+            .map(|i| Point {
+                x: i as f64,
+                y: 0.0,
+            })
+            .collect();
+        let c = dbscan(&line, 1.0, 3);
+        assert_eq!(c.labels, vec![0, 0, 0, 0]);
+        assert_eq!(c.centroids, vec![Point { x: 1.5, y: 0.0 }]);
+        assert_eq!(dbscan(&line, 0.5, 2).labels, vec![NOISE; 4]);
+    }
+}
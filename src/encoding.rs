@@ -0,0 +1,249 @@
+use alloc::vec::Vec;
+
+mod ecc;
+mod radix;
+
+pub use ecc::{
+    hamming74_decode, hamming74_encode, hamming84_decode, hamming84_encode, ReedSolomon,
+};
+pub use radix::{from_radix, from_radix_signed, to_radix, to_radix_signed, Alphabet};
+
+// -----------------------------------------------------------------------------
+// Run-length encoding
+// -----------------------------------------------------------------------------
+
+/// Compresses a slice into runs of equal consecutive values.
+///
+/// # Arguments
+///
+/// * `data` - Values to compress
+///
+/// # Examples
+///
+/// ```
+/// use ctl::encoding::run_length_encode;
+/// let r = run_length_encode(&[1, 1, 1, 4, 4, 1]); // [(1, 3), (4, 2), (1, 1)]
+/// ```
+pub fn run_length_encode<T: PartialEq + Clone>(data: &[T]) -> Vec<(T, usize)> {
+    let mut runs: Vec<(T, usize)> = Vec::new();
+
+    for x in data {
+        match runs.last_mut() {
+            Some((v, n)) if v == x => *n += 1,
+            _ => runs.push((x.clone(), 1)),
+        }
+    }
+
+    runs
+}
+
+/// Expands runs created by [`run_length_encode`] back into the original values.
+///
+/// # Arguments
+///
+/// * `runs` - Pairs of value and repetition count
+///
+/// # Examples
+///
+/// ```
+/// use ctl::encoding::run_length_decode;
+/// let d = run_length_decode(&[('a', 2), ('b', 1)]); // ['a', 'a', 'b']
+/// ```
+pub fn run_length_decode<T: Clone>(runs: &[(T, usize)]) -> Vec<T> {
+    let mut data = Vec::with_capacity(runs.iter().map(|(_, n)| n).sum());
+
+    for (v, n) in runs {
+        data.extend(core::iter::repeat_n(v.clone(), *n));
+    }
+
+    data
+}
+
+// -----------------------------------------------------------------------------
+// Delta encoding
+// -----------------------------------------------------------------------------
+
+/// Integers which can be stored as differences of their neighbours. Differences wrap around so
+/// encoding never overflows and decoding restores the exact values.
+pub trait Delta: Copy {
+    const ZERO: Self;
+
+    /// Wrapping difference `self - prev`
+    fn delta(self, prev: Self) -> Self;
+
+    /// Wrapping sum `self + prev`, inverse of [`Delta::delta`]
+    fn undelta(self, prev: Self) -> Self;
+}
+
+/// Signed integers which can be mapped onto unsigned ones so that values of small magnitude
+/// stay small: 0, -1, 1, -2, 2, ... become 0, 1, 2, 3, 4, ...
+pub trait ZigZag: Copy {
+    type Unsigned;
+
+    fn zigzag(self) -> Self::Unsigned;
+    fn unzigzag(u: Self::Unsigned) -> Self;
+}
+
+macro_rules! gen_delta {
+    ($($T:ty),*) => {
+        $(
+            impl Delta for $T {
+                const ZERO: Self = 0;
+
+                fn delta(self, prev: Self) -> Self {
+                    self.wrapping_sub(prev)
+                }
+
+                fn undelta(self, prev: Self) -> Self {
+                    self.wrapping_add(prev)
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! gen_zigzag {
+    ($($T:ty => $U:ty),*) => {
+        $(
+            impl ZigZag for $T {
+                type Unsigned = $U;
+
+                fn zigzag(self) -> $U {
+                    ((self << 1) ^ (self >> (<$T>::BITS - 1))) as $U
+                }
+
+                fn unzigzag(u: $U) -> Self {
+                    ((u >> 1) as $T) ^ -((u & 1) as $T)
+                }
+            }
+        )*
+    };
+}
+
+gen_delta!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+gen_zigzag!(i8 => u8, i16 => u16, i32 => u32, i64 => u64, i128 => u128, isize => usize);
+
+/// Replaces every value by its difference to the previous one. The first value is kept as is.
+///
+/// # Arguments
+///
+/// * `data` - Values to encode
+///
+/// # Examples
+///
+/// ```
+/// use ctl::encoding::delta_encode;
+/// let d = delta_encode(&[100, 102, 101, 105]); // [100, 2, -1, 4]
+/// ```
+pub fn delta_encode<T: Delta>(data: &[T]) -> Vec<T> {
+    let mut prev = T::ZERO;
+
+    data.iter()
+        .map(|&x| {
+            let d = x.delta(prev);
+            prev = x;
+            d
+        })
+        .collect()
+}
+
+/// Restores values encoded by [`delta_encode`] using a running sum.
+///
+/// # Arguments
+///
+/// * `deltas` - Differences to decode
+///
+/// # Examples
+///
+/// ```
+/// use ctl::encoding::delta_decode;
+/// let d = delta_decode(&[100, 2, -1, 4]); // [100, 102, 101, 105]
+/// ```
+pub fn delta_decode<T: Delta>(deltas: &[T]) -> Vec<T> {
+    let mut prev = T::ZERO;
+
+    deltas
+        .iter()
+        .map(|&d| {
+            prev = d.undelta(prev);
+            prev
+        })
+        .collect()
+}
+
+/// Maps a signed integer onto an unsigned one keeping small magnitudes small.
+///
+/// # Arguments
+///
+/// * `x` - Signed value
+///
+/// # Examples
+///
+/// ```
+/// use ctl::encoding::zigzag_encode;
+/// let z = zigzag_encode(-3i32); // 5
+/// ```
+pub fn zigzag_encode<T: ZigZag>(x: T) -> T::Unsigned {
+    x.zigzag()
+}
+
+/// Inverse of [`zigzag_encode`].
+///
+/// # Arguments
+///
+/// * `u` - Zigzag encoded value
+///
+/// # Examples
+///
+/// ```
+/// use ctl::encoding::zigzag_decode;
+/// let x: i32 = zigzag_decode(5u32); // -3
+/// ```
+pub fn zigzag_decode<T: ZigZag>(u: T::Unsigned) -> T {
+    T::unzigzag(u)
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_length() {
+        let data = [1, 1, 1, 4, 4, 1];
+        let runs = run_length_encode(&data);
+
+        assert_eq!(runs, vec![(1, 3), (4, 2), (1, 1)]);
+        assert_eq!(run_length_decode(&runs), data.to_vec());
+        assert_eq!(run_length_encode::<u8>(&[]), vec![]);
+    }
+
+    #[test]
+    fn test_delta() {
+        let data = [100i64, 102, 101, 105, i64::MIN, i64::MAX];
+        let deltas = delta_encode(&data);
+
+        assert_eq!(&deltas[..4], &[100, 2, -1, 4]);
+        assert_eq!(delta_decode(&deltas), data.to_vec());
+
+        let data = [5u8, 3, 255, 0];
+        assert_eq!(delta_decode(&delta_encode(&data)), data.to_vec());
+    }
+
+    #[test]
+    fn test_zigzag() {
+        assert_eq!(zigzag_encode(0i32), 0);
+        assert_eq!(zigzag_encode(-1i32), 1);
+        assert_eq!(zigzag_encode(1i32), 2);
+        assert_eq!(zigzag_encode(-3i32), 5);
+        assert_eq!(zigzag_encode(i8::MIN), 255);
+        assert_eq!(zigzag_encode(i8::MAX), 254);
+
+        for x in [-1000i64, -1, 0, 7, i64::MIN, i64::MAX] {
+            assert_eq!(zigzag_decode::<i64>(zigzag_encode(x)), x);
+        }
+    }
+}
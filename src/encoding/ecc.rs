@@ -0,0 +1,373 @@
+use alloc::vec::Vec;
+
+// -----------------------------------------------------------------------------
+// Hamming codes
+// -----------------------------------------------------------------------------
+
+/// Syndrome of a Hamming(7,4) word, the 1 based position of a single flipped bit or 0.
+fn hamming_syndrome(code: u8) -> u8 {
+    (1..=7u8)
+        .filter(|&p| code >> (p - 1) & 1 == 1)
+        .fold(0, |s, p| s ^ p)
+}
+
+/// Encodes the low 4 bits into a Hamming(7,4) code word, correcting any single flipped bit.
+/// Bit i - 1 holds position i of the layout p1 p2 d1 p3 d2 d3 d4, each parity bit covering the
+/// positions sharing one of its bits.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::encoding::{hamming74_decode, hamming74_encode};
+/// let c = hamming74_encode(0b1011); // 0b1010101
+/// let d = hamming74_decode(c ^ 0b100); // 0b1011, flipped bit corrected
+/// ```
+pub fn hamming74_encode(nibble: u8) -> u8 {
+    let d = |i: u8| nibble >> i & 1;
+    let (d1, d2, d3, d4) = (d(0), d(1), d(2), d(3));
+
+    let p1 = d1 ^ d2 ^ d4;
+    let p2 = d1 ^ d3 ^ d4;
+    let p3 = d2 ^ d3 ^ d4;
+
+    p1 | p2 << 1 | d1 << 2 | p3 << 3 | d2 << 4 | d3 << 5 | d4 << 6
+}
+
+/// Decodes a Hamming(7,4) code word, correcting up to one flipped bit. The 8th bit is ignored.
+pub fn hamming74_decode(code: u8) -> u8 {
+    let mut code = code & 0x7F;
+    let s = hamming_syndrome(code);
+    if s != 0 {
+        code ^= 1 << (s - 1);
+    }
+
+    (code >> 2 & 1) | (code >> 4 & 1) << 1 | (code >> 5 & 1) << 2 | (code >> 6 & 1) << 3
+}
+
+/// Encodes the low 4 bits into an extended Hamming(8,4) code word, the Hamming(7,4) word plus
+/// an overall parity bit on top. Corrects single and detects double flipped bits.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::encoding::{hamming84_decode, hamming84_encode};
+/// let c = hamming84_encode(0b0110);
+/// let a = hamming84_decode(c ^ 0b1000_0000); // Some(0b0110)
+/// let b = hamming84_decode(c ^ 0b11); // None, 2 bits flipped
+/// ```
+pub fn hamming84_encode(nibble: u8) -> u8 {
+    let code = hamming74_encode(nibble);
+    code | ((code.count_ones() & 1) as u8) << 7
+}
+
+/// Decodes an extended Hamming(8,4) code word. `None` if 2 bits were flipped, which can be
+/// detected but not corrected.
+pub fn hamming84_decode(code: u8) -> Option<u8> {
+    let s = hamming_syndrome(code & 0x7F);
+    let odd = code.count_ones() & 1 == 1;
+
+    match (s, odd) {
+        // A nonzero syndrome with even parity means 2 flips
+        (0, _) | (_, true) => Some(hamming74_decode(code)),
+        _ => None,
+    }
+}
+
+// -----------------------------------------------------------------------------
+// GF(256)
+// -----------------------------------------------------------------------------
+
+/// Powers of the generator 2 modulo x^8 + x^4 + x^3 + x^2 + 1, repeated once so products of 2
+/// logarithms need no reduction.
+const EXP: [u8; 512] = gf_tables().0;
+const LOG: [u8; 256] = gf_tables().1;
+
+const fn gf_tables() -> ([u8; 512], [u8; 256]) {
+    let mut exp = [0u8; 512];
+    let mut log = [0u8; 256];
+    let mut x: u16 = 1;
+    let mut i = 0;
+
+    while i < 255 {
+        exp[i] = x as u8;
+        exp[i + 255] = x as u8;
+        log[x as usize] = i as u8;
+
+        x <<= 1;
+        if x & 0x100 != 0 {
+            x ^= 0x11D;
+        }
+        i += 1;
+    }
+
+    (exp, log)
+}
+
+fn gf_mul(a: u8, b: u8) -> u8 {
+    if a == 0 || b == 0 {
+        return 0;
+    }
+    EXP[LOG[a as usize] as usize + LOG[b as usize] as usize]
+}
+
+fn gf_div(a: u8, b: u8) -> u8 {
+    assert!(b != 0, "division by zero in GF(256)");
+    if a == 0 {
+        return 0;
+    }
+    EXP[LOG[a as usize] as usize + 255 - LOG[b as usize] as usize]
+}
+
+/// 2^e for any exponent, negative ones included.
+fn gf_alpha(e: i64) -> u8 {
+    EXP[e.rem_euclid(255) as usize]
+}
+
+/// Evaluates a polynomial with the lowest coefficient first.
+fn poly_eval(p: &[u8], x: u8) -> u8 {
+    p.iter().rev().fold(0, |acc, &c| gf_mul(acc, x) ^ c)
+}
+
+// -----------------------------------------------------------------------------
+// Reed-Solomon
+// -----------------------------------------------------------------------------
+
+/// Reed-Solomon code over GF(256) appending `parity` check bytes to each message, which then
+/// corrects up to parity / 2 wrong bytes anywhere in the code word. Messages and parity
+/// together are limited to 255 bytes. The generator polynomial has the roots 2^0 to
+/// 2^(parity - 1), as in QR codes.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::encoding::ReedSolomon;
+/// let rs = ReedSolomon::new(4);
+/// let mut code = rs.encode(b"hello"); // 9 bytes
+/// code[0] ^= 0xFF;
+/// code[7] = 0;
+/// let data = rs.decode(&code); // Some(b"hello")
+/// ```
+#[derive(Debug, Clone)]
+pub struct ReedSolomon {
+    parity: usize,
+    /// Generator polynomial, highest coefficient first
+    generator: Vec<u8>,
+}
+
+impl ReedSolomon {
+    pub fn new(parity: usize) -> Self {
+        assert!(parity < 255, "too many parity bytes");
+
+        let mut generator = vec![1u8];
+        for i in 0..parity {
+            // Multiply by (x - 2^i)
+            let root = gf_alpha(i as i64);
+            let mut next = generator.clone();
+            next.push(0);
+            for (j, &g) in generator.iter().enumerate() {
+                next[j + 1] ^= gf_mul(g, root);
+            }
+            generator = next;
+        }
+
+        ReedSolomon { parity, generator }
+    }
+
+    /// Number of check bytes appended to each message.
+    pub fn parity(&self) -> usize {
+        self.parity
+    }
+
+    /// Appends the check bytes, the remainder of the message times x^parity divided by the
+    /// generator polynomial.
+    pub fn encode(&self, data: &[u8]) -> Vec<u8> {
+        assert!(data.len() + self.parity <= 255, "message too long");
+
+        let mut rem = vec![0u8; self.parity];
+        for &d in data {
+            let factor = d ^ rem.first().copied().unwrap_or(0);
+            rem.rotate_left(1);
+            if let Some(last) = rem.last_mut() {
+                *last = 0;
+            }
+            for (r, &g) in rem.iter_mut().zip(&self.generator[1..]) {
+                *r ^= gf_mul(g, factor);
+            }
+        }
+
+        let mut code = data.to_vec();
+        code.extend(rem);
+        code
+    }
+
+    /// Syndromes r(2^i) of the received word, all 0 for a valid code word.
+    fn syndromes(&self, code: &[u8]) -> Vec<u8> {
+        (0..self.parity)
+            .map(|i| {
+                let x = gf_alpha(i as i64);
+                code.iter().fold(0, |acc, &c| gf_mul(acc, x) ^ c)
+            })
+            .collect()
+    }
+
+    /// Corrects up to parity / 2 wrong bytes with Berlekamp-Massey, the Chien search and
+    /// Forney's formula, and returns the message without the check bytes. `None` if there are
+    /// too many errors to correct.
+    pub fn decode(&self, code: &[u8]) -> Option<Vec<u8>> {
+        let n = code.len();
+        if n < self.parity || n > 255 {
+            return None;
+        }
+
+        let s = self.syndromes(code);
+        if s.iter().all(|&x| x == 0) {
+            return Some(code[..n - self.parity].to_vec());
+        }
+
+        // Error locator Λ(x) = Π (1 - X x), lowest coefficient first
+        let (mut lambda, mut prev) = (vec![1u8], vec![1u8]);
+        let (mut len, mut gap, mut last) = (0, 1, 1u8);
+        for k in 0..self.parity {
+            let d = (1..=len).fold(s[k], |d, i| d ^ gf_mul(lambda[i], s[k - i]));
+            if d == 0 {
+                gap += 1;
+                continue;
+            }
+
+            let coef = gf_div(d, last);
+            let old = lambda.clone();
+            if lambda.len() < prev.len() + gap {
+                lambda.resize(prev.len() + gap, 0);
+            }
+            for (j, &p) in prev.iter().enumerate() {
+                lambda[j + gap] ^= gf_mul(coef, p);
+            }
+
+            if 2 * len <= k {
+                len = k + 1 - len;
+                prev = old;
+                last = d;
+                gap = 1;
+            } else {
+                gap += 1;
+            }
+        }
+        lambda.truncate(len + 1);
+        if 2 * len > self.parity {
+            return None;
+        }
+
+        // Chien search, the byte at index p belongs to the power n - 1 - p
+        let errors: Vec<usize> = (0..n)
+            .filter(|&p| poly_eval(&lambda, gf_alpha(-((n - 1 - p) as i64))) == 0)
+            .collect();
+        if errors.len() != len {
+            return None;
+        }
+
+        // Forney: e = X Ω(1 / X) / Λ'(1 / X) with Ω = S Λ mod x^parity
+        let mut omega = vec![0u8; self.parity];
+        for (i, &a) in s.iter().enumerate() {
+            for (j, &b) in lambda.iter().enumerate().take(self.parity - i) {
+                omega[i + j] ^= gf_mul(a, b);
+            }
+        }
+        let derivative: Vec<u8> = (1..lambda.len())
+            .map(|i| if i % 2 == 1 { lambda[i] } else { 0 })
+            .collect();
+
+        let mut fixed = code.to_vec();
+        for &p in &errors {
+            let x = gf_alpha((n - 1 - p) as i64);
+            let x_inv = gf_div(1, x);
+            let denom = poly_eval(&derivative, x_inv);
+            if denom == 0 {
+                return None;
+            }
+            fixed[p] ^= gf_div(gf_mul(x, poly_eval(&omega, x_inv)), denom);
+        }
+
+        if self.syndromes(&fixed).iter().any(|&x| x != 0) {
+            return None;
+        }
+        fixed.truncate(n - self.parity);
+        Some(fixed)
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::random::Rng;
+
+    #[test]
+    fn test_hamming() {
+        for nibble in 0..16u8 {
+            let c7 = hamming74_encode(nibble);
+            let c8 = hamming84_encode(nibble);
+            assert_eq!(hamming74_decode(c7), nibble);
+            assert_eq!(hamming84_decode(c8), Some(nibble));
+            assert_eq!(c8.count_ones() % 2, 0);
+
+            for i in 0..7 {
+                assert_eq!(hamming74_decode(c7 ^ 1 << i), nibble);
+            }
+            for i in 0..8 {
+                assert_eq!(hamming84_decode(c8 ^ 1 << i), Some(nibble));
+                for j in 0..i {
+                    assert_eq!(hamming84_decode(c8 ^ 1 << i ^ 1 << j), None);
+                }
+            }
+        }
+
+        // Every pair of code words differs in at least 3 and 4 bits
+        for a in 0..16u8 {
+            for b in 0..a {
+                assert!((hamming74_encode(a) ^ hamming74_encode(b)).count_ones() >= 3);
+                assert!((hamming84_encode(a) ^ hamming84_encode(b)).count_ones() >= 4);
+            }
+        }
+    }
+
+    #[test]
+    fn test_reed_solomon() {
+        assert_eq!(gf_mul(gf_div(57, 201), 201), 57);
+        assert_eq!(gf_mul(0x80, 2), 0x1D);
+
+        let mut rng = Rng::new(9);
+        for &(len, parity) in &[(5, 4), (20, 10), (223, 32), (1, 2), (0, 6)] {
+            let rs = ReedSolomon::new(parity);
+            let data: Vec<u8> = (0..len).map(|_| rng.next_u64() as u8).collect();
+            let code = rs.encode(&data);
+            assert_eq!(code.len(), len + parity);
+            assert_eq!(&code[..len], &data[..]);
+            assert_eq!(rs.decode(&code), Some(data.clone()));
+
+            // Up to parity / 2 errors at random places
+            for errors in 1..=parity / 2 {
+                for _ in 0..20 {
+                    let mut bad = code.clone();
+                    let mut places: Vec<usize> = (0..bad.len()).collect();
+                    rng.shuffle(&mut places);
+                    for &p in &places[..errors] {
+                        bad[p] ^= (rng.next_u64() % 255 + 1) as u8;
+                    }
+                    assert_eq!(rs.decode(&bad), Some(data.clone()), "{} errors", errors);
+                }
+            }
+        }
+
+        // Too many errors are detected rather than miscorrected here
+        let rs = ReedSolomon::new(4);
+        let mut bad = rs.encode(b"ctl rocks");
+        for b in &mut bad[..3] {
+            *b ^= 0x55;
+        }
+        assert_ne!(rs.decode(&bad), Some(b"ctl rocks".to_vec()));
+        assert_eq!(rs.decode(&[1, 2]), None);
+    }
+}
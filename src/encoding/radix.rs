@@ -0,0 +1,274 @@
+use crate::{Error, Result};
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+
+// -----------------------------------------------------------------------------
+// Alphabet
+// -----------------------------------------------------------------------------
+
+/// Digits 0 to 61 of the default alphabet. Bases up to 36 use lower case letters, like the
+/// `{:x}` format does.
+const DIGITS: &str = "0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+/// Digit characters of a positional number system, the base being their count. The signed
+/// functions read a leading `-` as the sign, so it shouldn't be a digit there.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::encoding::Alphabet;
+/// let dna = Alphabet::new("ACGT").unwrap();
+/// let s = dna.format(27); // "CGT"
+/// let n = dna.parse("CGT"); // Ok(27)
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Alphabet {
+    digits: Vec<char>,
+}
+
+impl Alphabet {
+    /// Alphabet of the given digits, lowest first. `None` for fewer than 2 digits or a repeated
+    /// one.
+    pub fn new(digits: &str) -> Option<Self> {
+        let digits: Vec<char> = digits.chars().collect();
+        let unique = digits
+            .iter()
+            .enumerate()
+            .all(|(i, c)| !digits[..i].contains(c));
+
+        if digits.len() >= 2 && unique {
+            Some(Alphabet { digits })
+        } else {
+            None
+        }
+    }
+
+    /// Number of digits.
+    pub fn base(&self) -> u32 {
+        self.digits.len() as u32
+    }
+
+    fn value(&self, c: char) -> Option<u64> {
+        self.digits.iter().position(|&d| d == c).map(|i| i as u64)
+    }
+
+    /// Writes n with the most significant digit first, a single zero digit for 0.
+    pub fn format(&self, mut n: u64) -> String {
+        let base = self.digits.len() as u64;
+        let mut rev = Vec::new();
+        loop {
+            rev.push(self.digits[(n % base) as usize]);
+            n /= base;
+            if n == 0 {
+                break;
+            }
+        }
+        rev.iter().rev().collect()
+    }
+
+    /// Writes n with a leading `-` if negative.
+    pub fn format_signed(&self, n: i64) -> String {
+        let digits = self.format(n.unsigned_abs());
+        if n < 0 {
+            format!("-{}", digits)
+        } else {
+            digits
+        }
+    }
+
+    /// Reads digits of this alphabet. Fails with [`Error::ParseError`] for an empty string or
+    /// an unknown character, and with [`Error::Overflow`] if the value doesn't fit.
+    pub fn parse(&self, s: &str) -> Result<u64> {
+        self.parse_with(s, |c| self.value(c))
+    }
+
+    /// Reads digits with an optional leading `-` or `+`. Fails like [`Alphabet::parse`].
+    pub fn parse_signed(&self, s: &str) -> Result<i64> {
+        parse_signed_with(s, |s| self.parse(s))
+    }
+
+    fn parse_with<F: Fn(char) -> Option<u64>>(&self, s: &str, value: F) -> Result<u64> {
+        if s.is_empty() {
+            return Err(Error::ParseError);
+        }
+
+        let base = self.digits.len() as u64;
+        s.chars().try_fold(0u64, |acc, c| {
+            let d = value(c).ok_or(Error::ParseError)?;
+            acc.checked_mul(base)
+                .and_then(|x| x.checked_add(d))
+                .ok_or(Error::Overflow)
+        })
+    }
+}
+
+/// Splits off the sign and range checks the magnitude.
+fn parse_signed_with<F: Fn(&str) -> Result<u64>>(s: &str, parse: F) -> Result<i64> {
+    let (negative, digits) = match s.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, s.strip_prefix('+').unwrap_or(s)),
+    };
+    let magnitude = parse(digits)?;
+
+    if negative {
+        0i64.checked_sub_unsigned(magnitude).ok_or(Error::Overflow)
+    } else {
+        i64::try_from(magnitude).map_err(|_| Error::Overflow)
+    }
+}
+
+/// Default alphabet of a base, the first `base` digits of 0-9, a-z, A-Z.
+fn default_alphabet(base: u32) -> Alphabet {
+    assert!((2..=62).contains(&base), "base must lie in 2..=62");
+    Alphabet {
+        digits: DIGITS.chars().take(base as usize).collect(),
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Conversion
+// -----------------------------------------------------------------------------
+
+/// Writes n in a base from 2 to 62, using the digits 0-9, then a-z, then A-Z. Panics for other
+/// bases. See [`Alphabet`] for other digits.
+///
+/// # Arguments
+///
+/// * `n` - Value to write
+/// * `base` - Base from 2 to 62
+///
+/// # Examples
+///
+/// ```
+/// use ctl::encoding::to_radix;
+/// let a = to_radix(255, 16); // "ff"
+/// let b = to_radix(3843, 62); // "ZZ"
+/// let c = to_radix(0, 2); // "0"
+/// ```
+pub fn to_radix(n: u64, base: u32) -> String {
+    default_alphabet(base).format(n)
+}
+
+/// Signed version of [`to_radix`], writing a leading `-` for negative values.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::encoding::to_radix_signed;
+/// let s = to_radix_signed(i64::MIN, 2); // "-1" followed by 63 zeros
+/// ```
+pub fn to_radix_signed(n: i64, base: u32) -> String {
+    default_alphabet(base).format_signed(n)
+}
+
+/// Reads a number written in a base from 2 to 62 as by [`to_radix`]. Up to base 36 the letters
+/// may be in either case, like for `u64::from_str_radix`. Panics for other bases.
+///
+/// Fails with [`Error::ParseError`] for an empty string or an invalid digit, and with
+/// [`Error::Overflow`] if the value doesn't fit into a `u64`.
+///
+/// # Arguments
+///
+/// * `s` - Digits, most significant first
+/// * `base` - Base from 2 to 62
+///
+/// # Examples
+///
+/// ```
+/// use ctl::encoding::from_radix;
+/// let a = from_radix("FF", 16); // Ok(255)
+/// let b = from_radix("ZZ", 62); // Ok(3843)
+/// let c = from_radix("12", 2); // Err(Error::ParseError)
+/// ```
+pub fn from_radix(s: &str, base: u32) -> Result<u64> {
+    let alphabet = default_alphabet(base);
+    if base > 36 {
+        return alphabet.parse(s);
+    }
+
+    alphabet.parse_with(s, |c| {
+        c.to_digit(36).map(u64::from).filter(|&d| d < base as u64)
+    })
+}
+
+/// Signed version of [`from_radix`], accepting a leading `-` or `+`.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::encoding::from_radix_signed;
+/// let x = from_radix_signed("-7f", 16); // Ok(-127)
+/// ```
+pub fn from_radix_signed(s: &str, base: u32) -> Result<i64> {
+    parse_signed_with(s, |s| from_radix(s, base))
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::random::Rng;
+
+    #[test]
+    fn test_radix() {
+        let mut rng = Rng::new(3);
+        for base in 2..=62 {
+            for _ in 0..50 {
+                let n = rng.next_u64() >> (rng.next_u64() % 64);
+                assert_eq!(from_radix(&to_radix(n, base), base), Ok(n));
+                let i = n as i64;
+                assert_eq!(from_radix_signed(&to_radix_signed(i, base), base), Ok(i));
+            }
+            if base <= 36 {
+                let n = rng.next_u64();
+                assert_eq!(Ok(n), u64::from_str_radix(&to_radix(n, base), base));
+            }
+        }
+
+        assert_eq!(to_radix(u64::MAX, 16), "ffffffffffffffff");
+        assert_eq!(to_radix(61, 62), "Z");
+        assert_eq!(from_radix("DeadBeef", 16), Ok(0xDEAD_BEEF));
+        assert_eq!(from_radix("a", 10), Err(Error::ParseError));
+        assert_eq!(from_radix("", 10), Err(Error::ParseError));
+        assert_eq!(from_radix("aZ", 36), Ok(10 * 36 + 35));
+        assert_eq!(from_radix("a-", 36), Err(Error::ParseError));
+        assert_eq!(from_radix("A", 62), Ok(36));
+        assert_eq!(from_radix("18446744073709551616", 10), Err(Error::Overflow));
+
+        assert_eq!(to_radix_signed(-255, 16), "-ff");
+        assert_eq!(from_radix_signed("+12", 10), Ok(12));
+        assert_eq!(
+            from_radix_signed(&to_radix_signed(i64::MIN, 7), 7),
+            Ok(i64::MIN)
+        );
+        assert_eq!(
+            from_radix_signed("9223372036854775808", 10),
+            Err(Error::Overflow)
+        );
+        assert_eq!(from_radix_signed("-", 10), Err(Error::ParseError));
+    }
+
+    #[test]
+    fn test_alphabet() {
+        assert_eq!(Alphabet::new("0"), None);
+        assert_eq!(Alphabet::new("aba"), None);
+
+        let dna = Alphabet::new("ACGT").unwrap();
+        assert_eq!(dna.base(), 4);
+        assert_eq!(dna.format(0), "A");
+        assert_eq!(dna.format(27), "CGT");
+        assert_eq!(dna.parse("CGT"), Ok(27));
+        assert_eq!(dna.parse("cgt"), Err(Error::ParseError));
+        assert_eq!(dna.format_signed(-27), "-CGT");
+        assert_eq!(dna.parse_signed("-CGT"), Ok(-27));
+
+        // Multi byte digits
+        let dice = Alphabet::new("⚀⚁⚂⚃⚄⚅").unwrap();
+        assert_eq!(dice.format(7), "⚁⚁");
+        assert_eq!(dice.parse("⚅⚅"), Ok(35));
+    }
+}
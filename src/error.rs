@@ -0,0 +1,39 @@
+use core::fmt;
+
+/// Errors returned by the fallible operations of the crate, e.g. the `try_` prefixed ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    /// A value was divided by zero
+    DivisionByZero,
+    /// The result does not fit into the used integer type
+    Overflow,
+    /// A matrix has no inverse
+    SingularMatrix,
+    /// The input shape is degenerate, e.g. collinear points or a zero length segment
+    DegenerateGeometry,
+    /// Text could not be parsed
+    ParseError,
+    /// An argument lies outside the domain of the operation, e.g. a logarithm of a negative value
+    Domain,
+}
+
+/// Result type used by the fallible operations of the crate.
+pub type Result<T> = core::result::Result<T, Error>;
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let msg = match self {
+            Error::DivisionByZero => "division by zero",
+            Error::Overflow => "arithmetic overflow",
+            Error::SingularMatrix => "matrix is singular",
+            Error::DegenerateGeometry => "degenerate geometry",
+            Error::ParseError => "parse error",
+            Error::Domain => "argument outside of domain",
+        };
+
+        f.write_str(msg)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
@@ -0,0 +1,431 @@
+use crate::{Error, Result};
+use alloc::vec::Vec;
+
+// -----------------------------------------------------------------------------
+// Common
+// -----------------------------------------------------------------------------
+
+/// Result of a least squares fit.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fit {
+    /// Fitted model parameters, their meaning depends on the model
+    pub params: Vec<f64>,
+    /// Standard error of every parameter. NaN if there are no more samples than parameters.
+    pub std_errors: Vec<f64>,
+    /// Sum of the squared residuals
+    pub residual_sum: f64,
+}
+
+/// Solves the dense `n` by `n` system `a x = b` with Gaussian elimination and partial pivoting.
+fn solve(mut a: Vec<f64>, mut b: Vec<f64>, n: usize) -> Result<Vec<f64>> {
+    let scale = a.iter().fold(0.0f64, |m, v| m.max(v.abs()));
+
+    for col in 0..n {
+        let pivot = (col..n)
+            .max_by(|&i, &j| {
+                a[i * n + col]
+                    .abs()
+                    .partial_cmp(&a[j * n + col].abs())
+                    .unwrap_or(core::cmp::Ordering::Equal)
+            })
+            .unwrap();
+
+        if a[pivot * n + col].abs() <= scale * 1e-14 || !a[pivot * n + col].is_finite() {
+            return Err(Error::SingularMatrix);
+        }
+
+        for k in 0..n {
+            a.swap(col * n + k, pivot * n + k);
+        }
+        b.swap(col, pivot);
+
+        for row in col + 1..n {
+            let f = a[row * n + col] / a[col * n + col];
+
+            for k in col..n {
+                a[row * n + k] -= f * a[col * n + k];
+            }
+            b[row] -= f * b[col];
+        }
+    }
+
+    for col in (0..n).rev() {
+        let s: f64 = (col + 1..n).map(|k| a[col * n + k] * b[k]).sum();
+        b[col] = (b[col] - s) / a[col * n + col];
+    }
+
+    Ok(b)
+}
+
+/// Standard errors from the normal matrix `J^T J` through the diagonal of
+/// `rss / (m - p) (J^T J)^-1`.
+fn std_errors(jtj: &[f64], p: usize, rss: f64, m: usize) -> Result<Vec<f64>> {
+    if m <= p {
+        return Ok(vec![f64::NAN; p]);
+    }
+
+    let sigma2 = rss / (m - p) as f64;
+
+    (0..p)
+        .map(|i| {
+            let mut e = vec![0.0; p];
+            e[i] = 1.0;
+            solve(jtj.to_vec(), e, p).map(|col| (sigma2 * col[i]).max(0.0).sqrt())
+        })
+        .collect()
+}
+
+fn check_len(xs: &[f64], ys: &[f64]) {
+    assert_eq!(xs.len(), ys.len(), "every sample needs an x and y value");
+}
+
+// -----------------------------------------------------------------------------
+// Linear models
+// -----------------------------------------------------------------------------
+
+/// Fits the polynomial `y = c0 + c1 x + ... + cd x^d` by linear least squares. The parameters
+/// are the coefficients in ascending order.
+///
+/// # Arguments
+///
+/// * `xs` - Sample positions
+/// * `ys` - Sample values
+/// * `degree` - Degree of the polynomial
+///
+/// # Examples
+///
+/// ```
+/// use ctl::fit::polynomial;
+/// let f = polynomial(&[0.0, 1.0, 2.0, 3.0], &[1.0, 6.0, 17.0, 34.0], 2).unwrap();
+/// // f.params ~ [1, 2, 3]
+/// ```
+pub fn polynomial(xs: &[f64], ys: &[f64], degree: usize) -> Result<Fit> {
+    check_len(xs, ys);
+
+    let p = degree + 1;
+    let mut jtj = vec![0.0; p * p];
+    let mut jty = vec![0.0; p];
+    let mut powers = vec![0.0; p];
+
+    for (&x, &y) in xs.iter().zip(ys) {
+        let mut v = 1.0;
+        for pw in powers.iter_mut() {
+            *pw = v;
+            v *= x;
+        }
+
+        for i in 0..p {
+            jty[i] += powers[i] * y;
+            for j in 0..p {
+                jtj[i * p + j] += powers[i] * powers[j];
+            }
+        }
+    }
+
+    let params = solve(jtj.clone(), jty, p)?;
+    let residual_sum = xs
+        .iter()
+        .zip(ys)
+        .map(|(&x, &y)| {
+            let v = params.iter().rev().fold(0.0, |acc, c| acc * x + c);
+            (y - v) * (y - v)
+        })
+        .sum();
+
+    Ok(Fit {
+        std_errors: std_errors(&jtj, p, residual_sum, xs.len())?,
+        params,
+        residual_sum,
+    })
+}
+
+/// Fits the line `y = a + b x`. The parameters are `[a, b]`.
+///
+/// # Arguments
+///
+/// * `xs` - Sample positions
+/// * `ys` - Sample values
+///
+/// # Examples
+///
+/// ```
+/// use ctl::fit::linear;
+/// let f = linear(&[0.0, 1.0, 2.0], &[1.0, 3.0, 5.0]).unwrap(); // f.params ~ [1, 2]
+/// ```
+pub fn linear(xs: &[f64], ys: &[f64]) -> Result<Fit> {
+    polynomial(xs, ys, 1)
+}
+
+/// Fits `y = a + b ln(x)`. The parameters are `[a, b]`. Fails with [`Error::Domain`] if an x
+/// value isn't positive.
+///
+/// # Arguments
+///
+/// * `xs` - Sample positions
+/// * `ys` - Sample values
+///
+/// # Examples
+///
+/// ```
+/// use ctl::fit::logarithmic;
+/// let xs = [1.0, 2.0, 4.0, 8.0];
+/// let ys: Vec<f64> = xs.iter().map(|x: &f64| 2.0 + 3.0 * x.ln()).collect();
+/// let f = logarithmic(&xs, &ys).unwrap(); // f.params ~ [2, 3]
+/// ```
+pub fn logarithmic(xs: &[f64], ys: &[f64]) -> Result<Fit> {
+    let lx = ln_all(xs)?;
+    linear(&lx, ys)
+}
+
+fn ln_all(v: &[f64]) -> Result<Vec<f64>> {
+    v.iter()
+        .map(|&v| {
+            if v > 0.0 {
+                Ok(v.ln())
+            } else {
+                Err(Error::Domain)
+            }
+        })
+        .collect()
+}
+
+// -----------------------------------------------------------------------------
+// Nonlinear models
+// -----------------------------------------------------------------------------
+
+/// Fits an arbitrary model with the Levenberg-Marquardt method.
+///
+/// # Arguments
+///
+/// * `xs` - Sample positions
+/// * `ys` - Sample values
+/// * `model` - Evaluates the model at `x` with the given parameters and writes the partial
+///   derivatives with respect to the parameters into the last argument
+/// * `initial` - Start parameters
+/// * `max_iterations` - Limit of refinement steps
+///
+/// # Examples
+///
+/// ```
+/// use ctl::fit::levenberg_marquardt;
+/// let xs = [0.0, 1.0, 2.0, 3.0];
+/// let ys = [0.0, 0.8415, 0.9093, 0.1411];
+/// let model = |x: f64, p: &[f64], d: &mut [f64]| {
+///     d[0] = (p[1] * x).sin();
+///     d[1] = p[0] * x * (p[1] * x).cos();
+///     p[0] * (p[1] * x).sin()
+/// };
+/// let f = levenberg_marquardt(&xs, &ys, model, &[0.8, 1.1], 100).unwrap(); // ~ [1, 1]
+/// ```
+pub fn levenberg_marquardt<F>(
+    xs: &[f64],
+    ys: &[f64],
+    model: F,
+    initial: &[f64],
+    max_iterations: usize,
+) -> Result<Fit>
+where
+    F: Fn(f64, &[f64], &mut [f64]) -> f64,
+{
+    check_len(xs, ys);
+
+    let p = initial.len();
+    let mut params = initial.to_vec();
+    let mut d = vec![0.0; p];
+
+    // Normal equations and residual sum at the given parameters
+    let normal = |params: &[f64], d: &mut [f64]| {
+        let mut jtj = vec![0.0; p * p];
+        let mut jtr = vec![0.0; p];
+        let mut rss = 0.0;
+
+        for (&x, &y) in xs.iter().zip(ys) {
+            let r = y - model(x, params, d);
+            rss += r * r;
+
+            for i in 0..p {
+                jtr[i] += d[i] * r;
+                for j in 0..p {
+                    jtj[i * p + j] += d[i] * d[j];
+                }
+            }
+        }
+
+        (jtj, jtr, rss)
+    };
+
+    let (mut jtj, mut jtr, mut rss) = normal(&params, &mut d);
+    let mut lambda = 1e-3;
+
+    for _ in 0..max_iterations {
+        let mut damped = jtj.clone();
+        for i in 0..p {
+            damped[i * p + i] += lambda * jtj[i * p + i].max(1e-12);
+        }
+
+        let step = match solve(damped, jtr.clone(), p) {
+            Ok(step) => step,
+            Err(_) => {
+                lambda *= 10.0;
+                continue;
+            }
+        };
+
+        let trial: Vec<f64> = params.iter().zip(&step).map(|(p, s)| p + s).collect();
+        let (t_jtj, t_jtr, t_rss) = normal(&trial, &mut d);
+
+        if t_rss.is_finite() && t_rss <= rss {
+            let done = rss - t_rss <= 1e-15 * rss.max(1e-300);
+
+            params = trial;
+            jtj = t_jtj;
+            jtr = t_jtr;
+            rss = t_rss;
+            lambda = (lambda / 10.0).max(1e-12);
+
+            if done {
+                break;
+            }
+        } else {
+            lambda *= 10.0;
+
+            if lambda > 1e16 {
+                break;
+            }
+        }
+    }
+
+    Ok(Fit {
+        std_errors: std_errors(&jtj, p, rss, xs.len())?,
+        params,
+        residual_sum: rss,
+    })
+}
+
+/// Fits `y = a e^(b x)`. The parameters are `[a, b]`. A line fit of `ln(y)` gives the start
+/// values, Levenberg-Marquardt then minimizes the residuals of `y` itself. Fails with
+/// [`Error::Domain`] if a y value isn't positive.
+///
+/// # Arguments
+///
+/// * `xs` - Sample positions
+/// * `ys` - Sample values
+///
+/// # Examples
+///
+/// ```
+/// use ctl::fit::exponential;
+/// let xs = [0.0, 1.0, 2.0, 3.0];
+/// let ys: Vec<f64> = xs.iter().map(|x: &f64| 2.0 * (0.5 * x).exp()).collect();
+/// let f = exponential(&xs, &ys).unwrap(); // f.params ~ [2, 0.5]
+/// ```
+pub fn exponential(xs: &[f64], ys: &[f64]) -> Result<Fit> {
+    let start = linear(xs, &ln_all(ys)?)?;
+    let initial = [start.params[0].exp(), start.params[1]];
+
+    let model = |x: f64, p: &[f64], d: &mut [f64]| {
+        let e = (p[1] * x).exp();
+        d[0] = e;
+        d[1] = p[0] * x * e;
+        p[0] * e
+    };
+
+    levenberg_marquardt(xs, ys, model, &initial, 100)
+}
+
+/// Fits `y = a x^b`. The parameters are `[a, b]`. A line fit in log-log space gives the start
+/// values, Levenberg-Marquardt then minimizes the residuals of `y` itself. Fails with
+/// [`Error::Domain`] if an x or y value isn't positive.
+///
+/// # Arguments
+///
+/// * `xs` - Sample positions
+/// * `ys` - Sample values
+///
+/// # Examples
+///
+/// ```
+/// use ctl::fit::power_law;
+/// let xs = [1.0, 2.0, 3.0, 4.0];
+/// let ys: Vec<f64> = xs.iter().map(|x: &f64| 3.0 * x.powf(1.5)).collect();
+/// let f = power_law(&xs, &ys).unwrap(); // f.params ~ [3, 1.5]
+/// ```
+pub fn power_law(xs: &[f64], ys: &[f64]) -> Result<Fit> {
+    let start = linear(&ln_all(xs)?, &ln_all(ys)?)?;
+    let initial = [start.params[0].exp(), start.params[1]];
+
+    let model = |x: f64, p: &[f64], d: &mut [f64]| {
+        let v = x.powf(p[1]);
+        d[0] = v;
+        d[1] = p[0] * v * x.ln();
+        p[0] * v
+    };
+
+    levenberg_marquardt(xs, ys, model, &initial, 100)
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn near(a: f64, b: f64, eps: f64) -> bool {
+        (a - b).abs() < eps
+    }
+
+    #[test]
+    fn test_linear_models() {
+        let xs: Vec<f64> = (0..6).map(|i| i as f64).collect();
+        let ys: Vec<f64> = xs.iter().map(|x| 1.0 - 2.0 * x + 0.5 * x * x).collect();
+
+        let f = polynomial(&xs, &ys, 2).unwrap();
+        assert!(near(f.params[0], 1.0, 1e-9));
+        assert!(near(f.params[1], -2.0, 1e-9));
+        assert!(near(f.params[2], 0.5, 1e-9));
+        assert!(f.residual_sum < 1e-18);
+
+        // y = 1 + x with alternating noise, known standard error of the slope
+        let ys = [1.1, 1.9, 3.1, 3.9];
+        let f = linear(&xs[..4], &ys).unwrap();
+        let sxx: f64 = xs[..4].iter().map(|x| (x - 1.5) * (x - 1.5)).sum();
+        let expected = (f.residual_sum / 2.0 / sxx).sqrt();
+        assert!(near(f.std_errors[1], expected, 1e-12));
+
+        assert_eq!(linear(&[1.0, 1.0], &[2.0, 3.0]), Err(Error::SingularMatrix));
+        assert_eq!(logarithmic(&[0.0, 1.0], &[2.0, 3.0]), Err(Error::Domain));
+        assert!(linear(&[0.0, 1.0], &[0.0, 1.0]).unwrap().std_errors[0].is_nan());
+    }
+
+    #[test]
+    fn test_nonlinear_models() {
+        let xs: Vec<f64> = (1..10).map(|i| i as f64 * 0.5).collect();
+        let noise = |i: usize| if i & 1 == 0 { 0.01 } else { -0.01 };
+
+        let ys: Vec<f64> = xs
+            .iter()
+            .enumerate()
+            .map(|(i, x)| 1.5 * (-0.7 * x).exp() + noise(i))
+            .collect();
+        let f = exponential(&xs, &ys).unwrap();
+        assert!(near(f.params[0], 1.5, 0.02));
+        assert!(near(f.params[1], -0.7, 0.02));
+        assert!(f.std_errors.iter().all(|&e| e > 0.0 && e < 0.05));
+
+        // Refinement can only improve on the linearized start
+        let lin = linear(&xs, &ln_all(&ys).unwrap()).unwrap();
+        let start_rss: f64 = xs
+            .iter()
+            .zip(&ys)
+            .map(|(x, y)| (y - lin.params[0].exp() * (lin.params[1] * x).exp()).powi(2))
+            .sum();
+        assert!(f.residual_sum <= start_rss);
+
+        let ys: Vec<f64> = xs.iter().map(|x| 0.3 * x.powf(2.2)).collect();
+        let f = power_law(&xs, &ys).unwrap();
+        assert!(near(f.params[0], 0.3, 1e-9));
+        assert!(near(f.params[1], 2.2, 1e-9));
+    }
+}
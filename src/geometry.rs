@@ -1,4 +1,47 @@
-use std::ops::{Add, Sub};
+use core::ops::{Add, Div, Mul, Sub};
+
+#[cfg(feature = "std")]
+mod arc;
+mod broadphase;
+mod decompose;
+mod delaunay;
+mod hull;
+mod inscribed;
+mod mesh;
+#[cfg(feature = "std")]
+mod navmesh;
+mod path;
+mod rounded;
+mod shape;
+#[cfg(feature = "std")]
+mod skeleton;
+#[cfg(feature = "std")]
+mod stroke;
+mod swept;
+#[cfg(feature = "std")]
+mod tessellate;
+
+#[cfg(feature = "std")]
+pub use arc::{Arc, Sector};
+pub use broadphase::SweepPrune;
+pub use hull::{convex_hull, par_convex_hull};
+#[cfg(feature = "std")]
+pub use inscribed::largest_empty_circle;
+pub use mesh::{HalfEdge, HalfEdgeMesh};
+pub use path::{FillRule, Path, PathCommand};
+pub use rounded::RoundedRect;
+#[cfg(feature = "std")]
+pub use rounded::Superellipse;
+pub use shape::{Circle, Polygon, Shape2D, Triangle};
+#[cfg(feature = "std")]
+pub use skeleton::StraightSkeleton;
+#[cfg(feature = "std")]
+pub use stroke::{stroke, LineCap, LineJoin};
+pub use swept::aabb_time_of_impact;
+#[cfg(feature = "std")]
+pub use swept::{circle_segment_time_of_impact, circle_time_of_impact};
+#[cfg(feature = "std")]
+pub use tessellate::Tessellate;
 
 // -----------------------------------------------------------------------------
 // Point
@@ -42,26 +85,6 @@ macro_rules! gen_point {
 
             gen_point!($fp => $T);
 		}
-
-        impl Add for Point<$T>
-        {
-            type Output = Self;
-
-            fn add(self, p: Self) -> Self
-            {
-                Self { x: self.x + p.x, y: self.y + p.y }
-            }
-        }
-
-        impl Sub for Point<$T>
-        {
-            type Output = Self;
-
-            fn sub(self, p: Self) -> Self
-            {
-                Self { x: self.x - p.x, y: self.y - p.y }
-            }
-        }
 	};
 }
 
@@ -69,11 +92,469 @@ gen_point!(i u8);
 gen_point!(i u16);
 gen_point!(i u32);
 gen_point!(i u64);
+gen_point!(i usize);
 
 gen_point!(i i8);
 gen_point!(i i16);
 gen_point!(i i32);
 gen_point!(i i64);
+gen_point!(i isize);
 
 gen_point!(f f32);
 gen_point!(f f64);
+
+impl<T: Add<Output = T>> Add for Point<T>
+{
+	type Output = Self;
+
+	fn add(self, p: Self) -> Self
+	{
+		Point {
+			x: self.x + p.x,
+			y: self.y + p.y,
+		}
+	}
+}
+
+impl<T: Sub<Output = T>> Sub for Point<T>
+{
+	type Output = Self;
+
+	fn sub(self, p: Self) -> Self
+	{
+		Point {
+			x: self.x - p.x,
+			y: self.y - p.y,
+		}
+	}
+}
+
+// -----------------------------------------------------------------------------
+// Rect
+// -----------------------------------------------------------------------------
+
+/// Axis aligned rectangle spanned by its minimum and maximum corner.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Rect<T>
+{
+	pub min: Point<T>,
+	pub max: Point<T>,
+}
+
+fn min_of<T: PartialOrd>(a: T, b: T) -> T
+{
+	if b < a {
+		b
+	} else {
+		a
+	}
+}
+
+fn max_of<T: PartialOrd>(a: T, b: T) -> T
+{
+	if b > a {
+		b
+	} else {
+		a
+	}
+}
+
+impl<T: Copy + PartialOrd> Rect<T>
+{
+	/// Creates the rectangle spanned by 2 arbitrary corners.
+	pub fn from_corners(a: Point<T>, b: Point<T>) -> Self
+	{
+		Rect {
+			min: Point {
+				x: min_of(a.x, b.x),
+				y: min_of(a.y, b.y),
+			},
+			max: Point {
+				x: max_of(a.x, b.x),
+				y: max_of(a.y, b.y),
+			},
+		}
+	}
+
+	/// Checks whether the point lies inside or on the border of the rectangle.
+	pub fn contains(&self, p: Point<T>) -> bool
+	{
+		self.min.x <= p.x && p.x <= self.max.x && self.min.y <= p.y && p.y <= self.max.y
+	}
+
+	/// Checks whether the rectangles overlap or touch.
+	pub fn intersects(&self, r: &Rect<T>) -> bool
+	{
+		self.min.x <= r.max.x
+			&& r.min.x <= self.max.x
+			&& self.min.y <= r.max.y
+			&& r.min.y <= self.max.y
+	}
+
+	/// Smallest rectangle containing both rectangles.
+	pub fn union(&self, r: &Rect<T>) -> Rect<T>
+	{
+		Rect {
+			min: Point {
+				x: min_of(self.min.x, r.min.x),
+				y: min_of(self.min.y, r.min.y),
+			},
+			max: Point {
+				x: max_of(self.max.x, r.max.x),
+				y: max_of(self.max.y, r.max.y),
+			},
+		}
+	}
+
+	/// Grows the rectangle so that it contains the point.
+	pub fn expand(&mut self, p: Point<T>)
+	{
+		*self = self.union(&Rect { min: p, max: p });
+	}
+}
+
+impl<T: Copy + Sub<Output = T>> Rect<T>
+{
+	pub fn width(&self) -> T
+	{
+		self.max.x - self.min.x
+	}
+
+	pub fn height(&self) -> T
+	{
+		self.max.y - self.min.y
+	}
+}
+
+// -----------------------------------------------------------------------------
+// Predicates
+// -----------------------------------------------------------------------------
+
+/// Turn direction of 3 points, assuming a y axis pointing up.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Orientation
+{
+	CounterClockwise,
+	Clockwise,
+	Collinear,
+}
+
+/// Determines whether `a`, `b`, `c` make a left turn, a right turn or lie on a line.
+///
+/// The test only multiplies, subtracts and compares coordinates, so it is exact for integer and
+/// [`Fraction`](crate::math::Fraction) points as long as the products don't overflow.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::geometry::{orientation, Orientation, Point};
+/// use ctl::math::frac;
+/// let p = |x, y| Point { x, y };
+/// let o = orientation(p(0, 0), p(2, 0), p(1, 1)); // Orientation::CounterClockwise
+/// let f = |x, y| Point { x: frac(x, 3), y: frac(y, 3) };
+/// let c = orientation(f(0, 0), f(1, 1), f(2, 2)); // Orientation::Collinear
+/// ```
+pub fn orientation<T>(a: Point<T>, b: Point<T>, c: Point<T>) -> Orientation
+where
+	T: Copy + Sub<Output = T> + Mul<Output = T> + PartialOrd,
+{
+	let (ab, ac) = (b - a, c - a);
+
+	// Compare both halves of the cross product instead of the difference to zero
+	let l = ab.x * ac.y;
+	let r = ab.y * ac.x;
+
+	if l > r {
+		Orientation::CounterClockwise
+	} else if l < r {
+		Orientation::Clockwise
+	} else {
+		Orientation::Collinear
+	}
+}
+
+/// Line segment between 2 points.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Segment<T>
+{
+	pub a: Point<T>,
+	pub b: Point<T>,
+}
+
+impl<T> Segment<T>
+where
+	T: Copy + Sub<Output = T> + Mul<Output = T> + PartialOrd,
+{
+	/// Checks whether the point lies on the segment, end points included.
+	pub fn contains(&self, p: Point<T>) -> bool
+	{
+		orientation(self.a, self.b, p) == Orientation::Collinear
+			&& Rect::from_corners(self.a, self.b).contains(p)
+	}
+
+	/// Checks whether the segments share at least one point.
+	pub fn intersects(&self, s: &Segment<T>) -> bool
+	{
+		let o1 = orientation(self.a, self.b, s.a);
+		let o2 = orientation(self.a, self.b, s.b);
+		let o3 = orientation(s.a, s.b, self.a);
+		let o4 = orientation(s.a, s.b, self.b);
+
+		// Proper crossing or an end point touching the inside of the other segment
+		if o1 != o2 && o3 != o4 {
+			return true;
+		}
+
+		self.contains(s.a) || self.contains(s.b) || s.contains(self.a) || s.contains(self.b)
+	}
+}
+
+impl<T> Segment<T>
+where
+	T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Div<Output = T> + PartialOrd,
+{
+	/// Calculates the crossing point of 2 segments. Returns `None` if they don't intersect or are
+	/// parallel, including overlapping collinear segments. With
+	/// [`Fraction`](crate::math::Fraction) coordinates the point is exact.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use ctl::geometry::{Point, Segment};
+	/// use ctl::math::frac;
+	/// let p = |x, y| Point { x: frac(x, 1), y: frac(y, 1) };
+	/// let s = Segment { a: p(0, 0), b: p(1, 1) };
+	/// let t = Segment { a: p(0, 1), b: p(2, 0) };
+	/// let i = s.intersection(&t); // Some((2/3, 2/3))
+	/// ```
+	pub fn intersection(&self, s: &Segment<T>) -> Option<Point<T>>
+	{
+		let (r, q) = (self.b - self.a, s.b - s.a);
+
+		if r.x * q.y == r.y * q.x || !self.intersects(s) {
+			return None;
+		}
+
+		let w = s.a - self.a;
+		let t = (w.x * q.y - w.y * q.x) / (r.x * q.y - r.y * q.x);
+
+		Some(Point {
+			x: self.a.x + r.x * t,
+			y: self.a.y + r.y * t,
+		})
+	}
+}
+
+/// Checks whether the point lies inside the polygon given by its corners in order, using the
+/// winding number so self overlapping polygons work as well. Points on the border count as
+/// inside. Exact for integer and [`Fraction`](crate::math::Fraction) coordinates.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::geometry::{point_in_polygon, Point};
+/// let p = |x, y| Point { x, y };
+/// let square = [p(0, 0), p(4, 0), p(4, 4), p(0, 4)];
+/// let a = point_in_polygon(p(2, 2), &square); // true
+/// let b = point_in_polygon(p(5, 2), &square); // false
+/// ```
+pub fn point_in_polygon<T>(p: Point<T>, polygon: &[Point<T>]) -> bool
+where
+	T: Copy + Sub<Output = T> + Mul<Output = T> + PartialOrd,
+{
+	let mut winding = 0i32;
+
+	for (i, &a) in polygon.iter().enumerate() {
+		let b = polygon[(i + 1) % polygon.len()];
+
+		if (Segment { a, b }).contains(p) {
+			return true;
+		}
+
+		if a.y <= p.y {
+			if b.y > p.y && orientation(a, b, p) == Orientation::CounterClockwise {
+				winding += 1;
+			}
+		} else if b.y <= p.y && orientation(a, b, p) == Orientation::Clockwise {
+			winding -= 1;
+		}
+	}
+
+	winding != 0
+}
+
+// -----------------------------------------------------------------------------
+// Bounding boxes
+// -----------------------------------------------------------------------------
+
+/// Shapes with an axis aligned bounding box, e.g. for spatial indexes accepting any shape.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::geometry::{Bounded, Point, Rect, Segment};
+/// let s = Segment { a: Point { x: 3, y: 0 }, b: Point { x: 1, y: 2 } };
+/// let r = s.aabb(); // Rect { min: (1, 0), max: (3, 2) }
+/// ```
+pub trait Bounded<T>
+{
+	/// Smallest axis aligned rectangle containing the shape.
+	fn aabb(&self) -> Rect<T>;
+}
+
+impl<T: Copy> Bounded<T> for Point<T>
+{
+	fn aabb(&self) -> Rect<T>
+	{
+		Rect {
+			min: *self,
+			max: *self,
+		}
+	}
+}
+
+impl<T: Copy> Bounded<T> for Rect<T>
+{
+	fn aabb(&self) -> Rect<T>
+	{
+		*self
+	}
+}
+
+impl<T: Copy + PartialOrd> Bounded<T> for Segment<T>
+{
+	fn aabb(&self) -> Rect<T>
+	{
+		Rect::from_corners(self.a, self.b)
+	}
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests
+{
+	use super::*;
+	use crate::math::{frac, Fraction};
+
+	fn p<T>(x: T, y: T) -> Point<T>
+	{
+		Point { x, y }
+	}
+
+	fn pf(x: i32, y: i32, d: i32) -> Point<Fraction>
+	{
+		p(frac(x, d), frac(y, d))
+	}
+
+	#[test]
+	fn test_orientation()
+	{
+		assert_eq!(
+			orientation(p(0, 0), p(2, 0), p(1, 1)),
+			Orientation::CounterClockwise
+		);
+		assert_eq!(
+			orientation(p(0, 0), p(2, 0), p(1, -1)),
+			Orientation::Clockwise
+		);
+		assert_eq!(
+			orientation(p(0, 0), p(2, 0), p(3, 0)),
+			Orientation::Collinear
+		);
+
+		// 0.1 + 0.2 style rounding makes the float version miss the line
+		let (a, b, c) = (pf(1, 1, 10), pf(2, 2, 10), pf(3, 3, 10));
+		assert_eq!(orientation(a, b, c), Orientation::Collinear);
+	}
+
+	#[test]
+	fn test_segments()
+	{
+		let s = Segment {
+			a: p(0, 0),
+			b: p(4, 4),
+		};
+
+		assert!(s.intersects(&Segment {
+			a: p(0, 4),
+			b: p(4, 0)
+		}));
+		assert!(s.intersects(&Segment {
+			a: p(2, 2),
+			b: p(5, 0)
+		}));
+		assert!(s.intersects(&Segment {
+			a: p(4, 4),
+			b: p(6, 6)
+		}));
+		assert!(!s.intersects(&Segment {
+			a: p(5, 5),
+			b: p(6, 6)
+		}));
+		assert!(!s.intersects(&Segment {
+			a: p(1, 0),
+			b: p(4, 3)
+		}));
+		assert!(s.contains(p(1, 1)) && !s.contains(p(5, 5)));
+
+		let s = Segment {
+			a: pf(0, 0, 1),
+			b: pf(1, 1, 1),
+		};
+		let t = Segment {
+			a: pf(0, 1, 1),
+			b: pf(2, 0, 1),
+		};
+		assert_eq!(s.intersection(&t), Some(pf(2, 2, 3)));
+		assert_eq!(
+			s.intersection(&Segment {
+				a: pf(1, 0, 1),
+				b: pf(2, 1, 1)
+			}),
+			None
+		);
+	}
+
+	#[test]
+	fn test_point_in_polygon()
+	{
+		let poly = [p(0, 0), p(4, 0), p(4, 4), p(2, 1), p(0, 4)];
+
+		assert!(point_in_polygon(p(1, 1), &poly));
+		assert!(point_in_polygon(p(2, 0), &poly));
+		assert!(!point_in_polygon(p(2, 3), &poly));
+		assert!(!point_in_polygon(p(-1, 1), &poly));
+
+		let tri = [pf(0, 0, 1), pf(1, 0, 1), pf(0, 1, 1)];
+		assert!(point_in_polygon(pf(1, 1, 2), &tri));
+		assert!(!point_in_polygon(pf(51, 50, 100), &tri));
+	}
+
+	#[test]
+	fn test_bounded()
+	{
+		let s = Segment {
+			a: p(3, 0),
+			b: p(1, 2),
+		};
+		assert_eq!(s.aabb(), Rect::from_corners(p(1, 0), p(3, 2)));
+		assert_eq!(
+			p(1, 1).aabb(),
+			Rect {
+				min: p(1, 1),
+				max: p(1, 1)
+			}
+		);
+
+		let shapes: [&dyn Bounded<i32>; 2] = [&s, &Rect::from_corners(p(5, 5), p(0, 4))];
+		let all = shapes
+			.iter()
+			.map(|b| b.aabb())
+			.reduce(|a, b| a.union(&b))
+			.unwrap();
+		assert_eq!(all, Rect::from_corners(p(0, 0), p(5, 5)));
+	}
+}
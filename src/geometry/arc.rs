@@ -0,0 +1,348 @@
+use super::{Bounded, Point, Polygon, Rect, Segment};
+use crate::units::{Angle, Quantity};
+use alloc::vec::Vec;
+use core::f64::consts::{PI, TAU};
+
+const EPS: f64 = 1e-9;
+
+// -----------------------------------------------------------------------------
+// Arc
+// -----------------------------------------------------------------------------
+
+/// Part of a circle running counter clockwise from the `start` to the `end` angle, measured from
+/// the positive x axis. `end - start` lies in [0, 2π], with 2π being the whole circle.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Arc
+{
+	pub center: Point<f64>,
+	pub radius: f64,
+	pub start: Quantity<f64, Angle>,
+	pub end: Quantity<f64, Angle>,
+}
+
+/// Circle slice bounded by an arc and the 2 radii to its end points, like a pie piece.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Sector
+{
+	pub center: Point<f64>,
+	pub radius: f64,
+	pub start: Quantity<f64, Angle>,
+	pub end: Quantity<f64, Angle>,
+}
+
+impl Arc
+{
+	/// Angle covered by the arc.
+	pub fn sweep(&self) -> Quantity<f64, Angle>
+	{
+		Quantity::new((self.end.value - self.start.value).clamp(0.0, TAU))
+	}
+
+	pub fn length(&self) -> f64
+	{
+		self.radius * self.sweep().value
+	}
+
+	/// Point on the circle at the given angle.
+	pub fn point_at(&self, angle: Quantity<f64, Angle>) -> Point<f64>
+	{
+		Point {
+			x: self.center.x + self.radius * angle.value.cos(),
+			y: self.center.y + self.radius * angle.value.sin(),
+		}
+	}
+
+	/// Checks whether the direction of the angle lies within the arc.
+	pub fn covers(&self, angle: Quantity<f64, Angle>) -> bool
+	{
+		let sweep = self.sweep().value;
+		let d = (angle.value - self.start.value).rem_euclid(TAU);
+
+		// Tolerate rounding around the end points
+		d <= sweep + EPS || d >= TAU - EPS
+	}
+
+	/// Calculates the up to 2 points where the arc crosses or touches the segment.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use ctl::geometry::{Arc, Point, Segment};
+	/// use ctl::units::Quantity;
+	/// let arc = Arc {
+	///     center: Point { x: 0.0, y: 0.0 },
+	///     radius: 1.0,
+	///     start: Quantity::new(0.0),
+	///     end: Quantity::new(std::f64::consts::PI),
+	/// };
+	/// let s = Segment { a: Point { x: -2.0, y: 0.5 }, b: Point { x: 2.0, y: 0.5 } };
+	/// let i = arc.intersect_segment(&s); // [(-0.866, 0.5), (0.866, 0.5)]
+	/// ```
+	pub fn intersect_segment(&self, s: &Segment<f64>) -> Vec<Point<f64>>
+	{
+		let d = s.b - s.a;
+		let f = s.a - self.center;
+
+		// |a + t d - c|^2 = r^2
+		let a = d.x * d.x + d.y * d.y;
+		let b = 2.0 * (f.x * d.x + f.y * d.y);
+		let c = f.x * f.x + f.y * f.y - self.radius * self.radius;
+		let disc = b * b - 4.0 * a * c;
+
+		if a == 0.0 || disc < 0.0 {
+			return Vec::new();
+		}
+
+		let root = disc.sqrt();
+		let mut ts = vec![(-b - root) / (2.0 * a)];
+		if root > 0.0 {
+			ts.push((-b + root) / (2.0 * a));
+		}
+
+		ts.into_iter()
+			.filter(|t| (0.0..=1.0).contains(t))
+			.map(|t| Point {
+				x: s.a.x + d.x * t,
+				y: s.a.y + d.y * t,
+			})
+			.filter(|p| {
+				let angle = (p.y - self.center.y).atan2(p.x - self.center.x);
+				self.covers(Quantity::new(angle))
+			})
+			.collect()
+	}
+
+	/// Approximates the arc by a polyline from its start to its end point, using as few points
+	/// as possible while keeping every chord within `tolerance` of the arc.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use ctl::geometry::{Arc, Point};
+	/// use ctl::units::Quantity;
+	/// let arc = Arc {
+	///     center: Point { x: 0.0, y: 0.0 },
+	///     radius: 10.0,
+	///     start: Quantity::new(0.0),
+	///     end: Quantity::new(std::f64::consts::FRAC_PI_2),
+	/// };
+	/// let line = arc.flatten(0.1); // 7 points
+	/// ```
+	pub fn flatten(&self, tolerance: f64) -> Vec<Point<f64>>
+	{
+		let sweep = self.sweep().value;
+
+		// A chord spanning the angle φ is r (1 - cos(φ / 2)) away from the arc at most
+		let step = if tolerance < self.radius {
+			2.0 * (1.0 - tolerance / self.radius).acos()
+		} else {
+			PI
+		};
+		let n = ((sweep / step).ceil() as usize).max(1);
+
+		(0..=n)
+			.map(|i| {
+				let angle = self.start.value + sweep * i as f64 / n as f64;
+				self.point_at(Quantity::new(angle))
+			})
+			.collect()
+	}
+}
+
+impl Bounded<f64> for Arc
+{
+	/// Bounding box of the end points and the axis extremes lying on the arc.
+	fn aabb(&self) -> Rect<f64>
+	{
+		let mut r = self.point_at(self.start).aabb();
+		r.expand(self.point_at(Quantity::new(self.start.value + self.sweep().value)));
+
+		for k in 0..4 {
+			let angle = Quantity::new(k as f64 * PI / 2.0);
+			if self.covers(angle) {
+				r.expand(self.point_at(angle));
+			}
+		}
+
+		r
+	}
+}
+
+// -----------------------------------------------------------------------------
+// Sector
+// -----------------------------------------------------------------------------
+
+impl Sector
+{
+	/// Bounding arc of the sector.
+	pub fn arc(&self) -> Arc
+	{
+		Arc {
+			center: self.center,
+			radius: self.radius,
+			start: self.start,
+			end: self.end,
+		}
+	}
+
+	pub fn area(&self) -> f64
+	{
+		self.radius * self.radius * self.arc().sweep().value / 2.0
+	}
+
+	/// Length of the border, the arc and both radii.
+	pub fn perimeter(&self) -> f64
+	{
+		self.arc().length() + 2.0 * self.radius
+	}
+
+	/// Checks whether the point lies inside or on the border of the sector.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use ctl::geometry::{Point, Sector};
+	/// use ctl::units::{Angle, Quantity};
+	/// let deg = Quantity::<f64, Angle>::from_degrees;
+	/// let s = Sector {
+	///     center: Point { x: 0.0, y: 0.0 },
+	///     radius: 2.0,
+	///     start: deg(-45.0),
+	///     end: deg(45.0),
+	/// };
+	/// let a = s.contains(Point { x: 1.0, y: 0.5 }); // true
+	/// let b = s.contains(Point { x: 0.5, y: 1.0 }); // false
+	/// ```
+	pub fn contains(&self, p: Point<f64>) -> bool
+	{
+		let d = p - self.center;
+		if d.x * d.x + d.y * d.y > self.radius * self.radius {
+			return false;
+		}
+
+		(d.x == 0.0 && d.y == 0.0) || self.arc().covers(Quantity::new(d.y.atan2(d.x)))
+	}
+
+	/// Approximates the sector by a polygon of the center and the flattened arc, see
+	/// [`Arc::flatten`]. The whole circle drops the center.
+	pub fn to_polygon(&self, tolerance: f64) -> Polygon
+	{
+		let arc = self.arc();
+		let mut points = arc.flatten(tolerance);
+
+		if arc.sweep().value >= TAU {
+			points.pop();
+		} else {
+			points.push(self.center);
+		}
+
+		Polygon { points }
+	}
+}
+
+impl Bounded<f64> for Sector
+{
+	fn aabb(&self) -> Rect<f64>
+	{
+		let mut r = self.arc().aabb();
+		r.expand(self.center);
+		r
+	}
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests
+{
+	use super::*;
+
+	fn p(x: f64, y: f64) -> Point<f64>
+	{
+		Point { x, y }
+	}
+
+	fn deg(d: f64) -> Quantity<f64, Angle>
+	{
+		Quantity::<f64, Angle>::from_degrees(d)
+	}
+
+	fn close(a: Point<f64>, b: Point<f64>) -> bool
+	{
+		(a.x - b.x).abs() < 1e-9 && (a.y - b.y).abs() < 1e-9
+	}
+
+	#[test]
+	fn test_arc()
+	{
+		let arc = Arc {
+			center: p(1.0, 1.0),
+			radius: 2.0,
+			start: deg(90.0),
+			end: deg(360.0),
+		};
+
+		assert!((arc.length() - 3.0 * PI).abs() < 1e-12);
+		assert!(arc.covers(deg(-90.0)));
+		assert!(!arc.covers(deg(45.0)));
+
+		let b = arc.aabb();
+		assert!(close(b.min, p(-1.0, -1.0)) && close(b.max, p(3.0, 3.0)));
+
+		// The vertical line through the center hits the top end point and the bottom
+		let s = Segment {
+			a: p(1.0, -5.0),
+			b: p(1.0, 5.0),
+		};
+		let hits = arc.intersect_segment(&s);
+		assert_eq!(hits.len(), 2);
+		assert!(close(hits[0], p(1.0, -1.0)) && close(hits[1], p(1.0, 3.0)));
+
+		// Crossing only the uncovered quarter
+		let s = Segment {
+			a: p(2.0, 1.5),
+			b: p(4.0, 3.0),
+		};
+		assert!(arc.intersect_segment(&s).is_empty());
+
+		let line = arc.flatten(0.01);
+		assert!(close(line[0], p(1.0, 3.0)) && close(line[line.len() - 1], p(3.0, 1.0)));
+		for w in line.windows(2) {
+			let m = p((w[0].x + w[1].x) / 2.0, (w[0].y + w[1].y) / 2.0);
+			let d = ((m.x - 1.0).powi(2) + (m.y - 1.0).powi(2)).sqrt();
+			assert!(2.0 - d <= 0.01 + 1e-12);
+		}
+	}
+
+	#[test]
+	fn test_sector()
+	{
+		let s = Sector {
+			center: p(0.0, 0.0),
+			radius: 2.0,
+			start: deg(-45.0),
+			end: deg(45.0),
+		};
+
+		assert!((s.area() - PI).abs() < 1e-12);
+		assert!((s.perimeter() - (PI + 4.0)).abs() < 1e-12);
+		assert!(s.contains(p(0.0, 0.0)) && s.contains(p(1.9, 0.0)));
+		assert!(!s.contains(p(2.1, 0.0)) && !s.contains(p(-1.0, 0.0)));
+
+		let poly = s.to_polygon(1e-4);
+		assert!((poly.area() - s.area()).abs() < 1e-3);
+		assert!(close(poly.points[poly.points.len() - 1], s.center));
+
+		let b = s.aabb();
+		assert!(close(b.min, p(0.0, -2.0f64.sqrt())) && close(b.max, p(2.0, 2.0f64.sqrt())));
+
+		let full = Sector {
+			end: Quantity::new(s.start.value + TAU),
+			..s
+		};
+		assert!(full.contains(p(-1.0, 0.0)));
+		assert!((full.to_polygon(1e-4).area() - 4.0 * PI).abs() < 1e-2);
+	}
+}
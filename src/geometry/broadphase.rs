@@ -0,0 +1,256 @@
+use super::Rect;
+use alloc::vec::Vec;
+
+// -----------------------------------------------------------------------------
+// Sweep and prune
+// -----------------------------------------------------------------------------
+
+#[derive(Debug, Copy, Clone)]
+struct Endpoint<T>
+{
+	value: T,
+	id: usize,
+	max: bool,
+}
+
+impl<T: PartialOrd> Endpoint<T>
+{
+	/// Sort order along the x axis. Minimum endpoints go first on ties so touching boxes count
+	/// as overlapping, just like [`Rect::intersects`].
+	fn before(&self, e: &Endpoint<T>) -> bool
+	{
+		self.value < e.value || self.value == e.value && !self.max && e.max
+	}
+}
+
+/// Broad phase collision detection keeping the x extents of all boxes sorted. The order is
+/// repaired with an insertion sort each query, which is close to linear when the boxes move
+/// only a little between frames.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::geometry::{Point, Rect, SweepPrune};
+/// let r = |x0, y0, x1, y1| Rect::from_corners(Point { x: x0, y: y0 }, Point { x: x1, y: y1 });
+/// let mut sp = SweepPrune::new();
+/// let a = sp.insert(r(0, 0, 2, 2));
+/// let b = sp.insert(r(1, 1, 3, 3));
+/// let c = sp.insert(r(5, 0, 6, 1));
+/// let pairs = sp.pairs(); // [(a, b)]
+///
+/// sp.update(c, r(2, 2, 3, 3));
+/// let pairs = sp.pairs(); // [(a, b), (a, c), (b, c)]
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SweepPrune<T>
+{
+	boxes: Vec<Option<Rect<T>>>,
+	free: Vec<usize>,
+	endpoints: Vec<Endpoint<T>>,
+}
+
+impl<T: Copy + PartialOrd> SweepPrune<T>
+{
+	pub fn new() -> Self
+	{
+		SweepPrune {
+			boxes: Vec::new(),
+			free: Vec::new(),
+			endpoints: Vec::new(),
+		}
+	}
+
+	/// Adds a box and returns its id. Ids of removed boxes get reused.
+	pub fn insert(&mut self, r: Rect<T>) -> usize
+	{
+		let id = match self.free.pop() {
+			Some(id) => {
+				self.boxes[id] = Some(r);
+				id
+			}
+			None => {
+				self.boxes.push(Some(r));
+				self.boxes.len() - 1
+			}
+		};
+
+		self.endpoints.push(Endpoint {
+			value: r.min.x,
+			id,
+			max: false,
+		});
+		self.endpoints.push(Endpoint {
+			value: r.max.x,
+			id,
+			max: true,
+		});
+
+		id
+	}
+
+	/// Moves the box `id`. Panics if the id isn't in use.
+	pub fn update(&mut self, id: usize, r: Rect<T>)
+	{
+		let b = self.boxes[id].as_mut().expect("unknown box id");
+		*b = r;
+	}
+
+	/// Removes the box `id` and returns it, `None` if the id isn't in use.
+	pub fn remove(&mut self, id: usize) -> Option<Rect<T>>
+	{
+		let r = self.boxes.get_mut(id)?.take()?;
+
+		self.endpoints.retain(|e| e.id != id);
+		self.free.push(id);
+
+		Some(r)
+	}
+
+	/// Current box with the id.
+	pub fn get(&self, id: usize) -> Option<&Rect<T>>
+	{
+		self.boxes.get(id)?.as_ref()
+	}
+
+	/// Number of stored boxes.
+	pub fn len(&self) -> usize
+	{
+		self.endpoints.len() / 2
+	}
+
+	pub fn is_empty(&self) -> bool
+	{
+		self.endpoints.is_empty()
+	}
+
+	/// Finds all pairs of overlapping or touching boxes. Every pair `(a, b)` has `a < b` and the
+	/// pairs are sorted.
+	pub fn pairs(&mut self) -> Vec<(usize, usize)>
+	{
+		let boxes = &self.boxes;
+
+		for e in &mut self.endpoints {
+			let r = boxes[e.id].as_ref().unwrap();
+			e.value = if e.max { r.max.x } else { r.min.x };
+		}
+
+		// Insertion sort, cheap for the nearly sorted endpoints of coherent motion
+		for i in 1..self.endpoints.len() {
+			let mut j = i;
+
+			while j > 0 && self.endpoints[j].before(&self.endpoints[j - 1]) {
+				self.endpoints.swap(j, j - 1);
+				j -= 1;
+			}
+		}
+
+		let mut active: Vec<usize> = Vec::new();
+		let mut pairs = Vec::new();
+
+		for e in &self.endpoints {
+			if e.max {
+				let i = active.iter().position(|&a| a == e.id).unwrap();
+				active.swap_remove(i);
+				continue;
+			}
+
+			let r = boxes[e.id].as_ref().unwrap();
+
+			for &a in &active {
+				let o = boxes[a].as_ref().unwrap();
+
+				if r.min.y <= o.max.y && o.min.y <= r.max.y {
+					pairs.push((a.min(e.id), a.max(e.id)));
+				}
+			}
+
+			active.push(e.id);
+		}
+
+		pairs.sort_unstable();
+		pairs
+	}
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests
+{
+	use super::*;
+	use crate::geometry::Point;
+	use crate::random::Rng;
+
+	fn rect(x: i32, y: i32, w: i32, h: i32) -> Rect<i32>
+	{
+		Rect {
+			min: Point { x, y },
+			max: Point { x: x + w, y: y + h },
+		}
+	}
+
+	fn brute_force(sp: &SweepPrune<i32>, ids: &[usize]) -> Vec<(usize, usize)>
+	{
+		let mut pairs = Vec::new();
+
+		for (i, &a) in ids.iter().enumerate() {
+			for &b in &ids[i + 1..] {
+				if sp.get(a).unwrap().intersects(sp.get(b).unwrap()) {
+					pairs.push((a.min(b), a.max(b)));
+				}
+			}
+		}
+
+		pairs.sort_unstable();
+		pairs
+	}
+
+	#[test]
+	fn test_sweep_prune()
+	{
+		let mut rng = Rng::new(9);
+		let mut sp = SweepPrune::new();
+		let mut ids: Vec<usize> = (0..40)
+			.map(|_| {
+				sp.insert(rect(
+					rng.range(0..100) as i32,
+					rng.range(0..100) as i32,
+					8,
+					8,
+				))
+			})
+			.collect();
+
+		for frame in 0..20 {
+			for &id in &ids {
+				let r = *sp.get(id).unwrap();
+				let (dx, dy) = (rng.range(-3..4) as i32, rng.range(-3..4) as i32);
+				sp.update(id, rect(r.min.x + dx, r.min.y + dy, 8, 8));
+			}
+
+			if frame % 5 == 4 {
+				let id = ids.swap_remove(0);
+				assert!(sp.remove(id).is_some());
+				assert!(sp.remove(id).is_none());
+				ids.push(sp.insert(rect(50, 50, 20, 20)));
+			}
+
+			assert_eq!(sp.pairs(), brute_force(&sp, &ids));
+		}
+
+		assert_eq!(sp.len(), 40);
+	}
+
+	#[test]
+	fn test_touching()
+	{
+		let mut sp = SweepPrune::new();
+		let a = sp.insert(rect(0, 0, 2, 2));
+		let b = sp.insert(rect(2, 2, 2, 2));
+		sp.insert(rect(5, 0, 1, 1));
+
+		assert_eq!(sp.pairs(), vec![(a, b)]);
+	}
+}
@@ -0,0 +1,386 @@
+use super::{orientation, point_in_polygon, Orientation, Point, Polygon, Segment, Triangle};
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+// -----------------------------------------------------------------------------
+// Decomposition
+// -----------------------------------------------------------------------------
+
+/// Corner indices of the polygon in counter clockwise order.
+fn ccw_order(points: &[Point<f64>]) -> Vec<usize>
+{
+	let twice_area: f64 = (0..points.len())
+		.map(|i| {
+			let (a, b) = (points[i], points[(i + 1) % points.len()]);
+			a.x * b.y - b.x * a.y
+		})
+		.sum();
+
+	if twice_area < 0.0 {
+		(0..points.len()).rev().collect()
+	} else {
+		(0..points.len()).collect()
+	}
+}
+
+/// Clips ears off the polygon, returning the triangles as counter clockwise corner indices.
+pub(super) fn ear_clip(points: &[Point<f64>]) -> Vec<[usize; 3]>
+{
+	let mut ring = ccw_order(points);
+	let mut tris = Vec::with_capacity(points.len().saturating_sub(2));
+	let mut i = 0;
+	let mut misses = 0;
+
+	let corner = |ring: &[usize], i: usize| {
+		let n = ring.len();
+		(ring[(i + n - 1) % n], ring[i], ring[(i + 1) % n])
+	};
+
+	while ring.len() > 3 {
+		if misses == ring.len() {
+			// Only straight corners are left to cut, drop one without a triangle
+			match (0..ring.len()).find(|&j| {
+				let (a, b, c) = corner(&ring, j);
+				orientation(points[a], points[b], points[c]) == Orientation::Collinear
+			}) {
+				Some(j) => {
+					ring.remove(j);
+					i = 0;
+					misses = 0;
+					continue;
+				}
+				None => break,
+			}
+		}
+
+		let (a, b, c) = corner(&ring, i);
+		let t = Triangle {
+			a: points[a],
+			b: points[b],
+			c: points[c],
+		};
+
+		// Straight corners are kept as long as possible so they become triangle corners
+		let ear = orientation(t.a, t.b, t.c) == Orientation::CounterClockwise
+			&& !ring.iter().any(|&j| {
+				let p = points[j];
+				p != t.a && p != t.b && p != t.c && t.contains(p)
+			});
+
+		if ear {
+			tris.push([a, b, c]);
+			ring.remove(i);
+			misses = 0;
+		} else {
+			i += 1;
+			misses += 1;
+		}
+
+		i %= ring.len();
+	}
+
+	if ring.len() == 3
+		&& orientation(points[ring[0]], points[ring[1]], points[ring[2]])
+			== Orientation::CounterClockwise
+	{
+		tris.push([ring[0], ring[1], ring[2]]);
+	}
+
+	tris
+}
+
+/// Joins the holes into the outline, each by a bridge from its rightmost corner to the closest
+/// corner it can see, walked there and back. Returns the corners of the outline followed by those
+/// of the holes, and the counter clockwise ring of indices into them, which visits the ends of
+/// every bridge twice.
+pub(super) fn bridge_holes(
+	outline: &[Point<f64>],
+	holes: &[Polygon],
+) -> (Vec<Point<f64>>, Vec<usize>)
+{
+	let mut points = outline.to_vec();
+	let mut rings = Vec::with_capacity(holes.len());
+	for h in holes {
+		let first = points.len();
+		points.extend_from_slice(&h.points);
+
+		// Clockwise, so the area around the hole stays on the left
+		rings.push(
+			ccw_order(&h.points)
+				.into_iter()
+				.rev()
+				.map(|i| first + i)
+				.collect::<Vec<_>>(),
+		);
+	}
+
+	let right = |ring: &[usize]| {
+		(0..ring.len())
+			.max_by(|&a, &b| {
+				let (p, q) = (points[ring[a]], points[ring[b]]);
+				p.x.partial_cmp(&q.x).unwrap_or(Ordering::Equal)
+			})
+			.unwrap_or(0)
+	};
+	rings.sort_by(|a, b| {
+		let (p, q) = (points[b[right(b)]], points[a[right(a)]]);
+		p.x.partial_cmp(&q.x).unwrap_or(Ordering::Equal)
+	});
+
+	let mut ring = ccw_order(outline);
+	for (k, hole) in rings.iter().enumerate() {
+		if hole.is_empty() {
+			continue;
+		}
+
+		let start = right(hole);
+		let m = points[hole[start]];
+
+		// The bridge may not touch another edge or run through a hole
+		let visible = |v: Point<f64>| {
+			let bridge = Segment { a: m, b: v };
+			let mid = Point {
+				x: (m.x + v.x) / 2.0,
+				y: (m.y + v.y) / 2.0,
+			};
+			let blocked = core::iter::once(&ring).chain(&rings[k..]).any(|r| {
+				(0..r.len()).any(|i| {
+					let (a, b) = (points[r[i]], points[r[(i + 1) % r.len()]]);
+					![a, b].contains(&m)
+						&& ![a, b].contains(&v)
+						&& bridge.intersects(&Segment { a, b })
+				})
+			});
+			!blocked
+				&& rings.iter().all(|r| {
+					!point_in_polygon(mid, &r.iter().map(|&i| points[i]).collect::<Vec<_>>())
+				})
+		};
+
+		let dist = |v: Point<f64>| (v.x - m.x) * (v.x - m.x) + (v.y - m.y) * (v.y - m.y);
+		let j = (0..ring.len())
+			.filter(|&j| points[ring[j]] != m && visible(points[ring[j]]))
+			.min_by(|&a, &b| {
+				dist(points[ring[a]])
+					.partial_cmp(&dist(points[ring[b]]))
+					.unwrap_or(Ordering::Equal)
+			});
+		let j = match j {
+			Some(j) => j,
+			None => continue,
+		};
+
+		let around = hole[start..]
+			.iter()
+			.chain(&hole[..start])
+			.chain(&hole[start..=start]);
+		let inserted: Vec<usize> = around.copied().chain(core::iter::once(ring[j])).collect();
+		ring.splice(j + 1..j + 1, inserted);
+	}
+
+	(points, ring)
+}
+
+/// Checks whether the corner `i` of a counter clockwise ring is not reflex.
+fn convex_at(points: &[Point<f64>], ring: &[usize], i: usize) -> bool
+{
+	let n = ring.len();
+	orientation(
+		points[ring[(i + n - 1) % n]],
+		points[ring[i]],
+		points[ring[(i + 1) % n]],
+	) != Orientation::Clockwise
+}
+
+/// Joins the rings `p` and `q` sharing the edge u -> v in `p` and v -> u in `q`.
+fn merge(p: &[usize], q: &[usize], u: usize, v: usize) -> Vec<usize>
+{
+	let pv = p.iter().position(|&x| x == v).unwrap();
+	let qu = q.iter().position(|&x| x == u).unwrap();
+
+	// p from v around to u, then q strictly between u and v
+	let mut out: Vec<usize> = p[pv..].iter().chain(&p[..pv]).copied().collect();
+	out.extend(q[qu..].iter().chain(&q[..qu]).skip(1).take(q.len() - 2));
+	out
+}
+
+/// Position of `a` in the ring if it is directly followed by `b`.
+fn find_edge(ring: &[usize], a: usize, b: usize) -> Option<usize>
+{
+	(0..ring.len()).find(|&i| ring[i] == a && ring[(i + 1) % ring.len()] == b)
+}
+
+impl Polygon
+{
+	/// Checks whether no corner of the polygon is reflex.
+	pub fn is_convex(&self) -> bool
+	{
+		let ring = ccw_order(&self.points);
+		(0..ring.len()).all(|i| convex_at(&self.points, &ring, i))
+	}
+
+	/// Splits the simple polygon into triangles by ear clipping in O(n^3). Corners on a straight
+	/// line become triangle corners where possible and are dropped otherwise.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use ctl::geometry::{Point, Polygon};
+	/// let p = |x, y| Point { x, y };
+	/// let square = Polygon { points: vec![p(0.0, 0.0), p(1.0, 0.0), p(1.0, 1.0), p(0.0, 1.0)] };
+	/// let tris = square.triangulate(); // 2 triangles
+	/// ```
+	pub fn triangulate(&self) -> Vec<Triangle>
+	{
+		ear_clip(&self.points)
+			.into_iter()
+			.map(|[a, b, c]| Triangle {
+				a: self.points[a],
+				b: self.points[b],
+				c: self.points[c],
+			})
+			.collect()
+	}
+
+	/// Splits the simple polygon into convex polygons with the Hertel–Mehlhorn algorithm. The
+	/// polygon is triangulated and every diagonal whose removal leaves both of its ends convex is
+	/// dropped, which gives at most 4 times the minimal number of parts. The parts are counter
+	/// clockwise.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use ctl::geometry::{Point, Polygon};
+	/// let p = |x, y| Point { x, y };
+	/// let l = Polygon {
+	///     points: vec![p(0.0, 0.0), p(2.0, 0.0), p(2.0, 1.0), p(1.0, 1.0), p(1.0, 2.0), p(0.0, 2.0)],
+	/// };
+	/// let parts = l.convex_parts(); // 2 parts
+	/// ```
+	pub fn convex_parts(&self) -> Vec<Polygon>
+	{
+		let pts = &self.points;
+		let mut parts: Vec<Vec<usize>> = ear_clip(pts).iter().map(|t| t.to_vec()).collect();
+
+		// Every inner triangle edge is a diagonal
+		let diagonals: Vec<(usize, usize)> = parts
+			.iter()
+			.flat_map(|t| (0..3).map(move |i| (t[i], t[(i + 1) % 3])))
+			.filter(|&(a, b)| a < b)
+			.filter(|&(a, b)| parts.iter().any(|t| find_edge(t, b, a).is_some()))
+			.collect();
+
+		// Parts only grow, so a diagonal kept once stays essential
+		for (u, v) in diagonals {
+			let pi = parts.iter().position(|r| find_edge(r, u, v).is_some());
+			let qi = parts.iter().position(|r| find_edge(r, v, u).is_some());
+
+			let (pi, qi) = match (pi, qi) {
+				(Some(pi), Some(qi)) if pi != qi => (pi, qi),
+				_ => continue,
+			};
+
+			let joined = merge(&parts[pi], &parts[qi], u, v);
+			let corner = |x| joined.iter().position(|&j| j == x).unwrap();
+
+			if convex_at(pts, &joined, corner(u)) && convex_at(pts, &joined, corner(v)) {
+				parts[pi] = joined;
+				parts.swap_remove(qi);
+			}
+		}
+
+		parts
+			.into_iter()
+			.map(|r| Polygon {
+				points: r.into_iter().map(|i| pts[i]).collect(),
+			})
+			.collect()
+	}
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests
+{
+	use super::*;
+
+	fn p(x: f64, y: f64) -> Point<f64>
+	{
+		Point { x, y }
+	}
+
+	fn comb() -> Polygon
+	{
+		// Clockwise comb with 3 teeth
+		let points = [
+			(0, 0),
+			(0, 3),
+			(1, 3),
+			(1, 1),
+			(2, 1),
+			(2, 3),
+			(3, 3),
+			(3, 1),
+			(4, 1),
+			(4, 3),
+			(5, 3),
+			(5, 0),
+		]
+		.iter()
+		.map(|&(x, y)| p(x as f64, y as f64))
+		.collect();
+		Polygon { points }
+	}
+
+	#[test]
+	fn test_triangulate()
+	{
+		let poly = comb();
+		let tris = poly.triangulate();
+
+		// Clipping can leave straight corners behind, which need no triangle
+		assert!(tris.len() <= poly.points.len() - 2);
+		assert!(tris.iter().all(|t| t.area() > 0.0));
+		let sum: f64 = tris.iter().map(|t| t.area()).sum();
+		assert!((sum - poly.area()).abs() < 1e-12);
+		assert!(!poly.is_convex());
+
+		// A straight corner still counts as convex
+		let square = Polygon {
+			points: vec![
+				p(0.0, 0.0),
+				p(1.0, 0.0),
+				p(2.0, 0.0),
+				p(2.0, 2.0),
+				p(0.0, 2.0),
+			],
+		};
+		assert!(square.is_convex());
+		let sum: f64 = square.triangulate().iter().map(|t| t.area()).sum();
+		assert_eq!(sum, 4.0);
+	}
+
+	#[test]
+	fn test_convex_parts()
+	{
+		let poly = comb();
+		let parts = poly.convex_parts();
+
+		// The base and 3 teeth
+		assert!(parts.len() >= 4 && parts.len() <= 7);
+		assert!(parts.iter().all(|c| c.is_convex()));
+		let sum: f64 = parts.iter().map(|c| c.area()).sum();
+		assert!((sum - poly.area()).abs() < 1e-12);
+
+		let hexagon: Vec<_> = (0..6)
+			.map(|i| {
+				let a = i as f64 * core::f64::consts::PI / 3.0;
+				p(a.cos(), a.sin())
+			})
+			.collect();
+		assert_eq!(Polygon { points: hexagon }.convex_parts().len(), 1);
+	}
+}
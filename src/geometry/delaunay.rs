@@ -0,0 +1,511 @@
+use super::{orientation, point_in_polygon, HalfEdgeMesh, Orientation, Point, Polygon, Segment};
+use alloc::collections::{BTreeSet, VecDeque};
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+// -----------------------------------------------------------------------------
+// Delaunay triangulation
+// -----------------------------------------------------------------------------
+
+/// Checks whether d lies inside the circumcircle of the counter clockwise triangle a, b, c, with
+/// a tolerance relative to the terms so cocircular points don't flip back and forth.
+fn in_circle(a: Point<f64>, b: Point<f64>, c: Point<f64>, d: Point<f64>) -> bool
+{
+	let (a, b, c) = (a - d, b - d, c - d);
+	let t = [
+		(a.x * a.x + a.y * a.y) * (b.x * c.y - c.x * b.y),
+		(b.x * b.x + b.y * b.y) * (c.x * a.y - a.x * c.y),
+		(c.x * c.x + c.y * c.y) * (a.x * b.y - b.x * a.y),
+	];
+	let scale: f64 = t.iter().map(|x| x.abs()).sum();
+	t.iter().sum::<f64>() > 1e-12 * scale
+}
+
+/// Checks whether the segments p q and r s cross at a point inside both.
+fn crosses(p: Point<f64>, q: Point<f64>, r: Point<f64>, s: Point<f64>) -> bool
+{
+	let opposite = |a, b| a != Orientation::Collinear && b != Orientation::Collinear && a != b;
+	opposite(orientation(p, q, r), orientation(p, q, s))
+		&& opposite(orientation(r, s, p), orientation(r, s, q))
+}
+
+/// Triangulates the points by sweeping them in x order and connecting each to the edges of the
+/// convex hull it sees. Repeated points are left out.
+fn sweep(points: &[Point<f64>]) -> Vec<[usize; 3]>
+{
+	let mut order: Vec<usize> = (0..points.len()).collect();
+	order.sort_by(|&a, &b| {
+		let (p, q) = (points[a], points[b]);
+		(p.x, p.y)
+			.partial_cmp(&(q.x, q.y))
+			.unwrap_or(Ordering::Equal)
+	});
+	order.dedup_by(|a, b| points[*a] == points[*b]);
+
+	// Fan the leading collinear points to the first one off their line
+	let k = match (2..order.len()).find(|&i| {
+		orientation(points[order[0]], points[order[1]], points[order[i]]) != Orientation::Collinear
+	}) {
+		Some(k) => k,
+		None => return Vec::new(),
+	};
+
+	let mut tris: Vec<[usize; 3]> = (0..k - 1)
+		.map(|i| [order[i], order[i + 1], order[k]])
+		.collect();
+	let mut hull: Vec<usize> = order[..k].to_vec();
+	if orientation(points[order[0]], points[order[1]], points[order[k]]) == Orientation::Clockwise {
+		hull.reverse();
+	}
+	hull.push(order[k]);
+
+	for &p in &order[k + 1..] {
+		let m = hull.len();
+		let visible: Vec<bool> = (0..m)
+			.map(|i| {
+				orientation(points[hull[i]], points[hull[(i + 1) % m]], points[p])
+					== Orientation::Clockwise
+			})
+			.collect();
+
+		// The visible edges form one chain, rotate it to the front
+		let start = (0..m)
+			.find(|&i| visible[i] && !visible[(i + m - 1) % m])
+			.unwrap();
+		let n = (0..m).take_while(|&i| visible[(start + i) % m]).count();
+		hull.rotate_left(start);
+
+		tris.extend((0..n).map(|i| [hull[i + 1], hull[i], p]));
+		hull.splice(1..n, core::iter::once(p));
+	}
+
+	tris
+}
+
+/// Splits the segment at the vertices lying on it.
+fn split_at_vertices(points: &[Point<f64>], [u, v]: [usize; 2]) -> Vec<usize>
+{
+	let s = Segment {
+		a: points[u],
+		b: points[v],
+	};
+	let dist = |w: usize| {
+		let d = points[w] - points[u];
+		d.x * d.x + d.y * d.y
+	};
+
+	let mut chain: Vec<usize> = (0..points.len())
+		.filter(|&w| {
+			w != u && w != v && points[w] != s.a && points[w] != s.b && s.contains(points[w])
+		})
+		.collect();
+	chain.sort_by(|&a, &b| dist(a).partial_cmp(&dist(b)).unwrap_or(Ordering::Equal));
+	chain.insert(0, u);
+	chain.push(v);
+	chain
+}
+
+impl HalfEdgeMesh
+{
+	fn edge_count(&self) -> usize
+	{
+		3 * self.face_count()
+	}
+
+	/// Corners a, b, c of the face of the edge a -> b and the corner d across it.
+	fn quad(&self, e: usize) -> Option<[usize; 4]>
+	{
+		let t = self.edge(e).twin?;
+		let opposite = |e: usize| self.edge(self.edge(self.edge(e).next).next).origin;
+		Some([
+			self.edge(e).origin,
+			self.target(e),
+			opposite(e),
+			opposite(t),
+		])
+	}
+
+	/// Flips edges whose quad's opposite corner lies inside the circumcircle until none are left,
+	/// which ends in the Delaunay triangulation (Lawson's algorithm). Edges between the pairs of
+	/// vertices in `fixed` stay.
+	fn make_delaunay(&mut self, fixed: &BTreeSet<(usize, usize)>)
+	{
+		let mut stack: Vec<usize> = (0..self.edge_count()).collect();
+
+		while let Some(e) = stack.pop() {
+			let [a, b, c, d] = match self.quad(e) {
+				Some(q) => q,
+				None => continue,
+			};
+			let v = &self.vertices;
+			if fixed.contains(&(a.min(b), a.max(b))) || !in_circle(v[a], v[b], v[c], v[d]) {
+				continue;
+			}
+
+			if self.flip(e) {
+				let t = self.edge(e).twin.unwrap();
+				for h in [e, t] {
+					let n = self.edge(h).next;
+					stack.push(n);
+					stack.push(self.edge(n).next);
+				}
+			}
+		}
+	}
+
+	/// Delaunay triangulation of the points: no point lies inside the circumcircle of a triangle,
+	/// which maximizes the smallest angle. The points are triangulated by a sweep, then edges are
+	/// flipped into place. Repeated points stay unconnected, as do all points on a single line.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use ctl::geometry::{HalfEdgeMesh, Point};
+	/// let p = |x, y| Point { x, y };
+	/// let pts = vec![p(0.0, 0.0), p(4.0, 0.0), p(4.0, 1.0), p(0.0, 1.0), p(2.0, 0.4)];
+	/// let mesh = HalfEdgeMesh::delaunay(pts); // 4 triangles around the inner point
+	/// ```
+	pub fn delaunay(vertices: Vec<Point<f64>>) -> Self
+	{
+		Self::constrained_delaunay(vertices, &[])
+	}
+
+	/// Constrained Delaunay triangulation: the triangulation of the points that contains the
+	/// segments given as vertex index pairs and is Delaunay apart from them. Every segment is
+	/// inserted into the Delaunay triangulation by flipping the edges crossing it (Sloan's
+	/// algorithm), then the other edges are flipped back into place. Segments through points are
+	/// split at them. Panics for segments crossing each other.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use ctl::geometry::{HalfEdgeMesh, Point};
+	/// let p = |x, y| Point { x, y };
+	/// let pts = vec![p(0.0, 0.0), p(2.0, -1.0), p(4.0, 0.0), p(2.0, 1.0)];
+	/// let mesh = HalfEdgeMesh::constrained_delaunay(pts, &[[0, 2]]);
+	/// let e = mesh.find_edge(0, 2); // Some, where Delaunay alone connects 1 and 3
+	/// ```
+	pub fn constrained_delaunay(vertices: Vec<Point<f64>>, segments: &[[usize; 2]]) -> Self
+	{
+		for (i, &[a, b]) in segments.iter().enumerate() {
+			for &[c, d] in &segments[i + 1..] {
+				let v = &vertices;
+				assert!(
+					!crosses(v[a], v[b], v[c], v[d]),
+					"constraint segments cross"
+				);
+			}
+		}
+
+		let tris = sweep(&vertices);
+		let mut mesh = Self::from_triangles(vertices, &tris);
+		mesh.make_delaunay(&BTreeSet::new());
+
+		let mut fixed = BTreeSet::new();
+		for &segment in segments {
+			let chain = split_at_vertices(&mesh.vertices, segment);
+			for w in chain.windows(2) {
+				mesh.insert_segment(w[0], w[1]);
+				fixed.insert((w[0].min(w[1]), w[0].max(w[1])));
+			}
+		}
+
+		mesh.make_delaunay(&fixed);
+		mesh
+	}
+
+	/// Flips the edges crossing u v until it is an edge of the mesh.
+	fn insert_segment(&mut self, u: usize, v: usize)
+	{
+		let (pu, pv) = (self.vertices[u], self.vertices[v]);
+		let crossing = |mesh: &Self, e: usize| {
+			crosses(
+				pu,
+				pv,
+				mesh.vertices[mesh.edge(e).origin],
+				mesh.vertices[mesh.target(e)],
+			)
+		};
+
+		// A flip renumbers the edges around it, so the queue holds vertex pairs
+		let mut queue: VecDeque<(usize, usize)> = (0..self.edge_count())
+			.filter(|&e| matches!(self.edge(e).twin, Some(t) if e < t) && crossing(self, e))
+			.map(|e| (self.edge(e).origin, self.target(e)))
+			.collect();
+
+		// Flips of non convex quads are retried once their neighbours changed
+		while let Some((a, b)) = queue.pop_front() {
+			let e = self.find_edge(a, b).unwrap();
+			if !self.flip(e) {
+				queue.push_back((a, b));
+			} else if crossing(self, e) {
+				queue.push_back((self.edge(e).origin, self.target(e)));
+			}
+		}
+	}
+
+	/// Constrained Delaunay triangulation of the area inside the outline and outside the holes,
+	/// e.g. the walkable area of a navmesh with obstacles cut out. The polygons may have any
+	/// winding but must not touch each other.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use ctl::geometry::{HalfEdgeMesh, Point, Polygon};
+	/// let p = |x, y| Point { x, y };
+	/// let square = |x: f64, y: f64, s: f64| Polygon {
+	///     points: vec![p(x, y), p(x + s, y), p(x + s, y + s), p(x, y + s)],
+	/// };
+	/// let mesh = HalfEdgeMesh::from_polygon_delaunay(&square(0.0, 0.0, 3.0), &[square(1.0, 1.0, 1.0)]);
+	/// let outline = mesh.boundary(); // the outer square and the hole
+	/// ```
+	pub fn from_polygon_delaunay(outline: &Polygon, holes: &[Polygon]) -> Self
+	{
+		let mut vertices = Vec::new();
+		let mut segments = Vec::new();
+		for poly in core::iter::once(outline).chain(holes) {
+			let (first, n) = (vertices.len(), poly.points.len());
+			vertices.extend_from_slice(&poly.points);
+			segments.extend((0..n).map(|i| [first + i, first + (i + 1) % n]));
+		}
+
+		let mesh = Self::constrained_delaunay(vertices, &segments);
+
+		let inside = |f: usize| {
+			let [a, b, c] = mesh.face_vertices(f).map(|i| mesh.vertices[i]);
+			let center = Point {
+				x: (a.x + b.x + c.x) / 3.0,
+				y: (a.y + b.y + c.y) / 3.0,
+			};
+			point_in_polygon(center, &outline.points)
+				&& !holes.iter().any(|h| point_in_polygon(center, &h.points))
+		};
+		let tris: Vec<[usize; 3]> = (0..mesh.face_count())
+			.filter(|&f| inside(f))
+			.map(|f| mesh.face_vertices(f))
+			.collect();
+
+		Self::from_triangles(mesh.vertices, &tris)
+	}
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests
+{
+	use super::*;
+
+	fn p(x: f64, y: f64) -> Point<f64>
+	{
+		Point { x, y }
+	}
+
+	/// Checks that no vertex lies inside the circumcircle across an unconstrained edge.
+	fn is_delaunay(mesh: &HalfEdgeMesh, fixed: &[[usize; 2]]) -> bool
+	{
+		(0..mesh.edge_count()).all(|e| match mesh.quad(e) {
+			Some([a, b, c, d]) => {
+				let v = &mesh.vertices;
+				fixed
+					.iter()
+					.any(|&[u, w]| (u, w) == (a, b) || (u, w) == (b, a))
+					|| !in_circle(v[a], v[b], v[c], v[d])
+			}
+			None => true,
+		})
+	}
+
+	fn random(seed: u64) -> impl FnMut() -> f64
+	{
+		let mut seed = seed;
+		move || {
+			seed = seed
+				.wrapping_mul(6364136223846793005)
+				.wrapping_add(1442695040888963407);
+			(seed >> 11) as f64 / (1u64 << 53) as f64
+		}
+	}
+
+	fn area(points: &[Point<f64>]) -> f64
+	{
+		let n = points.len();
+		(0..n)
+			.map(|i| {
+				let (a, b) = (points[i], points[(i + 1) % n]);
+				a.x * b.y - a.y * b.x
+			})
+			.sum::<f64>()
+			/ 2.0
+	}
+
+	fn mesh_area(mesh: &HalfEdgeMesh) -> f64
+	{
+		(0..mesh.face_count())
+			.map(|f| area(&mesh.face_vertices(f).map(|i| mesh.vertices[i])))
+			.sum()
+	}
+
+	#[test]
+	fn test_delaunay_random()
+	{
+		// The triangles cover the hull once and no point lies inside any circumcircle
+		let mut next = random(11);
+		for n in 3..60 {
+			let pts: Vec<Point<f64>> = (0..n).map(|_| p(next() * 10.0, next() * 10.0)).collect();
+			let mesh = HalfEdgeMesh::delaunay(pts.clone());
+
+			let hull = crate::geometry::convex_hull(&pts);
+			assert!((mesh_area(&mesh) - area(&hull)).abs() < 1e-9, "{:?}", pts);
+			for f in 0..mesh.face_count() {
+				let [a, b, c] = mesh.face_vertices(f);
+				assert!((0..n)
+					.filter(|&d| ![a, b, c].contains(&d))
+					.all(|d| !in_circle(pts[a], pts[b], pts[c], pts[d])));
+			}
+		}
+
+		let kite = vec![p(1.0, 0.0), p(0.0, 3.0), p(3.0, 0.0), p(3.0, 3.0)];
+		let mesh = HalfEdgeMesh::delaunay(kite);
+		assert_eq!(mesh.face_count(), 2);
+		assert!((mesh_area(&mesh) - 7.5).abs() < 1e-12);
+
+		// Random segments that don't cross each other end up as edges
+		for n in 4..40 {
+			let pts: Vec<Point<f64>> = (0..n).map(|_| p(next() * 10.0, next() * 10.0)).collect();
+			let mut segments: Vec<[usize; 2]> = Vec::new();
+			for _ in 0..n {
+				let (a, b) = ((next() * n as f64) as usize, (next() * n as f64) as usize);
+				let free = segments
+					.iter()
+					.all(|&[c, d]| !crosses(pts[a], pts[b], pts[c], pts[d]));
+				if a != b && free {
+					segments.push([a, b]);
+				}
+			}
+
+			let mesh = HalfEdgeMesh::constrained_delaunay(pts.clone(), &segments);
+			let hull = crate::geometry::convex_hull(&pts);
+			assert!((mesh_area(&mesh) - area(&hull)).abs() < 1e-9);
+			assert!(segments
+				.iter()
+				.all(|&[a, b]| mesh.find_edge(a, b).is_some() || mesh.find_edge(b, a).is_some()));
+			assert!(is_delaunay(&mesh, &segments));
+		}
+	}
+
+	#[test]
+	fn test_delaunay()
+	{
+		// Pseudo random points, a grid with cocircular quads and collinear starts
+		let mut seed = 7u64;
+		let mut next = || {
+			seed = seed
+				.wrapping_mul(6364136223846793005)
+				.wrapping_add(1442695040888963407);
+			(seed >> 11) as f64 / (1u64 << 53) as f64
+		};
+		let random: Vec<Point<f64>> = (0..200).map(|_| p(next() * 10.0, next() * 10.0)).collect();
+		let grid: Vec<Point<f64>> = (0..36).map(|i| p((i % 6) as f64, (i / 6) as f64)).collect();
+
+		for pts in [random, grid] {
+			let n = pts.len();
+			let mesh = HalfEdgeMesh::delaunay(pts);
+			assert!(is_delaunay(&mesh, &[]));
+
+			// Euler's formula for a triangulated convex hull of n points with h on the hull
+			let h = mesh.boundary()[0].len();
+			assert_eq!(mesh.face_count(), 2 * n - 2 - h);
+		}
+
+		let line = vec![p(0.0, 0.0), p(1.0, 1.0), p(2.0, 2.0), p(0.0, 0.0)];
+		assert_eq!(HalfEdgeMesh::delaunay(line).face_count(), 0);
+		let fan = vec![
+			p(0.0, 0.0),
+			p(0.0, 1.0),
+			p(0.0, 2.0),
+			p(0.0, 2.0),
+			p(1.0, 1.0),
+		];
+		assert_eq!(HalfEdgeMesh::delaunay(fan).face_count(), 2);
+	}
+
+	#[test]
+	fn test_constrained_delaunay()
+	{
+		// A long diagonal through a row of points, split at the point on it
+		let mut pts: Vec<Point<f64>> = (0..5).map(|i| p(i as f64, 1.0)).collect();
+		pts.extend((0..5).map(|i| p(i as f64, -1.0)));
+		pts.extend([p(-1.0, 0.0), p(2.0, 0.0), p(5.0, 0.0)]);
+		let segments = [[10, 12]];
+		let mesh = HalfEdgeMesh::constrained_delaunay(pts, &segments);
+
+		let fixed = [[10, 11], [11, 12]];
+		for &[a, b] in &fixed {
+			assert!(mesh.find_edge(a, b).is_some() && mesh.find_edge(b, a).is_some());
+		}
+		assert!(is_delaunay(&mesh, &fixed));
+		assert_eq!(mesh.face_count(), 2 * 13 - 2 - 12);
+
+		// The flat quad's short diagonal gives way to the constraint
+		let quad = vec![p(0.0, 0.0), p(2.0, -1.0), p(4.0, 0.0), p(2.0, 1.0)];
+		assert!(HalfEdgeMesh::delaunay(quad.clone())
+			.find_edge(0, 2)
+			.is_none());
+		let mesh = HalfEdgeMesh::constrained_delaunay(quad, &[[0, 2]]);
+		assert!(mesh.find_edge(0, 2).is_some() && mesh.find_edge(1, 3).is_none());
+	}
+
+	#[test]
+	fn test_from_polygon_delaunay()
+	{
+		// Random star shaped polygons keep their area and outline
+		#[cfg(feature = "std")]
+		{
+			let mut next = random(5);
+			for n in 3..60 {
+				let points: Vec<Point<f64>> = (0..n)
+					.map(|i| {
+						let a = (i as f64 + 0.8 * next()) / n as f64 * core::f64::consts::TAU;
+						let r = 0.3 + next();
+						p(r * a.cos(), r * a.sin())
+					})
+					.collect();
+				let mesh = HalfEdgeMesh::from_polygon_delaunay(
+					&Polygon {
+						points: points.clone(),
+					},
+					&[],
+				);
+
+				assert_eq!(mesh.face_count(), n - 2);
+				assert!((mesh_area(&mesh) - area(&points)).abs() < 1e-9);
+				assert!((0..n).all(|i| mesh.find_edge(i, (i + 1) % n).is_some()));
+			}
+		}
+
+		let outline = Polygon {
+			points: vec![p(0.0, 0.0), p(10.0, 0.0), p(10.0, 6.0), p(0.0, 6.0)],
+		};
+		let hole = Polygon {
+			points: vec![p(4.0, 1.0), p(4.0, 5.0), p(6.0, 5.0), p(6.0, 1.0)],
+		};
+		let mesh = HalfEdgeMesh::from_polygon_delaunay(&outline, &[hole]);
+
+		// 8 corners around one hole give 8 triangles covering the area between
+		assert_eq!(mesh.face_count(), 8);
+		assert_eq!(mesh.boundary().len(), 2);
+
+		// The navmesh walks around the hole
+		#[cfg(feature = "std")]
+		{
+			let path = mesh.find_path(p(1.0, 3.0), p(9.0, 3.0)).unwrap();
+			assert!(path.len() > 2);
+			assert!(path
+				.iter()
+				.all(|q| !(q.x > 4.0 && q.x < 6.0 && q.y > 1.0 && q.y < 5.0)));
+		}
+	}
+}
@@ -0,0 +1,223 @@
+use super::{orientation, Orientation, Point};
+use crate::parallel;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::ops::{Mul, Sub};
+
+// -----------------------------------------------------------------------------
+// Convex hull
+// -----------------------------------------------------------------------------
+
+/// Points filtered per chunk by [`par_convex_hull`].
+const CHUNK: usize = 1 << 14;
+
+/// Keeps the leftmost, lowest, rightmost and highest of the corners, the first on ties.
+fn extremes<T: Copy + PartialOrd>(q: [Point<T>; 4], r: [Point<T>; 4]) -> [Point<T>; 4]
+{
+	[
+		if r[0].x < q[0].x { r[0] } else { q[0] },
+		if r[1].y < q[1].y { r[1] } else { q[1] },
+		if r[2].x > q[2].x { r[2] } else { q[2] },
+		if r[3].y > q[3].y { r[3] } else { q[3] },
+	]
+}
+
+/// Whether p lies strictly inside the counter clockwise quadrilateral of the extremes, which
+/// rules it out as a corner of the hull.
+fn inside<T>(quad: &[Point<T>; 4], p: Point<T>) -> bool
+where
+	T: Copy + Sub<Output = T> + Mul<Output = T> + PartialOrd,
+{
+	quad.iter().any(|&c| c != quad[0])
+		&& (0..4).all(|i| {
+			let (a, b) = (quad[i], quad[(i + 1) % 4]);
+			a == b || orientation(a, b, p) == Orientation::CounterClockwise
+		})
+}
+
+/// Pushes p after dropping the corners above `keep` that don't turn left towards it.
+fn turn_left<T>(hull: &mut Vec<Point<T>>, p: Point<T>, keep: usize)
+where
+	T: Copy + Sub<Output = T> + Mul<Output = T> + PartialOrd,
+{
+	while hull.len() > keep
+		&& orientation(hull[hull.len() - 2], hull[hull.len() - 1], p)
+			!= Orientation::CounterClockwise
+	{
+		hull.pop();
+	}
+	hull.push(p);
+}
+
+/// Andrew's monotone chain over the remaining points.
+fn chain<T>(mut points: Vec<Point<T>>) -> Vec<Point<T>>
+where
+	T: Copy + Sub<Output = T> + Mul<Output = T> + PartialOrd,
+{
+	points.sort_by(|a, b| {
+		a.x.partial_cmp(&b.x)
+			.unwrap_or(Ordering::Equal)
+			.then(a.y.partial_cmp(&b.y).unwrap_or(Ordering::Equal))
+	});
+	points.dedup();
+	if points.len() < 3 {
+		return points;
+	}
+
+	let mut hull: Vec<Point<T>> = Vec::with_capacity(points.len() + 1);
+
+	// Lower half left to right, then the upper half back, ending on the first point again
+	for &p in &points {
+		turn_left(&mut hull, p, 1);
+	}
+	let lower = hull.len();
+	for &p in points.iter().rev().skip(1) {
+		turn_left(&mut hull, p, lower);
+	}
+
+	hull.pop();
+	hull
+}
+
+/// Corners of the convex hull counter clockwise, starting with the leftmost and lowest point.
+/// Points on the hull's edges are left out, fewer than 3 distinct points are returned sorted.
+///
+/// Points strictly inside the quadrilateral of the leftmost, lowest, rightmost and highest point
+/// are filtered out first (Akl–Toussaint), which leaves few to sort for spread out inputs. The
+/// hull itself is O(n log n) and exact for integer points, like [`orientation`].
+///
+/// # Examples
+///
+/// ```
+/// use ctl::geometry::{convex_hull, Point};
+/// let p = |x, y| Point { x, y };
+/// let hull = convex_hull(&[p(0, 0), p(2, 1), p(4, 0), p(2, 3), p(2, 0)]);
+/// // [(0, 0), (4, 0), (2, 3)]
+/// ```
+pub fn convex_hull<T>(points: &[Point<T>]) -> Vec<Point<T>>
+where
+	T: Copy + Sub<Output = T> + Mul<Output = T> + PartialOrd,
+{
+	if points.is_empty() {
+		return Vec::new();
+	}
+
+	let quad = points
+		.iter()
+		.fold([points[0]; 4], |q, &p| extremes(q, [p; 4]));
+	chain(
+		points
+			.iter()
+			.copied()
+			.filter(|&p| !inside(&quad, p))
+			.collect(),
+	)
+}
+
+/// Same hull as [`convex_hull`], filtering the points in chunks on all cores with the
+/// `parallel` feature before the sort.
+pub fn par_convex_hull<T>(points: &[Point<T>]) -> Vec<Point<T>>
+where
+	T: Copy + Send + Sync + Sub<Output = T> + Mul<Output = T> + PartialOrd,
+{
+	if points.is_empty() {
+		return Vec::new();
+	}
+
+	let quad = parallel::map_chunks(points, CHUNK, |_, c| {
+		c.iter().fold([c[0]; 4], |q, &p| extremes(q, [p; 4]))
+	})
+	.into_iter()
+	.fold([points[0]; 4], extremes);
+
+	let kept = parallel::map_chunks(points, CHUNK, |_, c| {
+		c.iter()
+			.copied()
+			.filter(|&p| !inside(&quad, p))
+			.collect::<Vec<_>>()
+	});
+	chain(kept.concat())
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests
+{
+	use super::*;
+
+	fn p(x: i64, y: i64) -> Point<i64>
+	{
+		Point { x, y }
+	}
+
+	#[test]
+	fn test_convex_hull()
+	{
+		// Collinear and repeated points are dropped
+		let square = [
+			p(0, 0),
+			p(1, 0),
+			p(2, 0),
+			p(2, 2),
+			p(1, 1),
+			p(0, 2),
+			p(2, 2),
+			p(0, 1),
+		];
+		assert_eq!(
+			convex_hull(&square),
+			vec![p(0, 0), p(2, 0), p(2, 2), p(0, 2)]
+		);
+
+		assert_eq!(convex_hull::<i64>(&[]), vec![]);
+		assert_eq!(convex_hull(&[p(3, 3), p(3, 3)]), vec![p(3, 3)]);
+		assert_eq!(
+			convex_hull(&[p(2, 2), p(0, 0), p(1, 1)]),
+			vec![p(0, 0), p(2, 2)]
+		);
+
+		// Every point of a pseudo random cloud lies left of or on the edges, which turn left
+		let mut seed = 7u64;
+		let mut next = || {
+			seed = seed
+				.wrapping_mul(6364136223846793005)
+				.wrapping_add(1442695040888963407);
+			(seed >> 40) as i64 % 1000
+		};
+		let cloud: Vec<_> = (0..300).map(|_| p(next(), next())).collect();
+		let hull = convex_hull(&cloud);
+		assert!(hull.len() >= 3);
+		for i in 0..hull.len() {
+			let (a, b) = (hull[i], hull[(i + 1) % hull.len()]);
+			assert!(cloud
+				.iter()
+				.all(|&q| orientation(a, b, q) != Orientation::Clockwise));
+			assert_eq!(
+				orientation(a, b, hull[(i + 2) % hull.len()]),
+				Orientation::CounterClockwise
+			);
+		}
+	}
+
+	#[test]
+	fn test_par_convex_hull()
+	{
+		// Several chunks, with floats in a disc
+		let cloud: Vec<Point<f64>> = (0..100_000u64)
+			.map(|i| Point {
+				x: ((i * 7919) % 2001) as f64 / 1000.0 - 1.0,
+				y: ((i * 104729) % 2003) as f64 / 1000.0 - 1.0,
+			})
+			.filter(|q| q.x * q.x + q.y * q.y <= 1.0)
+			.collect();
+		let hull = par_convex_hull(&cloud);
+		assert!(hull.len() > 10);
+		assert_eq!(hull, convex_hull(&cloud));
+
+		assert_eq!(par_convex_hull::<i64>(&[]), vec![]);
+		assert_eq!(par_convex_hull(&[p(1, 2)]), vec![p(1, 2)]);
+	}
+}
@@ -0,0 +1,378 @@
+#[cfg(feature = "std")]
+use super::{convex_hull, Circle, HalfEdgeMesh, Segment};
+use super::{point_in_polygon, Point, Polygon, Rect};
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+// -----------------------------------------------------------------------------
+// Inscribed rectangle
+// -----------------------------------------------------------------------------
+
+/// Sorted distinct coordinates of the corners and of `steps` equal subdivisions between them.
+fn grid_lines(mut coords: Vec<f64>, steps: usize) -> Vec<f64>
+{
+	coords.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+	let (lo, hi) = (coords[0], coords[coords.len() - 1]);
+
+	coords.extend((1..steps).map(|k| lo + (hi - lo) * k as f64 / steps as f64));
+	coords.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+	coords.dedup();
+	coords
+}
+
+impl Polygon
+{
+	/// Largest axis aligned rectangle inside the simple polygon, searched on a grid through the
+	/// corner coordinates refined by `steps` equal subdivisions of the bounding box. Every grid
+	/// cell is tested against the edges crossing its row, then the cells inside are searched like
+	/// a histogram per row, which takes O(steps² n) overall.
+	///
+	/// Exact for rectilinear polygons with any `steps`. With sloped edges the optimum may touch
+	/// them between grid lines, the result then loses at most one subdivision on each side.
+	/// Returns `None` if no cell lies inside, e.g. for fewer than 3 corners.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use ctl::geometry::{Point, Polygon};
+	/// let p = |x, y| Point { x, y };
+	/// let l = Polygon {
+	///     points: vec![p(0.0, 0.0), p(4.0, 0.0), p(4.0, 1.0), p(1.0, 1.0), p(1.0, 3.0), p(0.0, 3.0)],
+	/// };
+	/// let r = l.largest_inscribed_rect(1); // Some((0, 0) to (4, 1))
+	/// ```
+	pub fn largest_inscribed_rect(&self, steps: usize) -> Option<Rect<f64>>
+	{
+		let pts = &self.points;
+		if pts.len() < 3 {
+			return None;
+		}
+
+		let xs = grid_lines(pts.iter().map(|p| p.x).collect(), steps);
+		let ys = grid_lines(pts.iter().map(|p| p.y).collect(), steps);
+		let cols = xs.len() - 1;
+
+		let mut heights = vec![0.0; cols + 1];
+		let mut best: Option<(f64, Rect<f64>)> = None;
+
+		for r in 0..ys.len() - 1 {
+			let (y0, y1) = (ys[r], ys[r + 1]);
+
+			// Corners lie on grid lines, so an edge entering the row spans all of it
+			let mut blocked = vec![false; cols];
+			for (i, &a) in pts.iter().enumerate() {
+				let b = pts[(i + 1) % pts.len()];
+				if a.y.max(b.y) <= y0 || a.y.min(b.y) >= y1 {
+					continue;
+				}
+
+				let at = |y: f64| a.x + (b.x - a.x) * (y - a.y) / (b.y - a.y);
+				let (lo, hi) = (at(y0).min(at(y1)), at(y0).max(at(y1)));
+				for (c, cell) in blocked.iter_mut().enumerate() {
+					let (x0, x1) = (xs[c], xs[c + 1]);
+					*cell |= if lo == hi {
+						x0 < lo && lo < x1
+					} else {
+						x0 < hi && lo < x1
+					};
+				}
+			}
+
+			for c in 0..cols {
+				let center = Point {
+					x: (xs[c] + xs[c + 1]) / 2.0,
+					y: (y0 + y1) / 2.0,
+				};
+				heights[c] = if !blocked[c] && point_in_polygon(center, pts) {
+					heights[c] + (y1 - y0)
+				} else {
+					0.0
+				};
+			}
+
+			// Largest rectangle under the histogram, the last height of 0 empties the stack
+			let mut stack: Vec<(usize, f64)> = Vec::new();
+			for (c, &h) in heights.iter().enumerate() {
+				let mut start = c;
+				while let Some(&(s, sh)) = stack.last() {
+					if sh < h {
+						break;
+					}
+
+					stack.pop();
+					let area = sh * (xs[c] - xs[s]);
+					if area > best.map_or(0.0, |b| b.0) {
+						let rect = Rect {
+							min: Point {
+								x: xs[s],
+								y: y1 - sh,
+							},
+							max: Point { x: xs[c], y: y1 },
+						};
+						best = Some((area, rect));
+					}
+					start = s;
+				}
+				stack.push((start, h));
+			}
+		}
+
+		best.map(|b| b.1)
+	}
+}
+
+// -----------------------------------------------------------------------------
+// Empty circle
+// -----------------------------------------------------------------------------
+
+#[cfg(feature = "std")]
+fn dist(a: Point<f64>, b: Point<f64>) -> f64
+{
+	let d = a - b;
+	(d.x * d.x + d.y * d.y).sqrt()
+}
+
+/// Center of the circle through the corners of the triangle.
+#[cfg(feature = "std")]
+fn circumcenter(a: Point<f64>, b: Point<f64>, c: Point<f64>) -> Point<f64>
+{
+	let (ab, ac) = (b - a, c - a);
+	let d = 2.0 * (ab.x * ac.y - ab.y * ac.x);
+	let (l, m) = (ab.x * ab.x + ab.y * ab.y, ac.x * ac.x + ac.y * ac.y);
+	Point {
+		x: a.x + (ac.y * l - ab.y * m) / d,
+		y: a.y + (ab.x * m - ac.x * l) / d,
+	}
+}
+
+/// Largest circle containing none of the points, with its center inside their convex hull.
+/// Returns `None` for points without a hull of positive area.
+///
+/// The centers of the empty circles touching 3 points are the vertices of the Voronoi diagram,
+/// which are the circumcenters of the Delaunay triangles. Along the hull's border the best
+/// centers lie where it crosses a Voronoi edge, the part of the bisector of 2 neighbouring
+/// points between the circumcenters of their triangles. Checking both takes O(n h) after the
+/// triangulation, for h corners of the hull.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::geometry::{largest_empty_circle, Point};
+/// let p = |x, y| Point { x, y };
+/// let c = largest_empty_circle(&[p(0.0, 0.0), p(4.0, 0.0), p(4.0, 4.0), p(0.0, 4.0), p(1.0, 1.0)]);
+/// // Through (1, 1), (4, 0) and (4, 4) around (3, 2), or its mirror image around (2, 3)
+/// ```
+#[cfg(feature = "std")]
+pub fn largest_empty_circle(points: &[Point<f64>]) -> Option<Circle>
+{
+	let hull = convex_hull(points);
+	if hull.len() < 3 {
+		return None;
+	}
+
+	let border: Vec<Segment<f64>> = (0..hull.len())
+		.map(|i| Segment {
+			a: hull[i],
+			b: hull[(i + 1) % hull.len()],
+		})
+		.collect();
+	let size = border.iter().map(|s| dist(s.a, s.b)).sum::<f64>();
+
+	let mesh = HalfEdgeMesh::delaunay(points.to_vec());
+	let v = &mesh.vertices;
+	let center = |f: usize| {
+		let [a, b, c] = mesh.face_vertices(f).map(|i| v[i]);
+		circumcenter(a, b, c)
+	};
+
+	let mut best: Option<Circle> = None;
+	let mut offer = |center: Point<f64>, site: Point<f64>| {
+		let radius = dist(center, site);
+		if best.is_none_or(|b| radius > b.radius) {
+			best = Some(Circle { center, radius });
+		}
+	};
+
+	for f in 0..mesh.face_count() {
+		let c = center(f);
+		if point_in_polygon(c, &hull) {
+			offer(c, v[mesh.face_vertices(f)[0]]);
+		}
+
+		for e in mesh.face_edges(f) {
+			let (p, q) = (v[mesh.edge(e).origin], v[mesh.target(e)]);
+			let voronoi = match mesh.edge(e).twin {
+				Some(t) if e < t => Segment {
+					a: c,
+					b: center(mesh.edge(t).face),
+				},
+				Some(_) => continue,
+				None => {
+					// Ray away from the triangle, long enough to leave the hull
+					let (d, len) = (q - p, dist(p, q));
+					let far = (dist(c, p) + size) / len;
+					Segment {
+						a: c,
+						b: Point {
+							x: c.x + d.y * far,
+							y: c.y - d.x * far,
+						},
+					}
+				}
+			};
+
+			for s in &border {
+				if let Some(x) = voronoi.intersection(s) {
+					offer(x, p);
+				}
+			}
+		}
+	}
+
+	best
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests
+{
+	use super::*;
+
+	fn p(x: f64, y: f64) -> Point<f64>
+	{
+		Point { x, y }
+	}
+
+	fn area(r: Rect<f64>) -> f64
+	{
+		r.width() * r.height()
+	}
+
+	#[cfg(feature = "std")]
+	#[test]
+	fn test_largest_empty_circle()
+	{
+		// The center of a square is empty, the inner point pushes the circle to the right edge
+		let mut pts = vec![p(0.0, 0.0), p(4.0, 0.0), p(4.0, 4.0), p(0.0, 4.0)];
+		let c = largest_empty_circle(&pts).unwrap();
+		assert!((c.radius - 8f64.sqrt()).abs() < 1e-12);
+		assert!(dist(c.center, p(2.0, 2.0)) < 1e-12);
+
+		pts.push(p(1.0, 1.0));
+		let c = largest_empty_circle(&pts).unwrap();
+		assert!((c.radius - 5f64.sqrt()).abs() < 1e-12);
+		assert!(dist(c.center, p(3.0, 2.0)) < 1e-12 || dist(c.center, p(2.0, 3.0)) < 1e-12);
+
+		assert!(largest_empty_circle(&[p(0.0, 0.0), p(1.0, 1.0), p(2.0, 2.0)]).is_none());
+
+		// Against the best center of a fine grid over the hull, which can only be slightly worse
+		let mut seed = 9u64;
+		let mut next = || {
+			seed = seed
+				.wrapping_mul(6364136223846793005)
+				.wrapping_add(1442695040888963407);
+			(seed >> 11) as f64 / (1u64 << 53) as f64
+		};
+		for n in [3, 5, 10, 25, 60] {
+			let pts: Vec<Point<f64>> = (0..n).map(|_| p(next() * 10.0, next() * 10.0)).collect();
+			let c = largest_empty_circle(&pts).unwrap();
+			let nearest = |q: Point<f64>| {
+				pts.iter()
+					.map(|&s| dist(q, s))
+					.fold(f64::INFINITY, f64::min)
+			};
+
+			let hull = convex_hull(&pts);
+			// Inside or on the border of the counter clockwise hull, up to rounding
+			assert!((0..hull.len()).all(|k| {
+				let (a, b) = (hull[k], hull[(k + 1) % hull.len()]);
+				let (d, e) = (b - a, c.center - a);
+				d.x * e.y - d.y * e.x >= -1e-9 * dist(a, b)
+			}));
+			assert!((nearest(c.center) - c.radius).abs() < 1e-9);
+
+			let steps = 300;
+			let mut grid = 0.0f64;
+			for i in 0..=steps {
+				for j in 0..=steps {
+					let q = p(
+						i as f64 * 10.0 / steps as f64,
+						j as f64 * 10.0 / steps as f64,
+					);
+					if point_in_polygon(q, &hull) {
+						grid = grid.max(nearest(q));
+					}
+				}
+			}
+			let cell = 10.0 / steps as f64 * 2f64.sqrt();
+			assert!(
+				grid <= c.radius + 1e-9 && grid >= c.radius - cell,
+				"{} {}",
+				grid,
+				c.radius
+			);
+		}
+	}
+
+	#[test]
+	fn test_largest_inscribed_rect()
+	{
+		// Rectilinear shapes are exact without subdivisions, in either winding
+		let mut l = Polygon {
+			points: vec![
+				p(0.0, 0.0),
+				p(4.0, 0.0),
+				p(4.0, 1.0),
+				p(1.0, 1.0),
+				p(1.0, 3.0),
+				p(0.0, 3.0),
+			],
+		};
+		let expected = Rect::from_corners(p(0.0, 0.0), p(4.0, 1.0));
+		assert_eq!(l.largest_inscribed_rect(1), Some(expected));
+		l.points.reverse();
+		assert_eq!(l.largest_inscribed_rect(1), Some(expected));
+
+		let u = Polygon {
+			points: vec![
+				p(0.0, 0.0),
+				p(5.0, 0.0),
+				p(5.0, 4.0),
+				p(4.0, 4.0),
+				p(4.0, 1.0),
+				p(1.0, 1.0),
+				p(1.0, 4.0),
+				p(0.0, 4.0),
+			],
+		};
+		assert_eq!(u.largest_inscribed_rect(0).map(area), Some(5.0));
+
+		// A diamond holds the square through its edge midpoints, on the grid for even steps
+		let diamond = Polygon {
+			points: vec![p(1.0, 0.0), p(0.0, 1.0), p(-1.0, 0.0), p(0.0, -1.0)],
+		};
+		let r = diamond.largest_inscribed_rect(4).unwrap();
+		assert_eq!(r, Rect::from_corners(p(-0.5, -0.5), p(0.5, 0.5)));
+
+		// Off the grid the result stays inside and approaches the optimum of 1
+		let triangle = Polygon {
+			points: vec![p(0.0, 0.0), p(2.0, 0.0), p(0.0, 2.0)],
+		};
+		for steps in [7, 31, 101] {
+			let r = triangle.largest_inscribed_rect(steps).unwrap();
+			assert!(area(r) <= 1.0 && area(r) > 1.0 - 4.0 / steps as f64);
+			assert!(point_in_polygon(r.max, &triangle.points));
+		}
+
+		assert_eq!(
+			Polygon {
+				points: vec![p(0.0, 0.0), p(1.0, 1.0)]
+			}
+			.largest_inscribed_rect(4),
+			None
+		);
+	}
+}
@@ -0,0 +1,431 @@
+use super::decompose::{bridge_holes, ear_clip};
+use super::{orientation, Orientation, Point, Polygon};
+use alloc::vec::Vec;
+
+// -----------------------------------------------------------------------------
+// Half-edge mesh
+// -----------------------------------------------------------------------------
+
+/// Directed edge of a [`HalfEdgeMesh`] running counter clockwise around its face.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct HalfEdge
+{
+	/// Vertex the edge starts at
+	pub origin: usize,
+	/// Opposite edge of the neighbouring face, `None` on the boundary
+	pub twin: Option<usize>,
+	/// Following edge around the face
+	pub next: usize,
+	pub face: usize,
+}
+
+/// Triangle mesh stored as a doubly connected edge list. Face `f` owns the half-edges `3f`,
+/// `3f + 1` and `3f + 2`, which stays true across edge flips.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::geometry::{HalfEdgeMesh, Point};
+/// let p = |x, y| Point { x, y };
+/// let square = vec![p(0.0, 0.0), p(1.0, 0.0), p(1.0, 1.0), p(0.0, 1.0)];
+/// let mut mesh = HalfEdgeMesh::from_triangles(square, &[[0, 1, 2], [0, 2, 3]]);
+///
+/// let diagonal = mesh.find_edge(0, 2).unwrap();
+/// mesh.flip(diagonal); // true, now runs between 1 and 3
+/// let outline = mesh.boundary(); // [[0, 1, 2, 3]]
+/// ```
+#[derive(Debug, Clone)]
+pub struct HalfEdgeMesh
+{
+	pub vertices: Vec<Point<f64>>,
+	edges: Vec<HalfEdge>,
+	/// One outgoing edge per vertex, the boundary one if there is one
+	vertex_edge: Vec<Option<usize>>,
+}
+
+impl HalfEdgeMesh
+{
+	/// Builds the mesh from triangles given as vertex indices in any winding. Every directed edge
+	/// may belong to one triangle only.
+	pub fn from_triangles(vertices: Vec<Point<f64>>, triangles: &[[usize; 3]]) -> Self
+	{
+		let mut edges = Vec::with_capacity(triangles.len() * 3);
+
+		for (f, &[a, b, c]) in triangles.iter().enumerate() {
+			let corners = match orientation(vertices[a], vertices[b], vertices[c]) {
+				Orientation::Clockwise => [a, c, b],
+				_ => [a, b, c],
+			};
+
+			for (i, &origin) in corners.iter().enumerate() {
+				edges.push(HalfEdge {
+					origin,
+					twin: None,
+					next: 3 * f + (i + 1) % 3,
+					face: f,
+				});
+			}
+		}
+
+		// Pair up opposite edges through a sorted list of (from, to)
+		let mut keys: Vec<(usize, usize, usize)> = (0..edges.len())
+			.map(|e| (edges[e].origin, edges[edges[e].next].origin, e))
+			.collect();
+		keys.sort_unstable();
+
+		for w in keys.windows(2) {
+			assert!(
+				w[0].0 != w[1].0 || w[0].1 != w[1].1,
+				"edge used by more than one triangle"
+			);
+		}
+
+		for &(a, b, e) in &keys {
+			if let Ok(i) = keys.binary_search_by(|k| (k.0, k.1).cmp(&(b, a))) {
+				edges[e].twin = Some(keys[i].2);
+			}
+		}
+
+		let mut vertex_edge = vec![None; vertices.len()];
+		for (e, h) in edges.iter().enumerate() {
+			if vertex_edge[h.origin].is_none() || h.twin.is_none() {
+				vertex_edge[h.origin] = Some(e);
+			}
+		}
+
+		HalfEdgeMesh {
+			vertices,
+			edges,
+			vertex_edge,
+		}
+	}
+
+	/// Builds the mesh from an ear clipping triangulation of the simple polygon.
+	pub fn from_polygon(polygon: &Polygon) -> Self
+	{
+		Self::from_polygon_with_holes(polygon, &[])
+	}
+
+	/// Builds the mesh from an ear clipping triangulation of the area inside the outline and
+	/// outside the holes. Every hole is joined to the outline by a bridge first, which becomes an
+	/// edge between two triangles. The polygons may have any winding but must not touch each
+	/// other. The vertices are the outline's corners followed by those of the holes.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use ctl::geometry::{HalfEdgeMesh, Point, Polygon};
+	/// let p = |x, y| Point { x, y };
+	/// let square = |x: f64, y: f64, s: f64| Polygon {
+	///     points: vec![p(x, y), p(x + s, y), p(x + s, y + s), p(x, y + s)],
+	/// };
+	/// let mesh = HalfEdgeMesh::from_polygon_with_holes(&square(0.0, 0.0, 3.0), &[square(1.0, 1.0, 1.0)]);
+	/// let n = mesh.face_count(); // 8
+	/// ```
+	pub fn from_polygon_with_holes(outline: &Polygon, holes: &[Polygon]) -> Self
+	{
+		let (points, ring) = bridge_holes(&outline.points, holes);
+		let corners: Vec<Point<f64>> = ring.iter().map(|&i| points[i]).collect();
+		let tris: Vec<[usize; 3]> = ear_clip(&corners)
+			.into_iter()
+			.map(|t| t.map(|i| ring[i]))
+			.collect();
+		Self::from_triangles(points, &tris)
+	}
+
+	pub fn face_count(&self) -> usize
+	{
+		self.edges.len() / 3
+	}
+
+	pub fn edge(&self, e: usize) -> &HalfEdge
+	{
+		&self.edges[e]
+	}
+
+	/// Vertex the edge ends at.
+	pub fn target(&self, e: usize) -> usize
+	{
+		self.edges[self.edges[e].next].origin
+	}
+
+	/// Finds the half-edge running from `a` to `b`.
+	pub fn find_edge(&self, a: usize, b: usize) -> Option<usize>
+	{
+		self.outgoing(a).into_iter().find(|&e| self.target(e) == b)
+	}
+
+	/// Half-edges of the face in counter clockwise order.
+	pub fn face_edges(&self, f: usize) -> [usize; 3]
+	{
+		let e = 3 * f;
+		let n = self.edges[e].next;
+		[e, n, self.edges[n].next]
+	}
+
+	/// Corners of the face in counter clockwise order.
+	pub fn face_vertices(&self, f: usize) -> [usize; 3]
+	{
+		self.face_edges(f).map(|e| self.edges[e].origin)
+	}
+
+	/// Faces sharing an edge with the face.
+	pub fn face_neighbours(&self, f: usize) -> Vec<usize>
+	{
+		self.face_edges(f)
+			.iter()
+			.filter_map(|&e| self.edges[e].twin)
+			.map(|t| self.edges[t].face)
+			.collect()
+	}
+
+	fn prev(&self, e: usize) -> usize
+	{
+		self.edges[self.edges[e].next].next
+	}
+
+	/// Half-edges leaving the vertex in counter clockwise order, starting at the boundary for
+	/// vertices on it.
+	pub fn outgoing(&self, v: usize) -> Vec<usize>
+	{
+		let mut out = Vec::new();
+		let start = match self.vertex_edge[v] {
+			Some(e) => e,
+			None => return out,
+		};
+
+		let mut e = start;
+		loop {
+			out.push(e);
+			match self.edges[self.prev(e)].twin {
+				Some(t) if t != start => e = t,
+				_ => return out,
+			}
+		}
+	}
+
+	/// Flips the edge shared by two triangles to the other diagonal of their quad. Returns false
+	/// and leaves the mesh as is for boundary edges and non convex quads.
+	///
+	/// Both faces keep their half-edges, so `e` and its twin turn into the new diagonal while the
+	/// 4 edges around the quad move to other indices.
+	pub fn flip(&mut self, e: usize) -> bool
+	{
+		let t = match self.edges[e].twin {
+			Some(t) => t,
+			None => return false,
+		};
+
+		// e: a -> b -> c, t: b -> a -> d
+		let (e1, e2) = (self.edges[e].next, self.prev(e));
+		let (t1, t2) = (self.edges[t].next, self.prev(t));
+		let (a, b) = (self.edges[e].origin, self.edges[t].origin);
+		let (c, d) = (self.edges[e2].origin, self.edges[t2].origin);
+
+		let v = &self.vertices;
+		if orientation(v[d], v[c], v[a]) != Orientation::CounterClockwise
+			|| orientation(v[c], v[d], v[b]) != Orientation::CounterClockwise
+		{
+			return false;
+		}
+
+		// Both faces keep their slots and links, their corners and twins rotate into the new
+		// triangles e: d -> c -> a and t: c -> d -> b
+		let twins = [e1, e2, t1, t2].map(|h| self.edges[h].twin);
+		let corners = [(e, d, Some(t)), (e1, c, twins[1]), (e2, a, twins[2])];
+		let opposite = [(t, c, Some(e)), (t1, d, twins[3]), (t2, b, twins[0])];
+		for &(h, origin, twin) in corners.iter().chain(&opposite) {
+			self.edges[h].origin = origin;
+			self.edges[h].twin = twin;
+			if let Some(o) = twin {
+				self.edges[o].twin = Some(h);
+			}
+		}
+
+		// Stored outgoing edges move along with their half-edge
+		let moved = [(e, e2), (t, t2), (e1, t2), (e2, e1), (t1, e2), (t2, t1)];
+		for v in [a, b, c, d] {
+			if let Some(&(_, to)) = moved.iter().find(|m| self.vertex_edge[v] == Some(m.0)) {
+				self.vertex_edge[v] = Some(to);
+			}
+		}
+
+		true
+	}
+
+	/// Vertex loops of the mesh outline, counter clockwise around the mesh and clockwise around
+	/// holes.
+	pub fn boundary(&self) -> Vec<Vec<usize>>
+	{
+		let mut seen = vec![false; self.edges.len()];
+		let mut loops = Vec::new();
+
+		for start in 0..self.edges.len() {
+			if seen[start] || self.edges[start].twin.is_some() {
+				continue;
+			}
+
+			let mut ring = Vec::new();
+			let mut e = start;
+
+			while !seen[e] {
+				seen[e] = true;
+				ring.push(self.edges[e].origin);
+
+				// The boundary edge leaving the end vertex is stored for it
+				e = self.vertex_edge[self.target(e)].unwrap();
+			}
+
+			loops.push(ring);
+		}
+
+		loops
+	}
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests
+{
+	use super::*;
+
+	fn p(x: f64, y: f64) -> Point<f64>
+	{
+		Point { x, y }
+	}
+
+	#[test]
+	fn test_traversal()
+	{
+		// Fan of 4 triangles around the center 4, the last one given clockwise
+		let pts = vec![
+			p(0.0, 0.0),
+			p(2.0, 0.0),
+			p(2.0, 2.0),
+			p(0.0, 2.0),
+			p(1.0, 1.0),
+		];
+		let mesh = HalfEdgeMesh::from_triangles(pts, &[[0, 1, 4], [1, 2, 4], [2, 3, 4], [0, 3, 4]]);
+
+		assert_eq!(mesh.face_count(), 4);
+		assert_eq!(mesh.face_vertices(3), [0, 4, 3]);
+		assert_eq!(mesh.outgoing(4).len(), 4);
+		assert_eq!(mesh.outgoing(0).len(), 2);
+
+		let mut n = mesh.face_neighbours(0);
+		n.sort_unstable();
+		assert_eq!(n, vec![1, 3]);
+
+		let e = mesh.find_edge(4, 2).unwrap();
+		assert_eq!(mesh.edge(mesh.edge(e).twin.unwrap()).origin, 2);
+		assert_eq!(mesh.boundary(), vec![vec![0, 1, 2, 3]]);
+	}
+
+	#[test]
+	fn test_flip()
+	{
+		let pts = vec![
+			p(0.0, 0.0),
+			p(1.0, 0.0),
+			p(1.0, 1.0),
+			p(0.0, 1.0),
+			p(3.0, 0.5),
+		];
+		let mut mesh = HalfEdgeMesh::from_triangles(pts, &[[0, 1, 2], [0, 2, 3], [1, 4, 2]]);
+
+		let d = mesh.find_edge(0, 2).unwrap();
+		assert!(mesh.flip(d));
+		assert!(mesh.find_edge(0, 2).is_none());
+		assert!(mesh.find_edge(1, 3).is_some() && mesh.find_edge(3, 1).is_some());
+
+		for f in 0..mesh.face_count() {
+			let [a, b, c] = mesh.face_vertices(f);
+			let v = &mesh.vertices;
+			assert_eq!(orientation(v[a], v[b], v[c]), Orientation::CounterClockwise);
+		}
+		let mut outline = mesh.boundary();
+		assert_eq!(outline.len(), 1);
+		let start = outline[0].iter().position(|&v| v == 0).unwrap();
+		outline[0].rotate_left(start);
+		assert_eq!(outline[0], vec![0, 1, 4, 2, 3]);
+
+		// The faces keep their slots and now hold the triangles on either side of 1 -> 3
+		let sorted = |f: usize| {
+			let mut c = mesh.face_vertices(f);
+			c.sort_unstable();
+			c
+		};
+		assert_eq!(
+			[sorted(0), sorted(1), sorted(2)],
+			[[1, 2, 3], [0, 1, 3], [1, 2, 4]]
+		);
+		for f in 0..mesh.face_count() {
+			for e in mesh.face_edges(f) {
+				assert_eq!(mesh.edge(e).face, f);
+				if let Some(t) = mesh.edge(e).twin {
+					assert_eq!(mesh.edge(t).twin, Some(e));
+					assert_eq!(mesh.edge(t).origin, mesh.target(e));
+				}
+			}
+		}
+		for v in 0..5 {
+			assert!(mesh.outgoing(v).iter().all(|&e| mesh.edge(e).origin == v));
+		}
+		assert_eq!(mesh.outgoing(1).len(), 3);
+
+		// Flipping twice restores the diagonal, flipping the boundary does nothing
+		let d = mesh.find_edge(1, 3).unwrap();
+		assert!(mesh.flip(d));
+		assert!(mesh.find_edge(2, 0).is_some());
+		assert!(!mesh.flip(mesh.find_edge(0, 1).unwrap()));
+
+		// The quad 0, 1, 4, 2 is not convex at 2 after moving 4 up
+		mesh.vertices[4] = p(3.0, 4.0);
+		assert!(!mesh.flip(mesh.find_edge(1, 2).unwrap()));
+	}
+
+	#[test]
+	fn test_from_polygon()
+	{
+		let poly = Polygon {
+			points: vec![
+				p(0.0, 0.0),
+				p(0.0, 2.0),
+				p(1.0, 1.0),
+				p(2.0, 2.0),
+				p(2.0, 0.0),
+			],
+		};
+		let mesh = HalfEdgeMesh::from_polygon(&poly);
+
+		assert_eq!(mesh.face_count(), 3);
+		assert_eq!(mesh.boundary(), vec![vec![4, 3, 2, 1, 0]]);
+
+		// Two holes of either winding, one left of the other so its bridge may end on it
+		let square = |x: f64, y: f64, s: f64| Polygon {
+			points: vec![p(x, y), p(x + s, y), p(x + s, y + s), p(x, y + s)],
+		};
+		let mut right = square(4.0, 1.0, 1.0);
+		right.points.reverse();
+		let outline = Polygon {
+			points: vec![p(0.0, 0.0), p(6.0, 0.0), p(6.0, 3.0), p(0.0, 3.0)],
+		};
+		let holes = [square(1.0, 1.0, 1.0), right];
+		let mesh = HalfEdgeMesh::from_polygon_with_holes(&outline, &holes);
+
+		// n corners and h holes give n + 2h - 2 triangles
+		assert_eq!(mesh.face_count(), 12 + 4 - 2);
+		let area: f64 = (0..mesh.face_count())
+			.map(|f| {
+				let [a, b, c] = mesh.face_vertices(f).map(|i| mesh.vertices[i]);
+				let (ab, ac) = (b - a, c - a);
+				(ab.x * ac.y - ab.y * ac.x) / 2.0
+			})
+			.sum();
+		assert!((area - 16.0).abs() < 1e-12);
+		assert_eq!(mesh.boundary().len(), 3);
+	}
+}
@@ -0,0 +1,353 @@
+use super::{HalfEdgeMesh, Point, Triangle};
+use alloc::collections::BinaryHeap;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+// -----------------------------------------------------------------------------
+// Navmesh
+// -----------------------------------------------------------------------------
+
+/// Open face of the A* search ordered by lowest estimate first.
+struct Open
+{
+	estimate: f64,
+	face: usize,
+}
+
+impl PartialEq for Open
+{
+	fn eq(&self, o: &Self) -> bool
+	{
+		self.estimate == o.estimate
+	}
+}
+
+impl Eq for Open {}
+
+impl PartialOrd for Open
+{
+	fn partial_cmp(&self, o: &Self) -> Option<Ordering>
+	{
+		Some(self.cmp(o))
+	}
+}
+
+impl Ord for Open
+{
+	fn cmp(&self, o: &Self) -> Ordering
+	{
+		o.estimate
+			.partial_cmp(&self.estimate)
+			.unwrap_or(Ordering::Equal)
+	}
+}
+
+fn dist(a: Point<f64>, b: Point<f64>) -> f64
+{
+	let d = a - b;
+	(d.x * d.x + d.y * d.y).sqrt()
+}
+
+/// Twice the signed area of a, b, c, positive for counter clockwise turns.
+fn cross(a: Point<f64>, b: Point<f64>, c: Point<f64>) -> f64
+{
+	let (ab, ac) = (b - a, c - a);
+	ab.x * ac.y - ab.y * ac.x
+}
+
+/// Shortest path through the portals with the simple stupid funnel algorithm. Every portal is a
+/// (left, right) pair as seen walking along the corridor, the first and last one are the end
+/// points.
+fn string_pull(portals: &[(Point<f64>, Point<f64>)]) -> Vec<Point<f64>>
+{
+	let mut path = vec![portals[0].0];
+	let (mut apex, mut left, mut right) = (portals[0].0, portals[0].0, portals[0].1);
+	let (mut left_i, mut right_i) = (0, 0);
+	let mut i = 1;
+
+	while i < portals.len() {
+		let (l, r) = portals[i];
+
+		// Tighten the right side
+		if cross(apex, right, r) >= 0.0 {
+			if apex == right || cross(apex, left, r) < 0.0 {
+				right = r;
+				right_i = i;
+			} else {
+				// Crossed over the left side, its end becomes a corner of the path
+				apex = left;
+				if path[path.len() - 1] != apex {
+					path.push(apex);
+				}
+				right = apex;
+				right_i = left_i;
+				i = left_i + 1;
+				continue;
+			}
+		}
+
+		// Tighten the left side
+		if cross(apex, left, l) <= 0.0 {
+			if apex == left || cross(apex, right, l) > 0.0 {
+				left = l;
+				left_i = i;
+			} else {
+				apex = right;
+				if path[path.len() - 1] != apex {
+					path.push(apex);
+				}
+				left = apex;
+				left_i = right_i;
+				i = right_i + 1;
+				continue;
+			}
+		}
+
+		i += 1;
+	}
+
+	let goal = portals[portals.len() - 1].0;
+	if path[path.len() - 1] != goal {
+		path.push(goal);
+	}
+
+	path
+}
+
+impl HalfEdgeMesh
+{
+	fn triangle(&self, f: usize) -> Triangle
+	{
+		let [a, b, c] = self.face_vertices(f);
+		Triangle {
+			a: self.vertices[a],
+			b: self.vertices[b],
+			c: self.vertices[c],
+		}
+	}
+
+	/// Finds a face containing the point.
+	pub fn locate(&self, p: Point<f64>) -> Option<usize>
+	{
+		(0..self.face_count()).find(|&f| self.triangle(f).contains(p))
+	}
+
+	/// Finds a path between two points inside the mesh, treating it as a navigation mesh. A*
+	/// searches the chain of faces between the end points, which the funnel algorithm then pulls
+	/// tight. Returns `None` if a point lies outside of the mesh or the faces are not connected.
+	///
+	/// The path is not always the shortest one. A* measures the chain between face centers, so
+	/// with uneven triangles it can pick a longer corridor around an obstacle, and the funnel only
+	/// shortens the path inside that corridor.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use ctl::geometry::{HalfEdgeMesh, Point, Polygon};
+	/// let p = |x, y| Point { x, y };
+	/// let u = Polygon {
+	///     points: vec![
+	///         p(0.0, 0.0), p(3.0, 0.0), p(3.0, 3.0), p(2.0, 3.0),
+	///         p(2.0, 1.0), p(1.0, 1.0), p(1.0, 3.0), p(0.0, 3.0),
+	///     ],
+	/// };
+	/// let mesh = HalfEdgeMesh::from_polygon(&u);
+	/// let path = mesh.find_path(p(0.5, 2.5), p(2.5, 2.5));
+	/// // Some([(0.5, 2.5), (1, 1), (2, 1), (2.5, 2.5)])
+	/// ```
+	pub fn find_path(&self, start: Point<f64>, goal: Point<f64>) -> Option<Vec<Point<f64>>>
+	{
+		let (from, to) = (self.locate(start)?, self.locate(goal)?);
+
+		let center = |f: usize| {
+			let t = self.triangle(f);
+			Point {
+				x: (t.a.x + t.b.x + t.c.x) / 3.0,
+				y: (t.a.y + t.b.y + t.c.y) / 3.0,
+			}
+		};
+
+		// A* over face adjacency, remembering the edge each face was entered through
+		let mut cost = vec![f64::INFINITY; self.face_count()];
+		let mut via = vec![usize::MAX; self.face_count()];
+		let mut open = BinaryHeap::new();
+
+		cost[from] = 0.0;
+		open.push(Open {
+			estimate: dist(center(from), goal),
+			face: from,
+		});
+
+		while let Some(Open { estimate, face }) = open.pop() {
+			if face == to {
+				break;
+			}
+			if estimate > cost[face] + dist(center(face), goal) {
+				continue;
+			}
+
+			for e in self.face_edges(face) {
+				let next = match self.edge(e).twin {
+					Some(t) => self.edge(t).face,
+					None => continue,
+				};
+
+				let c = cost[face] + dist(center(face), center(next));
+				if c < cost[next] {
+					cost[next] = c;
+					via[next] = e;
+					open.push(Open {
+						estimate: c + dist(center(next), goal),
+						face: next,
+					});
+				}
+			}
+		}
+
+		if from != to && via[to] == usize::MAX {
+			return None;
+		}
+
+		// Leaving a counter clockwise face through a -> b has b on the left
+		let mut portals = vec![(goal, goal)];
+		let mut f = to;
+		while f != from {
+			let e = via[f];
+			let (a, b) = (self.edge(e).origin, self.target(e));
+			portals.push((self.vertices[b], self.vertices[a]));
+			f = self.edge(e).face;
+		}
+		portals.push((start, start));
+		portals.reverse();
+
+		Some(string_pull(&portals))
+	}
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests
+{
+	use super::*;
+	use crate::geometry::Polygon;
+
+	fn p(x: f64, y: f64) -> Point<f64>
+	{
+		Point { x, y }
+	}
+
+	fn length(path: &[Point<f64>]) -> f64
+	{
+		path.windows(2).map(|w| dist(w[0], w[1])).sum()
+	}
+
+	#[test]
+	fn test_polygon()
+	{
+		let u = Polygon {
+			points: vec![
+				p(0.0, 0.0),
+				p(3.0, 0.0),
+				p(3.0, 3.0),
+				p(2.0, 3.0),
+				p(2.0, 1.0),
+				p(1.0, 1.0),
+				p(1.0, 3.0),
+				p(0.0, 3.0),
+			],
+		};
+		let mesh = HalfEdgeMesh::from_polygon(&u);
+
+		let path = mesh.find_path(p(0.5, 2.5), p(2.5, 2.5)).unwrap();
+		assert_eq!(
+			path,
+			vec![p(0.5, 2.5), p(1.0, 1.0), p(2.0, 1.0), p(2.5, 2.5)]
+		);
+
+		let back = mesh.find_path(p(2.5, 2.5), p(0.5, 2.5)).unwrap();
+		assert_eq!(
+			back,
+			vec![p(2.5, 2.5), p(2.0, 1.0), p(1.0, 1.0), p(0.5, 2.5)]
+		);
+
+		// Straight lines when nothing is in the way
+		let path = mesh.find_path(p(0.5, 2.5), p(2.5, 0.5)).unwrap();
+		assert_eq!(path, vec![p(0.5, 2.5), p(1.0, 1.0), p(2.5, 0.5)]);
+		let path = mesh.find_path(p(0.5, 0.5), p(2.5, 0.5)).unwrap();
+		assert_eq!(path, vec![p(0.5, 0.5), p(2.5, 0.5)]);
+
+		assert!(mesh.find_path(p(1.5, 2.0), p(0.5, 0.5)).is_none());
+	}
+
+	#[test]
+	fn test_flipped()
+	{
+		let square = vec![p(0.0, 0.0), p(1.0, 0.0), p(1.0, 1.0), p(0.0, 1.0)];
+		let mut mesh = HalfEdgeMesh::from_triangles(square, &[[0, 1, 2], [0, 2, 3]]);
+		assert!(mesh.flip(mesh.find_edge(0, 2).unwrap()));
+
+		let path = mesh.find_path(p(0.9, 0.1), p(0.1, 0.9)).unwrap();
+		assert_eq!(path, vec![p(0.9, 0.1), p(0.1, 0.9)]);
+		let path = mesh.find_path(p(0.1, 0.1), p(0.9, 0.9)).unwrap();
+		assert_eq!(path.len(), 2);
+	}
+
+	#[test]
+	fn test_holes()
+	{
+		// Square ring around the hole 1..2, plus a separate triangle
+		let pts = vec![
+			p(0.0, 0.0),
+			p(3.0, 0.0),
+			p(3.0, 3.0),
+			p(0.0, 3.0),
+			p(1.0, 1.0),
+			p(2.0, 1.0),
+			p(2.0, 2.0),
+			p(1.0, 2.0),
+			p(5.0, 0.0),
+			p(6.0, 0.0),
+			p(5.0, 1.0),
+		];
+		let tris = [
+			[0, 1, 5],
+			[0, 5, 4],
+			[1, 2, 6],
+			[1, 6, 5],
+			[2, 3, 7],
+			[2, 7, 6],
+			[3, 0, 4],
+			[3, 4, 7],
+			[8, 9, 10],
+		];
+		let mesh = HalfEdgeMesh::from_triangles(pts, &tris);
+
+		let path = mesh.find_path(p(0.5, 1.5), p(2.5, 1.5)).unwrap();
+		assert_eq!(path.len(), 4);
+		assert!((length(&path) - (1.0 + 2.0 * 0.5f64.sqrt())).abs() < 1e-12);
+
+		// The same ring built from the outline and the hole
+		let square = |x: f64, y: f64, s: f64| Polygon {
+			points: vec![p(x, y), p(x + s, y), p(x + s, y + s), p(x, y + s)],
+		};
+		let ring =
+			HalfEdgeMesh::from_polygon_with_holes(&square(0.0, 0.0, 3.0), &[square(1.0, 1.0, 1.0)]);
+		for (a, b) in [(p(0.5, 1.5), p(2.5, 1.5)), (p(1.5, 0.5), p(1.5, 2.5))] {
+			let path = ring.find_path(a, b).unwrap();
+			assert!((length(&path) - (1.0 + 2.0 * 0.5f64.sqrt())).abs() < 1e-12);
+			assert!(path.windows(2).all(|w| {
+				let mid = p((w[0].x + w[1].x) / 2.0, (w[0].y + w[1].y) / 2.0);
+				!(mid.x > 1.0 && mid.x < 2.0 && mid.y > 1.0 && mid.y < 2.0)
+			}));
+		}
+
+		assert!(mesh.find_path(p(0.5, 0.5), p(5.2, 0.2)).is_none());
+		assert_eq!(
+			mesh.find_path(p(5.2, 0.2), p(5.1, 0.1)),
+			Some(vec![p(5.2, 0.2), p(5.1, 0.1)])
+		);
+	}
+}
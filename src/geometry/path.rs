@@ -0,0 +1,1064 @@
+use super::Point;
+#[cfg(feature = "std")]
+use super::{orientation, Bounded, Orientation, Polygon, Rect, Segment};
+use crate::{Error, Result};
+use alloc::string::String;
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use core::f64::consts::{PI, TAU};
+use core::fmt;
+use core::str::FromStr;
+
+// -----------------------------------------------------------------------------
+// Path
+// -----------------------------------------------------------------------------
+
+/// Drawing command of a [`Path`], every point is absolute.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum PathCommand
+{
+	/// Starts a new subpath
+	MoveTo(Point<f64>),
+	LineTo(Point<f64>),
+	/// Quadratic Bezier curve through a control point
+	QuadTo(Point<f64>, Point<f64>),
+	/// Cubic Bezier curve through 2 control points
+	CubicTo(Point<f64>, Point<f64>, Point<f64>),
+	/// Elliptical arc as in SVG, choosing 1 of the 4 possible arcs by the flags
+	ArcTo
+	{
+		/// Radii along the rotated x and y axis
+		radii: Point<f64>,
+		/// Rotation of the x axis of the ellipse in degrees
+		rotation: f64,
+		large_arc: bool,
+		/// Runs towards increasing angles, counter clockwise with the y axis pointing up
+		sweep: bool,
+		to: Point<f64>,
+	},
+	/// Connects back to the start of the subpath
+	Close,
+}
+
+/// Decides which points are inside a shape from the winding number, the signed count of
+/// times the border runs around them.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum FillRule
+{
+	/// Inside for any winding number but 0, so overlapping parts stay filled
+	NonZero,
+	/// Inside for odd winding numbers, so overlapping parts cut holes
+	EvenOdd,
+}
+
+impl FillRule
+{
+	/// Checks whether a winding number counts as inside.
+	pub fn is_inside(self, winding: i32) -> bool
+	{
+		match self {
+			FillRule::NonZero => winding != 0,
+			FillRule::EvenOdd => winding % 2 != 0,
+		}
+	}
+}
+
+/// Sequence of subpaths built from lines, Bezier curves and arcs, like the `d` attribute of a
+/// SVG path.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::geometry::{Path, Point};
+/// let mut path = Path::new();
+/// path.move_to(Point { x: 0.0, y: 0.0 });
+/// path.line_to(Point { x: 10.0, y: 0.0 });
+/// path.quad_to(Point { x: 10.0, y: 10.0 }, Point { x: 0.0, y: 10.0 });
+/// path.close();
+/// let d = path.to_svg(); // "M0 0 L10 0 Q10 10 0 10 Z"
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Path
+{
+	pub commands: Vec<PathCommand>,
+}
+
+impl Path
+{
+	pub fn new() -> Self
+	{
+		Path {
+			commands: Vec::new(),
+		}
+	}
+
+	pub fn move_to(&mut self, p: Point<f64>)
+	{
+		self.commands.push(PathCommand::MoveTo(p));
+	}
+
+	pub fn line_to(&mut self, p: Point<f64>)
+	{
+		self.commands.push(PathCommand::LineTo(p));
+	}
+
+	pub fn quad_to(&mut self, c: Point<f64>, p: Point<f64>)
+	{
+		self.commands.push(PathCommand::QuadTo(c, p));
+	}
+
+	pub fn cubic_to(&mut self, c1: Point<f64>, c2: Point<f64>, p: Point<f64>)
+	{
+		self.commands.push(PathCommand::CubicTo(c1, c2, p));
+	}
+
+	pub fn arc_to(
+		&mut self,
+		radii: Point<f64>,
+		rotation: f64,
+		large_arc: bool,
+		sweep: bool,
+		to: Point<f64>,
+	)
+	{
+		self.commands.push(PathCommand::ArcTo {
+			radii,
+			rotation,
+			large_arc,
+			sweep,
+			to,
+		});
+	}
+
+	pub fn close(&mut self)
+	{
+		self.commands.push(PathCommand::Close);
+	}
+
+	/// Parses the path data of a SVG `d` attribute. Relative commands are made absolute, the
+	/// horizontal and vertical lines become [`PathCommand::LineTo`] and the smooth curves get
+	/// their reflected control point.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use ctl::geometry::{Path, PathCommand, Point};
+	/// let path = Path::parse_svg("m10 10h5v-5z").unwrap();
+	/// // [MoveTo(10, 10), LineTo(15, 10), LineTo(15, 5), Close]
+	/// ```
+	pub fn parse_svg(d: &str) -> Result<Self>
+	{
+		Parser {
+			s: d.as_bytes(),
+			i: 0,
+		}
+		.parse()
+	}
+
+	/// Approximates every subpath by a polygon, keeping the curves within `tolerance`. Open
+	/// subpaths are closed implicitly like when filling, subpaths of a single point are dropped.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use ctl::geometry::Path;
+	/// let path = Path::parse_svg("M0 0 Q1 2 2 0 Z").unwrap();
+	/// let polygons = path.to_polygons(0.01);
+	/// let area = polygons[0].area(); // 1.33
+	/// ```
+	#[cfg(feature = "std")]
+	pub fn to_polygons(&self, tolerance: f64) -> Vec<Polygon>
+	{
+		let mut polygons = Vec::new();
+		let mut points: Vec<Point<f64>> = Vec::new();
+		let mut start = Point { x: 0.0, y: 0.0 };
+
+		let mut finish = |points: &mut Vec<Point<f64>>| {
+			if points.len() > 1 && points[0] == points[points.len() - 1] {
+				points.pop();
+			}
+			if points.len() > 1 {
+				polygons.push(Polygon {
+					points: core::mem::take(points),
+				});
+			}
+			points.clear();
+		};
+
+		for cmd in &self.commands {
+			// Drawing after a close continues from the start of the closed subpath
+			let current = match points.last() {
+				Some(&p) => p,
+				None => {
+					if !matches!(cmd, PathCommand::MoveTo(_) | PathCommand::Close) {
+						points.push(start);
+					}
+					start
+				}
+			};
+
+			match *cmd {
+				PathCommand::MoveTo(p) => {
+					finish(&mut points);
+					points.push(p);
+					start = p;
+				}
+				PathCommand::LineTo(p) => points.push(p),
+				PathCommand::QuadTo(c, p) => {
+					// Uniform steps keep the chords within |p0 - 2 c + p| / (4 n²) of the curve
+					let dd = norm(current.x - 2.0 * c.x + p.x, current.y - 2.0 * c.y + p.y);
+					let n = steps(dd / 4.0, tolerance);
+					points.extend((1..=n).map(|i| {
+						let t = i as f64 / n as f64;
+						let u = 1.0 - t;
+						Point {
+							x: u * u * current.x + 2.0 * u * t * c.x + t * t * p.x,
+							y: u * u * current.y + 2.0 * u * t * c.y + t * t * p.y,
+						}
+					}));
+				}
+				PathCommand::CubicTo(c1, c2, p) => {
+					// The second derivative is at most 6 times the larger second difference
+					let dd = norm(current.x - 2.0 * c1.x + c2.x, current.y - 2.0 * c1.y + c2.y)
+						.max(norm(c1.x - 2.0 * c2.x + p.x, c1.y - 2.0 * c2.y + p.y));
+					let n = steps(0.75 * dd, tolerance);
+					points.extend((1..=n).map(|i| {
+						let t = i as f64 / n as f64;
+						let u = 1.0 - t;
+						let (a, b, c, d) = (u * u * u, 3.0 * u * u * t, 3.0 * u * t * t, t * t * t);
+						Point {
+							x: a * current.x + b * c1.x + c * c2.x + d * p.x,
+							y: a * current.y + b * c1.y + c * c2.y + d * p.y,
+						}
+					}));
+				}
+				PathCommand::ArcTo {
+					radii,
+					rotation,
+					large_arc,
+					sweep,
+					to,
+				} => flatten_arc(
+					&mut points,
+					current,
+					radii,
+					rotation,
+					large_arc,
+					sweep,
+					to,
+					tolerance,
+				),
+				PathCommand::Close => {
+					finish(&mut points);
+				}
+			}
+		}
+		finish(&mut points);
+
+		polygons
+	}
+
+	/// Winding number of the point around the flattened path, counter clockwise turns counting
+	/// positive.
+	#[cfg(feature = "std")]
+	pub fn winding_number(&self, p: Point<f64>, tolerance: f64) -> i32
+	{
+		self.to_polygons(tolerance)
+			.iter()
+			.map(|poly| winding(p, &poly.points))
+			.sum()
+	}
+
+	/// Checks whether the point lies inside the flattened path under the fill rule. Points on
+	/// the border count as inside.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use ctl::geometry::{FillRule, Path, Point};
+	/// // 2 nested squares running the same way
+	/// let path = Path::parse_svg("M0 0 H4 V4 H0 Z M1 1 H3 V3 H1 Z").unwrap();
+	/// let center = Point { x: 2.0, y: 2.0 };
+	/// let a = path.contains(center, FillRule::NonZero, 0.01); // true
+	/// let b = path.contains(center, FillRule::EvenOdd, 0.01); // false
+	/// ```
+	#[cfg(feature = "std")]
+	pub fn contains(&self, p: Point<f64>, rule: FillRule, tolerance: f64) -> bool
+	{
+		let polygons = self.to_polygons(tolerance);
+		let on_border = polygons.iter().any(|poly| {
+			let pts = &poly.points;
+			(0..pts.len()).any(|i| {
+				Segment {
+					a: pts[i],
+					b: pts[(i + 1) % pts.len()],
+				}
+				.contains(p)
+			})
+		});
+
+		on_border || rule.is_inside(polygons.iter().map(|poly| winding(p, &poly.points)).sum())
+	}
+
+	/// Writes the path as SVG path data using absolute commands only.
+	pub fn to_svg(&self) -> String
+	{
+		use alloc::string::ToString;
+		self.to_string()
+	}
+}
+
+impl FromStr for Path
+{
+	type Err = Error;
+
+	fn from_str(s: &str) -> Result<Self>
+	{
+		Path::parse_svg(s)
+	}
+}
+
+impl fmt::Display for Path
+{
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+	{
+		for (i, cmd) in self.commands.iter().enumerate() {
+			if i > 0 {
+				f.write_str(" ")?;
+			}
+
+			match *cmd {
+				PathCommand::MoveTo(p) => write!(f, "M{} {}", p.x, p.y)?,
+				PathCommand::LineTo(p) => write!(f, "L{} {}", p.x, p.y)?,
+				PathCommand::QuadTo(c, p) => write!(f, "Q{} {} {} {}", c.x, c.y, p.x, p.y)?,
+				PathCommand::CubicTo(c1, c2, p) => {
+					write!(f, "C{} {} {} {} {} {}", c1.x, c1.y, c2.x, c2.y, p.x, p.y)?
+				}
+				PathCommand::ArcTo {
+					radii,
+					rotation,
+					large_arc,
+					sweep,
+					to,
+				} => write!(
+					f,
+					"A{} {} {} {} {} {} {}",
+					radii.x, radii.y, rotation, large_arc as u8, sweep as u8, to.x, to.y
+				)?,
+				PathCommand::Close => f.write_str("Z")?,
+			}
+		}
+
+		Ok(())
+	}
+}
+
+// -----------------------------------------------------------------------------
+// Flattening
+// -----------------------------------------------------------------------------
+
+#[cfg(feature = "std")]
+fn norm(x: f64, y: f64) -> f64
+{
+	(x * x + y * y).sqrt()
+}
+
+/// Number of uniform steps keeping the error `bound / n²` within the tolerance.
+#[cfg(feature = "std")]
+fn steps(bound: f64, tolerance: f64) -> usize
+{
+	((bound / tolerance).sqrt().ceil() as usize).max(1)
+}
+
+/// Angle turning from u to v.
+#[cfg(feature = "std")]
+fn angle_between(u: (f64, f64), v: (f64, f64)) -> f64
+{
+	(u.0 * v.1 - u.1 * v.0).atan2(u.0 * v.0 + u.1 * v.1)
+}
+
+/// Center parameterization of a SVG arc.
+#[cfg(feature = "std")]
+struct Ellipse
+{
+	center: Point<f64>,
+	rx: f64,
+	ry: f64,
+	/// Sine and cosine of the rotation
+	sin: f64,
+	cos: f64,
+	start: f64,
+	delta: f64,
+}
+
+#[cfg(feature = "std")]
+impl Ellipse
+{
+	/// Converts the end point form, scaling up radii too small to reach the end point. None for
+	/// an empty arc or zero radii, which draw nothing or a line.
+	fn new(
+		from: Point<f64>,
+		radii: Point<f64>,
+		rotation: f64,
+		large_arc: bool,
+		sweep: bool,
+		to: Point<f64>,
+	) -> Option<Self>
+	{
+		let (mut rx, mut ry) = (radii.x.abs(), radii.y.abs());
+		if from == to || rx == 0.0 || ry == 0.0 {
+			return None;
+		}
+
+		// Half the chord in the frame of the ellipse
+		let (sin, cos) = rotation.to_radians().sin_cos();
+		let (dx, dy) = ((from.x - to.x) / 2.0, (from.y - to.y) / 2.0);
+		let (x1, y1) = (cos * dx + sin * dy, -sin * dx + cos * dy);
+
+		let lambda = (x1 * x1) / (rx * rx) + (y1 * y1) / (ry * ry);
+		if lambda > 1.0 {
+			rx *= lambda.sqrt();
+			ry *= lambda.sqrt();
+		}
+
+		let num = rx * rx * ry * ry - rx * rx * y1 * y1 - ry * ry * x1 * x1;
+		let den = rx * rx * y1 * y1 + ry * ry * x1 * x1;
+		let mut k = (num / den).max(0.0).sqrt();
+		if large_arc == sweep {
+			k = -k;
+		}
+		let (cx1, cy1) = (k * rx * y1 / ry, -k * ry * x1 / rx);
+		let center = Point {
+			x: cos * cx1 - sin * cy1 + (from.x + to.x) / 2.0,
+			y: sin * cx1 + cos * cy1 + (from.y + to.y) / 2.0,
+		};
+
+		let u = ((x1 - cx1) / rx, (y1 - cy1) / ry);
+		let start = angle_between((1.0, 0.0), u);
+		let mut delta = angle_between(u, ((-x1 - cx1) / rx, (-y1 - cy1) / ry));
+		if sweep && delta < 0.0 {
+			delta += TAU;
+		} else if !sweep && delta > 0.0 {
+			delta -= TAU;
+		}
+
+		Some(Ellipse {
+			center,
+			rx,
+			ry,
+			sin,
+			cos,
+			start,
+			delta,
+		})
+	}
+
+	fn at(&self, t: f64) -> Point<f64>
+	{
+		let (x, y) = (self.rx * t.cos(), self.ry * t.sin());
+		Point {
+			x: self.center.x + self.cos * x - self.sin * y,
+			y: self.center.y + self.sin * x + self.cos * y,
+		}
+	}
+
+	/// Checks whether the angle lies on the arc.
+	fn covers(&self, t: f64) -> bool
+	{
+		if self.delta >= 0.0 {
+			(t - self.start).rem_euclid(TAU) <= self.delta
+		} else {
+			(self.start - t).rem_euclid(TAU) <= -self.delta
+		}
+	}
+}
+
+/// Appends the points of a SVG arc after `from`. Zero radii give a line.
+#[cfg(feature = "std")]
+#[allow(clippy::too_many_arguments)]
+fn flatten_arc(
+	out: &mut Vec<Point<f64>>,
+	from: Point<f64>,
+	radii: Point<f64>,
+	rotation: f64,
+	large_arc: bool,
+	sweep: bool,
+	to: Point<f64>,
+	tolerance: f64,
+)
+{
+	let e = match Ellipse::new(from, radii, rotation, large_arc, sweep, to) {
+		Some(e) => e,
+		None => {
+			if from != to {
+				out.push(to);
+			}
+			return;
+		}
+	};
+
+	// Same step as for circular arcs, using the larger radius
+	let r = e.rx.max(e.ry);
+	let step = if tolerance < r {
+		2.0 * (1.0 - tolerance / r).acos()
+	} else {
+		PI
+	};
+	let n = ((e.delta.abs() / step).ceil() as usize).max(1);
+
+	out.extend((1..n).map(|i| e.at(e.start + e.delta * i as f64 / n as f64)));
+	out.push(to);
+}
+
+/// Winding number of the point around the polygon.
+#[cfg(feature = "std")]
+fn winding(p: Point<f64>, points: &[Point<f64>]) -> i32
+{
+	let mut winding = 0;
+
+	for (i, &a) in points.iter().enumerate() {
+		let b = points[(i + 1) % points.len()];
+
+		if a.y <= p.y {
+			if b.y > p.y && orientation(a, b, p) == Orientation::CounterClockwise {
+				winding += 1;
+			}
+		} else if b.y <= p.y && orientation(a, b, p) == Orientation::Clockwise {
+			winding -= 1;
+		}
+	}
+
+	winding
+}
+
+// -----------------------------------------------------------------------------
+// Bounds
+// -----------------------------------------------------------------------------
+
+/// Parameters in (0, 1) where a t² + b t + c is zero.
+#[cfg(feature = "std")]
+fn unit_roots(a: f64, b: f64, c: f64) -> impl Iterator<Item = f64>
+{
+	let roots = if a.abs() < 1e-12 {
+		[if b != 0.0 { -c / b } else { f64::NAN }, f64::NAN]
+	} else {
+		let disc = b * b - 4.0 * a * c;
+		let root = disc.max(0.0).sqrt();
+		if disc < 0.0 {
+			[f64::NAN; 2]
+		} else {
+			[(-b - root) / (2.0 * a), (-b + root) / (2.0 * a)]
+		}
+	};
+
+	IntoIterator::into_iter(roots).filter(|&t| t > 0.0 && t < 1.0)
+}
+
+#[cfg(feature = "std")]
+impl Bounded<f64> for Path
+{
+	/// Tight bounding box of the end points, the axis extremes of the Bezier curves and those
+	/// lying on the arcs. Drawing before the first move starts at the origin like when
+	/// flattening. Panics for a path without commands.
+	fn aabb(&self) -> Rect<f64>
+	{
+		let mut bounds: Option<Rect<f64>> = None;
+		let mut add = |p: Point<f64>| match &mut bounds {
+			Some(r) => r.expand(p),
+			None => bounds = Some(p.aabb()),
+		};
+		let mut start = Point { x: 0.0, y: 0.0 };
+		let mut current = start;
+
+		for cmd in &self.commands {
+			if !matches!(cmd, PathCommand::MoveTo(_) | PathCommand::Close) {
+				add(current);
+			}
+
+			match *cmd {
+				PathCommand::MoveTo(p) => {
+					add(p);
+					start = p;
+				}
+				PathCommand::LineTo(p) => add(p),
+				PathCommand::QuadTo(c, p) => {
+					// The derivative is linear in t per axis
+					let at = |t: f64| {
+						let u = 1.0 - t;
+						Point {
+							x: u * u * current.x + 2.0 * u * t * c.x + t * t * p.x,
+							y: u * u * current.y + 2.0 * u * t * c.y + t * t * p.y,
+						}
+					};
+					let dd = current - (c + c) + p;
+					unit_roots(0.0, dd.x, c.x - current.x)
+						.chain(unit_roots(0.0, dd.y, c.y - current.y))
+						.for_each(|t| add(at(t)));
+					add(p);
+				}
+				PathCommand::CubicTo(c1, c2, p) => {
+					// The derivative is quadratic in t per axis
+					let at = |t: f64| {
+						let u = 1.0 - t;
+						let (a, b, c, d) = (u * u * u, 3.0 * u * u * t, 3.0 * u * t * t, t * t * t);
+						Point {
+							x: a * current.x + b * c1.x + c * c2.x + d * p.x,
+							y: a * current.y + b * c1.y + c * c2.y + d * p.y,
+						}
+					};
+					let (d, dd) = (c1 - c2, current - (c1 + c1) + c2);
+					let (a, b) = (p - current + d + d + d, dd + dd);
+					let c = c1 - current;
+					unit_roots(a.x, b.x, c.x)
+						.chain(unit_roots(a.y, b.y, c.y))
+						.for_each(|t| add(at(t)));
+					add(p);
+				}
+				PathCommand::ArcTo {
+					radii,
+					rotation,
+					large_arc,
+					sweep,
+					to,
+				} => {
+					if let Some(e) = Ellipse::new(current, radii, rotation, large_arc, sweep, to) {
+						// Angles where the rotated ellipse runs parallel to an axis
+						let tx = (-e.sin * e.ry).atan2(e.cos * e.rx);
+						let ty = (e.cos * e.ry).atan2(e.sin * e.rx);
+						IntoIterator::into_iter([tx, tx + PI, ty, ty + PI])
+							.filter(|&t| e.covers(t))
+							.for_each(|t| add(e.at(t)));
+					}
+					add(to);
+				}
+				PathCommand::Close => (),
+			}
+
+			current = match *cmd {
+				PathCommand::MoveTo(p) | PathCommand::LineTo(p) | PathCommand::QuadTo(_, p) => p,
+				PathCommand::CubicTo(_, _, p) => p,
+				PathCommand::ArcTo { to, .. } => to,
+				PathCommand::Close => start,
+			};
+		}
+
+		bounds.expect("path without commands")
+	}
+}
+
+// -----------------------------------------------------------------------------
+// SVG parsing
+// -----------------------------------------------------------------------------
+
+struct Parser<'a>
+{
+	s: &'a [u8],
+	i: usize,
+}
+
+impl Parser<'_>
+{
+	fn skip_separators(&mut self)
+	{
+		while self.i < self.s.len()
+			&& (self.s[self.i].is_ascii_whitespace() || self.s[self.i] == b',')
+		{
+			self.i += 1;
+		}
+	}
+
+	/// Checks whether another number follows, repeating the last command.
+	fn at_number(&mut self) -> bool
+	{
+		self.skip_separators();
+		self.i < self.s.len() && matches!(self.s[self.i], b'0'..=b'9' | b'-' | b'+' | b'.')
+	}
+
+	fn number(&mut self) -> Result<f64>
+	{
+		self.skip_separators();
+		let start = self.i;
+		let digits = |p: &mut Self| {
+			let from = p.i;
+			while p.i < p.s.len() && p.s[p.i].is_ascii_digit() {
+				p.i += 1;
+			}
+			p.i > from
+		};
+
+		if self.i < self.s.len() && matches!(self.s[self.i], b'-' | b'+') {
+			self.i += 1;
+		}
+		let mut any = digits(self);
+		if self.i < self.s.len() && self.s[self.i] == b'.' {
+			self.i += 1;
+			any |= digits(self);
+		}
+		if !any {
+			return Err(Error::ParseError);
+		}
+
+		// Only an exponent followed by digits belongs to the number
+		if self.i < self.s.len() && matches!(self.s[self.i], b'e' | b'E') {
+			let mark = self.i;
+			self.i += 1;
+			if self.i < self.s.len() && matches!(self.s[self.i], b'-' | b'+') {
+				self.i += 1;
+			}
+			if !digits(self) {
+				self.i = mark;
+			}
+		}
+
+		core::str::from_utf8(&self.s[start..self.i])
+			.ok()
+			.and_then(|n| n.parse().ok())
+			.ok_or(Error::ParseError)
+	}
+
+	/// Arc flags may be written without any separator.
+	fn flag(&mut self) -> Result<bool>
+	{
+		self.skip_separators();
+		let flag = match self.s.get(self.i) {
+			Some(b'0') => false,
+			Some(b'1') => true,
+			_ => return Err(Error::ParseError),
+		};
+		self.i += 1;
+
+		Ok(flag)
+	}
+
+	fn point(&mut self, base: Point<f64>) -> Result<Point<f64>>
+	{
+		let x = self.number()?;
+		let y = self.number()?;
+
+		Ok(Point {
+			x: base.x + x,
+			y: base.y + y,
+		})
+	}
+
+	fn parse(mut self) -> Result<Path>
+	{
+		let mut path = Path::new();
+		let origin = Point { x: 0.0, y: 0.0 };
+		let (mut current, mut start) = (origin, origin);
+		// Last control point of a curve, reflected by the smooth curves
+		let mut last_cubic = None;
+		let mut last_quad = None;
+
+		self.skip_separators();
+		if self.i == self.s.len() {
+			return Ok(path);
+		}
+
+		let mut cmd = 0;
+		loop {
+			self.skip_separators();
+			if self.i == self.s.len() {
+				break;
+			}
+
+			// A missing letter repeats the last command, moves continue as lines
+			if self.s[self.i].is_ascii_alphabetic() {
+				cmd = self.s[self.i];
+				self.i += 1;
+			} else if cmd == 0 || matches!(cmd, b'Z' | b'z') {
+				return Err(Error::ParseError);
+			}
+
+			let base = if cmd.is_ascii_lowercase() {
+				current
+			} else {
+				origin
+			};
+			let (mut cubic, mut quad) = (None, None);
+
+			match cmd.to_ascii_uppercase() {
+				b'M' => {
+					current = self.point(base)?;
+					start = current;
+					path.move_to(current);
+					cmd = if cmd == b'm' { b'l' } else { b'L' };
+				}
+				b'L' => {
+					current = self.point(base)?;
+					path.line_to(current);
+				}
+				b'H' => {
+					current.x = base.x + self.number()?;
+					path.line_to(current);
+				}
+				b'V' => {
+					current.y = base.y + self.number()?;
+					path.line_to(current);
+				}
+				b'C' => {
+					let c1 = self.point(base)?;
+					let c2 = self.point(base)?;
+					current = self.point(base)?;
+					path.cubic_to(c1, c2, current);
+					cubic = Some(c2);
+				}
+				b'S' => {
+					let c1 = reflect(last_cubic, current);
+					let c2 = self.point(base)?;
+					current = self.point(base)?;
+					path.cubic_to(c1, c2, current);
+					cubic = Some(c2);
+				}
+				b'Q' => {
+					let c = self.point(base)?;
+					current = self.point(base)?;
+					path.quad_to(c, current);
+					quad = Some(c);
+				}
+				b'T' => {
+					let c = reflect(last_quad, current);
+					current = self.point(base)?;
+					path.quad_to(c, current);
+					quad = Some(c);
+				}
+				b'A' => {
+					let rx = self.number()?;
+					let ry = self.number()?;
+					let rotation = self.number()?;
+					let large_arc = self.flag()?;
+					let sweep = self.flag()?;
+					current = self.point(base)?;
+					path.arc_to(Point { x: rx, y: ry }, rotation, large_arc, sweep, current);
+				}
+				b'Z' => {
+					current = start;
+					path.close();
+				}
+				_ => return Err(Error::ParseError),
+			}
+
+			// Paths have to start with a move
+			if !matches!(path.commands[0], PathCommand::MoveTo(_)) {
+				return Err(Error::ParseError);
+			}
+
+			last_cubic = cubic;
+			last_quad = quad;
+
+			// Anything but a number after a close needs a new letter
+			if matches!(cmd, b'Z' | b'z') && self.at_number() {
+				return Err(Error::ParseError);
+			}
+		}
+
+		Ok(path)
+	}
+}
+
+/// Mirrors the control point of the previous curve at the current point, or the current point
+/// without such a curve.
+fn reflect(control: Option<Point<f64>>, current: Point<f64>) -> Point<f64>
+{
+	match control {
+		Some(c) => Point {
+			x: 2.0 * current.x - c.x,
+			y: 2.0 * current.y - c.y,
+		},
+		None => current,
+	}
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests
+{
+	use super::*;
+
+	fn p(x: f64, y: f64) -> Point<f64>
+	{
+		Point { x, y }
+	}
+
+	#[test]
+	fn test_parse_svg()
+	{
+		use PathCommand::*;
+
+		let path = Path::parse_svg("m10 10h5v-5z l1,1 H0 V0").unwrap();
+		assert_eq!(
+			path.commands,
+			vec![
+				MoveTo(p(10.0, 10.0)),
+				LineTo(p(15.0, 10.0)),
+				LineTo(p(15.0, 5.0)),
+				Close,
+				LineTo(p(11.0, 11.0)),
+				LineTo(p(0.0, 11.0)),
+				LineTo(p(0.0, 0.0)),
+			]
+		);
+
+		// Implicit lines after a move, packed numbers and smooth curves
+		let path: Path = "M.5.5 1-1.5 C0 1 2 1 2 0 s2-1 2 0 Q5 1 6 0 t2 0"
+			.parse()
+			.unwrap();
+		assert_eq!(
+			path.commands,
+			vec![
+				MoveTo(p(0.5, 0.5)),
+				LineTo(p(1.0, -1.5)),
+				CubicTo(p(0.0, 1.0), p(2.0, 1.0), p(2.0, 0.0)),
+				CubicTo(p(2.0, -1.0), p(4.0, -1.0), p(4.0, 0.0)),
+				QuadTo(p(5.0, 1.0), p(6.0, 0.0)),
+				QuadTo(p(7.0, -1.0), p(8.0, 0.0)),
+			]
+		);
+
+		// Flags without separators and exponents
+		let path = Path::parse_svg("M1e1 0a5 5 30 1010 0").unwrap();
+		assert_eq!(
+			path.commands[1],
+			ArcTo {
+				radii: p(5.0, 5.0),
+				rotation: 30.0,
+				large_arc: true,
+				sweep: false,
+				to: p(20.0, 0.0),
+			}
+		);
+
+		assert_eq!(Path::parse_svg("  ").unwrap(), Path::new());
+		for bad in [
+			"L1 1",
+			"M1",
+			"M0 0 X1 1",
+			"M0 0 A1 1 0 2 0 1 1",
+			"M0 0 Z 1 1",
+			"M0 0 L1e 1",
+		] {
+			assert_eq!(Path::parse_svg(bad), Err(Error::ParseError), "{}", bad);
+		}
+	}
+
+	#[test]
+	fn test_to_svg()
+	{
+		let mut path = Path::new();
+		path.move_to(p(0.0, 0.5));
+		path.cubic_to(p(1.0, 2.0), p(3.0, 4.0), p(-5.0, 6.25));
+		path.arc_to(p(2.0, 1.0), 45.0, false, true, p(1.0, 0.0));
+		path.close();
+
+		let d = path.to_svg();
+		assert_eq!(d, "M0 0.5 C1 2 3 4 -5 6.25 A2 1 45 0 1 1 0 Z");
+		assert_eq!(Path::parse_svg(&d).unwrap(), path);
+	}
+
+	#[test]
+	#[cfg(feature = "std")]
+	fn test_fill_rules()
+	{
+		// Same and opposite running inner squares
+		let same = Path::parse_svg("M0 0 H4 V4 H0 Z M1 1 H3 V3 H1 Z").unwrap();
+		let opposite = Path::parse_svg("M0 0 H4 V4 H0 Z M1 1 V3 H3 V1 Z").unwrap();
+		let (center, ring) = (p(2.0, 2.0), p(0.5, 2.0));
+
+		assert_eq!(same.winding_number(center, 0.1), 2);
+		assert_eq!(opposite.winding_number(center, 0.1), 0);
+		assert!(same.contains(center, FillRule::NonZero, 0.1));
+		assert!(!same.contains(center, FillRule::EvenOdd, 0.1));
+		assert!(!opposite.contains(center, FillRule::NonZero, 0.1));
+		for path in [&same, &opposite] {
+			for rule in [FillRule::NonZero, FillRule::EvenOdd] {
+				assert!(path.contains(ring, rule, 0.1));
+				assert!(path.contains(p(4.0, 1.0), rule, 0.1));
+				assert!(!path.contains(p(5.0, 2.0), rule, 0.1));
+			}
+		}
+	}
+
+	#[test]
+	#[cfg(feature = "std")]
+	fn test_to_polygons()
+	{
+		use core::f64::consts::PI;
+
+		// Parabola segment of area 2/3 base height
+		let quad = Path::parse_svg("M0 0 Q1 2 2 0 Z")
+			.unwrap()
+			.to_polygons(1e-4);
+		assert_eq!(quad.len(), 1);
+		assert!((quad[0].area() - 4.0 / 3.0).abs() < 1e-3);
+
+		// Cubic with the area 3/5 below it, the implicit close and a lone move
+		let cubic = Path::parse_svg("M5 5 M0 0 C0 1 1 1 1 0")
+			.unwrap()
+			.to_polygons(1e-4);
+		assert_eq!(cubic.len(), 1);
+		assert!((cubic[0].area() - 0.6).abs() < 1e-3);
+
+		// Circle of 2 half arcs, and a rotated ellipse whose radii need scaling
+		let circle = Path::parse_svg("M-2 0 A2 2 0 0 1 2 0 A2 2 0 0 1 -2 0 Z").unwrap();
+		let poly = circle.to_polygons(1e-4);
+		assert!((poly[0].area() - 4.0 * PI).abs() < 1e-2);
+		assert_eq!(circle.winding_number(p(0.0, 0.0), 1e-4), 1);
+
+		let ellipse = Path::parse_svg("M0 0 A1 0.5 45 1 1 2 2 A1 0.5 45 1 1 0 0").unwrap();
+		let poly = ellipse.to_polygons(1e-4);
+		assert!((poly[0].area() - 2.0 * PI * 0.5).abs() < 1e-2);
+		assert!(ellipse.contains(p(1.0, 1.0), FillRule::NonZero, 1e-4));
+		assert!(!ellipse.contains(p(1.6, 0.4), FillRule::NonZero, 1e-4));
+
+		// Drawing on after a close starts at the closed subpath
+		let path = Path::parse_svg("M0 0 H1 V1 Z L-1 0 V-1").unwrap();
+		let polys = path.to_polygons(0.1);
+		assert_eq!(polys.len(), 2);
+		assert_eq!(
+			polys[1].points,
+			vec![p(0.0, 0.0), p(-1.0, 0.0), p(-1.0, -1.0)]
+		);
+	}
+
+	#[cfg(feature = "std")]
+	#[test]
+	fn test_aabb()
+	{
+		let bounds = |d: &str| Path::parse_svg(d).unwrap().aabb();
+
+		// Curve extremes instead of the control points
+		assert_eq!(
+			bounds("M0 0 C0 3 3 3 3 0"),
+			Rect::from_corners(p(0.0, 0.0), p(3.0, 2.25))
+		);
+		assert_eq!(
+			bounds("M1 1 L3 -1 Z"),
+			Rect::from_corners(p(1.0, -1.0), p(3.0, 1.0))
+		);
+
+		// Drawing without a move starts at the origin
+		let quad = Path {
+			commands: vec![PathCommand::QuadTo(p(1.0, 2.0), p(2.0, 0.0))],
+		};
+		assert_eq!(quad.aabb(), Rect::from_corners(p(0.0, 0.0), p(2.0, 1.0)));
+
+		// Half circle bulging downwards
+		let r = bounds("M0 0 A1 1 0 0 1 2 0");
+		assert!(r.min.x.abs() < 1e-12 && (r.min.y + 1.0).abs() < 1e-12);
+		assert!((r.max.x - 2.0).abs() < 1e-12 && r.max.y.abs() < 1e-12);
+
+		// Rotated arcs and skewed curves agree with the densely flattened path
+		for d in &[
+			"M0 0 A3 1 30 1 0 4 2 C6 -3 -2 5 1 1",
+			"M2 0 A1 2 -70 0 0 0 1 Q-4 3 0 0 Z",
+		] {
+			let path = Path::parse_svg(d).unwrap();
+			let (r, flat) = (path.aabb(), path.to_polygons(1e-7)[0].aabb());
+			assert!((flat.min.x - r.min.x).abs() < 1e-5 && (flat.min.y - r.min.y).abs() < 1e-5);
+			assert!((flat.max.x - r.max.x).abs() < 1e-5 && (flat.max.y - r.max.y).abs() < 1e-5);
+		}
+	}
+}
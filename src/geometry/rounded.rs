@@ -0,0 +1,339 @@
+#[cfg(feature = "std")]
+use super::{Arc, Polygon};
+use super::{Bounded, Point, Rect};
+#[cfg(feature = "std")]
+use crate::units::Quantity;
+#[cfg(feature = "std")]
+use alloc::vec::Vec;
+use core::f64::consts::PI;
+#[cfg(feature = "std")]
+use core::f64::consts::{FRAC_PI_2, TAU};
+
+// -----------------------------------------------------------------------------
+// Rounded rectangle
+// -----------------------------------------------------------------------------
+
+/// Axis aligned rectangle whose corners are rounded by quarter circles of the same radius. The
+/// radius is limited to half of the shorter side, which turns the shape into a stadium.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct RoundedRect
+{
+	pub rect: Rect<f64>,
+	pub radius: f64,
+}
+
+impl RoundedRect
+{
+	/// Corner radius after limiting it to the size of the rectangle.
+	pub fn corner_radius(&self) -> f64
+	{
+		let half = self.rect.width().min(self.rect.height()) / 2.0;
+		self.radius.clamp(0.0, half.max(0.0))
+	}
+
+	/// Checks whether the point lies inside or on the border.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use ctl::geometry::{Point, Rect, RoundedRect};
+	/// let r = RoundedRect {
+	///     rect: Rect::from_corners(Point { x: 0.0, y: 0.0 }, Point { x: 4.0, y: 2.0 }),
+	///     radius: 1.0,
+	/// };
+	/// let a = r.contains(Point { x: 2.0, y: 0.0 }); // true
+	/// let b = r.contains(Point { x: 0.1, y: 0.1 }); // false, cut off corner
+	/// ```
+	pub fn contains(&self, p: Point<f64>) -> bool
+	{
+		if !self.rect.contains(p) {
+			return false;
+		}
+
+		// Distance to the rectangle shrunk by the radius
+		let r = self.corner_radius();
+		let dx = (self.rect.min.x + r - p.x)
+			.max(p.x - self.rect.max.x + r)
+			.max(0.0);
+		let dy = (self.rect.min.y + r - p.y)
+			.max(p.y - self.rect.max.y + r)
+			.max(0.0);
+
+		dx * dx + dy * dy <= r * r
+	}
+
+	pub fn area(&self) -> f64
+	{
+		let r = self.corner_radius();
+		self.rect.width() * self.rect.height() - (4.0 - PI) * r * r
+	}
+
+	pub fn perimeter(&self) -> f64
+	{
+		let r = self.corner_radius();
+		2.0 * (self.rect.width() + self.rect.height()) - (8.0 - 2.0 * PI) * r
+	}
+
+	/// Approximates the shape by a counter clockwise polygon, flattening the corners like
+	/// [`Arc::flatten`] so the border stays within `tolerance`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use ctl::geometry::{Point, Rect, RoundedRect};
+	/// let r = RoundedRect {
+	///     rect: Rect::from_corners(Point { x: 0.0, y: 0.0 }, Point { x: 4.0, y: 2.0 }),
+	///     radius: 0.5,
+	/// };
+	/// let poly = r.to_polygon(0.01);
+	/// ```
+	#[cfg(feature = "std")]
+	pub fn to_polygon(&self, tolerance: f64) -> Polygon
+	{
+		let (min, max) = (self.rect.min, self.rect.max);
+		let r = self.corner_radius();
+
+		if r == 0.0 {
+			return Polygon {
+				points: vec![
+					min,
+					Point { x: max.x, y: min.y },
+					max,
+					Point { x: min.x, y: max.y },
+				],
+			};
+		}
+
+		let centers = [
+			Point {
+				x: max.x - r,
+				y: min.y + r,
+			},
+			Point {
+				x: max.x - r,
+				y: max.y - r,
+			},
+			Point {
+				x: min.x + r,
+				y: max.y - r,
+			},
+			Point {
+				x: min.x + r,
+				y: min.y + r,
+			},
+		];
+
+		let mut points = Vec::new();
+		for (i, &center) in centers.iter().enumerate() {
+			let start = -FRAC_PI_2 + i as f64 * FRAC_PI_2;
+			let arc = Arc {
+				center,
+				radius: r,
+				start: Quantity::new(start),
+				end: Quantity::new(start + FRAC_PI_2),
+			};
+			points.extend(arc.flatten(tolerance));
+		}
+
+		// Straight sides of zero length leave duplicate corners behind
+		points.dedup_by(|a, b| (a.x - b.x).abs() < 1e-12 && (a.y - b.y).abs() < 1e-12);
+		if points.len() > 1 && points[0] == points[points.len() - 1] {
+			points.pop();
+		}
+
+		Polygon { points }
+	}
+}
+
+impl Bounded<f64> for RoundedRect
+{
+	fn aabb(&self) -> Rect<f64>
+	{
+		self.rect
+	}
+}
+
+// -----------------------------------------------------------------------------
+// Superellipse
+// -----------------------------------------------------------------------------
+
+/// Lamé curve |x / a|^n + |y / b|^n <= 1 around a center. An exponent of 2 gives an ellipse,
+/// larger ones approach the rectangle with the half sizes a and b, a popular squircle being
+/// n = 4.
+#[cfg(feature = "std")]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Superellipse
+{
+	pub center: Point<f64>,
+	/// Half width
+	pub a: f64,
+	/// Half height
+	pub b: f64,
+	/// Exponent, positive
+	pub n: f64,
+}
+
+#[cfg(feature = "std")]
+impl Superellipse
+{
+	/// Checks whether the point lies inside or on the border.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use ctl::geometry::{Point, Superellipse};
+	/// let s = Superellipse { center: Point { x: 0.0, y: 0.0 }, a: 2.0, b: 1.0, n: 4.0 };
+	/// let a = s.contains(Point { x: 1.5, y: 0.8 }); // true
+	/// let b = s.contains(Point { x: 1.9, y: 0.9 }); // false
+	/// ```
+	pub fn contains(&self, p: Point<f64>) -> bool
+	{
+		let u = ((p.x - self.center.x) / self.a).abs().powf(self.n);
+		let v = ((p.y - self.center.y) / self.b).abs().powf(self.n);
+
+		u + v <= 1.0
+	}
+
+	/// Exact area 4ab Γ(1 + 1/n)² / Γ(1 + 2/n).
+	pub fn area(&self) -> f64
+	{
+		use crate::math::gamma;
+
+		let g = gamma(1.0 + 1.0 / self.n);
+		4.0 * self.a * self.b * g * g / gamma(1.0 + 2.0 / self.n)
+	}
+
+	/// Perimeter measured along a polygon of 4096 points, the relative error is below 1e-6 for
+	/// exponents between 1 and 10.
+	pub fn perimeter(&self) -> f64
+	{
+		let points = self.to_polygon(4096).points;
+
+		points
+			.iter()
+			.zip(points.iter().cycle().skip(1))
+			.map(|(p, q)| ((q.x - p.x).powi(2) + (q.y - p.y).powi(2)).sqrt())
+			.sum()
+	}
+
+	/// Samples the border at `segments` evenly spaced parameters counter clockwise, starting at
+	/// the right.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use ctl::geometry::{Point, Superellipse};
+	/// let s = Superellipse { center: Point { x: 0.0, y: 0.0 }, a: 2.0, b: 1.0, n: 4.0 };
+	/// let poly = s.to_polygon(64);
+	/// ```
+	pub fn to_polygon(&self, segments: usize) -> Polygon
+	{
+		let e = 2.0 / self.n;
+		let f = |c: f64| c.signum() * c.abs().powf(e);
+
+		let points = (0..segments)
+			.map(|i| {
+				let t = TAU * i as f64 / segments as f64;
+				Point {
+					x: self.center.x + self.a * f(t.cos()),
+					y: self.center.y + self.b * f(t.sin()),
+				}
+			})
+			.collect();
+
+		Polygon { points }
+	}
+}
+
+#[cfg(feature = "std")]
+impl Bounded<f64> for Superellipse
+{
+	fn aabb(&self) -> Rect<f64>
+	{
+		let h = Point {
+			x: self.a,
+			y: self.b,
+		};
+		Rect {
+			min: self.center - h,
+			max: self.center + h,
+		}
+	}
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests
+{
+	use super::*;
+
+	fn p(x: f64, y: f64) -> Point<f64>
+	{
+		Point { x, y }
+	}
+
+	#[test]
+	fn test_rounded_rect()
+	{
+		let r = RoundedRect {
+			rect: Rect::from_corners(p(1.0, 1.0), p(5.0, 3.0)),
+			radius: 0.5,
+		};
+
+		assert!(r.contains(p(3.0, 1.0)) && r.contains(p(1.0, 2.0)));
+		assert!(r.contains(p(1.2, 1.2)) && !r.contains(p(1.1, 1.1)));
+		assert!(!r.contains(p(0.9, 2.0)));
+		assert!((r.area() - (8.0 - (4.0 - PI) / 4.0)).abs() < 1e-12);
+		assert!((r.perimeter() - (12.0 - 4.0 + PI)).abs() < 1e-12);
+
+		// Radius limited to a stadium, and a plain rectangle
+		let stadium = RoundedRect { radius: 5.0, ..r };
+		assert_eq!(stadium.corner_radius(), 1.0);
+		assert!((stadium.area() - (4.0 + PI)).abs() < 1e-12);
+
+		#[cfg(feature = "std")]
+		{
+			let poly = r.to_polygon(1e-4);
+			assert!((poly.area() - r.area()).abs() < 1e-3);
+			assert!((stadium.to_polygon(1e-4).area() - stadium.area()).abs() < 1e-3);
+			assert_eq!(
+				RoundedRect { radius: 0.0, ..r }
+					.to_polygon(0.1)
+					.points
+					.len(),
+				4
+			);
+		}
+	}
+
+	#[cfg(feature = "std")]
+	#[test]
+	fn test_superellipse()
+	{
+		let ellipse = Superellipse {
+			center: p(1.0, -1.0),
+			a: 3.0,
+			b: 2.0,
+			n: 2.0,
+		};
+		assert!((ellipse.area() - 6.0 * PI).abs() < 1e-9);
+		assert!(ellipse.contains(p(4.0, -1.0)) && !ellipse.contains(p(3.5, 0.5)));
+
+		// Ramanujan's approximation is exact to about 1e-10 here
+		let h: f64 = (3.0 - 2.0) * (3.0 - 2.0) / 25.0;
+		let ramanujan = PI * 5.0 * (1.0 + 3.0 * h / (10.0 + (4.0 - 3.0 * h).sqrt()));
+		assert!((ellipse.perimeter() / ramanujan - 1.0).abs() < 1e-6);
+
+		let diamond = Superellipse { n: 1.0, ..ellipse };
+		assert!((diamond.area() - 12.0).abs() < 1e-9);
+		assert!((diamond.perimeter() - 4.0 * 13.0f64.sqrt()).abs() < 1e-9);
+
+		let squircle = Superellipse { n: 4.0, ..ellipse };
+		let poly = squircle.to_polygon(2048);
+		assert!((poly.area() / squircle.area() - 1.0).abs() < 1e-4);
+		assert!(squircle.contains(p(3.5, 0.2)) && !squircle.contains(p(3.9, 0.7)));
+	}
+}
@@ -0,0 +1,369 @@
+use super::{orientation, point_in_polygon, Bounded, Orientation, Point, Rect, Segment};
+use alloc::vec::Vec;
+
+// -----------------------------------------------------------------------------
+// Shapes
+// -----------------------------------------------------------------------------
+
+/// Circle given by its center and radius.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Circle
+{
+	pub center: Point<f64>,
+	pub radius: f64,
+}
+
+/// Triangle given by its 3 corners in any order.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Triangle
+{
+	pub a: Point<f64>,
+	pub b: Point<f64>,
+	pub c: Point<f64>,
+}
+
+/// Simple polygon given by its corners in order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Polygon
+{
+	pub points: Vec<Point<f64>>,
+}
+
+fn dist2(a: Point<f64>, b: Point<f64>) -> f64
+{
+	let d = a - b;
+	d.x * d.x + d.y * d.y
+}
+
+/// Squared distance between a point and the closest point of a segment.
+fn segment_dist2(s: Segment<f64>, p: Point<f64>) -> f64
+{
+	let (ab, ap) = (s.b - s.a, p - s.a);
+	let len2 = ab.x * ab.x + ab.y * ab.y;
+
+	if len2 == 0.0 {
+		return dist2(s.a, p);
+	}
+
+	let t = ((ap.x * ab.x + ap.y * ab.y) / len2).clamp(0.0, 1.0);
+	dist2(
+		Point {
+			x: s.a.x + t * ab.x,
+			y: s.a.y + t * ab.y,
+		},
+		p,
+	)
+}
+
+fn edges(points: &[Point<f64>]) -> impl Iterator<Item = Segment<f64>> + '_
+{
+	(0..points.len()).map(move |i| Segment {
+		a: points[i],
+		b: points[(i + 1) % points.len()],
+	})
+}
+
+fn points_aabb(points: &[Point<f64>]) -> Rect<f64>
+{
+	let mut r = points[0].aabb();
+	points[1..].iter().for_each(|&p| r.expand(p));
+	r
+}
+
+impl Circle
+{
+	pub fn contains(&self, p: Point<f64>) -> bool
+	{
+		dist2(self.center, p) <= self.radius * self.radius
+	}
+
+	pub fn area(&self) -> f64
+	{
+		core::f64::consts::PI * self.radius * self.radius
+	}
+}
+
+impl Triangle
+{
+	/// Checks whether the point lies inside or on the border of the triangle. A triangle without
+	/// area only contains the points of its sides.
+	pub fn contains(&self, p: Point<f64>) -> bool
+	{
+		if orientation(self.a, self.b, self.c) == Orientation::Collinear {
+			return [(self.a, self.b), (self.b, self.c), (self.c, self.a)]
+				.iter()
+				.any(|&(a, b)| Segment { a, b }.contains(p));
+		}
+
+		let o = [
+			orientation(self.a, self.b, p),
+			orientation(self.b, self.c, p),
+			orientation(self.c, self.a, p),
+		];
+
+		!(o.contains(&Orientation::Clockwise) && o.contains(&Orientation::CounterClockwise))
+	}
+
+	pub fn area(&self) -> f64
+	{
+		let (ab, ac) = (self.b - self.a, self.c - self.a);
+		(ab.x * ac.y - ab.y * ac.x).abs() / 2.0
+	}
+}
+
+impl Polygon
+{
+	/// Checks whether the point lies inside or on the border of the polygon.
+	pub fn contains(&self, p: Point<f64>) -> bool
+	{
+		point_in_polygon(p, &self.points)
+	}
+
+	/// Area of the polygon using the shoelace formula.
+	pub fn area(&self) -> f64
+	{
+		edges(&self.points)
+			.map(|e| e.a.x * e.b.y - e.b.x * e.a.y)
+			.sum::<f64>()
+			.abs() / 2.0
+	}
+}
+
+impl Bounded<f64> for Circle
+{
+	fn aabb(&self) -> Rect<f64>
+	{
+		let r = Point {
+			x: self.radius,
+			y: self.radius,
+		};
+
+		Rect {
+			min: self.center - r,
+			max: self.center + r,
+		}
+	}
+}
+
+impl Bounded<f64> for Triangle
+{
+	fn aabb(&self) -> Rect<f64>
+	{
+		points_aabb(&[self.a, self.b, self.c])
+	}
+}
+
+impl Bounded<f64> for Polygon
+{
+	/// Bounding box of the corners. Panics for a polygon without corners.
+	fn aabb(&self) -> Rect<f64>
+	{
+		points_aabb(&self.points)
+	}
+}
+
+// -----------------------------------------------------------------------------
+// Shape2D
+// -----------------------------------------------------------------------------
+
+/// Any of the 2D shapes, so mixed shapes can be stored in one collection.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::geometry::{Circle, Point, Rect, Shape2D};
+/// let p = |x, y| Point { x, y };
+/// let shapes = vec![
+///     Shape2D::Rect(Rect::from_corners(p(0.0, 0.0), p(2.0, 1.0))),
+///     Shape2D::Circle(Circle { center: p(5.0, 5.0), radius: 1.0 }),
+/// ];
+/// let hit = shapes.iter().filter(|s| s.contains(p(1.0, 0.5))).count(); // 1
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum Shape2D
+{
+	Rect(Rect<f64>),
+	Circle(Circle),
+	Triangle(Triangle),
+	Polygon(Polygon),
+}
+
+impl Shape2D
+{
+	/// Checks whether the point lies inside or on the border of the shape.
+	pub fn contains(&self, p: Point<f64>) -> bool
+	{
+		match self {
+			Shape2D::Rect(r) => r.contains(p),
+			Shape2D::Circle(c) => c.contains(p),
+			Shape2D::Triangle(t) => t.contains(p),
+			Shape2D::Polygon(poly) => poly.contains(p),
+		}
+	}
+
+	pub fn area(&self) -> f64
+	{
+		match self {
+			Shape2D::Rect(r) => r.width() * r.height(),
+			Shape2D::Circle(c) => c.area(),
+			Shape2D::Triangle(t) => t.area(),
+			Shape2D::Polygon(poly) => poly.area(),
+		}
+	}
+
+	/// Moves the shape by `d`.
+	pub fn translate(&mut self, d: Point<f64>)
+	{
+		match self {
+			Shape2D::Rect(r) => {
+				r.min = r.min + d;
+				r.max = r.max + d;
+			}
+			Shape2D::Circle(c) => c.center = c.center + d,
+			Shape2D::Triangle(t) => {
+				t.a = t.a + d;
+				t.b = t.b + d;
+				t.c = t.c + d;
+			}
+			Shape2D::Polygon(poly) => poly.points.iter_mut().for_each(|p| *p = *p + d),
+		}
+	}
+
+	/// Corners of the polygonal shapes, `None` for circles.
+	fn outline(&self) -> Option<Vec<Point<f64>>>
+	{
+		match self {
+			Shape2D::Rect(r) => Some(vec![
+				r.min,
+				Point {
+					x: r.max.x,
+					y: r.min.y,
+				},
+				r.max,
+				Point {
+					x: r.min.x,
+					y: r.max.y,
+				},
+			]),
+			Shape2D::Circle(_) => None,
+			Shape2D::Triangle(t) => Some(vec![t.a, t.b, t.c]),
+			Shape2D::Polygon(poly) => Some(poly.points.clone()),
+		}
+	}
+
+	/// Checks whether the shapes overlap or touch.
+	pub fn intersects(&self, other: &Shape2D) -> bool
+	{
+		if !self.aabb().intersects(&other.aabb()) {
+			return false;
+		}
+
+		match (self, other) {
+			(Shape2D::Circle(a), Shape2D::Circle(b)) => {
+				let r = a.radius + b.radius;
+				dist2(a.center, b.center) <= r * r
+			}
+			(Shape2D::Circle(c), s) | (s, Shape2D::Circle(c)) => {
+				let poly = s.outline().unwrap();
+
+				point_in_polygon(c.center, &poly)
+					|| edges(&poly).any(|e| segment_dist2(e, c.center) <= c.radius * c.radius)
+			}
+			(a, b) => {
+				let (a, b) = (a.outline().unwrap(), b.outline().unwrap());
+
+				edges(&a).any(|ea| edges(&b).any(|eb| ea.intersects(&eb)))
+					|| point_in_polygon(a[0], &b)
+					|| point_in_polygon(b[0], &a)
+			}
+		}
+	}
+}
+
+impl Bounded<f64> for Shape2D
+{
+	fn aabb(&self) -> Rect<f64>
+	{
+		match self {
+			Shape2D::Rect(r) => *r,
+			Shape2D::Circle(c) => c.aabb(),
+			Shape2D::Triangle(t) => t.aabb(),
+			Shape2D::Polygon(poly) => poly.aabb(),
+		}
+	}
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests
+{
+	use super::*;
+
+	fn p(x: f64, y: f64) -> Point<f64>
+	{
+		Point { x, y }
+	}
+
+	#[test]
+	fn test_shapes()
+	{
+		let rect = Shape2D::Rect(Rect::from_corners(p(0.0, 0.0), p(4.0, 2.0)));
+		let circle = Shape2D::Circle(Circle {
+			center: p(6.0, 1.0),
+			radius: 2.0,
+		});
+		let tri = Shape2D::Triangle(Triangle {
+			a: p(0.0, 3.0),
+			b: p(2.0, 3.0),
+			c: p(0.0, 5.0),
+		});
+		let poly = Shape2D::Polygon(Polygon {
+			points: vec![p(10.0, 0.0), p(12.0, 0.0), p(12.0, 2.0), p(10.0, 2.0)],
+		});
+
+		assert_eq!(rect.area(), 8.0);
+		assert_eq!(tri.area(), 2.0);
+		assert_eq!(poly.area(), 4.0);
+		assert!((circle.area() - 4.0 * core::f64::consts::PI).abs() < 1e-12);
+
+		assert!(rect.contains(p(4.0, 1.0)) && !rect.contains(p(4.1, 1.0)));
+		assert!(tri.contains(p(1.0, 4.0)) && !tri.contains(p(1.5, 4.5)));
+
+		// Degenerate triangles contain their sides only, not the rest of the line
+		let flat = Triangle {
+			a: p(0.0, 0.0),
+			b: p(1.0, 1.0),
+			c: p(3.0, 3.0),
+		};
+		assert!(flat.contains(p(2.0, 2.0)) && flat.contains(p(0.0, 0.0)));
+		assert!(!flat.contains(p(4.0, 4.0)) && !flat.contains(p(-1.0, -1.0)));
+		assert!(!flat.contains(p(1.0, 2.0)));
+		let point = Triangle {
+			a: p(1.0, 1.0),
+			b: p(1.0, 1.0),
+			c: p(1.0, 1.0),
+		};
+		assert!(point.contains(p(1.0, 1.0)) && !point.contains(p(2.0, 2.0)));
+		assert_eq!(circle.aabb(), Rect::from_corners(p(4.0, -1.0), p(8.0, 3.0)));
+
+		assert!(rect.intersects(&circle) && circle.intersects(&rect));
+		assert!(!rect.intersects(&tri));
+		assert!(!circle.intersects(&poly));
+
+		// Containment without crossing edges
+		let inner = Shape2D::Triangle(Triangle {
+			a: p(1.0, 0.5),
+			b: p(2.0, 0.5),
+			c: p(1.0, 1.5),
+		});
+		assert!(rect.intersects(&inner) && inner.intersects(&rect));
+
+		let mut moved = poly.clone();
+		moved.translate(p(-5.0, 0.0));
+		assert!(moved.intersects(&circle));
+		assert_eq!(moved.aabb(), Rect::from_corners(p(5.0, 0.0), p(7.0, 2.0)));
+	}
+}
@@ -0,0 +1,797 @@
+use super::{point_in_polygon, HalfEdgeMesh, Point, Polygon, Segment};
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+// -----------------------------------------------------------------------------
+// Wavefront
+// -----------------------------------------------------------------------------
+
+fn dot(a: Point<f64>, b: Point<f64>) -> f64
+{
+	a.x * b.x + a.y * b.y
+}
+
+fn cross(a: Point<f64>, b: Point<f64>) -> f64
+{
+	a.x * b.y - a.y * b.x
+}
+
+/// a + v t
+fn along(a: Point<f64>, v: Point<f64>, t: f64) -> Point<f64>
+{
+	Point {
+		x: a.x + v.x * t,
+		y: a.y + v.y * t,
+	}
+}
+
+/// Normal pointing to the left of the direction, into a counter clockwise polygon.
+fn normal(d: Point<f64>) -> Point<f64>
+{
+	Point { x: -d.y, y: d.x }
+}
+
+/// Corner of a wavefront ring.
+#[derive(Debug, Copy, Clone)]
+struct Vertex
+{
+	pos: Point<f64>,
+	/// Skeleton node the corner started from
+	node: usize,
+	/// Unit direction of the edge to the next corner
+	dir: Point<f64>,
+}
+
+enum Event
+{
+	/// The edge from corner i shrinks to a point
+	Edge
+	{
+		ring: usize, i: usize
+	},
+	/// The reflex corner i runs into the edge from corner a
+	Split
+	{
+		ring: usize, i: usize, a: usize
+	},
+}
+
+/// Counter clockwise rings of the polygon shrinking at unit speed, recording the paths of their
+/// corners as the skeleton.
+struct Wavefront
+{
+	rings: Vec<Vec<Vertex>>,
+	time: f64,
+	nodes: Vec<Point<f64>>,
+	heights: Vec<f64>,
+	arcs: Vec<[usize; 2]>,
+	eps: f64,
+}
+
+impl Wavefront
+{
+	fn new(points: &[Point<f64>], ccw: bool) -> Self
+	{
+		let n = points.len();
+		let order: Vec<usize> = if ccw {
+			(0..n).collect()
+		} else {
+			(0..n).rev().collect()
+		};
+
+		let ring = (0..n)
+			.map(|k| {
+				let (i, j) = (order[k], order[(k + 1) % n]);
+				let d = points[j] - points[i];
+				let len = dot(d, d).sqrt();
+				Vertex {
+					pos: points[i],
+					node: i,
+					dir: Point {
+						x: d.x / len,
+						y: d.y / len,
+					},
+				}
+			})
+			.collect();
+
+		let extent = points
+			.iter()
+			.flat_map(|p| [p.x.abs(), p.y.abs()])
+			.fold(1.0, f64::max);
+
+		Wavefront {
+			rings: vec![ring],
+			time: 0.0,
+			nodes: points.to_vec(),
+			heights: vec![0.0; n],
+			arcs: Vec::new(),
+			eps: 1e-9 * extent,
+		}
+	}
+
+	/// Velocity of the corner keeping unit distance per time to the lines of both its edges.
+	fn velocity(ring: &[Vertex], i: usize) -> Point<f64>
+	{
+		let prev = ring[(i + ring.len() - 1) % ring.len()];
+		let (n1, n2) = (normal(prev.dir), normal(ring[i].dir));
+		let s = (1.0 + dot(n1, n2)).max(1e-12);
+		Point {
+			x: (n1.x + n2.x) / s,
+			y: (n1.y + n2.y) / s,
+		}
+	}
+
+	/// Earliest edge or split event as time from now.
+	fn next_event(&self) -> Option<(f64, Event)>
+	{
+		let mut best: Option<(f64, Event)> = None;
+		let mut offer = |dt: f64, e: Event| {
+			if best.as_ref().is_none_or(|b| dt < b.0) {
+				best = Some((dt.max(0.0), e));
+			}
+		};
+
+		for (r, ring) in self.rings.iter().enumerate() {
+			let m = ring.len();
+			let vel: Vec<Point<f64>> = (0..m).map(|i| Self::velocity(ring, i)).collect();
+
+			for i in 0..m {
+				let j = (i + 1) % m;
+				let d = ring[i].dir;
+
+				// The corners approach each other along the edge
+				let rate = dot(vel[i] - vel[j], d);
+				if rate > 1e-12 {
+					offer(
+						dot(ring[j].pos - ring[i].pos, d) / rate,
+						Event::Edge { ring: r, i },
+					);
+				}
+
+				let prev = ring[(i + m - 1) % m];
+				if cross(prev.dir, d) >= -1e-12 {
+					continue;
+				}
+
+				// The reflex corner reaches the line of an edge within its current extent
+				for a in (0..m).filter(|&a| a != i && (a + 1) % m != i) {
+					let b = (a + 1) % m;
+					let (e, n) = (ring[a].dir, normal(ring[a].dir));
+					let (dist, speed) = (dot(ring[i].pos - ring[a].pos, n), 1.0 - dot(vel[i], n));
+					if dist < -self.eps || speed <= 1e-12 {
+						continue;
+					}
+
+					let dt = dist.max(0.0) / speed;
+					let hit = along(ring[i].pos, vel[i], dt);
+					if dot(hit - along(ring[a].pos, vel[a], dt), e) >= -self.eps
+						&& dot(hit - along(ring[b].pos, vel[b], dt), e) <= self.eps
+					{
+						offer(dt, Event::Split { ring: r, i, a });
+					}
+				}
+			}
+		}
+
+		best
+	}
+
+	fn advance(&mut self, dt: f64)
+	{
+		for ring in &mut self.rings {
+			let vel: Vec<Point<f64>> = (0..ring.len()).map(|i| Self::velocity(ring, i)).collect();
+			for (v, d) in ring.iter_mut().zip(vel) {
+				v.pos = along(v.pos, d, dt);
+			}
+		}
+		self.time += dt;
+	}
+
+	/// Node at the point, reusing one that coincides with it.
+	fn node(&mut self, p: Point<f64>) -> usize
+	{
+		let d = |q: Point<f64>| dot(q - p, q - p).sqrt();
+		match self.nodes.iter().position(|&q| d(q) <= self.eps) {
+			Some(i) => i,
+			None => {
+				self.nodes.push(p);
+				self.heights.push(self.time);
+				self.nodes.len() - 1
+			}
+		}
+	}
+
+	fn arc(&mut self, a: usize, b: usize)
+	{
+		let key = [a.min(b), a.max(b)];
+		if a != b && !self.arcs.contains(&key) {
+			self.arcs.push(key);
+		}
+	}
+
+	/// Replaces the corner i and the next one by one at the point, with the edge between them
+	/// gone.
+	fn merge(&mut self, ring: &mut Vec<Vertex>, i: usize, pos: Point<f64>)
+	{
+		let j = (i + 1) % ring.len();
+		let (u, v) = (ring[i], ring[j]);
+
+		let node = self.node(pos);
+		self.arc(u.node, node);
+		self.arc(v.node, node);
+		ring[i] = Vertex {
+			pos,
+			node,
+			dir: v.dir,
+		};
+		ring.remove(j);
+	}
+
+	/// Adds the ring back after merging coinciding neighbours, which vertex events leave behind.
+	/// A ring collapsed to a point or without area ends its corners where they are, joined by
+	/// ridges along what is left of it.
+	fn settle(&mut self, mut ring: Vec<Vertex>)
+	{
+		let mut i = 0;
+		while ring.len() > 1 && i < ring.len() {
+			let j = (i + 1) % ring.len();
+			let d = ring[j].pos - ring[i].pos;
+			if dot(d, d).sqrt() > self.eps {
+				i += 1;
+				continue;
+			}
+
+			let pos = ring[i].pos;
+			self.merge(&mut ring, i, pos);
+			if j == 0 {
+				i -= 1;
+			}
+		}
+
+		let m = ring.len();
+		let (twice_area, perimeter) = (0..m).fold((0.0, 0.0), |(a, l), i| {
+			let (p, q) = (ring[i].pos, ring[(i + 1) % m].pos);
+			(a + cross(p, q), l + dot(q - p, q - p).sqrt())
+		});
+		if m >= 3 && twice_area.abs() > self.eps * perimeter {
+			self.rings.push(ring);
+			return;
+		}
+
+		let ends: Vec<usize> = ring
+			.iter()
+			.map(|v| {
+				let n = self.node(v.pos);
+				self.arc(v.node, n);
+				n
+			})
+			.collect();
+		for (k, &n) in ends.iter().enumerate() {
+			self.arc(n, ends[(k + 1) % ends.len()]);
+		}
+	}
+
+	fn handle(&mut self, event: Event)
+	{
+		match event {
+			Event::Edge { ring, i } => {
+				let mut ring = self.rings.swap_remove(ring);
+				let (u, v) = (ring[i].pos, ring[(i + 1) % ring.len()].pos);
+				let mid = Point {
+					x: (u.x + v.x) / 2.0,
+					y: (u.y + v.y) / 2.0,
+				};
+				self.merge(&mut ring, i, mid);
+				self.settle(ring);
+			}
+			Event::Split { ring, i, a } => {
+				let ring = self.rings.swap_remove(ring);
+				let m = ring.len();
+				let v = ring[i];
+
+				let node = self.node(v.pos);
+				self.arc(v.node, node);
+
+				// The corner continues on both sides of the split edge
+				let left = Vertex { node, ..v };
+				let right = Vertex {
+					node,
+					dir: ring[a].dir,
+					..v
+				};
+				let ring = &ring;
+				let part = |from: usize, to: usize| {
+					let len = (to + m - from) % m + 1;
+					(0..len).map(move |k| ring[(from + k) % m])
+				};
+
+				let first = core::iter::once(left).chain(part((i + 1) % m, a)).collect();
+				let second = core::iter::once(right)
+					.chain(part((a + 1) % m, (i + m - 1) % m))
+					.collect();
+				self.settle(first);
+				self.settle(second);
+			}
+		}
+	}
+
+	/// Shrinks the rings until the time reaches `stop` or they all collapsed.
+	fn run(&mut self, stop: f64)
+	{
+		while let Some((dt, event)) = self.next_event() {
+			if self.time + dt > stop {
+				self.advance(stop - self.time);
+				return;
+			}
+
+			self.advance(dt);
+			self.handle(event);
+		}
+
+		self.split_arcs();
+	}
+
+	/// Splits arcs at nodes lying on them, which rings collapsing side by side along one line
+	/// leave behind.
+	fn split_arcs(&mut self)
+	{
+		let mut k = 0;
+		while k < self.arcs.len() {
+			let [a, b] = self.arcs[k];
+			let (p, q) = (self.nodes[a], self.nodes[b]);
+			let len = dot(q - p, q - p).sqrt();
+			let on = (0..self.nodes.len()).find(|&n| {
+				let d = self.nodes[n] - p;
+				let t = dot(d, q - p) / len;
+				n != a
+					&& n != b && t > self.eps
+					&& t < len - self.eps
+					&& cross(q - p, d).abs() <= self.eps * len
+			});
+
+			match on {
+				Some(n) => {
+					self.arcs.swap_remove(k);
+					self.arc(a, n);
+					self.arc(n, b);
+				}
+				None => k += 1,
+			}
+		}
+	}
+}
+
+// -----------------------------------------------------------------------------
+// Straight skeleton
+// -----------------------------------------------------------------------------
+
+/// Straight skeleton of a simple polygon: the paths the corners take while all edges move inward
+/// at the same speed, split where reflex corners run into edges. It is a tree over the polygon's
+/// corners and the points where the shrinking polygon changes shape.
+#[derive(Debug, Clone)]
+pub struct StraightSkeleton
+{
+	/// Skeleton nodes, starting with the polygon's corners in their order
+	pub nodes: Vec<Point<f64>>,
+	/// Time at which the wavefront passes each node, its distance to the lines of the edges that
+	/// meet there and the height of the roof above it
+	pub heights: Vec<f64>,
+	/// Node pairs joined by the skeleton
+	pub arcs: Vec<[usize; 2]>,
+	corners: usize,
+	ccw: bool,
+}
+
+impl StraightSkeleton
+{
+	/// Roof model: the faces of the skeleton, one per polygon edge, as counter clockwise node
+	/// loops starting with the edge. Face i lies above the edge between corner i and i + 1 and
+	/// rises from it with slope 1, taking the [`StraightSkeleton::heights`] of the nodes as
+	/// heights.
+	pub fn roof(&self) -> Vec<Vec<usize>>
+	{
+		let (n, corners) = (self.nodes.len(), self.corners);
+
+		let mut adjacent = vec![Vec::new(); n];
+		let edges = (0..corners).map(|i| [i, (i + 1) % corners]);
+		for [a, b] in self.arcs.iter().copied().chain(edges) {
+			adjacent[a].push(b);
+			adjacent[b].push(a);
+		}
+
+		// Neighbours in counter clockwise order around each node
+		for (v, adj) in adjacent.iter_mut().enumerate() {
+			let angle = |w: &usize| {
+				let d = self.nodes[*w] - self.nodes[v];
+				d.y.atan2(d.x)
+			};
+			adj.sort_by(|a, b| angle(a).partial_cmp(&angle(b)).unwrap_or(Ordering::Equal));
+		}
+
+		(0..corners)
+			.map(|i| {
+				let j = (i + 1) % corners;
+				let (u, v) = if self.ccw { (i, j) } else { (j, i) };
+
+				// Keep the face on the left by turning into the next neighbour clockwise
+				let mut face = vec![u];
+				let (mut a, mut b) = (u, v);
+				while b != u && face.len() <= n {
+					face.push(b);
+					let adj = &adjacent[b];
+					let k = adj.iter().position(|&w| w == a).unwrap();
+					let w = adj[(k + adj.len() - 1) % adj.len()];
+					a = b;
+					b = w;
+				}
+				face
+			})
+			.collect()
+	}
+}
+
+/// Checks whether the corners run counter clockwise.
+fn is_ccw(points: &[Point<f64>]) -> bool
+{
+	let twice_area: f64 = (0..points.len())
+		.map(|i| cross(points[i], points[(i + 1) % points.len()]))
+		.sum();
+	twice_area >= 0.0
+}
+
+impl Polygon
+{
+	/// Straight skeleton of the simple polygon, found by simulating the shrinking polygon event by
+	/// event in O(n³). Corners have to be distinct.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use ctl::geometry::{Point, Polygon};
+	/// let p = |x, y| Point { x, y };
+	/// let rect = Polygon { points: vec![p(0.0, 0.0), p(4.0, 0.0), p(4.0, 2.0), p(0.0, 2.0)] };
+	/// let s = rect.straight_skeleton(); // the ridge (1, 1) to (3, 1) joined to every corner
+	/// let roof = s.roof(); // 2 triangles at the short edges, 2 trapezoids at the long ones
+	/// ```
+	pub fn straight_skeleton(&self) -> StraightSkeleton
+	{
+		let ccw = is_ccw(&self.points);
+		let mut wave = Wavefront::new(&self.points, ccw);
+		wave.run(f64::INFINITY);
+
+		StraightSkeleton {
+			nodes: wave.nodes,
+			heights: wave.heights,
+			arcs: wave.arcs,
+			corners: self.points.len(),
+			ccw,
+		}
+	}
+
+	/// Offset curve at distance d inside the simple polygon, the shrinking polygon of the straight
+	/// skeleton at time d. Edges stay parallel to the original ones and corners stay sharp, the
+	/// polygon may split into several counter clockwise parts or vanish.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use ctl::geometry::{Point, Polygon};
+	/// let p = |x, y| Point { x, y };
+	/// let rect = Polygon { points: vec![p(0.0, 0.0), p(4.0, 0.0), p(4.0, 2.0), p(0.0, 2.0)] };
+	/// let inner = rect.inset(0.5); // the rectangle (0.5, 0.5) to (3.5, 1.5)
+	/// ```
+	pub fn inset(&self, d: f64) -> Vec<Polygon>
+	{
+		let mut wave = Wavefront::new(&self.points, is_ccw(&self.points));
+		wave.run(d);
+
+		wave.rings
+			.into_iter()
+			.map(|ring| Polygon {
+				points: ring.iter().map(|v| v.pos).collect(),
+			})
+			.collect()
+	}
+}
+
+// -----------------------------------------------------------------------------
+// Medial axis
+// -----------------------------------------------------------------------------
+
+/// Center of the circle through the corners of the triangle.
+fn circumcenter(a: Point<f64>, b: Point<f64>, c: Point<f64>) -> Point<f64>
+{
+	let (ab, ac) = (b - a, c - a);
+	let d = 2.0 * cross(ab, ac);
+	let (l, m) = (dot(ab, ab), dot(ac, ac));
+	Point {
+		x: a.x + (ac.y * l - ab.y * m) / d,
+		y: a.y + (ab.x * m - ac.x * l) / d,
+	}
+}
+
+impl Polygon
+{
+	/// Approximate medial axis of the simple polygon: the centers of the circles inside it that
+	/// touch its border at 2 or more points. Unlike the straight skeleton it bends into parabolas
+	/// around reflex corners, which stay off the axis.
+	///
+	/// The border is sampled at most `spacing` apart and the axis made of the Voronoi edges of the
+	/// samples that lie inside the polygon, with the Voronoi vertices at the circumcenters of their
+	/// Delaunay triangles. The edges between neighbouring samples cross the border and are left
+	/// out. The segments lie within about `spacing` of the exact axis.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use ctl::geometry::{Point, Polygon};
+	/// let p = |x, y| Point { x, y };
+	/// let rect = Polygon { points: vec![p(0.0, 0.0), p(4.0, 0.0), p(4.0, 2.0), p(0.0, 2.0)] };
+	/// let axis = rect.medial_axis(0.05); // the ridge (1, 1) to (3, 1) and the corners' bisectors
+	/// ```
+	pub fn medial_axis(&self, spacing: f64) -> Vec<Segment<f64>>
+	{
+		let (pts, n) = (&self.points, self.points.len());
+		let mut samples = Vec::new();
+		for i in 0..n {
+			let (a, b) = (pts[i], pts[(i + 1) % n]);
+			let d = b - a;
+			let k = (dot(d, d).sqrt() / spacing).ceil().max(1.0) as usize;
+			samples.extend((0..k).map(|s| along(a, d, s as f64 / k as f64)));
+		}
+
+		let mesh = HalfEdgeMesh::delaunay(samples);
+		let v = &mesh.vertices;
+		let center = |f: usize| {
+			let [a, b, c] = mesh.face_vertices(f).map(|i| v[i]);
+			circumcenter(a, b, c)
+		};
+
+		let mut axis = Vec::new();
+		for e in 0..mesh.face_count() * 3 {
+			let t = match mesh.edge(e).twin {
+				Some(t) if e < t => t,
+				_ => continue,
+			};
+			let (a, b) = (center(mesh.edge(e).face), center(mesh.edge(t).face));
+			if a != b && point_in_polygon(a, pts) && point_in_polygon(b, pts) {
+				axis.push(Segment { a, b });
+			}
+		}
+		axis
+	}
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests
+{
+	use super::*;
+
+	fn p(x: f64, y: f64) -> Point<f64>
+	{
+		Point { x, y }
+	}
+
+	fn area(points: &[Point<f64>]) -> f64
+	{
+		(0..points.len())
+			.map(|i| cross(points[i], points[(i + 1) % points.len()]))
+			.sum::<f64>()
+			/ 2.0
+	}
+
+	fn close(a: Point<f64>, b: Point<f64>) -> bool
+	{
+		(a.x - b.x).abs() < 1e-9 && (a.y - b.y).abs() < 1e-9
+	}
+
+	/// The skeleton is a tree and the roof faces tile the polygon.
+	fn check(poly: &Polygon) -> StraightSkeleton
+	{
+		let s = poly.straight_skeleton();
+		assert_eq!(s.arcs.len(), s.nodes.len() - 1);
+
+		let roof = s.roof();
+		assert_eq!(roof.len(), poly.points.len());
+		let faces: f64 = roof
+			.iter()
+			.map(|f| area(&f.iter().map(|&i| s.nodes[i]).collect::<Vec<_>>()))
+			.sum();
+		assert!((faces - area(&poly.points).abs()).abs() < 1e-9);
+		s
+	}
+
+	#[test]
+	fn test_convex()
+	{
+		// A rectangle's ridge, in either winding
+		let mut rect = Polygon {
+			points: vec![p(0.0, 0.0), p(4.0, 0.0), p(4.0, 2.0), p(0.0, 2.0)],
+		};
+		for _ in 0..2 {
+			let s = check(&rect);
+			assert_eq!(s.nodes.len(), 6);
+			assert!(s.nodes[4..]
+				.iter()
+				.all(|&q| close(q, p(1.0, 1.0)) || close(q, p(3.0, 1.0))));
+			assert!(s.heights[4..].iter().all(|&h| (h - 1.0).abs() < 1e-9));
+			assert_eq!(s.roof().iter().filter(|f| f.len() == 3).count(), 2);
+
+			let inner = rect.inset(0.5);
+			assert_eq!(inner.len(), 1);
+			assert!((area(&inner[0].points) - 3.0).abs() < 1e-9);
+			assert!(rect.inset(1.5).is_empty());
+			rect.points.reverse();
+		}
+
+		// A square shrinks to its center, a triangle to its incenter
+		let s = check(&Polygon {
+			points: vec![p(0.0, 0.0), p(2.0, 0.0), p(2.0, 2.0), p(0.0, 2.0)],
+		});
+		assert_eq!(s.nodes.len(), 5);
+		assert!(close(s.nodes[4], p(1.0, 1.0)));
+
+		let s = check(&Polygon {
+			points: vec![p(0.0, 0.0), p(4.0, 0.0), p(0.0, 3.0)],
+		});
+		assert_eq!(s.nodes.len(), 4);
+		assert!(close(s.nodes[3], p(1.0, 1.0)) && (s.heights[3] - 1.0).abs() < 1e-9);
+	}
+
+	#[test]
+	fn test_reflex()
+	{
+		// The notch's corner runs into the bottom edge and splits the polygon in two
+		let notched = Polygon {
+			points: vec![
+				p(0.0, 0.0),
+				p(6.0, 0.0),
+				p(6.0, 2.0),
+				p(3.5, 2.0),
+				p(3.0, 1.0),
+				p(2.5, 2.0),
+				p(0.0, 2.0),
+			],
+		};
+		let s = check(&notched);
+		assert!(s.heights.iter().all(|&h| h <= 1.0 + 1e-9));
+		assert_eq!(notched.inset(0.1).len(), 1);
+		let parts = notched.inset(0.6);
+		assert_eq!(parts.len(), 2);
+		assert!(parts.iter().all(|q| area(&q.points) > 0.0));
+
+		// An L shape has a reflex corner without a split
+		let l = Polygon {
+			points: vec![
+				p(0.0, 0.0),
+				p(3.0, 0.0),
+				p(3.0, 1.0),
+				p(1.0, 1.0),
+				p(1.0, 3.0),
+				p(0.0, 3.0),
+			],
+		};
+		check(&l);
+		let inner = l.inset(0.25);
+		assert_eq!(inner.len(), 1);
+		assert_eq!(inner[0].points.len(), 6);
+		assert!((area(&inner[0].points) - 2.25).abs() < 1e-9);
+
+		// The bar under the teeth collapses to a line at the same time as a tooth
+		let comb = Polygon {
+			points: vec![
+				p(0.0, 0.0),
+				p(5.0, 0.0),
+				p(4.0, 2.0),
+				p(3.0, 2.0),
+				p(3.0, 1.0),
+				p(2.0, 1.0),
+				p(2.0, 2.0),
+				p(1.0, 2.0),
+				p(1.0, 1.0),
+				p(0.0, 1.0),
+			],
+		};
+		let s = check(&comb);
+		assert!(s.nodes.iter().any(|&q| close(q, p(1.5, 0.5))));
+	}
+
+	/// Points along the segments are about as far from 2 border points that lie apart, as the
+	/// centers of circles touching the border twice.
+	fn check_axis(poly: &Polygon, spacing: f64) -> Vec<Segment<f64>>
+	{
+		let axis = poly.medial_axis(spacing);
+		assert!(!axis.is_empty());
+
+		let n = poly.points.len();
+		let foot = |m: Point<f64>, i: usize| {
+			let (a, b) = (poly.points[i], poly.points[(i + 1) % n]);
+			let d = b - a;
+			along(a, d, (dot(m - a, d) / dot(d, d)).clamp(0.0, 1.0))
+		};
+		let dist = |a: Point<f64>, b: Point<f64>| dot(a - b, a - b).sqrt();
+
+		for s in &axis {
+			for k in 0..=4 {
+				let m = along(s.a, s.b - s.a, k as f64 / 4.0);
+				assert!(point_in_polygon(m, &poly.points));
+				let feet: Vec<_> = (0..n).map(|i| foot(m, i)).collect();
+				let d = feet
+					.iter()
+					.map(|&f| dist(m, f))
+					.fold(f64::INFINITY, f64::min);
+				let near: Vec<_> = feet
+					.into_iter()
+					.filter(|&f| dist(m, f) <= d + 2.0 * spacing)
+					.collect();
+				assert!(
+					near.iter().any(|&f| near.iter().any(|&g| dist(f, g) >= d)),
+					"{:?}",
+					m
+				);
+			}
+		}
+		axis
+	}
+
+	#[test]
+	fn test_medial_axis()
+	{
+		// The ridge and the bisectors of the corners, in either winding
+		let mut rect = Polygon {
+			points: vec![p(0.0, 0.0), p(4.0, 0.0), p(4.0, 2.0), p(0.0, 2.0)],
+		};
+		for _ in 0..2 {
+			let axis = check_axis(&rect, 0.05);
+			let covered = |q: Point<f64>| axis.iter().any(|s| dot(s.a - q, s.a - q).sqrt() < 0.1);
+			assert!((0..=20).all(|i| covered(p(1.0 + i as f64 / 10.0, 1.0))));
+			assert!((1..10).all(|i| covered(p(i as f64 / 10.0, i as f64 / 10.0))));
+			assert!((1..10).all(|i| covered(p(4.0 - i as f64 / 10.0, 2.0 - i as f64 / 10.0))));
+			rect.points.reverse();
+		}
+
+		// Around the reflex corner the axis bends at distance, the comb's teeth get their own
+		let l = Polygon {
+			points: vec![
+				p(0.0, 0.0),
+				p(3.0, 0.0),
+				p(3.0, 1.0),
+				p(1.0, 1.0),
+				p(1.0, 3.0),
+				p(0.0, 3.0),
+			],
+		};
+		let axis = check_axis(&l, 0.05);
+		let corner = p(1.0, 1.0);
+		assert!(axis
+			.iter()
+			.all(|s| dot(s.a - corner, s.a - corner).sqrt() > 0.45));
+		assert!(axis
+			.iter()
+			.any(|s| dot(s.a - corner, s.a - corner).sqrt() < 0.6));
+
+		let comb = Polygon {
+			points: vec![
+				p(0.0, 0.0),
+				p(5.0, 0.0),
+				p(4.0, 2.0),
+				p(3.0, 2.0),
+				p(3.0, 1.0),
+				p(2.0, 1.0),
+				p(2.0, 2.0),
+				p(1.0, 2.0),
+				p(1.0, 1.0),
+				p(0.0, 1.0),
+			],
+		};
+		let axis = check_axis(&comb, 0.05);
+		assert!(axis
+			.iter()
+			.any(|s| s.a.y > 1.5 && s.a.x > 1.0 && s.a.x < 2.0));
+	}
+}
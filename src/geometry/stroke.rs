@@ -0,0 +1,319 @@
+use super::{Point, Polygon};
+use alloc::vec::Vec;
+use core::f64::consts::PI;
+
+// -----------------------------------------------------------------------------
+// Stroke
+// -----------------------------------------------------------------------------
+
+/// Shape of the outer corner where 2 segments of a stroke meet.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum LineJoin
+{
+	/// Extends both sides until they meet. Falls back to a bevel when the tip would reach
+	/// farther than the limit times the half width from the corner, like SVG's miter limit.
+	Miter(f64),
+	/// Circle around the corner
+	Round,
+	/// Straight cut between both sides
+	Bevel,
+}
+
+/// Shape of the ends of an open stroke.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum LineCap
+{
+	/// Ends flat at the end point
+	Butt,
+	/// Half circle around the end point
+	Round,
+	/// Square sticking out by half the width
+	Square,
+}
+
+/// Pushes the points of a circle around `center` from the angle `start` turning by `sweep`,
+/// both ends included. The chords stay within a thousandth of the radius, see
+/// [`Arc::flatten`](super::Arc::flatten).
+fn push_round(out: &mut Vec<Point<f64>>, center: Point<f64>, r: f64, start: f64, sweep: f64)
+{
+	let step = 2.0 * 0.999f64.acos();
+	let n = ((sweep.abs() / step).ceil() as usize).max(1);
+
+	out.extend((0..=n).map(|i| {
+		let a = start + sweep * i as f64 / n as f64;
+		Point {
+			x: center.x + r * a.cos(),
+			y: center.y + r * a.sin(),
+		}
+	}));
+}
+
+fn offset(p: Point<f64>, n: Point<f64>, k: f64) -> Point<f64>
+{
+	Point {
+		x: p.x + n.x * k,
+		y: p.y + n.y * k,
+	}
+}
+
+/// Outline of a polyline drawn with a thick pen, as a counter clockwise polygon running along
+/// the right side, around the end cap, back along the left side and around the start cap.
+/// Sharp inner corners whose offset lines don't meet within the segments keep the corner point,
+/// so the outline overlaps itself there and is meant to be filled with the nonzero rule.
+///
+/// Repeated points are skipped, a single point only gives a shape with round or square caps.
+///
+/// # Arguments
+///
+/// * `polyline` - Points of the open path
+/// * `width` - Full width of the stroke
+/// * `join` - Shape of the corners
+/// * `cap` - Shape of both ends
+///
+/// # Examples
+///
+/// ```
+/// use ctl::geometry::{stroke, LineCap, LineJoin, Point};
+/// let p = |x, y| Point { x, y };
+/// let outline = stroke(&[p(0.0, 0.0), p(10.0, 0.0), p(10.0, 10.0)], 2.0, LineJoin::Miter(4.0), LineCap::Butt);
+/// // [(0, -1), (11, -1), (11, 10), (9, 10), (9, 1), (0, 1)]
+/// ```
+pub fn stroke(polyline: &[Point<f64>], width: f64, join: LineJoin, cap: LineCap) -> Polygon
+{
+	let mut pts: Vec<Point<f64>> = polyline.to_vec();
+	pts.dedup();
+	let hw = width / 2.0;
+
+	if pts.is_empty() {
+		return Polygon { points: Vec::new() };
+	}
+	if pts.len() == 1 {
+		let c = pts[0];
+		let mut points = Vec::new();
+		match cap {
+			LineCap::Butt => {}
+			LineCap::Round => {
+				push_round(&mut points, c, hw, 0.0, 2.0 * PI);
+				points.pop();
+			}
+			LineCap::Square => {
+				for (dx, dy) in [(1.0, -1.0), (1.0, 1.0), (-1.0, 1.0), (-1.0, -1.0)] {
+					points.push(Point {
+						x: c.x + dx * hw,
+						y: c.y + dy * hw,
+					});
+				}
+			}
+		}
+		return Polygon { points };
+	}
+
+	// Unit directions, left normals and lengths of the segments
+	let segs: Vec<_> = pts
+		.windows(2)
+		.map(|w| {
+			let (dx, dy) = (w[1].x - w[0].x, w[1].y - w[0].y);
+			let len = (dx * dx + dy * dy).sqrt();
+			let d = Point {
+				x: dx / len,
+				y: dy / len,
+			};
+			(d, Point { x: -d.y, y: d.x }, len)
+		})
+		.collect();
+
+	let (first, last) = (segs[0], segs[segs.len() - 1]);
+	let mut right = vec![offset(pts[0], first.1, -hw)];
+	let mut left = vec![offset(pts[0], first.1, hw)];
+
+	for (i, w) in segs.windows(2).enumerate() {
+		let ((d1, n1, len1), (d2, n2, len2)) = (w[0], w[1]);
+		let p = pts[i + 1];
+
+		let turn = (d1.x * d2.y - d1.y * d2.x).atan2(d1.x * d2.x + d1.y * d2.y);
+		let c = n1.x * n2.x + n1.y * n2.y;
+
+		// Left turns have the outer corner on the right
+		let (outer, inner, k) = if turn >= 0.0 {
+			(&mut right, &mut left, -hw)
+		} else {
+			(&mut left, &mut right, hw)
+		};
+
+		// Meeting point of the offset lines, a distance of hw / cos(turn / 2) from the corner
+		let meet = |k: f64| {
+			let s = k / (1.0 + c);
+			Point {
+				x: p.x + (n1.x + n2.x) * s,
+				y: p.y + (n1.y + n2.y) * s,
+			}
+		};
+
+		// Inner side
+		let reach = if 1.0 + c > 1e-12 {
+			hw * ((1.0 - c) / (1.0 + c)).sqrt()
+		} else {
+			f64::INFINITY
+		};
+		if reach <= len1 && reach <= len2 {
+			inner.push(meet(-k));
+		} else {
+			inner.push(offset(p, n1, -k));
+			inner.push(p);
+			inner.push(offset(p, n2, -k));
+		}
+
+		// Outer side
+		let (a, b) = (offset(p, n1, k), offset(p, n2, k));
+		match join {
+			LineJoin::Miter(limit) if 1.0 + c > 1e-12 && (2.0 / (1.0 + c)).sqrt() <= limit => {
+				outer.push(meet(k));
+			}
+			LineJoin::Round => {
+				let start = (a.y - p.y).atan2(a.x - p.x);
+				push_round(outer, p, hw, start, turn);
+			}
+			_ => {
+				outer.push(a);
+				outer.push(b);
+			}
+		}
+	}
+
+	let end = pts[pts.len() - 1];
+	right.push(offset(end, last.1, -hw));
+	left.push(offset(end, last.1, hw));
+
+	// Right side forward, end cap, left side backward, start cap
+	let mut points = right;
+	add_cap(&mut points, cap, end, last.0, last.1, hw);
+	points.extend(left.into_iter().rev());
+
+	let back = Point {
+		x: -first.0.x,
+		y: -first.0.y,
+	};
+	let normal = Point {
+		x: -first.1.x,
+		y: -first.1.y,
+	};
+	add_cap(&mut points, cap, pts[0], back, normal, hw);
+
+	Polygon { points }
+}
+
+/// Adds the cap between the right and the left corner at the end `p` facing `d`, with `n` the
+/// left normal, skipping the corners already in place.
+fn add_cap(
+	points: &mut Vec<Point<f64>>,
+	cap: LineCap,
+	p: Point<f64>,
+	d: Point<f64>,
+	n: Point<f64>,
+	hw: f64,
+)
+{
+	match cap {
+		LineCap::Butt => {}
+		LineCap::Round => {
+			let mut arc = Vec::new();
+			push_round(&mut arc, p, hw, (-n.y).atan2(-n.x), PI);
+			points.extend(&arc[1..arc.len() - 1]);
+		}
+		LineCap::Square => {
+			let tip = offset(p, d, hw);
+			points.push(offset(tip, n, -hw));
+			points.push(offset(tip, n, hw));
+		}
+	}
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests
+{
+	use super::*;
+
+	fn p(x: f64, y: f64) -> Point<f64>
+	{
+		Point { x, y }
+	}
+
+	/// Signed area, positive for counter clockwise outlines.
+	fn area(poly: &Polygon) -> f64
+	{
+		let pts = &poly.points;
+		(0..pts.len())
+			.map(|i| {
+				let (a, b) = (pts[i], pts[(i + 1) % pts.len()]);
+				a.x * b.y - b.x * a.y
+			})
+			.sum::<f64>()
+			/ 2.0
+	}
+
+	#[test]
+	fn test_caps()
+	{
+		let line = [p(0.0, 0.0), p(4.0, 0.0), p(4.0, 0.0)];
+		let butt = stroke(&line, 2.0, LineJoin::Bevel, LineCap::Butt);
+		assert_eq!(
+			butt.points,
+			vec![p(0.0, -1.0), p(4.0, -1.0), p(4.0, 1.0), p(0.0, 1.0)]
+		);
+
+		let square = stroke(&line, 2.0, LineJoin::Bevel, LineCap::Square);
+		assert!((area(&square) - 12.0).abs() < 1e-12);
+
+		let round = stroke(&line, 2.0, LineJoin::Bevel, LineCap::Round);
+		assert!((area(&round) - (8.0 + PI)).abs() < 1e-2);
+
+		let dot = stroke(&line[..1], 2.0, LineJoin::Bevel, LineCap::Round);
+		assert!((area(&dot) - PI).abs() < 1e-2);
+		assert!(stroke(&line[..1], 2.0, LineJoin::Bevel, LineCap::Butt)
+			.points
+			.is_empty());
+	}
+
+	#[test]
+	fn test_joins()
+	{
+		let path = [p(0.0, 0.0), p(10.0, 0.0), p(10.0, 10.0)];
+
+		let miter = stroke(&path, 2.0, LineJoin::Miter(4.0), LineCap::Butt);
+		assert_eq!(
+			miter.points,
+			vec![
+				p(0.0, -1.0),
+				p(11.0, -1.0),
+				p(11.0, 10.0),
+				p(9.0, 10.0),
+				p(9.0, 1.0),
+				p(0.0, 1.0)
+			]
+		);
+		assert!((area(&miter) - 40.0).abs() < 1e-12);
+
+		// A right angle needs a limit of at least sqrt(2)
+		let limited = stroke(&path, 2.0, LineJoin::Miter(1.4), LineCap::Butt);
+		let bevel = stroke(&path, 2.0, LineJoin::Bevel, LineCap::Butt);
+		assert_eq!(limited, bevel);
+		assert!((area(&bevel) - 39.5).abs() < 1e-12);
+
+		let round = stroke(&path, 2.0, LineJoin::Round, LineCap::Butt);
+		assert!((area(&round) - (39.0 + PI / 4.0)).abs() < 1e-2);
+
+		// Turning right mirrors everything, the outline stays counter clockwise
+		let mirrored: Vec<_> = path.iter().map(|q| p(q.x, -q.y)).collect();
+		let round = stroke(&mirrored, 2.0, LineJoin::Round, LineCap::Round);
+		assert!((area(&round) - (39.0 + PI / 4.0 + PI)).abs() < 1e-2);
+
+		// Segments shorter than the inner offset keep the corner point
+		let hairpin = [p(0.0, 0.0), p(10.0, 0.0), p(9.0, 0.5)];
+		let outline = stroke(&hairpin, 2.0, LineJoin::Bevel, LineCap::Butt);
+		assert!(outline.points.contains(&p(10.0, 0.0)));
+	}
+}
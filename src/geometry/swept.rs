@@ -0,0 +1,294 @@
+#[cfg(feature = "std")]
+use super::{Circle, Segment};
+use super::{Point, Rect};
+
+// -----------------------------------------------------------------------------
+// Time of impact
+// -----------------------------------------------------------------------------
+
+/// Earliest time in [0, 1] at which box `a` moving by `da` touches box `b` moving by `db`
+/// during one step. Returns 0 if they already overlap and `None` if they don't meet.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::geometry::{aabb_time_of_impact, Point, Rect};
+/// let a = Rect::from_corners(Point { x: 0.0, y: 0.0 }, Point { x: 1.0, y: 1.0 });
+/// let b = Rect::from_corners(Point { x: 3.0, y: 0.0 }, Point { x: 4.0, y: 1.0 });
+/// let t = aabb_time_of_impact(&a, Point { x: 4.0, y: 0.0 }, &b, Point { x: 0.0, y: 0.0 });
+/// // Some(0.5)
+/// ```
+pub fn aabb_time_of_impact(
+	a: &Rect<f64>,
+	da: Point<f64>,
+	b: &Rect<f64>,
+	db: Point<f64>,
+) -> Option<f64>
+{
+	// Move a relative to a resting b
+	let v = da - db;
+	let (mut enter, mut exit) = (0.0f64, 1.0f64);
+
+	for (a_min, a_max, b_min, b_max, v) in [
+		(a.min.x, a.max.x, b.min.x, b.max.x, v.x),
+		(a.min.y, a.max.y, b.min.y, b.max.y, v.y),
+	] {
+		if v == 0.0 {
+			if a_max < b_min || b_max < a_min {
+				return None;
+			}
+			continue;
+		}
+
+		let t0 = (b_min - a_max) / v;
+		let t1 = (b_max - a_min) / v;
+
+		enter = enter.max(t0.min(t1));
+		exit = exit.min(t0.max(t1));
+
+		if enter > exit {
+			return None;
+		}
+	}
+
+	Some(enter)
+}
+
+/// Earliest non-negative root of `a t^2 + b t + c = 0` within [0, 1].
+#[cfg(feature = "std")]
+fn first_root(a: f64, b: f64, c: f64) -> Option<f64>
+{
+	if c <= 0.0 {
+		// Already touching
+		return Some(0.0);
+	}
+	if a == 0.0 {
+		return None;
+	}
+
+	let disc = b * b - 4.0 * a * c;
+	if disc < 0.0 {
+		return None;
+	}
+
+	let t = (-b - disc.sqrt()) / (2.0 * a);
+	if (0.0..=1.0).contains(&t) {
+		Some(t)
+	} else {
+		None
+	}
+}
+
+/// Earliest time in [0, 1] at which circle `a` moving by `da` touches circle `b` moving by `db`
+/// during one step. Returns 0 if they already overlap and `None` if they don't meet.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::geometry::{circle_time_of_impact, Circle, Point};
+/// let a = Circle { center: Point { x: 0.0, y: 0.0 }, radius: 1.0 };
+/// let b = Circle { center: Point { x: 10.0, y: 0.0 }, radius: 1.0 };
+/// let t = circle_time_of_impact(&a, Point { x: 16.0, y: 0.0 }, &b, Point { x: 0.0, y: 0.0 });
+/// // Some(0.5)
+/// ```
+#[cfg(feature = "std")]
+pub fn circle_time_of_impact(a: &Circle, da: Point<f64>, b: &Circle, db: Point<f64>)
+	-> Option<f64>
+{
+	let v = da - db;
+	let d = a.center - b.center;
+	let r = a.radius + b.radius;
+
+	first_root(
+		v.x * v.x + v.y * v.y,
+		2.0 * (d.x * v.x + d.y * v.y),
+		d.x * d.x + d.y * d.y - r * r,
+	)
+}
+
+/// Earliest time in [0, 1] at which circle `c` moving by `d` touches the resting segment `s`.
+/// Returns 0 if they already overlap and `None` if they don't meet.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::geometry::{circle_segment_time_of_impact, Circle, Point, Segment};
+/// let c = Circle { center: Point { x: 0.0, y: 3.0 }, radius: 1.0 };
+/// let wall = Segment { a: Point { x: -5.0, y: 0.0 }, b: Point { x: 5.0, y: 0.0 } };
+/// let t = circle_segment_time_of_impact(&c, Point { x: 0.0, y: -4.0 }, &wall); // Some(0.5)
+/// ```
+#[cfg(feature = "std")]
+pub fn circle_segment_time_of_impact(c: &Circle, d: Point<f64>, s: &Segment<f64>) -> Option<f64>
+{
+	let e = s.b - s.a;
+	let len2 = e.x * e.x + e.y * e.y;
+
+	// End points behave like circles of radius 0
+	let cap = |p: Point<f64>| {
+		let end = Circle {
+			center: p,
+			radius: 0.0,
+		};
+		circle_time_of_impact(c, d, &end, Point { x: 0.0, y: 0.0 })
+	};
+
+	let mut best = match (cap(s.a), cap(s.b)) {
+		(Some(a), Some(b)) => Some(a.min(b)),
+		(a, b) => a.or(b),
+	};
+
+	if len2 == 0.0 {
+		return best;
+	}
+
+	// Signed distance of the center to the segment line over time
+	let len = len2.sqrt();
+	let n = Point {
+		x: -e.y / len,
+		y: e.x / len,
+	};
+	let w = c.center - s.a;
+	let dist = w.x * n.x + w.y * n.y;
+	let speed = d.x * n.x + d.y * n.y;
+
+	let on_segment = |t: f64| {
+		let p = w + Point {
+			x: d.x * t,
+			y: d.y * t,
+		};
+		let u = (p.x * e.x + p.y * e.y) / len2;
+		(0.0..=1.0).contains(&u)
+	};
+
+	let side_hit = if dist.abs() <= c.radius {
+		Some(0.0)
+	} else if speed != 0.0 {
+		let t = (dist - c.radius * dist.signum()) / -speed;
+		if (0.0..=1.0).contains(&t) {
+			Some(t)
+		} else {
+			None
+		}
+	} else {
+		None
+	};
+
+	if let Some(t) = side_hit.filter(|&t| on_segment(t)) {
+		best = Some(best.map_or(t, |b| b.min(t)));
+	}
+
+	best
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests
+{
+	use super::*;
+
+	fn p(x: f64, y: f64) -> Point<f64>
+	{
+		Point { x, y }
+	}
+
+	#[test]
+	fn test_aabb()
+	{
+		let a = Rect::from_corners(p(0.0, 0.0), p(1.0, 1.0));
+		let b = Rect::from_corners(p(3.0, 0.0), p(4.0, 1.0));
+
+		assert_eq!(
+			aabb_time_of_impact(&a, p(4.0, 0.0), &b, p(0.0, 0.0)),
+			Some(0.5)
+		);
+		assert_eq!(
+			aabb_time_of_impact(&a, p(1.0, 0.0), &b, p(-1.0, 0.0)),
+			Some(1.0)
+		);
+		assert_eq!(aabb_time_of_impact(&a, p(1.0, 0.0), &b, p(0.0, 0.0)), None);
+		assert_eq!(aabb_time_of_impact(&a, p(4.0, 5.0), &b, p(0.0, 0.0)), None);
+		assert_eq!(
+			aabb_time_of_impact(&a, p(0.0, 0.0), &a, p(9.0, 9.0)),
+			Some(0.0)
+		);
+
+		// Fast enough to tunnel through with discrete checks
+		let thin = Rect::from_corners(p(50.0, -10.0), p(50.1, 10.0));
+		let t = aabb_time_of_impact(&a, p(100.0, 0.0), &thin, p(0.0, 0.0)).unwrap();
+		assert!((t - 0.49).abs() < 1e-12);
+	}
+
+	#[cfg(feature = "std")]
+	#[test]
+	fn test_circles()
+	{
+		let a = Circle {
+			center: p(0.0, 0.0),
+			radius: 1.0,
+		};
+		let b = Circle {
+			center: p(10.0, 0.0),
+			radius: 1.0,
+		};
+
+		assert_eq!(
+			circle_time_of_impact(&a, p(16.0, 0.0), &b, p(0.0, 0.0)),
+			Some(0.5)
+		);
+		assert_eq!(
+			circle_time_of_impact(&a, p(4.0, 0.0), &b, p(-4.0, 0.0)),
+			Some(1.0)
+		);
+		assert_eq!(
+			circle_time_of_impact(&a, p(0.0, 16.0), &b, p(0.0, 0.0)),
+			None
+		);
+		assert_eq!(
+			circle_time_of_impact(&a, p(-16.0, 0.0), &b, p(0.0, 0.0)),
+			None
+		);
+		assert_eq!(
+			circle_time_of_impact(&a, p(0.0, 0.0), &a, p(0.0, 0.0)),
+			Some(0.0)
+		);
+	}
+
+	#[cfg(feature = "std")]
+	#[test]
+	fn test_circle_segment()
+	{
+		let c = Circle {
+			center: p(0.0, 3.0),
+			radius: 1.0,
+		};
+		let wall = Segment {
+			a: p(-5.0, 0.0),
+			b: p(5.0, 0.0),
+		};
+
+		assert_eq!(
+			circle_segment_time_of_impact(&c, p(0.0, -4.0), &wall),
+			Some(0.5)
+		);
+		assert_eq!(circle_segment_time_of_impact(&c, p(0.0, 4.0), &wall), None);
+		assert_eq!(
+			circle_segment_time_of_impact(&c, p(20.0, -4.0), &wall),
+			None
+		);
+
+		// Hitting the end point before the side
+		let t = circle_segment_time_of_impact(
+			&Circle {
+				center: p(7.0, 0.0),
+				radius: 1.0,
+			},
+			p(-4.0, 0.0),
+			&wall,
+		)
+		.unwrap();
+		assert!((t - 0.25).abs() < 1e-12);
+	}
+}
@@ -0,0 +1,242 @@
+use super::decompose::ear_clip;
+use super::{Arc, Circle, Path, Point, Polygon, RoundedRect, Sector};
+use crate::units::Quantity;
+use alloc::vec::Vec;
+use core::f64::consts::TAU;
+
+// -----------------------------------------------------------------------------
+// Tessellation
+// -----------------------------------------------------------------------------
+
+/// Filled shapes that can be turned into an indexed triangle list ready for the GPU. Curved
+/// borders are flattened so they stay within the tolerance, the triangles are counter clockwise.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::geometry::{Circle, Point, Tessellate};
+/// let c = Circle { center: Point { x: 0.0, y: 0.0 }, radius: 10.0 };
+/// let (vertices, indices) = c.tessellate(0.1);
+/// // vertices[0] is the center, every index triple is a triangle
+/// ```
+pub trait Tessellate
+{
+	/// Vertices and corner indices of the triangles.
+	fn tessellate(&self, tolerance: f64) -> (Vec<Point<f32>>, Vec<[u32; 3]>);
+}
+
+fn to_f32(points: &[Point<f64>]) -> Vec<Point<f32>>
+{
+	points
+		.iter()
+		.map(|p| Point {
+			x: p.x as f32,
+			y: p.y as f32,
+		})
+		.collect()
+}
+
+/// Triangle fan around the first point of a convex counter clockwise ring. With `closed` the
+/// last triangle wraps around to the second point.
+fn fan(points: &[Point<f64>], closed: bool) -> (Vec<Point<f32>>, Vec<[u32; 3]>)
+{
+	let n = points.len() as u32;
+	let mut indices: Vec<_> = (1..n.saturating_sub(1)).map(|i| [0, i, i + 1]).collect();
+	if closed && n > 2 {
+		indices.push([0, n - 1, 1]);
+	}
+
+	(to_f32(points), indices)
+}
+
+/// Center followed by the flattened arc, as a fan around the center.
+fn pie(arc: &Arc, tolerance: f64) -> (Vec<Point<f32>>, Vec<[u32; 3]>)
+{
+	let mut points = vec![arc.center];
+	points.extend(arc.flatten(tolerance));
+
+	// The last arc point repeats the first one for a whole circle
+	let closed = arc.sweep().value >= TAU;
+	if closed {
+		points.pop();
+	}
+
+	fan(&points, closed)
+}
+
+impl Tessellate for Circle
+{
+	fn tessellate(&self, tolerance: f64) -> (Vec<Point<f32>>, Vec<[u32; 3]>)
+	{
+		let arc = Arc {
+			center: self.center,
+			radius: self.radius,
+			start: Quantity::new(0.0),
+			end: Quantity::new(TAU),
+		};
+
+		pie(&arc, tolerance)
+	}
+}
+
+impl Tessellate for Sector
+{
+	fn tessellate(&self, tolerance: f64) -> (Vec<Point<f32>>, Vec<[u32; 3]>)
+	{
+		pie(&self.arc(), tolerance)
+	}
+}
+
+impl Tessellate for Arc
+{
+	/// Fills the circular segment between the arc and its chord, a whole circle for a full
+	/// sweep.
+	fn tessellate(&self, tolerance: f64) -> (Vec<Point<f32>>, Vec<[u32; 3]>)
+	{
+		if self.sweep().value >= TAU {
+			return pie(self, tolerance);
+		}
+
+		// Always convex, so the fan can start at the first end point
+		fan(&self.flatten(tolerance), false)
+	}
+}
+
+impl Tessellate for RoundedRect
+{
+	fn tessellate(&self, tolerance: f64) -> (Vec<Point<f32>>, Vec<[u32; 3]>)
+	{
+		fan(&self.to_polygon(tolerance).points, false)
+	}
+}
+
+impl Tessellate for Polygon
+{
+	/// Ear clips the simple polygon, the tolerance is unused.
+	fn tessellate(&self, _tolerance: f64) -> (Vec<Point<f32>>, Vec<[u32; 3]>)
+	{
+		let indices = ear_clip(&self.points)
+			.into_iter()
+			.map(|[a, b, c]| [a as u32, b as u32, c as u32])
+			.collect();
+
+		(to_f32(&self.points), indices)
+	}
+}
+
+impl Tessellate for Path
+{
+	/// Flattens the subpaths to polygons within the tolerance and ear clips each of them into
+	/// one shared triangle list. Every subpath is filled on its own, so an inner subpath is
+	/// filled over instead of cutting a hole, and the subpaths have to be simple.
+	fn tessellate(&self, tolerance: f64) -> (Vec<Point<f32>>, Vec<[u32; 3]>)
+	{
+		let mut vertices = Vec::new();
+		let mut indices = Vec::new();
+
+		for poly in self.to_polygons(tolerance) {
+			let offset = vertices.len() as u32;
+			let (v, i) = poly.tessellate(tolerance);
+			vertices.extend(v);
+			indices.extend(i.into_iter().map(|t| t.map(|k| k + offset)));
+		}
+
+		(vertices, indices)
+	}
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests
+{
+	use super::*;
+	use crate::geometry::Rect;
+	use core::f64::consts::PI;
+
+	fn p(x: f64, y: f64) -> Point<f64>
+	{
+		Point { x, y }
+	}
+
+	/// Total area of the triangles, failing on clockwise ones.
+	fn area((vertices, indices): &(Vec<Point<f32>>, Vec<[u32; 3]>)) -> f64
+	{
+		indices
+			.iter()
+			.map(|&[a, b, c]| {
+				let (a, b, c) = (
+					vertices[a as usize],
+					vertices[b as usize],
+					vertices[c as usize],
+				);
+				let cross = (b.x - a.x) as f64 * (c.y - a.y) as f64
+					- (b.y - a.y) as f64 * (c.x - a.x) as f64;
+				assert!(cross >= 0.0);
+				cross / 2.0
+			})
+			.sum()
+	}
+
+	#[test]
+	fn test_tessellate()
+	{
+		let circle = Circle {
+			center: p(1.0, 2.0),
+			radius: 3.0,
+		};
+		let mesh = circle.tessellate(1e-3);
+		assert_eq!(mesh.0.len(), mesh.1.len() + 1);
+		assert!((area(&mesh) - 9.0 * PI).abs() < 0.02);
+
+		let arc = Arc {
+			center: p(0.0, 0.0),
+			radius: 2.0,
+			start: Quantity::new(0.0),
+			end: Quantity::new(PI),
+		};
+		assert!((area(&arc.tessellate(1e-4)) - 2.0 * PI).abs() < 1e-2);
+
+		let sector = Sector {
+			center: p(0.0, 0.0),
+			radius: 2.0,
+			start: Quantity::new(0.0),
+			end: Quantity::new(1.5 * PI),
+		};
+		assert!((area(&sector.tessellate(1e-4)) - 3.0 * PI).abs() < 1e-2);
+
+		let rounded = RoundedRect {
+			rect: Rect::from_corners(p(0.0, 0.0), p(4.0, 2.0)),
+			radius: 0.5,
+		};
+		assert!((area(&rounded.tessellate(1e-4)) - rounded.area()).abs() < 1e-3);
+
+		let l = Polygon {
+			points: vec![
+				p(0.0, 0.0),
+				p(2.0, 0.0),
+				p(2.0, 1.0),
+				p(1.0, 1.0),
+				p(1.0, 2.0),
+				p(0.0, 2.0),
+			],
+		};
+		let mesh = l.tessellate(0.0);
+		assert_eq!(mesh.1.len(), 4);
+		assert!((area(&mesh) - 3.0).abs() < 1e-6);
+
+		// Cubic segment with the area 3/5 below it, next to a clockwise triangle
+		let path = Path::parse_svg("M0 0 C0 1 1 1 1 0 Z M2 0 L2 1 L3 0 Z").unwrap();
+		let mesh = path.tessellate(1e-4);
+		assert!(mesh.0.len() > 10);
+		assert!(mesh
+			.1
+			.iter()
+			.flatten()
+			.all(|&i| (i as usize) < mesh.0.len()));
+		assert!((area(&mesh) - 0.6 - 0.5).abs() < 1e-3);
+		assert!(Path::new().tessellate(0.1).1.is_empty());
+	}
+}
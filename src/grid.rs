@@ -0,0 +1,471 @@
+use crate::geometry::Point;
+use alloc::vec::Vec;
+use core::ops::{Index, IndexMut};
+
+mod contour;
+mod distance;
+mod filter;
+mod hex;
+mod index;
+mod iso;
+mod path;
+mod raster;
+mod region;
+mod visibility;
+
+pub use contour::marching_squares;
+#[cfg(feature = "std")]
+pub use distance::euclidean_distance_transform;
+pub use distance::{
+    chebyshev_distance_transform, manhattan_distance_transform,
+    squared_euclidean_distance_transform,
+};
+#[cfg(feature = "std")]
+pub use filter::gaussian_blur;
+pub use filter::{box_blur, convolve, sobel, Edge};
+pub use hex::{Cube, Hex};
+pub use index::{ClampedIndex, Torus, WrappingIndex};
+pub use iso::Isometric;
+pub use path::{astar, flow_field, integration_field, jump_point_search, path_length};
+pub use raster::{fill_polygon, Coverage};
+pub use region::{connected_components, flood_fill, Components, Region};
+pub use visibility::{bresenham, field_of_view, line_of_sight};
+
+const OFFSETS_4: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+const OFFSETS_8: [(isize, isize); 8] = [
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+];
+
+// -----------------------------------------------------------------------------
+// Grid2
+// -----------------------------------------------------------------------------
+
+/// Dense 2D grid stored in row-major order. Cells are addressed by `Point<usize>` or `(x, y)`
+/// where x is the column and y the row.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::grid::Grid2;
+/// use ctl::geometry::Point;
+/// let mut g = Grid2::new(3, 2, 0);
+/// g[(1, 0)] = 5;
+/// let v = g[Point::<usize>::from_coords(1, 0)]; // 5
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid2<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid2<T> {
+    /// Creates a grid filled with copies of `value`.
+    ///
+    /// # Arguments
+    ///
+    /// * `width` - Number of columns
+    /// * `height` - Number of rows
+    /// * `value` - Initial value of every cell
+    pub fn new(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid2 {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// Creates a grid from row-major cells.
+    ///
+    /// # Arguments
+    ///
+    /// * `width` - Number of columns
+    /// * `height` - Number of rows
+    /// * `cells` - Row-major cell values, must hold exactly `width * height` values
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ctl::grid::Grid2;
+    /// let g = Grid2::from_vec(2, 2, vec![1, 2, 3, 4]);
+    /// let v = g[(0, 1)]; // 3
+    /// ```
+    pub fn from_vec(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(cells.len(), width * height, "cell count doesn't match size");
+        Grid2 {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Creates a grid by calling `f` for every cell position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ctl::grid::Grid2;
+    /// let g = Grid2::from_fn(3, 3, |p| p.x * p.y);
+    /// let v = g[(2, 2)]; // 4
+    /// ```
+    pub fn from_fn<F: FnMut(Point<usize>) -> T>(width: usize, height: usize, mut f: F) -> Self {
+        let mut cells = Vec::with_capacity(width * height);
+
+        for y in 0..height {
+            for x in 0..width {
+                cells.push(f(Point { x, y }));
+            }
+        }
+
+        Grid2 {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Row-major slice of all cells.
+    pub fn as_slice(&self) -> &[T] {
+        &self.cells
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        &mut self.cells
+    }
+
+    pub fn into_vec(self) -> Vec<T> {
+        self.cells
+    }
+
+    /// Checks whether the position lies inside the grid.
+    pub fn contains(&self, p: Point<usize>) -> bool {
+        p.x < self.width && p.y < self.height
+    }
+
+    pub fn get(&self, p: Point<usize>) -> Option<&T> {
+        if self.contains(p) {
+            Some(&self.cells[p.y * self.width + p.x])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, p: Point<usize>) -> Option<&mut T> {
+        if self.contains(p) {
+            Some(&mut self.cells[p.y * self.width + p.x])
+        } else {
+            None
+        }
+    }
+
+    /// Iterates over all cells in row-major order.
+    pub fn iter(&self) -> core::slice::Iter<'_, T> {
+        self.cells.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> core::slice::IterMut<'_, T> {
+        self.cells.iter_mut()
+    }
+
+    /// Iterates over all cell positions in row-major order.
+    pub fn positions(&self) -> impl Iterator<Item = Point<usize>> {
+        let w = self.width;
+        (0..self.width * self.height).map(move |i| Point { x: i % w, y: i / w })
+    }
+
+    /// Cells of row `y`.
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn row_mut(&mut self, y: usize) -> &mut [T] {
+        &mut self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    /// Iterates over the cells of column `x` from top to bottom.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {} out of bounds", x);
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    /// Iterates over all rows.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // chunks panics on a zero size, which only happens for grids without cells
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// Iterates over the positions of the up to 4 edge-adjacent neighbours of `p` inside the
+    /// grid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ctl::grid::Grid2;
+    /// use ctl::geometry::Point;
+    /// let g = Grid2::new(3, 3, 0);
+    /// let n = g.neighbors4(Point { x: 0, y: 0 }).count(); // 2
+    /// ```
+    pub fn neighbors4(&self, p: Point<usize>) -> impl Iterator<Item = Point<usize>> {
+        self.offset_iter(p, &OFFSETS_4)
+    }
+
+    /// Iterates over the positions of the up to 8 edge- or corner-adjacent neighbours of `p`
+    /// inside the grid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ctl::grid::Grid2;
+    /// use ctl::geometry::Point;
+    /// let g = Grid2::new(3, 3, 0);
+    /// let n = g.neighbors8(Point { x: 1, y: 1 }).count(); // 8
+    /// ```
+    pub fn neighbors8(&self, p: Point<usize>) -> impl Iterator<Item = Point<usize>> {
+        self.offset_iter(p, &OFFSETS_8)
+    }
+
+    fn offset_iter(
+        &self,
+        p: Point<usize>,
+        offsets: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = Point<usize>> {
+        let (w, h) = (self.width, self.height);
+
+        offsets.iter().filter_map(move |&(dx, dy)| {
+            let x = p.x.checked_add_signed(dx)?;
+            let y = p.y.checked_add_signed(dy)?;
+
+            if x < w && y < h {
+                Some(Point { x, y })
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Creates a new grid by applying `f` to every cell.
+    pub fn map<U, F: FnMut(&T) -> U>(&self, f: F) -> Grid2<U> {
+        Grid2 {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// Replaces the 4-connected region of cells equal to the one at `start` with `value`.
+    /// Returns the number of changed cells.
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - Seed position of the fill
+    /// * `value` - New value of the region
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ctl::grid::Grid2;
+    /// use ctl::geometry::Point;
+    /// let mut g = Grid2::from_vec(3, 1, vec![0, 0, 1]);
+    /// let n = g.flood_fill(Point { x: 0, y: 0 }, 2); // 2, g == [2, 2, 1]
+    /// ```
+    pub fn flood_fill(&mut self, start: Point<usize>, value: T) -> usize
+    where
+        T: PartialEq + Clone,
+    {
+        let target = match self.get(start) {
+            Some(t) if *t != value => t.clone(),
+            _ => return 0,
+        };
+
+        let region = flood_fill(self, start, |v| *v == target);
+
+        for &p in &region {
+            self[p] = value.clone();
+        }
+
+        region.len()
+    }
+
+    /// Transposed copy of the grid, swapping rows and columns.
+    pub fn transpose(&self) -> Grid2<T>
+    where
+        T: Clone,
+    {
+        Grid2::from_fn(self.height, self.width, |p| self[(p.y, p.x)].clone())
+    }
+
+    /// Copy of the grid rotated clockwise by 90 degrees.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ctl::grid::Grid2;
+    /// let g = Grid2::from_vec(2, 1, vec![1, 2]);
+    /// let r = g.rotate90(); // 1x2 grid [1, 2] with 1 on top
+    /// ```
+    pub fn rotate90(&self) -> Grid2<T>
+    where
+        T: Clone,
+    {
+        let h = self.height;
+        Grid2::from_fn(self.height, self.width, |p| {
+            self[(p.y, h - 1 - p.x)].clone()
+        })
+    }
+
+    /// Copy of the grid rotated by 180 degrees.
+    pub fn rotate180(&self) -> Grid2<T>
+    where
+        T: Clone,
+    {
+        let mut cells = self.cells.clone();
+        cells.reverse();
+        Grid2::from_vec(self.width, self.height, cells)
+    }
+
+    /// Copy of the grid rotated counterclockwise by 90 degrees.
+    pub fn rotate270(&self) -> Grid2<T>
+    where
+        T: Clone,
+    {
+        let w = self.width;
+        Grid2::from_fn(self.height, self.width, |p| {
+            self[(w - 1 - p.y, p.x)].clone()
+        })
+    }
+
+    /// Mirrors the grid in place along the vertical axis, swapping left and right.
+    pub fn flip_horizontal(&mut self) {
+        for y in 0..self.height {
+            self.row_mut(y).reverse();
+        }
+    }
+
+    /// Mirrors the grid in place along the horizontal axis, swapping top and bottom.
+    pub fn flip_vertical(&mut self) {
+        for y in 0..self.height / 2 {
+            let (top, bottom) = self.cells.split_at_mut((self.height - 1 - y) * self.width);
+            top[y * self.width..(y + 1) * self.width].swap_with_slice(&mut bottom[..self.width]);
+        }
+    }
+}
+
+impl<T> Index<Point<usize>> for Grid2<T> {
+    type Output = T;
+
+    fn index(&self, p: Point<usize>) -> &T {
+        self.get(p).expect("grid position out of bounds")
+    }
+}
+
+impl<T> IndexMut<Point<usize>> for Grid2<T> {
+    fn index_mut(&mut self, p: Point<usize>) -> &mut T {
+        self.get_mut(p).expect("grid position out of bounds")
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid2<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        &self[Point { x, y }]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid2<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        &mut self[Point { x, y }]
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn p(x: usize, y: usize) -> Point<usize> {
+        Point { x, y }
+    }
+
+    #[test]
+    fn test_indexing() {
+        let mut g = Grid2::from_fn(3, 2, |p| p.x + 10 * p.y);
+
+        assert_eq!(g[(2, 1)], 12);
+        assert_eq!(g[p(1, 0)], 1);
+        assert_eq!(g.get(p(3, 0)), None);
+        assert_eq!(g.row(1), &[10, 11, 12]);
+        assert_eq!(g.column(1).copied().collect::<Vec<_>>(), vec![1, 11]);
+        assert_eq!(g.rows().count(), 2);
+
+        g[(0, 1)] = 7;
+        assert_eq!(g.as_slice(), &[0, 1, 2, 7, 11, 12]);
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let g = Grid2::new(3, 3, 0);
+
+        assert_eq!(
+            g.neighbors4(p(0, 0)).collect::<Vec<_>>(),
+            vec![p(1, 0), p(0, 1)]
+        );
+        assert_eq!(g.neighbors4(p(1, 1)).count(), 4);
+        assert_eq!(g.neighbors8(p(1, 1)).count(), 8);
+        assert_eq!(g.neighbors8(p(2, 2)).count(), 3);
+    }
+
+    #[test]
+    fn test_flood_fill() {
+        #[rustfmt::skip]
+        let mut g = Grid2::from_vec(4, 3, vec![
+            0, 0, 1, 0,
+            1, 0, 1, 0,
+            0, 0, 1, 0,
+        ]);
+
+        assert_eq!(g.flood_fill(p(0, 0), 2), 5);
+        assert_eq!(g.as_slice(), &[2, 2, 1, 0, 1, 2, 1, 0, 2, 2, 1, 0]);
+        assert_eq!(g.flood_fill(p(0, 0), 2), 0);
+    }
+
+    #[test]
+    fn test_transform() {
+        let g = Grid2::from_vec(3, 2, vec![1, 2, 3, 4, 5, 6]);
+
+        assert_eq!(g.rotate90(), Grid2::from_vec(2, 3, vec![4, 1, 5, 2, 6, 3]));
+        assert_eq!(g.rotate270(), Grid2::from_vec(2, 3, vec![3, 6, 2, 5, 1, 4]));
+        assert_eq!(g.rotate180(), Grid2::from_vec(3, 2, vec![6, 5, 4, 3, 2, 1]));
+        assert_eq!(g.transpose(), Grid2::from_vec(2, 3, vec![1, 4, 2, 5, 3, 6]));
+        assert_eq!(g.rotate90().rotate90(), g.rotate180());
+
+        let mut h = g.clone();
+        h.flip_horizontal();
+        assert_eq!(h.as_slice(), &[3, 2, 1, 6, 5, 4]);
+
+        let mut v = Grid2::from_vec(2, 3, vec![1, 2, 3, 4, 5, 6]);
+        v.flip_vertical();
+        assert_eq!(v.as_slice(), &[5, 6, 3, 4, 1, 2]);
+    }
+}
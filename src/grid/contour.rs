@@ -0,0 +1,195 @@
+use super::Grid2;
+use crate::geometry::Point;
+use alloc::vec::Vec;
+
+// -----------------------------------------------------------------------------
+// Marching squares
+// -----------------------------------------------------------------------------
+
+const NONE: usize = usize::MAX;
+
+/// Extracts the iso-contours of a scalar field using marching squares. Samples lie at integer
+/// coordinates (`grid[(x, y)]` at point (x, y)) and cells with values `>= threshold` count as
+/// inside. Ambiguous saddle cells are resolved using the average of their corners.
+///
+/// Every contour is returned as a polyline. Closed contours repeat their first point at the
+/// end, open ones start and end at the grid border.
+///
+/// # Arguments
+///
+/// * `grid` - Scalar field samples
+/// * `threshold` - Iso value of the contours
+///
+/// # Examples
+///
+/// ```
+/// use ctl::grid::{marching_squares, Grid2};
+/// let g = Grid2::from_fn(3, 3, |p| if p.x == 1 && p.y == 1 { 1.0 } else { 0.0 });
+/// let c = marching_squares(&g, 0.5); // 1 closed diamond around (1, 1)
+/// ```
+pub fn marching_squares(grid: &Grid2<f64>, threshold: f64) -> Vec<Vec<Point<f64>>> {
+    let (w, h) = (grid.width(), grid.height());
+
+    if w < 2 || h < 2 {
+        return Vec::new();
+    }
+
+    // Nodes are the cell edges crossed by a contour. Horizontal edges come first, followed by
+    // the vertical ones.
+    let horizontal = |x: usize, y: usize| y * (w - 1) + x;
+    let vertical = |x: usize, y: usize| h * (w - 1) + y * w + x;
+    let node_count = h * (w - 1) + (h - 1) * w;
+
+    let node_pos = |n: usize| -> Point<f64> {
+        let (a, b) = if n < h * (w - 1) {
+            let (x, y) = (n % (w - 1), n / (w - 1));
+            ((x, y), (x + 1, y))
+        } else {
+            let n = n - h * (w - 1);
+            let (x, y) = (n % w, n / w);
+            ((x, y), (x, y + 1))
+        };
+
+        let (va, vb) = (grid[a], grid[b]);
+        let t = if va == vb {
+            0.5
+        } else {
+            (threshold - va) / (vb - va)
+        };
+
+        Point {
+            x: a.0 as f64 + t * (b.0 as f64 - a.0 as f64),
+            y: a.1 as f64 + t * (b.1 as f64 - a.1 as f64),
+        }
+    };
+
+    let mut segments: Vec<(usize, usize)> = Vec::new();
+
+    for y in 0..h - 1 {
+        for x in 0..w - 1 {
+            let corners = [
+                grid[(x, y)],
+                grid[(x + 1, y)],
+                grid[(x + 1, y + 1)],
+                grid[(x, y + 1)],
+            ];
+            let case = corners
+                .iter()
+                .fold(0, |c, &v| (c << 1) | (v >= threshold) as usize);
+
+            let top = horizontal(x, y);
+            let right = vertical(x + 1, y);
+            let bottom = horizontal(x, y + 1);
+            let left = vertical(x, y);
+            let center_inside = corners.iter().sum::<f64>() / 4.0 >= threshold;
+
+            match case {
+                1 | 14 => segments.push((left, bottom)),
+                2 | 13 => segments.push((bottom, right)),
+                3 | 12 => segments.push((left, right)),
+                4 | 11 => segments.push((top, right)),
+                6 | 9 => segments.push((top, bottom)),
+                7 | 8 => segments.push((left, top)),
+                5 | 10 => {
+                    // Corner pattern tl, tr, br, bl = 0101 or 1010. The inside corners are
+                    // connected through the center when it is inside as well.
+                    if (case == 5) == center_inside {
+                        segments.push((left, top));
+                        segments.push((bottom, right));
+                    } else {
+                        segments.push((top, right));
+                        segments.push((left, bottom));
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    let mut adjacent = vec![[NONE; 2]; node_count];
+
+    for (i, &(a, b)) in segments.iter().enumerate() {
+        for n in [a, b] {
+            let slot = if adjacent[n][0] == NONE { 0 } else { 1 };
+            adjacent[n][slot] = i;
+        }
+    }
+
+    let mut used = vec![false; segments.len()];
+    let mut contours = Vec::new();
+
+    let mut walk = |start: usize, used: &mut Vec<bool>| {
+        let mut line = vec![node_pos(start)];
+        let mut node = start;
+
+        while let Some(&s) = adjacent[node].iter().find(|&&s| s != NONE && !used[s]) {
+            used[s] = true;
+            let (a, b) = segments[s];
+            node = if a == node { b } else { a };
+            line.push(node_pos(node));
+        }
+
+        contours.push(line);
+    };
+
+    // Open contours start at nodes with a single segment, everything left afterwards is closed
+    for (n, &[a, b]) in adjacent.iter().enumerate() {
+        if a != NONE && b == NONE && !used[a] {
+            walk(n, &mut used);
+        }
+    }
+
+    for s in 0..segments.len() {
+        if !used[s] {
+            walk(segments[s].0, &mut used);
+        }
+    }
+
+    contours
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_closed_contour() {
+        let g = Grid2::from_fn(3, 3, |p| if p.x == 1 && p.y == 1 { 1.0 } else { 0.0 });
+        let c = marching_squares(&g, 0.5);
+
+        assert_eq!(c.len(), 1);
+        assert_eq!(c[0].len(), 5);
+        assert_eq!(c[0].first(), c[0].last());
+
+        for p in &c[0] {
+            assert!(((p.x - 1.0).abs() + (p.y - 1.0).abs() - 0.5).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_open_contour() {
+        let g = Grid2::from_fn(4, 3, |p| p.x as f64);
+        let c = marching_squares(&g, 1.5);
+
+        assert_eq!(c.len(), 1);
+        assert_eq!(c[0].len(), 3);
+
+        for p in &c[0] {
+            assert_eq!(p.x, 1.5);
+        }
+    }
+
+    #[test]
+    fn test_saddle() {
+        let g = Grid2::from_vec(2, 2, vec![1.0, 0.0, 0.0, 1.0]);
+
+        assert_eq!(marching_squares(&g, 0.4).len(), 2);
+        assert_eq!(marching_squares(&g, 0.6).len(), 2);
+        assert!(marching_squares(&g, 2.0).is_empty());
+        assert!(marching_squares(&Grid2::new(1, 5, 0.0), 0.5).is_empty());
+    }
+}
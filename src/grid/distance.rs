@@ -0,0 +1,266 @@
+use super::Grid2;
+use alloc::vec::Vec;
+
+// -----------------------------------------------------------------------------
+// Chamfer transforms
+// -----------------------------------------------------------------------------
+
+/// Two pass chamfer transform. `forward` holds the neighbour offsets visited before a cell in
+/// row-major order, the backward pass uses the mirrored offsets.
+fn chamfer(grid: &Grid2<bool>, forward: &[(isize, isize)]) -> Grid2<u32> {
+    let (w, h) = (grid.width() as isize, grid.height() as isize);
+    let mut dist = grid.map(|&set| if set { 0 } else { u32::MAX });
+
+    let relax = |dist: &mut Grid2<u32>, x: isize, y: isize, sign: isize| {
+        for &(dx, dy) in forward {
+            let (nx, ny) = (x + sign * dx, y + sign * dy);
+
+            if nx >= 0 && ny >= 0 && nx < w && ny < h {
+                let d = dist[(nx as usize, ny as usize)].saturating_add(1);
+                let c = &mut dist[(x as usize, y as usize)];
+                *c = (*c).min(d);
+            }
+        }
+    };
+
+    for y in 0..h {
+        for x in 0..w {
+            relax(&mut dist, x, y, 1);
+        }
+    }
+
+    for y in (0..h).rev() {
+        for x in (0..w).rev() {
+            relax(&mut dist, x, y, -1);
+        }
+    }
+
+    dist
+}
+
+/// Calculates for every cell the Manhattan (4-connected) distance to the nearest set cell.
+/// Cells are `u32::MAX` when the grid has no set cell.
+///
+/// # Arguments
+///
+/// * `grid` - Grid marking the target cells
+///
+/// # Examples
+///
+/// ```
+/// use ctl::grid::{manhattan_distance_transform, Grid2};
+/// let g = Grid2::from_vec(3, 1, vec![true, false, false]);
+/// let d = manhattan_distance_transform(&g); // [0, 1, 2]
+/// ```
+pub fn manhattan_distance_transform(grid: &Grid2<bool>) -> Grid2<u32> {
+    chamfer(grid, &[(-1, 0), (0, -1)])
+}
+
+/// Calculates for every cell the Chebyshev (8-connected) distance to the nearest set cell.
+/// Cells are `u32::MAX` when the grid has no set cell.
+///
+/// # Arguments
+///
+/// * `grid` - Grid marking the target cells
+///
+/// # Examples
+///
+/// ```
+/// use ctl::grid::{chebyshev_distance_transform, Grid2};
+/// let g = Grid2::from_fn(3, 3, |p| p.x == 0 && p.y == 0);
+/// let d = chebyshev_distance_transform(&g); // d[(2, 2)] == 2
+/// ```
+pub fn chebyshev_distance_transform(grid: &Grid2<bool>) -> Grid2<u32> {
+    chamfer(grid, &[(-1, 0), (-1, -1), (0, -1), (1, -1)])
+}
+
+// -----------------------------------------------------------------------------
+// Euclidean transform
+// -----------------------------------------------------------------------------
+
+/// Lower envelope of the parabolas rooted at every finite sample of `f` (Felzenszwalb and
+/// Huttenlocher). Writes the squared distances to `out`.
+fn envelope_1d(f: &[f64], out: &mut [f64], v: &mut Vec<usize>, z: &mut Vec<f64>) {
+    v.clear();
+    z.clear();
+
+    for (q, &fq) in f.iter().enumerate() {
+        if !fq.is_finite() {
+            continue;
+        }
+
+        let qf = q as f64;
+
+        loop {
+            match v.last() {
+                Some(&p) => {
+                    let pf = p as f64;
+                    let s = ((fq + qf * qf) - (f[p] + pf * pf)) / (2.0 * (qf - pf));
+
+                    if s <= *z.last().unwrap() {
+                        v.pop();
+                        z.pop();
+                        continue;
+                    }
+
+                    v.push(q);
+                    z.push(s);
+                }
+                None => {
+                    v.push(q);
+                    z.push(f64::NEG_INFINITY);
+                }
+            }
+
+            break;
+        }
+    }
+
+    if v.is_empty() {
+        out.iter_mut().for_each(|o| *o = f64::INFINITY);
+        return;
+    }
+
+    let mut k = 0;
+
+    for (q, o) in out.iter_mut().enumerate() {
+        let qf = q as f64;
+
+        while k + 1 < v.len() && z[k + 1] < qf {
+            k += 1;
+        }
+
+        let d = qf - v[k] as f64;
+        *o = d * d + f[v[k]];
+    }
+}
+
+/// Calculates for every cell the exact squared Euclidean distance to the nearest set cell in
+/// O(n) using the Felzenszwalb-Huttenlocher algorithm. Cells are infinite when the grid has no
+/// set cell.
+///
+/// # Arguments
+///
+/// * `grid` - Grid marking the target cells
+///
+/// # Examples
+///
+/// ```
+/// use ctl::grid::{squared_euclidean_distance_transform, Grid2};
+/// let g = Grid2::from_fn(3, 3, |p| p.x == 0 && p.y == 0);
+/// let d = squared_euclidean_distance_transform(&g); // d[(2, 1)] == 5.0
+/// ```
+pub fn squared_euclidean_distance_transform(grid: &Grid2<bool>) -> Grid2<f64> {
+    let (w, h) = (grid.width(), grid.height());
+    let mut dist = grid.map(|&set| if set { 0.0 } else { f64::INFINITY });
+
+    let n = w.max(h);
+    let (mut f, mut out) = (vec![0.0; n], vec![0.0; n]);
+    let (mut v, mut z) = (Vec::with_capacity(n), Vec::with_capacity(n));
+
+    for x in 0..w {
+        for y in 0..h {
+            f[y] = dist[(x, y)];
+        }
+
+        envelope_1d(&f[..h], &mut out[..h], &mut v, &mut z);
+
+        for y in 0..h {
+            dist[(x, y)] = out[y];
+        }
+    }
+
+    for y in 0..h {
+        f[..w].copy_from_slice(dist.row(y));
+        envelope_1d(&f[..w], &mut out[..w], &mut v, &mut z);
+        dist.row_mut(y).copy_from_slice(&out[..w]);
+    }
+
+    dist
+}
+
+/// Calculates for every cell the exact Euclidean distance to the nearest set cell. See
+/// [`squared_euclidean_distance_transform`].
+///
+/// # Arguments
+///
+/// * `grid` - Grid marking the target cells
+///
+/// # Examples
+///
+/// ```
+/// use ctl::grid::{euclidean_distance_transform, Grid2};
+/// let g = Grid2::from_fn(3, 3, |p| p.x == 0 && p.y == 0);
+/// let d = euclidean_distance_transform(&g); // d[(1, 1)] == 2f64.sqrt()
+/// ```
+#[cfg(feature = "std")]
+pub fn euclidean_distance_transform(grid: &Grid2<bool>) -> Grid2<f64> {
+    squared_euclidean_distance_transform(grid).map(|d| d.sqrt())
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Grid2<bool> {
+        #[rustfmt::skip]
+        let cells = vec![
+            false, false, false, false, false,
+            false, true,  false, false, false,
+            false, false, false, false, false,
+            false, false, false, false, true,
+        ];
+
+        Grid2::from_vec(5, 4, cells)
+    }
+
+    fn brute_force(grid: &Grid2<bool>, metric: impl Fn(i64, i64) -> i64) -> Grid2<i64> {
+        Grid2::from_fn(grid.width(), grid.height(), |p| {
+            grid.positions()
+                .filter(|&q| grid[q])
+                .map(|q| metric(p.x as i64 - q.x as i64, p.y as i64 - q.y as i64))
+                .min()
+                .unwrap()
+        })
+    }
+
+    #[test]
+    fn test_chamfer() {
+        let g = sample();
+
+        assert_eq!(
+            manhattan_distance_transform(&g).map(|&d| d as i64),
+            brute_force(&g, |dx, dy| dx.abs() + dy.abs())
+        );
+        assert_eq!(
+            chebyshev_distance_transform(&g).map(|&d| d as i64),
+            brute_force(&g, |dx, dy| dx.abs().max(dy.abs()))
+        );
+
+        let empty = Grid2::new(2, 2, false);
+        assert!(manhattan_distance_transform(&empty)
+            .iter()
+            .all(|&d| d == u32::MAX));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_euclidean() {
+        let g = sample();
+
+        assert_eq!(
+            squared_euclidean_distance_transform(&g).map(|&d| d as i64),
+            brute_force(&g, |dx, dy| dx * dx + dy * dy)
+        );
+        assert_eq!(euclidean_distance_transform(&g)[(3, 1)], 2.0);
+
+        let empty = Grid2::new(3, 2, false);
+        assert!(squared_euclidean_distance_transform(&empty)
+            .iter()
+            .all(|d| d.is_infinite()));
+    }
+}
@@ -0,0 +1,213 @@
+use super::{ClampedIndex, Grid2, WrappingIndex};
+#[cfg(feature = "std")]
+use alloc::vec::Vec;
+
+// -----------------------------------------------------------------------------
+// Convolution
+// -----------------------------------------------------------------------------
+
+/// Handling of cells read outside of the grid by filters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Edge {
+    /// Repeats the closest border cell
+    Clamp,
+    /// Continues on the opposite side
+    Wrap,
+    /// Reflects at the border cell without repeating it, so -1 reads 1
+    Mirror,
+}
+
+impl Edge {
+    fn index(self, i: isize, len: usize) -> usize {
+        let n = len as isize;
+
+        match self {
+            Edge::Clamp => ClampedIndex::new(len).get(i),
+            Edge::Wrap => WrappingIndex::new(len).get(i),
+            Edge::Mirror if n == 1 => 0,
+            Edge::Mirror => {
+                let i = i.rem_euclid(2 * n - 2);
+                (if i < n { i } else { 2 * n - 2 - i }) as usize
+            }
+        }
+    }
+}
+
+/// Weighted sum over the kernel laid on top of every cell, without flipping it.
+fn correlate(grid: &Grid2<f64>, kernel: &Grid2<f64>, edge: Edge) -> Grid2<f64> {
+    let (cx, cy) = (
+        (kernel.width() / 2) as isize,
+        (kernel.height() / 2) as isize,
+    );
+
+    Grid2::from_fn(grid.width(), grid.height(), |p| {
+        let mut sum = 0.0;
+
+        for ky in 0..kernel.height() {
+            let y = edge.index(p.y as isize + ky as isize - cy, grid.height());
+
+            for kx in 0..kernel.width() {
+                let x = edge.index(p.x as isize + kx as isize - cx, grid.width());
+                sum += kernel[(kx, ky)] * grid[(x, y)];
+            }
+        }
+
+        sum
+    })
+}
+
+/// Applies a symmetric 1D kernel along the rows and then along the columns.
+fn separable(grid: &Grid2<f64>, weights: &[f64], edge: Edge) -> Grid2<f64> {
+    let row = Grid2::from_vec(weights.len(), 1, weights.to_vec());
+    let column = Grid2::from_vec(1, weights.len(), weights.to_vec());
+
+    correlate(&correlate(grid, &row, edge), &column, edge)
+}
+
+/// Convolves the grid with a kernel. The kernel is centered on cell (width / 2, height / 2),
+/// so odd sizes are symmetric around the filtered cell.
+///
+/// # Arguments
+///
+/// * `grid` - Filtered grid, must not be empty
+/// * `kernel` - Weights of the neighbourhood
+/// * `edge` - Handling of cells outside of the grid
+///
+/// # Examples
+///
+/// ```
+/// use ctl::grid::{convolve, Edge, Grid2};
+/// let g = Grid2::from_vec(3, 1, vec![0.0, 1.0, 0.0]);
+/// let k = Grid2::from_vec(3, 1, vec![1.0, 2.0, 3.0]);
+/// let r = convolve(&g, &k, Edge::Clamp); // [1, 2, 3]
+/// ```
+pub fn convolve(grid: &Grid2<f64>, kernel: &Grid2<f64>, edge: Edge) -> Grid2<f64> {
+    let mut flipped = kernel.clone();
+    flipped.flip_horizontal();
+    flipped.flip_vertical();
+
+    correlate(grid, &flipped, edge)
+}
+
+/// Mean over the square of side `2 * radius + 1` around every cell.
+///
+/// # Arguments
+///
+/// * `grid` - Filtered grid, must not be empty
+/// * `radius` - Reach of the filter in cells
+/// * `edge` - Handling of cells outside of the grid
+pub fn box_blur(grid: &Grid2<f64>, radius: usize, edge: Edge) -> Grid2<f64> {
+    let n = 2 * radius + 1;
+    separable(grid, &vec![1.0 / n as f64; n], edge)
+}
+
+/// Gaussian blur with a kernel cut off at 3 standard deviations.
+///
+/// # Arguments
+///
+/// * `grid` - Filtered grid, must not be empty
+/// * `sigma` - Standard deviation in cells
+/// * `edge` - Handling of cells outside of the grid
+///
+/// # Examples
+///
+/// ```
+/// use ctl::grid::{gaussian_blur, Edge, Grid2};
+/// let mut g = Grid2::new(9, 9, 0.0);
+/// g[(4, 4)] = 1.0;
+/// let r = gaussian_blur(&g, 1.0, Edge::Clamp); // r[(4, 4)] ~ 0.16, sums up to 1
+/// ```
+#[cfg(feature = "std")]
+pub fn gaussian_blur(grid: &Grid2<f64>, sigma: f64, edge: Edge) -> Grid2<f64> {
+    let radius = (3.0 * sigma).ceil() as isize;
+    let mut weights: Vec<f64> = (-radius..=radius)
+        .map(|i| (-((i * i) as f64) / (2.0 * sigma * sigma)).exp())
+        .collect();
+
+    let total: f64 = weights.iter().sum();
+    weights.iter_mut().for_each(|w| *w /= total);
+
+    separable(grid, &weights, edge)
+}
+
+/// Gradients of the grid with the 3x3 Sobel operator, positive where values grow with x and y
+/// respectively. A ramp increasing by 1 per cell gives 8.
+///
+/// # Arguments
+///
+/// * `grid` - Filtered grid, must not be empty
+/// * `edge` - Handling of cells outside of the grid
+///
+/// # Examples
+///
+/// ```
+/// use ctl::grid::{sobel, Edge, Grid2};
+/// let g = Grid2::from_fn(4, 4, |p| p.x as f64);
+/// let (gx, gy) = sobel(&g, Edge::Mirror); // gx = 8, gy = 0 everywhere
+/// ```
+pub fn sobel(grid: &Grid2<f64>, edge: Edge) -> (Grid2<f64>, Grid2<f64>) {
+    let kx = Grid2::from_vec(3, 3, vec![-1.0, 0.0, 1.0, -2.0, 0.0, 2.0, -1.0, 0.0, 1.0]);
+    let ky = kx.transpose();
+
+    (correlate(grid, &kx, edge), correlate(grid, &ky, edge))
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_edges() {
+        let idx = |e: Edge| (-3..7).map(|i| e.index(i, 4)).collect::<Vec<_>>();
+
+        assert_eq!(idx(Edge::Clamp), vec![0, 0, 0, 0, 1, 2, 3, 3, 3, 3]);
+        assert_eq!(idx(Edge::Wrap), vec![1, 2, 3, 0, 1, 2, 3, 0, 1, 2]);
+        assert_eq!(idx(Edge::Mirror), vec![3, 2, 1, 0, 1, 2, 3, 2, 1, 0]);
+        assert_eq!(Edge::Mirror.index(-2, 1), 0);
+
+        let g = Grid2::from_vec(4, 1, vec![1.0, 2.0, 3.0, 4.0]);
+        let shift = Grid2::from_vec(3, 1, vec![1.0, 0.0, 0.0]);
+        assert_eq!(
+            convolve(&g, &shift, Edge::Wrap).as_slice(),
+            &[2.0, 3.0, 4.0, 1.0]
+        );
+        assert_eq!(
+            convolve(&g, &shift, Edge::Clamp).as_slice(),
+            &[2.0, 3.0, 4.0, 4.0]
+        );
+        assert_eq!(
+            convolve(&g, &shift, Edge::Mirror).as_slice(),
+            &[2.0, 3.0, 4.0, 3.0]
+        );
+    }
+
+    #[test]
+    fn test_filters() {
+        let g = Grid2::from_fn(6, 5, |p| (p.x * 3 + p.y * p.y) as f64);
+
+        // Blurs keep the total with wrapping borders
+        let total: f64 = g.iter().sum();
+        let boxed = box_blur(&g, 1, Edge::Wrap);
+        assert!((boxed.iter().sum::<f64>() - total).abs() < 1e-9);
+        assert!(
+            (boxed[(2, 2)] - (3.0 * (3.0 + 6.0 + 9.0) + 3.0 * (1.0 + 4.0 + 9.0)) / 9.0).abs()
+                < 1e-12
+        );
+
+        #[cfg(feature = "std")]
+        {
+            let blurred = gaussian_blur(&g, 0.8, Edge::Wrap);
+            assert!((blurred.iter().sum::<f64>() - total).abs() < 1e-9);
+        }
+
+        let (gx, gy) = sobel(&g, Edge::Clamp);
+        assert_eq!(gx[(2, 2)], 24.0);
+        assert_eq!(gy[(2, 2)], 32.0);
+        assert_eq!(gx[(0, 0)], 12.0);
+    }
+}
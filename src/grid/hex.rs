@@ -0,0 +1,317 @@
+use crate::geometry::Point;
+use alloc::vec::Vec;
+use core::ops::{Add, Mul, Sub};
+
+const SQRT_3: f64 = 1.732_050_807_568_877_2;
+
+fn floor(x: f64) -> i32 {
+    let i = x as i32;
+    if (i as f64) > x {
+        i - 1
+    } else {
+        i
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Coordinates
+// -----------------------------------------------------------------------------
+
+/// Axial coordinate of a cell on a hexagonal grid. The third cube coordinate is implied by
+/// q + r + s = 0, see [`Cube`].
+///
+/// Pixel conversions use pointy-top hexagons, with q growing to the right and r growing down
+/// to the right.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Hex {
+    pub q: i32,
+    pub r: i32,
+}
+
+/// Cube coordinate of a cell on a hexagonal grid, always satisfying q + r + s = 0.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Cube {
+    pub q: i32,
+    pub r: i32,
+    pub s: i32,
+}
+
+impl From<Hex> for Cube {
+    fn from(h: Hex) -> Self {
+        Cube {
+            q: h.q,
+            r: h.r,
+            s: h.s(),
+        }
+    }
+}
+
+impl From<Cube> for Hex {
+    fn from(c: Cube) -> Self {
+        Hex { q: c.q, r: c.r }
+    }
+}
+
+impl Add for Hex {
+    type Output = Hex;
+
+    fn add(self, o: Hex) -> Hex {
+        Hex {
+            q: self.q + o.q,
+            r: self.r + o.r,
+        }
+    }
+}
+
+impl Sub for Hex {
+    type Output = Hex;
+
+    fn sub(self, o: Hex) -> Hex {
+        Hex {
+            q: self.q - o.q,
+            r: self.r - o.r,
+        }
+    }
+}
+
+impl Mul<i32> for Hex {
+    type Output = Hex;
+
+    fn mul(self, k: i32) -> Hex {
+        Hex {
+            q: self.q * k,
+            r: self.r * k,
+        }
+    }
+}
+
+/// Rounds fractional cube coordinates to the containing cell, fixing up the coordinate with the
+/// largest rounding error so the sum stays 0.
+fn cube_round(q: f64, r: f64, s: f64) -> Hex {
+    let (mut rq, mut rr, rs) = (floor(q + 0.5), floor(r + 0.5), floor(s + 0.5));
+    let dq = (rq as f64 - q).abs();
+    let dr = (rr as f64 - r).abs();
+    let ds = (rs as f64 - s).abs();
+
+    if dq > dr && dq > ds {
+        rq = -rr - rs;
+    } else if dr > ds {
+        rr = -rq - rs;
+    }
+
+    Hex { q: rq, r: rr }
+}
+
+// -----------------------------------------------------------------------------
+// Hex
+// -----------------------------------------------------------------------------
+
+impl Hex {
+    /// Offsets of the 6 neighbours, counter clockwise starting to the right.
+    pub const DIRECTIONS: [Hex; 6] = [
+        Hex { q: 1, r: 0 },
+        Hex { q: 1, r: -1 },
+        Hex { q: 0, r: -1 },
+        Hex { q: -1, r: 0 },
+        Hex { q: -1, r: 1 },
+        Hex { q: 0, r: 1 },
+    ];
+
+    pub fn new(q: i32, r: i32) -> Self {
+        Hex { q, r }
+    }
+
+    /// Third cube coordinate.
+    pub fn s(self) -> i32 {
+        -self.q - self.r
+    }
+
+    /// Iterates over the 6 adjacent cells in the order of [`Hex::DIRECTIONS`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ctl::grid::Hex;
+    /// let n: Vec<_> = Hex::new(0, 0).neighbors().collect();
+    /// // [(1, 0), (1, -1), (0, -1), (-1, 0), (-1, 1), (0, 1)]
+    /// ```
+    pub fn neighbors(self) -> impl Iterator<Item = Hex> {
+        Hex::DIRECTIONS.iter().map(move |&d| self + d)
+    }
+
+    /// Number of steps between 2 cells.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ctl::grid::Hex;
+    /// let d = Hex::new(0, 0).distance(Hex::new(3, -1)); // 3
+    /// ```
+    pub fn distance(self, o: Hex) -> u32 {
+        let d = self - o;
+        (d.q.unsigned_abs() + d.r.unsigned_abs() + d.s().unsigned_abs()) / 2
+    }
+
+    /// Cells on the straight line to `o`, both ends included, one per step of the distance.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ctl::grid::Hex;
+    /// let l = Hex::new(0, 0).line_to(Hex::new(3, 0)); // [(0, 0), (1, 0), (2, 0), (3, 0)]
+    /// ```
+    pub fn line_to(self, o: Hex) -> Vec<Hex> {
+        let n = self.distance(o);
+        if n == 0 {
+            return vec![self];
+        }
+
+        // Nudge the end points off the cell edges so ties round consistently
+        let (aq, ar, as_) = (
+            self.q as f64 + 1e-6,
+            self.r as f64 + 2e-6,
+            self.s() as f64 - 3e-6,
+        );
+        let (bq, br, bs) = (o.q as f64 + 1e-6, o.r as f64 + 2e-6, o.s() as f64 - 3e-6);
+
+        (0..=n)
+            .map(|i| {
+                let t = i as f64 / n as f64;
+                cube_round(aq + (bq - aq) * t, ar + (br - ar) * t, as_ + (bs - as_) * t)
+            })
+            .collect()
+    }
+
+    /// All cells within `radius` steps, including the cell itself.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ctl::grid::Hex;
+    /// let n = Hex::new(2, 1).range(2).len(); // 19
+    /// ```
+    pub fn range(self, radius: u32) -> Vec<Hex> {
+        let n = radius as i32;
+        let mut cells = Vec::with_capacity((3 * n * (n + 1) + 1) as usize);
+
+        for q in -n..=n {
+            for r in (-n).max(-q - n)..=n.min(-q + n) {
+                cells.push(self + Hex { q, r });
+            }
+        }
+
+        cells
+    }
+
+    /// Cells exactly `radius` steps away, walking counter clockwise. A radius of 0 gives the cell
+    /// itself.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ctl::grid::Hex;
+    /// let n = Hex::new(0, 0).ring(3).len(); // 18
+    /// ```
+    pub fn ring(self, radius: u32) -> Vec<Hex> {
+        if radius == 0 {
+            return vec![self];
+        }
+
+        let mut cells = Vec::with_capacity(6 * radius as usize);
+        let mut h = self + Hex::DIRECTIONS[4] * radius as i32;
+
+        for &d in &Hex::DIRECTIONS {
+            for _ in 0..radius {
+                cells.push(h);
+                h = h + d;
+            }
+        }
+
+        cells
+    }
+
+    /// Center of the cell in pixels for pointy-top hexagons with the given outer radius, the
+    /// origin cell being centered at (0, 0).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ctl::grid::Hex;
+    /// let p = Hex::new(0, 2).to_pixel(10.0); // (10 sqrt(3), 30)
+    /// ```
+    pub fn to_pixel(self, size: f64) -> Point<f64> {
+        Point {
+            x: size * SQRT_3 * (self.q as f64 + self.r as f64 / 2.0),
+            y: size * 1.5 * self.r as f64,
+        }
+    }
+
+    /// Cell containing a pixel, the inverse of [`Hex::to_pixel`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ctl::geometry::Point;
+    /// use ctl::grid::Hex;
+    /// let h = Hex::from_pixel(Point { x: 18.0, y: 28.0 }, 10.0); // (0, 2)
+    /// ```
+    pub fn from_pixel(p: Point<f64>, size: f64) -> Hex {
+        let q = (SQRT_3 / 3.0 * p.x - p.y / 3.0) / size;
+        let r = (2.0 / 3.0 * p.y) / size;
+
+        cube_round(q, r, -q - r)
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hex() {
+        let o = Hex::new(1, -2);
+        assert_eq!(Cube::from(o), Cube { q: 1, r: -2, s: 1 });
+        assert_eq!(Hex::from(Cube::from(o)), o);
+
+        assert!(o.neighbors().all(|n| n.distance(o) == 1));
+        assert_eq!(o.distance(Hex::new(-2, 3)), 5);
+
+        for k in 0..5 {
+            let area = o.range(k);
+            assert_eq!(area.len() as u32, 3 * k * (k + 1) + 1);
+            assert!(area.iter().all(|h| h.distance(o) <= k));
+
+            let ring = o.ring(k);
+            assert_eq!(ring.len() as u32, if k == 0 { 1 } else { 6 * k });
+            assert!(ring.iter().all(|h| h.distance(o) == k));
+        }
+
+        let line = o.line_to(Hex::new(5, -4));
+        assert_eq!(line.len(), 5);
+        assert_eq!((line[0], line[4]), (o, Hex::new(5, -4)));
+        assert!(line.windows(2).all(|w| w[0].distance(w[1]) == 1));
+        assert_eq!(o.line_to(o), vec![o]);
+    }
+
+    #[test]
+    fn test_pixel() {
+        let p = Hex::new(0, 2).to_pixel(10.0);
+        assert!((p.x - 10.0 * SQRT_3).abs() < 1e-12 && p.y == 30.0);
+
+        for h in Hex::new(0, 0).range(6) {
+            let c = h.to_pixel(4.0);
+            assert_eq!(Hex::from_pixel(c, 4.0), h);
+
+            // Points well inside the inner circle belong to the cell
+            let off = Point {
+                x: c.x + 2.9,
+                y: c.y - 1.2,
+            };
+            assert_eq!(Hex::from_pixel(off, 4.0), h);
+        }
+    }
+}
@@ -0,0 +1,218 @@
+use super::{Grid2, OFFSETS_4, OFFSETS_8};
+use crate::geometry::Point;
+use core::ops::Index;
+
+// -----------------------------------------------------------------------------
+// Index types
+// -----------------------------------------------------------------------------
+
+/// Maps signed indices into `0..len` by continuing on the other side.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::grid::WrappingIndex;
+/// let w = WrappingIndex::new(5);
+/// let a = w.get(-1); // 4
+/// let b = w.offset(3, 4); // 2
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WrappingIndex {
+    len: usize,
+}
+
+impl WrappingIndex {
+    pub fn new(len: usize) -> Self {
+        assert!(len > 0, "index range must not be empty");
+        WrappingIndex { len }
+    }
+
+    pub fn get(&self, i: isize) -> usize {
+        i.rem_euclid(self.len as isize) as usize
+    }
+
+    /// Moves the index by `d` steps.
+    pub fn offset(&self, i: usize, d: isize) -> usize {
+        self.get((i % self.len) as isize + d % self.len as isize)
+    }
+
+    /// Shortest signed step count leading from `a` to `b`, ties go forward.
+    pub fn delta(&self, a: usize, b: usize) -> isize {
+        let d = self.get(b as isize - a as isize) as isize;
+        let n = self.len as isize;
+
+        if 2 * d > n {
+            d - n
+        } else {
+            d
+        }
+    }
+}
+
+/// Maps signed indices into `0..len` by sticking to the closest end.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::grid::ClampedIndex;
+/// let c = ClampedIndex::new(5);
+/// let a = c.get(-1); // 0
+/// let b = c.offset(3, 4); // 4
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClampedIndex {
+    len: usize,
+}
+
+impl ClampedIndex {
+    pub fn new(len: usize) -> Self {
+        assert!(len > 0, "index range must not be empty");
+        ClampedIndex { len }
+    }
+
+    pub fn get(&self, i: isize) -> usize {
+        i.clamp(0, self.len as isize - 1) as usize
+    }
+
+    /// Moves the index by `d` steps.
+    pub fn offset(&self, i: usize, d: isize) -> usize {
+        i.saturating_add_signed(d).min(self.len - 1)
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Torus
+// -----------------------------------------------------------------------------
+
+/// View of a grid whose opposite borders are connected, for maps wrapping around in both
+/// directions. Positions are signed and may lie anywhere.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::grid::{Grid2, Torus};
+/// use ctl::geometry::Point;
+/// let g = Grid2::from_fn(3, 3, |p| p.x + 10 * p.y);
+/// let t = Torus::new(&g);
+/// let v = t[(-1, 3)]; // 2, same as g[(2, 0)]
+/// let n = t.neighbors4(Point { x: 0, y: 0 }).count(); // 4
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Torus<'a, T> {
+    grid: &'a Grid2<T>,
+    xs: WrappingIndex,
+    ys: WrappingIndex,
+}
+
+impl<'a, T> Torus<'a, T> {
+    /// Wraps a non-empty grid.
+    pub fn new(grid: &'a Grid2<T>) -> Self {
+        Torus {
+            grid,
+            xs: WrappingIndex::new(grid.width()),
+            ys: WrappingIndex::new(grid.height()),
+        }
+    }
+
+    pub fn grid(&self) -> &'a Grid2<T> {
+        self.grid
+    }
+
+    /// Position inside the grid the signed position lands on.
+    pub fn wrap(&self, x: isize, y: isize) -> Point<usize> {
+        Point {
+            x: self.xs.get(x),
+            y: self.ys.get(y),
+        }
+    }
+
+    /// Shortest offset leading from `a` to `b` around the torus.
+    pub fn delta(&self, a: Point<usize>, b: Point<usize>) -> Point<isize> {
+        Point {
+            x: self.xs.delta(a.x, b.x),
+            y: self.ys.delta(a.y, b.y),
+        }
+    }
+
+    /// Iterates over the positions of the 4 edge-adjacent neighbours of `p`. Grids narrower than
+    /// 3 cells yield some positions twice.
+    pub fn neighbors4(&self, p: Point<usize>) -> impl Iterator<Item = Point<usize>> + 'a {
+        self.offset_iter(p, &OFFSETS_4)
+    }
+
+    /// Iterates over the positions of the 8 edge- or corner-adjacent neighbours of `p`. Grids
+    /// narrower than 3 cells yield some positions twice.
+    pub fn neighbors8(&self, p: Point<usize>) -> impl Iterator<Item = Point<usize>> + 'a {
+        self.offset_iter(p, &OFFSETS_8)
+    }
+
+    fn offset_iter(
+        &self,
+        p: Point<usize>,
+        offsets: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = Point<usize>> + 'a {
+        let (xs, ys) = (self.xs, self.ys);
+
+        offsets.iter().map(move |&(dx, dy)| Point {
+            x: xs.offset(p.x, dx),
+            y: ys.offset(p.y, dy),
+        })
+    }
+}
+
+impl<T> Index<(isize, isize)> for Torus<'_, T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (isize, isize)) -> &T {
+        &self.grid[self.wrap(x, y)]
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_indices() {
+        let w = WrappingIndex::new(4);
+        assert_eq!(
+            (-5..6).map(|i| w.get(i)).collect::<Vec<_>>(),
+            vec![3, 0, 1, 2, 3, 0, 1, 2, 3, 0, 1]
+        );
+        assert_eq!(w.offset(1, -2), 3);
+        assert_eq!(w.offset(3, isize::MAX), w.get(3 + isize::MAX % 4));
+        assert_eq!(w.delta(0, 3), -1);
+        assert_eq!(w.delta(0, 2), 2);
+        assert_eq!(w.delta(3, 1), 2);
+
+        let c = ClampedIndex::new(4);
+        assert_eq!(
+            (-2..6).map(|i| c.get(i)).collect::<Vec<_>>(),
+            vec![0, 0, 0, 1, 2, 3, 3, 3]
+        );
+        assert_eq!(c.offset(1, -5), 0);
+        assert_eq!(c.offset(1, 5), 3);
+    }
+
+    #[test]
+    fn test_torus() {
+        let g = Grid2::from_fn(4, 3, |p| p.x + 10 * p.y);
+        let t = Torus::new(&g);
+
+        assert_eq!(t[(-1, -1)], 23);
+        assert_eq!(t[(5, 7)], 11);
+        assert_eq!(
+            t.delta(Point { x: 0, y: 0 }, Point { x: 3, y: 2 }),
+            Point { x: -1, y: -1 }
+        );
+
+        let n: Vec<_> = t.neighbors4(Point { x: 0, y: 2 }).map(|p| g[p]).collect();
+        assert_eq!(n, vec![10, 21, 0, 23]);
+        assert_eq!(t.neighbors8(Point { x: 3, y: 0 }).count(), 8);
+    }
+}
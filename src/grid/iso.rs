@@ -0,0 +1,219 @@
+use super::Grid2;
+use crate::geometry::Point;
+
+fn floor(x: f64) -> i64 {
+    let i = x as i64;
+    if (i as f64) > x {
+        i - 1
+    } else {
+        i
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Isometric
+// -----------------------------------------------------------------------------
+
+/// Axonometric projection of a tile map onto the screen. World x runs down to the right and
+/// world y down to the left, one world unit being one tile. A tile is drawn as a diamond of the
+/// given width and height, a 2:1 ratio gives the classic isometric look.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Isometric {
+    /// Screen width of a tile diamond
+    pub tile_width: f64,
+    /// Screen height of a tile diamond
+    pub tile_height: f64,
+    /// Screen position of the top corner of tile (0, 0)
+    pub origin: Point<f64>,
+}
+
+impl Isometric {
+    /// Creates a projection with the top corner of tile (0, 0) at the screen origin.
+    ///
+    /// # Arguments
+    ///
+    /// * `tile_width` - Screen width of a tile diamond
+    /// * `tile_height` - Screen height of a tile diamond
+    pub fn new(tile_width: f64, tile_height: f64) -> Self {
+        Isometric {
+            tile_width,
+            tile_height,
+            origin: Point { x: 0.0, y: 0.0 },
+        }
+    }
+
+    /// Projects a world position onto the screen.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ctl::geometry::Point;
+    /// use ctl::grid::Isometric;
+    /// let iso = Isometric::new(64.0, 32.0);
+    /// let s = iso.to_screen(Point { x: 1.0, y: 0.0 }); // (32, 16)
+    /// ```
+    pub fn to_screen(&self, world: Point<f64>) -> Point<f64> {
+        Point {
+            x: self.origin.x + (world.x - world.y) * self.tile_width / 2.0,
+            y: self.origin.y + (world.x + world.y) * self.tile_height / 2.0,
+        }
+    }
+
+    /// Projects a world position standing `z` tiles high onto the screen, raising it by a tile
+    /// height per unit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ctl::geometry::Point;
+    /// use ctl::grid::Isometric;
+    /// let iso = Isometric::new(64.0, 32.0);
+    /// let s = iso.to_screen_raised(Point { x: 1.0, y: 1.0 }, 1.0); // (0, 0)
+    /// ```
+    pub fn to_screen_raised(&self, world: Point<f64>, z: f64) -> Point<f64> {
+        let s = self.to_screen(world);
+        Point {
+            x: s.x,
+            y: s.y - z * self.tile_height,
+        }
+    }
+
+    /// Inverse of [`Isometric::to_screen`], assuming the ground plane.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ctl::geometry::Point;
+    /// use ctl::grid::Isometric;
+    /// let iso = Isometric::new(64.0, 32.0);
+    /// let w = iso.to_world(Point { x: 32.0, y: 16.0 }); // (1, 0)
+    /// ```
+    pub fn to_world(&self, screen: Point<f64>) -> Point<f64> {
+        let u = (screen.x - self.origin.x) / self.tile_width;
+        let v = (screen.y - self.origin.y) / self.tile_height;
+
+        Point { x: v + u, y: v - u }
+    }
+
+    /// Screen position of the center of a tile's diamond.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ctl::geometry::Point;
+    /// use ctl::grid::Isometric;
+    /// let iso = Isometric::new(64.0, 32.0);
+    /// let c = iso.tile_center(Point { x: 0, y: 0 }); // (0, 16)
+    /// ```
+    pub fn tile_center(&self, tile: Point<usize>) -> Point<f64> {
+        self.to_screen(Point {
+            x: tile.x as f64 + 0.5,
+            y: tile.y as f64 + 0.5,
+        })
+    }
+
+    /// Tile whose diamond contains the screen position, which may lie outside of any map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ctl::geometry::Point;
+    /// use ctl::grid::Isometric;
+    /// let iso = Isometric::new(64.0, 32.0);
+    /// let t = iso.tile_at(Point { x: -10.0, y: 10.0 }); // (0, 0)
+    /// let u = iso.tile_at(Point { x: 10.0, y: -10.0 }); // (0, -1)
+    /// ```
+    pub fn tile_at(&self, screen: Point<f64>) -> Point<i64> {
+        let w = self.to_world(screen);
+        Point {
+            x: floor(w.x),
+            y: floor(w.y),
+        }
+    }
+
+    /// Picks the tile of a grid under the screen position, `None` if it is outside of the grid.
+    ///
+    /// # Arguments
+    ///
+    /// * `grid` - Tile map
+    /// * `screen` - Screen position, e.g. of the mouse
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ctl::geometry::Point;
+    /// use ctl::grid::{Grid2, Isometric};
+    /// let iso = Isometric::new(64.0, 32.0);
+    /// let map = Grid2::new(4, 4, 0);
+    /// let t = iso.pick(&map, Point { x: 0.0, y: 40.0 }); // Some((1, 1))
+    /// let u = iso.pick(&map, Point { x: 0.0, y: -1.0 }); // None
+    /// ```
+    pub fn pick<T>(&self, grid: &Grid2<T>, screen: Point<f64>) -> Option<Point<usize>> {
+        let t = self.tile_at(screen);
+        if t.x < 0 || t.y < 0 {
+            return None;
+        }
+
+        let p = Point {
+            x: t.x as usize,
+            y: t.y as usize,
+        };
+        Some(p).filter(|&p| grid.contains(p))
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_isometric() {
+        let mut iso = Isometric::new(64.0, 32.0);
+        iso.origin = Point { x: 300.0, y: 20.0 };
+
+        let w = Point { x: 2.25, y: -1.5 };
+        let back = iso.to_world(iso.to_screen(w));
+        assert!((back.x - w.x).abs() < 1e-12 && (back.y - w.y).abs() < 1e-12);
+
+        // Top and bottom corners of tile (2, 1)
+        assert_eq!(
+            iso.to_screen(Point { x: 2.0, y: 1.0 }),
+            Point { x: 332.0, y: 68.0 }
+        );
+        assert_eq!(
+            iso.to_screen(Point { x: 3.0, y: 2.0 }),
+            Point { x: 332.0, y: 100.0 }
+        );
+
+        let map = Grid2::new(5, 3, ());
+        for t in map.positions() {
+            let c = iso.tile_center(t);
+            assert_eq!(iso.pick(&map, c), Some(t));
+
+            // Just inside the left and right corners
+            let l = Point {
+                x: c.x - 31.0,
+                y: c.y,
+            };
+            let r = Point {
+                x: c.x + 31.0,
+                y: c.y,
+            };
+            assert_eq!((iso.pick(&map, l), iso.pick(&map, r)), (Some(t), Some(t)));
+        }
+
+        assert_eq!(iso.pick(&map, Point { x: 300.0, y: 19.0 }), None);
+        assert_eq!(
+            iso.tile_at(Point { x: 300.0, y: 19.0 }),
+            Point { x: -1, y: -1 }
+        );
+        assert_eq!(iso.pick(&map, iso.tile_center(Point { x: 5, y: 0 })), None);
+
+        let raised = iso.to_screen_raised(Point { x: 1.0, y: 1.0 }, 0.5);
+        assert_eq!(raised, Point { x: 300.0, y: 36.0 });
+    }
+}
@@ -0,0 +1,611 @@
+use super::Grid2;
+use crate::geometry::Point;
+use alloc::collections::BinaryHeap;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::f64::consts::SQRT_2;
+
+// -----------------------------------------------------------------------------
+// Search
+// -----------------------------------------------------------------------------
+
+/// Open cell of the search ordered by lowest estimate first.
+struct Open {
+    estimate: f64,
+    cell: usize,
+}
+
+impl PartialEq for Open {
+    fn eq(&self, o: &Self) -> bool {
+        self.estimate == o.estimate
+    }
+}
+
+impl Eq for Open {}
+
+impl PartialOrd for Open {
+    fn partial_cmp(&self, o: &Self) -> Option<Ordering> {
+        Some(self.cmp(o))
+    }
+}
+
+impl Ord for Open {
+    fn cmp(&self, o: &Self) -> Ordering {
+        o.estimate
+            .partial_cmp(&self.estimate)
+            .unwrap_or(Ordering::Equal)
+    }
+}
+
+/// 8-connected grid where diagonal steps may not cut past a blocked corner.
+struct Walls<'a> {
+    walls: &'a Grid2<bool>,
+    goal: (isize, isize),
+}
+
+impl Walls<'_> {
+    fn free(&self, x: isize, y: isize) -> bool {
+        x >= 0
+            && y >= 0
+            && (x as usize) < self.walls.width()
+            && (y as usize) < self.walls.height()
+            && !self.walls[(x as usize, y as usize)]
+    }
+
+    /// Whether the step from (x, y) by (dx, dy) is allowed.
+    fn step(&self, x: isize, y: isize, dx: isize, dy: isize) -> bool {
+        self.free(x + dx, y + dy)
+            && (dx == 0 || dy == 0 || self.free(x + dx, y) && self.free(x, y + dy))
+    }
+
+    fn index(&self, x: isize, y: isize) -> usize {
+        y as usize * self.walls.width() + x as usize
+    }
+
+    fn point(&self, i: usize) -> (isize, isize) {
+        (
+            (i % self.walls.width()) as isize,
+            (i / self.walls.width()) as isize,
+        )
+    }
+
+    /// Moves straight from (x, y) until the goal or a cell with a forced neighbour is reached.
+    fn jump_straight(
+        &self,
+        mut x: isize,
+        mut y: isize,
+        dx: isize,
+        dy: isize,
+    ) -> Option<(isize, isize)> {
+        loop {
+            if !self.step(x, y, dx, dy) {
+                return None;
+            }
+            x += dx;
+            y += dy;
+
+            if (x, y) == self.goal {
+                return Some((x, y));
+            }
+
+            // A side cell that can't be reached diagonally from behind is forced
+            let forced = if dx != 0 {
+                (self.free(x, y - 1) && !self.free(x - dx, y - 1))
+                    || (self.free(x, y + 1) && !self.free(x - dx, y + 1))
+            } else {
+                (self.free(x - 1, y) && !self.free(x - 1, y - dy))
+                    || (self.free(x + 1, y) && !self.free(x + 1, y - dy))
+            };
+            if forced {
+                return Some((x, y));
+            }
+        }
+    }
+
+    /// Moves diagonally from (x, y) until the goal or a cell from which a straight jump
+    /// succeeds is reached.
+    fn jump_diagonal(
+        &self,
+        mut x: isize,
+        mut y: isize,
+        dx: isize,
+        dy: isize,
+    ) -> Option<(isize, isize)> {
+        loop {
+            if !self.step(x, y, dx, dy) {
+                return None;
+            }
+            x += dx;
+            y += dy;
+
+            if (x, y) == self.goal
+                || self.jump_straight(x, y, dx, 0).is_some()
+                || self.jump_straight(x, y, 0, dy).is_some()
+            {
+                return Some((x, y));
+            }
+        }
+    }
+
+    /// A* over the cells, `next` pushes the successors of a cell given its parent.
+    fn search<F>(
+        &self,
+        start: Point<usize>,
+        goal: Point<usize>,
+        mut next: F,
+    ) -> Option<Vec<Point<usize>>>
+    where
+        F: FnMut(&Self, (isize, isize), Option<(isize, isize)>, &mut Vec<(isize, isize)>),
+    {
+        let (sx, sy) = (start.x as isize, start.y as isize);
+        if !self.free(sx, sy) || !self.free(self.goal.0, self.goal.1) {
+            return None;
+        }
+
+        let n = self.walls.width() * self.walls.height();
+        let mut cost = vec![f64::INFINITY; n];
+        let mut via = vec![usize::MAX; n];
+        let mut open = BinaryHeap::new();
+        let mut succ = Vec::new();
+
+        let (from, to) = (self.index(sx, sy), self.index(self.goal.0, self.goal.1));
+        cost[from] = 0.0;
+        open.push(Open {
+            estimate: octile((sx, sy), self.goal),
+            cell: from,
+        });
+
+        while let Some(Open { estimate, cell }) = open.pop() {
+            if cell == to {
+                break;
+            }
+            let p = self.point(cell);
+            if estimate > cost[cell] + octile(p, self.goal) {
+                continue;
+            }
+
+            let parent = Some(via[cell])
+                .filter(|&v| v != usize::MAX)
+                .map(|v| self.point(v));
+            succ.clear();
+            next(self, p, parent, &mut succ);
+
+            for &q in &succ {
+                let j = self.index(q.0, q.1);
+                let c = cost[cell] + octile(p, q);
+                if c < cost[j] {
+                    cost[j] = c;
+                    via[j] = cell;
+                    open.push(Open {
+                        estimate: c + octile(q, self.goal),
+                        cell: j,
+                    });
+                }
+            }
+        }
+
+        if cost[to] == f64::INFINITY {
+            return None;
+        }
+
+        // Walk back, filling in the straight or diagonal runs between the visited cells
+        let mut path = vec![goal];
+        let mut cell = to;
+        while cell != from {
+            let (p, q) = (self.point(cell), self.point(via[cell]));
+            let (dx, dy) = ((q.0 - p.0).signum(), (q.1 - p.1).signum());
+            let (mut x, mut y) = p;
+
+            while (x, y) != q {
+                x += dx;
+                y += dy;
+                path.push(Point {
+                    x: x as usize,
+                    y: y as usize,
+                });
+            }
+            cell = via[cell];
+        }
+        path.reverse();
+
+        Some(path)
+    }
+}
+
+/// Octile distance, the exact cost between 2 cells without obstacles.
+fn octile(a: (isize, isize), b: (isize, isize)) -> f64 {
+    let (dx, dy) = ((a.0 - b.0).abs(), (a.1 - b.1).abs());
+    let (lo, hi) = (dx.min(dy), dx.max(dy));
+
+    lo as f64 * SQRT_2 + (hi - lo) as f64
+}
+
+const DIRECTIONS: [(isize, isize); 8] = [
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+];
+
+// -----------------------------------------------------------------------------
+// Pathfinding
+// -----------------------------------------------------------------------------
+
+/// Length of a path of adjacent cells, counting straight steps as 1 and diagonal ones as √2.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::geometry::Point;
+/// use ctl::grid::path_length;
+/// let l = path_length(&[Point { x: 0, y: 0 }, Point { x: 1, y: 1 }, Point { x: 2, y: 1 }]);
+/// // 1 + sqrt(2)
+/// ```
+pub fn path_length(path: &[Point<usize>]) -> f64 {
+    path.windows(2)
+        .map(|w| {
+            let a = (w[0].x as isize, w[0].y as isize);
+            octile(a, (w[1].x as isize, w[1].y as isize))
+        })
+        .sum()
+}
+
+/// Finds a shortest 8-connected path with A*. Straight steps cost 1, diagonal ones √2 and may
+/// not cut past a blocked corner.
+///
+/// # Arguments
+///
+/// * `walls` - Blocked cells are `true`
+/// * `start` - First cell of the path
+/// * `goal` - Last cell of the path
+///
+/// # Examples
+///
+/// ```
+/// use ctl::geometry::Point;
+/// use ctl::grid::{astar, Grid2};
+/// let mut walls = Grid2::new(3, 3, false);
+/// walls[(1, 0)] = true;
+/// walls[(1, 1)] = true;
+/// let path = astar(&walls, Point { x: 0, y: 0 }, Point { x: 2, y: 0 });
+/// // Some([(0, 0), (0, 1), (1, 2), (2, 1), (2, 0)]) or an equally long path
+/// ```
+pub fn astar(
+    walls: &Grid2<bool>,
+    start: Point<usize>,
+    goal: Point<usize>,
+) -> Option<Vec<Point<usize>>> {
+    let w = Walls {
+        walls,
+        goal: (goal.x as isize, goal.y as isize),
+    };
+
+    w.search(start, goal, |w, (x, y), _, succ| {
+        for &(dx, dy) in &DIRECTIONS {
+            if w.step(x, y, dx, dy) {
+                succ.push((x + dx, y + dy));
+            }
+        }
+    })
+}
+
+/// Finds a shortest 8-connected path with jump point search. Uses the same costs and corner
+/// rule as [`astar`] and finds paths of the same length, but only puts the cells where a path
+/// can turn into the open list, which is much faster on large open areas. The returned path
+/// contains every cell.
+///
+/// # Arguments
+///
+/// * `walls` - Blocked cells are `true`
+/// * `start` - First cell of the path
+/// * `goal` - Last cell of the path
+///
+/// # Examples
+///
+/// ```
+/// use ctl::geometry::Point;
+/// use ctl::grid::{jump_point_search, Grid2};
+/// let walls = Grid2::new(100, 100, false);
+/// let path = jump_point_search(&walls, Point { x: 0, y: 0 }, Point { x: 99, y: 50 });
+/// // 100 cells long
+/// ```
+pub fn jump_point_search(
+    walls: &Grid2<bool>,
+    start: Point<usize>,
+    goal: Point<usize>,
+) -> Option<Vec<Point<usize>>> {
+    let w = Walls {
+        walls,
+        goal: (goal.x as isize, goal.y as isize),
+    };
+
+    w.search(start, goal, |w, (x, y), parent, succ| {
+        let mut jump = |dx: isize, dy: isize| {
+            let j = if dx != 0 && dy != 0 {
+                w.jump_diagonal(x, y, dx, dy)
+            } else {
+                w.jump_straight(x, y, dx, dy)
+            };
+            succ.extend(j);
+        };
+
+        let (dx, dy) = match parent {
+            Some((px, py)) => ((x - px).signum(), (y - py).signum()),
+            None => {
+                for &(dx, dy) in &DIRECTIONS {
+                    jump(dx, dy);
+                }
+                return;
+            }
+        };
+
+        // Pruned neighbours: the natural ones ahead plus the forced ones to the side
+        if dx != 0 && dy != 0 {
+            jump(dx, 0);
+            jump(0, dy);
+            jump(dx, dy);
+        } else if dx != 0 {
+            jump(dx, 0);
+            for s in [-1, 1] {
+                if w.free(x, y + s) {
+                    jump(0, s);
+                    jump(dx, s);
+                }
+            }
+        } else {
+            jump(0, dy);
+            for s in [-1, 1] {
+                if w.free(x + s, y) {
+                    jump(s, 0);
+                    jump(s, dy);
+                }
+            }
+        }
+    })
+}
+
+// -----------------------------------------------------------------------------
+// Flow fields
+// -----------------------------------------------------------------------------
+
+/// Computes the integration field (Dijkstra map) holding for every cell the length of the
+/// shortest path to the nearest of the goals, with the moves of [`astar`]. Blocked and
+/// unreachable cells are infinite.
+///
+/// # Arguments
+///
+/// * `walls` - Blocked cells are `true`
+/// * `goals` - Cells with a distance of 0, blocked ones are ignored
+///
+/// # Examples
+///
+/// ```
+/// use ctl::geometry::Point;
+/// use ctl::grid::{integration_field, Grid2};
+/// let walls = Grid2::new(4, 3, false);
+/// let d = integration_field(&walls, &[Point { x: 0, y: 0 }]);
+/// // d[(3, 0)] == 3, d[(2, 2)] == 2 sqrt(2)
+/// ```
+pub fn integration_field(walls: &Grid2<bool>, goals: &[Point<usize>]) -> Grid2<f64> {
+    // There is no single goal to stop at
+    let w = Walls {
+        walls,
+        goal: (-1, -1),
+    };
+    let mut cost = Grid2::new(walls.width(), walls.height(), f64::INFINITY);
+    let mut open = BinaryHeap::new();
+
+    for &g in goals {
+        if !walls[g] {
+            cost[g] = 0.0;
+            open.push(Open {
+                estimate: 0.0,
+                cell: w.index(g.x as isize, g.y as isize),
+            });
+        }
+    }
+
+    while let Some(Open { estimate, cell }) = open.pop() {
+        if estimate > cost.as_slice()[cell] {
+            continue;
+        }
+        let (x, y) = w.point(cell);
+
+        for &(dx, dy) in &DIRECTIONS {
+            if w.step(x, y, dx, dy) {
+                let j = w.index(x + dx, y + dy);
+                let c = estimate + octile((0, 0), (dx, dy));
+                if c < cost.as_slice()[j] {
+                    cost.as_mut_slice()[j] = c;
+                    open.push(Open {
+                        estimate: c,
+                        cell: j,
+                    });
+                }
+            }
+        }
+    }
+
+    cost
+}
+
+/// Derives the flow field from an integration field: every cell points to the neighbour on a
+/// shortest path towards the nearest goal, so any number of agents can follow it without their
+/// own search. Goals, blocked and unreachable cells have no direction.
+///
+/// # Arguments
+///
+/// * `field` - Integration field from [`integration_field`]
+///
+/// # Examples
+///
+/// ```
+/// use ctl::geometry::Point;
+/// use ctl::grid::{flow_field, integration_field, Grid2};
+/// let walls = Grid2::new(4, 3, false);
+/// let flow = flow_field(&integration_field(&walls, &[Point { x: 0, y: 0 }]));
+/// // flow[(2, 2)] == Some(Point { x: 1, y: 1 })
+/// ```
+pub fn flow_field(field: &Grid2<f64>) -> Grid2<Option<Point<usize>>> {
+    // Blocked cells are infinite in the field, which also forbids cutting their corners
+    let open = |x: isize, y: isize| {
+        x >= 0
+            && y >= 0
+            && (x as usize) < field.width()
+            && (y as usize) < field.height()
+            && field[(x as usize, y as usize)].is_finite()
+    };
+
+    Grid2::from_fn(field.width(), field.height(), |p| {
+        let (x, y) = (p.x as isize, p.y as isize);
+        if !open(x, y) || field[p] == 0.0 {
+            return None;
+        }
+
+        let mut best = (f64::INFINITY, None);
+
+        for &(dx, dy) in &DIRECTIONS {
+            if !open(x + dx, y + dy)
+                || (dx != 0 && dy != 0 && !(open(x + dx, y) && open(x, y + dy)))
+            {
+                continue;
+            }
+
+            let q = Point {
+                x: (x + dx) as usize,
+                y: (y + dy) as usize,
+            };
+            let c = field[q] + octile((0, 0), (dx, dy));
+            if c < best.0 {
+                best = (c, Some(q));
+            }
+        }
+
+        best.1
+    })
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn p(x: usize, y: usize) -> Point<usize> {
+        Point { x, y }
+    }
+
+    /// Checks that the path is connected by allowed steps.
+    fn valid(walls: &Grid2<bool>, path: &[Point<usize>]) -> bool {
+        path.iter().all(|&q| !walls[q])
+            && path.windows(2).all(|w| {
+                let (dx, dy) = (
+                    w[1].x as isize - w[0].x as isize,
+                    w[1].y as isize - w[0].y as isize,
+                );
+                dx.abs() <= 1
+                    && dy.abs() <= 1
+                    && (dx == 0 || dy == 0 || !walls[(w[1].x, w[0].y)] && !walls[(w[0].x, w[1].y)])
+                    && (dx, dy) != (0, 0)
+            })
+    }
+
+    #[test]
+    fn test_astar() {
+        let mut walls = Grid2::new(5, 5, false);
+        for y in 0..4 {
+            walls[(2, y)] = true;
+        }
+
+        let path = astar(&walls, p(0, 0), p(4, 0)).unwrap();
+        assert!(valid(&walls, &path));
+        assert_eq!((path[0], path[path.len() - 1]), (p(0, 0), p(4, 0)));
+        assert!(
+            (path_length(&path) - (8.0 + 2.0 * SQRT_2)).abs() < 1e-9,
+            "{:?}",
+            path
+        );
+
+        assert_eq!(astar(&walls, p(1, 1), p(1, 1)), Some(vec![p(1, 1)]));
+        assert_eq!(astar(&walls, p(0, 0), p(2, 0)), None);
+
+        // The gap is only reachable diagonally past blocked corners
+        walls[(1, 4)] = true;
+        walls[(3, 3)] = true;
+        assert_eq!(astar(&walls, p(0, 0), p(4, 0)), None);
+    }
+
+    #[test]
+    fn test_jump_point_search() {
+        // Pseudo random mazes, JPS must match the A* path length
+        let mut seed = 0x2545_f491_4f6c_dd1du64;
+        for _ in 0..200 {
+            let walls = Grid2::from_fn(16, 12, |_| {
+                seed ^= seed << 13;
+                seed ^= seed >> 7;
+                seed ^= seed << 17;
+                seed % 100 < 30
+            });
+            let (a, b) = (p(0, 0), p(15, 11));
+
+            match (astar(&walls, a, b), jump_point_search(&walls, a, b)) {
+                (Some(x), Some(y)) => {
+                    assert!(valid(&walls, &y));
+                    assert_eq!((y[0], y[y.len() - 1]), (a, b));
+                    assert!((path_length(&x) - path_length(&y)).abs() < 1e-9);
+                }
+                (x, y) => assert_eq!(x, y),
+            }
+        }
+
+        let open = Grid2::new(100, 100, false);
+        let path = jump_point_search(&open, p(0, 0), p(99, 50)).unwrap();
+        assert_eq!(path.len(), 100);
+        assert_eq!(
+            jump_point_search(&open, p(7, 7), p(7, 7)),
+            Some(vec![p(7, 7)])
+        );
+    }
+
+    #[test]
+    fn test_flow_field() {
+        let mut walls = Grid2::new(6, 5, false);
+        for y in 1..5 {
+            walls[(3, y)] = true;
+        }
+        walls[(0, 4)] = true;
+
+        let goals = [p(5, 4), p(0, 0)];
+        let field = integration_field(&walls, &goals);
+        let flow = flow_field(&field);
+
+        assert_eq!(field[(0, 0)], 0.0);
+        assert_eq!(field[(3, 3)], f64::INFINITY);
+        assert_eq!(
+            (flow[(0, 0)], flow[(3, 3)], flow[(0, 4)]),
+            (None, None, None)
+        );
+
+        // Following the flow walks a shortest path to the nearest goal
+        for start in walls.positions().filter(|&q| !walls[q]) {
+            let mut path = vec![start];
+            while let Some(q) = flow[path[path.len() - 1]] {
+                path.push(q);
+            }
+
+            let end = path[path.len() - 1];
+            assert!(goals.contains(&end));
+            assert!(valid(&walls, &path));
+            assert!((path_length(&path) - field[start]).abs() < 1e-9);
+
+            let a = astar(&walls, start, end).unwrap();
+            assert!((path_length(&a) - field[start]).abs() < 1e-9);
+        }
+    }
+}
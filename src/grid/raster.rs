@@ -0,0 +1,218 @@
+use super::Grid2;
+use crate::geometry::{Point, Polygon};
+use alloc::vec::Vec;
+
+// -----------------------------------------------------------------------------
+// Polygon rasterization
+// -----------------------------------------------------------------------------
+
+/// Rule deciding which cells a rasterized polygon covers. Cell (x, y) spans the square from
+/// (x, y) to (x + 1, y + 1).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Coverage {
+    /// Cells whose center lies inside the polygon. Centers exactly on an edge count only for
+    /// left and bottom edges, so polygons sharing an edge never cover the same cell.
+    Center,
+    /// Cells sharing any area with the polygon, touching along a border is not enough.
+    Overlap,
+}
+
+fn floor(x: f64) -> i64 {
+    let t = x as i64;
+    if t as f64 > x {
+        t - 1
+    } else {
+        t
+    }
+}
+
+fn ceil(x: f64) -> i64 {
+    -floor(-x)
+}
+
+/// Clamps the cell range `lo..hi` to `0..len`.
+fn clamp_range(lo: i64, hi: i64, len: usize) -> core::ops::Range<usize> {
+    let clamp = |v: i64| v.clamp(0, len as i64) as usize;
+    clamp(lo)..clamp(hi).max(clamp(lo))
+}
+
+/// Marks the cells whose center lies inside using an even-odd scanline through every row.
+fn fill_centers(mask: &mut Grid2<bool>, points: &[Point<f64>]) {
+    let mut xs = Vec::new();
+
+    for y in 0..mask.height() {
+        let yc = y as f64 + 0.5;
+
+        xs.clear();
+        for i in 0..points.len() {
+            let (a, b) = (points[i], points[(i + 1) % points.len()]);
+            if (a.y > yc) != (b.y > yc) {
+                xs.push(a.x + (yc - a.y) * (b.x - a.x) / (b.y - a.y));
+            }
+        }
+        xs.sort_by(|a, b| a.partial_cmp(b).unwrap_or(core::cmp::Ordering::Equal));
+
+        for span in xs.chunks_exact(2) {
+            for x in clamp_range(ceil(span[0] - 0.5), ceil(span[1] - 0.5), mask.width()) {
+                mask[(x, y)] = true;
+            }
+        }
+    }
+}
+
+/// Marks the cells whose open interior an edge passes through.
+fn fill_edges(mask: &mut Grid2<bool>, points: &[Point<f64>]) {
+    for i in 0..points.len() {
+        let (a, b) = (points[i], points[(i + 1) % points.len()]);
+        let (lo, hi) = if a.y < b.y { (a, b) } else { (b, a) };
+
+        for y in clamp_range(floor(lo.y), ceil(hi.y), mask.height()) {
+            // Part of the edge inside the row, skipped if it only runs along its border
+            let (y0, y1) = (lo.y.max(y as f64), hi.y.min(y as f64 + 1.0));
+            let (x0, x1) = if lo.y == hi.y {
+                if lo.y == y as f64 {
+                    continue;
+                }
+                (lo.x.min(hi.x), lo.x.max(hi.x))
+            } else {
+                if y0 >= y1 {
+                    continue;
+                }
+                let at = |y: f64| lo.x + (y - lo.y) * (hi.x - lo.x) / (hi.y - lo.y);
+                (at(y0).min(at(y1)), at(y0).max(at(y1)))
+            };
+
+            let cells = if x0 == x1 {
+                // Vertical parts on a cell border touch no interior
+                if x0 == floor(x0) as f64 {
+                    continue;
+                }
+                floor(x0)..floor(x0) + 1
+            } else {
+                floor(x0)..ceil(x1)
+            };
+
+            for x in clamp_range(cells.start, cells.end, mask.width()) {
+                mask[(x, y)] = true;
+            }
+        }
+    }
+}
+
+/// Sets the cells covered by a simple polygon to `value`, treating cell (x, y) as the unit
+/// square at (x, y). Parts outside of the grid are ignored. Returns the number of covered
+/// cells.
+///
+/// # Arguments
+///
+/// * `grid` - Grid to draw into
+/// * `polygon` - Drawn polygon in cell coordinates
+/// * `coverage` - Rule deciding which cells count as covered
+/// * `value` - New value of the covered cells
+///
+/// # Examples
+///
+/// ```
+/// use ctl::geometry::{Point, Polygon};
+/// use ctl::grid::{fill_polygon, Coverage, Grid2};
+/// let p = |x, y| Point { x, y };
+/// let tri = Polygon { points: vec![p(0.0, 0.0), p(4.0, 0.0), p(0.0, 4.0)] };
+/// let mut g = Grid2::new(4, 4, 0);
+/// let n = fill_polygon(&mut g, &tri, Coverage::Center, 1); // 6
+/// let n = fill_polygon(&mut g, &tri, Coverage::Overlap, 2); // 10
+/// ```
+pub fn fill_polygon<T: Clone>(
+    grid: &mut Grid2<T>,
+    polygon: &Polygon,
+    coverage: Coverage,
+    value: T,
+) -> usize {
+    let mut mask = Grid2::new(grid.width(), grid.height(), false);
+
+    fill_centers(&mut mask, &polygon.points);
+    if coverage == Coverage::Overlap {
+        fill_edges(&mut mask, &polygon.points);
+    }
+
+    let mut n = 0;
+    for (c, &m) in grid.iter_mut().zip(mask.iter()) {
+        if m {
+            *c = value.clone();
+            n += 1;
+        }
+    }
+
+    n
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn p(x: f64, y: f64) -> Point<f64> {
+        Point { x, y }
+    }
+
+    fn rows(g: &Grid2<u8>) -> Vec<Vec<u8>> {
+        g.rows().map(|r| r.to_vec()).collect()
+    }
+
+    #[test]
+    fn test_triangle() {
+        let tri = Polygon {
+            points: vec![p(0.0, 0.0), p(4.0, 0.0), p(0.0, 4.0)],
+        };
+
+        let mut g = Grid2::new(4, 4, 0u8);
+        assert_eq!(fill_polygon(&mut g, &tri, Coverage::Center, 1), 6);
+        assert_eq!(
+            rows(&g),
+            vec![
+                vec![1, 1, 1, 0],
+                vec![1, 1, 0, 0],
+                vec![1, 0, 0, 0],
+                vec![0, 0, 0, 0]
+            ]
+        );
+
+        let mut g = Grid2::new(4, 4, 0u8);
+        assert_eq!(fill_polygon(&mut g, &tri, Coverage::Overlap, 1), 10);
+        assert_eq!(
+            rows(&g),
+            vec![
+                vec![1, 1, 1, 1],
+                vec![1, 1, 1, 0],
+                vec![1, 1, 0, 0],
+                vec![1, 0, 0, 0]
+            ]
+        );
+    }
+
+    #[test]
+    fn test_clipping_and_borders() {
+        // Aligned square partly outside, borders don't count for either rule
+        let square = Polygon {
+            points: vec![p(-1.0, 1.0), p(2.0, 1.0), p(2.0, 3.0), p(-1.0, 3.0)],
+        };
+        for &rule in &[Coverage::Center, Coverage::Overlap] {
+            let mut g = Grid2::new(4, 4, 0u8);
+            assert_eq!(fill_polygon(&mut g, &square, rule, 1), 4);
+            assert_eq!(g[(1, 2)], 1);
+            assert_eq!(g[(2, 2)], 0);
+            assert_eq!(g[(0, 0)], 0);
+        }
+
+        // Thin sliver missing every center
+        let sliver = Polygon {
+            points: vec![p(0.2, 1.2), p(3.8, 1.2), p(3.8, 1.4), p(0.2, 1.4)],
+        };
+        let mut g = Grid2::new(4, 4, 0u8);
+        assert_eq!(fill_polygon(&mut g, &sliver, Coverage::Center, 1), 0);
+        assert_eq!(fill_polygon(&mut g, &sliver, Coverage::Overlap, 1), 4);
+        assert_eq!(g.row(1), &[1, 1, 1, 1]);
+    }
+}
@@ -0,0 +1,185 @@
+use super::Grid2;
+use crate::geometry::{Point, Rect};
+use alloc::vec::Vec;
+
+// -----------------------------------------------------------------------------
+// Flood fill
+// -----------------------------------------------------------------------------
+
+/// Collects the 4-connected region of cells around `start` whose values satisfy `pred`. The
+/// result is empty if the start cell itself doesn't satisfy it.
+///
+/// # Arguments
+///
+/// * `grid` - Searched grid
+/// * `start` - Seed position
+/// * `pred` - Decides whether a cell belongs to the region
+///
+/// # Examples
+///
+/// ```
+/// use ctl::grid::{flood_fill, Grid2};
+/// use ctl::geometry::Point;
+/// let g = Grid2::from_vec(3, 2, vec![1, 1, 0, 0, 1, 0]);
+/// let r = flood_fill(&g, Point { x: 0, y: 0 }, |&v| v == 1); // 3 positions
+/// ```
+pub fn flood_fill<T, F: FnMut(&T) -> bool>(
+    grid: &Grid2<T>,
+    start: Point<usize>,
+    mut pred: F,
+) -> Vec<Point<usize>> {
+    let mut region = Vec::new();
+
+    match grid.get(start) {
+        Some(v) if pred(v) => {}
+        _ => return region,
+    }
+
+    let mut seen = Grid2::new(grid.width(), grid.height(), false);
+    let mut stack = vec![start];
+    seen[start] = true;
+
+    while let Some(p) = stack.pop() {
+        region.push(p);
+
+        for n in grid.neighbors4(p) {
+            if !seen[n] && pred(&grid[n]) {
+                seen[n] = true;
+                stack.push(n);
+            }
+        }
+    }
+
+    region
+}
+
+// -----------------------------------------------------------------------------
+// Connected components
+// -----------------------------------------------------------------------------
+
+/// Single labeled region found by [`connected_components`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Region {
+    /// Smallest rectangle of cells containing the region, `max` is inclusive
+    pub bounds: Rect<usize>,
+    /// Number of cells in the region
+    pub size: usize,
+}
+
+/// Result of [`connected_components`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Components {
+    /// Region index of every cell
+    pub labels: Grid2<usize>,
+    /// Regions in order of their first cell in row-major order
+    pub regions: Vec<Region>,
+}
+
+/// Labels the 4-connected regions of equal cells.
+///
+/// # Arguments
+///
+/// * `grid` - Labeled grid
+///
+/// # Examples
+///
+/// ```
+/// use ctl::grid::{connected_components, Grid2};
+/// let g = Grid2::from_vec(3, 1, vec![1, 0, 1]);
+/// let c = connected_components(&g); // labels [0, 1, 2], 3 regions
+/// ```
+pub fn connected_components<T: PartialEq>(grid: &Grid2<T>) -> Components {
+    const UNSET: usize = usize::MAX;
+
+    let mut labels = Grid2::new(grid.width(), grid.height(), UNSET);
+    let mut regions = Vec::new();
+    let mut stack = Vec::new();
+
+    for start in grid.positions() {
+        if labels[start] != UNSET {
+            continue;
+        }
+
+        let label = regions.len();
+        let mut region = Region {
+            bounds: Rect {
+                min: start,
+                max: start,
+            },
+            size: 0,
+        };
+
+        labels[start] = label;
+        stack.push(start);
+
+        while let Some(p) = stack.pop() {
+            region.size += 1;
+            region.bounds.expand(p);
+
+            for n in grid.neighbors4(p) {
+                if labels[n] == UNSET && grid[n] == grid[p] {
+                    labels[n] = label;
+                    stack.push(n);
+                }
+            }
+        }
+
+        regions.push(region);
+    }
+
+    Components { labels, regions }
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn p(x: usize, y: usize) -> Point<usize> {
+        Point { x, y }
+    }
+
+    #[test]
+    fn test_flood_fill() {
+        #[rustfmt::skip]
+        let g = Grid2::from_vec(4, 3, vec![
+            1, 1, 0, 1,
+            0, 1, 0, 1,
+            1, 1, 0, 1,
+        ]);
+
+        let mut r = flood_fill(&g, p(0, 0), |&v| v == 1);
+        r.sort_by_key(|p| (p.y, p.x));
+        assert_eq!(r, vec![p(0, 0), p(1, 0), p(1, 1), p(0, 2), p(1, 2)]);
+
+        assert!(flood_fill(&g, p(2, 0), |&v| v == 1).is_empty());
+        assert_eq!(flood_fill(&g, p(3, 1), |&v| v == 1).len(), 3);
+    }
+
+    #[test]
+    fn test_connected_components() {
+        #[rustfmt::skip]
+        let g = Grid2::from_vec(4, 3, vec![
+            1, 1, 0, 1,
+            0, 1, 0, 1,
+            1, 1, 0, 0,
+        ]);
+
+        let c = connected_components(&g);
+
+        assert_eq!(c.regions.len(), 4);
+        assert_eq!(c.labels.as_slice(), &[0, 0, 1, 2, 3, 0, 1, 2, 0, 0, 1, 1]);
+        assert_eq!(
+            c.regions[0],
+            Region {
+                bounds: Rect::from_corners(p(0, 0), p(1, 2)),
+                size: 5
+            }
+        );
+        assert_eq!(c.regions[1].bounds, Rect::from_corners(p(2, 0), p(3, 2)));
+        assert_eq!(c.regions[3].size, 1);
+    }
+}
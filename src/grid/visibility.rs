@@ -0,0 +1,261 @@
+use super::Grid2;
+use crate::geometry::Point;
+use alloc::vec::Vec;
+
+// -----------------------------------------------------------------------------
+// Line of sight
+// -----------------------------------------------------------------------------
+
+/// Cells on the Bresenham line from `a` to `b`, both included.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::geometry::Point;
+/// use ctl::grid::bresenham;
+/// let l = bresenham(Point { x: 0, y: 0 }, Point { x: 4, y: 2 });
+/// // (0, 0), (1, 0), (2, 1), (3, 1), (4, 2)
+/// ```
+pub fn bresenham(a: Point<usize>, b: Point<usize>) -> Vec<Point<usize>> {
+    let (x1, y1) = (b.x as isize, b.y as isize);
+    let (mut x, mut y) = (a.x as isize, a.y as isize);
+
+    let dx = (x1 - x).abs();
+    let dy = -(y1 - y).abs();
+    let (sx, sy) = ((x1 - x).signum(), (y1 - y).signum());
+    let mut err = dx + dy;
+
+    let mut out = Vec::with_capacity(dx.max(-dy) as usize + 1);
+    loop {
+        out.push(Point {
+            x: x as usize,
+            y: y as usize,
+        });
+        if x == x1 && y == y1 {
+            return out;
+        }
+
+        let e2 = 2 * err;
+        if e2 >= dy {
+            err += dy;
+            x += sx;
+        }
+        if e2 <= dx {
+            err += dx;
+            y += sy;
+        }
+    }
+}
+
+/// Checks whether no obstacle lies on the Bresenham line strictly between the 2 cells, so a
+/// wall itself can be seen.
+///
+/// # Arguments
+///
+/// * `obstacles` - Cells blocking the sight are `true`
+/// * `from` - Viewer position
+/// * `to` - Target position
+///
+/// # Examples
+///
+/// ```
+/// use ctl::geometry::Point;
+/// use ctl::grid::{line_of_sight, Grid2};
+/// let mut walls = Grid2::new(5, 1, false);
+/// walls[(2, 0)] = true;
+/// let a = line_of_sight(&walls, Point { x: 0, y: 0 }, Point { x: 2, y: 0 }); // true
+/// let b = line_of_sight(&walls, Point { x: 0, y: 0 }, Point { x: 4, y: 0 }); // false
+/// ```
+pub fn line_of_sight(obstacles: &Grid2<bool>, from: Point<usize>, to: Point<usize>) -> bool {
+    let line = bresenham(from, to);
+    let inner = line.len().saturating_sub(2);
+
+    line.iter().skip(1).take(inner).all(|&p| !obstacles[p])
+}
+
+// -----------------------------------------------------------------------------
+// Field of view
+// -----------------------------------------------------------------------------
+
+/// Transforms of the first octant onto all 8, as (xx, xy, yx, yy).
+const OCTANTS: [(isize, isize, isize, isize); 8] = [
+    (1, 0, 0, 1),
+    (0, 1, 1, 0),
+    (0, -1, 1, 0),
+    (-1, 0, 0, 1),
+    (-1, 0, 0, -1),
+    (0, -1, -1, 0),
+    (0, 1, -1, 0),
+    (1, 0, 0, -1),
+];
+
+struct Shadowcast<'a> {
+    obstacles: &'a Grid2<bool>,
+    visible: Grid2<bool>,
+    origin: Point<usize>,
+    radius: isize,
+}
+
+impl Shadowcast<'_> {
+    fn cell(&self, dx: isize, dy: isize, t: (isize, isize, isize, isize)) -> Option<Point<usize>> {
+        let x = self.origin.x as isize + dx * t.0 + dy * t.1;
+        let y = self.origin.y as isize + dx * t.2 + dy * t.3;
+
+        if x >= 0 && y >= 0 && (x as usize) < self.obstacles.width() {
+            let p = Point {
+                x: x as usize,
+                y: y as usize,
+            };
+            Some(p).filter(|p| p.y < self.obstacles.height())
+        } else {
+            None
+        }
+    }
+
+    /// Scans the rows of an octant from `row` outwards between the slopes `start` > `end`,
+    /// recursing into the gaps between obstacles.
+    fn cast(&mut self, row: isize, mut start: f64, end: f64, t: (isize, isize, isize, isize)) {
+        if start < end {
+            return;
+        }
+
+        let mut next_start = start;
+
+        for j in row..=self.radius {
+            let dy = -j;
+            let mut blocked = false;
+
+            for dx in -j..=0 {
+                let left = (dx as f64 - 0.5) / (dy as f64 + 0.5);
+                let right = (dx as f64 + 0.5) / (dy as f64 - 0.5);
+
+                if start < right {
+                    continue;
+                }
+                if end > left {
+                    break;
+                }
+
+                // Cells outside of the grid block the view
+                let cell = self.cell(dx, dy, t);
+                if let Some(p) = cell {
+                    if dx * dx + dy * dy <= self.radius * self.radius {
+                        self.visible[p] = true;
+                    }
+                }
+
+                let wall = cell.is_none_or(|p| self.obstacles[p]);
+                if blocked {
+                    if wall {
+                        next_start = right;
+                    } else {
+                        blocked = false;
+                        start = next_start;
+                    }
+                } else if wall && j < self.radius {
+                    blocked = true;
+                    self.cast(j + 1, start, left, t);
+                    next_start = right;
+                }
+            }
+
+            if blocked {
+                break;
+            }
+        }
+    }
+}
+
+/// Computes the cells visible from `origin` within `radius` using recursive shadowcasting.
+/// Obstacles are visible themselves but hide everything behind them.
+///
+/// # Arguments
+///
+/// * `obstacles` - Cells blocking the sight are `true`
+/// * `origin` - Viewer position inside the grid
+/// * `radius` - Euclidean view distance in cells
+///
+/// # Examples
+///
+/// ```
+/// use ctl::geometry::Point;
+/// use ctl::grid::{field_of_view, Grid2};
+/// let mut walls = Grid2::new(7, 7, false);
+/// walls[(4, 3)] = true;
+/// let v = field_of_view(&walls, Point { x: 3, y: 3 }, 3);
+/// // v[(4, 3)] is true, v[(6, 3)] behind the wall is false
+/// ```
+pub fn field_of_view(obstacles: &Grid2<bool>, origin: Point<usize>, radius: usize) -> Grid2<bool> {
+    let mut s = Shadowcast {
+        obstacles,
+        visible: Grid2::new(obstacles.width(), obstacles.height(), false),
+        origin,
+        radius: radius as isize,
+    };
+
+    s.visible[origin] = true;
+    for &t in &OCTANTS {
+        s.cast(1, 1.0, 0.0, t);
+    }
+
+    s.visible
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn p(x: usize, y: usize) -> Point<usize> {
+        Point { x, y }
+    }
+
+    #[test]
+    fn test_line_of_sight() {
+        assert_eq!(bresenham(p(3, 3), p(3, 3)), vec![p(3, 3)]);
+        assert_eq!(
+            bresenham(p(4, 2), p(0, 0)),
+            vec![p(4, 2), p(3, 1), p(2, 1), p(1, 0), p(0, 0)]
+        );
+        assert_eq!(bresenham(p(0, 5), p(2, 0)).len(), 6);
+
+        let mut walls = Grid2::new(5, 5, false);
+        walls[(2, 2)] = true;
+        assert!(!line_of_sight(&walls, p(0, 0), p(4, 4)));
+        assert!(line_of_sight(&walls, p(0, 0), p(2, 2)));
+        assert!(line_of_sight(&walls, p(0, 0), p(4, 1)));
+        assert!(line_of_sight(&walls, p(1, 1), p(1, 1)));
+    }
+
+    #[test]
+    fn test_field_of_view() {
+        let open = Grid2::new(9, 9, false);
+        let v = field_of_view(&open, p(4, 4), 3);
+
+        // Exactly the disc of radius 3
+        for q in open.positions() {
+            let (dx, dy) = (q.x as isize - 4, q.y as isize - 4);
+            assert_eq!(v[q], dx * dx + dy * dy <= 9, "{:?}", q);
+        }
+
+        // A wall segment shades the cells behind it
+        let mut walls = Grid2::new(9, 9, false);
+        for y in 2..7 {
+            walls[(6, y)] = true;
+        }
+        let v = field_of_view(&walls, p(4, 4), 8);
+        assert!(v[(6, 4)] && v[(6, 2)]);
+        assert!(!v[(7, 4)] && !v[(8, 3)] && !v[(8, 5)]);
+        assert!(!v[(8, 8)] && v[(4, 8)] && v[(0, 0)]);
+
+        // Shadowcasting agrees with the line for straight rows
+        assert!(!line_of_sight(&walls, p(4, 4), p(8, 4)));
+
+        // Viewing from a corner of the map
+        let v = field_of_view(&open, p(0, 0), 20);
+        assert!(v.iter().all(|&c| c));
+    }
+}
@@ -0,0 +1,282 @@
+use core::hash::{BuildHasherDefault, Hasher};
+
+// -----------------------------------------------------------------------------
+// CRC32
+// -----------------------------------------------------------------------------
+
+const CRC32_TABLE: [u32; 256] = crc32_table();
+
+const fn crc32_table() -> [u32; 256] {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+
+    while i < 256 {
+        let mut c = i as u32;
+        let mut k = 0;
+
+        while k < 8 {
+            c = if c & 1 != 0 {
+                0xEDB8_8320 ^ (c >> 1)
+            } else {
+                c >> 1
+            };
+            k += 1;
+        }
+
+        table[i] = c;
+        i += 1;
+    }
+
+    table
+}
+
+/// Incremental CRC32 (IEEE 802.3) checksum.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::hash::Crc32;
+/// let mut c = Crc32::new();
+/// c.update(b"1234");
+/// c.update(b"56789");
+/// let r = c.finish(); // 0xCBF43926
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Crc32 {
+    state: u32,
+}
+
+impl Crc32 {
+    pub fn new() -> Self {
+        Crc32 { state: 0xFFFF_FFFF }
+    }
+
+    /// Feeds more bytes into the checksum.
+    pub fn update(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.state = CRC32_TABLE[((self.state ^ b as u32) & 0xFF) as usize] ^ (self.state >> 8);
+        }
+    }
+
+    /// Checksum of all bytes fed so far.
+    pub fn finish(&self) -> u32 {
+        !self.state
+    }
+}
+
+impl Default for Crc32 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Calculates the CRC32 checksum of a buffer.
+///
+/// # Arguments
+///
+/// * `bytes` - Data to checksum
+///
+/// # Examples
+///
+/// ```
+/// use ctl::hash::crc32;
+/// let r = crc32(b"123456789"); // 0xCBF43926
+/// ```
+pub fn crc32(bytes: &[u8]) -> u32 {
+    let mut c = Crc32::new();
+    c.update(bytes);
+    c.finish()
+}
+
+// -----------------------------------------------------------------------------
+// Adler-32
+// -----------------------------------------------------------------------------
+
+const ADLER_MOD: u32 = 65521;
+
+// Largest n such that 255 n (n + 1) / 2 + (n + 1) (ADLER_MOD - 1) fits into a u32
+const ADLER_NMAX: usize = 5552;
+
+/// Incremental Adler-32 checksum as used by zlib.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::hash::Adler32;
+/// let mut a = Adler32::new();
+/// a.update(b"Wiki");
+/// a.update(b"pedia");
+/// let r = a.finish(); // 0x11E60398
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Adler32 {
+    a: u32,
+    b: u32,
+}
+
+impl Adler32 {
+    pub fn new() -> Self {
+        Adler32 { a: 1, b: 0 }
+    }
+
+    /// Feeds more bytes into the checksum.
+    pub fn update(&mut self, bytes: &[u8]) {
+        for chunk in bytes.chunks(ADLER_NMAX) {
+            for &x in chunk {
+                self.a += x as u32;
+                self.b += self.a;
+            }
+
+            self.a %= ADLER_MOD;
+            self.b %= ADLER_MOD;
+        }
+    }
+
+    /// Checksum of all bytes fed so far.
+    pub fn finish(&self) -> u32 {
+        (self.b << 16) | self.a
+    }
+}
+
+impl Default for Adler32 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Calculates the Adler-32 checksum of a buffer.
+///
+/// # Arguments
+///
+/// * `bytes` - Data to checksum
+///
+/// # Examples
+///
+/// ```
+/// use ctl::hash::adler32;
+/// let r = adler32(b"Wikipedia"); // 0x11E60398
+/// ```
+pub fn adler32(bytes: &[u8]) -> u32 {
+    let mut a = Adler32::new();
+    a.update(bytes);
+    a.finish()
+}
+
+// -----------------------------------------------------------------------------
+// FNV-1a
+// -----------------------------------------------------------------------------
+
+const FNV_OFFSET: u64 = 0xCBF2_9CE4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01B3;
+
+/// Fast non-cryptographic 64 bit FNV-1a hasher. Works well for small keys like integers and
+/// points but offers no protection against hash flooding.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashMap;
+/// use ctl::hash::FnvBuildHasher;
+/// let mut m: HashMap<(i32, i32), &str, FnvBuildHasher> = HashMap::default();
+/// m.insert((1, 2), "a");
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct FnvHasher {
+    state: u64,
+}
+
+impl FnvHasher {
+    pub fn new() -> Self {
+        FnvHasher { state: FNV_OFFSET }
+    }
+}
+
+impl Default for FnvHasher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Hasher for FnvHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.state ^= b as u64;
+            self.state = self.state.wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.state
+    }
+}
+
+/// Builder for [`FnvHasher`] usable with std collections.
+pub type FnvBuildHasher = BuildHasherDefault<FnvHasher>;
+
+/// Calculates the 64 bit FNV-1a hash of a buffer.
+///
+/// # Arguments
+///
+/// * `bytes` - Data to hash
+///
+/// # Examples
+///
+/// ```
+/// use ctl::hash::fnv1a;
+/// let h = fnv1a(b"a"); // 0xAF63DC4C8601EC8C
+/// ```
+pub fn fnv1a(bytes: &[u8]) -> u64 {
+    let mut h = FnvHasher::new();
+    h.write(bytes);
+    h.finish()
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_crc32() {
+        assert_eq!(crc32(b""), 0);
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+        assert_eq!(
+            crc32(b"The quick brown fox jumps over the lazy dog"),
+            0x414F_A339
+        );
+
+        let mut c = Crc32::new();
+        c.update(b"1234");
+        c.update(b"56789");
+        assert_eq!(c.finish(), 0xCBF4_3926);
+    }
+
+    #[test]
+    fn test_adler32() {
+        assert_eq!(adler32(b""), 1);
+        assert_eq!(adler32(b"Wikipedia"), 0x11E6_0398);
+
+        let data = vec![0xFFu8; 100_000];
+        let mut a = Adler32::new();
+        a.update(&data[..3]);
+        a.update(&data[3..]);
+
+        let (mut s1, mut s2) = (1u64, 0u64);
+        for &x in &data {
+            s1 = (s1 + x as u64) % 65521;
+            s2 = (s2 + s1) % 65521;
+        }
+
+        assert_eq!(a.finish() as u64, (s2 << 16) | s1);
+    }
+
+    #[test]
+    fn test_fnv() {
+        assert_eq!(fnv1a(b""), 0xCBF2_9CE4_8422_2325);
+        assert_eq!(fnv1a(b"a"), 0xAF63_DC4C_8601_EC8C);
+        assert_eq!(fnv1a(b"foobar"), 0x8594_4171_F739_67E8);
+    }
+}
@@ -1,5 +1,49 @@
-#![feature(destructuring_assignment)]
+//! # `no_std`
+//!
+//! Without the default `std` feature the crate only needs `alloc`. Floating point functions like
+//! `sqrt`, `sin` or `exp` come from `std` with no `libm` fallback, so everything using them is
+//! left out:
+//!
+//! - the `fit` and `signal` modules
+//! - in [`geometry`]: arcs, superellipses, straight skeletons, insets and medial axes, strokes,
+//!   tessellation, the navigation mesh search, the largest empty circle, the swept circle tests,
+//!   flattening rounded rectangles and paths
+//! - in [`grid`]: the euclidean distance transform and the gaussian blur
+//! - in [`math`]: Chebyshev approximation, the special functions, the discrete logarithm and the
+//!   transcendental functions of dual numbers
+//! - in [`optimize`]: Adam and simulated annealing
+//! - in [`random`]: normal samples and brownian motion
+//! - in [`stats`]: eigen decomposition, PCA and the statistical tests
+//! - the `std::error::Error` implementation of [`Error`]
 
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[macro_use]
+extern crate alloc;
+
+mod error;
+pub use error::{Error, Result};
+
+pub mod algorithm;
+pub mod bits;
+pub mod cluster;
+pub mod encoding;
+#[cfg(feature = "std")]
+pub mod fit;
+pub mod geometry;
+pub mod grid;
+pub mod hash;
 pub mod math;
-pub extern crate clap;
+pub mod noise;
+pub mod optimize;
+mod parallel;
+pub mod prefix;
+pub mod random;
+pub mod sequence;
+#[cfg(feature = "std")]
+pub mod signal;
+pub mod stats;
+pub mod units;
 
+#[cfg(feature = "clap")]
+pub extern crate clap;
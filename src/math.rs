@@ -1,11 +1,76 @@
-use std::cmp::PartialEq;
-use std::ops::*;
+use crate::{Error, Result};
+use bigint::BigInt;
+use core::cmp::{Ordering, PartialEq};
+use core::convert::TryFrom;
+use core::ops::*;
+
+/// Arbitrary precision integers.
+pub mod bigint;
+#[cfg(feature = "std")]
+mod chebyshev;
+/// Counting arrangements: factorials, binomials and their relatives, exact with overflow
+/// checks or modulo a prime.
+pub mod combinatorics;
+mod decimal;
+mod dual;
+/// Formal power series truncated at x^n over [`ModInt`](modular::ModInt) coefficients,
+/// multiplied by the number theoretic transform, and the linear recurrences they generate. The
+/// modulus has to be a prime like 998244353 whose M - 1 is divisible by a large power of 2.
+pub mod fps;
+/// Finite fields GF(p^k) with p^k elements, built from polynomials over GF(p) modulo an
+/// irreducible one.
+pub mod galois;
+/// Linear algebra over GF(2), the field of the bits 0 and 1 with XOR as addition.
+pub mod gf2;
+mod integer;
+/// Arithmetic modulo a `u64`. Intermediate values are widened to `u128`, so every modulus up to
+/// `u64::MAX` works. The operands don't have to be reduced and the results always lie in
+/// [0, m). All functions panic for a modulus of 0, like `%` does.
+pub mod modular;
+mod polynomial;
+/// Prime numbers, from single primality tests to sieves over whole ranges.
+pub mod primes;
+mod rational_function;
+/// Matrices over semirings like min-plus, whose powers solve path problems with a fixed number
+/// of edges.
+pub mod semiring;
+/// Fibonacci numbers and the Lucas sequences generalizing them, evaluated in O(log n).
+pub mod sequences;
+#[cfg(feature = "std")]
+mod special;
+mod time;
+
+#[cfg(feature = "std")]
+pub use chebyshev::ChebyshevApprox;
+pub use decimal::Decimal;
+pub use dual::{derivative, Dual};
+pub use integer::{Integer, Signed};
+pub use polynomial::{interpolator, Coefficient, Polynomial};
+pub use primes::{factorize, is_prime};
+pub use rational_function::{PartialFraction, RationalFunction};
+#[cfg(feature = "std")]
+pub use special::{beta, beta_inc, erf, erfc, gamma, gamma_p, gamma_q, lgamma};
+pub use time::StepClock;
 
 // -----------------------------------------------------------------------------
 // GCD
 // -----------------------------------------------------------------------------
 
-/// Calculates the gcd of 2 values
+/// Euclid's algorithm on magnitudes.
+fn gcd_unsigned<U: Integer>(mut a: U, mut b: U) -> U {
+    while b != U::ZERO {
+        let r = a % b;
+        a = b;
+        b = r;
+    }
+
+    a
+}
+
+/// Calculates the gcd of 2 values of any primitive integer type. The result is never negative.
+///
+/// Panics if the result doesn't fit into a signed type, which only happens for `gcd(MIN, 0)`
+/// and `gcd(MIN, MIN)`. Use [`try_gcd`] to handle it.
 ///
 /// # Arguments
 ///
@@ -17,16 +82,101 @@ use std::ops::*;
 /// ```
 /// use ctl::math::gcd;
 /// let x = gcd(713, 552); // 23
+/// let y = gcd(-12i64, 18); // 6
+/// let z = gcd(u128::MAX, 5); // 5
 /// ```
-pub fn gcd(a: i32, b: i32) -> i32 {
-    if b == 0 {
+pub fn gcd<T: Integer>(a: T, b: T) -> T {
+    try_gcd(a, b).expect("gcd does not fit into the integer type")
+}
+
+/// Fallible version of [`gcd`]. Fails with [`Error::Overflow`] instead of panicking when the
+/// result can't be represented, e.g. for `gcd(i32::MIN, 0)`.
+///
+/// # Arguments
+///
+/// * `a` - First gcd integer parameter
+/// * `b` - Second gcd integer parameter
+///
+/// # Examples
+///
+/// ```
+/// use ctl::math::try_gcd;
+/// let x = try_gcd(713, 552); // Ok(23)
+/// let y = try_gcd(i32::MIN, 0); // Err(Error::Overflow)
+/// ```
+pub fn try_gcd<T: Integer>(a: T, b: T) -> Result<T> {
+    T::from_unsigned(gcd_unsigned(a.unsigned_abs(), b.unsigned_abs())).ok_or(Error::Overflow)
+}
+
+/// Stein's binary gcd on magnitudes, which only needs shifts and subtractions.
+fn binary_gcd_unsigned<U: Integer>(mut a: U, mut b: U) -> U {
+    if a == U::ZERO {
+        return b;
+    }
+    if b == U::ZERO {
         return a;
     }
 
-    gcd(b, a % b)
+    // Common factors of 2
+    let shift = a.trailing_zeros().min(b.trailing_zeros());
+    a = a >> a.trailing_zeros();
+
+    loop {
+        b = b >> b.trailing_zeros();
+        if a > b {
+            core::mem::swap(&mut a, &mut b);
+        }
+
+        b = b - a;
+        if b == U::ZERO {
+            return a << shift;
+        }
+    }
+}
+
+/// Calculates the gcd of 2 values with Stein's binary algorithm. Gives the same results as
+/// [`gcd`] but avoids divisions, which makes it faster for wide types like `u64` in hot loops.
+///
+/// Panics if the result doesn't fit into a signed type, like [`gcd`]. Use [`try_binary_gcd`] to
+/// handle it.
+///
+/// # Arguments
+///
+/// * `a` - First gcd integer parameter
+/// * `b` - Second gcd integer parameter
+///
+/// # Examples
+///
+/// ```
+/// use ctl::math::binary_gcd;
+/// let x = binary_gcd(713u64, 552); // 23
+/// let y = binary_gcd(-48, 180); // 12
+/// ```
+pub fn binary_gcd<T: Integer>(a: T, b: T) -> T {
+    try_binary_gcd(a, b).expect("gcd does not fit into the integer type")
+}
+
+/// Fallible version of [`binary_gcd`]. Fails with [`Error::Overflow`] instead of panicking when
+/// the result can't be represented.
+///
+/// # Arguments
+///
+/// * `a` - First gcd integer parameter
+/// * `b` - Second gcd integer parameter
+///
+/// # Examples
+///
+/// ```
+/// use ctl::math::try_binary_gcd;
+/// let x = try_binary_gcd(713, 552); // Ok(23)
+/// let y = try_binary_gcd(i64::MIN, 0); // Err(Error::Overflow)
+/// ```
+pub fn try_binary_gcd<T: Integer>(a: T, b: T) -> Result<T> {
+    T::from_unsigned(binary_gcd_unsigned(a.unsigned_abs(), b.unsigned_abs())).ok_or(Error::Overflow)
 }
 
 /// Extended version of the gcd algorithm. It also calculates s and t from gcd(a, b) = as + bt.
+/// See [`bezout`] for a generic version with a labeled result.
 ///
 /// # Arguments
 ///
@@ -44,7 +194,7 @@ pub fn extended_gcd(mut a: i32, mut b: i32) -> (i32, i32, i32) {
         return (a.abs(), 1, 0);
     }
 
-    if a * a < b * b {
+    if a.unsigned_abs() < b.unsigned_abs() {
         (a, b) = (b, a);
     }
 
@@ -73,6 +223,299 @@ pub fn extended_gcd(mut a: i32, mut b: i32) -> (i32, i32, i32) {
     }
 }
 
+/// Fallible version of [`extended_gcd`]. Fails with [`Error::Overflow`] for the inputs whose
+/// gcd or quotient doesn't fit into an `i32`, which are `i32::MIN` paired with 0, -1 or itself.
+///
+/// # Arguments
+///
+/// * `a` - First gcd integer parameter
+/// * `b` - Second gcd integer parameter
+///
+/// # Examples
+///
+/// ```
+/// use ctl::math::try_extended_gcd;
+/// let x = try_extended_gcd(713, 552); // Ok((23, 7, -9))
+/// let y = try_extended_gcd(i32::MIN, 0); // Err(Error::Overflow)
+/// ```
+pub fn try_extended_gcd(a: i32, b: i32) -> Result<(i32, i32, i32)> {
+    let other = if a == i32::MIN { b } else { a };
+    if (a == i32::MIN || b == i32::MIN) && (other == 0 || other == -1 || other == i32::MIN) {
+        return Err(Error::Overflow);
+    }
+
+    Ok(extended_gcd(a, b))
+}
+
+// -----------------------------------------------------------------------------
+// Bezout
+// -----------------------------------------------------------------------------
+
+/// Result of [`bezout`], satisfying a x + b y = gcd.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bezout<T> {
+    /// Never negative
+    pub gcd: T,
+    /// Coefficient of a
+    pub x: T,
+    /// Coefficient of b
+    pub y: T,
+}
+
+/// Generic extended gcd over all signed primitive integer types. The gcd is never negative and
+/// the coefficients carry the signs of the inputs, so a x + b y = gcd always holds. They are the
+/// small pair found by Euclid's algorithm with |x| <= |b| / gcd and |y| <= |a| / gcd, except for
+/// x = ±1, y = 0 when b is 0. Both inputs being 0 gives all zeros.
+///
+/// Panics if the gcd doesn't fit into the type, which only happens for `bezout(MIN, 0)` and
+/// `bezout(MIN, MIN)`. Use [`try_bezout`] to handle it.
+///
+/// # Arguments
+///
+/// * `a` - First gcd integer parameter
+/// * `b` - Second gcd integer parameter
+///
+/// # Examples
+///
+/// ```
+/// use ctl::math::{bezout, Bezout};
+/// let r = bezout(713i64, 552); // Bezout { gcd: 23, x: 7, y: -9 }
+/// let s = bezout(-4, 6); // Bezout { gcd: 2, x: 1, y: 1 }
+/// ```
+pub fn bezout<T: Signed>(a: T, b: T) -> Bezout<T> {
+    try_bezout(a, b).expect("gcd does not fit into the integer type")
+}
+
+/// Fallible version of [`bezout`]. Fails with [`Error::Overflow`] instead of panicking when the
+/// gcd can't be represented.
+///
+/// # Arguments
+///
+/// * `a` - First gcd integer parameter
+/// * `b` - Second gcd integer parameter
+///
+/// # Examples
+///
+/// ```
+/// use ctl::math::try_bezout;
+/// let r = try_bezout(i128::MIN, 3); // Ok(Bezout { gcd: 1, .. })
+/// let e = try_bezout(i8::MIN, 0); // Err(Error::Overflow)
+/// ```
+pub fn try_bezout<T: Signed>(a: T, b: T) -> Result<Bezout<T>> {
+    let (ua, ub) = (a.unsigned_abs(), b.unsigned_abs());
+
+    // Euclid on the magnitudes with the larger one first. The coefficients of the last nonzero
+    // remainder are bounded by half the magnitudes, the step after it is never computed.
+    let swap = ua < ub;
+    let (mut r0, mut r1) = if swap { (ub, ua) } else { (ua, ub) };
+    let (mut s0, mut s1, mut t0, mut t1) = (T::ONE, T::ZERO, T::ZERO, T::ONE);
+
+    let (gcd, x, y) = if r1 == T::Unsigned::ZERO {
+        let x = if r0 == r1 { T::ZERO } else { T::ONE };
+        (r0, x, T::ZERO)
+    } else {
+        loop {
+            let r = r0 % r1;
+            if r == T::Unsigned::ZERO {
+                break (r1, s1, t1);
+            }
+
+            let q = T::from_unsigned(r0 / r1).ok_or(Error::Overflow)?;
+            (s0, s1) = (s1, s0 - q * s1);
+            (t0, t1) = (t1, t0 - q * t1);
+            (r0, r1) = (r1, r);
+        }
+    };
+
+    let (mut x, mut y) = if swap { (y, x) } else { (x, y) };
+    if a < T::ZERO {
+        x = -x;
+    }
+    if b < T::ZERO {
+        y = -y;
+    }
+
+    Ok(Bezout {
+        gcd: T::from_unsigned(gcd).ok_or(Error::Overflow)?,
+        x,
+        y,
+    })
+}
+
+// -----------------------------------------------------------------------------
+// LCM
+// -----------------------------------------------------------------------------
+
+/// Least common multiple of magnitudes, dividing before multiplying to keep the intermediate
+/// value small. `None` on overflow.
+fn lcm_unsigned<U: Integer>(a: U, b: U) -> Option<U> {
+    if a == U::ZERO || b == U::ZERO {
+        return Some(U::ZERO);
+    }
+
+    (a / gcd_unsigned(a, b)).checked_mul(b)
+}
+
+/// Calculates the lcm of 2 values of any primitive integer type. The result is never negative
+/// and 0 if one of the values is 0.
+///
+/// Panics if the result overflows. Use [`try_lcm`] to handle it.
+///
+/// # Arguments
+///
+/// * `a` - First lcm integer parameter
+/// * `b` - Second lcm integer parameter
+///
+/// # Examples
+///
+/// ```
+/// use ctl::math::lcm;
+/// let x = lcm(4, 6); // 12
+/// let y = lcm(-3i64, 5); // 15
+/// ```
+pub fn lcm<T: Integer>(a: T, b: T) -> T {
+    try_lcm(a, b).expect("lcm does not fit into the integer type")
+}
+
+/// Fallible version of [`lcm`]. Fails with [`Error::Overflow`] instead of panicking when the
+/// result can't be represented.
+///
+/// # Arguments
+///
+/// * `a` - First lcm integer parameter
+/// * `b` - Second lcm integer parameter
+///
+/// # Examples
+///
+/// ```
+/// use ctl::math::try_lcm;
+/// let x = try_lcm(4, 6); // Ok(12)
+/// let y = try_lcm(1u8 << 7, 3); // Err(Error::Overflow)
+/// ```
+pub fn try_lcm<T: Integer>(a: T, b: T) -> Result<T> {
+    lcm_unsigned(a.unsigned_abs(), b.unsigned_abs())
+        .and_then(T::from_unsigned)
+        .ok_or(Error::Overflow)
+}
+
+/// Calculates the gcd of all values of an iterator, 0 if it is empty. The magnitudes are folded
+/// so only the final result has to fit, and the fold stops early once it reaches 1.
+///
+/// Panics if the result doesn't fit into a signed type, like [`gcd`].
+///
+/// # Arguments
+///
+/// * `values` - Integers to fold
+///
+/// # Examples
+///
+/// ```
+/// use ctl::math::gcd_iter;
+/// let x = gcd_iter((1..5).map(|i| i * 6)); // 6
+/// ```
+pub fn gcd_iter<T: Integer, I: IntoIterator<Item = T>>(values: I) -> T {
+    let mut acc = T::Unsigned::ZERO;
+    for v in values {
+        acc = gcd_unsigned(acc, v.unsigned_abs());
+        if acc == T::Unsigned::ONE {
+            break;
+        }
+    }
+
+    T::from_unsigned(acc).expect("gcd does not fit into the integer type")
+}
+
+/// Calculates the gcd of all values of a slice, see [`gcd_iter`].
+///
+/// # Arguments
+///
+/// * `values` - Integers to fold
+///
+/// # Examples
+///
+/// ```
+/// use ctl::math::gcd_of;
+/// let x = gcd_of(&[12i64, -18, 30]); // 6
+/// let y = gcd_of::<i32>(&[]); // 0
+/// ```
+pub fn gcd_of<T: Integer>(values: &[T]) -> T {
+    gcd_iter(values.iter().copied())
+}
+
+/// Calculates the lcm of all values of an iterator, 1 if it is empty.
+///
+/// Panics if the result overflows. Use [`try_lcm_iter`] to handle it.
+///
+/// # Arguments
+///
+/// * `values` - Integers to fold
+///
+/// # Examples
+///
+/// ```
+/// use ctl::math::lcm_iter;
+/// let x = lcm_iter(1..=10u64); // 2520
+/// ```
+pub fn lcm_iter<T: Integer, I: IntoIterator<Item = T>>(values: I) -> T {
+    try_lcm_iter(values).expect("lcm does not fit into the integer type")
+}
+
+/// Fallible version of [`lcm_iter`]. Fails with [`Error::Overflow`] as soon as the running lcm
+/// can't be represented.
+///
+/// # Arguments
+///
+/// * `values` - Integers to fold
+///
+/// # Examples
+///
+/// ```
+/// use ctl::math::try_lcm_iter;
+/// let x = try_lcm_iter(1..=30u32); // Err(Error::Overflow)
+/// ```
+pub fn try_lcm_iter<T: Integer, I: IntoIterator<Item = T>>(values: I) -> Result<T> {
+    let mut acc = T::Unsigned::ONE;
+    for v in values {
+        acc = lcm_unsigned(acc, v.unsigned_abs()).ok_or(Error::Overflow)?;
+    }
+
+    T::from_unsigned(acc).ok_or(Error::Overflow)
+}
+
+/// Calculates the lcm of all values of a slice, see [`lcm_iter`]. Useful to find the common
+/// denominator of several fractions.
+///
+/// # Arguments
+///
+/// * `values` - Integers to fold
+///
+/// # Examples
+///
+/// ```
+/// use ctl::math::lcm_of;
+/// let x = lcm_of(&[4i64, 6, 10]); // 60
+/// ```
+pub fn lcm_of<T: Integer>(values: &[T]) -> T {
+    lcm_iter(values.iter().copied())
+}
+
+/// Fallible version of [`lcm_of`].
+///
+/// # Arguments
+///
+/// * `values` - Integers to fold
+///
+/// # Examples
+///
+/// ```
+/// use ctl::math::try_lcm_of;
+/// let x = try_lcm_of(&[4i64, 6, 10]); // Ok(60)
+/// let y = try_lcm_of(&[i8::MAX, 2]); // Err(Error::Overflow)
+/// ```
+pub fn try_lcm_of<T: Integer>(values: &[T]) -> Result<T> {
+    try_lcm_iter(values.iter().copied())
+}
+
 // -----------------------------------------------------------------------------
 // Fraction
 // -----------------------------------------------------------------------------
@@ -100,6 +543,28 @@ pub fn frac(a: i32, b: i32) -> Fraction {
     Fraction { q: a, d: b }
 }
 
+/// Fallible version of [`frac`] which rejects a zero denominator.
+///
+/// # Arguments
+///
+/// * `a` - Top of fraction
+/// * `b` - Bottom of fraction
+///
+/// # Examples
+///
+/// ```
+/// use ctl::math::try_frac;
+/// let a = try_frac(1, 2); // Ok(Fraction { q: 1, d: 2 })
+/// let b = try_frac(1, 0); // Err(Error::DivisionByZero)
+/// ```
+pub fn try_frac(a: i32, b: i32) -> Result<Fraction> {
+    if b == 0 {
+        return Err(Error::DivisionByZero);
+    }
+
+    Ok(frac(a, b))
+}
+
 impl Fraction {
     /// Convert fraction to floating point representation.
     ///
@@ -111,7 +576,7 @@ impl Fraction {
     /// let r = f.to_f64(); // Outputs .25
     /// ```
     pub fn to_f64(self) -> f64 {
-        return self.q as f64 / self.d as f64;
+        self.q as f64 / self.d as f64
     }
 
     /// Outputs the reduced version of the fraction. Arithmetic operations often do it
@@ -125,9 +590,189 @@ impl Fraction {
     /// let r = f.reduce(); // Outputs (1, 2)
     /// ```
     pub fn reduce(self) -> Fraction {
-        let r = gcd(self.q, self.d);
+        let r = binary_gcd(self.q, self.d);
         frac(self.q / r, self.d / r)
     }
+
+    /// Fallible version of [`Fraction::reduce`]. Fails for the undefined fraction 0/0 and when
+    /// the reduced parts overflow.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ctl::math::frac;
+    /// let r = frac(2, 4).try_reduce(); // Ok(Fraction { q: 1, d: 2 })
+    /// let e = frac(0, 0).try_reduce(); // Err(Error::DivisionByZero)
+    /// ```
+    pub fn try_reduce(self) -> Result<Fraction> {
+        let r = try_binary_gcd(self.q, self.d)?;
+
+        if r == 0 {
+            return Err(Error::DivisionByZero);
+        }
+
+        Ok(frac(
+            self.q.checked_div(r).ok_or(Error::Overflow)?,
+            self.d.checked_div(r).ok_or(Error::Overflow)?,
+        ))
+    }
+
+    /// Adds 2 fractions, failing with [`Error::Overflow`] instead of panicking.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ctl::math::frac;
+    /// let r = frac(1, 2).try_add(frac(1, 3)); // Ok(Fraction { q: 5, d: 6 })
+    /// ```
+    pub fn try_add(self, rhs: Fraction) -> Result<Fraction> {
+        let q =
+            checked(self.q.checked_mul(rhs.d))?.checked_add(checked(rhs.q.checked_mul(self.d))?);
+
+        Ok(frac(checked(q)?, checked(self.d.checked_mul(rhs.d))?))
+    }
+
+    /// Subtracts 2 fractions, failing with [`Error::Overflow`] instead of panicking.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ctl::math::frac;
+    /// let r = frac(1, 2).try_sub(frac(1, 3)); // Ok(Fraction { q: 1, d: 6 })
+    /// ```
+    pub fn try_sub(self, rhs: Fraction) -> Result<Fraction> {
+        self.try_add(frac(checked(rhs.q.checked_neg())?, rhs.d))
+    }
+
+    /// Multiplies 2 fractions, failing with [`Error::Overflow`] instead of panicking.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ctl::math::frac;
+    /// let r = frac(1, 2).try_mul(frac(2, 3)); // Ok(Fraction { q: 2, d: 6 })
+    /// ```
+    pub fn try_mul(self, rhs: Fraction) -> Result<Fraction> {
+        Ok(frac(
+            checked(self.q.checked_mul(rhs.q))?,
+            checked(self.d.checked_mul(rhs.d))?,
+        ))
+    }
+
+    /// Divides 2 fractions, failing with [`Error::DivisionByZero`] when `rhs` is zero and with
+    /// [`Error::Overflow`] instead of panicking.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ctl::math::frac;
+    /// let r = frac(1, 2).try_div(frac(2, 3)); // Ok(Fraction { q: 3, d: 4 })
+    /// let e = frac(1, 2).try_div(frac(0, 3)); // Err(Error::DivisionByZero)
+    /// ```
+    pub fn try_div(self, rhs: Fraction) -> Result<Fraction> {
+        if rhs.q == 0 {
+            return Err(Error::DivisionByZero);
+        }
+
+        Ok(frac(
+            checked(self.q.checked_mul(rhs.d))?,
+            checked(self.d.checked_mul(rhs.q))?,
+        ))
+    }
+}
+
+impl Fraction {
+    /// Sums fractions exactly. The running sum keeps the least common denominator in `i128`
+    /// and is reduced after every step, so only a reduced result that doesn't fit into `i32`
+    /// fails with [`Error::Overflow`], not intermediate sums. Fails with
+    /// [`Error::DivisionByZero`] for a zero denominator.
+    ///
+    /// # Arguments
+    ///
+    /// * `values` - Fractions to sum
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ctl::math::{frac, Fraction};
+    /// let v = vec![frac(1, 6), frac(1, 10), frac(1, 15)];
+    /// let s = Fraction::sum_exact(v); // Ok(Fraction { q: 1, d: 3 })
+    /// let e = Fraction::sum_exact(vec![frac(1, 2), frac(1, 0)]); // Err(Error::DivisionByZero)
+    /// ```
+    pub fn sum_exact<I: IntoIterator<Item = Fraction>>(values: I) -> Result<Fraction> {
+        let (q, d) = sum_i128(values)?.0;
+        Ok(frac(narrow(q)?, narrow(d)?))
+    }
+
+    /// Arithmetic mean of fractions, exact like [`Fraction::sum_exact`]. Fails with
+    /// [`Error::DivisionByZero`] for no values.
+    ///
+    /// # Arguments
+    ///
+    /// * `values` - Fractions to average
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ctl::math::{frac, Fraction};
+    /// let v = vec![frac(1, 2), frac(1, 3)];
+    /// let m = Fraction::mean_exact(v); // Ok(Fraction { q: 5, d: 12 })
+    /// ```
+    pub fn mean_exact<I: IntoIterator<Item = Fraction>>(values: I) -> Result<Fraction> {
+        let ((q, d), n) = sum_i128(values)?;
+        if n == 0 {
+            return Err(Error::DivisionByZero);
+        }
+
+        let d = d.checked_mul(n as i128).ok_or(Error::Overflow)?;
+        let g = gcd(q, d);
+        Ok(frac(narrow(q / g)?, narrow(d / g)?))
+    }
+}
+
+/// Reduced sum (q, d) with d > 0 and the number of values.
+fn sum_i128<I: IntoIterator<Item = Fraction>>(values: I) -> Result<((i128, i128), usize)> {
+    let (mut q, mut d, mut n) = (0i128, 1i128, 0);
+
+    for f in values {
+        if f.d == 0 {
+            return Err(Error::DivisionByZero);
+        }
+        let (fq, fd) = if f.d < 0 {
+            (-(f.q as i128), -(f.d as i128))
+        } else {
+            (f.q as i128, f.d as i128)
+        };
+
+        // Extend both to the lcm of the denominators
+        let g = gcd(d, fd);
+        let l = (d / g).checked_mul(fd).ok_or(Error::Overflow)?;
+        let a = q.checked_mul(l / d).ok_or(Error::Overflow)?;
+        let b = fq.checked_mul(l / fd).ok_or(Error::Overflow)?;
+        q = a.checked_add(b).ok_or(Error::Overflow)?;
+        d = l;
+
+        let g = gcd(q, d);
+        q /= g;
+        d /= g;
+        n += 1;
+    }
+
+    Ok(((q, d), n))
+}
+
+fn narrow(x: i128) -> Result<i32> {
+    i32::try_from(x).map_err(|_| Error::Overflow)
+}
+
+fn checked(x: Option<i32>) -> Result<i32> {
+    x.ok_or(Error::Overflow)
+}
+
+impl From<i32> for Fraction {
+    fn from(x: i32) -> Self {
+        frac(x, 1)
+    }
 }
 
 impl Neg for Fraction {
@@ -227,6 +872,7 @@ impl Div for Fraction {
 impl Div<i32> for Fraction {
     type Output = Fraction;
 
+    #[allow(clippy::suspicious_arithmetic_impl)]
     fn div(self, rhs: i32) -> Self::Output {
         frac(self.q, self.d * rhs)
     }
@@ -235,6 +881,7 @@ impl Div<i32> for Fraction {
 impl Div<Fraction> for i32 {
     type Output = Fraction;
 
+    #[allow(clippy::suspicious_arithmetic_impl)]
     fn div(self, rhs: Fraction) -> Self::Output {
         frac(rhs.d * self, rhs.q)
     }
@@ -246,6 +893,194 @@ impl PartialEq for Fraction {
     }
 }
 
+impl PartialOrd for Fraction {
+    /// Compares the values of the fractions. Fractions with a zero denominator are unordered.
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        if self.d == 0 || other.d == 0 {
+            return None;
+        }
+
+        // Cross multiplication flips the order for a negative denominator product
+        let l = self.q as i64 * other.d as i64;
+        let r = other.q as i64 * self.d as i64;
+
+        if (self.d < 0) == (other.d < 0) {
+            l.partial_cmp(&r)
+        } else {
+            r.partial_cmp(&l)
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Square root approximation
+// -----------------------------------------------------------------------------
+
+/// Simplest fraction, the one with the smallest denominator, within `tolerance` of the square
+/// root of `value`. Panics where [`try_sqrt_approx`] fails.
+///
+/// # Arguments
+///
+/// * `value` - Non-negative fraction to take the root of
+/// * `tolerance` - Positive bound on the absolute error
+///
+/// # Examples
+///
+/// ```
+/// use ctl::math::{frac, sqrt_approx};
+/// let a = sqrt_approx(frac(2, 1), frac(1, 1000)); // Fraction { q: 41, d: 29 }
+/// let b = sqrt_approx(frac(9, 4), frac(1, 1000)); // Fraction { q: 3, d: 2 }
+/// ```
+pub fn sqrt_approx(value: Fraction, tolerance: Fraction) -> Fraction {
+    try_sqrt_approx(value, tolerance).expect("no approximation with parts fitting into i32")
+}
+
+/// Fallible version of [`sqrt_approx`]. Descends the Stern–Brocot tree, which lists every
+/// positive fraction once with denominators growing with depth, so the first fraction within
+/// the tolerance is the simplest one. Runs of steps in the same direction are taken at once by
+/// binary search and every comparison with the root is exact on squares, taking
+/// O(log² (1 / tolerance)) steps. Newton's iteration would get there in fewer steps, but its
+/// denominators square each time and leave `i32` after a handful.
+///
+/// Fails with [`Error::Domain`] for a negative value or a non-positive tolerance, with
+/// [`Error::DivisionByZero`] for a zero denominator and with [`Error::Overflow`] when the
+/// approximation needs parts beyond `i32`.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::math::{frac, try_sqrt_approx};
+/// let a = try_sqrt_approx(frac(-1, 1), frac(1, 10)); // Err(Error::Domain)
+/// ```
+pub fn try_sqrt_approx(value: Fraction, tolerance: Fraction) -> Result<Fraction> {
+    let (vn, vd) = positive_parts(value)?;
+    let (tn, td) = positive_parts(tolerance)?;
+    if tn == 0 {
+        return Err(Error::Domain);
+    }
+
+    let big = BigInt::from;
+    let (vn, vd, tn, td) = (big(vn), big(vd), big(tn), big(td));
+    let square_cmp = |x: &BigInt, d: &BigInt| (x * x * &vd).cmp(&(&vn * d * d));
+
+    // p / q against the root: below it, and near enough from below or from above
+    let below = |p: i64, q: i64| square_cmp(&big(p), &big(q)) == Ordering::Less;
+    let near_below = |p: i64, q: i64| {
+        let x = big(p) * &td + &tn * big(q);
+        square_cmp(&x, &(big(q) * &td)) != Ordering::Less
+    };
+    let near_above = |p: i64, q: i64| {
+        let x = big(p) * &td - &tn * big(q);
+        x.is_negative() || square_cmp(&x, &(big(q) * &td)) != Ordering::Greater
+    };
+
+    if near_below(0, 1) {
+        return Ok(frac(0, 1));
+    }
+
+    let max = i32::MAX as i64;
+    let (mut l, mut r) = ((0i64, 1i64), (1i64, 0i64));
+    loop {
+        let step =
+            |from: (i64, i64), to: (i64, i64), j: i64| (from.0 + j * to.0, from.1 + j * to.1);
+        let m = step(l, r, 1);
+        if m.0 > max || m.1 > max {
+            return Err(Error::Overflow);
+        }
+        if square_cmp(&big(m.0), &big(m.1)) == Ordering::Equal {
+            return Ok(frac(m.0 as i32, m.1 as i32));
+        }
+
+        // Largest run of fractions from l towards r, or r towards l, staying on the same side
+        let from_below = below(m.0, m.1);
+        let (from, to) = if from_below { (l, r) } else { (r, l) };
+        let side = |p, q| below(p, q) == from_below;
+        let near = |p, q| {
+            if from_below {
+                near_below(p, q)
+            } else {
+                near_above(p, q)
+            }
+        };
+        let cap = [(from.0, to.0), (from.1, to.1)]
+            .iter()
+            .filter(|&&(_, t)| t > 0)
+            .map(|&(f, t)| (max - f) / t)
+            .min()
+            .unwrap();
+        let at = |j| step(from, to, j);
+        let k = last_true(cap, |j| {
+            let (p, q) = at(j);
+            side(p, q)
+        });
+
+        let (p, q) = at(k);
+        if near(p, q) {
+            let j = first_true(k, |j| {
+                let (p, q) = at(j);
+                near(p, q)
+            });
+            let (p, q) = at(j);
+            return Ok(frac(p as i32, q as i32));
+        }
+
+        if from_below {
+            l = (p, q);
+        } else {
+            r = (p, q);
+        }
+    }
+}
+
+/// Non-negative numerator and positive denominator of a fraction.
+fn positive_parts(f: Fraction) -> Result<(i64, i64)> {
+    if f.d == 0 {
+        return Err(Error::DivisionByZero);
+    }
+
+    let (q, d) = (f.q as i64, f.d as i64);
+    let (q, d) = if d < 0 { (-q, -d) } else { (q, d) };
+    if q < 0 {
+        return Err(Error::Domain);
+    }
+    Ok((q, d))
+}
+
+/// Largest j in [1, cap] with pred(j), for a predicate true at 1 and then false from some
+/// point on.
+fn last_true<F: Fn(i64) -> bool>(cap: i64, pred: F) -> i64 {
+    // Gallop to a false value, then bisect
+    let (mut lo, mut hi) = (1, 2);
+    while hi <= cap && pred(hi) {
+        lo = hi;
+        hi *= 2;
+    }
+    let mut hi = hi.min(cap + 1);
+    while hi - lo > 1 {
+        let mid = lo + (hi - lo) / 2;
+        if pred(mid) {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+    lo
+}
+
+/// Smallest j in [1, hi] with pred(j), for a predicate false and then true up to hi.
+fn first_true<F: Fn(i64) -> bool>(hi: i64, pred: F) -> i64 {
+    let (mut lo, mut hi) = (0, hi);
+    while hi - lo > 1 {
+        let mid = lo + (hi - lo) / 2;
+        if pred(mid) {
+            hi = mid;
+        } else {
+            lo = mid;
+        }
+    }
+    hi
+}
+
 // -----------------------------------------------------------------------------
 // Tests
 // -----------------------------------------------------------------------------
@@ -253,15 +1088,67 @@ impl PartialEq for Fraction {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use alloc::vec::Vec;
 
     #[test]
     fn test_gcd() {
         assert_eq!(gcd(713, 552), 23);
         assert_eq!(gcd(713, 0), 713);
         assert_eq!(gcd(552, 713), 23);
-        assert_eq!(gcd(-552, -713), -23);
+        assert_eq!(gcd(-552, -713), 23);
         assert_eq!(gcd(11253, 2607), 33);
-        assert_eq!(gcd(-11253, 2607), -33);
+        assert_eq!(gcd(-11253, 2607), 33);
+        assert_eq!(gcd(0, -7), 7);
+        assert_eq!(gcd(i32::MIN, -1), 1);
+
+        assert_eq!(gcd(48u8, 180), 12);
+        assert_eq!(gcd(1u64 << 60, 3 << 40), 1 << 40);
+        assert_eq!(gcd(i128::MIN, 1 << 100), 1 << 100);
+        assert_eq!(gcd(u128::MAX, u128::MAX - 1), 1);
+        assert_eq!(gcd(-9isize, 0), 9);
+    }
+
+    #[test]
+    fn test_binary_gcd() {
+        let mut x = 0x2545_f491_4f6c_dd1du64;
+        for _ in 0..1000 {
+            x ^= x << 13;
+            x ^= x >> 7;
+            x ^= x << 17;
+            let (a, b) = (x >> (x % 64), x.rotate_left(17) >> (x % 50));
+            assert_eq!(binary_gcd(a, b), gcd(a, b));
+            assert_eq!(binary_gcd(a as i64, b as i64), gcd(a as i64, b as i64));
+        }
+
+        assert_eq!(binary_gcd(0u32, 0), 0);
+        assert_eq!(binary_gcd(0, -7), 7);
+        assert_eq!(binary_gcd(1u128 << 100, 3 << 40), 1 << 40);
+        assert_eq!(binary_gcd(i32::MIN, -1), 1);
+        assert_eq!(try_binary_gcd(i8::MIN, i8::MIN), Err(Error::Overflow));
+        assert_eq!(try_binary_gcd(u8::MAX, u8::MAX - 1), Ok(1));
+    }
+
+    #[test]
+    fn test_lcm() {
+        assert_eq!(lcm(4, 6), 12);
+        assert_eq!(lcm(-4, 6), 12);
+        assert_eq!(lcm(0, 6), 0);
+        assert_eq!(lcm(7u8, 7), 7);
+        assert_eq!(try_lcm(1u8 << 7, 3), Err(Error::Overflow));
+        assert_eq!(try_lcm(1u8 << 7, 64), Ok(128));
+        assert_eq!(try_lcm(i8::MIN, 2), Err(Error::Overflow));
+
+        assert_eq!(gcd_of(&[12i64, -18, 30]), 6);
+        assert_eq!(gcd_of::<i32>(&[]), 0);
+        assert_eq!(gcd_of(&[i32::MIN, 6]), 2);
+        assert_eq!(gcd_iter([9u16, 4, 0, 12]), 1);
+        assert_eq!(lcm_of(&[4i64, 6, 10]), 60);
+        assert_eq!(lcm_of::<u8>(&[]), 1);
+        assert_eq!(lcm_of(&[3, 0, 5]), 0);
+        assert_eq!(lcm_iter(1..=10u64), 2520);
+        assert_eq!(try_lcm_iter(1..=30u32), Err(Error::Overflow));
+        assert_eq!(try_lcm_iter(1..=20u64), Ok(232_792_560));
+        assert_eq!(try_lcm_of(&[i8::MAX, 2]), Err(Error::Overflow));
     }
 
     #[test]
@@ -274,6 +1161,82 @@ mod tests {
         assert_eq!(extended_gcd(11253, 2607), (33, 19, -82));
     }
 
+    #[test]
+    fn test_bezout() {
+        assert_eq!(
+            bezout(713, 552),
+            Bezout {
+                gcd: 23,
+                x: 7,
+                y: -9
+            }
+        );
+        assert_eq!(
+            bezout(552, 713),
+            Bezout {
+                gcd: 23,
+                x: -9,
+                y: 7
+            }
+        );
+        assert_eq!(bezout(-4, 6), Bezout { gcd: 2, x: 1, y: 1 });
+        assert_eq!(
+            bezout(-5i8, 0),
+            Bezout {
+                gcd: 5,
+                x: -1,
+                y: 0
+            }
+        );
+        assert_eq!(bezout(0i16, 0), Bezout { gcd: 0, x: 0, y: 0 });
+        assert_eq!(try_bezout(i8::MIN, 0), Err(Error::Overflow));
+        assert_eq!(try_bezout(i8::MIN, i8::MIN), Err(Error::Overflow));
+        assert_eq!(
+            bezout(i8::MIN, -1),
+            Bezout {
+                gcd: 1,
+                x: 0,
+                y: -1
+            }
+        );
+
+        let mut v = 0x2545_f491_4f6c_dd1du64;
+        for _ in 0..1000 {
+            v ^= v << 13;
+            v ^= v >> 7;
+            v ^= v << 17;
+            let (a, b) = (v as i64 >> (v % 60), (v.rotate_left(29) as i64) >> (v % 50));
+
+            let r = bezout(a, b);
+            assert_eq!(r.gcd, gcd(a, b));
+            let lhs = a as i128 * r.x as i128 + b as i128 * r.y as i128;
+            assert_eq!(lhs, r.gcd as i128);
+            if a != 0 && b != 0 {
+                assert!(r.x.unsigned_abs() <= b.unsigned_abs() / r.gcd as u64);
+                assert!(r.y.unsigned_abs() <= a.unsigned_abs() / r.gcd as u64);
+            }
+        }
+
+        // Extremes where a * a overflows
+        let r = bezout(i128::MIN, 3);
+        assert_eq!(r.gcd, 1);
+        assert_eq!(
+            i128::MIN
+                .wrapping_mul(r.x)
+                .wrapping_add(r.y.wrapping_mul(3)),
+            1
+        );
+        let r = bezout(i64::MAX, i64::MAX - 1);
+        assert_eq!(
+            r,
+            Bezout {
+                gcd: 1,
+                x: 1,
+                y: -1
+            }
+        );
+    }
+
     #[test]
     fn test_fractions() {
         let a = frac(1, 2);
@@ -286,5 +1249,116 @@ mod tests {
         assert_eq!(a - b, Fraction { q: -11, d: 6 });
         assert_eq!(d * c, Fraction { q: 184, d: 711 });
         assert_eq!(e / d, Fraction { q: -2212, d: 23 });
+
+        assert!(a < b && b > c && e < d);
+        assert!(frac(1, -2) < frac(1, 3));
+        assert!(frac(-1, -2) > frac(1, 3));
+        assert_eq!(frac(2, 4).partial_cmp(&frac(-1, -2)), Some(Ordering::Equal));
+        assert_eq!(frac(1, 0).partial_cmp(&a), None);
+    }
+
+    #[test]
+    fn test_exact_sums() {
+        let s = Fraction::sum_exact(vec![frac(1, 6), frac(1, 10), frac(1, 15)]).unwrap();
+        assert_eq!((s.q, s.d), (1, 3));
+        let s = Fraction::sum_exact(vec![frac(1, -2), frac(-1, -4)]).unwrap();
+        assert_eq!((s.q, s.d), (-1, 4));
+        let s = Fraction::sum_exact(Vec::new()).unwrap();
+        assert_eq!((s.q, s.d), (0, 1));
+
+        // H(20) fits although the product of its denominators doesn't, H(30) itself doesn't,
+        // and terms that cancel out never overflow
+        let h20 = Fraction::sum_exact((1..=20).map(|k| frac(1, k))).unwrap();
+        assert_eq!((h20.q, h20.d), (55835135, 15519504));
+        let h: Vec<Fraction> = (1..=30).map(|k| frac(1, k)).collect();
+        assert_eq!(Fraction::sum_exact(h.clone()), Err(Error::Overflow));
+        let big = frac(i32::MAX, 7);
+        let s = Fraction::sum_exact(vec![big, big, -big, -big, frac(1, 7)]).unwrap();
+        assert_eq!((s.q, s.d), (1, 7));
+        let s = Fraction::sum_exact(h.iter().copied().chain(h.iter().map(|&f| -f))).unwrap();
+        assert_eq!(s.q, 0);
+
+        let m = Fraction::mean_exact(vec![frac(1, 2), frac(1, 3)]).unwrap();
+        assert_eq!((m.q, m.d), (5, 12));
+        let m = Fraction::mean_exact(vec![frac(i32::MAX, 1), frac(i32::MAX, 1)]).unwrap();
+        assert_eq!((m.q, m.d), (i32::MAX, 1));
+        assert_eq!(Fraction::mean_exact(Vec::new()), Err(Error::DivisionByZero));
+        assert_eq!(
+            Fraction::sum_exact(vec![frac(1, 0)]),
+            Err(Error::DivisionByZero)
+        );
+    }
+
+    #[test]
+    fn test_sqrt_approx() {
+        assert_eq!(sqrt_approx(frac(2, 1), frac(1, 1000)), frac(41, 29));
+        assert_eq!(sqrt_approx(frac(18, 8), frac(1, 10_000)).d, 2);
+        assert_eq!(sqrt_approx(frac(0, 5), frac(1, 10)), frac(0, 1));
+        assert_eq!(sqrt_approx(frac(1, 10_000), frac(1, 50)), frac(0, 1));
+        assert_eq!(sqrt_approx(frac(-9, -1), frac(1, 2)), frac(3, 1));
+
+        // Within the tolerance, and no smaller denominator is
+        for (v, t) in [(2, 1_000_000), (3, 77), (1_000_000_007, 1000), (5, 1 << 30)] {
+            let r = try_sqrt_approx(frac(v, 1), frac(1, t)).unwrap();
+            let root = (v as f64).sqrt();
+            assert!(
+                (r.to_f64() - root).abs() <= 1.0 / t as f64 + 1e-12,
+                "{} {}",
+                v,
+                t
+            );
+            let better = (1..r.d.min(10_000)).any(|d| {
+                let p = (root * d as f64).round();
+                (p / d as f64 - root).abs() < 1.0 / t as f64 - 1e-12
+            });
+            assert!(!better, "{} {}", v, t);
+        }
+        let r = sqrt_approx(frac(1, 3), frac(1, 100_000));
+        assert!((r.to_f64() - (1.0f64 / 3.0).sqrt()).abs() <= 1e-5);
+
+        assert_eq!(try_sqrt_approx(frac(-1, 2), frac(1, 2)), Err(Error::Domain));
+        assert_eq!(try_sqrt_approx(frac(2, 1), frac(0, 2)), Err(Error::Domain));
+        assert_eq!(
+            try_sqrt_approx(frac(2, 0), frac(1, 2)),
+            Err(Error::DivisionByZero)
+        );
+        assert!(try_sqrt_approx(frac(2, 1), frac(1, i32::MAX)).is_ok());
+        assert_eq!(
+            try_sqrt_approx(frac(i32::MAX, 1), frac(1, i32::MAX)),
+            Err(Error::Overflow)
+        );
+    }
+
+    #[test]
+    fn test_try_fractions() {
+        let a = frac(1, 2);
+        let b = frac(7, 3);
+        let big = frac(i32::MAX, 2);
+
+        assert_eq!(try_frac(1, 0), Err(Error::DivisionByZero));
+        assert_eq!(a.try_add(a), Ok(a + a));
+        assert_eq!(a.try_sub(b), Ok(a - b));
+        assert_eq!(a.try_mul(b), Ok(a * b));
+        assert_eq!(a.try_div(b), Ok(a / b));
+        assert_eq!(a.try_div(frac(0, 5)), Err(Error::DivisionByZero));
+        assert_eq!(big.try_add(a), Err(Error::Overflow));
+        assert_eq!(big.try_mul(big), Err(Error::Overflow));
+        assert_eq!(frac(0, 0).try_reduce(), Err(Error::DivisionByZero));
+        assert_eq!(frac(6, -4).try_reduce(), Ok(frac(-3, 2)));
+
+        assert_eq!(try_gcd(713, 552), Ok(23));
+        assert_eq!(try_gcd(i32::MIN, 0), Err(Error::Overflow));
+        assert_eq!(try_gcd(i8::MIN, i8::MIN), Err(Error::Overflow));
+        assert_eq!(try_gcd(i32::MIN, -1), Ok(1));
+        assert_eq!(try_extended_gcd(713, 552), Ok((23, 7, -9)));
+        assert_eq!(try_extended_gcd(100_000, 7), Ok(extended_gcd(100_000, 7)));
+        assert_eq!(try_extended_gcd(i32::MIN, 0), Err(Error::Overflow));
+        assert_eq!(try_extended_gcd(-1, i32::MIN), Err(Error::Overflow));
+        assert_eq!(try_extended_gcd(i32::MIN, 1), Ok((1, 0, 1)));
+        assert_eq!(try_extended_gcd(i32::MIN, i32::MIN), Err(Error::Overflow));
+        assert_eq!(
+            try_extended_gcd(i32::MIN, i32::MIN / 2),
+            Ok((i32::MIN / 2, 0, 1))
+        );
     }
 }
@@ -0,0 +1,1579 @@
+use super::{frac, Fraction};
+use crate::{Error, Result};
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::convert::TryFrom;
+use core::fmt;
+use core::iter::{Product, Sum};
+use core::ops::{
+    Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Shl, Shr, Sub, SubAssign,
+};
+use core::str::FromStr;
+
+// -----------------------------------------------------------------------------
+// Magnitudes
+// -----------------------------------------------------------------------------
+
+// Magnitudes are little endian base 2^32 limbs without trailing zeros, so zero is empty.
+
+/// Operand length in limbs from which multiplication switches to Karatsuba.
+const KARATSUBA_THRESHOLD: usize = 32;
+
+fn trim(mut a: Vec<u32>) -> Vec<u32> {
+    while a.last() == Some(&0) {
+        a.pop();
+    }
+    a
+}
+
+fn cmp_mag(a: &[u32], b: &[u32]) -> Ordering {
+    a.len()
+        .cmp(&b.len())
+        .then_with(|| a.iter().rev().cmp(b.iter().rev()))
+}
+
+fn add_mag(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let (long, short) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+    let mut r = Vec::with_capacity(long.len() + 1);
+    let mut carry = 0u64;
+
+    for (i, &x) in long.iter().enumerate() {
+        let s = x as u64 + short.get(i).copied().unwrap_or(0) as u64 + carry;
+        r.push(s as u32);
+        carry = s >> 32;
+    }
+    if carry > 0 {
+        r.push(carry as u32);
+    }
+    r
+}
+
+/// a - b for a >= b.
+fn sub_mag(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut r = Vec::with_capacity(a.len());
+    let mut borrow = 0i64;
+
+    for (i, &x) in a.iter().enumerate() {
+        let d = x as i64 - b.get(i).copied().unwrap_or(0) as i64 - borrow;
+        r.push(d as u32);
+        borrow = (d < 0) as i64;
+    }
+    debug_assert_eq!(borrow, 0, "subtrahend is larger");
+    trim(r)
+}
+
+/// Adds `src` shifted by `shift` limbs onto `dst`, which has to be long enough.
+fn add_into(dst: &mut [u32], src: &[u32], shift: usize) {
+    let mut carry = 0u64;
+    for (i, &x) in src.iter().enumerate() {
+        let s = dst[i + shift] as u64 + x as u64 + carry;
+        dst[i + shift] = s as u32;
+        carry = s >> 32;
+    }
+
+    let mut i = shift + src.len();
+    while carry > 0 {
+        let s = dst[i] as u64 + carry;
+        dst[i] = s as u32;
+        carry = s >> 32;
+        i += 1;
+    }
+}
+
+fn mul_schoolbook(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut r = vec![0u32; a.len() + b.len()];
+    for (i, &x) in a.iter().enumerate() {
+        let mut carry = 0u64;
+        for (j, &y) in b.iter().enumerate() {
+            let t = x as u64 * y as u64 + r[i + j] as u64 + carry;
+            r[i + j] = t as u32;
+            carry = t >> 32;
+        }
+        r[i + b.len()] = carry as u32;
+    }
+    trim(r)
+}
+
+/// Karatsuba multiplication in O(n^1.585), splitting both operands at half the longer one:
+/// (a1 B + a0) (b1 B + b0) = a1 b1 B² + ((a0 + a1) (b0 + b1) - a0 b0 - a1 b1) B + a0 b0.
+fn mul_mag(a: &[u32], b: &[u32]) -> Vec<u32> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+    if a.len().min(b.len()) < KARATSUBA_THRESHOLD {
+        return mul_schoolbook(a, b);
+    }
+
+    let m = a.len().max(b.len()) / 2;
+    let split = |x: &[u32]| {
+        let (lo, hi) = x.split_at(m.min(x.len()));
+        (trim(lo.to_vec()), hi.to_vec())
+    };
+    let ((a0, a1), (b0, b1)) = (split(a), split(b));
+
+    let z0 = mul_mag(&a0, &b0);
+    let z2 = mul_mag(&a1, &b1);
+    let z1 = mul_mag(&add_mag(&a0, &a1), &add_mag(&b0, &b1));
+    let z1 = sub_mag(&sub_mag(&z1, &z0), &z2);
+
+    let mut r = vec![0u32; a.len() + b.len() + 1];
+    add_into(&mut r, &z0, 0);
+    add_into(&mut r, &z1, m);
+    add_into(&mut r, &z2, 2 * m);
+    trim(r)
+}
+
+/// a * m + c for single limbs.
+fn mul_small_add(a: &[u32], m: u32, c: u32) -> Vec<u32> {
+    let mut r = Vec::with_capacity(a.len() + 1);
+    let mut carry = c as u64;
+    for &x in a {
+        let t = x as u64 * m as u64 + carry;
+        r.push(t as u32);
+        carry = t >> 32;
+    }
+    if carry > 0 {
+        r.push(carry as u32);
+    }
+    trim(r)
+}
+
+/// Quotient and remainder by a single nonzero limb.
+fn divrem_small(a: &[u32], d: u32) -> (Vec<u32>, u32) {
+    let mut q = vec![0u32; a.len()];
+    let mut rem = 0u64;
+    for i in (0..a.len()).rev() {
+        let cur = rem << 32 | a[i] as u64;
+        q[i] = (cur / d as u64) as u32;
+        rem = cur % d as u64;
+    }
+    (trim(q), rem as u32)
+}
+
+fn shl_mag(a: &[u32], bits: usize) -> Vec<u32> {
+    if a.is_empty() {
+        return Vec::new();
+    }
+
+    let (limbs, bits) = (bits / 32, bits % 32);
+    let mut r = vec![0u32; limbs];
+    if bits == 0 {
+        r.extend_from_slice(a);
+        return r;
+    }
+
+    let mut carry = 0u32;
+    for &x in a {
+        r.push(x << bits | carry);
+        carry = x >> (32 - bits);
+    }
+    r.push(carry);
+    trim(r)
+}
+
+fn shr_mag(a: &[u32], bits: usize) -> Vec<u32> {
+    let (limbs, bits) = (bits / 32, bits % 32);
+    if limbs >= a.len() {
+        return Vec::new();
+    }
+
+    let a = &a[limbs..];
+    if bits == 0 {
+        return a.to_vec();
+    }
+    let r = (0..a.len())
+        .map(|i| a[i] >> bits | a.get(i + 1).map_or(0, |&h| h << (32 - bits)))
+        .collect();
+    trim(r)
+}
+
+/// Quotient and remainder of magnitudes by Knuth's algorithm D, for a nonzero divisor.
+fn divrem_mag(u: &[u32], v: &[u32]) -> (Vec<u32>, Vec<u32>) {
+    if cmp_mag(u, v) == Ordering::Less {
+        return (Vec::new(), u.to_vec());
+    }
+    if v.len() == 1 {
+        let (q, r) = divrem_small(u, v[0]);
+        return (q, trim(vec![r]));
+    }
+
+    // Normalize so the top limb of the divisor has its high bit set, making the quotient
+    // estimates off by at most 2
+    let s = v[v.len() - 1].leading_zeros() as usize;
+    let vn = shl_mag(v, s);
+    let mut un = shl_mag(u, s);
+    un.resize(u.len() + 1, 0);
+
+    let n = vn.len();
+    let m = u.len() - n;
+    let mut q = vec![0u32; m + 1];
+    let base = 1u64 << 32;
+
+    for j in (0..=m).rev() {
+        let num = (un[j + n] as u64) << 32 | un[j + n - 1] as u64;
+        let mut qhat = num / vn[n - 1] as u64;
+        let mut rhat = num % vn[n - 1] as u64;
+        while qhat >= base || qhat * vn[n - 2] as u64 > (rhat << 32 | un[j + n - 2] as u64) {
+            qhat -= 1;
+            rhat += vn[n - 1] as u64;
+            if rhat >= base {
+                break;
+            }
+        }
+
+        // Subtract qhat times the divisor
+        let mut k = 0i64;
+        for i in 0..n {
+            let p = qhat * vn[i] as u64;
+            let t = un[i + j] as i64 - k - (p & 0xFFFF_FFFF) as i64;
+            un[i + j] = t as u32;
+            k = (p >> 32) as i64 - (t >> 32);
+        }
+        let t = un[j + n] as i64 - k;
+        un[j + n] = t as u32;
+
+        // Overshot by one, add a divisor back
+        q[j] = qhat as u32;
+        if t < 0 {
+            q[j] -= 1;
+            let mut carry = 0u64;
+            for i in 0..n {
+                let s = un[i + j] as u64 + vn[i] as u64 + carry;
+                un[i + j] = s as u32;
+                carry = s >> 32;
+            }
+            un[j + n] = un[j + n].wrapping_add(carry as u32);
+        }
+    }
+
+    un.truncate(n);
+    (trim(q), shr_mag(&trim(un), s))
+}
+
+// -----------------------------------------------------------------------------
+// BigInt
+// -----------------------------------------------------------------------------
+
+/// Arbitrary precision signed integer. Behaves like the primitive signed integers without ever
+/// overflowing: division truncates towards zero, the remainder takes the sign of the dividend
+/// and `>>` rounds towards negative infinity. Products of large values use Karatsuba
+/// multiplication.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::math::bigint::BigInt;
+/// let a: BigInt = "123456789012345678901234567890".parse().unwrap();
+/// let b = BigInt::from(987_654_321);
+/// let c = &a * &b; // 121932631124828532112482853211126352690
+/// let d = BigInt::from(2).pow(100); // 1267650600228229401496703205376
+/// let e = (&a / &b, &a % &b); // (124999998873437499901, 574845669)
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct BigInt {
+    negative: bool,
+    mag: Vec<u32>,
+}
+
+impl BigInt {
+    fn from_parts(negative: bool, mag: Vec<u32>) -> Self {
+        let mag = trim(mag);
+        BigInt {
+            negative: negative && !mag.is_empty(),
+            mag,
+        }
+    }
+
+    pub fn zero() -> Self {
+        BigInt::default()
+    }
+
+    pub fn one() -> Self {
+        BigInt::from(1)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.mag.is_empty()
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    /// -1, 0 or 1 by the sign.
+    pub fn signum(&self) -> i32 {
+        match (self.negative, self.is_zero()) {
+            (true, _) => -1,
+            (false, true) => 0,
+            (false, false) => 1,
+        }
+    }
+
+    pub fn abs(&self) -> BigInt {
+        BigInt::from_parts(false, self.mag.clone())
+    }
+
+    /// Number of bits of the magnitude, 0 for zero.
+    pub fn bits(&self) -> u64 {
+        match self.mag.last() {
+            Some(&top) => self.mag.len() as u64 * 32 - top.leading_zeros() as u64,
+            None => 0,
+        }
+    }
+
+    /// Raises to the power `exp` by repeated squaring. 0^0 is 1.
+    pub fn pow(&self, mut exp: u32) -> BigInt {
+        let mut result = BigInt::one();
+        let mut base = self.clone();
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = &result * &base;
+            }
+            exp >>= 1;
+            if exp > 0 {
+                base = &base * &base;
+            }
+        }
+        result
+    }
+
+    /// self^exp mod m in [0, m) by sliding window exponentiation, reducing with [`Montgomery`]
+    /// for an odd modulus and with [`Barrett`] for an even one. Panics for a negative exponent
+    /// or a modulus that isn't positive, use [`BigInt::try_mod_pow`] to handle it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ctl::math::bigint::BigInt;
+    /// let p = &BigInt::from(2).pow(127) - &BigInt::one();
+    /// let x = BigInt::from(3).mod_pow(&(&p - &BigInt::one()), &p); // 1 as p is prime
+    /// let y = BigInt::from(-2).mod_pow(&BigInt::from(5), &BigInt::from(10)); // 8
+    /// ```
+    pub fn mod_pow(&self, exp: &BigInt, m: &BigInt) -> BigInt {
+        self.try_mod_pow(exp, m)
+            .expect("negative exponent or modulus not positive")
+    }
+
+    /// Fallible version of [`BigInt::mod_pow`]. Fails with [`Error::DivisionByZero`] for a
+    /// zero modulus and with [`Error::Domain`] for a negative exponent or modulus.
+    pub fn try_mod_pow(&self, exp: &BigInt, m: &BigInt) -> Result<BigInt> {
+        if m.is_zero() {
+            return Err(Error::DivisionByZero);
+        }
+        if m.negative || exp.negative {
+            return Err(Error::Domain);
+        }
+
+        Ok(match Montgomery::try_new(m) {
+            Ok(mont) => mont.pow(self, exp),
+            Err(_) => Barrett::new(m).pow(self, exp),
+        })
+    }
+
+    /// Quotient truncated towards zero and remainder with the sign of the dividend, like `/`
+    /// and `%` together. Panics on a zero divisor, use [`BigInt::try_div_rem`] to handle it.
+    pub fn div_rem(&self, o: &BigInt) -> (BigInt, BigInt) {
+        self.try_div_rem(o).expect("division by zero")
+    }
+
+    /// Fallible version of [`BigInt::div_rem`]. Fails with [`Error::DivisionByZero`].
+    pub fn try_div_rem(&self, o: &BigInt) -> Result<(BigInt, BigInt)> {
+        if o.is_zero() {
+            return Err(Error::DivisionByZero);
+        }
+
+        let (q, r) = divrem_mag(&self.mag, &o.mag);
+        Ok((
+            BigInt::from_parts(self.negative != o.negative, q),
+            BigInt::from_parts(self.negative, r),
+        ))
+    }
+
+    /// Greatest common divisor by the Euclidean algorithm, always non-negative. gcd(0, 0) is 0.
+    pub fn gcd(&self, o: &BigInt) -> BigInt {
+        let (mut a, mut b) = (self.abs(), o.abs());
+        while !b.is_zero() {
+            let r = &a % &b;
+            a = b;
+            b = r;
+        }
+        a
+    }
+
+    /// Value as an `i64`, `None` if it doesn't fit.
+    pub fn to_i64(&self) -> Option<i64> {
+        let m = self.to_u64_mag()?;
+        if self.negative {
+            0i64.checked_sub_unsigned(m)
+        } else {
+            i64::try_from(m).ok()
+        }
+    }
+
+    /// Value as a `u64`, `None` if it is negative or too large.
+    pub fn to_u64(&self) -> Option<u64> {
+        if self.negative {
+            return None;
+        }
+        self.to_u64_mag()
+    }
+
+    fn to_u64_mag(&self) -> Option<u64> {
+        match self.mag.len() {
+            0 => Some(0),
+            1 => Some(self.mag[0] as u64),
+            2 => Some((self.mag[1] as u64) << 32 | self.mag[0] as u64),
+            _ => None,
+        }
+    }
+
+    /// Closest `f64`, which may be off by one unit in the last place for values above 2^53.
+    /// Infinite beyond the range of `f64`.
+    pub fn to_f64(&self) -> f64 {
+        let m = self
+            .mag
+            .iter()
+            .rev()
+            .fold(0.0, |acc, &x| acc * 4_294_967_296.0 + x as f64);
+        if self.negative {
+            -m
+        } else {
+            m
+        }
+    }
+
+    /// Parses digits in a radix from 2 to 36 with an optional leading `-` or `+`. Letters may be
+    /// in either case. Fails with [`Error::ParseError`] for an empty or invalid string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ctl::math::bigint::BigInt;
+    /// let x = BigInt::from_str_radix("-ff", 16); // Ok(-255)
+    /// ```
+    pub fn from_str_radix(s: &str, radix: u32) -> Result<BigInt> {
+        assert!((2..=36).contains(&radix), "radix must lie in 2..=36");
+
+        let (negative, digits) = match s.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, s.strip_prefix('+').unwrap_or(s)),
+        };
+        if digits.is_empty() {
+            return Err(Error::ParseError);
+        }
+
+        let mut mag = Vec::new();
+        for c in digits.chars() {
+            let d = c.to_digit(radix).ok_or(Error::ParseError)?;
+            mag = mul_small_add(&mag, radix, d);
+        }
+        Ok(BigInt::from_parts(negative, mag))
+    }
+
+    /// Writes the value in a radix from 2 to 36 with lower case letters and a leading `-` if
+    /// negative.
+    pub fn to_str_radix(&self, radix: u32) -> String {
+        assert!((2..=36).contains(&radix), "radix must lie in 2..=36");
+        if self.is_zero() {
+            return String::from("0");
+        }
+
+        // Split off chunks of as many digits as fit into a limb
+        let (mut chunk, mut width) = (radix, 1);
+        while let Some(c) = chunk.checked_mul(radix) {
+            chunk = c;
+            width += 1;
+        }
+
+        let mut digits = Vec::new();
+        let mut mag = self.mag.clone();
+        while !mag.is_empty() {
+            let (q, mut r) = divrem_small(&mag, chunk);
+            mag = q;
+            for _ in 0..width {
+                digits.push(core::char::from_digit(r % radix, radix).unwrap());
+                r /= radix;
+                if mag.is_empty() && r == 0 {
+                    break;
+                }
+            }
+        }
+
+        if self.negative {
+            digits.push('-');
+        }
+        digits.iter().rev().collect()
+    }
+}
+
+macro_rules! gen_from {
+    ($($T:ty),*) => {
+        $(
+            impl From<$T> for BigInt {
+                fn from(x: $T) -> Self {
+                    let mut m = x.unsigned_abs() as u128;
+                    let mut mag = Vec::new();
+                    while m > 0 {
+                        mag.push(m as u32);
+                        m >>= 32;
+                    }
+                    #[allow(unused_comparisons)]
+                    BigInt::from_parts(x < 0, mag)
+                }
+            }
+        )*
+    };
+}
+
+gen_from!(i8, i16, i32, i64, i128, isize);
+
+macro_rules! gen_from_unsigned {
+    ($($T:ty),*) => {
+        $(
+            impl From<$T> for BigInt {
+                fn from(x: $T) -> Self {
+                    let mut m = x as u128;
+                    let mut mag = Vec::new();
+                    while m > 0 {
+                        mag.push(m as u32);
+                        m >>= 32;
+                    }
+                    BigInt::from_parts(false, mag)
+                }
+            }
+        )*
+    };
+}
+
+gen_from_unsigned!(u8, u16, u32, u64, u128, usize);
+
+impl FromStr for BigInt {
+    type Err = Error;
+
+    /// Parses a decimal integer, see [`BigInt::from_str_radix`].
+    fn from_str(s: &str) -> Result<Self> {
+        BigInt::from_str_radix(s, 10)
+    }
+}
+
+impl fmt::Display for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = self.to_str_radix(10);
+        let (negative, digits) = match s.strip_prefix('-') {
+            Some(d) => (true, d),
+            None => (false, s.as_str()),
+        };
+        f.pad_integral(!negative, "", digits)
+    }
+}
+
+impl Ord for BigInt {
+    fn cmp(&self, o: &Self) -> Ordering {
+        match (self.negative, o.negative) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => cmp_mag(&self.mag, &o.mag),
+            (true, true) => cmp_mag(&o.mag, &self.mag),
+        }
+    }
+}
+
+impl PartialOrd for BigInt {
+    fn partial_cmp(&self, o: &Self) -> Option<Ordering> {
+        Some(self.cmp(o))
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Operators
+// -----------------------------------------------------------------------------
+
+fn add_signed(a_neg: bool, a: &[u32], b_neg: bool, b: &[u32]) -> BigInt {
+    if a_neg == b_neg {
+        return BigInt::from_parts(a_neg, add_mag(a, b));
+    }
+    match cmp_mag(a, b) {
+        Ordering::Less => BigInt::from_parts(b_neg, sub_mag(b, a)),
+        _ => BigInt::from_parts(a_neg, sub_mag(a, b)),
+    }
+}
+
+impl Add for &BigInt {
+    type Output = BigInt;
+
+    fn add(self, o: &BigInt) -> BigInt {
+        add_signed(self.negative, &self.mag, o.negative, &o.mag)
+    }
+}
+
+impl Sub for &BigInt {
+    type Output = BigInt;
+
+    fn sub(self, o: &BigInt) -> BigInt {
+        add_signed(self.negative, &self.mag, !o.negative, &o.mag)
+    }
+}
+
+impl Mul for &BigInt {
+    type Output = BigInt;
+
+    fn mul(self, o: &BigInt) -> BigInt {
+        BigInt::from_parts(self.negative != o.negative, mul_mag(&self.mag, &o.mag))
+    }
+}
+
+impl Div for &BigInt {
+    type Output = BigInt;
+
+    /// Truncating division. Panics on a zero divisor.
+    fn div(self, o: &BigInt) -> BigInt {
+        self.div_rem(o).0
+    }
+}
+
+impl Rem for &BigInt {
+    type Output = BigInt;
+
+    /// Remainder with the sign of the dividend. Panics on a zero divisor.
+    fn rem(self, o: &BigInt) -> BigInt {
+        self.div_rem(o).1
+    }
+}
+
+impl Neg for &BigInt {
+    type Output = BigInt;
+
+    fn neg(self) -> BigInt {
+        BigInt::from_parts(!self.negative, self.mag.clone())
+    }
+}
+
+impl Neg for BigInt {
+    type Output = BigInt;
+
+    fn neg(self) -> BigInt {
+        BigInt::from_parts(!self.negative, self.mag)
+    }
+}
+
+impl Shl<u32> for &BigInt {
+    type Output = BigInt;
+
+    fn shl(self, bits: u32) -> BigInt {
+        BigInt::from_parts(self.negative, shl_mag(&self.mag, bits as usize))
+    }
+}
+
+impl Shr<u32> for &BigInt {
+    type Output = BigInt;
+
+    /// Shift rounding towards negative infinity, like for the primitive signed integers.
+    fn shr(self, bits: u32) -> BigInt {
+        let mag = shr_mag(&self.mag, bits as usize);
+        let lost = cmp_mag(&shl_mag(&mag, bits as usize), &self.mag) != Ordering::Equal;
+        if self.negative && lost {
+            BigInt::from_parts(true, add_mag(&mag, &[1]))
+        } else {
+            BigInt::from_parts(self.negative, mag)
+        }
+    }
+}
+
+// Owned and mixed operands forward to the reference versions
+macro_rules! gen_ops {
+    ($($Op:ident $op:ident $OpAssign:ident $op_assign:ident),*) => {
+        $(
+            impl $Op for BigInt {
+                type Output = BigInt;
+
+                fn $op(self, o: BigInt) -> BigInt {
+                    (&self).$op(&o)
+                }
+            }
+
+            impl $Op<&BigInt> for BigInt {
+                type Output = BigInt;
+
+                fn $op(self, o: &BigInt) -> BigInt {
+                    (&self).$op(o)
+                }
+            }
+
+            impl $Op<BigInt> for &BigInt {
+                type Output = BigInt;
+
+                fn $op(self, o: BigInt) -> BigInt {
+                    self.$op(&o)
+                }
+            }
+
+            impl $OpAssign for BigInt {
+                fn $op_assign(&mut self, o: BigInt) {
+                    *self = (&*self).$op(&o);
+                }
+            }
+
+            impl $OpAssign<&BigInt> for BigInt {
+                fn $op_assign(&mut self, o: &BigInt) {
+                    *self = (&*self).$op(o);
+                }
+            }
+        )*
+    };
+}
+
+gen_ops!(
+    Add add AddAssign add_assign,
+    Sub sub SubAssign sub_assign,
+    Mul mul MulAssign mul_assign,
+    Div div DivAssign div_assign,
+    Rem rem RemAssign rem_assign
+);
+
+impl Shl<u32> for BigInt {
+    type Output = BigInt;
+
+    fn shl(self, bits: u32) -> BigInt {
+        &self << bits
+    }
+}
+
+impl Shr<u32> for BigInt {
+    type Output = BigInt;
+
+    fn shr(self, bits: u32) -> BigInt {
+        &self >> bits
+    }
+}
+
+impl Sum for BigInt {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(BigInt::zero(), |a, b| a + b)
+    }
+}
+
+impl Product for BigInt {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(BigInt::one(), |a, b| a * b)
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Modular exponentiation
+// -----------------------------------------------------------------------------
+
+fn bit(a: &[u32], i: usize) -> bool {
+    a.get(i / 32).is_some_and(|&x| x >> (i % 32) & 1 == 1)
+}
+
+/// Residue of a in [0, m) for a nonzero m.
+fn residue(a: &BigInt, m: &[u32]) -> Vec<u32> {
+    let r = divrem_mag(&a.mag, m).1;
+    if a.negative && !r.is_empty() {
+        sub_mag(m, &r)
+    } else {
+        r
+    }
+}
+
+/// base^exp by left to right sliding windows: one squaring per exponent bit and one
+/// multiplication per window of up to w bits starting and ending with a 1, looked up in a table
+/// of the odd powers. That saves most of the multiplications of plain square and multiply,
+/// which does one per set bit. `one` and `base` are in whatever form `mul` works on.
+fn window_pow(
+    one: Vec<u32>,
+    base: &[u32],
+    exp: &[u32],
+    mul: impl Fn(&[u32], &[u32]) -> Vec<u32>,
+) -> Vec<u32> {
+    let bits = BigInt::from_parts(false, exp.to_vec()).bits() as usize;
+    let w = match bits {
+        672.. => 6,
+        240..=671 => 5,
+        80..=239 => 4,
+        24..=79 => 3,
+        _ => 1,
+    };
+
+    // base, base^3, ..., base^(2^w - 1)
+    let mut table = vec![base.to_vec()];
+    if w > 1 {
+        let square = mul(base, base);
+        for i in 1..1 << (w - 1) {
+            let next = mul(&table[i - 1], &square);
+            table.push(next);
+        }
+    }
+
+    let mut result: Option<Vec<u32>> = None;
+    let mut i = bits;
+    while i > 0 {
+        if !bit(exp, i - 1) {
+            result = result.map(|r| mul(&r, &r));
+            i -= 1;
+            continue;
+        }
+
+        // Longest window of bits j..i ending with a 1
+        let mut j = i.saturating_sub(w);
+        while !bit(exp, j) {
+            j += 1;
+        }
+        let value = (j..i).rev().fold(0, |v, k| v << 1 | bit(exp, k) as usize);
+        let power = &table[value >> 1];
+        result = Some(match result {
+            Some(mut r) => {
+                for _ in j..i {
+                    r = mul(&r, &r);
+                }
+                mul(&r, power)
+            }
+            None => power.clone(),
+        });
+        i = j;
+    }
+
+    result.unwrap_or(one)
+}
+
+/// Montgomery reduction for an odd modulus m > 1 of n limbs with R = 2^(32 n). Products of
+/// values in the Montgomery form a R mod m are reduced by multiplications and shifts instead of
+/// a division, which pays off for many multiplications by the same modulus, see
+/// [`BigInt::mod_pow`].
+///
+/// # Examples
+///
+/// ```
+/// use ctl::math::bigint::{BigInt, Montgomery};
+/// let m = Montgomery::new(&BigInt::from(1_000_000_007));
+/// let x = m.pow(&BigInt::from(3), &BigInt::from(200)); // 136318165
+/// let (a, b) = (m.to_montgomery(&BigInt::from(6)), m.to_montgomery(&BigInt::from(-7)));
+/// let y = m.from_montgomery(&m.mul(&a, &b)); // 999999965
+/// ```
+#[derive(Debug, Clone)]
+pub struct Montgomery {
+    m: Vec<u32>,
+    /// -1 / m mod 2^32
+    m_neg_inv: u32,
+    /// R² mod m
+    r2: Vec<u32>,
+}
+
+impl Montgomery {
+    /// Precomputes the constants of the modulus. Panics unless it is odd and above 1, use
+    /// [`Montgomery::try_new`] to handle it.
+    pub fn new(m: &BigInt) -> Self {
+        Montgomery::try_new(m).expect("modulus is not odd and above 1")
+    }
+
+    /// Fallible version of [`Montgomery::new`]. Fails with [`Error::Domain`] for an even
+    /// modulus or one below 2.
+    pub fn try_new(m: &BigInt) -> Result<Self> {
+        if m.negative || m.mag.first().is_none_or(|&x| x & 1 == 0) || m.mag == [1] {
+            return Err(Error::Domain);
+        }
+
+        // Newton's iteration doubles the correct low bits of the inverse each step
+        let mut inv = 1u32;
+        for _ in 0..5 {
+            inv = inv.wrapping_mul(2u32.wrapping_sub(m.mag[0].wrapping_mul(inv)));
+        }
+        let r2 = divrem_mag(&shl_mag(&[1], 64 * m.mag.len()), &m.mag).1;
+
+        Ok(Montgomery {
+            m: m.mag.clone(),
+            m_neg_inv: inv.wrapping_neg(),
+            r2,
+        })
+    }
+
+    pub fn modulus(&self) -> BigInt {
+        BigInt::from_parts(false, self.m.clone())
+    }
+
+    /// t / R mod m for t < m R, adding the multiple of m clearing the low limbs one at a time.
+    fn redc(&self, t: &[u32]) -> Vec<u32> {
+        let n = self.m.len();
+        let mut t = t.to_vec();
+        t.resize(2 * n + 1, 0);
+
+        for i in 0..n {
+            let u = t[i].wrapping_mul(self.m_neg_inv);
+            let mut carry = 0u64;
+            for (j, &x) in self.m.iter().enumerate() {
+                let s = t[i + j] as u64 + u as u64 * x as u64 + carry;
+                t[i + j] = s as u32;
+                carry = s >> 32;
+            }
+            let mut k = i + n;
+            while carry > 0 {
+                let s = t[k] as u64 + carry;
+                t[k] = s as u32;
+                carry = s >> 32;
+                k += 1;
+            }
+        }
+
+        let r = trim(t.split_off(n));
+        if cmp_mag(&r, &self.m) == Ordering::Less {
+            r
+        } else {
+            sub_mag(&r, &self.m)
+        }
+    }
+
+    fn mul_mag(&self, a: &[u32], b: &[u32]) -> Vec<u32> {
+        self.redc(&mul_mag(a, b))
+    }
+
+    /// Montgomery form a R mod m of any a, negative ones included.
+    pub fn to_montgomery(&self, a: &BigInt) -> BigInt {
+        BigInt::from_parts(false, self.mul_mag(&residue(a, &self.m), &self.r2))
+    }
+
+    /// a / R mod m, back out of the Montgomery form.
+    pub fn from_montgomery(&self, a: &BigInt) -> BigInt {
+        BigInt::from_parts(false, self.redc(&a.mag))
+    }
+
+    /// Product of 2 values in Montgomery form, a b / R mod m, itself in Montgomery form. Both
+    /// have to lie in [0, m).
+    pub fn mul(&self, a: &BigInt, b: &BigInt) -> BigInt {
+        BigInt::from_parts(false, self.mul_mag(&a.mag, &b.mag))
+    }
+
+    /// base^exp mod m in [0, m) of plain values by sliding window exponentiation, staying in
+    /// the Montgomery form in between. Panics for a negative exponent.
+    pub fn pow(&self, base: &BigInt, exp: &BigInt) -> BigInt {
+        assert!(!exp.negative, "negative exponent");
+        let one = self.to_montgomery(&BigInt::one());
+        let base = self.to_montgomery(base);
+        let r = window_pow(one.mag, &base.mag, &exp.mag, |a, b| self.mul_mag(a, b));
+        self.from_montgomery(&BigInt::from_parts(false, r))
+    }
+}
+
+/// Barrett reduction for a modulus m > 0 of k limbs, replacing the division by m by 2
+/// multiplications with the precomputed μ = ⌊2^(64 k) / m⌋. Works for any modulus, unlike
+/// [`Montgomery`] which is a little faster but needs an odd one.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::math::bigint::{Barrett, BigInt};
+/// let m = Barrett::new(&BigInt::from(2).pow(89));
+/// let x = m.reduce(&BigInt::from(3).pow(100)); // 3^100 mod 2^89
+/// let y = m.pow(&BigInt::from(3), &BigInt::from(100)); // the same
+/// ```
+#[derive(Debug, Clone)]
+pub struct Barrett {
+    m: Vec<u32>,
+    mu: Vec<u32>,
+}
+
+impl Barrett {
+    /// Precomputes μ for the modulus. Panics unless it is positive, use [`Barrett::try_new`]
+    /// to handle it.
+    pub fn new(m: &BigInt) -> Self {
+        Barrett::try_new(m).expect("modulus is not positive")
+    }
+
+    /// Fallible version of [`Barrett::new`]. Fails with [`Error::DivisionByZero`] for 0 and
+    /// with [`Error::Domain`] for a negative modulus.
+    pub fn try_new(m: &BigInt) -> Result<Self> {
+        if m.is_zero() {
+            return Err(Error::DivisionByZero);
+        }
+        if m.negative {
+            return Err(Error::Domain);
+        }
+
+        let mu = divrem_mag(&shl_mag(&[1], 64 * m.mag.len()), &m.mag).0;
+        Ok(Barrett {
+            m: m.mag.clone(),
+            mu,
+        })
+    }
+
+    pub fn modulus(&self) -> BigInt {
+        BigInt::from_parts(false, self.m.clone())
+    }
+
+    /// x mod m for x < 2^(64 k). The estimated quotient is at most 2 too small.
+    fn reduce_mag(&self, x: &[u32]) -> Vec<u32> {
+        let k = self.m.len();
+        if x.len() > 2 * k {
+            return divrem_mag(x, &self.m).1;
+        }
+
+        let q = mul_mag(&x[(k - 1).min(x.len())..], &self.mu);
+        let q = q.get(k + 1..).unwrap_or(&[]);
+        let mut r = sub_mag(x, &mul_mag(q, &self.m));
+        while cmp_mag(&r, &self.m) != Ordering::Less {
+            r = sub_mag(&r, &self.m);
+        }
+        r
+    }
+
+    /// Residue of x in [0, m). Values beyond m² and negative ones are reduced by a division
+    /// first.
+    pub fn reduce(&self, x: &BigInt) -> BigInt {
+        let r = if x.negative {
+            residue(x, &self.m)
+        } else {
+            self.reduce_mag(&x.mag)
+        };
+        BigInt::from_parts(false, r)
+    }
+
+    /// a b mod m of values in [0, m).
+    pub fn mul(&self, a: &BigInt, b: &BigInt) -> BigInt {
+        BigInt::from_parts(false, self.reduce_mag(&mul_mag(&a.mag, &b.mag)))
+    }
+
+    /// base^exp mod m in [0, m) by sliding window exponentiation. Panics for a negative
+    /// exponent.
+    pub fn pow(&self, base: &BigInt, exp: &BigInt) -> BigInt {
+        assert!(!exp.negative, "negative exponent");
+        let one = self.reduce(&BigInt::one());
+        let base = self.reduce(base);
+        let r = window_pow(one.mag, &base.mag, &exp.mag, |a, b| {
+            self.reduce_mag(&mul_mag(a, b))
+        });
+        BigInt::from_parts(false, r)
+    }
+}
+
+// -----------------------------------------------------------------------------
+// BigFraction
+// -----------------------------------------------------------------------------
+
+/// Fraction of [`BigInt`]s, the never overflowing counterpart of [`Fraction`]. The operators
+/// return reduced fractions with a positive denominator so the parts only grow as far as the
+/// value needs.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::math::bigint::{big_frac, BigFraction};
+/// // Σ 1/k for k up to 30
+/// let h: BigFraction = (1..=30).map(|k| big_frac(1, k)).sum();
+/// let s = h.to_string(); // "9304682830147/2329089562800"
+/// ```
+#[derive(Debug, Clone)]
+pub struct BigFraction {
+    pub q: BigInt,
+    pub d: BigInt,
+}
+
+/// Helper function to ease the creation of big fractions.
+///
+/// # Arguments
+///
+/// * `a` - Top of fraction
+/// * `b` - Bottom of fraction
+///
+/// # Examples
+///
+/// ```
+/// use ctl::math::bigint::big_frac;
+/// let a = big_frac(1, 2); // 1/2
+/// ```
+pub fn big_frac<A: Into<BigInt>, B: Into<BigInt>>(a: A, b: B) -> BigFraction {
+    BigFraction {
+        q: a.into(),
+        d: b.into(),
+    }
+}
+
+/// Fallible version of [`big_frac`] which rejects a zero denominator.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::math::bigint::try_big_frac;
+/// let b = try_big_frac(1, 0); // Err(Error::DivisionByZero)
+/// ```
+pub fn try_big_frac<A: Into<BigInt>, B: Into<BigInt>>(a: A, b: B) -> Result<BigFraction> {
+    let f = big_frac(a, b);
+    if f.d.is_zero() {
+        return Err(Error::DivisionByZero);
+    }
+
+    Ok(f)
+}
+
+/// x 2^e without leaving the range of `f64` in between.
+fn scale_pow2(mut x: f64, mut e: i64) -> f64 {
+    let step = |e: i64| f64::from_bits(((e + 1023) as u64) << 52);
+    while e > 1000 {
+        x *= step(1000);
+        e -= 1000;
+    }
+    while e < -1000 {
+        x *= step(-1000);
+        e += 1000;
+    }
+    x * step(e)
+}
+
+impl BigFraction {
+    /// Converts to the closest `f64`, up to an ulp, even when the parts themselves are out of
+    /// its range. A zero denominator gives an infinity or NaN like [`Fraction::to_f64`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ctl::math::bigint::{big_frac, BigInt};
+    /// let x = big_frac(BigInt::from(10).pow(400), BigInt::from(10).pow(399)).to_f64(); // 10
+    /// ```
+    pub fn to_f64(&self) -> f64 {
+        if self.d.is_zero() {
+            return self.q.to_f64() / self.d.to_f64();
+        }
+        if self.q.is_zero() {
+            return 0.0;
+        }
+
+        // Scale the quotient to 64 significant bits
+        let shift = 64 + self.d.bits() as i64 - self.q.bits() as i64;
+        let (q, d) = if shift >= 0 {
+            (&self.q << shift as u32, self.d.clone())
+        } else {
+            (self.q.clone(), &self.d << (-shift) as u32)
+        };
+        scale_pow2((&q / &d).to_f64(), -shift)
+    }
+
+    /// Outputs the reduced version of the fraction with a positive denominator. Panics for the
+    /// undefined fraction 0/0, use [`BigFraction::try_reduce`] to handle it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ctl::math::bigint::big_frac;
+    /// let r = big_frac(6, -4).reduce(); // -3/2
+    /// ```
+    pub fn reduce(&self) -> BigFraction {
+        self.try_reduce().expect("0/0 is undefined")
+    }
+
+    /// Fallible version of [`BigFraction::reduce`]. Fails with [`Error::DivisionByZero`] for
+    /// 0/0.
+    pub fn try_reduce(&self) -> Result<BigFraction> {
+        let g = self.q.gcd(&self.d);
+        if g.is_zero() {
+            return Err(Error::DivisionByZero);
+        }
+
+        let g = if self.d.is_negative() { -g } else { g };
+        Ok(big_frac(&self.q / &g, &self.d / &g))
+    }
+
+    /// Divides 2 fractions, failing with [`Error::DivisionByZero`] when `rhs` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ctl::math::bigint::big_frac;
+    /// let r = big_frac(1, 2).try_div(&big_frac(2, 3)); // Ok(3/4)
+    /// let e = big_frac(1, 2).try_div(&big_frac(0, 3)); // Err(Error::DivisionByZero)
+    /// ```
+    pub fn try_div(&self, rhs: &BigFraction) -> Result<BigFraction> {
+        if rhs.q.is_zero() {
+            return Err(Error::DivisionByZero);
+        }
+
+        Ok(big_frac(&self.q * &rhs.d, &self.d * &rhs.q).reduce())
+    }
+}
+
+impl From<BigInt> for BigFraction {
+    fn from(x: BigInt) -> Self {
+        big_frac(x, 1)
+    }
+}
+
+impl From<Fraction> for BigFraction {
+    fn from(f: Fraction) -> Self {
+        big_frac(f.q, f.d)
+    }
+}
+
+impl TryFrom<&BigFraction> for Fraction {
+    type Error = Error;
+
+    /// Reduces and converts back, failing with [`Error::Overflow`] if a part doesn't fit into an
+    /// `i32` and with [`Error::DivisionByZero`] for 0/0.
+    fn try_from(f: &BigFraction) -> Result<Fraction> {
+        let r = f.try_reduce()?;
+        let part = |x: &BigInt| {
+            x.to_i64()
+                .and_then(|v| i32::try_from(v).ok())
+                .ok_or(Error::Overflow)
+        };
+        Ok(frac(part(&r.q)?, part(&r.d)?))
+    }
+}
+
+impl TryFrom<BigFraction> for Fraction {
+    type Error = Error;
+
+    fn try_from(f: BigFraction) -> Result<Fraction> {
+        Fraction::try_from(&f)
+    }
+}
+
+impl fmt::Display for BigFraction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.q, self.d)
+    }
+}
+
+impl Neg for &BigFraction {
+    type Output = BigFraction;
+
+    fn neg(self) -> BigFraction {
+        big_frac(-&self.q, self.d.clone())
+    }
+}
+
+impl Neg for BigFraction {
+    type Output = BigFraction;
+
+    fn neg(self) -> BigFraction {
+        big_frac(-self.q, self.d)
+    }
+}
+
+impl Add for &BigFraction {
+    type Output = BigFraction;
+
+    fn add(self, rhs: &BigFraction) -> BigFraction {
+        big_frac(&self.q * &rhs.d + &rhs.q * &self.d, &self.d * &rhs.d).reduce()
+    }
+}
+
+impl Sub for &BigFraction {
+    type Output = BigFraction;
+
+    fn sub(self, rhs: &BigFraction) -> BigFraction {
+        big_frac(&self.q * &rhs.d - &rhs.q * &self.d, &self.d * &rhs.d).reduce()
+    }
+}
+
+impl Mul for &BigFraction {
+    type Output = BigFraction;
+
+    fn mul(self, rhs: &BigFraction) -> BigFraction {
+        big_frac(&self.q * &rhs.q, &self.d * &rhs.d).reduce()
+    }
+}
+
+impl Div for &BigFraction {
+    type Output = BigFraction;
+
+    /// Panics when `rhs` is zero, use [`BigFraction::try_div`] to handle it.
+    fn div(self, rhs: &BigFraction) -> BigFraction {
+        self.try_div(rhs).expect("division by zero")
+    }
+}
+
+macro_rules! gen_fraction_ops {
+    ($($Op:ident $op:ident $OpAssign:ident $op_assign:ident),*) => {
+        $(
+            impl $Op for BigFraction {
+                type Output = BigFraction;
+
+                fn $op(self, rhs: BigFraction) -> BigFraction {
+                    (&self).$op(&rhs)
+                }
+            }
+
+            impl $Op<&BigFraction> for BigFraction {
+                type Output = BigFraction;
+
+                fn $op(self, rhs: &BigFraction) -> BigFraction {
+                    (&self).$op(rhs)
+                }
+            }
+
+            impl $OpAssign<&BigFraction> for BigFraction {
+                fn $op_assign(&mut self, rhs: &BigFraction) {
+                    *self = (&*self).$op(rhs);
+                }
+            }
+
+            impl $OpAssign for BigFraction {
+                fn $op_assign(&mut self, rhs: BigFraction) {
+                    *self = (&*self).$op(&rhs);
+                }
+            }
+        )*
+    };
+}
+
+gen_fraction_ops!(
+    Add add AddAssign add_assign,
+    Sub sub SubAssign sub_assign,
+    Mul mul MulAssign mul_assign,
+    Div div DivAssign div_assign
+);
+
+impl Sum for BigFraction {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(big_frac(0, 1), |a, b| a + b)
+    }
+}
+
+impl Product for BigFraction {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(big_frac(1, 1), |a, b| a * b)
+    }
+}
+
+impl PartialEq for BigFraction {
+    fn eq(&self, other: &Self) -> bool {
+        &self.q * &other.d == &self.d * &other.q
+    }
+}
+
+impl PartialOrd for BigFraction {
+    /// Compares the values of the fractions. Fractions with a zero denominator are unordered.
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        if self.d.is_zero() || other.d.is_zero() {
+            return None;
+        }
+
+        // Cross multiplication flips the order for a negative denominator product
+        let l = &self.q * &other.d;
+        let r = &other.q * &self.d;
+
+        if self.d.is_negative() == other.d.is_negative() {
+            l.partial_cmp(&r)
+        } else {
+            r.partial_cmp(&l)
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::random::Rng;
+    use alloc::string::ToString;
+
+    fn random(rng: &mut Rng, limbs: usize) -> BigInt {
+        let mag = (0..limbs).map(|_| rng.next_u32()).collect();
+        BigInt::from_parts(rng.next_u32() & 1 == 1, mag)
+    }
+
+    #[test]
+    fn test_against_i128() {
+        let mut rng = Rng::new(21);
+        let values: Vec<i128> = (0..40)
+            .map(|i| match i % 4 {
+                0 => rng.next_u64() as i64 as i128,
+                1 => (rng.next_u32() as i32) as i128,
+                2 => (rng.next_u64() as i128) << 20 >> (rng.next_u64() % 40),
+                _ => i as i128 - 20,
+            })
+            .collect();
+
+        for &a in &values {
+            let x = BigInt::from(a);
+            assert_eq!(x.to_string(), a.to_string());
+            assert_eq!(
+                x.to_str_radix(16),
+                format!("{}{:x}", if a < 0 { "-" } else { "" }, a.unsigned_abs())
+            );
+            assert_eq!(-&x, BigInt::from(-a));
+            for s in [0, 1, 31, 32, 33, 70] {
+                assert_eq!(&x >> s, BigInt::from(a >> s.min(127)), "{} >> {}", a, s);
+            }
+            assert_eq!(&x << 5, BigInt::from(a * 32));
+
+            for &b in &values {
+                let y = BigInt::from(b);
+                assert_eq!(x.cmp(&y), a.cmp(&b));
+                assert_eq!(&x + &y, BigInt::from(a + b));
+                assert_eq!(&x - &y, BigInt::from(a - b));
+                if let Some(p) = a.checked_mul(b) {
+                    assert_eq!(&x * &y, BigInt::from(p));
+                }
+                if b != 0 {
+                    assert_eq!(x.div_rem(&y), (BigInt::from(a / b), BigInt::from(a % b)));
+                }
+            }
+        }
+
+        assert_eq!(
+            BigInt::from(5).try_div_rem(&BigInt::zero()),
+            Err(Error::DivisionByZero)
+        );
+        assert_eq!(BigInt::from(i64::MIN).to_i64(), Some(i64::MIN));
+        assert_eq!(BigInt::from(u64::MAX).to_i64(), None);
+        assert_eq!(BigInt::from(u64::MAX).to_u64(), Some(u64::MAX));
+        assert_eq!(BigInt::from(-1).to_u64(), None);
+        assert_eq!(BigInt::from(1u128 << 100).to_f64(), 2f64.powi(100));
+    }
+
+    #[test]
+    fn test_large() {
+        let mut rng = Rng::new(22);
+
+        // Division inverts multiplication, across the Karatsuba threshold
+        for &(la, lb) in &[(3, 2), (40, 35), (100, 64), (150, 3), (70, 70)] {
+            let a = random(&mut rng, la);
+            let b = random(&mut rng, lb);
+            let p = &a * &b;
+            assert_eq!(
+                BigInt::from_parts(p.negative, mul_schoolbook(&a.mag, &b.mag)),
+                p
+            );
+            let r = random(&mut rng, lb - 1).abs();
+            let r = if p.is_negative() { -r } else { r };
+            let n = &p + &r;
+            assert_eq!(n.div_rem(&b), (a.clone(), r));
+            assert_eq!(&(&a + &b) - &b, a);
+        }
+
+        // (2^64 - 1)² and 100!
+        let m = BigInt::from(u64::MAX);
+        assert_eq!(
+            (&m * &m).to_string(),
+            "340282366920938463426481119284349108225"
+        );
+        let f: BigInt = (1..=100).map(BigInt::from).product();
+        assert_eq!(f.to_string().len(), 158);
+        assert_eq!((&f % &BigInt::from(101)).to_i64(), Some(100)); // Wilson's theorem
+        assert_eq!(BigInt::from(3).pow(200).bits(), 317);
+        assert_eq!(BigInt::from(-2).pow(3), BigInt::from(-8));
+
+        // Parsing and formatting
+        let s = "-123456789012345678901234567890123456789";
+        let x: BigInt = s.parse().unwrap();
+        assert_eq!(x.to_string(), s);
+        assert_eq!(
+            BigInt::from_str_radix(&x.to_str_radix(36), 36),
+            Ok(x.clone())
+        );
+        assert_eq!(format!("{:>45}", x), format!("{:>45}", s));
+        assert_eq!("".parse::<BigInt>(), Err(Error::ParseError));
+        assert_eq!("-".parse::<BigInt>(), Err(Error::ParseError));
+        assert_eq!("12a".parse::<BigInt>(), Err(Error::ParseError));
+        assert_eq!("-0".parse::<BigInt>(), Ok(BigInt::zero()));
+        assert_eq!(BigInt::zero().to_string(), "0");
+    }
+
+    #[test]
+    fn test_mod_pow() {
+        let mut rng = Rng::new(23);
+        let modulo = |x: &BigInt, m: &BigInt| &(&(x % m) + m) % m;
+
+        // Plain square and multiply with a division per step as the reference
+        let naive = |b: &BigInt, e: &BigInt, m: &BigInt| {
+            let (mut r, mut b) = (&BigInt::one() % m, modulo(b, m));
+            for i in 0..e.bits() {
+                if bit(&e.mag, i as usize) {
+                    r = &(&r * &b) % m;
+                }
+                b = &(&b * &b) % m;
+            }
+            r
+        };
+
+        // Odd and even moduli from a limb up to past the Karatsuba threshold, and exponents
+        // across the window widths
+        for &(lm, le) in &[(1, 1), (2, 3), (3, 1), (4, 8), (8, 25), (40, 2)] {
+            for _ in 0..3 {
+                let m = random(&mut rng, lm).abs();
+                let parity = BigInt::from(m.mag.first().map_or(0, |&x| x & 1));
+                let odd = &(&m - &parity) + &BigInt::one();
+                let even = &m + &parity;
+                let (b, e) = (random(&mut rng, lm + 1), random(&mut rng, le).abs());
+                assert_eq!(b.mod_pow(&e, &odd), naive(&b, &e, &odd));
+                assert_eq!(b.mod_pow(&e, &even), naive(&b, &e, &even));
+            }
+        }
+
+        // Fermat's little theorem for the Mersenne prime 2^521 - 1
+        let p = &BigInt::from(2).pow(521) - &BigInt::one();
+        let pm1 = &p - &BigInt::one();
+        assert_eq!(random(&mut rng, 10).mod_pow(&pm1, &p), BigInt::one());
+
+        // Reductions on their own
+        let mont = Montgomery::new(&p);
+        let (a, b) = (random(&mut rng, 16), random(&mut rng, 12));
+        let (ma, mb) = (mont.to_montgomery(&a), mont.to_montgomery(&b));
+        let ab = mont.from_montgomery(&mont.mul(&ma, &mb));
+        assert_eq!(ab, modulo(&(&a * &b), &p));
+        let barrett = Barrett::new(&BigInt::from(2).pow(100));
+        assert_eq!(barrett.reduce(&a), modulo(&a, &barrett.modulus()));
+
+        // Edge cases
+        let (two, ten) = (BigInt::from(2), BigInt::from(10));
+        assert_eq!(two.mod_pow(&BigInt::zero(), &ten), BigInt::one());
+        assert_eq!(two.mod_pow(&ten, &BigInt::one()), BigInt::zero());
+        assert_eq!(BigInt::zero().mod_pow(&BigInt::zero(), &p), BigInt::one());
+        assert_eq!(
+            two.try_mod_pow(&ten, &BigInt::zero()),
+            Err(Error::DivisionByZero)
+        );
+        assert_eq!(two.try_mod_pow(&-&ten, &ten), Err(Error::Domain));
+        assert_eq!(two.try_mod_pow(&ten, &-&ten), Err(Error::Domain));
+        assert_eq!(Montgomery::try_new(&ten).err(), Some(Error::Domain));
+        assert_eq!(
+            Montgomery::try_new(&BigInt::one()).err(),
+            Some(Error::Domain)
+        );
+    }
+
+    #[test]
+    fn test_big_fraction() {
+        // Harmonic numbers, whose parts quickly leave i64
+        let h: BigFraction = (1..=100).map(|k| big_frac(1, k)).sum();
+        assert_eq!(h.d.to_string().len(), 40);
+        assert!((h.to_f64() - 5.187377517639621).abs() < 1e-14);
+        assert_eq!(h.q.gcd(&h.d), BigInt::one());
+
+        // Arithmetic stays reduced with a positive denominator
+        let a = big_frac(6, -4);
+        assert_eq!(a.reduce().to_string(), "-3/2");
+        let b = &a * &big_frac(-2, 3);
+        assert_eq!((b.q.to_i64(), b.d.to_i64()), (Some(1), Some(1)));
+        assert_eq!(&a - &a, big_frac(0, 7));
+        assert_eq!(&(&a / &big_frac(3, 1)) + &big_frac(1, 2), big_frac(0, 1));
+        assert!(big_frac(1, -3) < big_frac(-1, 4));
+        assert_eq!(big_frac(1, 0).partial_cmp(&big_frac(1, 2)), None);
+        assert_eq!(a.try_div(&big_frac(0, 5)), Err(Error::DivisionByZero));
+        assert_eq!(big_frac(0, 0).try_reduce(), Err(Error::DivisionByZero));
+        assert_eq!(try_big_frac(2, 0), Err(Error::DivisionByZero));
+
+        // Values whose parts overflow f64 convert fine
+        let big = BigInt::from(7).pow(500);
+        assert!((big_frac(&big * &BigInt::from(3), big.clone()).to_f64() - 3.0).abs() < 1e-15);
+        assert_eq!(big_frac(-3, 0).to_f64(), f64::NEG_INFINITY);
+        assert_eq!(
+            big_frac(big.clone(), BigInt::zero()).to_f64(),
+            f64::INFINITY
+        );
+        assert!(big_frac(0, 0).to_f64().is_nan());
+        let small = big_frac(BigInt::one(), BigInt::from(7).pow(300)).to_f64();
+        assert!((small / 7f64.powi(-300) - 1.0).abs() < 1e-14);
+
+        // Conversions to and from Fraction
+        assert_eq!(BigFraction::from(frac(2, 4)), big_frac(1, 2));
+        assert_eq!(
+            Fraction::try_from(big_frac(-10, 4)).map(|f| (f.q, f.d)),
+            Ok((-5, 2))
+        );
+        let huge = big_frac(BigInt::from(1u64 << 40), 3);
+        assert_eq!(Fraction::try_from(&huge).map(|f| f.q), Err(Error::Overflow));
+    }
+}
@@ -0,0 +1,165 @@
+use alloc::vec::Vec;
+use core::f64::consts::PI;
+
+// -----------------------------------------------------------------------------
+// Chebyshev approximation
+// -----------------------------------------------------------------------------
+
+/// Polynomial approximation c0 / 2 + Σ ck Tk(y) of a function on [a, b] in the Chebyshev
+/// polynomials Tk, with y mapping [a, b] onto [-1, 1]. Interpolating at the Chebyshev nodes
+/// comes close to the best polynomial of the degree, and for smooth functions the coefficients
+/// fall off quickly, so the last ones estimate the error.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::math::ChebyshevApprox;
+/// let c = ChebyshevApprox::fit(f64::exp, 0.0, 1.0, 12);
+/// let y = c.eval(0.5); // ~1.6487, error below 1e-14
+/// let e = c.error_estimate(); // ~1e-16
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChebyshevApprox {
+    a: f64,
+    b: f64,
+    coeffs: Vec<f64>,
+}
+
+impl ChebyshevApprox {
+    /// Fits by evaluating f once at each of the degree + 1 Chebyshev nodes of [a, b], in
+    /// O(degree²).
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - Function to approximate
+    /// * `a` - Start of the interval
+    /// * `b` - End of the interval, greater than a
+    /// * `degree` - Degree of the polynomial
+    pub fn fit<F: FnMut(f64) -> f64>(mut f: F, a: f64, b: f64, degree: usize) -> Self {
+        assert!(a < b, "interval is empty");
+
+        let n = degree + 1;
+        let (mid, half) = ((a + b) / 2.0, (b - a) / 2.0);
+        let samples: Vec<f64> = (0..n)
+            .map(|k| f(mid + half * (PI * (k as f64 + 0.5) / n as f64).cos()))
+            .collect();
+
+        let coeffs = (0..n)
+            .map(|j| {
+                let sum: f64 = samples
+                    .iter()
+                    .enumerate()
+                    .map(|(k, &s)| s * (PI * j as f64 * (k as f64 + 0.5) / n as f64).cos())
+                    .sum();
+                2.0 * sum / n as f64
+            })
+            .collect();
+
+        ChebyshevApprox { a, b, coeffs }
+    }
+
+    /// Chebyshev coefficients c0 to c_degree, c0 counting half.
+    pub fn coefficients(&self) -> &[f64] {
+        &self.coeffs
+    }
+
+    pub fn degree(&self) -> usize {
+        self.coeffs.len() - 1
+    }
+
+    /// Interval [a, b] of the fit.
+    pub fn interval(&self) -> (f64, f64) {
+        (self.a, self.b)
+    }
+
+    /// Evaluates the approximation by Clenshaw's recurrence in O(degree). Outside [a, b] it
+    /// extrapolates, which quickly becomes inaccurate.
+    pub fn eval(&self, x: f64) -> f64 {
+        let y = (2.0 * x - self.a - self.b) / (self.b - self.a);
+
+        // b_k = c_k + 2y b_(k + 1) - b_(k + 2), down to k = 1
+        let (mut b1, mut b2) = (0.0, 0.0);
+        for &c in self.coeffs[1..].iter().rev() {
+            let b0 = c + 2.0 * y * b1 - b2;
+            b2 = b1;
+            b1 = b0;
+        }
+
+        self.coeffs[0] / 2.0 + y * b1 - b2
+    }
+
+    /// Estimates the maximum error on [a, b] by the magnitude of the last 2 coefficients,
+    /// which bound what the next terms would add for a function with quickly falling
+    /// coefficients. 2 terms guard against odd or even functions with every other coefficient
+    /// 0.
+    pub fn error_estimate(&self) -> f64 {
+        self.coeffs.iter().rev().take(2).map(|c| c.abs()).sum()
+    }
+
+    /// Drops the trailing coefficients that together stay below `tol`, giving a cheaper
+    /// polynomial whose error grows by at most `tol`.
+    pub fn truncate(&mut self, tol: f64) {
+        let mut dropped = 0.0;
+        while self.coeffs.len() > 1 {
+            let c = self.coeffs[self.coeffs.len() - 1].abs();
+            if dropped + c > tol {
+                break;
+            }
+            dropped += c;
+            self.coeffs.pop();
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Largest error on a fine grid of [a, b].
+    fn max_error<F: Fn(f64) -> f64>(c: &ChebyshevApprox, f: F) -> f64 {
+        let (a, b) = c.interval();
+        (0..=1000)
+            .map(|i| a + (b - a) * i as f64 / 1000.0)
+            .map(|x| (c.eval(x) - f(x)).abs())
+            .fold(0.0, f64::max)
+    }
+
+    #[test]
+    fn test_chebyshev() {
+        // Polynomials up to the degree are reproduced exactly
+        let cubic = |x: f64| 2.0 * x * x * x - x + 3.0;
+        let c = ChebyshevApprox::fit(cubic, -2.0, 5.0, 3);
+        assert_eq!(c.degree(), 3);
+        assert!(max_error(&c, cubic) < 1e-11);
+
+        // T3 itself has the single coefficient c3 = 1
+        let t3 = ChebyshevApprox::fit(|x| 4.0 * x * x * x - 3.0 * x, -1.0, 1.0, 5);
+        for (i, &c) in t3.coefficients().iter().enumerate() {
+            assert!((c - (i == 3) as u8 as f64).abs() < 1e-12);
+        }
+
+        // Smooth functions converge quickly and the estimate tracks the error
+        for degree in [4, 8, 12] {
+            let c = ChebyshevApprox::fit(f64::exp, 0.0, 1.0, degree);
+            let err = max_error(&c, f64::exp);
+            assert!(
+                err <= 4.0 * c.error_estimate() + 1e-15,
+                "{} {}",
+                degree,
+                err
+            );
+        }
+        let c = ChebyshevApprox::fit(f64::sin, 0.0, PI, 20);
+        assert!(max_error(&c, f64::sin) < 1e-14);
+
+        // Truncation keeps the error within the tolerance
+        let mut c = ChebyshevApprox::fit(f64::cos, -3.0, 3.0, 30);
+        c.truncate(1e-8);
+        assert!(c.degree() < 20);
+        assert!(max_error(&c, f64::cos) < 1e-8);
+    }
+}
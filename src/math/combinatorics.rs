@@ -0,0 +1,570 @@
+use super::modular::ModInt;
+use crate::{Error, Result};
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use core::ops::{Add, Mul, Sub};
+
+// -----------------------------------------------------------------------------
+// Exact counts
+// -----------------------------------------------------------------------------
+
+/// Calculates n!. Panics if it doesn't fit into a `u64`, which happens from 21! on. Use
+/// [`try_factorial`] to handle it.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::math::combinatorics::factorial;
+/// let x = factorial(0); // 1
+/// let y = factorial(20); // 2432902008176640000
+/// ```
+pub fn factorial(n: u64) -> u64 {
+    try_factorial(n).expect("factorial does not fit into u64")
+}
+
+/// Fallible version of [`factorial`]. Fails with [`Error::Overflow`] instead of panicking.
+pub fn try_factorial(n: u64) -> Result<u64> {
+    (2..=n).try_fold(1u64, |acc, i| acc.checked_mul(i).ok_or(Error::Overflow))
+}
+
+/// Calculates the binomial coefficient n choose k, the number of k element subsets of n
+/// elements. It is 0 for k > n. Panics if the result doesn't fit into a `u64`; use
+/// [`try_binomial`] to handle it.
+///
+/// # Arguments
+///
+/// * `n` - Number of elements
+/// * `k` - Size of the subsets
+///
+/// # Examples
+///
+/// ```
+/// use ctl::math::combinatorics::binomial;
+/// let x = binomial(5, 2); // 10
+/// let y = binomial(67, 33); // 14226520737620288370
+/// let z = binomial(2, 3); // 0
+/// ```
+pub fn binomial(n: u64, k: u64) -> u64 {
+    try_binomial(n, k).expect("binomial coefficient does not fit into u64")
+}
+
+/// Fallible version of [`binomial`]. Fails with [`Error::Overflow`] only if the result itself
+/// doesn't fit, intermediate values are widened.
+pub fn try_binomial(n: u64, k: u64) -> Result<u64> {
+    if k > n {
+        return Ok(0);
+    }
+
+    // C(n, i + 1) = C(n, i) (n - i) / (i + 1) stays exact, and each C(n, i) is at most the
+    // result for k ≤ n / 2, so the product fits into a u128
+    let k = k.min(n - k);
+    (0..k).try_fold(1u64, |acc, i| {
+        let next = acc as u128 * (n - i) as u128 / (i + 1) as u128;
+        u64::try_from(next).map_err(|_| Error::Overflow)
+    })
+}
+
+/// Calculates the number of ordered selections of k out of n elements, n! / (n - k)!. It is 0
+/// for k > n. Panics if the result doesn't fit into a `u64`; use [`try_permutations`] to handle
+/// it.
+///
+/// # Arguments
+///
+/// * `n` - Number of elements
+/// * `k` - Number of elements selected in order
+///
+/// # Examples
+///
+/// ```
+/// use ctl::math::combinatorics::permutations;
+/// let x = permutations(5, 2); // 20
+/// let y = permutations(30, 0); // 1
+/// ```
+pub fn permutations(n: u64, k: u64) -> u64 {
+    try_permutations(n, k).expect("permutation count does not fit into u64")
+}
+
+/// Fallible version of [`permutations`]. Fails with [`Error::Overflow`] instead of panicking.
+pub fn try_permutations(n: u64, k: u64) -> Result<u64> {
+    if k > n {
+        return Ok(0);
+    }
+    (n - k + 1..=n).try_fold(1u64, |acc, i| acc.checked_mul(i).ok_or(Error::Overflow))
+}
+
+/// Calculates the multinomial coefficient (k1 + k2 + ...)! / (k1! k2! ...), the number of ways
+/// to split a set into groups of the given sizes. Panics if the result doesn't fit into a
+/// `u64`; use [`try_multinomial`] to handle it.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::math::combinatorics::multinomial;
+/// let x = multinomial(&[2, 1, 1]); // 12 = 4! / 2!
+/// let y = multinomial(&[]); // 1
+/// ```
+pub fn multinomial(ks: &[u64]) -> u64 {
+    try_multinomial(ks).expect("multinomial coefficient does not fit into u64")
+}
+
+/// Fallible version of [`multinomial`]. Fails with [`Error::Overflow`] instead of panicking.
+pub fn try_multinomial(ks: &[u64]) -> Result<u64> {
+    // Product of binomials choosing each group out of the elements so far
+    let mut total = 0u64;
+    ks.iter().try_fold(1u64, |acc, &k| {
+        total = total.checked_add(k).ok_or(Error::Overflow)?;
+        acc.checked_mul(try_binomial(total, k)?)
+            .ok_or(Error::Overflow)
+    })
+}
+
+// -----------------------------------------------------------------------------
+// Modular counts
+// -----------------------------------------------------------------------------
+
+/// Factorials and their inverses modulo the prime M up to a fixed n, answering binomials and
+/// the other counts in O(1) each. n has to stay below M, since larger factorials are 0.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::math::combinatorics::Factorials;
+/// let f = Factorials::<1_000_000_007>::new(100_000);
+/// let x = f.binomial(100_000, 50_000).value(); // 149033233
+/// let y = f.factorial(20).value(); // 146326063
+/// ```
+#[derive(Debug, Clone)]
+pub struct Factorials<const M: u64> {
+    fact: Vec<ModInt<M>>,
+    inv_fact: Vec<ModInt<M>>,
+}
+
+impl<const M: u64> Factorials<M> {
+    /// Precomputes the tables up to n! in O(n).
+    pub fn new(n: usize) -> Self {
+        assert!((n as u64) < M, "factorials up to the modulus are 0");
+
+        let mut fact = Vec::with_capacity(n + 1);
+        fact.push(ModInt::new(1));
+        for i in 1..=n {
+            fact.push(fact[i - 1] * ModInt::new(i as u64));
+        }
+
+        // One inversion, then 1 / (i - 1)! = i / i!
+        let mut inv_fact = vec![ModInt::new(0); n + 1];
+        inv_fact[n] = fact[n].inverse().expect("modulus is not prime");
+        for i in (1..=n).rev() {
+            inv_fact[i - 1] = inv_fact[i] * ModInt::new(i as u64);
+        }
+
+        Factorials { fact, inv_fact }
+    }
+
+    /// Largest n covered by the tables.
+    pub fn limit(&self) -> usize {
+        self.fact.len() - 1
+    }
+
+    /// n! mod M. Panics above the limit.
+    pub fn factorial(&self, n: usize) -> ModInt<M> {
+        self.fact[n]
+    }
+
+    /// 1 / n! mod M. Panics above the limit.
+    pub fn inverse_factorial(&self, n: usize) -> ModInt<M> {
+        self.inv_fact[n]
+    }
+
+    /// n choose k mod M, 0 for k > n. Panics if n is above the limit.
+    pub fn binomial(&self, n: usize, k: usize) -> ModInt<M> {
+        if k > n {
+            return ModInt::new(0);
+        }
+        self.fact[n] * self.inv_fact[k] * self.inv_fact[n - k]
+    }
+
+    /// n! / (n - k)! mod M, 0 for k > n. Panics if n is above the limit.
+    pub fn permutations(&self, n: usize, k: usize) -> ModInt<M> {
+        if k > n {
+            return ModInt::new(0);
+        }
+        self.fact[n] * self.inv_fact[n - k]
+    }
+
+    /// (k1 + k2 + ...)! / (k1! k2! ...) mod M. Panics if the sum is above the limit.
+    pub fn multinomial(&self, ks: &[usize]) -> ModInt<M> {
+        let total: usize = ks.iter().sum();
+        ks.iter()
+            .fold(self.fact[total], |acc, &k| acc * self.inv_fact[k])
+    }
+
+    /// n-th Catalan number (2n choose n) / (n + 1) mod M. Panics if 2n is above the limit.
+    pub fn catalan(&self, n: usize) -> ModInt<M> {
+        self.binomial(2 * n, n) * self.inv_fact[n + 1] * self.fact[n]
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Number tables
+// -----------------------------------------------------------------------------
+
+// The tables only add and multiply, so they work for exact `u128` counts as well as for
+// `ModInt`. Exact values overflow past the limits given for each table, which panics in debug
+// builds.
+
+/// Catalan numbers C(0) to C(n) by C(i + 1) = Σ C(j) C(i - j) in O(n²). C(i) counts the
+/// binary trees with i nodes and the balanced strings of i bracket pairs. Exact up to
+/// C(69) as `u128`.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::math::combinatorics::catalan_numbers;
+/// let c = catalan_numbers::<u128>(5); // [1, 1, 2, 5, 14, 42]
+/// ```
+pub fn catalan_numbers<T>(n: usize) -> Vec<T>
+where
+    T: Copy + From<u64> + Add<Output = T> + Mul<Output = T>,
+{
+    let mut c: Vec<T> = Vec::with_capacity(n + 1);
+    c.push(T::from(1));
+    for i in 0..n {
+        let next = (0..=i).fold(T::from(0), |acc, j| acc + c[j] * c[i - j]);
+        c.push(next);
+    }
+    c
+}
+
+/// Unsigned Stirling numbers of the first kind, `table[i][k]` counting the permutations of i
+/// elements with k cycles, for i up to n. Built from c(i + 1, k) = i c(i, k) + c(i, k - 1) in
+/// O(n²). Every entry is exact as `u128` up to n = 34.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::math::combinatorics::stirling_first;
+/// let s = stirling_first::<u128>(4);
+/// let x = s[4][2]; // 11
+/// ```
+pub fn stirling_first<T>(n: usize) -> Vec<Vec<T>>
+where
+    T: Copy + From<u64> + Add<Output = T> + Mul<Output = T>,
+{
+    stirling(n, |i, _| i as u64)
+}
+
+/// Stirling numbers of the second kind, `table[i][k]` counting the partitions of i elements
+/// into k nonempty blocks, for i up to n. Built from S(i + 1, k) = k S(i, k) + S(i, k - 1) in
+/// O(n²). Every entry is exact as `u128` up to n = 43.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::math::combinatorics::stirling_second;
+/// let s = stirling_second::<u128>(4);
+/// let x = s[4][2]; // 7
+/// ```
+pub fn stirling_second<T>(n: usize) -> Vec<Vec<T>>
+where
+    T: Copy + From<u64> + Add<Output = T> + Mul<Output = T>,
+{
+    stirling(n, |_, k| k as u64)
+}
+
+/// Triangle with t(i + 1, k) = w(i, k) t(i, k) + t(i, k - 1) and t(0, 0) = 1.
+fn stirling<T, W>(n: usize, weight: W) -> Vec<Vec<T>>
+where
+    T: Copy + From<u64> + Add<Output = T> + Mul<Output = T>,
+    W: Fn(usize, usize) -> u64,
+{
+    let mut table: Vec<Vec<T>> = Vec::with_capacity(n + 1);
+    table.push(vec![T::from(1)]);
+    for i in 0..n {
+        let prev = &table[i];
+        let row = (0..=i + 1)
+            .map(|k| {
+                let stay = if k <= i {
+                    T::from(weight(i, k)) * prev[k]
+                } else {
+                    T::from(0)
+                };
+                if k > 0 {
+                    stay + prev[k - 1]
+                } else {
+                    stay
+                }
+            })
+            .collect();
+        table.push(row);
+    }
+    table
+}
+
+/// Bell numbers B(0) to B(n), the partitions of a set of i elements into any number of
+/// blocks, by the Bell triangle in O(n²) additions. Exact up to B(42) as `u128`.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::math::combinatorics::bell_numbers;
+/// let b = bell_numbers::<u128>(5); // [1, 1, 2, 5, 15, 52]
+/// ```
+pub fn bell_numbers<T>(n: usize) -> Vec<T>
+where
+    T: Copy + From<u64> + Add<Output = T>,
+{
+    // Each row starts with the last entry of the previous one, B(i), and adds the entry
+    // above. The last row is left out, its entries reach past B(n).
+    let mut bell = Vec::with_capacity(n + 1);
+    let mut row = vec![T::from(1)];
+    bell.push(row[0]);
+    for i in 0..n {
+        let first = row[row.len() - 1];
+        bell.push(first);
+        if i + 1 == n {
+            break;
+        }
+
+        let mut next = Vec::with_capacity(row.len() + 1);
+        next.push(first);
+        for &x in &row {
+            next.push(next[next.len() - 1] + x);
+        }
+        row = next;
+    }
+    bell
+}
+
+// -----------------------------------------------------------------------------
+// Partitions
+// -----------------------------------------------------------------------------
+
+/// Counts the partitions p(n) of n into positive parts regardless of order, by Euler's
+/// pentagonal number recurrence p(n) = Σ (-1)^(k + 1) (p(n - k(3k - 1)/2) + p(n - k(3k + 1)/2))
+/// in O(n^1.5). Exact up to p(1416) as `u128`, where the positive terms alone still fit, or
+/// modular with [`ModInt`].
+///
+/// # Examples
+///
+/// ```
+/// use ctl::math::combinatorics::partitions;
+/// use ctl::math::modular::ModInt;
+/// let x = partitions::<u128>(100); // 190569292
+/// let y = partitions::<ModInt<1_000_000_007>>(1000).value(); // 709496666
+/// ```
+pub fn partitions<T>(n: usize) -> T
+where
+    T: Copy + From<u64> + Add<Output = T> + Sub<Output = T>,
+{
+    let mut p: Vec<T> = Vec::with_capacity(n + 1);
+    p.push(T::from(1));
+
+    for i in 1..=n {
+        // Positive and negative terms apart, so unsigned sums never dip below 0
+        let (mut plus, mut minus) = (T::from(0), T::from(0));
+        for k in 1.. {
+            let small = k * (3 * k - 1) / 2;
+            if small > i {
+                break;
+            }
+            let mut term = p[i - small];
+            let large = small + k;
+            if large <= i {
+                term = term + p[i - large];
+            }
+
+            if k % 2 == 1 {
+                plus = plus + term;
+            } else {
+                minus = minus + term;
+            }
+        }
+        p.push(plus - minus);
+    }
+
+    p[n]
+}
+
+/// Iterator over the partitions of n as parts in descending order, from `[n]` down to
+/// `[1, 1, ..., 1]` in reverse lexicographic order. The only partition of 0 is empty. There
+/// are [`partitions`] of n many, which grows quickly, so this is meant for small n.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::math::combinatorics::Partitions;
+/// let all: Vec<_> = Partitions::new(4).collect();
+/// // [[4], [3, 1], [2, 2], [2, 1, 1], [1, 1, 1, 1]]
+/// ```
+#[derive(Debug, Clone)]
+pub struct Partitions {
+    /// Partition to be returned next, `None` when done
+    next: Option<Vec<usize>>,
+}
+
+impl Partitions {
+    pub fn new(n: usize) -> Self {
+        let first = if n == 0 { Vec::new() } else { vec![n] };
+        Partitions { next: Some(first) }
+    }
+}
+
+impl Iterator for Partitions {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Vec<usize>> {
+        let current = self.next.take()?;
+
+        // Lower the last part above 1 and spread it and the trailing ones as parts of at most
+        // its new size
+        let mut succ = current.clone();
+        let mut rest = 0;
+        while succ.last() == Some(&1) {
+            succ.pop();
+            rest += 1;
+        }
+        if let Some(last) = succ.last_mut() {
+            *last -= 1;
+            let part = *last;
+            rest += 1;
+            while rest > 0 {
+                succ.push(part.min(rest));
+                rest -= part.min(rest);
+            }
+            self.next = Some(succ);
+        }
+
+        Some(current)
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exact() {
+        assert_eq!(factorial(0), 1);
+        assert_eq!(factorial(20), 2_432_902_008_176_640_000);
+        assert_eq!(try_factorial(21), Err(Error::Overflow));
+
+        // Pascal's triangle
+        for n in 1..60 {
+            for k in 1..n {
+                assert_eq!(binomial(n, k), binomial(n - 1, k - 1) + binomial(n - 1, k));
+            }
+        }
+        assert_eq!(binomial(67, 33), 14_226_520_737_620_288_370);
+        assert_eq!(try_binomial(68, 34), Err(Error::Overflow));
+        assert_eq!(binomial(u64::MAX, 1), u64::MAX);
+        assert_eq!(binomial(1 << 32, 2), (1 << 31) * ((1 << 32) - 1));
+        assert_eq!(try_binomial(u64::MAX, u64::MAX - 2), Err(Error::Overflow));
+        assert_eq!(binomial(3, 4), 0);
+
+        assert_eq!(permutations(10, 3), 720);
+        assert_eq!(permutations(20, 20), factorial(20));
+        assert_eq!(permutations(3, 4), 0);
+        assert_eq!(try_permutations(21, 21), Err(Error::Overflow));
+
+        assert_eq!(multinomial(&[3, 2, 2]), 210);
+        assert_eq!(multinomial(&[5]), 1);
+        assert_eq!(multinomial(&[0, 0]), 1);
+        assert_eq!(try_multinomial(&[u64::MAX, 1]), Err(Error::Overflow));
+    }
+
+    #[test]
+    fn test_modular() {
+        type Mint = ModInt<1_000_000_007>;
+        let f = Factorials::<1_000_000_007>::new(1000);
+        assert_eq!(f.limit(), 1000);
+
+        for n in 0..=60 {
+            assert_eq!(f.factorial(n) * f.inverse_factorial(n), Mint::new(1));
+            for k in 0..=n + 1 {
+                let exact = binomial(n as u64, k as u64) % 1_000_000_007;
+                assert_eq!(f.binomial(n, k), Mint::new(exact));
+            }
+        }
+        assert_eq!(f.permutations(20, 7), Mint::new(permutations(20, 7)));
+        assert_eq!(f.multinomial(&[3, 2, 2]), Mint::new(210));
+
+        // Small prime, everything up to p - 1
+        let small = Factorials::<13>::new(12);
+        assert_eq!(small.factorial(12), ModInt::new(12)); // Wilson's theorem
+        assert_eq!(small.binomial(12, 5).value(), binomial(12, 5) % 13);
+    }
+
+    #[test]
+    fn test_number_tables() {
+        type Mint = ModInt<1_000_000_007>;
+
+        let c = catalan_numbers::<u128>(69);
+        assert_eq!(c[10], 16796);
+        let f = Factorials::<1_000_000_007>::new(200);
+        for (n, &x) in c.iter().enumerate() {
+            let exact = f.binomial(2 * n, n) / Mint::new(n as u64 + 1);
+            assert_eq!(f.catalan(n), exact);
+            assert_eq!(Mint::new((x % 1_000_000_007) as u64), exact);
+        }
+        assert_eq!(c[33], binomial(66, 33) as u128 / 34);
+
+        // Row sums: n! for the first kind, the Bell numbers for the second
+        let s1 = stirling_first::<u128>(20);
+        let s2 = stirling_second::<u128>(43);
+        let bell = bell_numbers::<u128>(42);
+        assert_eq!(
+            (s1[10][3], s2[10][4], bell[10]),
+            (1_172_700, 34_105, 115_975)
+        );
+        for (n, row) in s1.iter().enumerate() {
+            assert_eq!(row.iter().sum::<u128>(), factorial(n as u64) as u128);
+        }
+        for (row, &b) in s2.iter().zip(&bell) {
+            assert_eq!(row.iter().sum::<u128>(), b);
+        }
+
+        // Modular tables agree with the exact ones
+        let m1 = stirling_first::<Mint>(20);
+        let mb = bell_numbers::<Mint>(42);
+        assert_eq!(m1[20][5], Mint::new((s1[20][5] % 1_000_000_007) as u64));
+        assert_eq!(mb[42], Mint::new((bell[42] % 1_000_000_007) as u64));
+    }
+
+    #[test]
+    fn test_partitions() {
+        assert_eq!(partitions::<u128>(0), 1);
+        assert_eq!(partitions::<u128>(5), 7);
+        assert_eq!(partitions::<u128>(100), 190_569_292);
+        assert_eq!(
+            partitions::<ModInt<1_000_000_007>>(1000),
+            ModInt::new(709_496_666)
+        );
+
+        let four: Vec<_> = Partitions::new(4).collect();
+        assert_eq!(
+            four,
+            [&[4][..], &[3, 1], &[2, 2], &[2, 1, 1], &[1, 1, 1, 1]]
+        );
+        assert_eq!(
+            Partitions::new(0).collect::<Vec<_>>(),
+            [Vec::<usize>::new()]
+        );
+
+        // Every partition sums to n, descends and comes strictly after the previous one
+        for n in 1..=25 {
+            let all: Vec<_> = Partitions::new(n).collect();
+            assert_eq!(all.len() as u128, partitions::<u128>(n));
+            for w in all.windows(2) {
+                assert!(w[0] > w[1]);
+            }
+            for part in &all {
+                assert_eq!(part.iter().sum::<usize>(), n);
+                assert!(part.windows(2).all(|w| w[0] >= w[1]));
+            }
+        }
+    }
+}
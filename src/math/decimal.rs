@@ -0,0 +1,394 @@
+use crate::{Error, Result};
+use alloc::string::ToString;
+use core::cmp::Ordering;
+use core::convert::TryFrom;
+use core::fmt;
+use core::ops::{Add, Div, Mul, Neg, Sub};
+use core::str::FromStr;
+
+// -----------------------------------------------------------------------------
+// Helpers
+// -----------------------------------------------------------------------------
+
+/// 10^n, `None` beyond 10^38.
+fn pow10(n: u32) -> Option<i128> {
+    10i128.checked_pow(n)
+}
+
+/// n / d rounded to the nearest integer, ties to the even one.
+fn div_half_even(n: i128, d: i128) -> Result<i128> {
+    if d == 0 {
+        return Err(Error::DivisionByZero);
+    }
+
+    let q = n.checked_div(d).ok_or(Error::Overflow)?;
+    let r = (n % d).unsigned_abs();
+    let rest = d.unsigned_abs() - r;
+    let up = match r.cmp(&rest) {
+        Ordering::Greater => true,
+        Ordering::Equal => q % 2 != 0,
+        Ordering::Less => false,
+    };
+
+    if !up {
+        return Ok(q);
+    }
+    let step = if (n < 0) == (d < 0) { 1 } else { -1 };
+    q.checked_add(step).ok_or(Error::Overflow)
+}
+
+// -----------------------------------------------------------------------------
+// Decimal
+// -----------------------------------------------------------------------------
+
+/// Fixed-point decimal number mantissa / 10^scale with an `i128` mantissa, so values with up
+/// to 38 significant digits are represented exactly, unlike with `f64`. Addition and
+/// subtraction are exact. Multiplication and division round to the larger scale of the
+/// operands with banker's rounding, ties going to the even digit, which doesn't drift up over
+/// many roundings. Values compare equal independent of their scale.
+///
+/// The operators panic on overflow, the `try_` methods fail with [`Error::Overflow`] instead.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::math::Decimal;
+/// let price: Decimal = "19.99".parse().unwrap();
+/// let total = price * Decimal::from(3); // 59.97
+/// let share = total / Decimal::from(7); // 8.57
+/// let tax = Decimal::new(2_125, 3).round(2); // 2.12, the tie going to the even digit
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Decimal {
+    mantissa: i128,
+    scale: u32,
+}
+
+impl Decimal {
+    /// mantissa / 10^scale, like `Decimal::new(1999, 2)` for 19.99. Panics for a scale above
+    /// 38, whose unit wouldn't fit.
+    pub fn new(mantissa: i128, scale: u32) -> Self {
+        assert!(scale <= 38, "scale is above 38");
+        Decimal { mantissa, scale }
+    }
+
+    pub fn mantissa(self) -> i128 {
+        self.mantissa
+    }
+
+    /// Number of digits after the decimal point.
+    pub fn scale(self) -> u32 {
+        self.scale
+    }
+
+    pub fn is_zero(self) -> bool {
+        self.mantissa == 0
+    }
+
+    pub fn is_negative(self) -> bool {
+        self.mantissa < 0
+    }
+
+    pub fn abs(self) -> Decimal {
+        Decimal::new(self.mantissa.abs(), self.scale)
+    }
+
+    /// Value at another scale, rounded with banker's rounding when dropping digits. Panics on
+    /// overflow.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ctl::math::Decimal;
+    /// let a = Decimal::new(1_005, 3).round(2); // 1.00
+    /// let b = Decimal::new(1_015, 3).round(2); // 1.02
+    /// let c = Decimal::new(15, 1).round(3); // 1.500
+    /// ```
+    pub fn round(self, scale: u32) -> Decimal {
+        self.try_round(scale)
+            .expect("decimal does not fit into i128")
+    }
+
+    /// Fallible version of [`Decimal::round`].
+    pub fn try_round(self, scale: u32) -> Result<Decimal> {
+        assert!(scale <= 38, "scale is above 38");
+
+        let mantissa = if scale >= self.scale {
+            let f = pow10(scale - self.scale).ok_or(Error::Overflow)?;
+            self.mantissa.checked_mul(f).ok_or(Error::Overflow)?
+        } else {
+            let f = pow10(self.scale - scale).ok_or(Error::Overflow)?;
+            div_half_even(self.mantissa, f)?
+        };
+        Ok(Decimal::new(mantissa, scale))
+    }
+
+    /// Mantissas of both at the larger scale.
+    fn align(self, o: Decimal) -> Result<(i128, i128, u32)> {
+        let scale = self.scale.max(o.scale);
+        Ok((
+            self.try_round(scale)?.mantissa,
+            o.try_round(scale)?.mantissa,
+            scale,
+        ))
+    }
+
+    /// Exact sum, failing with [`Error::Overflow`] instead of panicking.
+    pub fn try_add(self, o: Decimal) -> Result<Decimal> {
+        let (a, b, scale) = self.align(o)?;
+        Ok(Decimal::new(
+            a.checked_add(b).ok_or(Error::Overflow)?,
+            scale,
+        ))
+    }
+
+    /// Exact difference, failing with [`Error::Overflow`] instead of panicking.
+    pub fn try_sub(self, o: Decimal) -> Result<Decimal> {
+        let (a, b, scale) = self.align(o)?;
+        Ok(Decimal::new(
+            a.checked_sub(b).ok_or(Error::Overflow)?,
+            scale,
+        ))
+    }
+
+    /// Product rounded to the larger scale, failing with [`Error::Overflow`] if the exact
+    /// product doesn't fit.
+    pub fn try_mul(self, o: Decimal) -> Result<Decimal> {
+        let p = self
+            .mantissa
+            .checked_mul(o.mantissa)
+            .ok_or(Error::Overflow)?;
+        Decimal::new(p, self.scale + o.scale).try_round(self.scale.max(o.scale))
+    }
+
+    /// Quotient rounded to the larger scale. Fails with [`Error::DivisionByZero`] and with
+    /// [`Error::Overflow`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ctl::math::Decimal;
+    /// let a = Decimal::new(100, 2).try_div(Decimal::new(3, 0)); // Ok(0.33)
+    /// let b = Decimal::new(1, 0).try_div(Decimal::new(0, 2)); // Err(Error::DivisionByZero)
+    /// ```
+    pub fn try_div(self, o: Decimal) -> Result<Decimal> {
+        if o.is_zero() {
+            return Err(Error::DivisionByZero);
+        }
+
+        // a / 10^sa / (b / 10^sb) at scale s is a 10^(s + sb - sa) / b
+        let scale = self.scale.max(o.scale);
+        let f = pow10(scale + o.scale - self.scale).ok_or(Error::Overflow)?;
+        let n = self.mantissa.checked_mul(f).ok_or(Error::Overflow)?;
+        Ok(Decimal::new(div_half_even(n, o.mantissa)?, scale))
+    }
+
+    /// Closest `f64`, inexact for most decimals.
+    pub fn to_f64(self) -> f64 {
+        self.mantissa as f64 / pow10(self.scale).unwrap() as f64
+    }
+}
+
+impl From<i64> for Decimal {
+    fn from(x: i64) -> Self {
+        Decimal::new(x as i128, 0)
+    }
+}
+
+impl FromStr for Decimal {
+    type Err = Error;
+
+    /// Parses an optionally signed decimal like `-12.340`, keeping the written digits as the
+    /// scale. Fails with [`Error::ParseError`] for anything else and with [`Error::Overflow`]
+    /// beyond 38 digits.
+    fn from_str(s: &str) -> Result<Self> {
+        let (negative, rest) = match s.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, s.strip_prefix('+').unwrap_or(s)),
+        };
+        let (int, frac) = rest.split_once('.').unwrap_or((rest, ""));
+        if int.is_empty() && frac.is_empty() {
+            return Err(Error::ParseError);
+        }
+
+        let mut mantissa = 0i128;
+        for c in int.chars().chain(frac.chars()) {
+            let d = c.to_digit(10).ok_or(Error::ParseError)?;
+            mantissa = mantissa
+                .checked_mul(10)
+                .and_then(|m| m.checked_add(d as i128))
+                .ok_or(Error::Overflow)?;
+        }
+
+        let scale = u32::try_from(frac.len()).map_err(|_| Error::Overflow)?;
+        if scale > 38 {
+            return Err(Error::Overflow);
+        }
+        Ok(Decimal::new(
+            if negative { -mantissa } else { mantissa },
+            scale,
+        ))
+    }
+}
+
+impl fmt::Display for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let digits = self.mantissa.unsigned_abs().to_string();
+        let scale = self.scale as usize;
+        let digits = format!("{:0>width$}", digits, width = scale + 1);
+        let (int, frac) = digits.split_at(digits.len() - scale);
+
+        let body = if scale == 0 {
+            int.to_string()
+        } else {
+            format!("{}.{}", int, frac)
+        };
+        f.pad_integral(self.mantissa >= 0, "", &body)
+    }
+}
+
+impl PartialEq for Decimal {
+    fn eq(&self, o: &Self) -> bool {
+        self.cmp(o) == Ordering::Equal
+    }
+}
+
+impl Eq for Decimal {}
+
+impl Ord for Decimal {
+    fn cmp(&self, o: &Self) -> Ordering {
+        match self.align(*o) {
+            Ok((a, b, _)) => a.cmp(&b),
+            // Only the one scaled up can overflow, and it then dwarfs the other
+            Err(_) if self.scale < o.scale => self.mantissa.cmp(&0),
+            Err(_) => 0.cmp(&o.mantissa),
+        }
+    }
+}
+
+impl PartialOrd for Decimal {
+    fn partial_cmp(&self, o: &Self) -> Option<Ordering> {
+        Some(self.cmp(o))
+    }
+}
+
+impl Neg for Decimal {
+    type Output = Decimal;
+
+    fn neg(self) -> Decimal {
+        Decimal::new(-self.mantissa, self.scale)
+    }
+}
+
+impl Add for Decimal {
+    type Output = Decimal;
+
+    fn add(self, o: Decimal) -> Decimal {
+        self.try_add(o).expect("decimal does not fit into i128")
+    }
+}
+
+impl Sub for Decimal {
+    type Output = Decimal;
+
+    fn sub(self, o: Decimal) -> Decimal {
+        self.try_sub(o).expect("decimal does not fit into i128")
+    }
+}
+
+impl Mul for Decimal {
+    type Output = Decimal;
+
+    fn mul(self, o: Decimal) -> Decimal {
+        self.try_mul(o).expect("decimal does not fit into i128")
+    }
+}
+
+impl Div for Decimal {
+    type Output = Decimal;
+
+    /// Panics on a zero divisor or overflow, see [`Decimal::try_div`].
+    fn div(self, o: Decimal) -> Decimal {
+        self.try_div(o).expect("division by zero or overflow")
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn d(s: &str) -> Decimal {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_decimal_arithmetic() {
+        // Exact where f64 isn't
+        let sum = (0..10).fold(Decimal::default(), |acc, _| acc + d("0.1"));
+        assert_eq!(sum, Decimal::from(1));
+        assert_eq!((d("1.25") + d("-3.5")).to_string(), "-2.25");
+        assert_eq!((d("1") - d("0.001")).to_string(), "0.999");
+
+        // Banker's rounding on products and quotients
+        assert_eq!((d("0.5") * d("0.5")).to_string(), "0.2");
+        assert_eq!((d("1.5") * d("0.5")).to_string(), "0.8");
+        assert_eq!((d("19.99") * Decimal::from(3)).to_string(), "59.97");
+        assert_eq!((d("2.00") / Decimal::from(3)).to_string(), "0.67");
+        assert_eq!((d("-2.00") / Decimal::from(3)).to_string(), "-0.67");
+        assert_eq!((d("1.0") / d("-8.0")).to_string(), "-0.1");
+        assert_eq!((d("3.0") / d("8.0")).to_string(), "0.4");
+        assert_eq!((d("0.25") / d("2")).to_string(), "0.12");
+        for (m, r) in [
+            (5, 0),
+            (15, 2),
+            (25, 2),
+            (-5, 0),
+            (-15, -2),
+            (26, 3),
+            (-24, -2),
+        ] {
+            assert_eq!(Decimal::new(m, 1).round(0).mantissa(), r, "{}", m);
+        }
+
+        assert_eq!(d("1").try_div(d("0.0")), Err(Error::DivisionByZero));
+        let max = Decimal::new(i128::MAX, 0);
+        assert_eq!(max.try_add(Decimal::from(1)), Err(Error::Overflow));
+        assert_eq!(max.try_mul(d("1.0")), Err(Error::Overflow));
+        assert_eq!(max.try_round(1), Err(Error::Overflow));
+        assert_eq!(
+            Decimal::new(i128::MIN, 0).try_div(d("-1")),
+            Err(Error::Overflow)
+        );
+    }
+
+    #[test]
+    fn test_decimal_parse_and_order() {
+        for s in ["0", "-12.340", "0.05", "123456789.987654321", "-0.5"] {
+            assert_eq!(d(s).to_string(), s);
+        }
+        assert_eq!(d("-12.340").scale(), 3);
+        assert_eq!(d(".5").to_string(), "0.5");
+        assert_eq!(d("7.").to_string(), "7");
+        assert_eq!(d("+3").mantissa(), 3);
+        assert_eq!(format!("{:>7}", d("-1.5")), "   -1.5");
+        assert!(d("-0.0").to_string() == "0.0");
+        for s in ["", ".", "-", "1.2.3", "1e5", "--1"] {
+            assert_eq!(s.parse::<Decimal>(), Err(Error::ParseError), "{}", s);
+        }
+        assert_eq!("1".repeat(40).parse::<Decimal>(), Err(Error::Overflow));
+
+        // Scale doesn't matter for comparisons
+        assert_eq!(d("1.50"), d("1.5"));
+        assert!(d("-1.51") < d("-1.5"));
+        assert!(d("0.001") > Decimal::default());
+        let big = Decimal::new(i128::MAX / 2, 0);
+        let small = Decimal::new(1, 30);
+        assert!(big > small);
+        assert!(-big < small);
+        assert!((d("2.5").to_f64() - 2.5).abs() < 1e-15);
+    }
+}
@@ -0,0 +1,342 @@
+use core::ops::*;
+
+// -----------------------------------------------------------------------------
+// Dual
+// -----------------------------------------------------------------------------
+
+/// Dual number `re + du * e` with `e * e = 0`. Evaluating a function on `Dual::variable(x)`
+/// yields its value at `x` in `re` and the exact first derivative in `du` (forward mode
+/// automatic differentiation).
+///
+/// # Examples
+///
+/// ```
+/// use ctl::math::Dual;
+/// let x = Dual::variable(3.0);
+/// let y = x * x + x * 2.0; // y.re == 15.0, y.du == 8.0
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Dual<T> {
+    /// Real part, the function value
+    pub re: T,
+    /// Dual part, the derivative
+    pub du: T,
+}
+
+impl<T> Dual<T> {
+    /// Creates a dual number from its parts.
+    pub fn new(re: T, du: T) -> Self {
+        Dual { re, du }
+    }
+}
+
+impl<T: From<u8>> Dual<T> {
+    /// Dual number of a constant, its derivative is 0.
+    pub fn constant(re: T) -> Self {
+        Dual { re, du: T::from(0) }
+    }
+
+    /// Dual number of the differentiated variable, its derivative is 1.
+    pub fn variable(re: T) -> Self {
+        Dual { re, du: T::from(1) }
+    }
+}
+
+macro_rules! gen_dual {
+    ($t:ty) => {
+        impl Dual<$t> {
+            /// Applies a function given its value `f` and derivative `df` at `re` (chain rule).
+            pub fn chain(self, f: $t, df: $t) -> Self {
+                Dual {
+                    re: f,
+                    du: df * self.du,
+                }
+            }
+
+            /// Absolute value. The derivative at 0 is taken to be 0.
+            pub fn abs(self) -> Self {
+                if self.re > 0.0 {
+                    self
+                } else if self.re < 0.0 {
+                    -self
+                } else {
+                    Dual::new(0.0, 0.0)
+                }
+            }
+
+            /// Reciprocal `1 / self`.
+            pub fn recip(self) -> Self {
+                self.chain(1.0 / self.re, -1.0 / (self.re * self.re))
+            }
+
+            /// Integer power.
+            pub fn powi(self, n: i32) -> Self {
+                let mut p: $t = 1.0;
+                let mut b = self.re;
+                let mut e = n.unsigned_abs();
+
+                // p = re^(n - 1) for positive and re^-n for negative n, computed through squaring
+                e = e.saturating_sub(if n > 0 { 1 } else { 0 });
+                while e > 0 {
+                    if e & 1 == 1 {
+                        p *= b;
+                    }
+                    b *= b;
+                    e >>= 1;
+                }
+
+                if n > 0 {
+                    self.chain(p * self.re, n as $t * p)
+                } else if n == 0 {
+                    Dual::constant(1.0)
+                } else {
+                    let inv = 1.0 / p;
+                    self.chain(inv, n as $t * inv / self.re)
+                }
+            }
+
+            /// Square root.
+            #[cfg(feature = "std")]
+            pub fn sqrt(self) -> Self {
+                let s = self.re.sqrt();
+                self.chain(s, 0.5 / s)
+            }
+
+            /// Real power.
+            #[cfg(feature = "std")]
+            pub fn powf(self, n: $t) -> Self {
+                self.chain(self.re.powf(n), n * self.re.powf(n - 1.0))
+            }
+
+            /// Natural exponential function.
+            #[cfg(feature = "std")]
+            pub fn exp(self) -> Self {
+                let e = self.re.exp();
+                self.chain(e, e)
+            }
+
+            /// Natural logarithm.
+            #[cfg(feature = "std")]
+            pub fn ln(self) -> Self {
+                self.chain(self.re.ln(), 1.0 / self.re)
+            }
+
+            /// Sine.
+            #[cfg(feature = "std")]
+            pub fn sin(self) -> Self {
+                self.chain(self.re.sin(), self.re.cos())
+            }
+
+            /// Cosine.
+            #[cfg(feature = "std")]
+            pub fn cos(self) -> Self {
+                self.chain(self.re.cos(), -self.re.sin())
+            }
+
+            /// Tangent.
+            #[cfg(feature = "std")]
+            pub fn tan(self) -> Self {
+                let t = self.re.tan();
+                self.chain(t, 1.0 + t * t)
+            }
+
+            /// Hyperbolic tangent.
+            #[cfg(feature = "std")]
+            pub fn tanh(self) -> Self {
+                let t = self.re.tanh();
+                self.chain(t, 1.0 - t * t)
+            }
+        }
+
+        impl Add<$t> for Dual<$t> {
+            type Output = Self;
+
+            fn add(self, rhs: $t) -> Self {
+                Dual::new(self.re + rhs, self.du)
+            }
+        }
+
+        impl Add<Dual<$t>> for $t {
+            type Output = Dual<$t>;
+
+            fn add(self, rhs: Dual<$t>) -> Dual<$t> {
+                rhs + self
+            }
+        }
+
+        impl Sub<$t> for Dual<$t> {
+            type Output = Self;
+
+            fn sub(self, rhs: $t) -> Self {
+                Dual::new(self.re - rhs, self.du)
+            }
+        }
+
+        impl Sub<Dual<$t>> for $t {
+            type Output = Dual<$t>;
+
+            fn sub(self, rhs: Dual<$t>) -> Dual<$t> {
+                Dual::new(self - rhs.re, -rhs.du)
+            }
+        }
+
+        impl Mul<$t> for Dual<$t> {
+            type Output = Self;
+
+            fn mul(self, rhs: $t) -> Self {
+                Dual::new(self.re * rhs, self.du * rhs)
+            }
+        }
+
+        impl Mul<Dual<$t>> for $t {
+            type Output = Dual<$t>;
+
+            fn mul(self, rhs: Dual<$t>) -> Dual<$t> {
+                rhs * self
+            }
+        }
+
+        impl Div<$t> for Dual<$t> {
+            type Output = Self;
+
+            fn div(self, rhs: $t) -> Self {
+                Dual::new(self.re / rhs, self.du / rhs)
+            }
+        }
+
+        impl Div<Dual<$t>> for $t {
+            type Output = Dual<$t>;
+
+            fn div(self, rhs: Dual<$t>) -> Dual<$t> {
+                Dual::new(self / rhs.re, -self * rhs.du / (rhs.re * rhs.re))
+            }
+        }
+    };
+}
+
+gen_dual!(f32);
+gen_dual!(f64);
+
+impl<T: Neg<Output = T>> Neg for Dual<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Dual::new(-self.re, -self.du)
+    }
+}
+
+impl<T: Add<Output = T>> Add for Dual<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Dual::new(self.re + rhs.re, self.du + rhs.du)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Dual<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Dual::new(self.re - rhs.re, self.du - rhs.du)
+    }
+}
+
+impl<T: Copy + Add<Output = T> + Mul<Output = T>> Mul for Dual<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        Dual::new(self.re * rhs.re, self.re * rhs.du + self.du * rhs.re)
+    }
+}
+
+impl<T: Copy + Sub<Output = T> + Mul<Output = T> + Div<Output = T>> Div for Dual<T> {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self {
+        Dual::new(
+            self.re / rhs.re,
+            (self.du * rhs.re - self.re * rhs.du) / (rhs.re * rhs.re),
+        )
+    }
+}
+
+impl<T: Copy + Add<Output = T>> AddAssign for Dual<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<T: Copy + Sub<Output = T>> SubAssign for Dual<T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<T: Copy + Add<Output = T> + Mul<Output = T>> MulAssign for Dual<T> {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl<T: Copy + Sub<Output = T> + Mul<Output = T> + Div<Output = T>> DivAssign for Dual<T> {
+    fn div_assign(&mut self, rhs: Self) {
+        *self = *self / rhs;
+    }
+}
+
+/// Calculates the value and the exact derivative of `f` at `x`.
+///
+/// # Arguments
+///
+/// * `f` - Differentiated function written in terms of [`Dual`]
+/// * `x` - Evaluation point
+///
+/// # Examples
+///
+/// ```
+/// use ctl::math::derivative;
+/// let (y, dy) = derivative(|x| x * x * x, 2.0); // (8.0, 12.0)
+/// ```
+pub fn derivative<F: Fn(Dual<f64>) -> Dual<f64>>(f: F, x: f64) -> (f64, f64) {
+    let y = f(Dual::variable(x));
+    (y.re, y.du)
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arithmetic() {
+        let (y, dy) = derivative(|x| (x * x + 1.0) / (x - 3.0), 1.0);
+        assert_eq!(y, -1.0);
+        assert_eq!(dy, (2.0 * -2.0 - 2.0) / 4.0);
+
+        let (y, dy) = derivative(|x| 2.0 - 1.0 / x, 4.0);
+        assert_eq!((y, dy), (1.75, 1.0 / 16.0));
+
+        for n in -3..4 {
+            let (y, dy) = derivative(|x| x.powi(n), 2.0);
+            assert_eq!(y, 2f64.powi(n));
+            assert_eq!(dy, n as f64 * 2f64.powi(n - 1));
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_functions() {
+        let (y, dy) = derivative(|x| x.sin() * x.exp(), 0.5);
+        assert!((y - 0.5f64.sin() * 0.5f64.exp()).abs() < 1e-15);
+        assert!((dy - (0.5f64.cos() + 0.5f64.sin()) * 0.5f64.exp()).abs() < 1e-15);
+
+        let (_, dy) = derivative(|x| (x * x).ln(), 3.0);
+        assert!((dy - 2.0 / 3.0).abs() < 1e-15);
+
+        let (_, dy) = derivative(|x| x.sqrt().cos(), 4.0);
+        assert!((dy + 2f64.sin() / 4.0).abs() < 1e-15);
+    }
+}
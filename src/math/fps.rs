@@ -0,0 +1,529 @@
+use super::modular::ModInt;
+use super::primes::primitive_root;
+use alloc::vec::Vec;
+
+// -----------------------------------------------------------------------------
+// Number theoretic transform
+// -----------------------------------------------------------------------------
+
+/// Transforms the coefficients in place into the values at the powers of a root of unity, or
+/// back with `invert`. The length has to be a power of 2 dividing M - 1 for the prime M, like
+/// 2^23 for 998244353.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::math::fps::ntt;
+/// use ctl::math::modular::ModInt;
+/// type Mint = ModInt<998_244_353>;
+/// let mut a: Vec<Mint> = [1, 2, 3, 4].iter().map(|&x| Mint::from(x)).collect();
+/// ntt(&mut a, false);
+/// ntt(&mut a, true); // [1, 2, 3, 4] again
+/// ```
+pub fn ntt<const M: u64>(a: &mut [ModInt<M>], invert: bool) {
+    let n = a.len();
+    assert!(n.is_power_of_two(), "length has to be a power of 2");
+    assert!(
+        (M - 1).trailing_zeros() >= n.trailing_zeros(),
+        "no root of unity of that order"
+    );
+
+    // Bit reversal permutation
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            a.swap(i, j);
+        }
+    }
+
+    let g = ModInt::<M>::new(primitive_root(M).expect("modulus is not prime"));
+    let mut len = 2;
+    while len <= n {
+        let mut w = g.pow((M - 1) / len as u64);
+        if invert {
+            w = w.inverse().unwrap();
+        }
+
+        for chunk in a.chunks_mut(len) {
+            let (lo, hi) = chunk.split_at_mut(len / 2);
+            let mut wk = ModInt::new(1);
+            for (x, y) in lo.iter_mut().zip(hi) {
+                let (u, v) = (*x, *y * wk);
+                *x = u + v;
+                *y = u - v;
+                wk *= w;
+            }
+        }
+        len <<= 1;
+    }
+
+    if invert {
+        let inv = ModInt::<M>::new(n as u64).inverse().unwrap();
+        a.iter_mut().for_each(|x| *x *= inv);
+    }
+}
+
+/// Product of 2 polynomials given by their coefficients, lowest first. Long inputs are
+/// multiplied through [`ntt`] in O(n log n).
+///
+/// # Examples
+///
+/// ```
+/// use ctl::math::fps::convolve;
+/// use ctl::math::modular::ModInt;
+/// type Mint = ModInt<998_244_353>;
+/// let a = [Mint::from(1), Mint::from(2)];
+/// let b = [Mint::from(1), Mint::from(3)];
+/// let c = convolve(&a, &b); // [1, 5, 6]
+/// ```
+pub fn convolve<const M: u64>(a: &[ModInt<M>], b: &[ModInt<M>]) -> Vec<ModInt<M>> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+
+    let len = a.len() + b.len() - 1;
+    if a.len().min(b.len()) <= 32 {
+        let mut c = vec![ModInt::new(0); len];
+        for (i, &x) in a.iter().enumerate() {
+            for (j, &y) in b.iter().enumerate() {
+                c[i + j] += x * y;
+            }
+        }
+        return c;
+    }
+
+    let n = len.next_power_of_two();
+    let (mut fa, mut fb) = (a.to_vec(), b.to_vec());
+    fa.resize(n, ModInt::new(0));
+    fb.resize(n, ModInt::new(0));
+    ntt(&mut fa, false);
+    ntt(&mut fb, false);
+
+    for (x, &y) in fa.iter_mut().zip(&fb) {
+        *x *= y;
+    }
+    ntt(&mut fa, true);
+    fa.truncate(len);
+
+    fa
+}
+
+// -----------------------------------------------------------------------------
+// Formal power series
+// -----------------------------------------------------------------------------
+
+/// First n coefficients, padded with zeros.
+fn prefix<const M: u64>(a: &[ModInt<M>], n: usize) -> Vec<ModInt<M>> {
+    let mut p: Vec<_> = a.iter().take(n).copied().collect();
+    p.resize(n, ModInt::new(0));
+    p
+}
+
+/// Series 1 / a modulo x^n, doubling the precision by Newton's iteration g (2 - a g). The
+/// constant term has to be invertible.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::math::fps::inverse;
+/// use ctl::math::modular::ModInt;
+/// type Mint = ModInt<998_244_353>;
+/// let a = [Mint::from(1), -Mint::from(1)]; // 1 - x
+/// let b = inverse(&a, 4); // [1, 1, 1, 1]
+/// ```
+pub fn inverse<const M: u64>(a: &[ModInt<M>], n: usize) -> Vec<ModInt<M>> {
+    let c = a.first().and_then(|c| c.inverse());
+    let mut g = vec![c.expect("constant term is not invertible")];
+
+    let mut len = 1;
+    while len < n {
+        len *= 2;
+        let mut e = convolve(&prefix(a, len), &g);
+        e.truncate(len);
+        e.iter_mut().for_each(|x| *x = -*x);
+        e[0] += ModInt::new(2);
+
+        g = convolve(&g, &e);
+        g.truncate(len);
+    }
+
+    prefix(&g, n)
+}
+
+/// Series ln(a) modulo x^n as the integral of a' / a. The constant term has to be 1.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::math::fps::log;
+/// use ctl::math::modular::ModInt;
+/// type Mint = ModInt<998_244_353>;
+/// let a = [Mint::from(1), Mint::from(1)]; // 1 + x
+/// let b = log(&a, 4); // [0, 1, -1/2, 1/3]
+/// ```
+pub fn log<const M: u64>(a: &[ModInt<M>], n: usize) -> Vec<ModInt<M>> {
+    assert!(
+        a.first() == Some(&ModInt::new(1)),
+        "constant term has to be 1"
+    );
+    if n == 0 {
+        return Vec::new();
+    }
+
+    let derivative: Vec<_> = (1..n.min(a.len()))
+        .map(|i| a[i] * ModInt::new(i as u64))
+        .collect();
+    let mut q = convolve(&derivative, &inverse(a, n));
+    q.resize(n, ModInt::new(0));
+
+    // Integrate, dividing by 1..n
+    let mut l = vec![ModInt::new(0); n];
+    for i in 1..n {
+        l[i] = q[i - 1] / ModInt::new(i as u64);
+    }
+
+    l
+}
+
+/// Series e^a modulo x^n by Newton's iteration g (1 - ln(g) + a). The constant term has to be
+/// 0.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::math::fps::exp;
+/// use ctl::math::modular::ModInt;
+/// type Mint = ModInt<998_244_353>;
+/// let a = [Mint::from(0), Mint::from(1)]; // x
+/// let b = exp(&a, 4); // [1, 1, 1/2, 1/6]
+/// ```
+pub fn exp<const M: u64>(a: &[ModInt<M>], n: usize) -> Vec<ModInt<M>> {
+    assert!(
+        a.first().is_none_or(|&c| c == ModInt::new(0)),
+        "constant term has to be 0"
+    );
+
+    let mut g = vec![ModInt::new(1)];
+    let mut len = 1;
+    while len < n {
+        len *= 2;
+        let mut e = prefix(a, len);
+        for (x, y) in e.iter_mut().zip(log(&g, len)) {
+            *x -= y;
+        }
+        e[0] += ModInt::new(1);
+
+        g = convolve(&g, &e);
+        g.truncate(len);
+    }
+
+    prefix(&g, n)
+}
+
+/// Series a^k modulo x^n through exp(k ln(a)), after dividing out the lowest nonzero term, so
+/// the constant term may be anything. a^0 is 1.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::math::fps::pow;
+/// use ctl::math::modular::ModInt;
+/// type Mint = ModInt<998_244_353>;
+/// let a = [Mint::from(0), Mint::from(1), Mint::from(1)]; // x + x²
+/// let b = pow(&a, 2, 5); // [0, 0, 1, 2, 1]
+/// ```
+pub fn pow<const M: u64>(a: &[ModInt<M>], k: u64, n: usize) -> Vec<ModInt<M>> {
+    let mut r = vec![ModInt::new(0); n];
+    if k == 0 {
+        if n > 0 {
+            r[0] = ModInt::new(1);
+        }
+        return r;
+    }
+
+    // a = c x^i b with b(0) = 1
+    let i = match a.iter().position(|&c| c != ModInt::new(0)) {
+        Some(i) if (i as u128) * (k as u128) < n as u128 => i,
+        _ => return r,
+    };
+    let shift = i * k as usize;
+    let c = a[i];
+    let inv = c.inverse().expect("lowest term is not invertible");
+
+    let b: Vec<_> = a[i..].iter().map(|&x| x * inv).collect();
+    let kl: Vec<_> = log(&b, n - shift)
+        .into_iter()
+        .map(|x| x * ModInt::new(k))
+        .collect();
+    let ck = c.pow(k);
+
+    for (j, x) in exp(&kl, n - shift).into_iter().enumerate() {
+        r[shift + j] = x * ck;
+    }
+
+    r
+}
+
+// -----------------------------------------------------------------------------
+// Linear recurrences
+// -----------------------------------------------------------------------------
+
+/// Finds the shortest linear recurrence s[i] = c[0] s[i - 1] + ... + c[k - 1] s[i - k]
+/// generating the sequence with Berlekamp-Massey in O(n²). 2k terms determine a recurrence of
+/// order k.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::math::fps::berlekamp_massey;
+/// use ctl::math::modular::ModInt;
+/// type Mint = ModInt<998_244_353>;
+/// let fib: Vec<Mint> = [0, 1, 1, 2, 3, 5, 8].iter().map(|&x| Mint::from(x)).collect();
+/// let c = berlekamp_massey(&fib); // [1, 1]
+/// ```
+pub fn berlekamp_massey<const M: u64>(s: &[ModInt<M>]) -> Vec<ModInt<M>> {
+    let zero = ModInt::new(0);
+
+    // Connection polynomials 1 - c[0] x - ..., current and before the last length change
+    let (mut c, mut b) = (vec![ModInt::new(1)], vec![ModInt::new(1)]);
+    let (mut len, mut gap, mut last) = (0, 1, ModInt::new(1));
+
+    for i in 0..s.len() {
+        let d: ModInt<M> = (0..=len).map(|j| c[j] * s[i - j]).sum();
+        if d == zero {
+            gap += 1;
+            continue;
+        }
+
+        let coef = d / last;
+        let prev = c.clone();
+        if c.len() < b.len() + gap {
+            c.resize(b.len() + gap, zero);
+        }
+        for (j, &x) in b.iter().enumerate() {
+            c[j + gap] -= coef * x;
+        }
+
+        if 2 * len <= i {
+            len = i + 1 - len;
+            b = prev;
+            last = d;
+            gap = 1;
+        } else {
+            gap += 1;
+        }
+    }
+
+    c.resize(len + 1, zero);
+    c[1..].iter().map(|&x| -x).collect()
+}
+
+/// Evaluates the n-th term of the sequence s[i] = c[0] s[i - 1] + ... + c[k - 1] s[i - k]
+/// given its first k terms, by computing x^n modulo the characteristic polynomial. The
+/// products go through [`convolve`] and the reductions through [`inverse`], taking
+/// O(k log k log n) for long recurrences.
+///
+/// # Arguments
+///
+/// * `coeffs` - Recurrence coefficients c
+/// * `initial` - At least the first k terms
+/// * `n` - Index of the wanted term
+///
+/// # Examples
+///
+/// ```
+/// use ctl::math::fps::kitamasa;
+/// use ctl::math::modular::ModInt;
+/// type Mint = ModInt<998_244_353>;
+/// let one = Mint::from(1);
+/// let f = kitamasa(&[one, one], &[Mint::from(0), one], 90); // F(90) = 532862916
+/// ```
+pub fn kitamasa<const M: u64>(coeffs: &[ModInt<M>], initial: &[ModInt<M>], n: u64) -> ModInt<M> {
+    let k = coeffs.len();
+    assert!(initial.len() >= k, "fewer initial terms than coefficients");
+    if k == 0 {
+        return ModInt::new(0);
+    }
+    if n < k as u64 {
+        return initial[n as usize];
+    }
+
+    // Characteristic polynomial x^k - c[0] x^(k - 1) - ..., and its reversal's inverse
+    let mut rev: Vec<_> = core::iter::once(ModInt::new(1))
+        .chain(coeffs.iter().map(|&c| -c))
+        .collect();
+    let inv = inverse(&rev, k);
+    rev.reverse();
+    let f = rev;
+
+    // Remainder of a polynomial of degree below 2k - 1, the quotient coming from the reversal
+    let reduce = |mut a: Vec<ModInt<M>>| {
+        if a.len() > k {
+            let d = a.len() - k;
+            let mut q: Vec<_> = a.iter().rev().take(d).copied().collect();
+            q = convolve(&q, &inv[..d]);
+            q.truncate(d);
+            q.reverse();
+
+            for (x, y) in a.iter_mut().zip(convolve(&q, &f)) {
+                *x -= y;
+            }
+        }
+        a.resize(k, ModInt::new(0));
+        a
+    };
+
+    let mut r = reduce(vec![ModInt::new(1)]);
+    let mut base = reduce(vec![ModInt::new(0), ModInt::new(1)]);
+    let mut e = n;
+    while e > 0 {
+        if e & 1 == 1 {
+            r = reduce(convolve(&r, &base));
+        }
+        base = reduce(convolve(&base, &base));
+        e >>= 1;
+    }
+
+    r.iter().zip(initial).map(|(&a, &b)| a * b).sum()
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::random::Rng;
+
+    type Mint = ModInt<998_244_353>;
+
+    fn mints(values: &[u64]) -> Vec<Mint> {
+        values.iter().map(|&x| Mint::from(x)).collect()
+    }
+
+    fn random(rng: &mut Rng, n: usize) -> Vec<Mint> {
+        (0..n).map(|_| Mint::from(rng.next_u64())).collect()
+    }
+
+    /// Plain O(n²) product modulo x^n.
+    fn naive(a: &[Mint], b: &[Mint], n: usize) -> Vec<Mint> {
+        let mut c = vec![Mint::new(0); n];
+        for (i, &x) in a.iter().enumerate().take(n) {
+            for (j, &y) in b.iter().enumerate().take(n - i) {
+                c[i + j] += x * y;
+            }
+        }
+        c
+    }
+
+    #[test]
+    fn test_convolve() {
+        let mut rng = Rng::new(7);
+        let (a, b) = (random(&mut rng, 300), random(&mut rng, 200));
+        assert_eq!(convolve(&a, &b), naive(&a, &b, 499));
+        assert!(convolve(&a, &[]).is_empty());
+
+        let mut c = a[..256].to_vec();
+        ntt(&mut c, false);
+        ntt(&mut c, true);
+        assert_eq!(c, a[..256]);
+
+        // Another NTT friendly prime
+        type Other = ModInt<469_762_049>;
+        let x: Vec<_> = (1..=100).map(Other::from).collect();
+        let y = convolve(&x, &x);
+        assert_eq!(y[0].value(), 1);
+        assert_eq!(y[99].value(), (1..=100).map(|i| i * (101 - i)).sum::<u64>());
+    }
+
+    #[test]
+    fn test_series() {
+        let mut rng = Rng::new(3);
+        let n = 200;
+
+        let mut a = random(&mut rng, n);
+        a[0] = Mint::new(1);
+        let one: Vec<_> = (0..n).map(|i| Mint::new((i == 0) as u64)).collect();
+
+        let inv = inverse(&a, n);
+        assert_eq!(naive(&a, &inv, n), one);
+
+        // exp and log undo each other, and the powers agree with repeated products
+        let l = log(&a, n);
+        assert_eq!(l[0], Mint::new(0));
+        assert_eq!(exp(&l, n), a);
+
+        let cube = naive(&naive(&a, &a, n), &a, n);
+        assert_eq!(pow(&a, 3, n), cube);
+
+        // Leading zeros and constants other than 1
+        let b = mints(&[0, 0, 3, 1]);
+        let b3 = naive(&naive(&b, &b, 10), &b, 10);
+        assert_eq!(pow(&b, 3, 10), b3);
+        assert_eq!(pow(&b, 5, 10), vec![Mint::new(0); 10]);
+        assert_eq!(pow(&b, 0, 3), mints(&[1, 0, 0]));
+        assert_eq!(pow(&[Mint::new(0)], 1 << 62, 3), mints(&[0, 0, 0]));
+
+        // e^x and ln(1 / (1 - x)) = x + x² / 2 + ...
+        let e = exp(&mints(&[0, 1]), 5);
+        let fact = [1, 1, 2, 6, 24];
+        for (c, f) in e.iter().zip(fact) {
+            assert_eq!(*c * Mint::new(f), Mint::new(1));
+        }
+        let geo = inverse(&[Mint::new(1), -Mint::new(1)], 6);
+        assert_eq!(geo, mints(&[1; 6]));
+        let harmonic = log(&geo, 6);
+        for (i, c) in harmonic.iter().enumerate().skip(1) {
+            assert_eq!(*c * Mint::new(i as u64), Mint::new(1));
+        }
+    }
+
+    #[test]
+    fn test_recurrences() {
+        let mut rng = Rng::new(11);
+
+        // Fibonacci, F(2n) = F(n) (2 F(n + 1) - F(n))
+        let one = Mint::new(1);
+        let fib = |n| kitamasa(&[one, one], &[Mint::new(0), one], n);
+        let fib_terms: Vec<_> = (0..10).map(fib).collect();
+        assert_eq!(fib_terms, mints(&[0, 1, 1, 2, 3, 5, 8, 13, 21, 34]));
+        assert_eq!(berlekamp_massey(&fib_terms), vec![one, one]);
+        let n = 1_000_000_000_000;
+        assert_eq!(fib(2 * n), fib(n) * (Mint::new(2) * fib(n + 1) - fib(n)));
+
+        // Random recurrences, short ones and some long enough for the NTT
+        for &k in &[1, 5, 40] {
+            let c = random(&mut rng, k);
+            let mut s = random(&mut rng, k);
+            for i in k..300 {
+                let next = (0..k).map(|j| c[j] * s[i - 1 - j]).sum();
+                s.push(next);
+            }
+
+            assert_eq!(berlekamp_massey(&s[..2 * k]), c);
+            assert_eq!(berlekamp_massey(&s), c);
+            for n in [0, k as u64 - 1, k as u64, 150, 299] {
+                assert_eq!(
+                    kitamasa(&c, &s[..k], n),
+                    s[n as usize],
+                    "k = {}, n = {}",
+                    k,
+                    n
+                );
+            }
+        }
+
+        // Zero sequences and a geometric one
+        assert!(berlekamp_massey(&mints(&[0, 0, 0])).is_empty());
+        assert_eq!(kitamasa(&[], &[], 5), Mint::new(0));
+        assert_eq!(berlekamp_massey(&mints(&[3, 6, 12, 24])), mints(&[2]));
+        assert_eq!(kitamasa(&mints(&[2]), &mints(&[3]), 20), Mint::new(3 << 20));
+    }
+}
@@ -0,0 +1,360 @@
+use super::modular::{mod_add, mod_inverse, mod_mul, mod_sub};
+use super::primes::{factorize, is_prime};
+use alloc::vec::Vec;
+
+// -----------------------------------------------------------------------------
+// Polynomials over GF(p)
+// -----------------------------------------------------------------------------
+
+// Coefficients come lowest first without trailing zeros, so the zero polynomial is empty.
+
+fn trim(mut a: Vec<u64>) -> Vec<u64> {
+    while a.last() == Some(&0) {
+        a.pop();
+    }
+    a
+}
+
+fn poly_sub(a: &[u64], b: &[u64], p: u64) -> Vec<u64> {
+    let mut r = a.to_vec();
+    r.resize(a.len().max(b.len()), 0);
+    for (x, &y) in r.iter_mut().zip(b) {
+        *x = mod_sub(*x, y, p);
+    }
+    trim(r)
+}
+
+fn poly_mul(a: &[u64], b: &[u64], p: u64) -> Vec<u64> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+
+    let mut r = vec![0; a.len() + b.len() - 1];
+    for (i, &x) in a.iter().enumerate() {
+        for (j, &y) in b.iter().enumerate() {
+            r[i + j] = mod_add(r[i + j], mod_mul(x, y, p), p);
+        }
+    }
+    trim(r)
+}
+
+/// Quotient and remainder of a / b for a nonzero b.
+fn poly_divrem(a: &[u64], b: &[u64], p: u64) -> (Vec<u64>, Vec<u64>) {
+    let lead_inv = mod_inverse(b[b.len() - 1], p).expect("divisor is not invertible");
+    let mut r = a.to_vec();
+    if r.len() < b.len() {
+        return (Vec::new(), r);
+    }
+
+    let mut q = vec![0; r.len() - b.len() + 1];
+    for i in (0..q.len()).rev() {
+        let c = mod_mul(r[i + b.len() - 1], lead_inv, p);
+        q[i] = c;
+        for (j, &y) in b.iter().enumerate() {
+            r[i + j] = mod_sub(r[i + j], mod_mul(c, y, p), p);
+        }
+    }
+
+    (trim(q), trim(r))
+}
+
+fn poly_gcd(a: &[u64], b: &[u64], p: u64) -> Vec<u64> {
+    let (mut a, mut b) = (a.to_vec(), b.to_vec());
+    while !b.is_empty() {
+        let r = poly_divrem(&a, &b, p).1;
+        a = b;
+        b = r;
+    }
+    a
+}
+
+// -----------------------------------------------------------------------------
+// Galois field
+// -----------------------------------------------------------------------------
+
+/// Element of a [`GaloisField`], a polynomial of degree below the field's degree given by its
+/// coefficients lowest first. Only meaningful together with the field it came from.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GfElement {
+    coeffs: Vec<u64>,
+}
+
+impl GfElement {
+    /// Coefficients in [0, p), lowest first, one per degree of the field.
+    pub fn coeffs(&self) -> &[u64] {
+        &self.coeffs
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.coeffs.iter().all(|&c| c == 0)
+    }
+}
+
+/// Finite field GF(p^k) of the polynomials over GF(p) modulo an irreducible polynomial of
+/// degree k. Elements are [`GfElement`]s and the field carries out the arithmetic on them, as
+/// the modulus is only known at runtime.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::math::galois::GaloisField;
+/// // GF(2^8) modulo x^8 + x^4 + x^3 + x^2 + 1, as used by Reed-Solomon codes
+/// let f = GaloisField::new(2, &[1, 0, 1, 1, 1, 0, 0, 0, 1]).unwrap();
+/// let x = f.element(&[0, 1]);
+/// let a = f.pow(&x, 8); // x^4 + x^3 + x^2 + 1
+/// let b = f.mul(&a, &f.inverse(&a).unwrap()); // 1
+/// let c = GaloisField::new(2, &[1, 0, 1]); // None, x^2 + 1 = (x + 1)^2
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GaloisField {
+    p: u64,
+    /// Monic modulus, lowest coefficient first
+    modulus: Vec<u64>,
+}
+
+impl GaloisField {
+    /// Creates GF(p^k) for a prime p and a polynomial of degree k ≥ 1, lowest coefficient
+    /// first. The polynomial is made monic. `None` if p isn't prime or the polynomial isn't
+    /// irreducible over GF(p), checked by Rabin's test.
+    ///
+    /// # Arguments
+    ///
+    /// * `p` - Characteristic of the field
+    /// * `modulus` - Irreducible polynomial, coefficients reduced modulo p
+    pub fn new(p: u64, modulus: &[u64]) -> Option<Self> {
+        if !is_prime(p) {
+            return None;
+        }
+
+        let modulus = trim(modulus.iter().map(|&c| c % p).collect());
+        if modulus.len() < 2 {
+            return None;
+        }
+        let lead_inv = mod_inverse(modulus[modulus.len() - 1], p)?;
+        let modulus = modulus.iter().map(|&c| mod_mul(c, lead_inv, p)).collect();
+
+        let field = GaloisField { p, modulus };
+        if field.is_irreducible() {
+            Some(field)
+        } else {
+            None
+        }
+    }
+
+    /// Rabin's test on the powers x^(p^j) for j = 0..=k.
+    fn is_irreducible(&self) -> bool {
+        let k = self.degree();
+        let x = self.element(&[0, 1]);
+
+        let mut frobenius = Vec::with_capacity(k + 1);
+        frobenius.push(x.clone());
+        for j in 0..k {
+            let next = self.pow(&frobenius[j], self.p);
+            frobenius.push(next);
+        }
+
+        // The polynomial divides x^(p^k) - x but shares no factor with x^(p^(k / q)) - x for
+        // the primes q dividing k, so it has no factor of a smaller degree
+        if frobenius[k] != x {
+            return false;
+        }
+        factorize(k as u64).iter().all(|&(q, _)| {
+            let h = self.sub(&frobenius[k / q as usize], &x);
+            poly_gcd(&self.modulus, &trim(h.coeffs), self.p).len() == 1
+        })
+    }
+
+    /// p, the number of times 1 has to be added to get 0.
+    pub fn characteristic(&self) -> u64 {
+        self.p
+    }
+
+    /// k, the degree of the modulus. The field has p^k elements.
+    pub fn degree(&self) -> usize {
+        self.modulus.len() - 1
+    }
+
+    /// Monic modulus, lowest coefficient first.
+    pub fn modulus(&self) -> &[u64] {
+        &self.modulus
+    }
+
+    /// Element of the polynomial with the given coefficients, lowest first, reduced modulo p
+    /// and the modulus.
+    pub fn element(&self, coeffs: &[u64]) -> GfElement {
+        let a = trim(coeffs.iter().map(|&c| c % self.p).collect());
+        self.wrap(poly_divrem(&a, &self.modulus, self.p).1)
+    }
+
+    /// Pads a reduced polynomial to one coefficient per degree.
+    fn wrap(&self, mut coeffs: Vec<u64>) -> GfElement {
+        coeffs.resize(self.degree(), 0);
+        GfElement { coeffs }
+    }
+
+    pub fn zero(&self) -> GfElement {
+        self.wrap(Vec::new())
+    }
+
+    pub fn one(&self) -> GfElement {
+        self.element(&[1])
+    }
+
+    pub fn add(&self, a: &GfElement, b: &GfElement) -> GfElement {
+        let coeffs = a
+            .coeffs
+            .iter()
+            .zip(&b.coeffs)
+            .map(|(&x, &y)| mod_add(x, y, self.p))
+            .collect();
+        GfElement { coeffs }
+    }
+
+    pub fn sub(&self, a: &GfElement, b: &GfElement) -> GfElement {
+        let coeffs = a
+            .coeffs
+            .iter()
+            .zip(&b.coeffs)
+            .map(|(&x, &y)| mod_sub(x, y, self.p))
+            .collect();
+        GfElement { coeffs }
+    }
+
+    pub fn neg(&self, a: &GfElement) -> GfElement {
+        self.sub(&self.zero(), a)
+    }
+
+    pub fn mul(&self, a: &GfElement, b: &GfElement) -> GfElement {
+        let prod = poly_mul(&trim(a.coeffs.clone()), &trim(b.coeffs.clone()), self.p);
+        self.wrap(poly_divrem(&prod, &self.modulus, self.p).1)
+    }
+
+    /// Multiplicative inverse by the extended Euclidean algorithm, `None` for zero.
+    pub fn inverse(&self, a: &GfElement) -> Option<GfElement> {
+        if a.is_zero() {
+            return None;
+        }
+
+        // Invariant: s a = r modulo the modulus
+        let (mut r0, mut r1) = (self.modulus.clone(), trim(a.coeffs.clone()));
+        let (mut s0, mut s1) = (Vec::new(), vec![1]);
+        while r1.len() > 1 {
+            let (q, r) = poly_divrem(&r0, &r1, self.p);
+            let s = poly_sub(&s0, &poly_mul(&q, &s1, self.p), self.p);
+            r0 = core::mem::replace(&mut r1, r);
+            s0 = core::mem::replace(&mut s1, s);
+        }
+
+        // r1 is a nonzero constant since the modulus is irreducible
+        let c = mod_inverse(r1[0], self.p)?;
+        Some(self.wrap(poly_mul(&s1, &[c], self.p)))
+    }
+
+    /// Raises a to the power `exp` by square and multiply. 0^0 is 1.
+    pub fn pow(&self, a: &GfElement, mut exp: u64) -> GfElement {
+        let mut result = self.one();
+        let mut base = a.clone();
+
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = self.mul(&result, &base);
+            }
+            base = self.mul(&base, &base);
+            exp >>= 1;
+        }
+
+        result
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// All p^k elements of a small field.
+    fn elements(f: &GaloisField) -> Vec<GfElement> {
+        let (p, k) = (f.characteristic(), f.degree() as u32);
+        (0..p.pow(k))
+            .map(|mut i| {
+                let coeffs: Vec<u64> = (0..k)
+                    .map(|_| {
+                        let c = i % p;
+                        i /= p;
+                        c
+                    })
+                    .collect();
+                f.element(&coeffs)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_irreducible() {
+        assert!(GaloisField::new(2, &[1, 1, 1]).is_some());
+        assert!(GaloisField::new(3, &[1, 0, 1]).is_some());
+        assert!(GaloisField::new(7, &[3]).is_none());
+        assert!(GaloisField::new(4, &[1, 1, 1]).is_none());
+
+        // Reducible without roots, (x^2 + x + 1)^2
+        assert!(GaloisField::new(2, &[1, 0, 1, 0, 1]).is_none());
+        // x^2 + 1 = (x - 2) (x + 2) modulo 5
+        assert!(GaloisField::new(5, &[1, 0, 1]).is_none());
+
+        // Made monic, 2 x^2 + 2 = 2 (x^2 + 1) modulo 3
+        let f = GaloisField::new(3, &[2, 0, 2]).unwrap();
+        assert_eq!(f.modulus(), &[1, 0, 1]);
+
+        // Irreducible polynomials of degree 4 over GF(2), out of all 16 monic ones
+        let count = (0..16u64)
+            .filter(|m| {
+                let coeffs: Vec<u64> = (0..4).map(|i| m >> i & 1).chain([1]).collect();
+                GaloisField::new(2, &coeffs).is_some()
+            })
+            .count();
+        assert_eq!(count, 3);
+    }
+
+    #[test]
+    fn test_field_axioms() {
+        for f in [
+            GaloisField::new(2, &[1, 1, 0, 1]).unwrap(),
+            GaloisField::new(3, &[2, 2, 1]).unwrap(),
+            GaloisField::new(5, &[1, 1, 0, 1]).unwrap(),
+            GaloisField::new(13, &[2, 1]).unwrap(),
+        ] {
+            let all = elements(&f);
+            let q = all.len() as u64;
+            assert_eq!(f.element(&[f.characteristic()]), f.zero());
+
+            for a in &all {
+                assert_eq!(f.add(a, &f.neg(a)), f.zero());
+                assert_eq!(f.pow(a, q), *a);
+                match f.inverse(a) {
+                    Some(inv) => assert_eq!(f.mul(a, &inv), f.one()),
+                    None => assert!(a.is_zero()),
+                }
+            }
+
+            for (i, a) in all.iter().enumerate().step_by(3) {
+                for b in all.iter().skip(i).step_by(5) {
+                    let c = &all[(i * 7 + 1) % all.len()];
+                    assert_eq!(f.mul(a, b), f.mul(b, a));
+                    assert_eq!(f.sub(&f.add(a, b), b), *a);
+                    let left = f.mul(a, &f.add(b, c));
+                    assert_eq!(left, f.add(&f.mul(a, b), &f.mul(a, c)));
+                }
+            }
+        }
+
+        // GF(2^8) with the Reed-Solomon modulus, where x generates every nonzero element
+        let f = GaloisField::new(2, &[1, 0, 1, 1, 1, 0, 0, 0, 1]).unwrap();
+        let x = f.element(&[0, 1]);
+        assert_eq!(f.pow(&x, 8).coeffs(), &[1, 0, 1, 1, 1, 0, 0, 0]);
+        assert_eq!(f.pow(&x, 255), f.one());
+        assert!((1..255).all(|e| f.pow(&x, e) != f.one()));
+    }
+}
@@ -0,0 +1,347 @@
+use alloc::vec::Vec;
+use core::ops::BitXorAssign;
+
+// -----------------------------------------------------------------------------
+// Bit vector
+// -----------------------------------------------------------------------------
+
+/// Fixed length vector over GF(2) packed into 64 bit words, so adding rows is a word wise XOR.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::math::gf2::BitVector;
+/// let mut a = BitVector::from_bits(&[true, false, true]);
+/// a ^= &BitVector::from_bits(&[true, true, false]);
+/// let b = a.get(1); // true
+/// let c = a.count_ones(); // 2
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitVector {
+    words: Vec<u64>,
+    len: usize,
+}
+
+impl BitVector {
+    /// Vector of `len` zeros.
+    pub fn new(len: usize) -> Self {
+        BitVector {
+            words: vec![0; len.div_ceil(64)],
+            len,
+        }
+    }
+
+    pub fn from_bits(bits: &[bool]) -> Self {
+        let mut v = Self::new(bits.len());
+        for (i, &b) in bits.iter().enumerate() {
+            v.set(i, b);
+        }
+        v
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn get(&self, i: usize) -> bool {
+        assert!(i < self.len, "bit index out of bounds");
+        self.words[i / 64] >> (i % 64) & 1 == 1
+    }
+
+    pub fn set(&mut self, i: usize, value: bool) {
+        assert!(i < self.len, "bit index out of bounds");
+        let mask = 1 << (i % 64);
+        if value {
+            self.words[i / 64] |= mask;
+        } else {
+            self.words[i / 64] &= !mask;
+        }
+    }
+
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    /// Index of the lowest set bit.
+    pub fn first_one(&self) -> Option<usize> {
+        self.words
+            .iter()
+            .position(|&w| w != 0)
+            .map(|i| i * 64 + self.words[i].trailing_zeros() as usize)
+    }
+}
+
+impl BitXorAssign<&BitVector> for BitVector {
+    /// Adds the vectors over GF(2). Panics if the lengths differ.
+    fn bitxor_assign(&mut self, o: &BitVector) {
+        assert_eq!(self.len, o.len, "bit vector lengths differ");
+        for (a, b) in self.words.iter_mut().zip(&o.words) {
+            *a ^= b;
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Linear systems
+// -----------------------------------------------------------------------------
+
+/// Brings the rows into reduced row echelon form, applying the same row operations to the
+/// right hand side. Returns the pivot column of each of the first rank rows.
+fn eliminate(rows: &mut [BitVector], rhs: &mut [bool]) -> Vec<usize> {
+    let cols = rows.first().map_or(0, |r| r.len());
+    let mut pivots = Vec::new();
+
+    for c in 0..cols {
+        let r = pivots.len();
+        let p = match (r..rows.len()).find(|&i| rows[i].get(c)) {
+            Some(p) => p,
+            None => continue,
+        };
+        rows.swap(r, p);
+        rhs.swap(r, p);
+
+        let (pivot, pivot_rhs) = (rows[r].clone(), rhs[r]);
+        for i in 0..rows.len() {
+            if i != r && rows[i].get(c) {
+                rows[i] ^= &pivot;
+                rhs[i] ^= pivot_rhs;
+            }
+        }
+        pivots.push(c);
+    }
+
+    pivots
+}
+
+/// Rank over GF(2) of the matrix with the given rows, by Gaussian elimination in
+/// O(rows² cols / 64).
+///
+/// # Examples
+///
+/// ```
+/// use ctl::math::gf2::{rank, BitVector};
+/// let rows = [
+///     BitVector::from_bits(&[true, true, false]),
+///     BitVector::from_bits(&[false, true, true]),
+///     BitVector::from_bits(&[true, false, true]),
+/// ];
+/// let r = rank(&rows); // 2, the last row is the sum of the others
+/// ```
+pub fn rank(rows: &[BitVector]) -> usize {
+    let mut rows = rows.to_vec();
+    let mut rhs = vec![false; rows.len()];
+
+    eliminate(&mut rows, &mut rhs).len()
+}
+
+/// Solves A x = b over GF(2) for the matrix A given by its rows, setting the free variables to
+/// 0. `None` if the system is inconsistent.
+///
+/// # Arguments
+///
+/// * `rows` - Rows of A, one bit per unknown
+/// * `rhs` - b, one bit per row
+///
+/// # Examples
+///
+/// ```
+/// use ctl::math::gf2::{solve, BitVector};
+/// // x0 + x1 = 1, x1 + x2 = 0, x2 = 1
+/// let rows = [
+///     BitVector::from_bits(&[true, true, false]),
+///     BitVector::from_bits(&[false, true, true]),
+///     BitVector::from_bits(&[false, false, true]),
+/// ];
+/// let x = solve(&rows, &[true, false, true]); // Some([0, 1, 1])
+/// ```
+pub fn solve(rows: &[BitVector], rhs: &[bool]) -> Option<BitVector> {
+    assert_eq!(rows.len(), rhs.len(), "one right hand side bit per row");
+    let cols = rows.first().map_or(0, |r| r.len());
+    let (mut rows, mut rhs) = (rows.to_vec(), rhs.to_vec());
+
+    let pivots = eliminate(&mut rows, &mut rhs);
+    if rhs[pivots.len()..].iter().any(|&b| b) {
+        return None;
+    }
+
+    let mut x = BitVector::new(cols);
+    for (r, &c) in pivots.iter().enumerate() {
+        x.set(c, rhs[r]);
+    }
+
+    Some(x)
+}
+
+// -----------------------------------------------------------------------------
+// XOR basis
+// -----------------------------------------------------------------------------
+
+/// Linear basis of 64 bit words under XOR, each basis word having a distinct highest bit.
+/// Answers which values are XORs of a subset of the inserted ones, and which is the largest.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::math::gf2::XorBasis;
+/// let mut b = XorBasis::new();
+/// for x in [3, 10, 5] {
+///     b.insert(x);
+/// }
+/// let m = b.max_xor(); // 15 = 10 ^ 5
+/// let c = b.contains(6); // true = 3 ^ 5
+/// ```
+#[derive(Debug, Clone)]
+pub struct XorBasis {
+    /// Basis word with the highest bit i, or 0
+    basis: [u64; 64],
+    rank: usize,
+}
+
+impl XorBasis {
+    pub fn new() -> Self {
+        XorBasis {
+            basis: [0; 64],
+            rank: 0,
+        }
+    }
+
+    /// Reduces x by the basis, clearing every bit that has a basis word.
+    fn reduce(&self, mut x: u64) -> u64 {
+        for i in (0..64).rev() {
+            if x >> i & 1 == 1 {
+                x ^= self.basis[i];
+            }
+        }
+        x
+    }
+
+    /// Adds x to the basis. Returns false if it is already a XOR of inserted values.
+    pub fn insert(&mut self, x: u64) -> bool {
+        let x = self.reduce(x);
+        if x == 0 {
+            return false;
+        }
+
+        self.basis[63 - x.leading_zeros() as usize] = x;
+        self.rank += 1;
+        true
+    }
+
+    /// Checks whether x is the XOR of some subset of the inserted values, 0 being the empty one.
+    pub fn contains(&self, x: u64) -> bool {
+        self.reduce(x) == 0
+    }
+
+    /// Largest XOR of a subset of the inserted values.
+    pub fn max_xor(&self) -> u64 {
+        self.basis
+            .iter()
+            .rev()
+            .fold(0, |acc, &b| if acc ^ b > acc { acc ^ b } else { acc })
+    }
+
+    /// Number of independent values inserted, there are 2^rank subset XORs.
+    pub fn rank(&self) -> usize {
+        self.rank
+    }
+}
+
+impl Default for XorBasis {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::random::Rng;
+
+    fn random_rows(rng: &mut Rng, rows: usize, cols: usize) -> Vec<BitVector> {
+        (0..rows)
+            .map(|_| {
+                let bits: Vec<_> = (0..cols).map(|_| rng.next_u64() & 3 == 0).collect();
+                BitVector::from_bits(&bits)
+            })
+            .collect()
+    }
+
+    /// A x over GF(2).
+    fn apply(rows: &[BitVector], x: &BitVector) -> Vec<bool> {
+        rows.iter()
+            .map(|r| (0..x.len()).filter(|&i| r.get(i) && x.get(i)).count() % 2 == 1)
+            .collect()
+    }
+
+    #[test]
+    fn test_linear_systems() {
+        let mut v = BitVector::new(130);
+        assert_eq!(v.first_one(), None);
+        v.set(129, true);
+        v.set(64, true);
+        assert_eq!((v.count_ones(), v.first_one()), (2, Some(64)));
+        v.set(64, false);
+        assert_eq!(v.first_one(), Some(129));
+
+        // Systems built from a known solution are solvable, their solutions fit
+        let mut rng = Rng::new(5);
+        for &(n, m) in &[(5, 5), (20, 70), (70, 20), (100, 100)] {
+            let rows = random_rows(&mut rng, n, m);
+            let bits: Vec<_> = (0..m).map(|_| rng.next_u64() & 1 == 1).collect();
+            let b = apply(&rows, &BitVector::from_bits(&bits));
+
+            let x = solve(&rows, &b).unwrap();
+            assert_eq!(apply(&rows, &x), b);
+            assert!(rank(&rows) <= n.min(m));
+        }
+
+        // Dependent rows and an inconsistent right hand side
+        let rows = [
+            BitVector::from_bits(&[true, true, false]),
+            BitVector::from_bits(&[false, true, true]),
+            BitVector::from_bits(&[true, false, true]),
+        ];
+        assert_eq!(rank(&rows), 2);
+        assert_eq!(solve(&rows, &[true, true, true]), None);
+        let x = solve(&rows, &[true, true, false]).unwrap();
+        assert_eq!(x, BitVector::from_bits(&[false, true, false]));
+        assert_eq!(rank(&[]), 0);
+    }
+
+    #[test]
+    fn test_xor_basis() {
+        let values = [0b1011u64, 0b0110, 0b1101, 0b0001, 0b1100];
+        let mut basis = XorBasis::new();
+        let inserted: Vec<_> = values.iter().map(|&x| basis.insert(x)).collect();
+        assert_eq!(inserted, [true, true, false, true, false]);
+        assert_eq!(basis.rank(), 3);
+
+        // Against all subsets
+        let subsets: Vec<u64> = (0..1 << values.len())
+            .map(|mask: u32| {
+                (0..values.len())
+                    .filter(|i| mask >> i & 1 == 1)
+                    .fold(0, |acc, i| acc ^ values[i])
+            })
+            .collect();
+        assert_eq!(basis.max_xor(), *subsets.iter().max().unwrap());
+        for x in 0..16 {
+            assert_eq!(basis.contains(x), subsets.contains(&x), "{}", x);
+        }
+
+        let mut full = XorBasis::new();
+        for i in 0..64 {
+            assert!(full.insert(1 << i | 1));
+        }
+        assert_eq!(full.max_xor(), u64::MAX);
+        assert!(!full.insert(12345));
+    }
+}
@@ -0,0 +1,101 @@
+use core::fmt::Debug;
+use core::ops::{Add, Div, Mul, Neg, Rem, Shl, Shr, Sub};
+
+// -----------------------------------------------------------------------------
+// Integer
+// -----------------------------------------------------------------------------
+
+/// Primitive integer type, implemented for all widths from `u8` / `i8` up to `u128` / `i128`
+/// and `usize` / `isize`.
+pub trait Integer:
+    Copy
+    + Ord
+    + Debug
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+    + Shl<u32, Output = Self>
+    + Shr<u32, Output = Self>
+{
+    /// Unsigned type of the same width
+    type Unsigned: Integer<Unsigned = Self::Unsigned>;
+
+    const ZERO: Self;
+    const ONE: Self;
+
+    /// Magnitude of the value, which always fits into the unsigned type.
+    fn unsigned_abs(self) -> Self::Unsigned;
+
+    /// Converts a magnitude back, `None` if it doesn't fit.
+    fn from_unsigned(u: Self::Unsigned) -> Option<Self>;
+
+    /// Multiplication returning `None` on overflow.
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+
+    /// Number of trailing zero bits.
+    fn trailing_zeros(self) -> u32;
+}
+
+macro_rules! gen_integer {
+    ($($T:ty => $U:ty, $abs:expr);* $(;)?) => {
+        $(
+            impl Integer for $T {
+                type Unsigned = $U;
+
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                #[inline]
+                fn unsigned_abs(self) -> $U {
+                    $abs(self)
+                }
+
+                #[inline]
+                fn from_unsigned(u: $U) -> Option<Self> {
+                    core::convert::TryFrom::try_from(u).ok()
+                }
+
+                #[inline]
+                fn checked_mul(self, rhs: Self) -> Option<Self> {
+                    <$T>::checked_mul(self, rhs)
+                }
+
+                #[inline]
+                fn trailing_zeros(self) -> u32 {
+                    <$T>::trailing_zeros(self)
+                }
+            }
+        )*
+    };
+}
+
+gen_integer!(
+    u8 => u8, |x| x;
+    u16 => u16, |x| x;
+    u32 => u32, |x| x;
+    u64 => u64, |x| x;
+    u128 => u128, |x| x;
+    usize => usize, |x| x;
+    i8 => u8, i8::unsigned_abs;
+    i16 => u16, i16::unsigned_abs;
+    i32 => u32, i32::unsigned_abs;
+    i64 => u64, i64::unsigned_abs;
+    i128 => u128, i128::unsigned_abs;
+    isize => usize, isize::unsigned_abs;
+);
+
+// -----------------------------------------------------------------------------
+// Signed
+// -----------------------------------------------------------------------------
+
+/// Signed primitive integer type, implemented for `i8` up to `i128` and `isize`.
+pub trait Signed: Integer + Neg<Output = Self> {}
+
+impl Signed for i8 {}
+impl Signed for i16 {}
+impl Signed for i32 {}
+impl Signed for i64 {}
+impl Signed for i128 {}
+impl Signed for isize {}
@@ -0,0 +1,735 @@
+use super::bezout;
+#[cfg(feature = "std")]
+use super::binary_gcd;
+use core::fmt;
+use core::iter::{Product, Sum};
+use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+// -----------------------------------------------------------------------------
+// Arithmetic
+// -----------------------------------------------------------------------------
+
+/// Calculates (a + b) mod m.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::math::modular::mod_add;
+/// let x = mod_add(5, 9, 7); // 0
+/// let y = mod_add(u64::MAX, u64::MAX, u64::MAX - 1); // 2
+/// ```
+pub fn mod_add(a: u64, b: u64, m: u64) -> u64 {
+    ((a as u128 + b as u128) % m as u128) as u64
+}
+
+/// Calculates (a - b) mod m, wrapping around into [0, m).
+///
+/// # Examples
+///
+/// ```
+/// use ctl::math::modular::mod_sub;
+/// let x = mod_sub(2, 5, 7); // 4
+/// ```
+pub fn mod_sub(a: u64, b: u64, m: u64) -> u64 {
+    let (a, b) = (a % m, b % m);
+
+    if a >= b {
+        a - b
+    } else {
+        m - (b - a)
+    }
+}
+
+/// Calculates (a * b) mod m.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::math::modular::mod_mul;
+/// let x = mod_mul(1 << 40, 1 << 40, 1_000_000_007); // 496641140
+/// ```
+pub fn mod_mul(a: u64, b: u64, m: u64) -> u64 {
+    (a as u128 * b as u128 % m as u128) as u64
+}
+
+/// Calculates base^exp mod m by square and multiply in O(log exp) steps. 0^0 is 1.
+///
+/// # Arguments
+///
+/// * `base` - Value to raise
+/// * `exp` - Exponent
+/// * `m` - Modulus
+///
+/// # Examples
+///
+/// ```
+/// use ctl::math::modular::mod_pow;
+/// let x = mod_pow(3, 200, 1_000_000_007); // 136318165
+/// let y = mod_pow(2, u64::MAX, u64::MAX); // 2^63
+/// ```
+pub fn mod_pow(base: u64, mut exp: u64, m: u64) -> u64 {
+    let mut result = 1 % m;
+    let mut b = base % m;
+
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mod_mul(result, b, m);
+        }
+        b = mod_mul(b, b, m);
+        exp >>= 1;
+    }
+
+    result
+}
+
+/// Calculates the multiplicative inverse x of a with a x = 1 mod m through the extended gcd.
+/// Returns `None` if a and m aren't coprime, so no inverse exists.
+///
+/// # Arguments
+///
+/// * `a` - Value to invert
+/// * `m` - Modulus
+///
+/// # Examples
+///
+/// ```
+/// use ctl::math::modular::mod_inverse;
+/// let x = mod_inverse(3, 7); // Some(5)
+/// let y = mod_inverse(4, 6); // None
+/// ```
+pub fn mod_inverse(a: u64, m: u64) -> Option<u64> {
+    assert!(m != 0, "modulus is zero");
+
+    let r = bezout((a % m) as i128, m as i128);
+    if r.gcd != 1 {
+        // Everything is congruent modulo 1, 0 is its own inverse there
+        return if m == 1 { Some(0) } else { None };
+    }
+
+    Some(r.x.rem_euclid(m as i128) as u64)
+}
+
+// -----------------------------------------------------------------------------
+// Chinese remainder theorem
+// -----------------------------------------------------------------------------
+
+/// Solves a system of congruences x = r (mod m) given as (r, m) pairs, where the moduli don't
+/// have to be coprime. Returns the solution as (x, M) with x in [0, M) and M the lcm of all
+/// moduli, so every solution is x + kM. An empty system gives (0, 1).
+///
+/// Returns `None` if the congruences contradict each other, a modulus isn't positive or the
+/// combined modulus doesn't fit into an `i64`.
+///
+/// # Arguments
+///
+/// * `residues` - Congruences as (residue, modulus) pairs, residues may be negative
+///
+/// # Examples
+///
+/// ```
+/// use ctl::math::modular::crt;
+/// let x = crt(&[(2, 3), (3, 5), (2, 7)]); // Some((23, 105))
+/// let y = crt(&[(3, 4), (5, 6)]); // Some((11, 12))
+/// let z = crt(&[(1, 4), (2, 6)]); // None
+/// ```
+pub fn crt(residues: &[(i64, i64)]) -> Option<(i64, i64)> {
+    let (mut x, mut m) = (0i128, 1i128);
+
+    for &(r, n) in residues {
+        if n <= 0 {
+            return None;
+        }
+        let (r, n) = (r as i128, n as i128);
+
+        // m p = g (mod n), so x + m k solves both for k = (r - x) / g p
+        let b = bezout(m, n);
+        let d = r - x;
+        if d % b.gcd != 0 {
+            return None;
+        }
+
+        let step = n / b.gcd;
+        let k = (d / b.gcd % step * (b.x % step)).rem_euclid(step);
+        x += m * k;
+        m *= step;
+
+        if m > i64::MAX as i128 {
+            return None;
+        }
+        x = x.rem_euclid(m);
+    }
+
+    Some((x as i64, m as i64))
+}
+
+// -----------------------------------------------------------------------------
+// Discrete logarithm
+// -----------------------------------------------------------------------------
+
+/// Finds the smallest x >= 0 with base^x ≡ target (mod m) by baby-step giant-step in
+/// O(sqrt(m)) time and memory. Common factors of the base and the modulus are divided out
+/// first, so any modulus works. 0^0 counts as 1.
+///
+/// # Arguments
+///
+/// * `base` - Raised value
+/// * `target` - Wanted power
+/// * `m` - Modulus
+///
+/// # Examples
+///
+/// ```
+/// use ctl::math::modular::discrete_log;
+/// let x = discrete_log(3, 13, 17); // Some(4), 3^4 = 81 = 4 * 17 + 13
+/// let y = discrete_log(2, 3, 8); // None
+/// let z = discrete_log(6, 0, 8); // Some(3)
+/// ```
+#[cfg(feature = "std")]
+pub fn discrete_log(base: u64, target: u64, mut m: u64) -> Option<u64> {
+    use crate::hash::FnvBuildHasher;
+    use std::collections::HashMap;
+
+    assert!(m != 0, "modulus is 0");
+    let a = base % m;
+    let mut b = target % m;
+
+    // Solve k a^x ≡ b while a shares factors with m, stepping x by one each time
+    let mut k = 1 % m;
+    let mut shift = 0;
+    loop {
+        let g = binary_gcd(a, m);
+        if g == 1 {
+            break;
+        }
+        if b == k {
+            return Some(shift);
+        }
+        if !b.is_multiple_of(g) {
+            return None;
+        }
+
+        b /= g;
+        m /= g;
+        shift += 1;
+        k = mod_mul(k, a / g, m);
+    }
+    if b == k % m {
+        return Some(shift);
+    }
+
+    // Baby steps b a^j, keeping the largest j for the smallest x = n i - j
+    let n = m.isqrt() + 1;
+    let mut baby = HashMap::with_capacity_and_hasher(n as usize, FnvBuildHasher::default());
+    let mut cur = b;
+    for j in 0..n {
+        baby.insert(cur, j);
+        cur = mod_mul(cur, a, m);
+    }
+
+    // Giant steps k a^(n i)
+    let giant = mod_pow(a, n, m);
+    let mut cur = k;
+    for i in 1..=n {
+        cur = mod_mul(cur, giant, m);
+        if let Some(&j) = baby.get(&cur) {
+            return Some(n * i - j + shift);
+        }
+    }
+
+    None
+}
+
+// -----------------------------------------------------------------------------
+// Constant time
+// -----------------------------------------------------------------------------
+
+// Secret operands never pick a branch, a memory location or a division here, only the modulus
+// may. Products go through Montgomery multiplication, which only needs multiplications, and
+// choices are masks. This keeps timings independent of the secrets as far as the compiler
+// and the hardware allow, which is fine for prototypes but no replacement for an audited
+// library.
+
+/// All ones if `bit` is 1, all zeros if it is 0.
+#[cfg(feature = "constant-time")]
+fn ct_mask(bit: u64) -> u64 {
+    core::hint::black_box(0u64.wrapping_sub(bit))
+}
+
+/// a if `mask` is all ones, b if it is all zeros.
+#[cfg(feature = "constant-time")]
+fn ct_select(mask: u64, a: u64, b: u64) -> u64 {
+    (a & mask) | (b & !mask)
+}
+
+/// x - m if x >= m, else x, for x < 2m with a possible carry out of the 64 bits.
+#[cfg(feature = "constant-time")]
+fn ct_reduce(x: u64, carry: u64, m: u64) -> u64 {
+    let (d, borrow) = x.overflowing_sub(m);
+    // Keep x only without a carry and with a borrow
+    ct_select(ct_mask(!carry & borrow as u64 & 1), x, d)
+}
+
+/// Montgomery context of an odd modulus m with R = 2^64.
+#[cfg(feature = "constant-time")]
+struct Montgomery {
+    m: u64,
+    /// -1 / m mod R
+    m_neg_inv: u64,
+    /// R² mod m
+    r2: u64,
+}
+
+#[cfg(feature = "constant-time")]
+impl Montgomery {
+    fn new(m: u64) -> Self {
+        assert!(m & 1 == 1, "constant time arithmetic needs an odd modulus");
+
+        // Newton's iteration doubles the correct low bits of the inverse each step
+        let mut inv = 1u64;
+        for _ in 0..6 {
+            inv = inv.wrapping_mul(2u64.wrapping_sub(m.wrapping_mul(inv)));
+        }
+        let r2 = ((1u128 << 64) % m as u128 * (1u128 << 64) % m as u128) as u64;
+
+        Montgomery {
+            m,
+            m_neg_inv: inv.wrapping_neg(),
+            r2,
+        }
+    }
+
+    /// t / R mod m for t < m R.
+    fn redc(&self, t: u128) -> u64 {
+        let u = (t as u64).wrapping_mul(self.m_neg_inv);
+        let (sum, carry) = t.overflowing_add(u as u128 * self.m as u128);
+        ct_reduce((sum >> 64) as u64, carry as u64, self.m)
+    }
+
+    fn mul(&self, a: u64, b: u64) -> u64 {
+        self.redc(a as u128 * b as u128)
+    }
+
+    /// Montgomery form a R mod m of any a, without dividing by m.
+    fn enter(&self, a: u64) -> u64 {
+        self.mul(a, self.r2)
+    }
+
+    /// a / R mod m, back out of the Montgomery form.
+    fn leave(&self, a: u64) -> u64 {
+        self.redc(a as u128)
+    }
+
+    /// base^exp by a Montgomery ladder doing the same work for each of the 64 bits.
+    fn pow(&self, base: u64, exp: u64) -> u64 {
+        let (mut r0, mut r1) = (self.enter(1), self.enter(base));
+
+        for i in (0..64).rev() {
+            // Swap, step with r0 ≤ r1, swap back
+            let mask = ct_mask(exp >> i & 1);
+            let (a, b) = (ct_select(mask, r1, r0), ct_select(mask, r0, r1));
+            let (a, b) = (self.mul(a, a), self.mul(a, b));
+            r0 = ct_select(mask, b, a);
+            r1 = ct_select(mask, a, b);
+        }
+
+        self.leave(r0)
+    }
+}
+
+/// Calculates base^exp mod m like [`mod_pow`], but in constant time regardless of the base and
+/// the exponent. The modulus must be odd and is treated as public.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "constant-time")] {
+/// use ctl::math::modular::ct_mod_pow;
+/// let x = ct_mod_pow(3, 200, 1_000_000_007); // 136318165
+/// # }
+/// ```
+#[cfg(feature = "constant-time")]
+pub fn ct_mod_pow(base: u64, exp: u64, m: u64) -> u64 {
+    Montgomery::new(m).pow(base, exp)
+}
+
+/// Calculates the inverse of a modulo the odd prime p in constant time, as a^(p - 2) by
+/// Fermat's little theorem. Gives 0 for a multiple of p instead of failing, to not branch on
+/// it. The result is only meaningful for a prime p, which isn't checked.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "constant-time")] {
+/// use ctl::math::modular::ct_mod_inverse;
+/// let x = ct_mod_inverse(3, 7); // 5
+/// # }
+/// ```
+#[cfg(feature = "constant-time")]
+pub fn ct_mod_inverse(a: u64, p: u64) -> u64 {
+    Montgomery::new(p).pow(a, p.wrapping_sub(2))
+}
+
+/// Compares 2 values without branching on where they differ.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "constant-time")] {
+/// use ctl::math::modular::ct_eq;
+/// let x = ct_eq(5, 5); // true
+/// # }
+/// ```
+#[cfg(feature = "constant-time")]
+pub fn ct_eq(a: u64, b: u64) -> bool {
+    let x = a ^ b;
+    // The top bit of x | -x is set for any nonzero x
+    core::hint::black_box(((x | x.wrapping_neg()) >> 63) ^ 1) == 1
+}
+
+// -----------------------------------------------------------------------------
+// ModInt
+// -----------------------------------------------------------------------------
+
+/// Element of the ring of integers modulo `M`, always kept reduced into [0, M). Arithmetic
+/// wraps around automatically and division multiplies by the modular inverse, so for a prime
+/// `M` it is a field.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::math::modular::ModInt;
+/// type Mint = ModInt<1_000_000_007>;
+/// let a = Mint::from(500_000_004); // 1/2
+/// let b = a * Mint::from(6) - Mint::from(4); // 1_000_000_006
+/// let c = Mint::from(1) / Mint::from(2); // 500_000_004
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct ModInt<const M: u64> {
+    value: u64,
+}
+
+impl<const M: u64> ModInt<M> {
+    /// Reduces the value modulo `M`.
+    pub fn new(value: u64) -> Self {
+        ModInt { value: value % M }
+    }
+
+    /// Representative in [0, M).
+    pub fn value(self) -> u64 {
+        self.value
+    }
+
+    /// Raises to the power `exp`, see [`mod_pow`].
+    pub fn pow(self, exp: u64) -> Self {
+        ModInt {
+            value: mod_pow(self.value, exp, M),
+        }
+    }
+
+    /// Multiplicative inverse, `None` if the value isn't coprime to `M`.
+    pub fn inverse(self) -> Option<Self> {
+        mod_inverse(self.value, M).map(|value| ModInt { value })
+    }
+}
+
+/// Constant time variants of the operations depending on the value, for an odd modulus `M`.
+#[cfg(feature = "constant-time")]
+impl<const M: u64> ModInt<M> {
+    /// Raises to the power `exp` in constant time, see [`ct_mod_pow`].
+    pub fn ct_pow(self, exp: u64) -> Self {
+        ModInt {
+            value: ct_mod_pow(self.value, exp, M),
+        }
+    }
+
+    /// Inverse in constant time for a prime `M`, see [`ct_mod_inverse`]. Zero maps to zero.
+    pub fn ct_inverse(self) -> Self {
+        ModInt {
+            value: ct_mod_inverse(self.value, M),
+        }
+    }
+
+    /// Equality without branching on the values, see [`ct_eq`].
+    pub fn ct_eq(self, o: Self) -> bool {
+        ct_eq(self.value, o.value)
+    }
+}
+
+impl<const M: u64> From<u64> for ModInt<M> {
+    fn from(value: u64) -> Self {
+        ModInt::new(value)
+    }
+}
+
+impl<const M: u64> fmt::Display for ModInt<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.value.fmt(f)
+    }
+}
+
+impl<const M: u64> Add for ModInt<M> {
+    type Output = Self;
+
+    fn add(self, o: Self) -> Self {
+        ModInt {
+            value: mod_add(self.value, o.value, M),
+        }
+    }
+}
+
+impl<const M: u64> Sub for ModInt<M> {
+    type Output = Self;
+
+    fn sub(self, o: Self) -> Self {
+        ModInt {
+            value: mod_sub(self.value, o.value, M),
+        }
+    }
+}
+
+impl<const M: u64> Mul for ModInt<M> {
+    type Output = Self;
+
+    fn mul(self, o: Self) -> Self {
+        ModInt {
+            value: mod_mul(self.value, o.value, M),
+        }
+    }
+}
+
+impl<const M: u64> Div for ModInt<M> {
+    type Output = Self;
+
+    /// Multiplies by the inverse. Panics if `o` has none, e.g. for 0.
+    fn div(self, o: Self) -> Self {
+        let inv = mod_inverse(o.value, M).expect("divisor is not invertible");
+        ModInt {
+            value: mod_mul(self.value, inv, M),
+        }
+    }
+}
+
+impl<const M: u64> Neg for ModInt<M> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        ModInt {
+            value: mod_sub(0, self.value, M),
+        }
+    }
+}
+
+impl<const M: u64> AddAssign for ModInt<M> {
+    fn add_assign(&mut self, o: Self) {
+        *self = *self + o;
+    }
+}
+
+impl<const M: u64> SubAssign for ModInt<M> {
+    fn sub_assign(&mut self, o: Self) {
+        *self = *self - o;
+    }
+}
+
+impl<const M: u64> MulAssign for ModInt<M> {
+    fn mul_assign(&mut self, o: Self) {
+        *self = *self * o;
+    }
+}
+
+impl<const M: u64> DivAssign for ModInt<M> {
+    fn div_assign(&mut self, o: Self) {
+        *self = *self / o;
+    }
+}
+
+impl<const M: u64> Sum for ModInt<M> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(ModInt::new(0), |a, b| a + b)
+    }
+}
+
+impl<const M: u64> Product for ModInt<M> {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(ModInt::new(1), |a, b| a * b)
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arithmetic() {
+        let m = u64::MAX - 58; // Largest 64 bit prime
+        assert_eq!(mod_add(m - 1, m - 1, m), m - 2);
+        assert_eq!(mod_add(u64::MAX, 1, m), 59);
+        assert_eq!(mod_sub(0, 1, m), m - 1);
+        assert_eq!(mod_sub(3, u64::MAX, m), m - 55);
+        assert_eq!(mod_sub(9, 4, 5), 0);
+        assert_eq!(mod_mul(m - 1, m - 1, m), 1);
+        assert_eq!(mod_mul(1 << 63, 4, 1_000_000_007), 164_688_009);
+        assert_eq!(mod_add(3, 4, 1), 0);
+    }
+
+    #[test]
+    fn test_pow() {
+        let m = u64::MAX - 58;
+        assert_eq!(mod_pow(3, 200, 1_000_000_007), 136_318_165);
+        assert_eq!(mod_pow(2, u64::MAX, u64::MAX), 1 << 63);
+        assert_eq!(mod_pow(0, 0, 7), 1);
+        assert_eq!(mod_pow(0, 5, 7), 0);
+        assert_eq!(mod_pow(5, 0, 1), 0);
+
+        // Fermat: a^(p - 1) = 1 and a^(p - 2) is the inverse
+        for a in [2u64, 3, 1 << 40, m - 1] {
+            assert_eq!(mod_pow(a, m - 1, m), 1);
+            assert_eq!(mod_pow(a, m - 2, m), mod_inverse(a, m).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_inverse() {
+        let m = u64::MAX - 58;
+        for a in [1u64, 2, 12_345, 1 << 63, m - 1] {
+            let x = mod_inverse(a, m).unwrap();
+            assert!(x < m);
+            assert_eq!(mod_mul(a, x, m), 1);
+        }
+
+        assert_eq!(mod_inverse(3, 7), Some(5));
+        assert_eq!(mod_inverse(10, 7), Some(5));
+        assert_eq!(mod_inverse(4, 6), None);
+        assert_eq!(mod_inverse(0, 7), None);
+        assert_eq!(mod_inverse(5, 1), Some(0));
+        assert_eq!(mod_inverse(u64::MAX, 1 << 63), Some((1 << 63) - 1));
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt(&[(3, 4), (5, 6)]), Some((11, 12)));
+        assert_eq!(crt(&[(1, 4), (2, 6)]), None);
+        assert_eq!(crt(&[(-1, 6), (8, 9), (11, 12)]), Some((35, 36)));
+        assert_eq!(crt(&[]), Some((0, 1)));
+        assert_eq!(crt(&[(5, 1), (3, 3)]), Some((0, 3)));
+        assert_eq!(crt(&[(1, 0)]), None);
+
+        // Large coprime moduli whose product just fits, then overflows
+        let (p, q) = (3_037_000_493i64, 3_037_000_453i64);
+        let (x, m) = crt(&[(123, p), (456, q)]).unwrap();
+        assert_eq!(m, p * q);
+        assert_eq!((x % p, x % q), (123, 456));
+        assert_eq!(crt(&[(0, p), (0, q), (1, 2)]), None);
+    }
+
+    #[test]
+    fn test_mod_int() {
+        type Mint = ModInt<1_000_000_007>;
+
+        let a = Mint::from(500_000_004);
+        assert_eq!(a * Mint::from(2), Mint::from(1));
+        assert_eq!(Mint::from(1) / Mint::from(2), a);
+        assert_eq!((a * Mint::from(6) - Mint::from(4)).value(), 1_000_000_006);
+        assert_eq!(-Mint::from(0), Mint::from(0));
+        assert_eq!(-Mint::from(1), Mint::from(1_000_000_006));
+        assert_eq!(Mint::from(3).pow(200).value(), 136_318_165);
+        assert_eq!(Mint::from(u64::MAX).value(), u64::MAX % 1_000_000_007);
+
+        // 10! and the sum 1 + ... + 10
+        let f: Mint = (1..=10).map(Mint::from).product();
+        let s: Mint = (1..=10).map(Mint::from).sum();
+        assert_eq!((f.value(), s.value()), (3_628_800, 55));
+
+        let mut x = Mint::from(7);
+        x += Mint::from(1_000_000_000);
+        x *= Mint::from(2);
+        x -= Mint::from(1);
+        x /= Mint::from(3);
+        assert_eq!(x * Mint::from(3), -Mint::from(1));
+
+        // Not a field, 2 has no inverse modulo 6
+        assert_eq!(ModInt::<6>::from(2).inverse(), None);
+        assert_eq!(ModInt::<6>::from(5).inverse(), Some(ModInt::from(5)));
+        assert_eq!(
+            format!("{}", ModInt::<{ u64::MAX }>::from(u64::MAX - 1)),
+            "18446744073709551614"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_discrete_log() {
+        // Against the first repeat of the powers, for every small modulus
+        for m in 1..=60u64 {
+            for a in 0..m {
+                for b in 0..m {
+                    let mut brute = None;
+                    let mut p = 1 % m;
+                    for x in 0..=m {
+                        if p == b {
+                            brute = Some(x);
+                            break;
+                        }
+                        p = p * a % m;
+                    }
+                    assert_eq!(discrete_log(a, b, m), brute, "{}^x = {} mod {}", a, b, m);
+                }
+            }
+        }
+
+        let m = 1_000_000_007;
+        let x = discrete_log(5, 123_456_789, m).unwrap();
+        assert_eq!(mod_pow(5, x, m), 123_456_789);
+        assert_eq!(discrete_log(5, mod_pow(5, 987_654, m), m), Some(987_654));
+        assert_eq!(discrete_log(2, 1 << 40, 1 << 50), Some(40));
+        assert_eq!(discrete_log(2, 3, 1 << 50), None);
+    }
+
+    #[cfg(feature = "constant-time")]
+    #[test]
+    fn test_constant_time() {
+        let mut rng = crate::random::Rng::new(12);
+        for m in [
+            1u64,
+            3,
+            1_000_000_007,
+            u64::MAX,
+            u64::MAX - 58,
+            (1 << 63) + 1,
+        ] {
+            for _ in 0..200 {
+                let (a, e) = (rng.next_u64(), rng.next_u64() >> (rng.next_u64() % 64));
+                assert_eq!(
+                    ct_mod_pow(a, e, m),
+                    mod_pow(a, e, m),
+                    "{}^{} mod {}",
+                    a,
+                    e,
+                    m
+                );
+            }
+            assert_eq!(ct_mod_pow(0, 0, m), 1 % m);
+        }
+
+        let p = u64::MAX - 58;
+        for a in [1u64, 2, 12345, p - 1, u64::MAX] {
+            assert_eq!(ct_mod_inverse(a, p), mod_inverse(a, p).unwrap());
+        }
+        assert_eq!(ct_mod_inverse(0, p), 0);
+        assert_eq!(ct_mod_inverse(p, p), 0);
+
+        assert!(ct_eq(u64::MAX, u64::MAX) && ct_eq(0, 0));
+        assert!(!ct_eq(0, 1 << 63) && !ct_eq(1, 0));
+
+        type Mint = ModInt<1_000_000_007>;
+        let a = Mint::new(123_456_789);
+        assert_eq!(a.ct_pow(1 << 40), a.pow(1 << 40));
+        assert_eq!(a.ct_inverse(), a.inverse().unwrap());
+        assert!(a.ct_eq(Mint::new(123_456_789)) && !a.ct_eq(Mint::new(0)));
+    }
+}
@@ -0,0 +1,1011 @@
+use super::bigint::BigInt;
+use super::{frac, primes, Fraction};
+use crate::{Error, Result};
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::convert::TryFrom;
+use core::ops::{Add, Div, Mul, Neg, Rem, Sub};
+
+// -----------------------------------------------------------------------------
+// Coefficient
+// -----------------------------------------------------------------------------
+
+/// Value type of polynomial coefficients with ring arithmetic and small integer constants,
+/// implemented for `f64`, `i32`, `i64`, `i128` and [`Fraction`].
+pub trait Coefficient:
+    Copy + PartialEq + From<i32> + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self>
+{
+    /// Canonical form of the value, applied to every computed coefficient. Reduces fractions
+    /// so their parts don't grow with each operation.
+    fn normalize(self) -> Self {
+        self
+    }
+}
+
+macro_rules! gen_coefficient {
+    ($($T:ty),*) => {
+        $(impl Coefficient for $T {})*
+    };
+}
+
+gen_coefficient!(f64, i32, i64, i128);
+
+impl Coefficient for Fraction {
+    fn normalize(self) -> Self {
+        let f = self.reduce();
+        if f.d < 0 {
+            frac(-f.q, -f.d)
+        } else {
+            f
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Polynomial
+// -----------------------------------------------------------------------------
+
+/// Polynomial c0 + c1 x + c2 x² + ... stored densely, lowest coefficient first, without
+/// trailing zeros so the zero polynomial has no coefficients.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::math::Polynomial;
+/// let p = Polynomial::new(vec![1, -3, 2]); // 2x² - 3x + 1
+/// let q = Polynomial::new(vec![0, 1]); // x
+/// let r = &p * &q; // 2x³ - 3x² + x
+/// let y = r.eval(3); // 30
+/// let d = r.derivative(); // 6x² - 6x + 1
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Polynomial<T> {
+    coeffs: Vec<T>,
+}
+
+impl<T: Coefficient> Polynomial<T> {
+    /// Polynomial of the coefficients c0, c1, ..., dropping trailing zeros.
+    pub fn new(mut coeffs: Vec<T>) -> Self {
+        coeffs.iter_mut().for_each(|c| *c = c.normalize());
+        while coeffs.last() == Some(&T::from(0)) {
+            coeffs.pop();
+        }
+        Polynomial { coeffs }
+    }
+
+    pub fn zero() -> Self {
+        Polynomial { coeffs: Vec::new() }
+    }
+
+    /// Polynomial of degree 0, or the zero polynomial for c = 0.
+    pub fn constant(c: T) -> Self {
+        Polynomial::new(vec![c])
+    }
+
+    /// Coefficients, lowest first.
+    pub fn coeffs(&self) -> &[T] {
+        &self.coeffs
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.coeffs.is_empty()
+    }
+
+    /// Highest power with a nonzero coefficient, `None` for the zero polynomial.
+    pub fn degree(&self) -> Option<usize> {
+        self.coeffs.len().checked_sub(1)
+    }
+
+    /// Coefficient of the highest power, `None` for the zero polynomial.
+    pub fn leading_coefficient(&self) -> Option<T> {
+        self.coeffs.last().copied()
+    }
+
+    /// Evaluates by Horner's scheme in O(degree).
+    pub fn eval(&self, x: T) -> T {
+        self.coeffs
+            .iter()
+            .rev()
+            .fold(T::from(0), |acc, &c| acc * x + c)
+    }
+
+    /// Derivative Σ k ck x^(k - 1).
+    pub fn derivative(&self) -> Self {
+        let coeffs = self
+            .coeffs
+            .iter()
+            .enumerate()
+            .skip(1)
+            .map(|(k, &c)| c * T::from(k as i32))
+            .collect();
+        Polynomial::new(coeffs)
+    }
+}
+
+impl<T: Coefficient + Div<Output = T>> Polynomial<T> {
+    /// Antiderivative Σ ck / (k + 1) x^(k + 1) with a constant term of 0. Exact over `f64` and
+    /// [`Fraction`](super::Fraction), over integers the divisions truncate.
+    pub fn antiderivative(&self) -> Self {
+        let mut coeffs = vec![T::from(0)];
+        coeffs.extend(
+            self.coeffs
+                .iter()
+                .enumerate()
+                .map(|(k, &c)| c / T::from(k as i32 + 1)),
+        );
+        Polynomial::new(coeffs)
+    }
+}
+
+/// Coefficient wise combination, padding the shorter with zeros.
+fn zip_with<T: Coefficient, F: Fn(T, T) -> T>(a: &[T], b: &[T], f: F) -> Vec<T> {
+    let zero = T::from(0);
+    (0..a.len().max(b.len()))
+        .map(|i| {
+            f(
+                a.get(i).copied().unwrap_or(zero),
+                b.get(i).copied().unwrap_or(zero),
+            )
+        })
+        .collect()
+}
+
+impl<T: Coefficient> Add for &Polynomial<T> {
+    type Output = Polynomial<T>;
+
+    fn add(self, o: &Polynomial<T>) -> Polynomial<T> {
+        Polynomial::new(zip_with(&self.coeffs, &o.coeffs, |a, b| a + b))
+    }
+}
+
+impl<T: Coefficient> Sub for &Polynomial<T> {
+    type Output = Polynomial<T>;
+
+    fn sub(self, o: &Polynomial<T>) -> Polynomial<T> {
+        Polynomial::new(zip_with(&self.coeffs, &o.coeffs, |a, b| a - b))
+    }
+}
+
+impl<T: Coefficient> Mul for &Polynomial<T> {
+    type Output = Polynomial<T>;
+
+    fn mul(self, o: &Polynomial<T>) -> Polynomial<T> {
+        if self.is_zero() || o.is_zero() {
+            return Polynomial::zero();
+        }
+
+        let mut coeffs = vec![T::from(0); self.coeffs.len() + o.coeffs.len() - 1];
+        for (i, &a) in self.coeffs.iter().enumerate() {
+            for (j, &b) in o.coeffs.iter().enumerate() {
+                coeffs[i + j] = coeffs[i + j] + a * b;
+            }
+        }
+        Polynomial::new(coeffs)
+    }
+}
+
+impl<T: Coefficient> Mul<T> for &Polynomial<T> {
+    type Output = Polynomial<T>;
+
+    /// Scales every coefficient.
+    fn mul(self, c: T) -> Polynomial<T> {
+        Polynomial::new(self.coeffs.iter().map(|&a| a * c).collect())
+    }
+}
+
+impl<T: Coefficient> Neg for &Polynomial<T> {
+    type Output = Polynomial<T>;
+
+    fn neg(self) -> Polynomial<T> {
+        Polynomial::new(self.coeffs.iter().map(|&a| T::from(0) - a).collect())
+    }
+}
+
+// Owned operands forward to the reference versions
+macro_rules! gen_ops {
+    ($($Op:ident $op:ident),*) => {
+        $(
+            impl<T: Coefficient> $Op for Polynomial<T> {
+                type Output = Polynomial<T>;
+
+                fn $op(self, o: Polynomial<T>) -> Polynomial<T> {
+                    (&self).$op(&o)
+                }
+            }
+        )*
+    };
+}
+
+gen_ops!(Add add, Sub sub, Mul mul);
+
+impl<T: Coefficient> Mul<T> for Polynomial<T> {
+    type Output = Polynomial<T>;
+
+    fn mul(self, c: T) -> Polynomial<T> {
+        &self * c
+    }
+}
+
+impl<T: Coefficient> Neg for Polynomial<T> {
+    type Output = Polynomial<T>;
+
+    fn neg(self) -> Polynomial<T> {
+        -&self
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Division
+// -----------------------------------------------------------------------------
+
+impl<T: Coefficient + Div<Output = T>> Polynomial<T> {
+    /// Quotient and remainder of the Euclidean division self = q d + r with deg r < deg d, by
+    /// long division in O(deg self deg d). Exact over [`Fraction`], over `f64` subject to
+    /// rounding, and meant for fields: over integers the coefficient divisions truncate. Panics
+    /// for d = 0, use [`Polynomial::try_div_rem`] to handle it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ctl::math::{frac, Polynomial};
+    /// let p = Polynomial::new(vec![frac(-1, 1), frac(0, 1), frac(2, 1)]); // 2x² - 1
+    /// let d = Polynomial::new(vec![frac(1, 1), frac(1, 1)]); // x + 1
+    /// let (q, r) = p.div_rem(&d); // 2x - 2 and 1
+    /// ```
+    pub fn div_rem(&self, d: &Polynomial<T>) -> (Polynomial<T>, Polynomial<T>) {
+        self.try_div_rem(d)
+            .expect("division by the zero polynomial")
+    }
+
+    /// Fallible version of [`Polynomial::div_rem`]. Fails with [`Error::DivisionByZero`] for
+    /// d = 0.
+    pub fn try_div_rem(&self, d: &Polynomial<T>) -> Result<(Polynomial<T>, Polynomial<T>)> {
+        let lead = d.leading_coefficient().ok_or(Error::DivisionByZero)?;
+        let m = d.coeffs.len() - 1;
+        if self.coeffs.len() <= m {
+            return Ok((Polynomial::zero(), self.clone()));
+        }
+
+        let mut r = self.coeffs.clone();
+        let mut q = vec![T::from(0); r.len() - m];
+        for i in (0..q.len()).rev() {
+            let c = (r[i + m] / lead).normalize();
+            q[i] = c;
+            for (j, &b) in d.coeffs.iter().enumerate() {
+                r[i + j] = (r[i + j] - c * b).normalize();
+            }
+        }
+
+        // The leading terms cancel by construction, also where rounding leaves a residue
+        r.truncate(m);
+        Ok((Polynomial::new(q), Polynomial::new(r)))
+    }
+
+    /// Divides by the leading coefficient, leaving the zero polynomial as is.
+    pub fn monic(&self) -> Polynomial<T> {
+        match self.leading_coefficient() {
+            Some(lead) => Polynomial::new(self.coeffs.iter().map(|&c| c / lead).collect()),
+            None => Polynomial::zero(),
+        }
+    }
+
+    /// Monic greatest common divisor by the Euclidean algorithm, the zero polynomial for
+    /// gcd(0, 0). Meant for exact coefficients like [`Fraction`], over `f64` rounding keeps
+    /// remainders from vanishing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ctl::math::{frac, Polynomial};
+    /// let f = |c: &[i32]| Polynomial::new(c.iter().map(|&x| frac(x, 1)).collect());
+    /// let a = f(&[-1, 0, 1]); // x² - 1
+    /// let b = f(&[1, 2, 1]); // x² + 2x + 1
+    /// let g = a.gcd(&b); // x + 1
+    /// ```
+    pub fn gcd(&self, o: &Polynomial<T>) -> Polynomial<T> {
+        // Monic remainders keep fraction coefficients from growing
+        let (mut a, mut b) = (self.monic(), o.monic());
+        while !b.is_zero() {
+            let r = a.div_rem(&b).1.monic();
+            a = b;
+            b = r;
+        }
+        a
+    }
+}
+
+impl<T: Coefficient + Div<Output = T>> Div for &Polynomial<T> {
+    type Output = Polynomial<T>;
+
+    /// Quotient of [`Polynomial::div_rem`].
+    fn div(self, d: &Polynomial<T>) -> Polynomial<T> {
+        self.div_rem(d).0
+    }
+}
+
+impl<T: Coefficient + Div<Output = T>> Rem for &Polynomial<T> {
+    type Output = Polynomial<T>;
+
+    /// Remainder of [`Polynomial::div_rem`].
+    fn rem(self, d: &Polynomial<T>) -> Polynomial<T> {
+        self.div_rem(d).1
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Rational roots
+// -----------------------------------------------------------------------------
+
+impl Polynomial<Fraction> {
+    /// All rational roots, each once and in ascending order. By the rational root theorem, after
+    /// scaling to integer coefficients a_i, every root in lowest terms p / q has p dividing
+    /// the lowest nonzero a_i and q dividing the leading one, so testing those candidates
+    /// exactly finds them all. The divisors come from [`primes::divisors`], which factorizes
+    /// with Pollard's rho and stays fast for large coefficients.
+    ///
+    /// Fails with [`Error::Domain`] for the zero polynomial, which every number is a root of,
+    /// with [`Error::DivisionByZero`] for a coefficient with a zero denominator and with
+    /// [`Error::Overflow`] if the integer coefficients don't fit into `i128`, the lowest or
+    /// leading one doesn't fit into `u64` or a root doesn't fit into a [`Fraction`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ctl::math::{frac, Polynomial};
+    /// // (2x - 1)(x + 3)(x² + 1) = 2x⁴ + 5x³ - x² + 5x - 3
+    /// let p = Polynomial::new([-3, 5, -1, 5, 2].iter().map(|&c| frac(c, 1)).collect());
+    /// let r = p.rational_roots(); // Ok([-3, 1/2])
+    /// ```
+    pub fn rational_roots(&self) -> Result<Vec<Fraction>> {
+        if self.is_zero() {
+            return Err(Error::Domain);
+        }
+        if self.coeffs.iter().any(|c| c.d == 0) {
+            return Err(Error::DivisionByZero);
+        }
+
+        let ints = self.integer_coefficients()?;
+
+        // x = 0 is a root of the powers of x factored out
+        let low = ints.iter().position(|&a| a != 0).unwrap();
+        let ints = &ints[low..];
+        let mut roots = Vec::new();
+        if low > 0 {
+            roots.push(frac(0, 1));
+        }
+
+        let magnitude = |a: i128| u64::try_from(a.unsigned_abs()).map_err(|_| Error::Overflow);
+        let lead = magnitude(ints[ints.len() - 1])?;
+        let constant: Vec<u64> = primes::divisors(magnitude(ints[0])?).collect();
+        for q in primes::divisors(lead) {
+            for &p in &constant {
+                if super::gcd(p, q) != 1 {
+                    continue;
+                }
+                let (p, q) = (p as i128, q as i128);
+                for p in [p, -p] {
+                    if is_root(ints, p, q) {
+                        let root = frac(
+                            i32::try_from(p).map_err(|_| Error::Overflow)?,
+                            i32::try_from(q).map_err(|_| Error::Overflow)?,
+                        );
+                        roots.push(root);
+                    }
+                }
+            }
+        }
+
+        roots.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+        Ok(roots)
+    }
+
+    /// Coefficients scaled by the lcm of the denominators to integers.
+    fn integer_coefficients(&self) -> Result<Vec<i128>> {
+        let l = self
+            .coeffs
+            .iter()
+            .try_fold(1i128, |l, c| {
+                let d = (c.d as i128).abs();
+                (l / super::gcd(l, d)).checked_mul(d)
+            })
+            .ok_or(Error::Overflow)?;
+        self.coeffs
+            .iter()
+            .map(|c| (c.q as i128).checked_mul(l / c.d as i128))
+            .collect::<Option<Vec<i128>>>()
+            .ok_or(Error::Overflow)
+    }
+}
+
+/// Checks Σ a_i p^i q^(n - i) = 0, the polynomial at p / q times q^n. Falls back to [`BigInt`]
+/// when the terms don't fit into `i128`.
+fn is_root(a: &[i128], p: i128, q: i128) -> bool {
+    // Horner's scheme with the powers of q folded in
+    let small = a
+        .iter()
+        .rev()
+        .enumerate()
+        .try_fold((0i128, 1i128), |(acc, q_pow), (i, &c)| {
+            let q_pow = if i > 0 { q_pow.checked_mul(q)? } else { q_pow };
+            Some((
+                acc.checked_mul(p)?.checked_add(c.checked_mul(q_pow)?)?,
+                q_pow,
+            ))
+        });
+    if let Some((acc, _)) = small {
+        return acc == 0;
+    }
+
+    let (p, q) = (BigInt::from(p), BigInt::from(q));
+    let mut acc = BigInt::zero();
+    let mut q_pow = BigInt::one();
+    for (i, &c) in a.iter().rev().enumerate() {
+        if i > 0 {
+            q_pow = &q_pow * &q;
+        }
+        acc = &(&acc * &p) + &(&BigInt::from(c) * &q_pow);
+    }
+    acc.is_zero()
+}
+
+// -----------------------------------------------------------------------------
+// Factorization
+// -----------------------------------------------------------------------------
+
+impl<T: Coefficient + Div<Output = T>> Polynomial<T> {
+    /// Square-free decomposition by Yun's algorithm: pairwise coprime, square-free and monic
+    /// s1, s2, ... with self = c s1 s2² s3³ ... for the leading coefficient c, as the pairs
+    /// (s_i, i) of the nonconstant s_i. Empty for constants and the zero polynomial. Meant for
+    /// exact coefficients of characteristic 0 like [`Fraction`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ctl::math::{frac, Polynomial};
+    /// let f = |c: &[i32]| Polynomial::new(c.iter().map(|&x| frac(x, 1)).collect());
+    /// let p = &f(&[-1, 1]) * &(&f(&[2, 1]) * &f(&[2, 1])); // (x - 1)(x + 2)²
+    /// let s = p.square_free(); // [(x - 1, 1), (x + 2, 2)]
+    /// ```
+    pub fn square_free(&self) -> Vec<(Polynomial<T>, usize)> {
+        if self.coeffs.len() < 2 {
+            return Vec::new();
+        }
+
+        let d = self.derivative();
+        let a = self.gcd(&d);
+        let (mut b, mut c) = (self / &a, &d / &a);
+        let mut factors = Vec::new();
+        for i in 1.. {
+            if b.coeffs.len() < 2 {
+                break;
+            }
+            let d = &c - &b.derivative();
+            let a = b.gcd(&d);
+            if a.coeffs.len() > 1 {
+                factors.push((a.clone(), i));
+            }
+            b = &b / &a;
+            c = &d / &a;
+        }
+        factors
+    }
+}
+
+impl Polynomial<Fraction> {
+    /// Factorization into irreducible polynomials over the rationals, as the leading coefficient
+    /// and the monic factors with their multiplicities, ordered by degree. Splits off linear
+    /// factors with [`Polynomial::rational_roots`], then searches the rest for factors of degree
+    /// 2, 3, ... with Kronecker's method: a factor of degree d is fixed by its values at d + 1
+    /// integers, which have to divide the polynomial's values there, so interpolating every
+    /// combination of divisors and dividing finds it. The number of combinations grows
+    /// exponentially with the degree, so this is meant for small degrees and coefficients. The
+    /// divisions are exact over `i128`, so the coefficients stay small.
+    ///
+    /// Fails like [`Polynomial::rational_roots`], also with [`Error::Overflow`] if a factor's
+    /// coefficients don't fit into a [`Fraction`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ctl::math::{frac, Polynomial};
+    /// let f = |c: &[i32]| Polynomial::new(c.iter().map(|&x| frac(x, 1)).collect());
+    /// // 2(x - 1)(x² + x + 1)²
+    /// let p = f(&[-2, 2]) * f(&[1, 1, 1]) * f(&[1, 1, 1]);
+    /// let (c, factors) = p.factor().unwrap(); // 2 and [(x - 1, 1), (x² + x + 1, 2)]
+    /// ```
+    #[allow(clippy::type_complexity)]
+    pub fn factor(&self) -> Result<(Fraction, Vec<(Polynomial<Fraction>, usize)>)> {
+        let lead = self.leading_coefficient().ok_or(Error::Domain)?;
+        if self.coeffs.iter().any(|c| c.d == 0) {
+            return Err(Error::DivisionByZero);
+        }
+        let mut ints = self.integer_coefficients()?;
+
+        // Each root p / q as often as q x - p divides
+        let mut found = Vec::new();
+        for r in self.rational_roots()? {
+            let linear = [-(r.q as i128), r.d as i128];
+            while let Some(q) = exact_division(&ints, &linear) {
+                found.push(linear.to_vec());
+                ints = q;
+            }
+        }
+        found.extend(kronecker(ints)?);
+
+        let mut factors: Vec<(Polynomial<Fraction>, usize)> = Vec::new();
+        for g in found {
+            let coeffs = g
+                .into_iter()
+                .map(|c| i32::try_from(c).map(Fraction::from))
+                .collect::<core::result::Result<Vec<_>, _>>()
+                .map_err(|_| Error::Overflow)?;
+            let g = Polynomial::new(coeffs).monic();
+            match factors.iter_mut().find(|(h, _)| *h == g) {
+                Some((_, i)) => *i += 1,
+                None => factors.push((g, 1)),
+            }
+        }
+        factors.sort_by_key(|(g, _)| g.coeffs.len());
+        Ok((lead, factors))
+    }
+}
+
+/// Irreducible factors of an integer polynomial without rational roots, repeated ones as often
+/// as they divide, by Kronecker's method with factors of increasing degree so each one found is
+/// irreducible.
+fn kronecker(mut f: Vec<i128>) -> Result<Vec<Vec<i128>>> {
+    let content = super::gcd_iter(f.iter().copied());
+    f.iter_mut().for_each(|c| *c /= content);
+
+    let mut factors = Vec::new();
+    let mut d = 2;
+    while 2 * d < f.len() {
+        match kronecker_factor(&f, d)? {
+            Some((g, q)) => {
+                factors.push(g);
+                f = q;
+            }
+            None => d += 1,
+        }
+    }
+    if f.len() > 1 {
+        factors.push(f);
+    }
+    Ok(factors)
+}
+
+/// A factor of degree d and the cofactor, trying every polynomial through the divisors of the
+/// values at the nodes 0, 1, -1, 2, -2, ..., which are nonzero without rational roots.
+fn kronecker_factor(f: &[i128], d: usize) -> Result<Option<(Vec<i128>, Vec<i128>)>> {
+    let nodes: Vec<i128> = (0..=d as i128)
+        .map(|i| if i % 2 == 1 { (i + 1) / 2 } else { -i / 2 })
+        .collect();
+    let mut divisors = Vec::new();
+    for (i, &x) in nodes.iter().enumerate() {
+        let v = f
+            .iter()
+            .rev()
+            .try_fold(0i128, |acc, &c| acc.checked_mul(x)?.checked_add(c))
+            .ok_or(Error::Overflow)?;
+        let v = u64::try_from(v.unsigned_abs()).map_err(|_| Error::Overflow)?;
+        let mut ds: Vec<i128> = primes::divisors(v).map(|u| u as i128).collect();
+        // Fixing the sign of the first value skips the negated candidates
+        if i > 0 {
+            ds.extend(ds.clone().into_iter().map(|u| -u));
+        }
+        divisors.push(ds);
+    }
+
+    // Odometer over the combinations of divisors
+    let mut pick = vec![0; d + 1];
+    loop {
+        let values: Vec<i128> = pick.iter().zip(&divisors).map(|(&j, ds)| ds[j]).collect();
+        if let Some(g) = integer_interpolation(&nodes, values) {
+            if g.len() == d + 1 {
+                if let Some(q) = exact_division(f, &g) {
+                    return Ok(Some((g, q)));
+                }
+            }
+        }
+
+        let mut i = 0;
+        while i <= d && pick[i] + 1 == divisors[i].len() {
+            pick[i] = 0;
+            i += 1;
+        }
+        if i > d {
+            return Ok(None);
+        }
+        pick[i] += 1;
+    }
+}
+
+/// Coefficients of the polynomial through (x_i, y_i), `None` if they aren't all integers.
+/// The divided differences of an integer polynomial at integer nodes are integers, so
+/// dividing exactly decides it.
+fn integer_interpolation(x: &[i128], mut c: Vec<i128>) -> Option<Vec<i128>> {
+    for j in 1..c.len() {
+        for i in (j..c.len()).rev() {
+            let (dy, dx) = (c[i] - c[i - 1], x[i] - x[i - j]);
+            if dy % dx != 0 {
+                return None;
+            }
+            c[i] = dy / dx;
+        }
+    }
+
+    // Newton form c0 + (x - x0)(c1 + (x - x1)(c2 + ...)) expanded from the inside out
+    let mut p = vec![c[c.len() - 1]];
+    for i in (0..c.len() - 1).rev() {
+        p.insert(0, 0);
+        for k in 0..p.len() - 1 {
+            p[k] = p[k].checked_sub(p[k + 1].checked_mul(x[i])?)?;
+        }
+        p[0] = p[0].checked_add(c[i])?;
+    }
+    while p.last() == Some(&0) {
+        p.pop();
+    }
+    Some(p)
+}
+
+/// Quotient f / g if g divides f over the integers.
+fn exact_division(f: &[i128], g: &[i128]) -> Option<Vec<i128>> {
+    let m = g.len() - 1;
+    let lead = g[m];
+    let mut r = f.to_vec();
+    let mut q = vec![0; f.len() - m];
+    for i in (0..q.len()).rev() {
+        if r[i + m] % lead != 0 {
+            return None;
+        }
+        q[i] = r[i + m] / lead;
+        for (j, &b) in g.iter().enumerate() {
+            r[i + j] = r[i + j].checked_sub(q[i].checked_mul(b)?)?;
+        }
+    }
+    if r.iter().all(|&c| c == 0) {
+        Some(q)
+    } else {
+        None
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Interpolation
+// -----------------------------------------------------------------------------
+
+/// p(x) (x - a) for a single root a.
+fn times_linear<T: Coefficient>(p: &[T], a: T) -> Vec<T> {
+    let mut r = vec![T::from(0); p.len() + 1];
+    for (i, &c) in p.iter().enumerate() {
+        r[i + 1] = r[i + 1] + c;
+        r[i] = r[i] - c * a;
+    }
+    r
+}
+
+/// Coefficients of the divided differences f[x0], f[x0, x1], ..., f[x0, ..., xn], failing
+/// for a repeated x.
+fn divided_differences<T: Coefficient + Div<Output = T>>(points: &[(T, T)]) -> Result<Vec<T>> {
+    let mut c: Vec<T> = points.iter().map(|&(_, y)| y).collect();
+    for j in 1..c.len() {
+        for i in (j..c.len()).rev() {
+            let dx = points[i].0 - points[i - j].0;
+            if dx == T::from(0) {
+                return Err(Error::DivisionByZero);
+            }
+            c[i] = ((c[i] - c[i - 1]) / dx).normalize();
+        }
+    }
+    Ok(c)
+}
+
+impl<T: Coefficient + Div<Output = T>> Polynomial<T> {
+    /// Polynomial of degree below n through n points (x, y) with distinct x, as the sum of the
+    /// Lagrange basis polynomials y_i Π (x - x_j) / (x_i - x_j) in O(n²). Exact over
+    /// [`Fraction`], and agreeing with [`Polynomial::newton`]. Fails with
+    /// [`Error::DivisionByZero`] for a repeated x. No points give the zero polynomial.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ctl::math::Polynomial;
+    /// let p = Polynomial::lagrange(&[(0.0, 1.0), (1.0, 3.0), (2.0, 7.0)]); // Ok(x² + x + 1)
+    /// ```
+    pub fn lagrange(points: &[(T, T)]) -> Result<Self> {
+        // Dividing the product of all (x - x_j) by one factor gives each basis numerator
+        let master = points
+            .iter()
+            .fold(vec![T::from(1)], |p, &(x, _)| times_linear(&p, x));
+
+        let mut sum = vec![T::from(0); points.len()];
+        for &(xi, yi) in points {
+            // Synthetic division by (x - xi), from the top
+            let mut basis = vec![T::from(0); points.len()];
+            let mut carry = T::from(0);
+            for k in (0..points.len()).rev() {
+                carry = (master[k + 1] + carry * xi).normalize();
+                basis[k] = carry;
+            }
+
+            let w = basis.iter().rev().fold(T::from(0), |acc, &c| acc * xi + c);
+            if w == T::from(0) {
+                return Err(Error::DivisionByZero);
+            }
+            let scale = (yi / w).normalize();
+            for (s, &b) in sum.iter_mut().zip(&basis) {
+                *s = (*s + scale * b).normalize();
+            }
+        }
+        Ok(Polynomial::new(sum))
+    }
+
+    /// Same interpolating polynomial as [`Polynomial::lagrange`], built from Newton's divided
+    /// differences as f[x0] + f[x0, x1] (x - x0) + ... in O(n²). Fails with
+    /// [`Error::DivisionByZero`] for a repeated x.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ctl::math::{frac, Polynomial};
+    /// let pts = [(frac(0, 1), frac(1, 1)), (frac(1, 2), frac(0, 1)), (frac(1, 1), frac(1, 1))];
+    /// let p = Polynomial::newton(&pts); // Ok(4x² - 4x + 1)
+    /// ```
+    pub fn newton(points: &[(T, T)]) -> Result<Self> {
+        let c = divided_differences(points)?;
+
+        // Expand the nested form from the innermost difference outwards
+        let mut p: Vec<T> = Vec::new();
+        for i in (0..c.len()).rev() {
+            p = times_linear(&p, points[i].0);
+            p.iter_mut().for_each(|v| *v = v.normalize());
+            match p.first_mut() {
+                Some(v) => *v = (*v + c[i]).normalize(),
+                None => p.push(c[i]),
+            }
+        }
+        Ok(Polynomial::new(p))
+    }
+}
+
+/// Evaluator of the polynomial through the points without expanding it, keeping the divided
+/// differences and evaluating Newton's nested form in O(n) per point, which is better
+/// conditioned than the expanded coefficients over `f64`. Fails with
+/// [`Error::DivisionByZero`] for a repeated x.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::math::interpolator;
+/// let f = interpolator(&[(1.0, 1.0), (2.0, 4.0), (3.0, 9.0)]).unwrap();
+/// let y = f(2.5); // 6.25
+/// ```
+pub fn interpolator<T: Coefficient + Div<Output = T>>(
+    points: &[(T, T)],
+) -> Result<impl Fn(T) -> T> {
+    let c = divided_differences(points)?;
+    let xs: Vec<T> = points.iter().map(|&(x, _)| x).collect();
+
+    Ok(move |x: T| {
+        c.iter().zip(&xs).rev().fold(T::from(0), |acc, (&ci, &xi)| {
+            (acc * (x - xi) + ci).normalize()
+        })
+    })
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_integer_polynomials() {
+        let p = Polynomial::new(vec![1i64, -3, 2]);
+        let q = Polynomial::new(vec![0i64, 1]);
+        assert_eq!(p.degree(), Some(2));
+        assert_eq!(p.leading_coefficient(), Some(2));
+        assert_eq!((&p * &q).coeffs(), [0, 1, -3, 2]);
+        assert_eq!((&p + &q).coeffs(), [1, -2, 2]);
+        assert_eq!((&p - &p).degree(), None);
+        assert!((&p - &p).is_zero());
+        assert_eq!((-&q).coeffs(), [0, -1]);
+        assert_eq!((p.clone() * 3).coeffs(), [3, -9, 6]);
+        assert_eq!(Polynomial::new(vec![0i64, 0]), Polynomial::zero());
+        assert_eq!(Polynomial::constant(0i32).degree(), None);
+
+        // Evaluation agrees with the factored form (2x - 1)(x - 1)
+        for x in -5..=5 {
+            assert_eq!(p.eval(x), (2 * x - 1) * (x - 1));
+        }
+        assert_eq!(Polynomial::<i64>::zero().eval(7), 0);
+
+        // Product rule
+        let r = &p * &Polynomial::new(vec![4, 0, 0, 1]);
+        let lhs = r.derivative();
+        let rhs = &(&p.derivative() * &Polynomial::new(vec![4, 0, 0, 1]))
+            + &(&p * &Polynomial::new(vec![0, 0, 3]));
+        assert_eq!(lhs, rhs);
+        assert!(Polynomial::constant(5i64).derivative().is_zero());
+    }
+
+    #[test]
+    fn test_field_polynomials() {
+        // Integrating and differentiating round trips over fractions
+        let p = Polynomial::new(vec![frac(1, 2), frac(-2, 3), frac(3, 1)]);
+        let a = p.antiderivative();
+        assert_eq!(a.coeffs()[0], frac(0, 1));
+        assert_eq!(a.coeffs()[3], Fraction::from(1));
+        assert_eq!(a.derivative(), p);
+        assert_eq!(p.eval(frac(1, 2)), frac(11, 12));
+
+        // Over floats, with the definite integral of x² on [0, 3]
+        let x2 = Polynomial::new(vec![0.0f64, 0.0, 1.0]);
+        let a = x2.antiderivative();
+        assert!((a.eval(3.0) - a.eval(0.0) - 9.0).abs() < 1e-12);
+        assert_eq!(x2.derivative().coeffs(), [0.0, 2.0]);
+    }
+
+    #[test]
+    fn test_division_and_roots() {
+        let f = |c: &[i32]| Polynomial::new(c.iter().map(|&x| frac(x, 1)).collect());
+
+        // Division reconstructs the dividend with a smaller remainder
+        let a = f(&[5, -3, 0, 2, 7]);
+        let b = Polynomial::new(vec![frac(1, 2), frac(0, 1), frac(3, 1)]);
+        let (q, r) = a.div_rem(&b);
+        assert!(r.degree() < b.degree());
+        assert_eq!(&(&q * &b) + &r, a);
+        assert_eq!(&a / &b, q);
+        assert_eq!(&a % &b, r);
+        assert_eq!(b.div_rem(&a), (Polynomial::zero(), b.clone()));
+        assert_eq!(
+            a.try_div_rem(&Polynomial::zero()),
+            Err(Error::DivisionByZero)
+        );
+        let (q, r) = f(&[-1, 0, 2]).div_rem(&f(&[1, 1]));
+        assert_eq!((q, r), (f(&[-2, 2]), f(&[1])));
+
+        // The gcd of products is the common factor, made monic
+        let common = f(&[-2, 0, 1]); // x² - 2
+        let g = (&common * &f(&[1, 3])).gcd(&(&common * &f(&[4, 0, 2])));
+        assert_eq!(g, common);
+        assert_eq!(f(&[1, 1]).gcd(&f(&[2, 1])), f(&[1]));
+        assert_eq!(f(&[0, 3]).gcd(&Polynomial::zero()), f(&[0, 1]));
+        assert!(Polynomial::<Fraction>::zero()
+            .gcd(&Polynomial::zero())
+            .is_zero());
+
+        // Roots with fractional coefficients, repeated roots and a root at 0
+        let p = f(&[-3, 5, -1, 5, 2]);
+        assert_eq!(p.rational_roots(), Ok(vec![frac(-3, 1), frac(1, 2)]));
+        let p = &(&f(&[0, 0, 1]) * &f(&[2, -3])) * &(&f(&[-1, 3]) * &f(&[-1, 3]));
+        let roots = p.rational_roots().unwrap();
+        assert_eq!(roots, [frac(0, 1), frac(1, 3), frac(2, 3)]);
+        let halves = Polynomial::new(vec![frac(-1, 6), frac(1, 2)]); // x / 2 - 1 / 6
+        assert_eq!(halves.rational_roots(), Ok(vec![frac(1, 3)]));
+        assert_eq!(f(&[1, 0, 1]).rational_roots(), Ok(vec![]));
+        assert_eq!(f(&[7]).rational_roots(), Ok(vec![]));
+        assert_eq!(Polynomial::zero().rational_roots(), Err(Error::Domain));
+
+        // Distinct prime denominators near 2³¹ scale to integer coefficients near 2⁶², which
+        // factorize quickly. One more pushes them beyond u64.
+        let ps = [2147483647, 2147483629, 2147483587, 2147483579];
+        let inv = |i: usize| frac(1, ps[i]);
+        let wide = Polynomial::new(vec![inv(0), inv(0), inv(1), inv(1), inv(2), inv(2)]);
+        assert_eq!(wide.rational_roots(), Ok(vec![frac(-1, 1)]));
+        let wider = Polynomial::new(vec![inv(0), inv(1), inv(2), inv(3)]);
+        assert_eq!(wider.rational_roots(), Err(Error::Overflow));
+
+        // Roots agree with division leaving no remainder
+        for r in roots {
+            let linear = Polynomial::new(vec![-r, frac(1, 1)]);
+            assert!((&p % &linear).is_zero());
+        }
+
+        // Floats divide approximately
+        let (q, r) =
+            Polynomial::new(vec![-1.0, 0.0, 1.0]).div_rem(&Polynomial::new(vec![-1.0, 1.0]));
+        assert_eq!((q.coeffs(), r.coeffs()), (&[1.0, 1.0][..], &[][..]));
+    }
+
+    #[test]
+    fn test_factorization() {
+        let f = |c: &[i32]| Polynomial::new(c.iter().map(|&x| frac(x, 1)).collect());
+        let product = |(c, factors): &(Fraction, Vec<(Polynomial<Fraction>, usize)>)| {
+            factors
+                .iter()
+                .fold(Polynomial::constant(*c), |acc, (g, i)| {
+                    (0..*i).fold(acc, |acc, _| &acc * g)
+                })
+        };
+
+        // Square-free parts of 3x³(x - 1)(x + 2)²
+        let x = f(&[0, 1]);
+        let p = &(&(&x * &x) * &(&x * &f(&[-3, 3]))) * &(&f(&[2, 1]) * &f(&[2, 1]));
+        let s = p.square_free();
+        assert_eq!(s, [(f(&[-1, 1]), 1), (f(&[2, 1]), 2), (x.clone(), 3)]);
+        assert!(f(&[5]).square_free().is_empty());
+
+        // Linear factors from the rational roots, the rest found by Kronecker's method
+        let q = &f(&[1, 1, 1]) * &f(&[1, 1, 1]);
+        let p = &(&f(&[-2, 1]) * &q) * &f(&[2]);
+        let r = p.factor().unwrap();
+        assert_eq!(r, (frac(2, 1), vec![(f(&[-2, 1]), 1), (f(&[1, 1, 1]), 2)]));
+        assert_eq!(product(&r), p);
+
+        // x⁴ + 4 = (x² - 2x + 2)(x² + 2x + 2) has no roots but splits, x⁴ + 1 doesn't
+        let r = f(&[4, 0, 0, 0, 1]).factor().unwrap();
+        assert_eq!(r.1, [(f(&[2, 2, 1]), 1), (f(&[2, -2, 1]), 1)]);
+        let r = f(&[1, 0, 0, 0, 1]).factor().unwrap();
+        assert_eq!(r.1, [(f(&[1, 0, 0, 0, 1]), 1)]);
+
+        // Two cubics, one with fractional coefficients, and a repeated quadratic
+        let a = Polynomial::new(vec![frac(1, 2), frac(2, 3), frac(0, 1), frac(1, 1)]);
+        let b = f(&[3, -1, 0, 1]);
+        let c = f(&[1, -1, 1]);
+        let p = &(&(&a * &b) * &(&c * &c)) * &Polynomial::constant(frac(-3, 4));
+        let r = p.factor().unwrap();
+        assert_eq!(r.0, frac(-3, 4));
+        assert_eq!(r.1.len(), 3);
+        assert_eq!(r.1[0], (c, 2));
+        assert!(r.1.contains(&(a, 1)) && r.1.contains(&(b, 1)));
+        assert_eq!(product(&r), p);
+
+        assert_eq!(f(&[3]).factor(), Ok((frac(3, 1), vec![])));
+        assert_eq!(Polynomial::zero().factor(), Err(Error::Domain));
+        let p = Polynomial::new(vec![frac(1, 0), frac(1, 1)]);
+        assert_eq!(p.factor(), Err(Error::DivisionByZero));
+    }
+
+    #[test]
+    fn test_interpolation() {
+        // Exact recovery of a cubic over fractions by both constructions
+        let cubic = Polynomial::new(vec![frac(1, 3), frac(-2, 1), frac(0, 1), frac(5, 4)]);
+        let pts: Vec<(Fraction, Fraction)> = [-2, 0, 1, 3]
+            .iter()
+            .map(|&x| (frac(x, 1), cubic.eval(frac(x, 1))))
+            .collect();
+        assert_eq!(Polynomial::lagrange(&pts), Ok(cubic.clone()));
+        assert_eq!(Polynomial::newton(&pts), Ok(cubic.clone()));
+        let f = interpolator(&pts).unwrap();
+        assert_eq!(f(frac(7, 2)), cubic.eval(frac(7, 2)));
+
+        // Floats agree approximately, also off the samples
+        let pts: Vec<(f64, f64)> = (0..6)
+            .map(|i| {
+                let x = i as f64 * 0.7 - 1.0;
+                (x, x.sin())
+            })
+            .collect();
+        let l = Polynomial::lagrange(&pts).unwrap();
+        let n = Polynomial::newton(&pts).unwrap();
+        let f = interpolator(&pts).unwrap();
+        for &(x, y) in &pts {
+            assert!((l.eval(x) - y).abs() < 1e-12 && (n.eval(x) - y).abs() < 1e-12);
+        }
+        for x in [-0.5, 0.3, 2.0] {
+            assert!((l.eval(x) - f(x)).abs() < 1e-12 && (n.eval(x) - f(x)).abs() < 1e-12);
+            assert!((f(x) - x.sin()).abs() < 1e-2);
+        }
+
+        // Degenerate inputs
+        assert_eq!(
+            Polynomial::lagrange(&[(1.0, 2.0), (1.0, 3.0)]),
+            Err(Error::DivisionByZero)
+        );
+        assert_eq!(
+            Polynomial::newton(&[(1.0, 2.0), (1.0, 3.0)]),
+            Err(Error::DivisionByZero)
+        );
+        assert!(interpolator(&[(frac(1, 2), frac(1, 1)), (frac(2, 4), frac(0, 1))]).is_err());
+        assert_eq!(Polynomial::<f64>::lagrange(&[]), Ok(Polynomial::zero()));
+        assert_eq!(
+            Polynomial::newton(&[(4.0, 5.0)]),
+            Ok(Polynomial::constant(5.0))
+        );
+    }
+}
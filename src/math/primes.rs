@@ -0,0 +1,827 @@
+use super::binary_gcd;
+use super::modular::{mod_add, mod_mul, mod_pow};
+use crate::parallel;
+use alloc::vec::Vec;
+
+// -----------------------------------------------------------------------------
+// Primality
+// -----------------------------------------------------------------------------
+
+/// Bases that make Miller-Rabin deterministic for every 64 bit integer.
+const WITNESSES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+
+/// Checks whether n is prime with the Miller-Rabin test. The first 12 primes as witnesses make
+/// the test exact for all `u64` values, each round takes O(log n) multiplications.
+///
+/// # Arguments
+///
+/// * `n` - Tested number
+///
+/// # Examples
+///
+/// ```
+/// use ctl::math::is_prime;
+/// let a = is_prime(1_000_000_007); // true
+/// let b = is_prime(3_215_031_751); // false, strong pseudoprime to the bases 2, 3, 5 and 7
+/// ```
+pub fn is_prime(n: u64) -> bool {
+    if n < 2 {
+        return false;
+    }
+    for &p in &WITNESSES {
+        if n.is_multiple_of(p) {
+            return n == p;
+        }
+    }
+
+    // n - 1 = d 2^s with d odd
+    let s = (n - 1).trailing_zeros();
+    let d = (n - 1) >> s;
+
+    WITNESSES.iter().all(|&a| {
+        let mut x = mod_pow(a, d, n);
+        if x == 1 || x == n - 1 {
+            return true;
+        }
+
+        for _ in 1..s {
+            x = mod_mul(x, x, n);
+            if x == n - 1 {
+                return true;
+            }
+        }
+
+        false
+    })
+}
+
+// -----------------------------------------------------------------------------
+// Factorization
+// -----------------------------------------------------------------------------
+
+/// Finds a proper factor of an odd composite n with Brent's variant of Pollard's rho. The
+/// products of 128 differences share one gcd, a failed constant is replaced by the next one.
+fn pollard_brent(n: u64) -> u64 {
+    const BATCH: u64 = 128;
+
+    for c in 1.. {
+        let f = |x| mod_add(mod_mul(x, x, n), c, n);
+        let (mut x, mut y, mut ys) = (0, 2, 0);
+        let (mut g, mut r, mut q) = (1, 1, 1);
+
+        while g == 1 {
+            x = y;
+            for _ in 0..r {
+                y = f(y);
+            }
+
+            let mut k = 0;
+            while k < r && g == 1 {
+                ys = y;
+                for _ in 0..BATCH.min(r - k) {
+                    y = f(y);
+                    q = mod_mul(q, x.abs_diff(y), n);
+                }
+                g = binary_gcd(q, n);
+                k += BATCH;
+            }
+            r *= 2;
+        }
+
+        // The batch overshot, redo it one step at a time
+        if g == n {
+            loop {
+                ys = f(ys);
+                g = binary_gcd(x.abs_diff(ys), n);
+                if g > 1 {
+                    break;
+                }
+            }
+        }
+
+        if g != n {
+            return g;
+        }
+    }
+
+    unreachable!()
+}
+
+/// Pushes the prime factors of n with repetitions.
+fn split(n: u64, factors: &mut Vec<u64>) {
+    if n == 1 {
+        return;
+    }
+    if is_prime(n) {
+        factors.push(n);
+        return;
+    }
+
+    let d = pollard_brent(n);
+    split(d, factors);
+    split(n / d, factors);
+}
+
+/// Splits n into its prime factors with their exponents in ascending order. Small factors are
+/// removed by trial division, the rest is split by Pollard's rho and checked by [`is_prime`],
+/// which factors any `u64` in milliseconds. 0 and 1 give no factors.
+///
+/// # Arguments
+///
+/// * `n` - Factorized number
+///
+/// # Examples
+///
+/// ```
+/// use ctl::math::factorize;
+/// let f = factorize(360); // [(2, 3), (3, 2), (5, 1)]
+/// let g = factorize(1_000_000_007 * 998_244_353); // [(998244353, 1), (1000000007, 1)]
+/// ```
+pub fn factorize(mut n: u64) -> Vec<(u64, u32)> {
+    if n == 0 {
+        return Vec::new();
+    }
+    let mut primes = Vec::new();
+
+    // Composite divisors never match, their prime factors are gone already
+    for p in 2..100 {
+        while n.is_multiple_of(p) {
+            primes.push(p);
+            n /= p;
+        }
+    }
+    split(n, &mut primes);
+    primes.sort_unstable();
+
+    let mut factors: Vec<(u64, u32)> = Vec::new();
+    for p in primes {
+        match factors.last_mut() {
+            Some((q, e)) if *q == p => *e += 1,
+            _ => factors.push((p, 1)),
+        }
+    }
+
+    factors
+}
+
+/// Smallest generator of the multiplicative group modulo the prime p, whose powers run through
+/// every nonzero residue. A candidate g generates the group iff g^((p - 1) / q) ≠ 1 for every
+/// prime factor q of p - 1. `None` if p isn't prime.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::math::primes::primitive_root;
+/// let g = primitive_root(998_244_353); // Some(3)
+/// let h = primitive_root(15); // None
+/// ```
+pub fn primitive_root(p: u64) -> Option<u64> {
+    if !is_prime(p) {
+        return None;
+    }
+    if p == 2 {
+        return Some(1);
+    }
+
+    let factors = factorize(p - 1);
+    (2..p).find(|&g| {
+        factors
+            .iter()
+            .all(|&(q, _)| mod_pow(g, (p - 1) / q, p) != 1)
+    })
+}
+
+// -----------------------------------------------------------------------------
+// Divisors
+// -----------------------------------------------------------------------------
+
+/// Iterator over the divisors of a number created by [`divisors`], stepping through the
+/// exponent combinations of its prime factors like an odometer.
+#[derive(Debug, Clone)]
+pub struct Divisors {
+    factors: Vec<(u64, u32)>,
+    exps: Vec<u32>,
+    current: u64,
+    done: bool,
+}
+
+impl Iterator for Divisors {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        if self.done {
+            return None;
+        }
+        let d = self.current;
+
+        // Increase the lowest exponent below its maximum, resetting the ones before
+        self.done = true;
+        for (i, &(p, e)) in self.factors.iter().enumerate() {
+            if self.exps[i] < e {
+                self.exps[i] += 1;
+                self.current *= p;
+                self.done = false;
+                break;
+            }
+
+            self.current /= p.pow(e);
+            self.exps[i] = 0;
+        }
+
+        Some(d)
+    }
+}
+
+/// Enumerates all positive divisors of n lazily in no particular order, keeping only the
+/// factorization in memory. 0 has none.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::math::primes::divisors;
+/// let mut d: Vec<_> = divisors(12).collect(); // 6 divisors
+/// d.sort();
+/// // [1, 2, 3, 4, 6, 12]
+/// ```
+pub fn divisors(n: u64) -> Divisors {
+    let factors = factorize(n);
+
+    Divisors {
+        exps: vec![0; factors.len()],
+        factors,
+        current: 1,
+        done: n == 0,
+    }
+}
+
+/// Enumerates all positive divisors of n in ascending order. Only the divisors up to sqrt(n)
+/// are stored, the larger ones are their cofactors.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::math::primes::sorted_divisors;
+/// let d: Vec<_> = sorted_divisors(36).collect(); // [1, 2, 3, 4, 6, 9, 12, 18, 36]
+/// ```
+pub fn sorted_divisors(n: u64) -> impl Iterator<Item = u64> {
+    let mut small: Vec<_> = divisors(n).filter(|&d| d <= n / d).collect();
+    small.sort_unstable();
+
+    // The root of a square is its own cofactor
+    let square = small.last().is_some_and(|&d| d * d == n) as usize;
+    let large: Vec<_> = small.iter().rev().skip(square).map(|&d| n / d).collect();
+
+    small.into_iter().chain(large)
+}
+
+// -----------------------------------------------------------------------------
+// Multiplicative functions
+// -----------------------------------------------------------------------------
+
+/// Euler's totient φ(n), the count of numbers in [1, n] coprime to n, from the factorization
+/// of n. φ(0) is 0.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::math::primes::euler_phi;
+/// let a = euler_phi(36); // 12
+/// let b = euler_phi(1_000_000_007); // 1000000006
+/// ```
+pub fn euler_phi(n: u64) -> u64 {
+    factorize(n)
+        .into_iter()
+        .fold(n, |phi, (p, _)| phi / p * (p - 1))
+}
+
+/// Number of positive divisors of n, 0 for n = 0.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::math::primes::divisor_count;
+/// let d = divisor_count(360); // 24
+/// ```
+pub fn divisor_count(n: u64) -> u64 {
+    if n == 0 {
+        return 0;
+    }
+
+    factorize(n).iter().map(|&(_, e)| e as u64 + 1).product()
+}
+
+/// Sum of the positive divisors of n, 0 for n = 0. Widened to `u128` as it exceeds n.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::math::primes::divisor_sum;
+/// let s = divisor_sum(28); // 56, a perfect number
+/// ```
+pub fn divisor_sum(n: u64) -> u128 {
+    if n == 0 {
+        return 0;
+    }
+
+    // (p^(e + 1) - 1) / (p - 1) for each prime power
+    factorize(n)
+        .into_iter()
+        .map(|(p, e)| {
+            let p = p as u128;
+            (0..e).fold(1, |s, _| s * p + 1)
+        })
+        .product()
+}
+
+/// Totients of all numbers up to and including n, computed by a sieve in O(n log log n).
+///
+/// # Examples
+///
+/// ```
+/// use ctl::math::primes::phi_table;
+/// let phi = phi_table(10); // [0, 1, 1, 2, 2, 4, 2, 6, 4, 6, 4]
+/// ```
+pub fn phi_table(n: usize) -> Vec<u64> {
+    let mut phi: Vec<u64> = (0..=n as u64).collect();
+
+    for p in 2..=n {
+        // Untouched entries are prime
+        if phi[p] == p as u64 {
+            for m in (p..=n).step_by(p) {
+                phi[m] -= phi[m] / p as u64;
+            }
+        }
+    }
+
+    phi
+}
+
+/// Divisor counts of all numbers up to and including n, adding each divisor to its multiples
+/// in O(n log n).
+pub fn divisor_count_table(n: usize) -> Vec<u64> {
+    let mut count = vec![0; n + 1];
+    for d in 1..=n {
+        for m in (d..=n).step_by(d) {
+            count[m] += 1;
+        }
+    }
+
+    count
+}
+
+/// Divisor sums of all numbers up to and including n in O(n log n).
+pub fn divisor_sum_table(n: usize) -> Vec<u64> {
+    let mut sum = vec![0; n + 1];
+    for d in 1..=n {
+        for m in (d..=n).step_by(d) {
+            sum[m] += d as u64;
+        }
+    }
+
+    sum
+}
+
+// -----------------------------------------------------------------------------
+// Sieve
+// -----------------------------------------------------------------------------
+
+/// Smallest prime factor of every number up to a limit, computed by a linear sieve of
+/// Eratosthenes in O(N). Answers primality queries in O(1) and factorizes in O(log n).
+///
+/// # Examples
+///
+/// ```
+/// use ctl::math::primes::Sieve;
+/// let sieve = Sieve::new(100);
+/// let a = sieve.is_prime(97); // true
+/// let b = sieve.factorize(84); // [(2, 2), (3, 1), (7, 1)]
+/// let c = sieve.primes().count(); // 25
+/// ```
+#[derive(Debug, Clone)]
+pub struct Sieve {
+    /// Smallest prime factor, 0 for 0 and 1
+    spf: Vec<u32>,
+    primes: Vec<u32>,
+}
+
+impl Sieve {
+    /// Sieves all numbers up to and including `limit`, which has to fit into a `u32`.
+    pub fn new(limit: usize) -> Self {
+        assert!(limit <= u32::MAX as usize, "sieve limit too large");
+
+        let mut spf = vec![0u32; limit + 1];
+        let mut primes = Vec::new();
+
+        // Every composite is crossed out once, by its smallest prime factor
+        for i in 2..=limit {
+            if spf[i] == 0 {
+                spf[i] = i as u32;
+                primes.push(i as u32);
+            }
+
+            for &p in &primes {
+                let m = i * p as usize;
+                if p > spf[i] || m > limit {
+                    break;
+                }
+                spf[m] = p;
+            }
+        }
+
+        Sieve { spf, primes }
+    }
+
+    /// Same sieve as [`Sieve::new`], built in segments on all cores with the `parallel` feature.
+    /// The primes up to √limit are sieved first, then every segment crosses out their multiples
+    /// in ascending order, so the first prime to reach a number is its smallest factor.
+    pub fn par_new(limit: usize) -> Self {
+        const SEGMENT: usize = 1 << 15;
+
+        assert!(limit <= u32::MAX as usize, "sieve limit too large");
+
+        let root = limit.isqrt();
+        let small = Sieve::new(root);
+        let mut spf = vec![0u32; limit + 1];
+        spf[..=root].copy_from_slice(&small.spf);
+
+        let lo = root + 1;
+        parallel::for_chunks_mut(&mut spf[lo..], SEGMENT, |start, seg| {
+            let (a, b) = (lo + start, lo + start + seg.len());
+            for p in small.primes() {
+                let first = (a.div_ceil(p) * p).max(p * p);
+                for m in (first..b).step_by(p) {
+                    if seg[m - a] == 0 {
+                        seg[m - a] = p as u32;
+                    }
+                }
+            }
+
+            for (i, s) in seg.iter_mut().enumerate() {
+                if *s == 0 {
+                    *s = (a + i) as u32;
+                }
+            }
+        });
+
+        let primes = parallel::map_chunks(&spf, SEGMENT, |start, seg| {
+            (start..)
+                .zip(seg)
+                .filter(|&(n, &s)| n >= 2 && s as usize == n)
+                .map(|(n, _)| n as u32)
+                .collect::<Vec<_>>()
+        })
+        .concat();
+
+        Sieve { spf, primes }
+    }
+
+    /// Largest number covered by the sieve.
+    pub fn limit(&self) -> usize {
+        self.spf.len() - 1
+    }
+
+    /// Checks whether n is prime, panics above the limit.
+    pub fn is_prime(&self, n: usize) -> bool {
+        n >= 2 && self.spf[n] as usize == n
+    }
+
+    /// Smallest prime factor of n, `None` for 0 and 1. Panics above the limit.
+    pub fn smallest_factor(&self, n: usize) -> Option<usize> {
+        match self.spf[n] {
+            0 => None,
+            p => Some(p as usize),
+        }
+    }
+
+    /// Iterates over the primes up to the limit in ascending order.
+    pub fn primes(&self) -> impl Iterator<Item = usize> + '_ {
+        self.primes.iter().map(|&p| p as usize)
+    }
+
+    /// Prime factors of n with their exponents in ascending order, empty for 0 and 1. Panics
+    /// above the limit.
+    pub fn factorize(&self, mut n: usize) -> Vec<(usize, u32)> {
+        let mut factors: Vec<(usize, u32)> = Vec::new();
+
+        while n >= 2 {
+            let p = self.spf[n] as usize;
+            match factors.last_mut() {
+                Some((q, e)) if *q == p => *e += 1,
+                _ => factors.push((p, 1)),
+            }
+            n /= p;
+        }
+
+        factors
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Segmented sieve
+// -----------------------------------------------------------------------------
+
+/// Numbers sieved at once by [`SegmentedSieve`].
+const SEGMENT: u64 = 1 << 15;
+
+/// Primes in the range [low, high) found segment by segment, so only O(sqrt(high)) memory is
+/// needed no matter how wide the range is. The primes up to sqrt(high) are sieved up front.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::math::primes::SegmentedSieve;
+/// let primes: Vec<_> = SegmentedSieve::new(1_000_000_000, 1_000_000_100).collect();
+/// // [1000000007, 1000000009, 1000000021, 1000000033, 1000000087, 1000000093, 1000000097]
+/// ```
+#[derive(Debug, Clone)]
+pub struct SegmentedSieve {
+    /// Primes up to sqrt(high)
+    base: Vec<u64>,
+    /// Start of the next segment
+    low: u64,
+    high: u64,
+    found: Vec<u64>,
+    pos: usize,
+}
+
+impl SegmentedSieve {
+    pub fn new(low: u64, high: u64) -> Self {
+        let root = high.saturating_sub(1).isqrt();
+        let base = Sieve::new(root as usize)
+            .primes()
+            .map(|p| p as u64)
+            .collect();
+
+        SegmentedSieve {
+            base,
+            low: low.max(2),
+            high,
+            found: Vec::new(),
+            pos: 0,
+        }
+    }
+
+    /// Sieves the next segment into `found`.
+    fn next_segment(&mut self) {
+        let (low, high) = (self.low, self.high.min(self.low.saturating_add(SEGMENT)));
+        let mut composite = vec![false; (high - low) as usize];
+
+        for &p in &self.base {
+            if p * p >= high {
+                break;
+            }
+
+            // First multiple inside the segment, skipping p itself
+            let mut m = match low.div_ceil(p).checked_mul(p) {
+                Some(m) => m.max(p * p),
+                None => continue,
+            };
+            while m < high {
+                composite[(m - low) as usize] = true;
+                m = match m.checked_add(p) {
+                    Some(m) => m,
+                    None => break,
+                };
+            }
+        }
+
+        self.found = (low..high)
+            .zip(composite)
+            .filter(|&(_, c)| !c)
+            .map(|(n, _)| n)
+            .collect();
+        self.pos = 0;
+        self.low = high;
+    }
+}
+
+impl Iterator for SegmentedSieve {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        while self.pos == self.found.len() {
+            if self.low >= self.high {
+                return None;
+            }
+            self.next_segment();
+        }
+
+        self.pos += 1;
+        Some(self.found[self.pos - 1])
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_prime() {
+        // Trial division up to 10^4
+        for n in 0..10_000u64 {
+            let trial = n >= 2 && (2..n).take_while(|d| d * d <= n).all(|d| n % d != 0);
+            assert_eq!(is_prime(n), trial, "{}", n);
+        }
+
+        assert!(is_prime(u64::MAX - 58));
+        assert!(is_prime((1 << 61) - 1));
+        assert!(!is_prime(u64::MAX));
+        assert!(!is_prime(3_215_031_751));
+        assert!(!is_prime(3_825_123_056_546_413_051));
+        assert!(!is_prime(4_294_967_297)); // 641 * 6700417
+        assert!(!is_prime(1_000_000_007 * 998_244_353));
+    }
+
+    #[test]
+    fn test_sieve() {
+        let sieve = Sieve::new(10_000);
+        assert_eq!(sieve.limit(), 10_000);
+        for n in 0..=10_000 {
+            assert_eq!(sieve.is_prime(n), is_prime(n as u64), "{}", n);
+        }
+        assert_eq!(sieve.primes().count(), 1229);
+        assert_eq!(sieve.primes().last(), Some(9973));
+
+        assert_eq!(sieve.smallest_factor(1), None);
+        assert_eq!(sieve.smallest_factor(9991), Some(97));
+        assert_eq!(sieve.factorize(1), vec![]);
+        assert_eq!(sieve.factorize(9973), vec![(9973, 1)]);
+        assert_eq!(sieve.factorize(8640), vec![(2, 6), (3, 3), (5, 1)]);
+
+        assert_eq!(Sieve::new(0).primes().count(), 0);
+        assert!(!Sieve::new(1).is_prime(1));
+    }
+
+    #[test]
+    fn test_sieve_par() {
+        // Several segments, and limits around squares
+        for limit in (0..40).chain([99_999, 100_489, 300_007]) {
+            let (a, b) = (Sieve::new(limit), Sieve::par_new(limit));
+            assert_eq!(a.spf, b.spf, "{}", limit);
+            assert_eq!(a.primes, b.primes, "{}", limit);
+        }
+    }
+
+    #[test]
+    fn test_segmented_sieve() {
+        // Crosses several segments and compares with the plain sieve
+        let sieve = Sieve::new(200_000);
+        let expected: Vec<_> = sieve
+            .primes()
+            .map(|p| p as u64)
+            .filter(|&p| p >= 3)
+            .collect();
+        assert!(SegmentedSieve::new(3, 200_001).eq(expected));
+
+        let big: Vec<_> = SegmentedSieve::new(1_000_000_000_000, 1_000_000_000_100).collect();
+        assert_eq!(
+            big,
+            vec![
+                1_000_000_000_039,
+                1_000_000_000_061,
+                1_000_000_000_063,
+                1_000_000_000_091
+            ]
+        );
+
+        assert_eq!(
+            SegmentedSieve::new(0, 10).collect::<Vec<_>>(),
+            vec![2, 3, 5, 7]
+        );
+        assert_eq!(SegmentedSieve::new(10, 10).count(), 0);
+        assert_eq!(SegmentedSieve::new(14, 17).count(), 0);
+    }
+
+    #[test]
+    fn test_factorize() {
+        assert_eq!(factorize(0), vec![]);
+        assert_eq!(factorize(1), vec![]);
+        assert_eq!(factorize(97), vec![(97, 1)]);
+        assert_eq!(factorize(8640), vec![(2, 6), (3, 3), (5, 1)]);
+
+        let sieve = Sieve::new(10_000);
+        for n in 1..=10_000 {
+            let expected: Vec<_> = sieve
+                .factorize(n)
+                .into_iter()
+                .map(|(p, e)| (p as u64, e))
+                .collect();
+            assert_eq!(factorize(n as u64), expected, "{}", n);
+        }
+
+        // Semiprimes of large factors, a prime square and the largest values
+        assert_eq!(
+            factorize(4_294_967_291 * 4_294_967_279),
+            vec![(4_294_967_279, 1), (4_294_967_291, 1)]
+        );
+        assert_eq!(
+            factorize(1_000_000_007 * 1_000_000_007),
+            vec![(1_000_000_007, 2)]
+        );
+        assert_eq!(factorize(u64::MAX - 58), vec![(u64::MAX - 58, 1)]);
+        assert_eq!(
+            factorize(u64::MAX),
+            vec![
+                (3, 1),
+                (5, 1),
+                (17, 1),
+                (257, 1),
+                (641, 1),
+                (65537, 1),
+                (6700417, 1)
+            ]
+        );
+        assert_eq!(factorize(1 << 63), vec![(2, 63)]);
+    }
+
+    #[test]
+    fn test_multiplicative() {
+        use crate::math::gcd;
+
+        let n = 1000;
+        let (phi, count, sum) = (phi_table(n), divisor_count_table(n), divisor_sum_table(n));
+        assert_eq!(phi[..11], [0, 1, 1, 2, 2, 4, 2, 6, 4, 6, 4]);
+        assert_eq!((count[0], sum[0]), (0, 0));
+
+        // Against the definitions
+        for k in 1..=n as u64 {
+            let coprime = (1..=k).filter(|&i| gcd(i, k) == 1).count() as u64;
+            let divisors: Vec<_> = (1..=k).filter(|d| k % d == 0).collect();
+
+            assert_eq!(euler_phi(k), coprime, "{}", k);
+            assert_eq!(phi[k as usize], coprime, "{}", k);
+            assert_eq!(divisor_count(k), divisors.len() as u64, "{}", k);
+            assert_eq!(count[k as usize], divisors.len() as u64, "{}", k);
+            assert_eq!(
+                divisor_sum(k),
+                divisors.iter().sum::<u64>() as u128,
+                "{}",
+                k
+            );
+            assert_eq!(sum[k as usize], divisors.iter().sum::<u64>(), "{}", k);
+        }
+
+        assert_eq!(euler_phi(0), 0);
+        assert_eq!((divisor_count(0), divisor_sum(0)), (0, 0));
+        assert_eq!(euler_phi(1 << 63), 1 << 62);
+        assert_eq!(divisor_count(1 << 63), 64);
+        assert_eq!(divisor_sum(1 << 63), (1 << 64) - 1);
+        assert_eq!(divisor_sum(u64::MAX - 58), u64::MAX as u128 - 57);
+    }
+
+    #[test]
+    fn test_primitive_root() {
+        // The powers of the root cover all residues exactly once
+        for p in [2, 3, 5, 7, 11, 13, 17, 97, 101, 257, 7919] {
+            let g = primitive_root(p).unwrap();
+            let mut seen = vec![false; p as usize];
+            let mut x = 1;
+            for _ in 1..p {
+                assert!(!seen[x as usize], "{} repeats modulo {}", g, p);
+                seen[x as usize] = true;
+                x = x * g % p;
+            }
+
+            // And no smaller candidate generates the group
+            for h in 2..g {
+                assert!((1..p - 1).any(|e| mod_pow(h, e, p) == 1));
+            }
+        }
+
+        assert_eq!(primitive_root(998_244_353), Some(3));
+        assert_eq!(primitive_root(1_000_000_007), Some(5));
+        assert_eq!(primitive_root(u64::MAX - 58), Some(2));
+        assert_eq!(primitive_root(0), None);
+        assert_eq!(primitive_root(1), None);
+        assert_eq!(primitive_root(561), None);
+    }
+
+    #[test]
+    fn test_divisors() {
+        for n in 0..=2000u64 {
+            let expected: Vec<_> = (1..=n).filter(|d| n % d == 0).collect();
+
+            let mut d: Vec<_> = divisors(n).collect();
+            d.sort_unstable();
+            assert_eq!(d, expected, "{}", n);
+            assert!(sorted_divisors(n).eq(expected), "{}", n);
+        }
+
+        // Highly composite numbers and the largest values
+        assert_eq!(divisors(963_761_198_400).count(), 6720);
+        let sorted: Vec<_> = sorted_divisors(963_761_198_400).collect();
+        assert!(sorted.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(sorted.len(), 6720);
+        assert_eq!(divisors(u64::MAX).count(), 128);
+        assert_eq!(sorted_divisors(u64::MAX).last(), Some(u64::MAX));
+        assert_eq!(
+            sorted_divisors(u64::MAX - 58).collect::<Vec<_>>(),
+            vec![1, u64::MAX - 58]
+        );
+        assert_eq!(divisors(1 << 63).count(), 64);
+    }
+}
@@ -0,0 +1,327 @@
+use super::{Coefficient, Fraction, Polynomial};
+use crate::{Error, Result};
+use alloc::vec::Vec;
+use core::ops::{Add, Div, Mul, Neg, Sub};
+
+// -----------------------------------------------------------------------------
+// Rational function
+// -----------------------------------------------------------------------------
+
+/// Ratio p / q of polynomials, kept in lowest terms: the gcd of p and q is divided out and q is
+/// monic, so equal functions compare equal. Meant for exact coefficients like [`Fraction`],
+/// like [`Polynomial::gcd`] the simplification relies on.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::math::{frac, Polynomial, RationalFunction};
+/// let f = |c: &[i32]| Polynomial::new(c.iter().map(|&x| frac(x, 1)).collect());
+/// let r = RationalFunction::new(f(&[-1, 0, 1]), f(&[-2, 2])); // (x² - 1) / (2x - 2)
+/// let n = r.numerator(); // x / 2 + 1 / 2
+/// let d = r.denominator(); // 1
+/// let s = &r + &RationalFunction::new(f(&[1]), f(&[0, 1])); // (x² + x + 2) / 2x
+/// let y = s.eval(frac(2, 1)); // 2
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct RationalFunction<T> {
+    num: Polynomial<T>,
+    den: Polynomial<T>,
+}
+
+impl<T: Coefficient + Div<Output = T>> RationalFunction<T> {
+    /// Rational function num / den in lowest terms. Panics for den = 0, use
+    /// [`RationalFunction::try_new`] to handle it.
+    pub fn new(num: Polynomial<T>, den: Polynomial<T>) -> Self {
+        Self::try_new(num, den).expect("denominator is the zero polynomial")
+    }
+
+    /// Fallible version of [`RationalFunction::new`]. Fails with [`Error::DivisionByZero`] for
+    /// den = 0.
+    pub fn try_new(num: Polynomial<T>, den: Polynomial<T>) -> Result<Self> {
+        let lead = den.leading_coefficient().ok_or(Error::DivisionByZero)?;
+        let g = num.gcd(&den);
+        let scale = T::from(1) / lead;
+        Ok(RationalFunction {
+            num: &(&num / &g) * scale,
+            den: (&den / &g).monic(),
+        })
+    }
+
+    /// Numerator in lowest terms.
+    pub fn numerator(&self) -> &Polynomial<T> {
+        &self.num
+    }
+
+    /// Monic denominator in lowest terms.
+    pub fn denominator(&self) -> &Polynomial<T> {
+        &self.den
+    }
+
+    /// Value at x. Panics at a pole, use [`RationalFunction::try_eval`] to handle it.
+    pub fn eval(&self, x: T) -> T {
+        self.try_eval(x).expect("evaluated at a pole")
+    }
+
+    /// Fallible version of [`RationalFunction::eval`]. Fails with [`Error::DivisionByZero`] at
+    /// a pole, the roots of the denominator.
+    pub fn try_eval(&self, x: T) -> Result<T> {
+        let d = self.den.eval(x);
+        if d == T::from(0) {
+            return Err(Error::DivisionByZero);
+        }
+        Ok((self.num.eval(x) / d).normalize())
+    }
+}
+
+impl<T: Coefficient + Div<Output = T>> From<Polynomial<T>> for RationalFunction<T> {
+    fn from(p: Polynomial<T>) -> Self {
+        RationalFunction {
+            num: p,
+            den: Polynomial::constant(T::from(1)),
+        }
+    }
+}
+
+impl<T: Coefficient + Div<Output = T>> Add for &RationalFunction<T> {
+    type Output = RationalFunction<T>;
+
+    fn add(self, o: &RationalFunction<T>) -> RationalFunction<T> {
+        let num = &(&self.num * &o.den) + &(&o.num * &self.den);
+        RationalFunction::new(num, &self.den * &o.den)
+    }
+}
+
+impl<T: Coefficient + Div<Output = T>> Sub for &RationalFunction<T> {
+    type Output = RationalFunction<T>;
+
+    fn sub(self, o: &RationalFunction<T>) -> RationalFunction<T> {
+        let num = &(&self.num * &o.den) - &(&o.num * &self.den);
+        RationalFunction::new(num, &self.den * &o.den)
+    }
+}
+
+impl<T: Coefficient + Div<Output = T>> Mul for &RationalFunction<T> {
+    type Output = RationalFunction<T>;
+
+    fn mul(self, o: &RationalFunction<T>) -> RationalFunction<T> {
+        RationalFunction::new(&self.num * &o.num, &self.den * &o.den)
+    }
+}
+
+impl<T: Coefficient + Div<Output = T>> Div for &RationalFunction<T> {
+    type Output = RationalFunction<T>;
+
+    /// Quotient, panics for the zero function as divisor.
+    fn div(self, o: &RationalFunction<T>) -> RationalFunction<T> {
+        RationalFunction::try_new(&self.num * &o.den, &self.den * &o.num)
+            .expect("division by the zero rational function")
+    }
+}
+
+impl<T: Coefficient + Div<Output = T>> Neg for &RationalFunction<T> {
+    type Output = RationalFunction<T>;
+
+    fn neg(self) -> RationalFunction<T> {
+        RationalFunction {
+            num: -&self.num,
+            den: self.den.clone(),
+        }
+    }
+}
+
+// Owned operands forward to the reference versions
+macro_rules! gen_ops {
+    ($($Op:ident $op:ident),*) => {
+        $(
+            impl<T: Coefficient + Div<Output = T>> $Op for RationalFunction<T> {
+                type Output = RationalFunction<T>;
+
+                fn $op(self, o: RationalFunction<T>) -> RationalFunction<T> {
+                    (&self).$op(&o)
+                }
+            }
+        )*
+    };
+}
+
+gen_ops!(Add add, Sub sub, Mul mul, Div div);
+
+impl<T: Coefficient + Div<Output = T>> Neg for RationalFunction<T> {
+    type Output = RationalFunction<T>;
+
+    fn neg(self) -> RationalFunction<T> {
+        -&self
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Partial fractions
+// -----------------------------------------------------------------------------
+
+/// Term a / g^k of a partial fraction decomposition, with g irreducible and monic and
+/// deg a < deg g.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PartialFraction {
+    /// Numerator a
+    pub numerator: Polynomial<Fraction>,
+    /// Irreducible factor g of the denominator
+    pub factor: Polynomial<Fraction>,
+    /// Power k
+    pub power: usize,
+}
+
+impl RationalFunction<Fraction> {
+    /// Partial fraction decomposition p / q = s + Σ a / g^k over the rationals, as the
+    /// polynomial part s and the terms ordered by factor, then by increasing power. The
+    /// denominator is factorized with [`Polynomial::factor`], then each g^m of it gets the
+    /// numerator p (q / g^m)⁻¹ mod g^m, whose digits in base g are the a over g^m, ..., g.
+    ///
+    /// Fails like [`Polynomial::factor`] on the denominator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ctl::math::{frac, Polynomial, RationalFunction};
+    /// let f = |c: &[i32]| Polynomial::new(c.iter().map(|&x| frac(x, 1)).collect());
+    /// // (x³ + 1) / (x² (x - 1)) = 1 + 2 / (x - 1) - 1 / x - 1 / x²
+    /// let r = RationalFunction::new(f(&[1, 0, 0, 1]), f(&[0, 0, -1, 1]));
+    /// let (s, terms) = r.partial_fractions().unwrap();
+    /// ```
+    pub fn partial_fractions(&self) -> Result<(Polynomial<Fraction>, Vec<PartialFraction>)> {
+        let (s, rem) = self.num.div_rem(&self.den);
+        let mut terms = Vec::new();
+        for (g, m) in self.den.factor()?.1 {
+            let gm = (1..m).fold(g.clone(), |acc, _| &acc * &g);
+            let cofactor = &self.den / &gm;
+            let mut r = &(&rem * &inverse_mod(&cofactor, &gm)) % &gm;
+
+            let mut digits = Vec::with_capacity(m);
+            for _ in 0..m {
+                let (q, a) = r.div_rem(&g);
+                digits.push(a);
+                r = q;
+            }
+            for (k, a) in (1..=m).zip(digits.into_iter().rev()) {
+                if !a.is_zero() {
+                    terms.push(PartialFraction {
+                        numerator: a,
+                        factor: g.clone(),
+                        power: k,
+                    });
+                }
+            }
+        }
+        Ok((s, terms))
+    }
+}
+
+/// Inverse of a modulo m for coprime a and m, by the extended Euclidean algorithm.
+fn inverse_mod<T: Coefficient + Div<Output = T>>(
+    a: &Polynomial<T>,
+    m: &Polynomial<T>,
+) -> Polynomial<T> {
+    let (mut r0, mut r1) = (m.clone(), a % m);
+    let (mut s0, mut s1) = (Polynomial::zero(), Polynomial::constant(T::from(1)));
+    while !r1.is_zero() {
+        let (q, r) = r0.div_rem(&r1);
+        let s = &s0 - &(&q * &s1);
+        r0 = r1;
+        r1 = r;
+        s0 = s1;
+        s1 = s;
+    }
+
+    // r0 is the constant gcd, s0 a = r0 mod m
+    let lead = r0.leading_coefficient().unwrap();
+    &s0 * (T::from(1) / lead)
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::frac;
+
+    fn f(c: &[i32]) -> Polynomial<Fraction> {
+        Polynomial::new(c.iter().map(|&x| frac(x, 1)).collect())
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        // Lowest terms with a monic denominator
+        let r = RationalFunction::new(f(&[-1, 0, 1]), f(&[-2, 2]));
+        assert_eq!(
+            r.numerator(),
+            &Polynomial::new(vec![frac(1, 2), frac(1, 2)])
+        );
+        assert_eq!(r.denominator(), &f(&[1]));
+        let z = RationalFunction::new(Polynomial::zero(), f(&[3, 1]));
+        assert_eq!(z, RationalFunction::from(Polynomial::zero()));
+        assert_eq!(
+            RationalFunction::try_new(f(&[1]), Polynomial::zero()),
+            Err(Error::DivisionByZero)
+        );
+
+        // Field operations agree with evaluation
+        let a = RationalFunction::new(f(&[1, 2]), f(&[-3, 0, 1]));
+        let b = RationalFunction::new(f(&[0, 1]), f(&[1, 1]));
+        for x in [frac(1, 2), frac(-3, 4), frac(5, 1)] {
+            let (ax, bx) = (a.eval(x), b.eval(x));
+            assert_eq!((&a + &b).eval(x), (ax + bx).normalize());
+            assert_eq!((&a - &b).eval(x), (ax - bx).normalize());
+            assert_eq!((&a * &b).eval(x), (ax * bx).normalize());
+            assert_eq!((&a / &b).eval(x), (ax / bx).normalize());
+            assert_eq!((-&a).eval(x), (-ax).normalize());
+        }
+        assert_eq!(&(&a / &b) * &b, a);
+        assert_eq!(a.clone() - a.clone(), z);
+        assert_eq!(b.try_eval(frac(-1, 1)), Err(Error::DivisionByZero));
+    }
+
+    #[test]
+    fn test_partial_fractions() {
+        let sum = |s: &Polynomial<Fraction>, terms: &[PartialFraction]| {
+            terms
+                .iter()
+                .fold(RationalFunction::from(s.clone()), |acc, t| {
+                    let gk = (1..t.power).fold(t.factor.clone(), |p, _| &p * &t.factor);
+                    &acc + &RationalFunction::new(t.numerator.clone(), gk)
+                })
+        };
+
+        // (x³ + 1) / (x² (x - 1)) = 1 + 2 / (x - 1) - 1 / x - 1 / x²
+        let r = RationalFunction::new(f(&[1, 0, 0, 1]), f(&[0, 0, -1, 1]));
+        let (s, terms) = r.partial_fractions().unwrap();
+        assert_eq!(s, f(&[1]));
+        let term = |a: &[i32], g: &[i32], k| PartialFraction {
+            numerator: f(a),
+            factor: f(g),
+            power: k,
+        };
+        assert_eq!(
+            terms,
+            [
+                term(&[-1], &[0, 1], 1),
+                term(&[-1], &[0, 1], 2),
+                term(&[2], &[-1, 1], 1)
+            ]
+        );
+
+        // Irreducible quadratics keep linear numerators
+        let den = &(&f(&[1, 0, 1]) * &f(&[1, 0, 1])) * &f(&[2, 1]);
+        let r = RationalFunction::new(f(&[1, -2, 0, 1]), den);
+        let (s, terms) = r.partial_fractions().unwrap();
+        assert!(s.is_zero());
+        assert_eq!(terms.len(), 3);
+        assert!(terms
+            .iter()
+            .all(|t| t.numerator.degree() < t.factor.degree()));
+        assert_eq!(sum(&s, &terms), r);
+
+        let p = RationalFunction::from(f(&[1, 2, 3]));
+        assert_eq!(p.partial_fractions(), Ok((f(&[1, 2, 3]), vec![])));
+    }
+}
@@ -0,0 +1,324 @@
+use super::modular::ModInt;
+use crate::parallel;
+use alloc::vec::Vec;
+use core::ops::{Index, IndexMut, Mul};
+
+// -----------------------------------------------------------------------------
+// Semirings
+// -----------------------------------------------------------------------------
+
+/// Values with an associative addition and a multiplication distributing over it, each with
+/// a neutral element. Zero has to absorb in products.
+pub trait Semiring: Copy {
+    /// Neutral element of the addition
+    fn zero() -> Self;
+    /// Neutral element of the multiplication
+    fn one() -> Self;
+    fn add(self, o: Self) -> Self;
+    fn mul(self, o: Self) -> Self;
+}
+
+/// Tropical semiring adding by `min` and multiplying by `+`, so matrix products combine path
+/// lengths into the shortest one. Zero is infinity, meaning no path.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct MinPlus(pub f64);
+
+/// Adds by `max` and multiplies by `+`, for longest paths. Zero is negative infinity.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct MaxPlus(pub f64);
+
+/// Adds by `or` and multiplies by `and`, for reachability.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Boolean(pub bool);
+
+impl Semiring for MinPlus {
+    fn zero() -> Self {
+        MinPlus(f64::INFINITY)
+    }
+
+    fn one() -> Self {
+        MinPlus(0.0)
+    }
+
+    fn add(self, o: Self) -> Self {
+        MinPlus(self.0.min(o.0))
+    }
+
+    fn mul(self, o: Self) -> Self {
+        MinPlus(self.0 + o.0)
+    }
+}
+
+impl Semiring for MaxPlus {
+    fn zero() -> Self {
+        MaxPlus(f64::NEG_INFINITY)
+    }
+
+    fn one() -> Self {
+        MaxPlus(0.0)
+    }
+
+    fn add(self, o: Self) -> Self {
+        MaxPlus(self.0.max(o.0))
+    }
+
+    fn mul(self, o: Self) -> Self {
+        MaxPlus(self.0 + o.0)
+    }
+}
+
+impl Semiring for Boolean {
+    fn zero() -> Self {
+        Boolean(false)
+    }
+
+    fn one() -> Self {
+        Boolean(true)
+    }
+
+    fn add(self, o: Self) -> Self {
+        Boolean(self.0 || o.0)
+    }
+
+    fn mul(self, o: Self) -> Self {
+        Boolean(self.0 && o.0)
+    }
+}
+
+/// Ordinary arithmetic, e.g. for counting walks.
+impl<const M: u64> Semiring for ModInt<M> {
+    fn zero() -> Self {
+        ModInt::new(0)
+    }
+
+    fn one() -> Self {
+        ModInt::new(1)
+    }
+
+    fn add(self, o: Self) -> Self {
+        self + o
+    }
+
+    fn mul(self, o: Self) -> Self {
+        self * o
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Matrix
+// -----------------------------------------------------------------------------
+
+/// Dense row major matrix over a semiring, indexed by `(row, column)`. Powers of an adjacency
+/// matrix answer questions about walks of exactly k edges: the shortest ones over [`MinPlus`],
+/// the existence over [`Boolean`] and the count over [`ModInt`].
+///
+/// # Examples
+///
+/// ```
+/// use ctl::math::semiring::{Matrix, MinPlus, Semiring};
+/// let inf = MinPlus::zero().0;
+/// let edges = [[inf, 1.0, 4.0], [inf, inf, 2.0], [1.0, inf, inf]];
+/// let m = Matrix::from_fn(3, 3, |r, c| MinPlus(edges[r][c]));
+/// let d = m.pow(2)[(0, 2)]; // MinPlus(3.0), 0 -> 1 -> 2
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Matrix<S> {
+    rows: usize,
+    cols: usize,
+    data: Vec<S>,
+}
+
+impl<S: Semiring> Matrix<S> {
+    /// Matrix filled with zero.
+    pub fn new(rows: usize, cols: usize) -> Self {
+        Matrix {
+            rows,
+            cols,
+            data: vec![S::zero(); rows * cols],
+        }
+    }
+
+    /// Square matrix with one on the diagonal and zero elsewhere.
+    pub fn identity(n: usize) -> Self {
+        Self::from_fn(n, n, |r, c| if r == c { S::one() } else { S::zero() })
+    }
+
+    /// Creates the matrix from a function of the row and column.
+    pub fn from_fn<F: FnMut(usize, usize) -> S>(rows: usize, cols: usize, mut f: F) -> Self {
+        let data = (0..rows * cols).map(|i| f(i / cols, i % cols)).collect();
+        Matrix { rows, cols, data }
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// Raises the square matrix to the power `exp` by repeated squaring in O(n³ log exp).
+    pub fn pow(&self, mut exp: u64) -> Self {
+        assert_eq!(self.rows, self.cols, "matrix is not square");
+
+        let mut result = Self::identity(self.rows);
+        let mut base = self.clone();
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = &result * &base;
+            }
+            base = &base * &base;
+            exp >>= 1;
+        }
+
+        result
+    }
+
+    /// Matrix product computing blocks of rows on all cores with the `parallel` feature. Every
+    /// entry is summed in the same order as by `*`, so the results are identical. Panics if the
+    /// sizes don't match.
+    pub fn par_mul(&self, o: &Matrix<S>) -> Matrix<S>
+    where
+        S: Send + Sync,
+    {
+        assert_eq!(self.cols, o.rows, "matrix sizes don't match");
+
+        // Roughly the same number of semiring operations per block
+        let rows = ((1 << 16) / (self.cols * o.cols).max(1)).max(1);
+
+        let mut m = Matrix::<S>::new(self.rows, o.cols);
+        let cols = o.cols.max(1);
+        parallel::for_chunks_mut(&mut m.data, rows * cols, |start, block| {
+            for (i, row) in block.chunks_mut(cols).enumerate() {
+                let r = start / cols + i;
+                for k in 0..self.cols {
+                    let a = self[(r, k)];
+                    for (c, x) in row.iter_mut().enumerate() {
+                        *x = x.add(a.mul(o[(k, c)]));
+                    }
+                }
+            }
+        });
+
+        m
+    }
+}
+
+impl<S> Index<(usize, usize)> for Matrix<S> {
+    type Output = S;
+
+    fn index(&self, (r, c): (usize, usize)) -> &S {
+        assert!(r < self.rows && c < self.cols, "index out of bounds");
+        &self.data[r * self.cols + c]
+    }
+}
+
+impl<S> IndexMut<(usize, usize)> for Matrix<S> {
+    fn index_mut(&mut self, (r, c): (usize, usize)) -> &mut S {
+        assert!(r < self.rows && c < self.cols, "index out of bounds");
+        &mut self.data[r * self.cols + c]
+    }
+}
+
+impl<S: Semiring> Mul for &Matrix<S> {
+    type Output = Matrix<S>;
+
+    /// Matrix product with the semiring operations. Panics if the sizes don't match.
+    fn mul(self, o: &Matrix<S>) -> Matrix<S> {
+        assert_eq!(self.cols, o.rows, "matrix sizes don't match");
+
+        let mut m = Matrix::<S>::new(self.rows, o.cols);
+        for r in 0..self.rows {
+            for k in 0..self.cols {
+                let a = self[(r, k)];
+                for c in 0..o.cols {
+                    m[(r, c)] = m[(r, c)].add(a.mul(o[(k, c)]));
+                }
+            }
+        }
+
+        m
+    }
+}
+
+impl<S: Semiring> Mul for Matrix<S> {
+    type Output = Matrix<S>;
+
+    fn mul(self, o: Matrix<S>) -> Matrix<S> {
+        &self * &o
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_semiring_matrix() {
+        // Cycle 0 -> 1 -> 2 -> 0 with a shortcut 0 -> 2
+        let edges = [(0, 1, 1.0), (1, 2, 2.0), (2, 0, 3.0), (0, 2, 5.0)];
+        let mut exact = Matrix::new(3, 3);
+        for &(a, b, w) in &edges {
+            exact[(a, b)] = MinPlus(w);
+        }
+
+        assert_eq!(exact.pow(0), Matrix::identity(3));
+        assert_eq!(exact.pow(1), exact);
+        assert_eq!(exact.pow(2)[(0, 2)], MinPlus(3.0));
+        assert_eq!(exact.pow(2)[(0, 1)], MinPlus(f64::INFINITY));
+        assert_eq!(exact.pow(3)[(0, 0)], MinPlus(6.0));
+        assert_eq!(exact.pow(5)[(0, 2)], MinPlus(9.0));
+
+        // A loop of weight 0 at 0 allows waiting, giving paths of at most k edges from 0
+        let mut waiting = exact.clone();
+        waiting[(0, 0)] = MinPlus(0.0);
+        assert_eq!(waiting.pow(5)[(0, 2)], MinPlus(3.0));
+
+        let longest = Matrix::from_fn(3, 3, |r, c| {
+            let w = edges.iter().find(|e| (e.0, e.1) == (r, c)).map(|e| e.2);
+            MaxPlus(w.unwrap_or(f64::NEG_INFINITY))
+        });
+        assert_eq!(longest.pow(5)[(0, 2)], MaxPlus(21.0));
+
+        // Reachability and walk counts on a path 0 -> 1 -> 2
+        let reach = Matrix::from_fn(3, 3, |r, c| Boolean(c == r + 1));
+        assert_eq!(reach.pow(2)[(0, 2)], Boolean(true));
+        assert_eq!(reach.pow(3), Matrix::new(3, 3));
+
+        // Fibonacci numbers count the walks in [[1, 1], [1, 0]]
+        type Mint = ModInt<1_000_000_007>;
+        let fib = Matrix::from_fn(2, 2, |r, c| Mint::new((r + c < 2) as u64));
+        assert_eq!(fib.pow(90)[(0, 1)], Mint::new(2_880_067_194_370_816_120));
+
+        // Rectangular products
+        let a = Matrix::from_fn(2, 3, |r, c| Mint::new((r * 3 + c) as u64));
+        let b = Matrix::from_fn(3, 1, |_, _| Mint::new(1));
+        let ab = a * b;
+        assert_eq!((ab.rows(), ab.cols()), (2, 1));
+        assert_eq!((ab[(0, 0)], ab[(1, 0)]), (Mint::new(3), Mint::new(12)));
+    }
+
+    #[test]
+    fn test_par_mul() {
+        // Several blocks of rows, with floats to check the summation order
+        let a = Matrix::from_fn(150, 70, |r, c| {
+            MinPlus(((r * 31 + c * 17) % 97) as f64 / 7.0)
+        });
+        let b = Matrix::from_fn(70, 90, |r, c| {
+            MinPlus(((r * 13 + c * 29) % 89) as f64 / 3.0)
+        });
+        assert_eq!(a.par_mul(&b), &a * &b);
+
+        type Mint = ModInt<998_244_353>;
+        let a = Matrix::from_fn(40, 300, |r, c| Mint::new((r * c) as u64 + 1));
+        let b = Matrix::from_fn(300, 1, |r, _| Mint::new(r as u64));
+        assert_eq!(a.par_mul(&b), &a * &b);
+
+        let empty = Matrix::<Boolean>::new(3, 0);
+        assert_eq!(empty.par_mul(&Matrix::new(0, 4)), Matrix::new(3, 4));
+    }
+}
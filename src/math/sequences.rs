@@ -0,0 +1,189 @@
+use super::modular::{mod_add, mod_mul, mod_sub};
+use crate::{Error, Result};
+
+// -----------------------------------------------------------------------------
+// Fibonacci numbers
+// -----------------------------------------------------------------------------
+
+/// Calculates the n-th Fibonacci number with F(0) = 0 and F(1) = 1. Panics if it doesn't fit
+/// into a `u128`, which happens from F(187) on. Use [`try_fibonacci`] to handle it.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::math::sequences::fibonacci;
+/// let x = fibonacci(10); // 55
+/// let y = fibonacci(186); // 332825110087067562321196029789634457848
+/// ```
+pub fn fibonacci(n: u64) -> u128 {
+    try_fibonacci(n).expect("fibonacci number does not fit into u128")
+}
+
+/// Fallible version of [`fibonacci`]. Fails with [`Error::Overflow`] instead of panicking.
+pub fn try_fibonacci(n: u64) -> Result<u128> {
+    // Only 187 values fit, so adding up is as fast as anything. Starts from F(0) and
+    // F(-1) = 1 to not compute past F(n).
+    let (mut a, mut b) = (0u128, 1u128);
+    for _ in 0..n {
+        let next = a.checked_add(b).ok_or(Error::Overflow)?;
+        b = a;
+        a = next;
+    }
+    Ok(a)
+}
+
+/// Calculates F(n) mod m by fast doubling in O(log n), using F(2k) = F(k) (2 F(k + 1) - F(k))
+/// and F(2k + 1) = F(k)² + F(k + 1)².
+///
+/// # Arguments
+///
+/// * `n` - Index of the Fibonacci number
+/// * `m` - Modulus
+///
+/// # Examples
+///
+/// ```
+/// use ctl::math::sequences::fibonacci_mod;
+/// let x = fibonacci_mod(1_000_000_000_000_000_000, 1_000_000_007); // 209783453
+/// ```
+pub fn fibonacci_mod(n: u64, m: u64) -> u64 {
+    assert!(m != 0, "modulus is 0");
+
+    // (F(k), F(k + 1)) for the leading bits k of n
+    let (mut a, mut b) = (0, 1 % m);
+    for i in (0..64 - n.leading_zeros()).rev() {
+        let c = mod_mul(a, mod_sub(mod_add(b, b, m), a, m), m);
+        let d = mod_add(mod_mul(a, a, m), mod_mul(b, b, m), m);
+
+        if n >> i & 1 == 1 {
+            a = d;
+            b = mod_add(c, d, m);
+        } else {
+            a = c;
+            b = d;
+        }
+    }
+
+    a
+}
+
+// -----------------------------------------------------------------------------
+// Lucas sequences
+// -----------------------------------------------------------------------------
+
+/// Evaluates the Lucas sequences U(n) and V(n) of the parameters P and Q modulo m in
+/// O(log n), defined by X(n + 2) = P X(n + 1) - Q X(n) with U(0) = 0, U(1) = 1, V(0) = 2 and
+/// V(1) = P. P = 1, Q = -1 gives the Fibonacci and Lucas numbers, P = 3, Q = 2 gives 2^n - 1
+/// and 2^n + 1. Works for any modulus, as it powers the companion matrix instead of halving.
+///
+/// # Arguments
+///
+/// * `p` - Parameter P
+/// * `q` - Parameter Q
+/// * `n` - Index
+/// * `m` - Modulus
+///
+/// # Examples
+///
+/// ```
+/// use ctl::math::sequences::lucas_sequence;
+/// let (u, v) = lucas_sequence(1, -1, 10, 1000); // (55, 123)
+/// let (a, b) = lucas_sequence(3, 2, 10, 1 << 20); // (1023, 1025)
+/// ```
+pub fn lucas_sequence(p: i64, q: i64, n: u64, m: u64) -> (u64, u64) {
+    assert!(m != 0, "modulus is 0");
+    let p = (p as i128).rem_euclid(m as i128) as u64;
+    let neg_q = (-(q as i128)).rem_euclid(m as i128) as u64;
+
+    // [[P, -Q], [1, 0]]^n = [[U(n + 1), -Q U(n)], [U(n), -Q U(n - 1)]]
+    let mul = |x: [u64; 4], y: [u64; 4]| {
+        let dot = |a, b, c, d| mod_add(mod_mul(a, b, m), mod_mul(c, d, m), m);
+        [
+            dot(x[0], y[0], x[1], y[2]),
+            dot(x[0], y[1], x[1], y[3]),
+            dot(x[2], y[0], x[3], y[2]),
+            dot(x[2], y[1], x[3], y[3]),
+        ]
+    };
+
+    let mut result = [1 % m, 0, 0, 1 % m];
+    let mut base = [p, neg_q, 1 % m, 0];
+    let mut exp = n;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mul(result, base);
+        }
+        base = mul(base, base);
+        exp >>= 1;
+    }
+
+    // V(n) = 2 U(n + 1) - P U(n)
+    let (u, u_next) = (result[2], result[0]);
+    let v = mod_sub(mod_add(u_next, u_next, m), mod_mul(p, u, m), m);
+    (u, v)
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fibonacci() {
+        assert_eq!(fibonacci(0), 0);
+        assert_eq!(fibonacci(1), 1);
+        assert_eq!(fibonacci(90), 2_880_067_194_370_816_120);
+        assert_eq!(
+            fibonacci(186),
+            332_825_110_087_067_562_321_196_029_789_634_457_848
+        );
+        assert_eq!(try_fibonacci(187), Err(Error::Overflow));
+
+        for n in 0..=186 {
+            for &m in &[1u64, 2, 10, 1_000_000_007, u64::MAX] {
+                assert_eq!(fibonacci_mod(n, m) as u128, fibonacci(n) % m as u128);
+            }
+        }
+        assert_eq!(
+            fibonacci_mod(1_000_000_000_000_000_000, 1_000_000_007),
+            209_783_453
+        );
+        // The Pisano period of 10 is 60
+        assert_eq!(
+            fibonacci_mod(u64::MAX, 10),
+            fibonacci_mod(u64::MAX % 60, 10)
+        );
+    }
+
+    #[test]
+    fn test_lucas_sequence() {
+        let m = u64::MAX - 58;
+        for n in 0..100u64 {
+            let (u, _) = lucas_sequence(1, -1, n, m);
+            assert_eq!(u, fibonacci_mod(n, m));
+
+            // Against the recurrence directly, with P = -2 and Q = 5
+            let r = |x: i128| x.rem_euclid(1_000_003);
+            let (mut u0, mut u1, mut v0, mut v1) = (0, 1, 2, r(-2));
+            for _ in 0..n {
+                let (u2, v2) = (r(-2 * u1 - 5 * u0), r(-2 * v1 - 5 * v0));
+                u0 = u1;
+                u1 = u2;
+                v0 = v1;
+                v1 = v2;
+            }
+            let expected = (u0 as u64, v0 as u64);
+            assert_eq!(lucas_sequence(-2, 5, n, 1_000_003), expected);
+        }
+
+        assert_eq!(lucas_sequence(1, -1, 10, 1000), (55, 123));
+        assert_eq!(
+            lucas_sequence(3, 2, 62, u64::MAX),
+            ((1 << 62) - 1, (1 << 62) + 1)
+        );
+        assert_eq!(lucas_sequence(7, 3, 0, 1), (0, 0));
+    }
+}
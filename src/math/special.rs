@@ -0,0 +1,400 @@
+use core::f64::consts::PI;
+
+// -----------------------------------------------------------------------------
+// Gamma
+// -----------------------------------------------------------------------------
+
+/// Lanczos approximation coefficients for g = 7, n = 9.
+const LANCZOS: [f64; 9] = [
+    0.999_999_999_999_809_9,
+    676.520_368_121_885_1,
+    -1_259.139_216_722_402_8,
+    771.323_428_777_653_1,
+    -176.615_029_162_140_6,
+    12.507_343_278_686_905,
+    -0.138_571_095_265_720_12,
+    9.984_369_578_019_572e-6,
+    1.505_632_735_149_311_6e-7,
+];
+
+/// Calculates the natural logarithm of the absolute value of the gamma function using the
+/// Lanczos approximation (g = 7, n = 9) and the reflection formula for x < 0.5. The absolute
+/// error is below 1e-12 for x in (0, 100]. Returns infinity at the poles 0, -1, -2, ...
+///
+/// # Arguments
+///
+/// * `x` - Evaluation point
+///
+/// # Examples
+///
+/// ```
+/// use ctl::math::lgamma;
+/// let x = lgamma(10.0); // ln(362880) ~ 12.8018
+/// ```
+pub fn lgamma(x: f64) -> f64 {
+    if x < 0.5 {
+        // Reflection formula
+        return (PI / (PI * x).sin().abs()).ln() - lgamma(1.0 - x);
+    }
+
+    let x = x - 1.0;
+    let t = x + 7.5;
+    let sum = LANCZOS[1..]
+        .iter()
+        .enumerate()
+        .fold(LANCZOS[0], |s, (i, &c)| s + c / (x + i as f64 + 1.0));
+
+    0.5 * (2.0 * PI).ln() + (x + 0.5) * t.ln() - t + sum.ln()
+}
+
+/// Calculates the gamma function. Positive integers up to 171 are computed exactly as
+/// factorials, everything else through [`lgamma`] with a relative error below 1e-11 for
+/// |x| <= 50. Returns NaN at the poles 0, -1, -2, ...
+///
+/// # Arguments
+///
+/// * `x` - Evaluation point
+///
+/// # Examples
+///
+/// ```
+/// use ctl::math::gamma;
+/// let a = gamma(5.0); // 24.0
+/// let b = gamma(0.5); // sqrt(pi)
+/// ```
+pub fn gamma(x: f64) -> f64 {
+    if x <= 0.0 && x == x.floor() {
+        return f64::NAN;
+    }
+
+    if x == x.floor() && x <= 171.0 {
+        return (2..x as u32).fold(1.0, |p, k| p * k as f64);
+    }
+
+    let sign = if x < 0.0 && (x.floor() as i64) % 2 != 0 {
+        -1.0
+    } else {
+        1.0
+    };
+
+    sign * lgamma(x).exp()
+}
+
+const EPS: f64 = 1e-15;
+const TINY: f64 = 1e-300;
+const MAX_ITERATIONS: usize = 500;
+
+/// Regularized lower incomplete gamma function P(a, x) through its power series.
+fn gamma_p_series(a: f64, x: f64) -> f64 {
+    let mut term = 1.0 / a;
+    let mut sum = term;
+
+    for n in 1..MAX_ITERATIONS {
+        term *= x / (a + n as f64);
+        sum += term;
+
+        if term.abs() < sum.abs() * EPS {
+            break;
+        }
+    }
+
+    sum * (-x + a * x.ln() - lgamma(a)).exp()
+}
+
+/// Regularized upper incomplete gamma function Q(a, x) through its continued fraction (modified
+/// Lentz method).
+fn gamma_q_fraction(a: f64, x: f64) -> f64 {
+    let mut b = x + 1.0 - a;
+    let mut c = 1.0 / TINY;
+    let mut d = 1.0 / b;
+    let mut h = d;
+
+    for i in 1..MAX_ITERATIONS {
+        let an = -(i as f64) * (i as f64 - a);
+        b += 2.0;
+
+        d = an * d + b;
+        if d.abs() < TINY {
+            d = TINY;
+        }
+        c = b + an / c;
+        if c.abs() < TINY {
+            c = TINY;
+        }
+
+        d = 1.0 / d;
+        let delta = d * c;
+        h *= delta;
+
+        if (delta - 1.0).abs() < EPS {
+            break;
+        }
+    }
+
+    (-x + a * x.ln() - lgamma(a)).exp() * h
+}
+
+/// Calculates the regularized lower incomplete gamma function P(a, x), the CDF of the gamma
+/// distribution. Uses the power series for x < a + 1 and the continued fraction otherwise, the
+/// absolute error is below 1e-13 for a in (0, 100].
+///
+/// # Arguments
+///
+/// * `a` - Shape parameter, positive
+/// * `x` - Upper integration limit, non-negative
+///
+/// # Examples
+///
+/// ```
+/// use ctl::math::gamma_p;
+/// let p = gamma_p(1.0, 2.0); // 1 - e^-2
+/// ```
+pub fn gamma_p(a: f64, x: f64) -> f64 {
+    if x <= 0.0 {
+        0.0
+    } else if x < a + 1.0 {
+        gamma_p_series(a, x)
+    } else {
+        1.0 - gamma_q_fraction(a, x)
+    }
+}
+
+/// Calculates the regularized upper incomplete gamma function Q(a, x) = 1 - P(a, x). The
+/// absolute error is below 1e-13 and the relative error below 1e-12 for a in (0, 100], so tiny
+/// tail probabilities keep their precision.
+///
+/// # Arguments
+///
+/// * `a` - Shape parameter, positive
+/// * `x` - Lower integration limit, non-negative
+///
+/// # Examples
+///
+/// ```
+/// use ctl::math::gamma_q;
+/// let q = gamma_q(1.0, 2.0); // e^-2
+/// ```
+pub fn gamma_q(a: f64, x: f64) -> f64 {
+    if x <= 0.0 {
+        1.0
+    } else if x < a + 1.0 {
+        1.0 - gamma_p_series(a, x)
+    } else {
+        gamma_q_fraction(a, x)
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Error function
+// -----------------------------------------------------------------------------
+
+/// Calculates the error function through erf(x) = sign(x) P(1/2, x^2). The relative error is
+/// below 1e-14.
+///
+/// # Arguments
+///
+/// * `x` - Evaluation point
+///
+/// # Examples
+///
+/// ```
+/// use ctl::math::erf;
+/// let y = erf(1.0); // 0.8427007929497149
+/// ```
+pub fn erf(x: f64) -> f64 {
+    if x >= 0.0 {
+        gamma_p(0.5, x * x)
+    } else {
+        -gamma_p(0.5, x * x)
+    }
+}
+
+/// Calculates the complementary error function 1 - erf(x) through erfc(x) = Q(1/2, x^2),
+/// which keeps the relative error below 1e-12 far into the tail (about x < 26, where the result
+/// underflows).
+///
+/// # Arguments
+///
+/// * `x` - Evaluation point
+///
+/// # Examples
+///
+/// ```
+/// use ctl::math::erfc;
+/// let y = erfc(5.0); // 1.5374597944280349e-12
+/// ```
+pub fn erfc(x: f64) -> f64 {
+    if x >= 0.0 {
+        gamma_q(0.5, x * x)
+    } else {
+        2.0 - gamma_q(0.5, x * x)
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Beta
+// -----------------------------------------------------------------------------
+
+/// Continued fraction of the incomplete beta function (modified Lentz method).
+fn beta_fraction(a: f64, b: f64, x: f64) -> f64 {
+    let (qab, qap, qam) = (a + b, a + 1.0, a - 1.0);
+    let mut c = 1.0;
+    let mut d = 1.0 - qab * x / qap;
+    if d.abs() < TINY {
+        d = TINY;
+    }
+    d = 1.0 / d;
+    let mut h = d;
+
+    for m in 1..MAX_ITERATIONS {
+        let m = m as f64;
+        let m2 = 2.0 * m;
+
+        // Even step
+        let aa = m * (b - m) * x / ((qam + m2) * (a + m2));
+        d = 1.0 + aa * d;
+        if d.abs() < TINY {
+            d = TINY;
+        }
+        c = 1.0 + aa / c;
+        if c.abs() < TINY {
+            c = TINY;
+        }
+        d = 1.0 / d;
+        h *= d * c;
+
+        // Odd step
+        let aa = -(a + m) * (qab + m) * x / ((a + m2) * (qap + m2));
+        d = 1.0 + aa * d;
+        if d.abs() < TINY {
+            d = TINY;
+        }
+        c = 1.0 + aa / c;
+        if c.abs() < TINY {
+            c = TINY;
+        }
+        d = 1.0 / d;
+        let delta = d * c;
+        h *= delta;
+
+        if (delta - 1.0).abs() < EPS {
+            break;
+        }
+    }
+
+    h
+}
+
+/// Calculates the beta function B(a, b) = Γ(a) Γ(b) / Γ(a + b) through [`lgamma`] for
+/// positive arguments.
+///
+/// # Arguments
+///
+/// * `a` - First parameter, positive
+/// * `b` - Second parameter, positive
+///
+/// # Examples
+///
+/// ```
+/// use ctl::math::beta;
+/// let y = beta(2.0, 3.0); // 1 / 12
+/// ```
+pub fn beta(a: f64, b: f64) -> f64 {
+    (lgamma(a) + lgamma(b) - lgamma(a + b)).exp()
+}
+
+/// Calculates the regularized incomplete beta function I_x(a, b), the CDF of the beta
+/// distribution, through its continued fraction. The absolute error is below 1e-12 for a, b in
+/// (0, 100].
+///
+/// # Arguments
+///
+/// * `a` - First shape parameter, positive
+/// * `b` - Second shape parameter, positive
+/// * `x` - Upper integration limit in [0, 1]
+///
+/// # Examples
+///
+/// ```
+/// use ctl::math::beta_inc;
+/// let y = beta_inc(2.0, 3.0, 0.4); // 0.5248
+/// ```
+pub fn beta_inc(a: f64, b: f64, x: f64) -> f64 {
+    if x <= 0.0 {
+        return 0.0;
+    }
+    if x >= 1.0 {
+        return 1.0;
+    }
+
+    let front = (lgamma(a + b) - lgamma(a) - lgamma(b) + a * x.ln() + b * (1.0 - x).ln()).exp();
+
+    // The fraction converges quickly for x below the mean, use the symmetry otherwise
+    if x < (a + 1.0) / (a + b + 2.0) {
+        front * beta_fraction(a, b, x) / a
+    } else {
+        1.0 - front * beta_fraction(b, a, 1.0 - x) / b
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: f64, b: f64, rel: f64) -> bool {
+        (a - b).abs() <= rel * b.abs()
+    }
+
+    #[test]
+    fn test_gamma() {
+        assert_eq!(gamma(1.0), 1.0);
+        assert_eq!(gamma(10.0), 362_880.0);
+        assert!(close(gamma(0.5), core::f64::consts::PI.sqrt(), 1e-14));
+        assert!(close(
+            gamma(-1.5),
+            4.0 / 3.0 * core::f64::consts::PI.sqrt(),
+            1e-14
+        ));
+        assert!(close(
+            gamma(-2.5),
+            -8.0 / 15.0 * core::f64::consts::PI.sqrt(),
+            1e-14
+        ));
+        assert!(gamma(-3.0).is_nan());
+
+        assert!(close(lgamma(100.0), 359.134_205_369_575_4, 1e-14));
+        assert!(close(
+            lgamma(-0.5),
+            (2.0 * core::f64::consts::PI.sqrt()).ln(),
+            1e-14
+        ));
+        assert!(close(beta(2.0, 3.0), 1.0 / 12.0, 1e-14));
+    }
+
+    #[test]
+    fn test_incomplete() {
+        assert!(close(gamma_p(1.0, 2.0), 1.0 - (-2.0f64).exp(), 1e-14));
+        assert!(close(gamma_q(1.0, 2.0), (-2.0f64).exp(), 1e-14));
+        assert!(close(gamma_q(3.0, 50.0), 1_301.0 * (-50.0f64).exp(), 1e-12));
+        assert_eq!(gamma_p(2.0, 0.0), 0.0);
+
+        assert!(close(erf(1.0), 0.842_700_792_949_714_9, 1e-14));
+        assert!(close(erf(-0.5), -0.520_499_877_813_046_5, 1e-14));
+        assert!(close(erfc(5.0), 1.537_459_794_428_035e-12, 1e-12));
+
+        // I_x(2, 3) = 6x^2 - 8x^3 + 3x^4
+        let x: f64 = 0.4;
+        let exact = 6.0 * x * x - 8.0 * x.powi(3) + 3.0 * x.powi(4);
+        assert!(close(beta_inc(2.0, 3.0, x), exact, 1e-13));
+        assert!(close(
+            beta_inc(3.0, 2.0, 0.9),
+            1.0 - beta_inc(2.0, 3.0, 0.1),
+            1e-13
+        ));
+        assert_eq!(beta_inc(1.0, 1.0, 1.0), 1.0);
+    }
+}
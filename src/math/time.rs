@@ -0,0 +1,202 @@
+use super::{frac, Fraction};
+use crate::{Error, Result};
+use core::convert::TryFrom;
+use core::time::Duration;
+
+const NANOS_PER_SEC: u128 = 1_000_000_000;
+
+fn gcd_u128(mut a: u128, mut b: u128) -> u128 {
+    while b != 0 {
+        let t = a % b;
+        a = b;
+        b = t;
+    }
+    a
+}
+
+/// Numerator and denominator of a non-negative fraction with a positive denominator.
+fn unsigned_parts(f: Fraction) -> Result<(u128, u128)> {
+    if f.d == 0 {
+        return Err(Error::DivisionByZero);
+    }
+    if (f.q < 0) != (f.d < 0) && f.q != 0 {
+        return Err(Error::Domain);
+    }
+
+    Ok((f.q.unsigned_abs() as u128, f.d.unsigned_abs() as u128))
+}
+
+/// Whole nanoseconds of `ticks * q / d` seconds, rounded down.
+fn duration_of(ticks: u128, q: u128, d: u128) -> Duration {
+    let nanos = ticks * q * NANOS_PER_SEC / d;
+
+    Duration::new(
+        (nanos / NANOS_PER_SEC) as u64,
+        (nanos % NANOS_PER_SEC) as u32,
+    )
+}
+
+// -----------------------------------------------------------------------------
+// Conversions
+// -----------------------------------------------------------------------------
+
+/// Converts seconds given as a fraction into a duration, rounding down to whole nanoseconds.
+/// Fails for zero denominators and negative values.
+///
+/// # Examples
+///
+/// ```
+/// use core::convert::TryFrom;
+/// use core::time::Duration;
+/// use ctl::math::frac;
+/// let d = Duration::try_from(frac(1, 60)); // Ok(16.666666ms)
+/// ```
+impl TryFrom<Fraction> for Duration {
+    type Error = Error;
+
+    fn try_from(f: Fraction) -> Result<Duration> {
+        let (q, d) = unsigned_parts(f)?;
+        Ok(duration_of(1, q, d))
+    }
+}
+
+/// Converts a duration into reduced fractional seconds. Fails with [`Error::Overflow`] if they
+/// don't fit, e.g. for durations beyond 2^31 seconds or odd nanosecond counts above 2 seconds.
+///
+/// # Examples
+///
+/// ```
+/// use core::convert::TryFrom;
+/// use core::time::Duration;
+/// use ctl::math::Fraction;
+/// let f = Fraction::try_from(Duration::from_millis(250)); // Ok(Fraction { q: 1, d: 4 })
+/// ```
+impl TryFrom<Duration> for Fraction {
+    type Error = Error;
+
+    fn try_from(t: Duration) -> Result<Fraction> {
+        let nanos = t.as_nanos();
+        let g = gcd_u128(nanos, NANOS_PER_SEC);
+
+        let q = i32::try_from(nanos / g).map_err(|_| Error::Overflow)?;
+        Ok(frac(q, (NANOS_PER_SEC / g) as i32))
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Step clock
+// -----------------------------------------------------------------------------
+
+/// Clock advancing in fixed steps of exact fractional seconds, e.g. 1/60 s frames. The elapsed
+/// time is computed from the step count instead of summing rounded durations, so it never
+/// drifts.
+///
+/// # Examples
+///
+/// ```
+/// use core::time::Duration;
+/// use ctl::math::{frac, StepClock};
+/// let mut clock = StepClock::new(frac(1, 60));
+/// clock.advance(60 * 60);
+/// let t = clock.elapsed(); // exactly 60 s
+///
+/// // Fixed timestep loop catching up with the real time
+/// let n = clock.catch_up(Duration::from_millis(60_050)); // 3 steps
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct StepClock {
+    q: u128,
+    d: u128,
+    ticks: u64,
+}
+
+impl StepClock {
+    /// Clock at 0 with a positive step length in seconds.
+    pub fn new(step: Fraction) -> Self {
+        let (q, d) = unsigned_parts(step).expect("step must be a valid positive fraction");
+        assert!(q > 0, "step must be positive");
+
+        StepClock { q, d, ticks: 0 }
+    }
+
+    /// Number of steps taken so far.
+    pub fn ticks(&self) -> u64 {
+        self.ticks
+    }
+
+    /// Step length rounded down to whole nanoseconds.
+    pub fn step(&self) -> Duration {
+        duration_of(1, self.q, self.d)
+    }
+
+    pub fn advance(&mut self, steps: u64) {
+        self.ticks += steps;
+    }
+
+    /// Time covered by the steps so far, rounded down to whole nanoseconds.
+    pub fn elapsed(&self) -> Duration {
+        duration_of(self.ticks as u128, self.q, self.d)
+    }
+
+    /// Number of whole steps fitting into the time.
+    pub fn steps_in(&self, t: Duration) -> u64 {
+        (t.as_nanos() * self.d / (self.q * NANOS_PER_SEC)) as u64
+    }
+
+    /// Advances to the last step not after `now`, returning the number of steps taken. Returns
+    /// 0 if the clock is already ahead.
+    pub fn catch_up(&mut self, now: Duration) -> u64 {
+        let n = self.steps_in(now).saturating_sub(self.ticks);
+        self.ticks += n;
+        n
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_conversions() {
+        let d = Duration::try_from(frac(1, 60)).unwrap();
+        assert_eq!(d, Duration::from_nanos(16_666_666));
+        assert_eq!(
+            Duration::try_from(frac(-3, -2)),
+            Ok(Duration::from_millis(1500))
+        );
+        assert_eq!(Duration::try_from(frac(-1, 2)), Err(Error::Domain));
+        assert_eq!(Duration::try_from(frac(1, 0)), Err(Error::DivisionByZero));
+
+        let f = Fraction::try_from(Duration::from_millis(250)).unwrap();
+        assert_eq!((f.q, f.d), (1, 4));
+        let f = Fraction::try_from(Duration::from_secs(90)).unwrap();
+        assert_eq!((f.q, f.d), (90, 1));
+        assert_eq!(
+            Fraction::try_from(Duration::from_nanos(3_000_000_001)),
+            Err(Error::Overflow)
+        );
+    }
+
+    #[test]
+    fn test_step_clock() {
+        let mut clock = StepClock::new(frac(1, 60));
+
+        // Summing the rounded step drifts, the clock doesn't
+        clock.advance(60 * 3600);
+        assert_eq!(clock.elapsed(), Duration::from_secs(3600));
+        assert!(clock.step() * (60 * 3600) < Duration::from_secs(3600));
+
+        assert_eq!(clock.catch_up(Duration::from_secs(3600)), 0);
+        assert_eq!(clock.catch_up(Duration::from_millis(3_600_050)), 3);
+        assert_eq!(clock.ticks(), 60 * 3600 + 3);
+        assert_eq!(clock.catch_up(Duration::from_secs(10)), 0);
+
+        let clock = StepClock::new(frac(2, 3));
+        assert_eq!(clock.steps_in(Duration::from_secs(2)), 3);
+        assert_eq!(clock.steps_in(Duration::from_nanos(1_999_999_999)), 2);
+    }
+}
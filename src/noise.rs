@@ -0,0 +1,508 @@
+use crate::geometry::Point;
+use crate::random::Rng;
+
+fn floor(x: f64) -> i64 {
+    let i = x as i64;
+    if (i as f64) > x {
+        i - 1
+    } else {
+        i
+    }
+}
+
+fn fade(t: f64) -> f64 {
+    t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
+
+fn lerp(t: f64, a: f64, b: f64) -> f64 {
+    a + t * (b - a)
+}
+
+/// Shuffled byte table repeated twice so that hashes of neighbouring lattice points never need
+/// wrapping.
+#[derive(Clone)]
+struct Permutation([u8; 512]);
+
+impl Permutation {
+    fn new(rng: &mut Rng) -> Self {
+        let mut p = [0u8; 512];
+
+        for (i, v) in p.iter_mut().take(256).enumerate() {
+            *v = i as u8;
+        }
+
+        rng.shuffle(&mut p[..256]);
+
+        for i in 0..256 {
+            p[i + 256] = p[i];
+        }
+
+        Permutation(p)
+    }
+
+    fn at(&self, i: i64) -> usize {
+        self.0[(i & 511) as usize] as usize
+    }
+
+    fn hash2(&self, x: i64, y: i64) -> usize {
+        self.at(self.at(x & 255) as i64 + (y & 255))
+    }
+
+    fn hash3(&self, x: i64, y: i64, z: i64) -> usize {
+        self.at(self.hash2(x, y) as i64 + (z & 255))
+    }
+}
+
+impl core::fmt::Debug for Permutation {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("Permutation")
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Noise traits
+// -----------------------------------------------------------------------------
+
+/// Two dimensional noise function.
+pub trait Noise2 {
+    /// Noise value at `p`, roughly inside [-1, 1].
+    fn sample2(&self, p: Point<f64>) -> f64;
+}
+
+/// Three dimensional noise function.
+pub trait Noise3 {
+    /// Noise value at (x, y, z), roughly inside [-1, 1].
+    fn sample3(&self, x: f64, y: f64, z: f64) -> f64;
+}
+
+// -----------------------------------------------------------------------------
+// Perlin
+// -----------------------------------------------------------------------------
+
+/// Improved Perlin gradient noise in 1, 2 and 3 dimensions. The noise is 0 at every integer
+/// lattice point and repeats every 256 units.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::noise::{Noise2, Perlin};
+/// use ctl::geometry::Point;
+/// let n = Perlin::from_seed(1);
+/// let v = n.sample2(Point { x: 0.5, y: 1.25 });
+/// ```
+#[derive(Debug, Clone)]
+pub struct Perlin {
+    perm: Permutation,
+}
+
+impl Perlin {
+    /// Creates the noise using a permutation table drawn from `rng`.
+    pub fn new(rng: &mut Rng) -> Self {
+        Perlin {
+            perm: Permutation::new(rng),
+        }
+    }
+
+    /// Creates the noise from a seed. Equal seeds produce equal noise.
+    pub fn from_seed(seed: u64) -> Self {
+        Self::new(&mut Rng::new(seed))
+    }
+
+    /// One dimensional noise value at `x`.
+    pub fn noise1(&self, x: f64) -> f64 {
+        let xi = floor(x);
+        let xf = x - xi as f64;
+
+        let grad = |h: usize, x: f64| {
+            // Gradients in [-1, 1] excluding 0
+            let g = ((h & 7) + 1) as f64 / 8.0;
+            if h & 8 == 0 {
+                g * x
+            } else {
+                -g * x
+            }
+        };
+
+        let a = grad(self.perm.at(xi & 255), xf);
+        let b = grad(self.perm.at((xi + 1) & 255), xf - 1.0);
+
+        // The maximum of the 1D noise is 0.5 for unit gradients
+        2.0 * lerp(fade(xf), a, b)
+    }
+
+    /// Two dimensional noise value at (x, y).
+    pub fn noise2(&self, x: f64, y: f64) -> f64 {
+        let (xi, yi) = (floor(x), floor(y));
+        let (xf, yf) = (x - xi as f64, y - yi as f64);
+
+        let grad = |h: usize, x: f64, y: f64| match h & 7 {
+            0 => x + y,
+            1 => -x + y,
+            2 => x - y,
+            3 => -x - y,
+            4 => x,
+            5 => -x,
+            6 => y,
+            _ => -y,
+        };
+
+        let p = &self.perm;
+        let (u, v) = (fade(xf), fade(yf));
+
+        let x1 = lerp(
+            u,
+            grad(p.hash2(xi, yi), xf, yf),
+            grad(p.hash2(xi + 1, yi), xf - 1.0, yf),
+        );
+        let x2 = lerp(
+            u,
+            grad(p.hash2(xi, yi + 1), xf, yf - 1.0),
+            grad(p.hash2(xi + 1, yi + 1), xf - 1.0, yf - 1.0),
+        );
+
+        lerp(v, x1, x2)
+    }
+
+    /// Three dimensional noise value at (x, y, z).
+    pub fn noise3(&self, x: f64, y: f64, z: f64) -> f64 {
+        let (xi, yi, zi) = (floor(x), floor(y), floor(z));
+        let (xf, yf, zf) = (x - xi as f64, y - yi as f64, z - zi as f64);
+
+        let grad = |h: usize, x: f64, y: f64, z: f64| {
+            let h = h & 15;
+            let u = if h < 8 { x } else { y };
+            let v = if h < 4 {
+                y
+            } else if h == 12 || h == 14 {
+                x
+            } else {
+                z
+            };
+
+            (if h & 1 == 0 { u } else { -u }) + (if h & 2 == 0 { v } else { -v })
+        };
+
+        let p = &self.perm;
+        let (u, v, w) = (fade(xf), fade(yf), fade(zf));
+        let corner = |dx: i64, dy: i64, dz: i64| {
+            grad(
+                p.hash3(xi + dx, yi + dy, zi + dz),
+                xf - dx as f64,
+                yf - dy as f64,
+                zf - dz as f64,
+            )
+        };
+
+        lerp(
+            w,
+            lerp(
+                v,
+                lerp(u, corner(0, 0, 0), corner(1, 0, 0)),
+                lerp(u, corner(0, 1, 0), corner(1, 1, 0)),
+            ),
+            lerp(
+                v,
+                lerp(u, corner(0, 0, 1), corner(1, 0, 1)),
+                lerp(u, corner(0, 1, 1), corner(1, 1, 1)),
+            ),
+        )
+    }
+}
+
+impl Noise2 for Perlin {
+    fn sample2(&self, p: Point<f64>) -> f64 {
+        self.noise2(p.x, p.y)
+    }
+}
+
+impl Noise3 for Perlin {
+    fn sample3(&self, x: f64, y: f64, z: f64) -> f64 {
+        self.noise3(x, y, z)
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Simplex
+// -----------------------------------------------------------------------------
+
+const GRAD3: [[f64; 3]; 12] = [
+    [1.0, 1.0, 0.0],
+    [-1.0, 1.0, 0.0],
+    [1.0, -1.0, 0.0],
+    [-1.0, -1.0, 0.0],
+    [1.0, 0.0, 1.0],
+    [-1.0, 0.0, 1.0],
+    [1.0, 0.0, -1.0],
+    [-1.0, 0.0, -1.0],
+    [0.0, 1.0, 1.0],
+    [0.0, -1.0, 1.0],
+    [0.0, 1.0, -1.0],
+    [0.0, -1.0, -1.0],
+];
+
+/// Simplex gradient noise in 2 and 3 dimensions. Compared to [`Perlin`] it has fewer
+/// directional artifacts and is cheaper in higher dimensions.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::noise::{Noise2, Simplex};
+/// use ctl::geometry::Point;
+/// let n = Simplex::from_seed(1);
+/// let v = n.sample2(Point { x: 0.5, y: 1.25 });
+/// ```
+#[derive(Debug, Clone)]
+pub struct Simplex {
+    perm: Permutation,
+}
+
+impl Simplex {
+    /// Creates the noise using a permutation table drawn from `rng`.
+    pub fn new(rng: &mut Rng) -> Self {
+        Simplex {
+            perm: Permutation::new(rng),
+        }
+    }
+
+    /// Creates the noise from a seed. Equal seeds produce equal noise.
+    pub fn from_seed(seed: u64) -> Self {
+        Self::new(&mut Rng::new(seed))
+    }
+
+    /// Two dimensional noise value at (x, y).
+    pub fn noise2(&self, x: f64, y: f64) -> f64 {
+        // (sqrt(3) - 1) / 2 and (3 - sqrt(3)) / 6
+        const F2: f64 = 0.366_025_403_784_438_6;
+        const G2: f64 = 0.211_324_865_405_187_1;
+
+        let s = (x + y) * F2;
+        let (i, j) = (floor(x + s), floor(y + s));
+        let t = (i + j) as f64 * G2;
+        let (x0, y0) = (x - (i as f64 - t), y - (j as f64 - t));
+
+        let (i1, j1) = if x0 > y0 { (1, 0) } else { (0, 1) };
+
+        let offsets = [
+            (0, 0, x0, y0),
+            (i1, j1, x0 - i1 as f64 + G2, y0 - j1 as f64 + G2),
+            (1, 1, x0 - 1.0 + 2.0 * G2, y0 - 1.0 + 2.0 * G2),
+        ];
+
+        let n: f64 = offsets
+            .iter()
+            .map(|&(di, dj, dx, dy)| {
+                let t = 0.5 - dx * dx - dy * dy;
+
+                if t < 0.0 {
+                    0.0
+                } else {
+                    let g = GRAD3[self.perm.hash2(i + di, j + dj) % 12];
+                    t * t * t * t * (g[0] * dx + g[1] * dy)
+                }
+            })
+            .sum();
+
+        70.0 * n
+    }
+
+    /// Three dimensional noise value at (x, y, z).
+    pub fn noise3(&self, x: f64, y: f64, z: f64) -> f64 {
+        const F3: f64 = 1.0 / 3.0;
+        const G3: f64 = 1.0 / 6.0;
+
+        let s = (x + y + z) * F3;
+        let (i, j, k) = (floor(x + s), floor(y + s), floor(z + s));
+        let t = (i + j + k) as f64 * G3;
+        let (x0, y0, z0) = (x - (i as f64 - t), y - (j as f64 - t), z - (k as f64 - t));
+
+        // Offsets of the second and third corner of the simplex containing the point
+        let (o1, o2) = if x0 >= y0 {
+            if y0 >= z0 {
+                ((1, 0, 0), (1, 1, 0))
+            } else if x0 >= z0 {
+                ((1, 0, 0), (1, 0, 1))
+            } else {
+                ((0, 0, 1), (1, 0, 1))
+            }
+        } else if y0 < z0 {
+            ((0, 0, 1), (0, 1, 1))
+        } else if x0 < z0 {
+            ((0, 1, 0), (0, 1, 1))
+        } else {
+            ((0, 1, 0), (1, 1, 0))
+        };
+
+        let corners = [(0, 0, 0), o1, o2, (1, 1, 1)];
+
+        let n: f64 = corners
+            .iter()
+            .enumerate()
+            .map(|(c, &(di, dj, dk))| {
+                let g = c as f64 * G3;
+                let dx = x0 - di as f64 + g;
+                let dy = y0 - dj as f64 + g;
+                let dz = z0 - dk as f64 + g;
+                let t = 0.6 - dx * dx - dy * dy - dz * dz;
+
+                if t < 0.0 {
+                    0.0
+                } else {
+                    let g = GRAD3[self.perm.hash3(i + di, j + dj, k + dk) % 12];
+                    t * t * t * t * (g[0] * dx + g[1] * dy + g[2] * dz)
+                }
+            })
+            .sum();
+
+        32.0 * n
+    }
+}
+
+impl Noise2 for Simplex {
+    fn sample2(&self, p: Point<f64>) -> f64 {
+        self.noise2(p.x, p.y)
+    }
+}
+
+impl Noise3 for Simplex {
+    fn sample3(&self, x: f64, y: f64, z: f64) -> f64 {
+        self.noise3(x, y, z)
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Fractal Brownian motion
+// -----------------------------------------------------------------------------
+
+/// Fractal Brownian motion summing several octaves of a base noise. Every octave multiplies the
+/// frequency by `lacunarity` and the amplitude by `gain`. The sum is normalized so the result
+/// stays inside the range of the base noise.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::noise::{Fbm, Noise2, Simplex};
+/// use ctl::geometry::Point;
+/// let f = Fbm::new(Simplex::from_seed(3), 5);
+/// let v = f.sample2(Point { x: 10.5, y: -2.0 });
+/// ```
+#[derive(Debug, Clone)]
+pub struct Fbm<N> {
+    pub noise: N,
+    pub octaves: u32,
+    pub lacunarity: f64,
+    pub gain: f64,
+}
+
+impl<N> Fbm<N> {
+    /// Creates a fBm with the common lacunarity of 2 and gain of 0.5.
+    pub fn new(noise: N, octaves: u32) -> Self {
+        Fbm {
+            noise,
+            octaves,
+            lacunarity: 2.0,
+            gain: 0.5,
+        }
+    }
+
+    fn sum(&self, mut octave: impl FnMut(f64) -> f64) -> f64 {
+        let (mut freq, mut amp) = (1.0, 1.0);
+        let (mut total, mut norm) = (0.0, 0.0);
+
+        for _ in 0..self.octaves {
+            total += amp * octave(freq);
+            norm += amp;
+            freq *= self.lacunarity;
+            amp *= self.gain;
+        }
+
+        if norm == 0.0 {
+            0.0
+        } else {
+            total / norm
+        }
+    }
+}
+
+impl<N: Noise2> Noise2 for Fbm<N> {
+    fn sample2(&self, p: Point<f64>) -> f64 {
+        self.sum(|f| {
+            self.noise.sample2(Point {
+                x: p.x * f,
+                y: p.y * f,
+            })
+        })
+    }
+}
+
+impl<N: Noise3> Noise3 for Fbm<N> {
+    fn sample3(&self, x: f64, y: f64, z: f64) -> f64 {
+        self.sum(|f| self.noise.sample3(x * f, y * f, z * f))
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn samples() -> impl Iterator<Item = (f64, f64, f64)> {
+        (0..2000).map(|i| {
+            let i = i as f64;
+            (i * 0.137 - 50.0, i * 0.071 - 20.0, i * 0.029)
+        })
+    }
+
+    #[test]
+    fn test_perlin() {
+        let n = Perlin::from_seed(5);
+
+        assert_eq!(n.noise1(3.0), 0.0);
+        assert_eq!(n.noise2(3.0, -7.0), 0.0);
+        assert_eq!(n.noise3(1.0, 2.0, 3.0), 0.0);
+        assert_eq!(n.noise2(0.3, 0.7), Perlin::from_seed(5).noise2(0.3, 0.7));
+        assert_eq!(n.noise2(0.5, 0.75), n.noise2(256.5, 0.75));
+
+        for (x, y, z) in samples() {
+            assert!(n.noise1(x).abs() <= 1.0);
+            assert!(n.noise2(x, y).abs() <= 1.0);
+            assert!(n.noise3(x, y, z).abs() <= 1.1);
+        }
+
+        let a = n.noise2(10.5, 10.5);
+        assert!((a - n.noise2(10.501, 10.5)).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_simplex() {
+        let n = Simplex::from_seed(5);
+        let mut nonzero = 0;
+
+        for (x, y, z) in samples() {
+            let a = n.noise2(x, y);
+            let b = n.noise3(x, y, z);
+            assert!(a.abs() <= 1.0 && b.abs() <= 1.0);
+            nonzero += (a != 0.0) as usize;
+        }
+
+        assert!(nonzero > 1900);
+        assert_ne!(n.noise2(0.3, 0.7), Simplex::from_seed(6).noise2(0.3, 0.7));
+    }
+
+    #[test]
+    fn test_fbm() {
+        let f = Fbm::new(Perlin::from_seed(1), 4);
+
+        for (x, y, z) in samples() {
+            assert!(f.sample2(Point { x, y }).abs() <= 1.0);
+            assert!(f.sample3(x, y, z).abs() <= 1.1);
+        }
+
+        assert_eq!(
+            Fbm::new(Perlin::from_seed(1), 0).sample3(0.5, 0.5, 0.5),
+            0.0
+        );
+    }
+}
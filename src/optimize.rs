@@ -0,0 +1,702 @@
+use crate::random::Rng;
+use alloc::vec::Vec;
+
+// -----------------------------------------------------------------------------
+// Common
+// -----------------------------------------------------------------------------
+
+/// Stopping criteria shared by all optimizers.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Options {
+    /// Maximal number of iterations before giving up
+    pub max_iterations: usize,
+    /// Convergence threshold. Gradient methods stop once the gradient norm falls below it,
+    /// Nelder-Mead once the function values of the simplex are this close.
+    pub tolerance: f64,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            max_iterations: 10_000,
+            tolerance: 1e-8,
+        }
+    }
+}
+
+/// Result of an optimization run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Minimum {
+    /// Best found position
+    pub x: Vec<f64>,
+    /// Function value at `x`
+    pub value: f64,
+    /// Number of performed iterations
+    pub iterations: usize,
+    /// Whether the convergence criterion was met before running out of iterations
+    pub converged: bool,
+}
+
+fn norm_squared(v: &[f64]) -> f64 {
+    v.iter().map(|x| x * x).sum()
+}
+
+// -----------------------------------------------------------------------------
+// Gradient descent
+// -----------------------------------------------------------------------------
+
+/// Step size rule of [`gradient_descent`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Step {
+    /// Constant learning rate
+    Fixed(f64),
+    /// Backtracking line search. Starts at `initial` and multiplies by `shrink` until the
+    /// Armijo condition `f(x - t g) <= f(x) - c t |g|^2` holds.
+    Backtracking { initial: f64, shrink: f64, c: f64 },
+}
+
+/// Minimizes `f` by following its negative gradient.
+///
+/// # Arguments
+///
+/// * `f` - Minimized function
+/// * `grad` - Writes the gradient of `f` at the first argument into the second
+/// * `x0` - Start position
+/// * `step` - Step size rule
+/// * `opts` - Stopping criteria
+///
+/// # Examples
+///
+/// ```
+/// use ctl::optimize::{gradient_descent, Options, Step};
+/// let f = |x: &[f64]| (x[0] - 1.0).powi(2) + 2.0 * (x[1] + 3.0).powi(2);
+/// let g = |x: &[f64], g: &mut [f64]| {
+///     g[0] = 2.0 * (x[0] - 1.0);
+///     g[1] = 4.0 * (x[1] + 3.0);
+/// };
+/// let m = gradient_descent(f, g, &[0.0, 0.0], Step::Fixed(0.1), &Options::default());
+/// // m.x ~ [1, -3]
+/// ```
+pub fn gradient_descent<F, G>(f: F, mut grad: G, x0: &[f64], step: Step, opts: &Options) -> Minimum
+where
+    F: Fn(&[f64]) -> f64,
+    G: FnMut(&[f64], &mut [f64]),
+{
+    let n = x0.len();
+    let mut x = x0.to_vec();
+    let mut g = vec![0.0; n];
+    let mut trial = vec![0.0; n];
+    let mut value = f(&x);
+
+    for it in 0..opts.max_iterations {
+        grad(&x, &mut g);
+        let g2 = norm_squared(&g);
+
+        if g2 < opts.tolerance * opts.tolerance {
+            return Minimum {
+                x,
+                value,
+                iterations: it,
+                converged: true,
+            };
+        }
+
+        match step {
+            Step::Fixed(rate) => {
+                x.iter_mut().zip(&g).for_each(|(x, g)| *x -= rate * g);
+                value = f(&x);
+            }
+            Step::Backtracking { initial, shrink, c } => {
+                let mut t = initial;
+
+                loop {
+                    for i in 0..n {
+                        trial[i] = x[i] - t * g[i];
+                    }
+
+                    let v = f(&trial);
+
+                    // Give up shrinking once the step no longer changes the position
+                    if v <= value - c * t * g2 || trial == x {
+                        value = v;
+                        break;
+                    }
+
+                    t *= shrink;
+                }
+
+                x.copy_from_slice(&trial);
+            }
+        }
+    }
+
+    Minimum {
+        x,
+        value,
+        iterations: opts.max_iterations,
+        converged: false,
+    }
+}
+
+/// Gradient descent with heavy ball momentum. The velocity accumulates the gradients with
+/// decay `beta`, which speeds up progress along shallow valleys.
+///
+/// # Arguments
+///
+/// * `f` - Minimized function
+/// * `grad` - Writes the gradient of `f` at the first argument into the second
+/// * `x0` - Start position
+/// * `rate` - Learning rate
+/// * `beta` - Momentum decay in [0, 1)
+/// * `opts` - Stopping criteria
+///
+/// # Examples
+///
+/// ```
+/// use ctl::optimize::{momentum, Options};
+/// let f = |x: &[f64]| x[0] * x[0] + 10.0 * x[1] * x[1];
+/// let g = |x: &[f64], g: &mut [f64]| {
+///     g[0] = 2.0 * x[0];
+///     g[1] = 20.0 * x[1];
+/// };
+/// let m = momentum(f, g, &[3.0, 1.0], 0.02, 0.9, &Options::default()); // m.x ~ [0, 0]
+/// ```
+pub fn momentum<F, G>(
+    f: F,
+    mut grad: G,
+    x0: &[f64],
+    rate: f64,
+    beta: f64,
+    opts: &Options,
+) -> Minimum
+where
+    F: Fn(&[f64]) -> f64,
+    G: FnMut(&[f64], &mut [f64]),
+{
+    let n = x0.len();
+    let mut x = x0.to_vec();
+    let mut g = vec![0.0; n];
+    let mut v = vec![0.0; n];
+
+    for it in 0..opts.max_iterations {
+        grad(&x, &mut g);
+
+        if norm_squared(&g) < opts.tolerance * opts.tolerance {
+            let value = f(&x);
+            return Minimum {
+                x,
+                value,
+                iterations: it,
+                converged: true,
+            };
+        }
+
+        for i in 0..n {
+            v[i] = beta * v[i] + g[i];
+            x[i] -= rate * v[i];
+        }
+    }
+
+    let value = f(&x);
+    Minimum {
+        x,
+        value,
+        iterations: opts.max_iterations,
+        converged: false,
+    }
+}
+
+/// Hyper parameters of [`adam`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Adam {
+    /// Learning rate
+    pub rate: f64,
+    /// Decay of the first moment estimate
+    pub beta1: f64,
+    /// Decay of the second moment estimate
+    pub beta2: f64,
+    /// Added to the denominator to avoid division by zero
+    pub epsilon: f64,
+}
+
+impl Default for Adam {
+    fn default() -> Self {
+        Adam {
+            rate: 0.001,
+            beta1: 0.9,
+            beta2: 0.999,
+            epsilon: 1e-8,
+        }
+    }
+}
+
+/// Gradient descent with adaptive per coordinate step sizes (Adam, Kingma and Ba).
+///
+/// # Arguments
+///
+/// * `f` - Minimized function
+/// * `grad` - Writes the gradient of `f` at the first argument into the second
+/// * `x0` - Start position
+/// * `params` - Hyper parameters
+/// * `opts` - Stopping criteria
+///
+/// # Examples
+///
+/// ```
+/// use ctl::optimize::{adam, Adam, Options};
+/// let f = |x: &[f64]| (x[0] - 2.0).powi(2);
+/// let g = |x: &[f64], g: &mut [f64]| g[0] = 2.0 * (x[0] - 2.0);
+/// let params = Adam { rate: 0.1, ..Adam::default() };
+/// let m = adam(f, g, &[0.0], params, &Options::default()); // m.x ~ [2]
+/// ```
+#[cfg(feature = "std")]
+pub fn adam<F, G>(f: F, mut grad: G, x0: &[f64], params: Adam, opts: &Options) -> Minimum
+where
+    F: Fn(&[f64]) -> f64,
+    G: FnMut(&[f64], &mut [f64]),
+{
+    let n = x0.len();
+    let mut x = x0.to_vec();
+    let mut g = vec![0.0; n];
+    let (mut m, mut v) = (vec![0.0; n], vec![0.0; n]);
+    let (mut b1, mut b2) = (1.0, 1.0);
+
+    for it in 0..opts.max_iterations {
+        grad(&x, &mut g);
+
+        if norm_squared(&g) < opts.tolerance * opts.tolerance {
+            let value = f(&x);
+            return Minimum {
+                x,
+                value,
+                iterations: it,
+                converged: true,
+            };
+        }
+
+        b1 *= params.beta1;
+        b2 *= params.beta2;
+
+        for i in 0..n {
+            m[i] = params.beta1 * m[i] + (1.0 - params.beta1) * g[i];
+            v[i] = params.beta2 * v[i] + (1.0 - params.beta2) * g[i] * g[i];
+
+            let m_hat = m[i] / (1.0 - b1);
+            let v_hat = v[i] / (1.0 - b2);
+            x[i] -= params.rate * m_hat / (v_hat.sqrt() + params.epsilon);
+        }
+    }
+
+    let value = f(&x);
+    Minimum {
+        x,
+        value,
+        iterations: opts.max_iterations,
+        converged: false,
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Nelder-Mead
+// -----------------------------------------------------------------------------
+
+/// Minimizes `f` without derivatives using the Nelder-Mead simplex method. The start simplex
+/// consists of `x0` and `x0` moved by `step` along every axis.
+///
+/// # Arguments
+///
+/// * `f` - Minimized function
+/// * `x0` - Start position
+/// * `step` - Size of the start simplex
+/// * `opts` - Stopping criteria
+///
+/// # Examples
+///
+/// ```
+/// use ctl::optimize::{nelder_mead, Options};
+/// let rosenbrock = |x: &[f64]| (1.0 - x[0]).powi(2) + 100.0 * (x[1] - x[0] * x[0]).powi(2);
+/// let m = nelder_mead(rosenbrock, &[-1.0, 2.0], 0.5, &Options::default()); // m.x ~ [1, 1]
+/// ```
+pub fn nelder_mead<F: Fn(&[f64]) -> f64>(f: F, x0: &[f64], step: f64, opts: &Options) -> Minimum {
+    const ALPHA: f64 = 1.0;
+    const GAMMA: f64 = 2.0;
+    const RHO: f64 = 0.5;
+    const SIGMA: f64 = 0.5;
+
+    let n = x0.len();
+    let mut simplex: Vec<(Vec<f64>, f64)> = (0..=n)
+        .map(|i| {
+            let mut x = x0.to_vec();
+            if i > 0 {
+                x[i - 1] += step;
+            }
+            let v = f(&x);
+            (x, v)
+        })
+        .collect();
+
+    // Point along the line from the centroid through the worst vertex
+    let along = |c: &[f64], w: &[f64], t: f64| -> Vec<f64> {
+        c.iter().zip(w).map(|(c, w)| c + t * (w - c)).collect()
+    };
+
+    let mut iterations = opts.max_iterations;
+    let mut converged = false;
+
+    for it in 0..opts.max_iterations {
+        simplex.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(core::cmp::Ordering::Equal));
+
+        if simplex[n].1 - simplex[0].1 <= opts.tolerance {
+            iterations = it;
+            converged = true;
+            break;
+        }
+
+        let mut centroid = vec![0.0; n];
+        for (x, _) in &simplex[..n] {
+            centroid
+                .iter_mut()
+                .zip(x)
+                .for_each(|(c, x)| *c += x / n as f64);
+        }
+
+        let worst = simplex[n].0.clone();
+        let reflected = along(&centroid, &worst, -ALPHA);
+        let fr = f(&reflected);
+
+        if fr < simplex[0].1 {
+            let expanded = along(&centroid, &worst, -GAMMA);
+            let fe = f(&expanded);
+            simplex[n] = if fe < fr {
+                (expanded, fe)
+            } else {
+                (reflected, fr)
+            };
+        } else if fr < simplex[n - 1].1 {
+            simplex[n] = (reflected, fr);
+        } else {
+            let contracted = if fr < simplex[n].1 {
+                along(&centroid, &reflected, RHO)
+            } else {
+                along(&centroid, &worst, RHO)
+            };
+            let fc = f(&contracted);
+
+            if fc < fr.min(simplex[n].1) {
+                simplex[n] = (contracted, fc);
+            } else {
+                let best = simplex[0].0.clone();
+                for (x, v) in &mut simplex[1..] {
+                    *x = along(&best, x, SIGMA);
+                    *v = f(x);
+                }
+            }
+        }
+    }
+
+    simplex.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(core::cmp::Ordering::Equal));
+    let (x, value) = simplex.swap_remove(0);
+
+    Minimum {
+        x,
+        value,
+        iterations,
+        converged,
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Metaheuristics
+// -----------------------------------------------------------------------------
+
+/// Best state found by [`hill_climb`] or [`simulated_annealing`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Solution<S> {
+    /// Lowest energy state seen
+    pub state: S,
+    /// Energy of `state`
+    pub energy: f64,
+    /// Number of accepted moves
+    pub accepted: usize,
+}
+
+/// Greedy local search. Proposes `iterations` neighbours and moves to a neighbour whenever it
+/// doesn't increase the energy.
+///
+/// # Arguments
+///
+/// * `initial` - Start state
+/// * `neighbor` - Proposes a random neighbour of a state
+/// * `energy` - Minimized energy of a state
+/// * `iterations` - Number of proposed moves
+/// * `rng` - Random source passed to `neighbor`
+///
+/// # Examples
+///
+/// ```
+/// use ctl::optimize::hill_climb;
+/// use ctl::random::Rng;
+/// let s = hill_climb(
+///     10i64,
+///     |&x, rng: &mut Rng| x + rng.range(-1..2),
+///     |&x| ((x - 3) * (x - 3)) as f64,
+///     1000,
+///     &mut Rng::new(1),
+/// ); // s.state == 3
+/// ```
+pub fn hill_climb<S, N, E>(
+    initial: S,
+    mut neighbor: N,
+    mut energy: E,
+    iterations: usize,
+    rng: &mut Rng,
+) -> Solution<S>
+where
+    N: FnMut(&S, &mut Rng) -> S,
+    E: FnMut(&S) -> f64,
+{
+    let mut best = Solution {
+        energy: energy(&initial),
+        state: initial,
+        accepted: 0,
+    };
+
+    for _ in 0..iterations {
+        let next = neighbor(&best.state, rng);
+        let e = energy(&next);
+
+        if e <= best.energy {
+            best.state = next;
+            best.energy = e;
+            best.accepted += 1;
+        }
+    }
+
+    best
+}
+
+/// Temperature schedule of [`simulated_annealing`].
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Cooling {
+    /// `t0 * alpha^k`
+    Exponential { t0: f64, alpha: f64 },
+    /// Falls linearly from `t0` to 0 over all iterations
+    Linear { t0: f64 },
+    /// `t0 / ln(k + e)`, slow but with convergence guarantees
+    Logarithmic { t0: f64 },
+}
+
+#[cfg(feature = "std")]
+impl Cooling {
+    /// Temperature at iteration `k` out of `n`.
+    pub fn temperature(&self, k: usize, n: usize) -> f64 {
+        match *self {
+            Cooling::Exponential { t0, alpha } => t0 * alpha.powf(k as f64),
+            Cooling::Linear { t0 } => t0 * (1.0 - k as f64 / n as f64),
+            Cooling::Logarithmic { t0 } => t0 / (k as f64 + core::f64::consts::E).ln(),
+        }
+    }
+}
+
+/// Simulated annealing. Proposes `iterations` neighbours and accepts a move with the Metropolis
+/// probability `exp(-(e' - e) / t)`, so uphill moves become rarer as the temperature cools.
+/// Returns the best state seen, not the final one.
+///
+/// # Arguments
+///
+/// * `initial` - Start state
+/// * `neighbor` - Proposes a random neighbour of a state
+/// * `energy` - Minimized energy of a state
+/// * `cooling` - Temperature schedule
+/// * `iterations` - Number of proposed moves
+/// * `rng` - Random source for `neighbor` and the acceptance test
+///
+/// # Examples
+///
+/// ```
+/// use ctl::optimize::{simulated_annealing, Cooling};
+/// use ctl::random::Rng;
+/// let s = simulated_annealing(
+///     10i64,
+///     |&x, rng: &mut Rng| x + rng.range(-2..3),
+///     |&x| ((x - 3) * (x - 3)) as f64 + 5.0 * (x as f64).sin(),
+///     Cooling::Exponential { t0: 10.0, alpha: 0.99 },
+///     2000,
+///     &mut Rng::new(1),
+/// );
+/// ```
+#[cfg(feature = "std")]
+pub fn simulated_annealing<S, N, E>(
+    initial: S,
+    mut neighbor: N,
+    mut energy: E,
+    cooling: Cooling,
+    iterations: usize,
+    rng: &mut Rng,
+) -> Solution<S>
+where
+    S: Clone,
+    N: FnMut(&S, &mut Rng) -> S,
+    E: FnMut(&S) -> f64,
+{
+    let mut current = initial;
+    let mut current_energy = energy(&current);
+    let mut best = Solution {
+        state: current.clone(),
+        energy: current_energy,
+        accepted: 0,
+    };
+
+    for k in 0..iterations {
+        let next = neighbor(&current, rng);
+        let e = energy(&next);
+        let t = cooling.temperature(k, iterations);
+
+        let accept =
+            e <= current_energy || (t > 0.0 && rng.chance((-(e - current_energy) / t).exp()));
+
+        if accept {
+            current = next;
+            current_energy = e;
+            best.accepted += 1;
+
+            if e < best.energy {
+                best.state = current.clone();
+                best.energy = e;
+            }
+        }
+    }
+
+    best
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bowl(x: &[f64]) -> f64 {
+        (x[0] - 1.0) * (x[0] - 1.0) + 5.0 * (x[1] + 2.0) * (x[1] + 2.0)
+    }
+
+    fn bowl_grad(x: &[f64], g: &mut [f64]) {
+        g[0] = 2.0 * (x[0] - 1.0);
+        g[1] = 10.0 * (x[1] + 2.0);
+    }
+
+    fn assert_near(m: &Minimum, x: &[f64], eps: f64) {
+        assert!(m.converged, "{:?}", m);
+        for (a, b) in m.x.iter().zip(x) {
+            assert!((a - b).abs() < eps, "{:?}", m);
+        }
+    }
+
+    #[test]
+    fn test_gradient_methods() {
+        let opts = Options::default();
+
+        let m = gradient_descent(bowl, bowl_grad, &[0.0, 0.0], Step::Fixed(0.09), &opts);
+        assert_near(&m, &[1.0, -2.0], 1e-6);
+
+        let step = Step::Backtracking {
+            initial: 1.0,
+            shrink: 0.5,
+            c: 1e-4,
+        };
+        let m = gradient_descent(bowl, bowl_grad, &[5.0, 5.0], step, &opts);
+        assert_near(&m, &[1.0, -2.0], 1e-6);
+
+        let m = momentum(bowl, bowl_grad, &[0.0, 0.0], 0.02, 0.8, &opts);
+        assert_near(&m, &[1.0, -2.0], 1e-6);
+
+        #[cfg(feature = "std")]
+        {
+            let params = Adam {
+                rate: 0.05,
+                ..Adam::default()
+            };
+            let m = adam(bowl, bowl_grad, &[0.0, 0.0], params, &opts);
+            assert_near(&m, &[1.0, -2.0], 1e-4);
+        }
+
+        let few = Options {
+            max_iterations: 3,
+            ..opts
+        };
+        let m = gradient_descent(bowl, bowl_grad, &[0.0, 0.0], Step::Fixed(0.01), &few);
+        assert!(!m.converged);
+        assert_eq!(m.iterations, 3);
+    }
+
+    #[test]
+    fn test_nelder_mead() {
+        let rosenbrock =
+            |x: &[f64]| (1.0 - x[0]) * (1.0 - x[0]) + 100.0 * (x[1] - x[0] * x[0]).powi(2);
+        let opts = Options {
+            tolerance: 1e-14,
+            ..Options::default()
+        };
+
+        let m = nelder_mead(rosenbrock, &[-1.2, 1.0], 0.5, &opts);
+        assert_near(&m, &[1.0, 1.0], 1e-4);
+
+        let m = nelder_mead(bowl, &[3.0, 3.0], 1.0, &opts);
+        assert_near(&m, &[1.0, -2.0], 1e-5);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_metaheuristics() {
+        use crate::geometry::Point;
+
+        // Tour through the corners of a regular polygon, the optimum walks along its perimeter
+        let n = 12;
+        let cities: Vec<Point<f64>> = (0..n)
+            .map(|i| {
+                let a = i as f64 * 2.0 * core::f64::consts::PI / n as f64;
+                Point {
+                    x: a.cos(),
+                    y: a.sin(),
+                }
+            })
+            .collect();
+
+        let length = |tour: &Vec<usize>| -> f64 {
+            (0..n)
+                .map(|i| {
+                    let (a, b) = (cities[tour[i]], cities[tour[(i + 1) % n]]);
+                    ((a.x - b.x).powi(2) + (a.y - b.y).powi(2)).sqrt()
+                })
+                .sum()
+        };
+        let two_opt = |tour: &Vec<usize>, rng: &mut Rng| {
+            let (i, j) = (rng.below(n as u64) as usize, rng.below(n as u64) as usize);
+            let mut t = tour.clone();
+            t[i.min(j)..=i.max(j)].reverse();
+            t
+        };
+
+        let mut start: Vec<usize> = (0..n).collect();
+        let mut rng = Rng::new(5);
+        rng.shuffle(&mut start);
+
+        let optimum = n as f64 * 2.0 * (core::f64::consts::PI / n as f64).sin();
+
+        let s = hill_climb(start.clone(), two_opt, length, 5000, &mut rng);
+        assert!(s.energy <= length(&start));
+
+        let cooling = Cooling::Exponential {
+            t0: 1.0,
+            alpha: 0.999,
+        };
+        let s = simulated_annealing(start, two_opt, length, cooling, 20000, &mut rng);
+        assert!((s.energy - optimum).abs() < 1e-9);
+        assert!((length(&s.state) - s.energy).abs() < 1e-12);
+
+        assert_eq!(Cooling::Linear { t0: 2.0 }.temperature(5, 10), 1.0);
+    }
+}
@@ -0,0 +1,74 @@
+use alloc::vec::Vec;
+
+// -----------------------------------------------------------------------------
+// Chunked work
+// -----------------------------------------------------------------------------
+
+// The chunks only depend on the input, so the par_ variants compute the same values with and
+// without the `parallel` feature and on any number of cores.
+
+/// Runs `f` on the items and returns the results in order. With the `parallel` feature the items
+/// are split into consecutive groups, one per core, each running on its own thread.
+fn run<I, R, F>(items: Vec<I>, f: F) -> Vec<R>
+where
+    I: Send,
+    R: Send,
+    F: Fn(I) -> R + Sync,
+{
+    #[cfg(feature = "parallel")]
+    {
+        let cores = std::thread::available_parallelism().map_or(1, |n| n.get());
+        let threads = cores.min(items.len());
+
+        if threads > 1 {
+            let per = items.len().div_ceil(threads);
+            let mut groups = Vec::with_capacity(threads);
+            let mut items = items;
+            while items.len() > per {
+                let rest = items.split_off(per);
+                groups.push(items);
+                items = rest;
+            }
+            groups.push(items);
+
+            let f = &f;
+            return std::thread::scope(|s| {
+                let handles: Vec<_> = groups
+                    .into_iter()
+                    .map(|g| s.spawn(move || g.into_iter().map(f).collect::<Vec<R>>()))
+                    .collect();
+
+                handles
+                    .into_iter()
+                    .flat_map(|h| h.join().unwrap_or_else(|e| std::panic::resume_unwind(e)))
+                    .collect()
+            });
+        }
+    }
+
+    items.into_iter().map(f).collect()
+}
+
+/// Applies `f` to the chunks of `data` of length `len`, the last one possibly shorter, together
+/// with their start index. Returns the results in the order of the chunks.
+pub(crate) fn map_chunks<T, R, F>(data: &[T], len: usize, f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(usize, &[T]) -> R + Sync,
+{
+    let len = len.max(1);
+    let chunks = data.chunks(len).enumerate().collect();
+    run(chunks, |(i, c)| f(i * len, c))
+}
+
+/// Like [`map_chunks`] for filling in the chunks.
+pub(crate) fn for_chunks_mut<T, F>(data: &mut [T], len: usize, f: F)
+where
+    T: Send,
+    F: Fn(usize, &mut [T]) + Sync,
+{
+    let len = len.max(1);
+    let chunks = data.chunks_mut(len).enumerate().collect();
+    run(chunks, |(i, c)| f(i * len, c));
+}
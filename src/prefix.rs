@@ -0,0 +1,557 @@
+use crate::grid::Grid2;
+use crate::parallel;
+use alloc::vec::Vec;
+use core::ops::{Add, Bound, Range, RangeBounds, Sub};
+
+fn to_range(r: impl RangeBounds<usize>, len: usize) -> Range<usize> {
+    let start = match r.start_bound() {
+        Bound::Included(&s) => s,
+        Bound::Excluded(&s) => s + 1,
+        Bound::Unbounded => 0,
+    };
+    let end = match r.end_bound() {
+        Bound::Included(&e) => e + 1,
+        Bound::Excluded(&e) => e,
+        Bound::Unbounded => len,
+    };
+
+    assert!(
+        start <= end && end <= len,
+        "range {}..{} out of bounds for length {}",
+        start,
+        end,
+        len
+    );
+
+    start..end
+}
+
+// -----------------------------------------------------------------------------
+// Prefix sums
+// -----------------------------------------------------------------------------
+
+/// Precomputed prefix sums of a sequence answering range-sum queries in O(1).
+///
+/// # Examples
+///
+/// ```
+/// use ctl::prefix::PrefixSum;
+/// let p = PrefixSum::new(&[1, 2, 3, 4]);
+/// let s = p.sum(1..3); // 5
+/// ```
+#[derive(Debug, Clone)]
+pub struct PrefixSum<T> {
+    sums: Vec<T>,
+}
+
+impl<T> PrefixSum<T>
+where
+    T: Copy + Default + Add<Output = T> + Sub<Output = T>,
+{
+    /// Builds the prefix sums in O(n).
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - Values to sum up
+    pub fn new(data: &[T]) -> Self {
+        let mut sums = Vec::with_capacity(data.len() + 1);
+        let mut acc = T::default();
+        sums.push(acc);
+
+        for &x in data {
+            acc = acc + x;
+            sums.push(acc);
+        }
+
+        PrefixSum { sums }
+    }
+
+    /// Builds the prefix sums in chunks, on all cores with the `parallel` feature. Equals
+    /// [`PrefixSum::new`] for integers; floats are summed per chunk, which can round
+    /// differently but the same on every run.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - Values to sum up
+    pub fn par_new(data: &[T]) -> Self
+    where
+        T: Send + Sync,
+    {
+        const CHUNK: usize = 1 << 14;
+
+        let totals = parallel::map_chunks(data, CHUNK, |_, c| {
+            c.iter().fold(T::default(), |acc, &x| acc + x)
+        });
+        let offsets: Vec<T> = totals
+            .iter()
+            .scan(T::default(), |acc, &t| {
+                let start = *acc;
+                *acc = start + t;
+                Some(start)
+            })
+            .collect();
+
+        let mut sums = vec![T::default(); data.len() + 1];
+        parallel::for_chunks_mut(&mut sums[1..], CHUNK, |start, out| {
+            let mut acc = offsets[start / CHUNK];
+            for (s, &x) in out.iter_mut().zip(&data[start..]) {
+                acc = acc + x;
+                *s = acc;
+            }
+        });
+
+        PrefixSum { sums }
+    }
+
+    /// Number of summed values.
+    pub fn len(&self) -> usize {
+        self.sums.len() - 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Sum of the values inside the index range.
+    ///
+    /// # Arguments
+    ///
+    /// * `range` - Index range of the summed values
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ctl::prefix::PrefixSum;
+    /// let p = PrefixSum::new(&[1, 2, 3, 4]);
+    /// let s = p.sum(..=1); // 3
+    /// ```
+    pub fn sum(&self, range: impl RangeBounds<usize>) -> T {
+        let r = to_range(range, self.len());
+        self.sums[r.end] - self.sums[r.start]
+    }
+
+    /// Sum of all values.
+    pub fn total(&self) -> T {
+        self.sums[self.len()]
+    }
+}
+
+/// Precomputed prefix sums of a row-major grid answering rectangle-sum queries in O(1).
+///
+/// # Examples
+///
+/// ```
+/// use ctl::prefix::PrefixSum2D;
+/// let p = PrefixSum2D::new(&[1, 2, 3, 4, 5, 6], 3); // 3x2 grid
+/// let s = p.sum(1..3, 0..2); // 2 + 3 + 5 + 6 = 16
+/// ```
+#[derive(Debug, Clone)]
+pub struct PrefixSum2D<T> {
+    width: usize,
+    height: usize,
+    sums: Vec<T>,
+}
+
+impl<T> PrefixSum2D<T>
+where
+    T: Copy + Default + Add<Output = T> + Sub<Output = T>,
+{
+    /// Builds the prefix sums in O(width * height).
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - Row-major grid values
+    /// * `width` - Number of columns of the grid
+    pub fn new(data: &[T], width: usize) -> Self {
+        let height = data.len().checked_div(width).unwrap_or(0);
+        assert_eq!(width * height, data.len(), "data is not a full grid");
+
+        let stride = width + 1;
+        let mut sums = vec![T::default(); stride * (height + 1)];
+
+        for y in 0..height {
+            let mut row = T::default();
+
+            for x in 0..width {
+                row = row + data[y * width + x];
+                sums[(y + 1) * stride + x + 1] = sums[y * stride + x + 1] + row;
+            }
+        }
+
+        PrefixSum2D {
+            width,
+            height,
+            sums,
+        }
+    }
+
+    /// Builds the summed-area table of a grid in O(width * height), converting the cells to the
+    /// sum type. Pick a wider type than the cells to avoid overflows, e.g. `u64` or `f64` for a
+    /// `Grid2<u32>`.
+    ///
+    /// # Arguments
+    ///
+    /// * `grid` - Summed grid
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ctl::grid::Grid2;
+    /// use ctl::prefix::PrefixSum2D;
+    /// let g = Grid2::from_fn(4, 4, |p| (p.x + p.y) as u32);
+    /// let p = PrefixSum2D::<u64>::from_grid(&g);
+    /// let s = p.sum(1..3, 2..4); // 3 + 4 + 4 + 5 = 16
+    /// ```
+    pub fn from_grid<U: Copy + Into<T>>(grid: &Grid2<U>) -> Self {
+        let cells: Vec<T> = grid.iter().map(|&v| v.into()).collect();
+        Self::new(&cells, grid.width())
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Sum of the values inside the rectangle spanned by the column and row ranges.
+    ///
+    /// # Arguments
+    ///
+    /// * `xs` - Column range
+    /// * `ys` - Row range
+    pub fn sum(&self, xs: impl RangeBounds<usize>, ys: impl RangeBounds<usize>) -> T {
+        let xs = to_range(xs, self.width);
+        let ys = to_range(ys, self.height);
+        let at = |x: usize, y: usize| self.sums[y * (self.width + 1) + x];
+
+        // Add before subtracting so unsigned sums never go below zero
+        at(xs.end, ys.end) + at(xs.start, ys.start) - at(xs.start, ys.end) - at(xs.end, ys.start)
+    }
+
+    /// Sum of all values.
+    pub fn total(&self) -> T {
+        self.sum(.., ..)
+    }
+
+    /// Mean of the values inside the rectangle spanned by the column and row ranges, NaN for
+    /// an empty rectangle.
+    ///
+    /// # Arguments
+    ///
+    /// * `xs` - Column range
+    /// * `ys` - Row range
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ctl::prefix::PrefixSum2D;
+    /// let p = PrefixSum2D::new(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0], 3);
+    /// let m = p.mean(1.., ..); // 4.0
+    /// ```
+    pub fn mean(&self, xs: impl RangeBounds<usize>, ys: impl RangeBounds<usize>) -> f64
+    where
+        T: Into<f64>,
+    {
+        let xs = to_range(xs, self.width);
+        let ys = to_range(ys, self.height);
+        let n = xs.len() * ys.len();
+
+        self.sum(xs, ys).into() / n as f64
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Difference arrays
+// -----------------------------------------------------------------------------
+
+/// Values a difference array can hold. Integers wrap around, so the negative differences of
+/// unsigned types cancel out again once the increments are summed up and the results are exact
+/// whenever they fit into the type. Floats use plain arithmetic.
+pub trait Difference: Copy + Default {
+    /// Wrapping sum `self + rhs`
+    fn add_wrapping(self, rhs: Self) -> Self;
+
+    /// Wrapping difference `self - rhs`
+    fn sub_wrapping(self, rhs: Self) -> Self;
+}
+
+macro_rules! gen_difference {
+    ($($T:ty),*) => {
+        $(
+            impl Difference for $T {
+                fn add_wrapping(self, rhs: Self) -> Self {
+                    self.wrapping_add(rhs)
+                }
+
+                fn sub_wrapping(self, rhs: Self) -> Self {
+                    self.wrapping_sub(rhs)
+                }
+            }
+        )*
+    };
+}
+
+gen_difference!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+impl Difference for f32 {
+    fn add_wrapping(self, rhs: Self) -> Self {
+        self + rhs
+    }
+
+    fn sub_wrapping(self, rhs: Self) -> Self {
+        self - rhs
+    }
+}
+
+impl Difference for f64 {
+    fn add_wrapping(self, rhs: Self) -> Self {
+        self + rhs
+    }
+
+    fn sub_wrapping(self, rhs: Self) -> Self {
+        self - rhs
+    }
+}
+
+/// Accumulates many range increments in O(1) each and materializes the result in O(n). The
+/// differences wrap around, so unsigned values work as well, see [`Difference`].
+///
+/// # Examples
+///
+/// ```
+/// use ctl::prefix::DifferenceArray;
+/// let mut d = DifferenceArray::new(5);
+/// d.add(1..4, 2);
+/// d.add(..2, 1);
+/// let r = d.build(); // [1, 3, 2, 2, 0]
+/// ```
+#[derive(Debug, Clone)]
+pub struct DifferenceArray<T> {
+    diff: Vec<T>,
+}
+
+impl<T: Difference> DifferenceArray<T> {
+    /// Creates a difference array over `len` zeros.
+    pub fn new(len: usize) -> Self {
+        DifferenceArray {
+            diff: vec![T::default(); len + 1],
+        }
+    }
+
+    /// Creates a difference array starting from existing values.
+    pub fn from_slice(data: &[T]) -> Self {
+        let mut d = Self::new(data.len());
+        let mut prev = T::default();
+
+        for (i, &x) in data.iter().enumerate() {
+            d.diff[i] = x.sub_wrapping(prev);
+            prev = x;
+        }
+
+        d
+    }
+
+    pub fn len(&self) -> usize {
+        self.diff.len() - 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Adds `value` to every element inside the index range.
+    pub fn add(&mut self, range: impl RangeBounds<usize>, value: T) {
+        let r = to_range(range, self.len());
+        self.diff[r.start] = self.diff[r.start].add_wrapping(value);
+        self.diff[r.end] = self.diff[r.end].sub_wrapping(value);
+    }
+
+    /// Resulting values after all increments.
+    pub fn build(&self) -> Vec<T> {
+        let mut acc = T::default();
+
+        self.diff[..self.len()]
+            .iter()
+            .map(|&d| {
+                acc = acc.add_wrapping(d);
+                acc
+            })
+            .collect()
+    }
+}
+
+/// Two dimensional [`DifferenceArray`] accumulating rectangle increments over a row-major grid.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::prefix::DifferenceArray2D;
+/// let mut d = DifferenceArray2D::new(3, 2);
+/// d.add(1..3, .., 1);
+/// let r = d.build(); // [0, 1, 1, 0, 1, 1]
+/// ```
+#[derive(Debug, Clone)]
+pub struct DifferenceArray2D<T> {
+    width: usize,
+    height: usize,
+    diff: Vec<T>,
+}
+
+impl<T: Difference> DifferenceArray2D<T> {
+    /// Creates a difference array over a `width` x `height` grid of zeros.
+    pub fn new(width: usize, height: usize) -> Self {
+        DifferenceArray2D {
+            width,
+            height,
+            diff: vec![T::default(); (width + 1) * (height + 1)],
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Adds `value` to every cell inside the rectangle spanned by the column and row ranges.
+    pub fn add(&mut self, xs: impl RangeBounds<usize>, ys: impl RangeBounds<usize>, value: T) {
+        let xs = to_range(xs, self.width);
+        let ys = to_range(ys, self.height);
+        let stride = self.width + 1;
+        let d = &mut self.diff;
+
+        d[ys.start * stride + xs.start] = d[ys.start * stride + xs.start].add_wrapping(value);
+        d[ys.start * stride + xs.end] = d[ys.start * stride + xs.end].sub_wrapping(value);
+        d[ys.end * stride + xs.start] = d[ys.end * stride + xs.start].sub_wrapping(value);
+        d[ys.end * stride + xs.end] = d[ys.end * stride + xs.end].add_wrapping(value);
+    }
+
+    /// Resulting row-major grid after all increments.
+    pub fn build(&self) -> Vec<T> {
+        let stride = self.width + 1;
+        let mut out = vec![T::default(); self.width * self.height];
+
+        for y in 0..self.height {
+            let mut row = T::default();
+
+            for x in 0..self.width {
+                row = row.add_wrapping(self.diff[y * stride + x]);
+                out[y * self.width + x] = if y == 0 {
+                    row
+                } else {
+                    out[(y - 1) * self.width + x].add_wrapping(row)
+                };
+            }
+        }
+
+        out
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prefix_sum() {
+        let data = [3, -1, 4, 1, 5, 9, 2, 6];
+        let p = PrefixSum::new(&data);
+
+        for i in 0..=data.len() {
+            for j in i..=data.len() {
+                assert_eq!(p.sum(i..j), data[i..j].iter().sum::<i32>());
+            }
+        }
+
+        assert_eq!(p.sum(..=2), 6);
+        assert_eq!(p.total(), 29);
+        assert!(PrefixSum::<f64>::new(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_prefix_sum_par() {
+        let data: Vec<i64> = (0..100_000).map(|i| (i * 7919) % 1000 - 500).collect();
+        let (p, q) = (PrefixSum::new(&data), PrefixSum::par_new(&data));
+
+        assert_eq!(p.sums, q.sums);
+        assert!(PrefixSum::<i32>::par_new(&[]).is_empty());
+        assert_eq!(PrefixSum::par_new(&[2.5]).total(), 2.5);
+    }
+
+    #[test]
+    fn test_prefix_sum_2d() {
+        let w = 4;
+        let data: Vec<i64> = (0..12).map(|i| i * i - 7).collect();
+        let p = PrefixSum2D::new(&data, w);
+
+        assert_eq!(p.height(), 3);
+
+        for x0 in 0..=4 {
+            for x1 in x0..=4 {
+                for y0 in 0..=3 {
+                    for y1 in y0..=3 {
+                        let mut s = 0;
+                        for y in y0..y1 {
+                            for x in x0..x1 {
+                                s += data[y * w + x];
+                            }
+                        }
+                        assert_eq!(p.sum(x0..x1, y0..y1), s);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_grid_sums() {
+        let g = Grid2::from_fn(5, 3, |p| (p.x * 7 + p.y) as u32 * 100_000_000);
+        let p = PrefixSum2D::<u64>::from_grid(&g);
+
+        assert_eq!(p.width(), 5);
+        assert_eq!(p.height(), 3);
+        assert_eq!(p.total(), g.iter().map(|&v| v as u64).sum::<u64>());
+        assert_eq!(p.sum(4..5, 2..3), 3_000_000_000);
+
+        let f = PrefixSum2D::<f64>::from_grid(&g.map(|&v| v as f64 / 1e8));
+        assert_eq!(f.mean(1..3, ..), 11.5);
+        assert!(f.mean(2..2, ..).is_nan());
+    }
+
+    #[test]
+    fn test_difference_array() {
+        let mut d = DifferenceArray::new(5);
+        d.add(1..4, 2);
+        d.add(..2, 1);
+        d.add(4..=4, -3);
+        assert_eq!(d.build(), vec![1, 3, 2, 2, -3]);
+
+        let mut d = DifferenceArray::from_slice(&[5, 5, 5]);
+        d.add(1.., 1);
+        assert_eq!(d.build(), vec![5, 6, 6]);
+
+        let mut d = DifferenceArray2D::new(3, 3);
+        d.add(0..2, 0..2, 1);
+        d.add(1..3, 1..3, 10);
+        assert_eq!(d.build(), vec![1, 1, 0, 1, 11, 10, 0, 10, 10]);
+
+        // Unsigned values wrap in between but come out exact
+        let mut d = DifferenceArray::<u32>::from_slice(&[7, 2, 9]);
+        d.add(..2, 4);
+        d.add(1..2, u32::MAX - 6);
+        assert_eq!(d.build(), vec![11, u32::MAX, 9]);
+
+        let mut d = DifferenceArray2D::<u32>::new(3, 2);
+        d.add(1..2, 0..1, 5);
+        d.add(.., 1.., 2);
+        assert_eq!(d.build(), vec![0, 5, 0, 2, 2, 2]);
+    }
+}
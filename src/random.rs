@@ -0,0 +1,409 @@
+use crate::geometry::Point;
+use core::ops::Range;
+
+// -----------------------------------------------------------------------------
+// Rng
+// -----------------------------------------------------------------------------
+
+/// Small and fast pseudo random number generator (xoshiro256**). The state is seeded through
+/// SplitMix64 so any seed, including 0, gives a well mixed sequence. Not suitable for
+/// cryptography.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::random::Rng;
+/// let mut rng = Rng::new(42);
+/// let a = rng.next_f64(); // in [0, 1)
+/// let b = rng.range(1..7); // dice roll
+/// ```
+#[derive(Debug, Clone)]
+pub struct Rng {
+    s: [u64; 4],
+}
+
+fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+impl Rng {
+    /// Creates a generator from a seed. Equal seeds produce equal sequences.
+    pub fn new(seed: u64) -> Self {
+        let mut state = seed;
+        Rng {
+            s: [
+                splitmix64(&mut state),
+                splitmix64(&mut state),
+                splitmix64(&mut state),
+                splitmix64(&mut state),
+            ],
+        }
+    }
+
+    /// Uniformly distributed 64 bit value.
+    pub fn next_u64(&mut self) -> u64 {
+        let result = self.s[1].wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+        let t = self.s[1] << 17;
+
+        self.s[2] ^= self.s[0];
+        self.s[3] ^= self.s[1];
+        self.s[1] ^= self.s[2];
+        self.s[0] ^= self.s[3];
+        self.s[2] ^= t;
+        self.s[3] = self.s[3].rotate_left(45);
+
+        result
+    }
+
+    /// Uniformly distributed 32 bit value.
+    pub fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+
+    /// Uniformly distributed value in [0, 1).
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 * (1.0 / (1u64 << 53) as f64)
+    }
+
+    /// Uniformly distributed value in [0, n) without modulo bias. Panics if `n` is 0.
+    pub fn below(&mut self, n: u64) -> u64 {
+        assert!(n > 0, "empty range");
+
+        // Lemire's multiply and reject method
+        loop {
+            let m = self.next_u64() as u128 * n as u128;
+
+            if (m as u64) >= n.wrapping_neg() % n {
+                return (m >> 64) as u64;
+            }
+        }
+    }
+
+    /// Uniformly distributed integer inside the range. Panics if the range is empty.
+    ///
+    /// # Arguments
+    ///
+    /// * `r` - Half open range of possible values
+    pub fn range(&mut self, r: Range<i64>) -> i64 {
+        assert!(r.start < r.end, "empty range");
+        let span = r.end.wrapping_sub(r.start) as u64;
+        r.start.wrapping_add(self.below(span) as i64)
+    }
+
+    /// Uniformly distributed float in [lo, hi).
+    pub fn range_f64(&mut self, lo: f64, hi: f64) -> f64 {
+        lo + (hi - lo) * self.next_f64()
+    }
+
+    /// Returns true with probability `p`.
+    pub fn chance(&mut self, p: f64) -> bool {
+        self.next_f64() < p
+    }
+
+    /// Shuffles the slice uniformly (Fisher-Yates).
+    pub fn shuffle<T>(&mut self, s: &mut [T]) {
+        for i in (1..s.len()).rev() {
+            let j = self.below(i as u64 + 1) as usize;
+            s.swap(i, j);
+        }
+    }
+
+    /// Standard normal distributed value (Box-Muller transform).
+    #[cfg(feature = "std")]
+    pub fn normal(&mut self) -> f64 {
+        let u1 = 1.0 - self.next_f64();
+        let u2 = self.next_f64();
+        (-2.0 * u1.ln()).sqrt() * (2.0 * core::f64::consts::PI * u2).cos()
+    }
+
+    /// Uniformly chosen element of the slice or `None` if it is empty.
+    pub fn choose<'a, T>(&mut self, s: &'a [T]) -> Option<&'a T> {
+        if s.is_empty() {
+            None
+        } else {
+            Some(&s[self.below(s.len() as u64) as usize])
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Random walks
+// -----------------------------------------------------------------------------
+
+const STEPS_4: [(i32, i32); 4] = [(1, 0), (0, 1), (-1, 0), (0, -1)];
+const STEPS_8: [(i32, i32); 8] = [
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+];
+
+/// Endless simple random walk on the integer lattice. The first item is the start position,
+/// every following one moves to a uniformly chosen neighbour.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::random::{LatticeWalk, Rng};
+/// use ctl::geometry::Point;
+/// let path: Vec<_> = LatticeWalk::new(Point { x: 0, y: 0 }, Rng::new(1)).take(100).collect();
+/// ```
+#[derive(Debug, Clone)]
+pub struct LatticeWalk {
+    rng: Rng,
+    pos: Point<i32>,
+    steps: &'static [(i32, i32)],
+    started: bool,
+}
+
+impl LatticeWalk {
+    /// Creates a walk moving to one of the 4 edge-adjacent neighbours each step.
+    pub fn new(start: Point<i32>, rng: Rng) -> Self {
+        LatticeWalk {
+            rng,
+            pos: start,
+            steps: &STEPS_4,
+            started: false,
+        }
+    }
+
+    /// Creates a walk moving to one of the 8 surrounding neighbours each step.
+    pub fn with_diagonals(start: Point<i32>, rng: Rng) -> Self {
+        LatticeWalk {
+            steps: &STEPS_8,
+            ..Self::new(start, rng)
+        }
+    }
+}
+
+impl Iterator for LatticeWalk {
+    type Item = Point<i32>;
+
+    fn next(&mut self) -> Option<Point<i32>> {
+        if self.started {
+            let (dx, dy) = *self.rng.choose(self.steps).unwrap();
+            self.pos = self.pos + Point { x: dx, y: dy };
+        }
+
+        self.started = true;
+        Some(self.pos)
+    }
+}
+
+/// Endless discretized Brownian motion with drift, `x' = x + mu dt + sigma sqrt(dt) Z`. The
+/// first item is the start value.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::random::{BrownianMotion, Rng};
+/// let path: Vec<f64> = BrownianMotion::new(0.0, 0.01, Rng::new(1)).take(101).collect();
+/// ```
+#[cfg(feature = "std")]
+#[derive(Debug, Clone)]
+pub struct BrownianMotion {
+    rng: Rng,
+    x: f64,
+    pub dt: f64,
+    pub drift: f64,
+    pub volatility: f64,
+    started: bool,
+}
+
+#[cfg(feature = "std")]
+impl BrownianMotion {
+    /// Creates a standard Brownian motion (no drift, unit volatility) sampled every `dt`.
+    pub fn new(start: f64, dt: f64, rng: Rng) -> Self {
+        BrownianMotion {
+            rng,
+            x: start,
+            dt,
+            drift: 0.0,
+            volatility: 1.0,
+            started: false,
+        }
+    }
+
+    /// Creates a Brownian motion with the given drift `mu` and volatility `sigma`.
+    pub fn with_params(start: f64, dt: f64, mu: f64, sigma: f64, rng: Rng) -> Self {
+        BrownianMotion {
+            drift: mu,
+            volatility: sigma,
+            ..Self::new(start, dt, rng)
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Iterator for BrownianMotion {
+    type Item = f64;
+
+    fn next(&mut self) -> Option<f64> {
+        if self.started {
+            self.x += self.drift * self.dt + self.volatility * self.dt.sqrt() * self.rng.normal();
+        }
+
+        self.started = true;
+        Some(self.x)
+    }
+}
+
+/// Endless discretized geometric Brownian motion, sampled exactly through
+/// `s' = s exp((mu - sigma^2 / 2) dt + sigma sqrt(dt) Z)`. The first item is the start value.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::random::{GeometricBrownianMotion, Rng};
+/// let prices: Vec<f64> = GeometricBrownianMotion::new(100.0, 1.0 / 252.0, 0.05, 0.2, Rng::new(1))
+///     .take(253)
+///     .collect();
+/// ```
+#[cfg(feature = "std")]
+#[derive(Debug, Clone)]
+pub struct GeometricBrownianMotion {
+    rng: Rng,
+    s: f64,
+    pub dt: f64,
+    pub drift: f64,
+    pub volatility: f64,
+    started: bool,
+}
+
+#[cfg(feature = "std")]
+impl GeometricBrownianMotion {
+    /// Creates a geometric Brownian motion with drift `mu` and volatility `sigma`.
+    pub fn new(start: f64, dt: f64, mu: f64, sigma: f64, rng: Rng) -> Self {
+        GeometricBrownianMotion {
+            rng,
+            s: start,
+            dt,
+            drift: mu,
+            volatility: sigma,
+            started: false,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Iterator for GeometricBrownianMotion {
+    type Item = f64;
+
+    fn next(&mut self) -> Option<f64> {
+        if self.started {
+            let sigma = self.volatility;
+            let exponent = (self.drift - 0.5 * sigma * sigma) * self.dt
+                + sigma * self.dt.sqrt() * self.rng.normal();
+            self.s *= exponent.exp();
+        }
+
+        self.started = true;
+        Some(self.s)
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_determinism() {
+        let mut a = Rng::new(7);
+        let mut b = Rng::new(7);
+        let mut c = Rng::new(8);
+
+        for _ in 0..100 {
+            let x = a.next_u64();
+            assert_eq!(x, b.next_u64());
+            assert_ne!(x, c.next_u64());
+        }
+    }
+
+    #[test]
+    fn test_ranges() {
+        let mut rng = Rng::new(1);
+        let mut hits = [0; 6];
+
+        for _ in 0..6000 {
+            let r = rng.range(-3..3);
+            assert!((-3..3).contains(&r));
+            hits[(r + 3) as usize] += 1;
+
+            let f = rng.next_f64();
+            assert!((0.0..1.0).contains(&f));
+        }
+
+        assert!(hits.iter().all(|&h| h > 800 && h < 1200));
+        assert_eq!(rng.range(i64::MIN..i64::MIN + 1), i64::MIN);
+    }
+
+    #[test]
+    fn test_shuffle() {
+        let mut rng = Rng::new(3);
+        let mut s: Vec<u32> = (0..50).collect();
+        rng.shuffle(&mut s);
+
+        assert_ne!(s, (0..50).collect::<Vec<_>>());
+        s.sort_unstable();
+        assert_eq!(s, (0..50).collect::<Vec<_>>());
+        assert_eq!(rng.choose::<u8>(&[]), None);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_normal() {
+        let mut rng = Rng::new(11);
+        let n = 20000;
+        let xs: Vec<f64> = (0..n).map(|_| rng.normal()).collect();
+        let mean = xs.iter().sum::<f64>() / n as f64;
+        let var = xs.iter().map(|x| (x - mean) * (x - mean)).sum::<f64>() / n as f64;
+
+        assert!(mean.abs() < 0.03);
+        assert!((var - 1.0).abs() < 0.05);
+    }
+
+    #[test]
+    fn test_walks() {
+        let start = Point { x: 3, y: -2 };
+        let path: Vec<_> = LatticeWalk::new(start, Rng::new(2)).take(200).collect();
+
+        assert_eq!(path[0], start);
+        for w in path.windows(2) {
+            assert_eq!((w[1].x - w[0].x).abs() + (w[1].y - w[0].y).abs(), 1);
+        }
+
+        let path: Vec<_> = LatticeWalk::with_diagonals(start, Rng::new(2))
+            .take(200)
+            .collect();
+        for w in path.windows(2) {
+            assert_eq!((w[1].x - w[0].x).abs().max((w[1].y - w[0].y).abs()), 1);
+        }
+
+        #[cfg(feature = "std")]
+        {
+            let b: Vec<f64> = BrownianMotion::with_params(1.0, 0.5, 2.0, 0.0, Rng::new(1))
+                .take(3)
+                .collect();
+            assert_eq!(b, vec![1.0, 2.0, 3.0]);
+
+            let g: Vec<f64> = GeometricBrownianMotion::new(10.0, 0.01, 0.1, 0.3, Rng::new(4))
+                .take(1000)
+                .collect();
+            assert_eq!(g[0], 10.0);
+            assert!(g.iter().all(|&s| s > 0.0));
+        }
+    }
+}
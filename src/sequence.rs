@@ -0,0 +1,409 @@
+use alloc::vec::Vec;
+use core::cmp::min;
+
+// -----------------------------------------------------------------------------
+// Edit distance
+// -----------------------------------------------------------------------------
+
+/// Costs of the single operations used by the edit distance functions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EditCosts {
+    pub insert: usize,
+    pub delete: usize,
+    pub substitute: usize,
+    pub transpose: usize,
+}
+
+impl Default for EditCosts {
+    fn default() -> Self {
+        EditCosts {
+            insert: 1,
+            delete: 1,
+            substitute: 1,
+            transpose: 1,
+        }
+    }
+}
+
+/// Calculates the Levenshtein distance between 2 sequences with unit costs.
+///
+/// # Arguments
+///
+/// * `a` - Source sequence
+/// * `b` - Target sequence
+///
+/// # Examples
+///
+/// ```
+/// use ctl::sequence::levenshtein;
+/// let d = levenshtein(b"kitten", b"sitting"); // 3
+/// ```
+pub fn levenshtein<T: PartialEq>(a: &[T], b: &[T]) -> usize {
+    levenshtein_with(a, b, &EditCosts::default())
+}
+
+/// Calculates the Levenshtein distance between 2 sequences using custom operation costs.
+/// The `transpose` cost is ignored.
+///
+/// # Arguments
+///
+/// * `a` - Source sequence
+/// * `b` - Target sequence
+/// * `costs` - Cost of each edit operation
+///
+/// # Examples
+///
+/// ```
+/// use ctl::sequence::{levenshtein_with, EditCosts};
+/// let costs = EditCosts { substitute: 3, ..EditCosts::default() };
+/// let d = levenshtein_with(b"abc", b"abd", &costs); // 2 (delete + insert)
+/// ```
+pub fn levenshtein_with<T: PartialEq>(a: &[T], b: &[T], costs: &EditCosts) -> usize {
+    let mut prev: Vec<usize> = (0..=b.len()).map(|j| j * costs.insert).collect();
+    let mut curr = vec![0; b.len() + 1];
+
+    for i in 1..=a.len() {
+        curr[0] = i * costs.delete;
+
+        for j in 1..=b.len() {
+            let sub = if a[i - 1] == b[j - 1] {
+                prev[j - 1]
+            } else {
+                prev[j - 1] + costs.substitute
+            };
+
+            curr[j] = min(sub, min(prev[j] + costs.delete, curr[j - 1] + costs.insert));
+        }
+
+        core::mem::swap(&mut prev, &mut curr);
+    }
+
+    prev[b.len()]
+}
+
+/// Calculates the Damerau-Levenshtein distance between 2 sequences with unit costs. This is
+/// the restricted variant (optimal string alignment) where no substring is edited twice.
+///
+/// # Arguments
+///
+/// * `a` - Source sequence
+/// * `b` - Target sequence
+///
+/// # Examples
+///
+/// ```
+/// use ctl::sequence::damerau_levenshtein;
+/// let d = damerau_levenshtein(b"ca", b"ac"); // 1
+/// ```
+pub fn damerau_levenshtein<T: PartialEq>(a: &[T], b: &[T]) -> usize {
+    damerau_levenshtein_with(a, b, &EditCosts::default())
+}
+
+/// Calculates the restricted Damerau-Levenshtein distance between 2 sequences using custom
+/// operation costs.
+///
+/// # Arguments
+///
+/// * `a` - Source sequence
+/// * `b` - Target sequence
+/// * `costs` - Cost of each edit operation
+///
+/// # Examples
+///
+/// ```
+/// use ctl::sequence::{damerau_levenshtein_with, EditCosts};
+/// let costs = EditCosts { transpose: 5, ..EditCosts::default() };
+/// let d = damerau_levenshtein_with(b"ca", b"ac", &costs); // 2 (2 substitutions)
+/// ```
+pub fn damerau_levenshtein_with<T: PartialEq>(a: &[T], b: &[T], costs: &EditCosts) -> usize {
+    let mut prev2 = vec![0; b.len() + 1];
+    let mut prev: Vec<usize> = (0..=b.len()).map(|j| j * costs.insert).collect();
+    let mut curr = vec![0; b.len() + 1];
+
+    for i in 1..=a.len() {
+        curr[0] = i * costs.delete;
+
+        for j in 1..=b.len() {
+            let sub = if a[i - 1] == b[j - 1] {
+                prev[j - 1]
+            } else {
+                prev[j - 1] + costs.substitute
+            };
+
+            curr[j] = min(sub, min(prev[j] + costs.delete, curr[j - 1] + costs.insert));
+
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                curr[j] = min(curr[j], prev2[j - 2] + costs.transpose);
+            }
+        }
+
+        core::mem::swap(&mut prev2, &mut prev);
+        core::mem::swap(&mut prev, &mut curr);
+    }
+
+    prev[b.len()]
+}
+
+// -----------------------------------------------------------------------------
+// Diff
+// -----------------------------------------------------------------------------
+
+/// Single step of an edit script. Indices refer to the old (`a`) and new (`b`) sequence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Edit {
+    /// `Keep(i, j)`: `a[i]` is kept and matches `b[j]`
+    Keep(usize, usize),
+    /// `Delete(i)`: `a[i]` is removed
+    Delete(usize),
+    /// `Insert(j)`: `b[j]` is inserted
+    Insert(usize),
+}
+
+/// Finds a point on a shortest edit path from `a` to `b` halfway through it, by running the
+/// Myers search from both ends until they overlap. Both sequences have to be non-empty.
+/// Returns `None` if they have nothing in common.
+fn middle<T: PartialEq>(a: &[T], b: &[T]) -> Option<(usize, usize)> {
+    let (n, m) = (a.len() as isize, b.len() as isize);
+    let max_d = (n + m + 1) / 2;
+    let at = |k: isize| (k + max_d) as usize;
+    let delta = n - m;
+    let odd = delta % 2 != 0;
+
+    // Furthest x on every diagonal, counted from the end for the reverse search
+    let mut fwd = vec![-1isize; 2 * max_d as usize + 2];
+    let mut rev = fwd.clone();
+    fwd[at(1)] = 0;
+    rev[at(1)] = 0;
+
+    // Diagonals that ran off the grid are skipped from then on
+    let (mut fwd_lo, mut fwd_hi, mut rev_lo, mut rev_hi) = (0, 0, 0, 0);
+
+    for d in 0..max_d {
+        for k in (-d + fwd_lo..=d - fwd_hi).step_by(2) {
+            let mut x = if k == -d || (k != d && fwd[at(k - 1)] < fwd[at(k + 1)]) {
+                fwd[at(k + 1)]
+            } else {
+                fwd[at(k - 1)] + 1
+            };
+            let mut y = x - k;
+            while x < n && y < m && a[x as usize] == b[y as usize] {
+                x += 1;
+                y += 1;
+            }
+            fwd[at(k)] = x;
+
+            if x > n {
+                fwd_hi += 2;
+            } else if y > m {
+                fwd_lo += 2;
+            } else if odd {
+                let r = delta - k;
+                if r.abs() <= max_d && rev[at(r)] != -1 && x >= n - rev[at(r)] {
+                    return Some((x as usize, y as usize));
+                }
+            }
+        }
+
+        for k in (-d + rev_lo..=d - rev_hi).step_by(2) {
+            let mut x = if k == -d || (k != d && rev[at(k - 1)] < rev[at(k + 1)]) {
+                rev[at(k + 1)]
+            } else {
+                rev[at(k - 1)] + 1
+            };
+            let mut y = x - k;
+            while x < n && y < m && a[(n - x - 1) as usize] == b[(m - y - 1) as usize] {
+                x += 1;
+                y += 1;
+            }
+            rev[at(k)] = x;
+
+            if x > n {
+                rev_hi += 2;
+            } else if y > m {
+                rev_lo += 2;
+            } else if !odd {
+                let f = delta - k;
+                if f.abs() <= max_d && fwd[at(f)] != -1 && fwd[at(f)] >= n - x {
+                    let x = fwd[at(f)];
+                    return Some((x as usize, (x - f) as usize));
+                }
+            }
+        }
+    }
+
+    None
+}
+
+/// Appends a shortest edit script from `a` to `b`, whose first elements are at `i` and `j`.
+fn diff_into<T: PartialEq>(a: &[T], b: &[T], i: usize, j: usize, edits: &mut Vec<Edit>) {
+    let pre = a.iter().zip(b).take_while(|(x, y)| x == y).count();
+    let suf = a[pre..]
+        .iter()
+        .rev()
+        .zip(b[pre..].iter().rev())
+        .take_while(|(x, y)| x == y)
+        .count();
+    let (a_mid, b_mid) = (&a[pre..a.len() - suf], &b[pre..b.len() - suf]);
+    let (i_mid, j_mid) = (i + pre, j + pre);
+
+    edits.extend((0..pre).map(|k| Edit::Keep(i + k, j + k)));
+
+    let split = if a_mid.is_empty() || b_mid.is_empty() {
+        None
+    } else {
+        middle(a_mid, b_mid)
+    };
+    match split {
+        Some((x, y)) => {
+            diff_into(&a_mid[..x], &b_mid[..y], i_mid, j_mid, edits);
+            diff_into(&a_mid[x..], &b_mid[y..], i_mid + x, j_mid + y, edits);
+        }
+        None => {
+            edits.extend((0..a_mid.len()).map(|k| Edit::Delete(i_mid + k)));
+            edits.extend((0..b_mid.len()).map(|k| Edit::Insert(j_mid + k)));
+        }
+    }
+
+    let (i_suf, j_suf) = (i_mid + a_mid.len(), j_mid + b_mid.len());
+    edits.extend((0..suf).map(|k| Edit::Keep(i_suf + k, j_suf + k)));
+}
+
+/// Calculates a shortest edit script transforming `a` into `b` using the Myers diff algorithm
+/// in its linear space variant, which splits the problem at the middle of an edit path. Runs
+/// in O((N + M) D) time and O(N + M) space where D is the size of the edit script.
+///
+/// # Arguments
+///
+/// * `a` - Old sequence
+/// * `b` - New sequence
+///
+/// # Examples
+///
+/// ```
+/// use ctl::sequence::{diff, Edit};
+/// let e = diff(b"abc", b"abd"); // [Keep(0, 0), Keep(1, 1), Delete(2), Insert(2)]
+/// ```
+pub fn diff<T: PartialEq>(a: &[T], b: &[T]) -> Vec<Edit> {
+    let mut edits = Vec::new();
+    diff_into(a, b, 0, 0, &mut edits);
+    edits
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn apply<T: Clone>(a: &[T], b: &[T], edits: &[Edit]) -> Vec<T> {
+        edits
+            .iter()
+            .filter_map(|e| match *e {
+                Edit::Keep(i, _) => Some(a[i].clone()),
+                Edit::Insert(j) => Some(b[j].clone()),
+                Edit::Delete(_) => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_levenshtein() {
+        assert_eq!(levenshtein(b"kitten", b"sitting"), 3);
+        assert_eq!(levenshtein(b"", b"abc"), 3);
+        assert_eq!(levenshtein(b"abc", b""), 3);
+        assert_eq!(levenshtein(b"flaw", b"lawn"), 2);
+        assert_eq!(levenshtein(b"ca", b"ac"), 2);
+
+        let costs = EditCosts {
+            insert: 2,
+            delete: 3,
+            substitute: 10,
+            transpose: 1,
+        };
+        assert_eq!(levenshtein_with(b"a", b"b", &costs), 5);
+        assert_eq!(levenshtein_with(b"", b"ab", &costs), 4);
+    }
+
+    #[test]
+    fn test_damerau_levenshtein() {
+        assert_eq!(damerau_levenshtein(b"ca", b"ac"), 1);
+        assert_eq!(damerau_levenshtein(b"ca", b"abc"), 3);
+        assert_eq!(damerau_levenshtein(b"kitten", b"sitting"), 3);
+        assert_eq!(damerau_levenshtein(b"abcdef", b"badcfe"), 3);
+    }
+
+    #[test]
+    fn test_diff() {
+        let a = b"ABCABBA";
+        let b = b"CBABAC";
+        let e = diff(a, b);
+
+        assert_eq!(apply(a, b, &e), b.to_vec());
+        assert_eq!(e.iter().filter(|e| !matches!(e, Edit::Keep(..))).count(), 5);
+
+        assert_eq!(
+            diff(b"abc", b"abd"),
+            vec![
+                Edit::Keep(0, 0),
+                Edit::Keep(1, 1),
+                Edit::Delete(2),
+                Edit::Insert(2)
+            ]
+        );
+        assert_eq!(diff::<u8>(b"", b""), vec![]);
+        assert_eq!(diff(b"", b"ab"), vec![Edit::Insert(0), Edit::Insert(1)]);
+        assert_eq!(diff(b"ab", b""), vec![Edit::Delete(0), Edit::Delete(1)]);
+    }
+
+    #[test]
+    fn test_diff_minimal() {
+        // The script size matches the edit distance without substitutions
+        let costs = EditCosts {
+            substitute: 2,
+            ..EditCosts::default()
+        };
+        let mut seed = 5u64;
+        let mut next = |k: u64| {
+            seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (seed >> 33) % k
+        };
+
+        for _ in 0..500 {
+            let (n, m, k) = (next(30) as usize, next(30) as usize, 1 + next(4));
+            let a: Vec<u64> = (0..n).map(|_| next(k)).collect();
+            let b: Vec<u64> = (0..m).map(|_| next(k)).collect();
+            let e = diff(&a, &b);
+
+            assert_eq!(apply(&a, &b, &e), b);
+            let changes = e.iter().filter(|e| !matches!(e, Edit::Keep(..))).count();
+            assert_eq!(changes, levenshtein_with(&a, &b, &costs));
+        }
+    }
+
+    #[test]
+    fn test_diff_large() {
+        // Disjoint inputs need every element edited, which the old trace kept in O((N + M) D)
+        let a: Vec<u32> = (0..4000).collect();
+        let b: Vec<u32> = (4000..8000).collect();
+        let e = diff(&a, &b);
+        assert_eq!(e.len(), 8000);
+        assert_eq!(apply(&a, &b, &e), b);
+
+        // Long inputs with a few changes
+        let a: Vec<u32> = (0..100_000).collect();
+        let mut b = a.clone();
+        for i in (0..b.len()).step_by(9973) {
+            b[i] += 1_000_000;
+        }
+        b.drain(500..700);
+        b.extend([7, 8, 9]);
+        let e = diff(&a, &b);
+        assert_eq!(apply(&a, &b, &e), b);
+        let changes = e.iter().filter(|e| !matches!(e, Edit::Keep(..))).count();
+        assert_eq!(changes, 2 * 11 + 200 + 3);
+    }
+}
@@ -0,0 +1,816 @@
+use alloc::collections::VecDeque;
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use core::f64::consts::PI;
+use core::ops::{Add, Mul, Sub};
+
+mod filter;
+mod resample;
+
+pub use filter::{Biquad, Filter, Fir};
+pub use resample::{decimate, resample_linear, resample_polyphase, Sample};
+
+// -----------------------------------------------------------------------------
+// Complex
+// -----------------------------------------------------------------------------
+
+/// Complex number, the value type of spectra.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Complex {
+    pub re: f64,
+    pub im: f64,
+}
+
+impl Complex {
+    pub fn new(re: f64, im: f64) -> Self {
+        Complex { re, im }
+    }
+
+    /// e^(i phi), the point at angle `phi` on the unit circle.
+    pub fn from_angle(phi: f64) -> Self {
+        Complex::new(phi.cos(), phi.sin())
+    }
+
+    /// Magnitude, the amplitude of a frequency.
+    pub fn norm(self) -> f64 {
+        self.re.hypot(self.im)
+    }
+
+    /// Angle in (-pi, pi], the phase of a frequency.
+    pub fn arg(self) -> f64 {
+        self.im.atan2(self.re)
+    }
+
+    pub fn conj(self) -> Self {
+        Complex::new(self.re, -self.im)
+    }
+}
+
+impl Add for Complex {
+    type Output = Complex;
+
+    fn add(self, o: Complex) -> Complex {
+        Complex::new(self.re + o.re, self.im + o.im)
+    }
+}
+
+impl Sub for Complex {
+    type Output = Complex;
+
+    fn sub(self, o: Complex) -> Complex {
+        Complex::new(self.re - o.re, self.im - o.im)
+    }
+}
+
+impl Mul for Complex {
+    type Output = Complex;
+
+    fn mul(self, o: Complex) -> Complex {
+        Complex::new(
+            self.re * o.re - self.im * o.im,
+            self.re * o.im + self.im * o.re,
+        )
+    }
+}
+
+// -----------------------------------------------------------------------------
+// FFT
+// -----------------------------------------------------------------------------
+
+/// Transforms in place into the DFT X(k) = Σ x(m) e^(-2 pi i k m / n) by the radix 2
+/// Cooley-Tukey algorithm in O(n log n), or back with `invert`, which divides by n. The length
+/// has to be a power of 2.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::signal::{fft, Complex};
+/// let mut a: Vec<_> = [1.0, 0.0, -1.0, 0.0].iter().map(|&x| Complex::new(x, 0.0)).collect();
+/// fft(&mut a, false); // [0, 2, 0, 2]
+/// fft(&mut a, true); // [1, 0, -1, 0] again
+/// ```
+pub fn fft(a: &mut [Complex], invert: bool) {
+    let n = a.len();
+    assert!(n.is_power_of_two(), "length has to be a power of 2");
+
+    // Bit reversal permutation
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            a.swap(i, j);
+        }
+    }
+
+    let sign = if invert { 1.0 } else { -1.0 };
+    let mut len = 2;
+    while len <= n {
+        // Twiddles straight from the angle, as repeated multiplication accumulates errors
+        let twiddles: Vec<Complex> = (0..len / 2)
+            .map(|k| Complex::from_angle(sign * 2.0 * PI * k as f64 / len as f64))
+            .collect();
+
+        for chunk in a.chunks_mut(len) {
+            let (lo, hi) = chunk.split_at_mut(len / 2);
+            for ((x, y), &w) in lo.iter_mut().zip(hi).zip(&twiddles) {
+                let (u, v) = (*x, *y * w);
+                *x = u + v;
+                *y = u - v;
+            }
+        }
+        len <<= 1;
+    }
+
+    if invert {
+        let inv = 1.0 / n as f64;
+        a.iter_mut()
+            .for_each(|x| *x = Complex::new(x.re * inv, x.im * inv));
+    }
+}
+
+/// Linear convolution of real sequences through [`fft`].
+fn convolve_fft(a: &[f64], b: &[f64]) -> Vec<f64> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+
+    let len = a.len() + b.len() - 1;
+    let n = len.next_power_of_two();
+    let complex = |x: &[f64]| {
+        let mut c: Vec<Complex> = x.iter().map(|&v| Complex::new(v, 0.0)).collect();
+        c.resize(n, Complex::default());
+        fft(&mut c, false);
+        c
+    };
+    let (mut fa, fb) = (complex(a), complex(b));
+
+    for (x, &y) in fa.iter_mut().zip(&fb) {
+        *x = *x * y;
+    }
+    fft(&mut fa, true);
+    fa.iter().take(len).map(|c| c.re).collect()
+}
+
+// -----------------------------------------------------------------------------
+// Correlation
+// -----------------------------------------------------------------------------
+
+/// Scaling of correlation sums.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Normalization {
+    /// Plain sums of products
+    None,
+    /// Divided by the length of the longer sequence, the usual estimator for periodicity, whose
+    /// values shrink with the lag
+    Biased,
+    /// Divided by the number of overlapping products at each lag
+    Unbiased,
+    /// Divided by the square root of the product of both energies Σ x², so values lie in
+    /// [-1, 1] and an autocorrelation is 1 at lag 0. All zeros for a zero sequence. Subtract
+    /// the means first to get Pearson correlation coefficients.
+    Coefficient,
+}
+
+/// Products overlapping at lag k when x is shifted against y.
+fn overlap(nx: usize, ny: usize, k: isize) -> usize {
+    let lo = (-k).max(0);
+    let hi = (ny as isize).min(nx as isize - k);
+    (hi - lo).max(0) as usize
+}
+
+/// Scales raw sums of lags -(ny - 1) to nx - 1.
+fn normalize(mut r: Vec<f64>, x: &[f64], y: &[f64], norm: Normalization) -> Vec<f64> {
+    let (nx, ny) = (x.len(), y.len());
+    match norm {
+        Normalization::None => {}
+        Normalization::Biased => {
+            let n = nx.max(ny) as f64;
+            r.iter_mut().for_each(|v| *v /= n);
+        }
+        Normalization::Unbiased => {
+            for (i, v) in r.iter_mut().enumerate() {
+                *v /= overlap(nx, ny, i as isize - (ny as isize - 1)) as f64;
+            }
+        }
+        Normalization::Coefficient => {
+            let energy = |s: &[f64]| s.iter().map(|v| v * v).sum::<f64>();
+            let scale = (energy(x) * energy(y)).sqrt();
+            r.iter_mut()
+                .for_each(|v| *v = if scale == 0.0 { 0.0 } else { *v / scale });
+        }
+    }
+    r
+}
+
+/// Cross-correlation r(k) = Σ x(m + k) y(m) of real sequences over all lags k from
+/// -(y.len() - 1) to x.len() - 1, computed directly in O(nx ny). Index i holds lag
+/// i - (y.len() - 1), so the peak of a copy of y delayed by d within x lies at d + y.len() - 1.
+/// See [`cross_correlation_fft`] for long inputs.
+///
+/// # Arguments
+///
+/// * `x` - Sequence to search in
+/// * `y` - Sequence shifted along x
+/// * `norm` - Scaling of the sums
+///
+/// # Examples
+///
+/// ```
+/// use ctl::signal::{cross_correlation, Normalization};
+/// let r = cross_correlation(&[0.0, 0.0, 1.0, 2.0], &[1.0, 2.0], Normalization::None);
+/// // [0, 0, 2, 5, 2], lags -1 to 3 with the peak at lag 2
+/// ```
+pub fn cross_correlation(x: &[f64], y: &[f64], norm: Normalization) -> Vec<f64> {
+    if x.is_empty() || y.is_empty() {
+        return Vec::new();
+    }
+
+    let ny = y.len() as isize;
+    let r = (-(ny - 1)..x.len() as isize)
+        .map(|k| {
+            y.iter()
+                .enumerate()
+                .filter_map(|(m, &b)| {
+                    let i = m as isize + k;
+                    x.get(usize::try_from(i).ok()?).map(|&a| a * b)
+                })
+                .sum()
+        })
+        .collect();
+    normalize(r, x, y, norm)
+}
+
+/// [`cross_correlation`] through the FFT in O((nx + ny) log(nx + ny)). Agrees up to rounding
+/// errors.
+pub fn cross_correlation_fft(x: &[f64], y: &[f64], norm: Normalization) -> Vec<f64> {
+    let reversed: Vec<f64> = y.iter().rev().copied().collect();
+    normalize(convolve_fft(x, &reversed), x, y, norm)
+}
+
+/// Autocorrelation r(k) = Σ x(m + k) x(m) for the lags 0 to `max_lag`, computed directly in
+/// O(n max_lag). Lags beyond the length are left out. Peaks at positive lags reveal periods.
+/// See [`autocorrelation_fft`] for many lags.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::signal::{autocorrelation, Normalization};
+/// let x = [1.0, -1.0, 1.0, -1.0];
+/// let r = autocorrelation(&x, 3, Normalization::Coefficient); // [1, -0.75, 0.5, -0.25]
+/// ```
+pub fn autocorrelation(x: &[f64], max_lag: usize, norm: Normalization) -> Vec<f64> {
+    let lags = (max_lag + 1).min(x.len());
+    let r = (0..lags)
+        .map(|k| x[k..].iter().zip(x).map(|(a, b)| a * b).sum())
+        .collect();
+    normalize_auto(r, x, norm)
+}
+
+/// [`autocorrelation`] through the FFT in O(n log n). Agrees up to rounding errors.
+pub fn autocorrelation_fft(x: &[f64], max_lag: usize, norm: Normalization) -> Vec<f64> {
+    let lags = (max_lag + 1).min(x.len());
+    let reversed: Vec<f64> = x.iter().rev().copied().collect();
+    let mut r = convolve_fft(x, &reversed);
+    r.drain(..x.len().saturating_sub(1));
+    r.truncate(lags);
+    normalize_auto(r, x, norm)
+}
+
+/// Scales raw sums of lags 0 on.
+fn normalize_auto(r: Vec<f64>, x: &[f64], norm: Normalization) -> Vec<f64> {
+    // Pad with the negative lags, which share the scaling of the positive ones
+    let skip = x.len().saturating_sub(1);
+    let mut full = vec![0.0; skip];
+    full.extend(r);
+    let mut full = normalize(full, x, x, norm);
+    full.drain(..skip);
+    full
+}
+
+// -----------------------------------------------------------------------------
+// Windows
+// -----------------------------------------------------------------------------
+
+/// Tapers multiplied onto a block of samples before a transform. Cutting a signal off hard
+/// smears a frequency over many bins, and the tapers trade a wider main peak for far less of
+/// that leakage: Hann and Hamming moderately, Blackman the most.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Window {
+    /// All ones, the block as is
+    Rectangular,
+    /// 0.5 - 0.5 cos, reaching 0 at both ends
+    Hann,
+    /// 0.54 - 0.46 cos, cancelling the nearest side lobe
+    Hamming,
+    /// 0.42 - 0.5 cos + 0.08 cos 2, the lowest side lobes
+    Blackman,
+}
+
+impl Window {
+    /// Window of n samples symmetric around its middle, as used for filter design.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ctl::signal::Window;
+    /// let w = Window::Hann.symmetric(5); // [0, 0.5, 1, 0.5, 0]
+    /// ```
+    pub fn symmetric(self, n: usize) -> Vec<f64> {
+        self.samples(n, n.saturating_sub(1))
+    }
+
+    /// Window of n samples with period n, one sample of a symmetric window of n + 1 short, as
+    /// used for spectral analysis.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ctl::signal::Window;
+    /// let w = Window::Hann.periodic(4); // [0, 0.5, 1, 0.5]
+    /// ```
+    pub fn periodic(self, n: usize) -> Vec<f64> {
+        self.samples(n, n)
+    }
+
+    /// Samples 0 to n - 1 of the window spanning `period`.
+    fn samples(self, n: usize, period: usize) -> Vec<f64> {
+        if period == 0 {
+            return vec![1.0; n];
+        }
+
+        (0..n)
+            .map(|i| {
+                let phi = 2.0 * PI * i as f64 / period as f64;
+                match self {
+                    Window::Rectangular => 1.0,
+                    Window::Hann => 0.5 - 0.5 * phi.cos(),
+                    Window::Hamming => 0.54 - 0.46 * phi.cos(),
+                    Window::Blackman => 0.42 - 0.5 * phi.cos() + 0.08 * (2.0 * phi).cos(),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Power spectral density by Welch's method: the signal is cut into overlapping segments,
+/// each is windowed and transformed, and their periodograms averaged, which lowers the
+/// variance at the cost of frequency resolution. Samples after the last full segment are left
+/// out and the mean isn't removed, so a constant offset shows up at bin 0.
+///
+/// Returns the one sided density of the bins 0 to segment_len / 2, bin k lying at frequency
+/// k sample_rate / segment_len. Summing it times sample_rate / segment_len gives about the
+/// mean power Σ x² / n of the signal.
+///
+/// # Arguments
+///
+/// * `x` - Signal, at least one segment long
+/// * `sample_rate` - Samples per unit of time
+/// * `segment_len` - Samples per segment, a power of 2
+/// * `overlap` - Samples shared by neighbouring segments, below `segment_len`
+/// * `window` - Taper of each segment, usually [`Window::Hann`] with half a segment overlap
+///
+/// # Examples
+///
+/// ```
+/// use ctl::signal::{welch, Window};
+/// let x: Vec<f64> = (0..1024).map(|i| (i as f64 * std::f64::consts::PI / 4.0).sin()).collect();
+/// let p = welch(&x, 1000.0, 64, 32, Window::Hann);
+/// // 33 bins 15.625 Hz apart, the peak at bin 8 for 125 Hz
+/// ```
+pub fn welch(
+    x: &[f64],
+    sample_rate: f64,
+    segment_len: usize,
+    overlap: usize,
+    window: Window,
+) -> Vec<f64> {
+    assert!(
+        segment_len.is_power_of_two(),
+        "segment length has to be a power of 2"
+    );
+    assert!(overlap < segment_len, "overlap spans the whole segment");
+    assert!(x.len() >= segment_len, "signal is shorter than a segment");
+
+    let w = window.periodic(segment_len);
+    let energy: f64 = w.iter().map(|v| v * v).sum();
+    let hop = segment_len - overlap;
+    let segments = (x.len() - segment_len) / hop + 1;
+
+    let mut power = vec![0.0; segment_len / 2 + 1];
+    let mut buf = vec![Complex::default(); segment_len];
+    for s in 0..segments {
+        let segment = &x[s * hop..s * hop + segment_len];
+        for ((b, &v), &t) in buf.iter_mut().zip(segment).zip(&w) {
+            *b = Complex::new(v * t, 0.0);
+        }
+        fft(&mut buf, false);
+
+        for (p, b) in power.iter_mut().zip(&buf) {
+            *p += b.re * b.re + b.im * b.im;
+        }
+    }
+
+    // Fold the negative frequencies onto the positive ones, except the unpaired 0 and Nyquist
+    let scale = 1.0 / (sample_rate * energy * segments as f64);
+    let last = power.len() - 1;
+    for (k, p) in power.iter_mut().enumerate() {
+        let one_sided = if k == 0 || k == last { 1.0 } else { 2.0 };
+        *p *= scale * one_sided;
+    }
+    power
+}
+
+// -----------------------------------------------------------------------------
+// Goertzel
+// -----------------------------------------------------------------------------
+
+/// Streaming Goertzel detector measuring the magnitude of a single frequency over blocks of
+/// samples, with one multiplication per sample. Equals the magnitude of the DFT bin when the
+/// frequency is a multiple of sample_rate / block_len, any other frequency works as well.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::signal::Goertzel;
+/// // Look for 400 Hz in blocks of 100 samples at 8000 Hz
+/// let mut g = Goertzel::new(400.0, 8000.0, 100);
+/// for i in 0..100 {
+///     let t = i as f64 / 8000.0;
+///     if let Some(m) = g.push((2.0 * std::f64::consts::PI * 400.0 * t).sin()) {
+///         // m ~ 50, half the block length for a unit sine
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Goertzel {
+    /// 2 cos(w) of the angular frequency w per sample
+    coeff: f64,
+    w: f64,
+    block_len: usize,
+    count: usize,
+    s1: f64,
+    s2: f64,
+}
+
+impl Goertzel {
+    /// Creates a detector for `freq` in blocks of `block_len` samples taken at `sample_rate`.
+    pub fn new(freq: f64, sample_rate: f64, block_len: usize) -> Self {
+        assert!(block_len > 0, "block length is 0");
+
+        let w = 2.0 * PI * freq / sample_rate;
+        Goertzel {
+            coeff: 2.0 * w.cos(),
+            w,
+            block_len,
+            count: 0,
+            s1: 0.0,
+            s2: 0.0,
+        }
+    }
+
+    /// Adds a sample. Returns the magnitude when it completes a block, then starts the next.
+    pub fn push(&mut self, x: f64) -> Option<f64> {
+        let s0 = x + self.coeff * self.s1 - self.s2;
+        self.s2 = self.s1;
+        self.s1 = s0;
+        self.count += 1;
+
+        if self.count < self.block_len {
+            return None;
+        }
+
+        let m = self.magnitude();
+        self.reset();
+        Some(m)
+    }
+
+    /// Magnitude of the samples of the current block so far.
+    pub fn magnitude(&self) -> f64 {
+        (self.s1 * self.s1 + self.s2 * self.s2 - self.coeff * self.s1 * self.s2)
+            .max(0.0)
+            .sqrt()
+    }
+
+    /// Complex DFT value of the samples of the current block so far, with the phase relative
+    /// to the first sample of the block.
+    pub fn value(&self) -> Complex {
+        // y = s1 - e^(-iw) s2, turned back by the n - 1 steps since the block start
+        let y = Complex::new(self.s1 - self.w.cos() * self.s2, self.w.sin() * self.s2);
+        let n = self.count.max(1) as f64;
+        y * Complex::from_angle(-self.w * (n - 1.0))
+    }
+
+    /// Drops the samples of the current block.
+    pub fn reset(&mut self) {
+        self.count = 0;
+        self.s1 = 0.0;
+        self.s2 = 0.0;
+    }
+}
+
+/// Magnitude of a single frequency over all samples by the Goertzel algorithm in O(n), see
+/// [`Goertzel`].
+///
+/// # Arguments
+///
+/// * `samples` - Signal
+/// * `freq` - Frequency to measure
+/// * `sample_rate` - Samples per unit of time of `freq`
+///
+/// # Examples
+///
+/// ```
+/// use ctl::signal::goertzel;
+/// let m = goertzel(&[1.0, 0.0, -1.0, 0.0], 1.0, 4.0); // 2, a quarter of the sample rate
+/// ```
+pub fn goertzel(samples: &[f64], freq: f64, sample_rate: f64) -> f64 {
+    // A block that never ends, so the state stays around for reading
+    let mut g = Goertzel::new(freq, sample_rate, usize::MAX);
+    for &x in samples {
+        g.push(x);
+    }
+    g.magnitude()
+}
+
+// -----------------------------------------------------------------------------
+// Sliding DFT
+// -----------------------------------------------------------------------------
+
+/// DFT bins X(k) = Σ x(m) e^(-2 pi i k m / n) over the last n samples, oldest first, updated
+/// in O(1) per bin and sample by X(k) ← (X(k) - oldest + newest) e^(2 pi i k / n). The window
+/// starts filled with zeros. Rounding errors would pile up in the recursion, so the bins are
+/// recomputed from the window once per n samples, which keeps the amortized cost.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::signal::SlidingDft;
+/// let mut dft = SlidingDft::new(8, &[0, 2]);
+/// for i in 0..20 {
+///     dft.push([1.0, 0.0, -1.0, 0.0][i % 4]);
+/// }
+/// let m = dft.bins()[1].norm(); // 4, bin 2 of 8 is a quarter of the sample rate
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct SlidingDft {
+    window: VecDeque<f64>,
+    /// Indices k of the tracked bins
+    ks: Vec<usize>,
+    /// e^(2 pi i k / n) per tracked bin
+    twiddles: Vec<Complex>,
+    bins: Vec<Complex>,
+    /// Samples since the last exact recomputation
+    since_exact: usize,
+}
+
+impl SlidingDft {
+    /// Tracks the bins `ks`, each below n, over windows of n samples.
+    pub fn new(n: usize, ks: &[usize]) -> Self {
+        assert!(n > 0, "window length is 0");
+        assert!(ks.iter().all(|&k| k < n), "bin index out of range");
+
+        SlidingDft {
+            window: core::iter::repeat_n(0.0, n).collect(),
+            ks: ks.to_vec(),
+            twiddles: ks
+                .iter()
+                .map(|&k| Complex::from_angle(2.0 * PI * k as f64 / n as f64))
+                .collect(),
+            bins: vec![Complex::default(); ks.len()],
+            since_exact: 0,
+        }
+    }
+
+    /// Shifts a sample into the window and updates the bins.
+    pub fn push(&mut self, x: f64) {
+        let oldest = self.window.pop_front().unwrap_or(0.0);
+        self.window.push_back(x);
+
+        self.since_exact += 1;
+        if self.since_exact == self.window.len() {
+            self.recompute();
+            return;
+        }
+
+        let delta = Complex::new(x - oldest, 0.0);
+        for (b, &t) in self.bins.iter_mut().zip(&self.twiddles) {
+            *b = (*b + delta) * t;
+        }
+    }
+
+    /// Calculates the bins directly from the window in O(n) each.
+    fn recompute(&mut self) {
+        let n = self.window.len() as f64;
+        for (b, &k) in self.bins.iter_mut().zip(&self.ks) {
+            *b = self
+                .window
+                .iter()
+                .enumerate()
+                .map(|(m, &x)| {
+                    Complex::from_angle(-2.0 * PI * (k * m) as f64 / n) * Complex::new(x, 0.0)
+                })
+                .fold(Complex::default(), |acc, c| acc + c);
+        }
+        self.since_exact = 0;
+    }
+
+    /// Current values of the tracked bins, in the order given to [`SlidingDft::new`].
+    pub fn bins(&self) -> &[Complex] {
+        &self.bins
+    }
+
+    /// Samples in the window, oldest first.
+    pub fn window(&self) -> &VecDeque<f64> {
+        &self.window
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::random::Rng;
+
+    /// Direct DFT bin of a signal.
+    fn dft(x: &[f64], k: usize) -> Complex {
+        let n = x.len() as f64;
+        x.iter()
+            .enumerate()
+            .map(|(m, &v)| {
+                Complex::from_angle(-2.0 * PI * (k * m) as f64 / n) * Complex::new(v, 0.0)
+            })
+            .fold(Complex::default(), |acc, c| acc + c)
+    }
+
+    fn close(a: Complex, b: Complex, tol: f64) -> bool {
+        (a - b).norm() < tol
+    }
+
+    #[test]
+    fn test_goertzel() {
+        let mut rng = Rng::new(4);
+        let x: Vec<f64> = (0..64).map(|_| rng.next_f64() - 0.5).collect();
+
+        let mut g = Goertzel::new(5.0, 64.0, 64);
+        for (i, &v) in x.iter().enumerate() {
+            assert_eq!(g.push(v).is_some(), i == 63);
+            if i == 40 {
+                // Partial blocks match the DFT of the samples so far at the same frequency
+                let expected: Complex = x[..=i]
+                    .iter()
+                    .enumerate()
+                    .map(|(m, &v)| {
+                        Complex::from_angle(-2.0 * PI * 5.0 * m as f64 / 64.0)
+                            * Complex::new(v, 0.0)
+                    })
+                    .fold(Complex::default(), |acc, c| acc + c);
+                assert!(close(g.value(), expected, 1e-9));
+                assert!((g.magnitude() - expected.norm()).abs() < 1e-9);
+            }
+        }
+        assert!((goertzel(&x, 5.0, 64.0) - dft(&x, 5).norm()).abs() < 1e-9);
+
+        // A pure tone shows up at its own frequency only
+        let tone: Vec<f64> = (0..200)
+            .map(|i| (2.0 * PI * 50.0 * i as f64 / 1000.0).cos())
+            .collect();
+        assert!((goertzel(&tone, 50.0, 1000.0) - 100.0).abs() < 1e-9);
+        assert!(goertzel(&tone, 100.0, 1000.0) < 1e-9);
+        assert_eq!(goertzel(&[], 1.0, 2.0), 0.0);
+    }
+
+    #[test]
+    fn test_sliding_dft() {
+        let mut rng = Rng::new(8);
+        let x: Vec<f64> = (0..1000).map(|_| rng.next_f64() * 2.0 - 1.0).collect();
+        let ks = [0, 1, 7, 15];
+        let mut s = SlidingDft::new(16, &ks);
+
+        for (i, &v) in x.iter().enumerate() {
+            s.push(v);
+            if i >= 15 {
+                let window = &x[i - 15..=i];
+                for (&k, &b) in ks.iter().zip(s.bins()) {
+                    assert!(close(b, dft(window, k), 1e-9), "{} {}", i, k);
+                }
+            }
+        }
+        assert_eq!(s.window().len(), 16);
+        assert_eq!(s.window()[15], x[999]);
+
+        // Before the window fills, the missing samples count as zeros
+        let mut s = SlidingDft::new(4, &[1]);
+        s.push(1.0);
+        assert!(close(s.bins()[0], dft(&[0.0, 0.0, 0.0, 1.0], 1), 1e-12));
+    }
+
+    #[test]
+    fn test_correlation() {
+        let mut rng = Rng::new(12);
+        let x: Vec<f64> = (0..300).map(|_| rng.next_f64() - 0.5).collect();
+
+        // The FFT matches the direct DFT and inverts
+        let mut a: Vec<Complex> = x[..256].iter().map(|&v| Complex::new(v, 0.0)).collect();
+        fft(&mut a, false);
+        for k in [0, 1, 100, 255] {
+            assert!(close(a[k], dft(&x[..256], k), 1e-9));
+        }
+        fft(&mut a, true);
+        assert!(a
+            .iter()
+            .zip(&x)
+            .all(|(c, &v)| close(*c, Complex::new(v, 0.0), 1e-12)));
+
+        // Both ways agree for every normalization
+        let y = &x[100..160];
+        for &norm in &[
+            Normalization::None,
+            Normalization::Biased,
+            Normalization::Unbiased,
+            Normalization::Coefficient,
+        ] {
+            let (d, f) = (
+                cross_correlation(&x, y, norm),
+                cross_correlation_fft(&x, y, norm),
+            );
+            assert_eq!(d.len(), x.len() + y.len() - 1);
+            assert!(d.iter().zip(&f).all(|(a, b)| (a - b).abs() < 1e-9));
+            let (d, f) = (
+                autocorrelation(&x, 50, norm),
+                autocorrelation_fft(&x, 50, norm),
+            );
+            assert_eq!(d.len(), 51);
+            assert!(d.iter().zip(&f).all(|(a, b)| (a - b).abs() < 1e-9));
+        }
+
+        // The copy is found at its offset
+        let r = cross_correlation_fft(&x, y, Normalization::None);
+        let peak = (0..r.len()).max_by(|&i, &j| r[i].total_cmp(&r[j])).unwrap();
+        assert_eq!(peak, 100 + y.len() - 1);
+
+        // A period of 4 shows up at lag 4
+        let periodic: Vec<f64> = (0..40).map(|i| [1.0, 2.0, 0.0, -3.0][i % 4]).collect();
+        let r = autocorrelation(&periodic, 6, Normalization::Unbiased);
+        assert!((r[4] - r[0]).abs() < 1e-12);
+        assert!(r[1..4].iter().all(|&v| v < r[0]));
+        assert_eq!(
+            autocorrelation(&periodic, 0, Normalization::Coefficient),
+            [1.0]
+        );
+        assert_eq!(
+            autocorrelation(&[1.0, 2.0], 9, Normalization::None),
+            [5.0, 2.0]
+        );
+        assert!(cross_correlation(&[], &[1.0], Normalization::Biased).is_empty());
+        assert!(autocorrelation_fft(&[], 3, Normalization::Biased).is_empty());
+    }
+
+    #[test]
+    fn test_windows_and_welch() {
+        for &w in &[
+            Window::Rectangular,
+            Window::Hann,
+            Window::Hamming,
+            Window::Blackman,
+        ] {
+            let s = w.symmetric(9);
+            assert!((0..9).all(|i| (s[i] - s[8 - i]).abs() < 1e-12));
+            assert!((s[4] - 1.0).abs() < 1e-12);
+            let p = w.periodic(8);
+            assert!((1..8).all(|i| (p[i] - p[8 - i]).abs() < 1e-12));
+            assert_eq!(w.symmetric(1), [1.0]);
+        }
+        assert!(Window::Blackman.symmetric(9)[0].abs() < 1e-12);
+        assert!((Window::Hamming.periodic(8)[0] - 0.08).abs() < 1e-12);
+
+        // A single rectangular segment satisfies Parseval's theorem exactly
+        let mut rng = Rng::new(5);
+        let x: Vec<f64> = (0..4096).map(|_| rng.next_f64() - 0.5).collect();
+        let p = welch(&x[..256], 50.0, 256, 0, Window::Rectangular);
+        let mean_power = x[..256].iter().map(|v| v * v).sum::<f64>() / 256.0;
+        assert!((p.iter().sum::<f64>() * 50.0 / 256.0 - mean_power).abs() < 1e-12);
+
+        // Averaging flattens white noise towards 2 variance / sample_rate
+        let p = welch(&x, 50.0, 64, 32, Window::Hann);
+        assert_eq!(p.len(), 33);
+        let mid = p[1..32].iter().sum::<f64>() / 31.0;
+        assert!((mid - 2.0 / 12.0 / 50.0).abs() < 0.1 * mid);
+
+        // A tone stands out at its bin
+        let tone: Vec<f64> = (0..2048)
+            .map(|i| (2.0 * PI * 200.0 * i as f64 / 1600.0).cos() + 0.1 * x[i])
+            .collect();
+        let p = welch(&tone, 1600.0, 128, 64, Window::Blackman);
+        let peak = (0..p.len()).max_by(|&i, &j| p[i].total_cmp(&p[j])).unwrap();
+        assert_eq!(peak, 16);
+        assert!(p[40] < 1e-3 * p[16]);
+    }
+}
@@ -0,0 +1,328 @@
+use super::Window;
+use alloc::vec::Vec;
+use core::f64::consts::PI;
+
+// -----------------------------------------------------------------------------
+// Filter
+// -----------------------------------------------------------------------------
+
+/// Stateful filter processing a stream sample by sample.
+pub trait Filter {
+    /// Feeds the next input sample and returns the next output sample.
+    fn push(&mut self, x: f64) -> f64;
+
+    /// Forgets all past samples, as if the input had always been 0.
+    fn reset(&mut self);
+
+    /// Filters a whole block, continuing from the current state.
+    fn apply(&mut self, xs: &[f64]) -> Vec<f64> {
+        xs.iter().map(|&x| self.push(x)).collect()
+    }
+}
+
+// -----------------------------------------------------------------------------
+// FIR
+// -----------------------------------------------------------------------------
+
+/// Finite impulse response filter y(n) = Σ h(k) x(n - k) over its taps h, costing one
+/// multiplication per tap and sample. Symmetric taps as designed here delay every frequency by
+/// the same (len - 1) / 2 samples.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::signal::{Filter, Fir, Window};
+/// // Keep what lies below 100 Hz at 1000 Hz
+/// let mut f = Fir::lowpass(100.0, 1000.0, 51, Window::Hamming);
+/// let y = f.apply(&[1.0; 100]); // settles at 1 after 50 samples
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fir {
+    taps: Vec<f64>,
+    /// Last inputs as a ring buffer, `pos` being the oldest
+    history: Vec<f64>,
+    pos: usize,
+}
+
+impl Fir {
+    /// Filter of the given taps h(0), h(1), ...
+    pub fn new(taps: Vec<f64>) -> Self {
+        assert!(!taps.is_empty(), "filter has no taps");
+        Fir {
+            history: vec![0.0; taps.len()],
+            taps,
+            pos: 0,
+        }
+    }
+
+    /// Lowpass by the windowed sinc method, the ideal infinite response cut off to `len` taps
+    /// by the window. More taps give a steeper transition, whose width is about 4 sample_rate
+    /// / len for Hann or Hamming and 6 sample_rate / len for Blackman. The gain at 0 Hz is
+    /// exactly 1.
+    ///
+    /// # Arguments
+    ///
+    /// * `cutoff` - Frequency of half the amplitude, below sample_rate / 2
+    /// * `sample_rate` - Samples per unit of time
+    /// * `len` - Number of taps
+    /// * `window` - Taper of the sinc, [`Window::Hamming`] being a common choice
+    pub fn lowpass(cutoff: f64, sample_rate: f64, len: usize, window: Window) -> Self {
+        Fir::new(sinc_lowpass(cutoff, sample_rate, len, window))
+    }
+
+    /// Highpass by spectral inversion of [`Fir::lowpass`], subtracting it from the identity.
+    /// Needs an odd number of taps, as even symmetric filters always block the Nyquist
+    /// frequency.
+    pub fn highpass(cutoff: f64, sample_rate: f64, len: usize, window: Window) -> Self {
+        assert!(len % 2 == 1, "highpass needs an odd number of taps");
+
+        let mut taps = sinc_lowpass(cutoff, sample_rate, len, window);
+        taps.iter_mut().for_each(|h| *h = -*h);
+        taps[len / 2] += 1.0;
+        Fir::new(taps)
+    }
+
+    /// Bandpass between `low` and `high` as the difference of 2 lowpasses, see
+    /// [`Fir::lowpass`].
+    pub fn bandpass(low: f64, high: f64, sample_rate: f64, len: usize, window: Window) -> Self {
+        assert!(low < high, "band is empty");
+
+        let lo = sinc_lowpass(low, sample_rate, len, window);
+        let hi = sinc_lowpass(high, sample_rate, len, window);
+        Fir::new(hi.iter().zip(&lo).map(|(h, l)| h - l).collect())
+    }
+
+    pub fn taps(&self) -> &[f64] {
+        &self.taps
+    }
+}
+
+/// Windowed sinc taps normalized to a gain of 1 at 0 Hz.
+pub(super) fn sinc_lowpass(cutoff: f64, sample_rate: f64, len: usize, window: Window) -> Vec<f64> {
+    assert!(
+        cutoff > 0.0 && cutoff < sample_rate / 2.0,
+        "cutoff has to lie between 0 and the Nyquist frequency"
+    );
+
+    let fc = cutoff / sample_rate;
+    let mid = (len - 1) as f64 / 2.0;
+    let mut taps: Vec<f64> = window
+        .symmetric(len)
+        .iter()
+        .enumerate()
+        .map(|(n, w)| {
+            let t = n as f64 - mid;
+            let sinc = if t == 0.0 {
+                2.0 * fc
+            } else {
+                (2.0 * PI * fc * t).sin() / (PI * t)
+            };
+            sinc * w
+        })
+        .collect();
+
+    let sum: f64 = taps.iter().sum();
+    taps.iter_mut().for_each(|h| *h /= sum);
+    taps
+}
+
+impl Filter for Fir {
+    fn push(&mut self, x: f64) -> f64 {
+        let n = self.taps.len();
+        self.history[self.pos] = x;
+        self.pos = (self.pos + 1) % n;
+
+        // Newest input meets h(0), walking back through the ring
+        let (older, newer) = self.history.split_at(self.pos);
+        newer
+            .iter()
+            .chain(older)
+            .rev()
+            .zip(&self.taps)
+            .map(|(x, h)| x * h)
+            .sum()
+    }
+
+    fn reset(&mut self) {
+        self.history.iter_mut().for_each(|x| *x = 0.0);
+        self.pos = 0;
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Biquad
+// -----------------------------------------------------------------------------
+
+/// Second order infinite impulse response section
+/// y(n) = b0 x(n) + b1 x(n - 1) + b2 x(n - 2) - a1 y(n - 1) - a2 y(n - 2), in transposed direct
+/// form II. The designs follow Robert Bristow-Johnson's audio EQ cookbook, giving a sharper
+/// response than a FIR of the same cost at the price of a frequency dependent delay.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::signal::{Biquad, Filter};
+/// let mut f = Biquad::lowpass(100.0, 1000.0, std::f64::consts::FRAC_1_SQRT_2);
+/// let y = f.apply(&[1.0; 100]); // settles at 1
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Biquad {
+    b: [f64; 3],
+    a: [f64; 2],
+    /// State of the transposed direct form
+    z: [f64; 2],
+}
+
+impl Biquad {
+    /// Filter of the transfer function (b0 + b1 z^-1 + b2 z^-2) / (a0 + a1 z^-1 + a2 z^-2).
+    pub fn new(b: [f64; 3], a: [f64; 3]) -> Self {
+        assert!(a[0] != 0.0, "a0 is 0");
+        Biquad {
+            b: [b[0] / a[0], b[1] / a[0], b[2] / a[0]],
+            a: [a[1] / a[0], a[2] / a[0]],
+            z: [0.0; 2],
+        }
+    }
+
+    /// Lowpass with a gain of 1 at 0 Hz. A `q` of 1 / √2 gives the flat Butterworth response,
+    /// larger values a resonant peak at the cutoff.
+    ///
+    /// # Arguments
+    ///
+    /// * `cutoff` - Corner frequency, below sample_rate / 2
+    /// * `sample_rate` - Samples per unit of time
+    /// * `q` - Quality factor
+    pub fn lowpass(cutoff: f64, sample_rate: f64, q: f64) -> Self {
+        let (cos, alpha) = rbj(cutoff, sample_rate, q);
+        let b1 = 1.0 - cos;
+        Biquad::new(
+            [b1 / 2.0, b1, b1 / 2.0],
+            [1.0 + alpha, -2.0 * cos, 1.0 - alpha],
+        )
+    }
+
+    /// Highpass with a gain of 1 at the Nyquist frequency, see [`Biquad::lowpass`].
+    pub fn highpass(cutoff: f64, sample_rate: f64, q: f64) -> Self {
+        let (cos, alpha) = rbj(cutoff, sample_rate, q);
+        let b1 = 1.0 + cos;
+        Biquad::new(
+            [b1 / 2.0, -b1, b1 / 2.0],
+            [1.0 + alpha, -2.0 * cos, 1.0 - alpha],
+        )
+    }
+
+    /// Bandpass with a gain of 1 at `center` and a bandwidth of about center / q.
+    pub fn bandpass(center: f64, sample_rate: f64, q: f64) -> Self {
+        let (cos, alpha) = rbj(center, sample_rate, q);
+        Biquad::new([alpha, 0.0, -alpha], [1.0 + alpha, -2.0 * cos, 1.0 - alpha])
+    }
+
+    /// Normalized coefficients ([b0, b1, b2], [a1, a2]) with a0 = 1.
+    pub fn coefficients(&self) -> ([f64; 3], [f64; 2]) {
+        (self.b, self.a)
+    }
+}
+
+/// cos(w) and alpha = sin(w) / 2q of the cookbook for the angular frequency w.
+fn rbj(freq: f64, sample_rate: f64, q: f64) -> (f64, f64) {
+    assert!(
+        freq > 0.0 && freq < sample_rate / 2.0,
+        "frequency has to lie between 0 and the Nyquist frequency"
+    );
+    assert!(q > 0.0, "q has to be positive");
+
+    let w = 2.0 * PI * freq / sample_rate;
+    (w.cos(), w.sin() / (2.0 * q))
+}
+
+impl Filter for Biquad {
+    fn push(&mut self, x: f64) -> f64 {
+        let y = self.b[0] * x + self.z[0];
+        self.z[0] = self.b[1] * x - self.a[0] * y + self.z[1];
+        self.z[1] = self.b[2] * x - self.a[1] * y;
+        y
+    }
+
+    fn reset(&mut self) {
+        self.z = [0.0; 2];
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Amplitude of a unit sine after passing through the filter and settling, from the mean
+    /// power over a whole number of periods.
+    fn gain<F: Filter>(f: &mut F, freq: f64, sample_rate: f64) -> f64 {
+        f.reset();
+        let x: Vec<f64> = (0..12000)
+            .map(|i| (2.0 * PI * freq * i as f64 / sample_rate).sin())
+            .collect();
+        let y = &f.apply(&x)[4000..];
+        (2.0 * y.iter().map(|v| v * v).sum::<f64>() / y.len() as f64).sqrt()
+    }
+
+    #[test]
+    fn test_fir() {
+        let mut lp = Fir::lowpass(100.0, 1000.0, 101, Window::Hamming);
+        assert_eq!(lp.taps().len(), 101);
+        assert!((lp.taps().iter().sum::<f64>() - 1.0).abs() < 1e-12);
+        assert!((0..101).all(|i| (lp.taps()[i] - lp.taps()[100 - i]).abs() < 1e-15));
+        assert!((gain(&mut lp, 20.0, 1000.0) - 1.0).abs() < 0.01);
+        assert!((gain(&mut lp, 100.0, 1000.0) - 0.5).abs() < 0.02);
+        assert!(gain(&mut lp, 250.0, 1000.0) < 0.01);
+
+        let mut hp = Fir::highpass(100.0, 1000.0, 101, Window::Blackman);
+        assert!(hp.taps().iter().sum::<f64>().abs() < 1e-12);
+        assert!(gain(&mut hp, 20.0, 1000.0) < 0.01);
+        assert!((gain(&mut hp, 300.0, 1000.0) - 1.0).abs() < 0.01);
+
+        let mut bp = Fir::bandpass(100.0, 200.0, 1000.0, 151, Window::Hann);
+        assert!(gain(&mut bp, 30.0, 1000.0) < 0.01);
+        assert!((gain(&mut bp, 150.0, 1000.0) - 1.0).abs() < 0.01);
+        assert!(gain(&mut bp, 350.0, 1000.0) < 0.01);
+
+        // Streaming matches the convolution with the taps
+        let mut f = Fir::new(vec![1.0, 2.0, 3.0]);
+        assert_eq!(
+            f.apply(&[1.0, 0.0, 0.0, 1.0, 1.0]),
+            [1.0, 2.0, 3.0, 1.0, 3.0]
+        );
+        f.reset();
+        assert_eq!(f.push(2.0), 2.0);
+    }
+
+    #[test]
+    fn test_biquad() {
+        let q = core::f64::consts::FRAC_1_SQRT_2;
+        let mut lp = Biquad::lowpass(100.0, 8000.0, q);
+        assert!((gain(&mut lp, 10.0, 8000.0) - 1.0).abs() < 0.01);
+        assert!((gain(&mut lp, 100.0, 8000.0) - q).abs() < 0.01);
+        assert!(gain(&mut lp, 1000.0, 8000.0) < 0.011);
+
+        let mut hp = Biquad::highpass(1000.0, 8000.0, q);
+        assert!(gain(&mut hp, 100.0, 8000.0) < 0.011);
+        assert!((gain(&mut hp, 1000.0, 8000.0) - q).abs() < 0.01);
+
+        let mut bp = Biquad::bandpass(500.0, 8000.0, 5.0);
+        assert!((gain(&mut bp, 500.0, 8000.0) - 1.0).abs() < 0.01);
+        assert!(gain(&mut bp, 100.0, 8000.0) < 0.05);
+        assert!(gain(&mut bp, 2500.0, 8000.0) < 0.05);
+
+        // Direct form of the difference equation
+        let mut f = Biquad::new([1.0, 2.0, 1.0], [2.0, 0.5, 0.25]);
+        let ([b0, b1, b2], [a1, a2]) = f.coefficients();
+        let x = [1.0, -1.0, 0.5, 2.0, 0.0, 3.0];
+        let y = f.apply(&x);
+        for n in 2..x.len() {
+            let expected =
+                b0 * x[n] + b1 * x[n - 1] + b2 * x[n - 2] - a1 * y[n - 1] - a2 * y[n - 2];
+            assert!((y[n] - expected).abs() < 1e-12);
+        }
+    }
+}
@@ -0,0 +1,211 @@
+use super::filter::sinc_lowpass;
+use super::Window;
+use alloc::vec::Vec;
+
+// -----------------------------------------------------------------------------
+// Samples
+// -----------------------------------------------------------------------------
+
+/// Sample type of the resamplers, computed on as `f64`.
+pub trait Sample: Copy {
+    fn to_f64(self) -> f64;
+    fn from_f64(x: f64) -> Self;
+}
+
+impl Sample for f64 {
+    fn to_f64(self) -> f64 {
+        self
+    }
+
+    fn from_f64(x: f64) -> Self {
+        x
+    }
+}
+
+impl Sample for f32 {
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+
+    fn from_f64(x: f64) -> Self {
+        x as f32
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Resampling
+// -----------------------------------------------------------------------------
+
+/// Converts the sample rate by drawing straight lines between the samples. Cheap and without
+/// delay, but it doesn't filter, so frequencies above the new Nyquist frequency fold back when
+/// lowering the rate. Use [`resample_polyphase`] for that. The output spans the same time as the
+/// input, from the first sample up to the last.
+///
+/// # Arguments
+///
+/// * `x` - Signal
+/// * `from_rate` - Sample rate of x
+/// * `to_rate` - Sample rate of the result
+///
+/// # Examples
+///
+/// ```
+/// use ctl::signal::resample_linear;
+/// let y = resample_linear(&[0.0, 1.0, 0.0], 1.0, 2.0); // [0, 0.5, 1, 0.5, 0]
+/// ```
+pub fn resample_linear<T: Sample>(x: &[T], from_rate: f64, to_rate: f64) -> Vec<T> {
+    assert!(
+        from_rate > 0.0 && to_rate > 0.0,
+        "sample rate is not positive"
+    );
+    if x.len() < 2 {
+        return x.to_vec();
+    }
+
+    let step = from_rate / to_rate;
+    let len = ((x.len() - 1) as f64 / step + 1e-9).floor() as usize + 1;
+    (0..len)
+        .map(|m| {
+            let t = m as f64 * step;
+            let i = (t as usize).min(x.len() - 2);
+            let frac = t - i as f64;
+            let (a, b) = (x[i].to_f64(), x[i + 1].to_f64());
+            T::from_f64(a + (b - a) * frac)
+        })
+        .collect()
+}
+
+/// Changes the sample rate by the rational factor up / down: conceptually inserting up - 1
+/// zeros between the samples, lowpass filtering below both Nyquist frequencies and keeping every
+/// down-th sample. The polyphase form only evaluates the filter at the kept samples and skips
+/// the zeros, costing about `quality` multiplications per output sample. The filter delay is
+/// compensated, so output sample m lies at time m down / up in input samples, and the result
+/// holds ceil(len up / down) samples.
+///
+/// # Arguments
+///
+/// * `x` - Signal
+/// * `up` - Upsampling factor
+/// * `down` - Downsampling factor
+/// * `quality` - Filter taps per phase, 16 to 64 being common. More taps give a steeper
+///   anti-aliasing filter.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::signal::resample_polyphase;
+/// // 44100 Hz to 48000 Hz is 160 / 147
+/// let x = vec![0.0f32; 441];
+/// let y = resample_polyphase(&x, 160, 147, 32); // 480 samples
+/// ```
+pub fn resample_polyphase<T: Sample>(x: &[T], up: usize, down: usize, quality: usize) -> Vec<T> {
+    assert!(up > 0 && down > 0, "factor is 0");
+    assert!(quality > 0, "quality is 0");
+
+    let g = crate::math::gcd(up, down);
+    let (up, down) = (up / g, down / g);
+    if up == 1 && down == 1 {
+        return x.to_vec();
+    }
+
+    // Cut off a little below the lower Nyquist frequency of the upsampled rate
+    let cutoff = 0.45 / up.max(down) as f64;
+    let len = 2 * quality * up.max(down) + 1;
+    let h: Vec<f64> = sinc_lowpass(cutoff, 1.0, len, Window::Blackman)
+        .iter()
+        .map(|v| v * up as f64)
+        .collect();
+    let delay = (len - 1) / 2;
+
+    let out_len = (x.len() * up).div_ceil(down);
+    (0..out_len)
+        .map(|m| {
+            // Taps k meeting a nonzero upsampled sample n0 - k = i up
+            let n0 = m * down + delay;
+            let sum: f64 = (n0 % up..len.min(n0 + 1))
+                .step_by(up)
+                .filter_map(|k| x.get((n0 - k) / up).map(|s| h[k] * s.to_f64()))
+                .sum();
+            T::from_f64(sum)
+        })
+        .collect()
+}
+
+/// Lowers the sample rate by an integer factor with anti-aliasing, see [`resample_polyphase`].
+///
+/// # Examples
+///
+/// ```
+/// use ctl::signal::decimate;
+/// let y = decimate(&[1.0; 100], 4, 32); // 25 samples, all about 1 except near the ends
+/// ```
+pub fn decimate<T: Sample>(x: &[T], factor: usize, quality: usize) -> Vec<T> {
+    resample_polyphase(x, 1, factor, quality)
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::f64::consts::PI;
+
+    fn sine(freq: f64, rate: f64, n: usize) -> Vec<f64> {
+        (0..n)
+            .map(|i| (2.0 * PI * freq * i as f64 / rate).sin())
+            .collect()
+    }
+
+    #[test]
+    fn test_resample_linear() {
+        // Lines are reproduced exactly
+        let ramp: Vec<f64> = (0..10).map(|i| 3.0 * i as f64 + 1.0).collect();
+        let y = resample_linear(&ramp, 3.0, 7.0);
+        assert_eq!(y.len(), 22);
+        for (m, v) in y.iter().enumerate() {
+            assert!((v - (3.0 * m as f64 * 3.0 / 7.0 + 1.0)).abs() < 1e-12);
+        }
+        assert_eq!(
+            resample_linear(&ramp, 2.0, 1.0),
+            [1.0, 7.0, 13.0, 19.0, 25.0]
+        );
+        assert_eq!(
+            resample_linear(&[1.0f32, 2.0], 1.0, 4.0),
+            [1.0, 1.25, 1.5, 1.75, 2.0]
+        );
+        assert_eq!(resample_linear(&[5.0], 1.0, 3.0), [5.0]);
+    }
+
+    #[test]
+    fn test_resample_polyphase() {
+        // A slow sine comes out as the same sine at the new rate, away from the edges
+        let x = sine(50.0, 1000.0, 1000);
+        for &(up, down) in &[(3, 2), (2, 5), (160, 147), (4, 4)] {
+            let y = resample_polyphase(&x, up, down, 32);
+            assert_eq!(y.len(), (1000 * up).div_ceil(down));
+            let rate = 1000.0 * up as f64 / down as f64;
+            let expected = sine(50.0, rate, y.len());
+            let margin = y.len() / 5;
+            for m in margin..y.len() - margin {
+                assert!((y[m] - expected[m]).abs() < 1e-3, "{}/{} {}", up, down, m);
+            }
+        }
+
+        // Decimation removes what lies above the new Nyquist frequency instead of folding it
+        let mix: Vec<f64> = sine(20.0, 1000.0, 2000)
+            .iter()
+            .zip(sine(300.0, 1000.0, 2000))
+            .map(|(a, b)| a + b)
+            .collect();
+        let y = decimate(&mix, 4, 32);
+        let expected = sine(20.0, 250.0, y.len());
+        for m in 100..y.len() - 100 {
+            assert!((y[m] - expected[m]).abs() < 1e-3);
+        }
+
+        let y = decimate(&vec![1.0f32; 400], 2, 16);
+        assert!(y[50..150].iter().all(|v| (v - 1.0).abs() < 1e-4));
+    }
+}
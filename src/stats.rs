@@ -0,0 +1,531 @@
+// Dense matrix code reads best with explicit indices
+#![allow(clippy::needless_range_loop)]
+
+#[cfg(feature = "std")]
+use crate::math::{beta_inc, erfc, gamma_q};
+#[cfg(feature = "std")]
+use alloc::vec::Vec;
+
+mod smooth;
+
+pub use smooth::{Ewma, Kalman1D, Kalman2D, MovingAverage, WeightedMovingAverage};
+
+// -----------------------------------------------------------------------------
+// Covariance
+// -----------------------------------------------------------------------------
+
+/// Calculates the mean of `N` dimensional samples. Returns zeros for an empty slice.
+///
+/// # Arguments
+///
+/// * `samples` - Samples with `N` coordinates each
+///
+/// # Examples
+///
+/// ```
+/// use ctl::stats::mean;
+/// let m = mean(&[[1.0, 2.0], [3.0, 6.0]]); // [2.0, 4.0]
+/// ```
+pub fn mean<const N: usize>(samples: &[[f64; N]]) -> [f64; N] {
+    let mut m = [0.0; N];
+
+    for s in samples {
+        for i in 0..N {
+            m[i] += s[i];
+        }
+    }
+
+    if !samples.is_empty() {
+        m.iter_mut().for_each(|m| *m /= samples.len() as f64);
+    }
+
+    m
+}
+
+/// Calculates the sample covariance matrix (normalized by `n - 1`) of `N` dimensional samples.
+/// Returns zeros for less than 2 samples.
+///
+/// # Arguments
+///
+/// * `samples` - Samples with `N` coordinates each
+///
+/// # Examples
+///
+/// ```
+/// use ctl::stats::covariance;
+/// let c = covariance(&[[0.0, 0.0], [1.0, 2.0], [2.0, 4.0]]); // [[1, 2], [2, 4]]
+/// ```
+pub fn covariance<const N: usize>(samples: &[[f64; N]]) -> [[f64; N]; N] {
+    let m = mean(samples);
+    let mut c = [[0.0; N]; N];
+
+    if samples.len() < 2 {
+        return c;
+    }
+
+    for s in samples {
+        for i in 0..N {
+            for j in i..N {
+                c[i][j] += (s[i] - m[i]) * (s[j] - m[j]);
+            }
+        }
+    }
+
+    let n = (samples.len() - 1) as f64;
+    for i in 0..N {
+        for j in i..N {
+            c[i][j] /= n;
+            c[j][i] = c[i][j];
+        }
+    }
+
+    c
+}
+
+// -----------------------------------------------------------------------------
+// Eigen decomposition
+// -----------------------------------------------------------------------------
+
+/// Calculates the eigenvalues and eigenvectors of a symmetric matrix with the cyclic Jacobi
+/// method. Eigenvalues are sorted in descending order, `vectors[i]` is the unit eigenvector of
+/// `values[i]`. Only the upper triangle of `m` is read.
+///
+/// # Arguments
+///
+/// * `m` - Symmetric matrix
+///
+/// # Examples
+///
+/// ```
+/// use ctl::stats::symmetric_eigen;
+/// let (values, vectors) = symmetric_eigen([[2.0, 1.0], [1.0, 2.0]]); // [3, 1]
+/// ```
+#[cfg(feature = "std")]
+pub fn symmetric_eigen<const N: usize>(mut m: [[f64; N]; N]) -> ([f64; N], [[f64; N]; N]) {
+    let mut v = [[0.0; N]; N];
+    for (i, row) in v.iter_mut().enumerate() {
+        row[i] = 1.0;
+    }
+
+    for i in 0..N {
+        for j in 0..i {
+            m[i][j] = m[j][i];
+        }
+    }
+
+    for _ in 0..100 {
+        let off: f64 = (0..N)
+            .flat_map(|i| (i + 1..N).map(move |j| (i, j)))
+            .map(|(i, j)| m[i][j] * m[i][j])
+            .sum();
+
+        if off < 1e-30 {
+            break;
+        }
+
+        for p in 0..N {
+            for q in p + 1..N {
+                if m[p][q] == 0.0 {
+                    continue;
+                }
+
+                // Rotation zeroing m[p][q]
+                let theta = (m[q][q] - m[p][p]) / (2.0 * m[p][q]);
+                let t = theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt());
+                let t = if theta == 0.0 { 1.0 } else { t };
+                let c = 1.0 / (t * t + 1.0).sqrt();
+                let s = t * c;
+
+                for k in 0..N {
+                    let (mkp, mkq) = (m[k][p], m[k][q]);
+                    m[k][p] = c * mkp - s * mkq;
+                    m[k][q] = s * mkp + c * mkq;
+                }
+                for k in 0..N {
+                    let (mpk, mqk) = (m[p][k], m[q][k]);
+                    m[p][k] = c * mpk - s * mqk;
+                    m[q][k] = s * mpk + c * mqk;
+                }
+                for row in v.iter_mut() {
+                    let (vp, vq) = (row[p], row[q]);
+                    row[p] = c * vp - s * vq;
+                    row[q] = s * vp + c * vq;
+                }
+            }
+        }
+    }
+
+    let mut order = [0; N];
+    for (i, o) in order.iter_mut().enumerate() {
+        *o = i;
+    }
+    order.sort_by(|&a, &b| {
+        m[b][b]
+            .partial_cmp(&m[a][a])
+            .unwrap_or(core::cmp::Ordering::Equal)
+    });
+
+    let mut values = [0.0; N];
+    let mut vectors = [[0.0; N]; N];
+    for (i, &o) in order.iter().enumerate() {
+        values[i] = m[o][o];
+        for k in 0..N {
+            vectors[i][k] = v[k][o];
+        }
+    }
+
+    (values, vectors)
+}
+
+// -----------------------------------------------------------------------------
+// PCA
+// -----------------------------------------------------------------------------
+
+/// Principal component analysis of a point cloud, see [`pca`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Pca<const N: usize> {
+    /// Mean of the samples, the origin of the projection
+    pub mean: [f64; N],
+    /// Principal axes as unit vectors, ordered by decreasing variance
+    pub axes: [[f64; N]; N],
+    /// Variance of the samples along every axis
+    pub variance: [f64; N],
+}
+
+impl<const N: usize> Pca<N> {
+    /// Share of the total variance explained by every axis.
+    pub fn explained_ratio(&self) -> [f64; N] {
+        let total: f64 = self.variance.iter().sum();
+        let mut r = [0.0; N];
+
+        if total > 0.0 {
+            for i in 0..N {
+                r[i] = self.variance[i] / total;
+            }
+        }
+
+        r
+    }
+
+    /// Coordinates of `p` relative to the mean along the principal axes.
+    pub fn project(&self, p: &[f64; N]) -> [f64; N] {
+        let mut r = [0.0; N];
+
+        for (r, axis) in r.iter_mut().zip(&self.axes) {
+            *r = (0..N).map(|k| (p[k] - self.mean[k]) * axis[k]).sum();
+        }
+
+        r
+    }
+
+    /// Inverse of [`Pca::project`].
+    pub fn unproject(&self, c: &[f64; N]) -> [f64; N] {
+        let mut p = self.mean;
+
+        for (&c, axis) in c.iter().zip(&self.axes) {
+            for k in 0..N {
+                p[k] += c * axis[k];
+            }
+        }
+
+        p
+    }
+}
+
+/// Calculates the principal axes of `N` dimensional samples through the eigen decomposition of
+/// their covariance matrix.
+///
+/// # Arguments
+///
+/// * `samples` - Samples with `N` coordinates each
+///
+/// # Examples
+///
+/// ```
+/// use ctl::stats::pca;
+/// let p = pca(&[[0.0, 0.0], [1.0, 1.0], [2.0, 2.0]]);
+/// // p.axes[0] ~ [0.707, 0.707], p.variance ~ [2, 0]
+/// ```
+#[cfg(feature = "std")]
+pub fn pca<const N: usize>(samples: &[[f64; N]]) -> Pca<N> {
+    let (variance, axes) = symmetric_eigen(covariance(samples));
+
+    Pca {
+        mean: mean(samples),
+        axes,
+        variance,
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Hypothesis tests
+// -----------------------------------------------------------------------------
+
+/// Outcome of a statistical hypothesis test.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TestResult {
+    /// Value of the test statistic
+    pub statistic: f64,
+    /// Probability of a statistic at least as extreme under the null hypothesis
+    pub p_value: f64,
+}
+
+/// Mean and unbiased variance of a sample.
+#[cfg(feature = "std")]
+fn mean_var(s: &[f64]) -> (f64, f64) {
+    let n = s.len() as f64;
+    let m = s.iter().sum::<f64>() / n;
+    let v = s.iter().map(|x| (x - m) * (x - m)).sum::<f64>() / (n - 1.0);
+    (m, v)
+}
+
+/// Two sided p-value of Student's t distribution with `df` degrees of freedom.
+#[cfg(feature = "std")]
+fn t_p_value(t: f64, df: f64) -> f64 {
+    beta_inc(0.5 * df, 0.5, df / (df + t * t))
+}
+
+/// One sample t-test of the null hypothesis that the sample has mean `mu`. Returns the t
+/// statistic and the two sided p-value.
+///
+/// # Arguments
+///
+/// * `sample` - Observations, at least 2
+/// * `mu` - Hypothesized mean
+///
+/// # Examples
+///
+/// ```
+/// use ctl::stats::t_test;
+/// let r = t_test(&[5.1, 4.9, 5.6, 5.8, 6.0, 5.7], 5.0); // t ~ 2.97, p ~ 0.031
+/// ```
+#[cfg(feature = "std")]
+pub fn t_test(sample: &[f64], mu: f64) -> TestResult {
+    let n = sample.len() as f64;
+    let (m, v) = mean_var(sample);
+    let t = (m - mu) / (v / n).sqrt();
+
+    TestResult {
+        statistic: t,
+        p_value: t_p_value(t, n - 1.0),
+    }
+}
+
+/// Two sample t-test of the null hypothesis that both samples share the same mean, assuming
+/// equal variances. Returns the t statistic and the two sided p-value.
+///
+/// # Arguments
+///
+/// * `a` - First sample, at least 2 observations
+/// * `b` - Second sample, at least 2 observations
+///
+/// # Examples
+///
+/// ```
+/// use ctl::stats::t_test_two_sample;
+/// let r = t_test_two_sample(&[1.0, 2.0, 3.0, 4.0], &[3.0, 4.0, 5.0, 6.0]); // p ~ 0.071
+/// ```
+#[cfg(feature = "std")]
+pub fn t_test_two_sample(a: &[f64], b: &[f64]) -> TestResult {
+    let (na, nb) = (a.len() as f64, b.len() as f64);
+    let ((ma, va), (mb, vb)) = (mean_var(a), mean_var(b));
+
+    let df = na + nb - 2.0;
+    let pooled = ((na - 1.0) * va + (nb - 1.0) * vb) / df;
+    let t = (ma - mb) / (pooled * (1.0 / na + 1.0 / nb)).sqrt();
+
+    TestResult {
+        statistic: t,
+        p_value: t_p_value(t, df),
+    }
+}
+
+/// Welch's t-test of the null hypothesis that both samples share the same mean, without
+/// assuming equal variances. The degrees of freedom follow the Welch-Satterthwaite equation.
+///
+/// # Arguments
+///
+/// * `a` - First sample, at least 2 observations
+/// * `b` - Second sample, at least 2 observations
+///
+/// # Examples
+///
+/// ```
+/// use ctl::stats::welch_t_test;
+/// let r = welch_t_test(&[1.0, 2.0, 3.0, 4.0], &[2.0, 6.0, 9.0, 12.0, 15.0]);
+/// ```
+#[cfg(feature = "std")]
+pub fn welch_t_test(a: &[f64], b: &[f64]) -> TestResult {
+    let (na, nb) = (a.len() as f64, b.len() as f64);
+    let ((ma, va), (mb, vb)) = (mean_var(a), mean_var(b));
+
+    let (sa, sb) = (va / na, vb / nb);
+    let t = (ma - mb) / (sa + sb).sqrt();
+    let df = (sa + sb) * (sa + sb) / (sa * sa / (na - 1.0) + sb * sb / (nb - 1.0));
+
+    TestResult {
+        statistic: t,
+        p_value: t_p_value(t, df),
+    }
+}
+
+/// Pearson's chi-squared goodness of fit test of observed category counts against expected
+/// ones, with `k - 1` degrees of freedom for `k` categories.
+///
+/// # Arguments
+///
+/// * `observed` - Observed count of every category
+/// * `expected` - Expected count of every category under the null hypothesis
+///
+/// # Examples
+///
+/// ```
+/// use ctl::stats::chi_squared_test;
+/// let r = chi_squared_test(&[16.0, 18.0, 16.0, 14.0, 12.0, 12.0], &[14.666; 6]);
+/// // statistic ~ 2.0, p ~ 0.85
+/// ```
+#[cfg(feature = "std")]
+pub fn chi_squared_test(observed: &[f64], expected: &[f64]) -> TestResult {
+    assert_eq!(observed.len(), expected.len(), "category counts differ");
+
+    let chi2: f64 = observed
+        .iter()
+        .zip(expected)
+        .map(|(o, e)| (o - e) * (o - e) / e)
+        .sum();
+    let df = (observed.len() - 1) as f64;
+
+    TestResult {
+        statistic: chi2,
+        p_value: gamma_q(0.5 * df, 0.5 * chi2),
+    }
+}
+
+/// Mann-Whitney U test of the null hypothesis that both samples come from the same
+/// distribution. Returns the U statistic of `a` and the two sided p-value of the normal
+/// approximation with tie and continuity correction, which is accurate for about 8 or more
+/// observations per sample.
+///
+/// # Arguments
+///
+/// * `a` - First sample
+/// * `b` - Second sample
+///
+/// # Examples
+///
+/// ```
+/// use ctl::stats::mann_whitney_u;
+/// let r = mann_whitney_u(&[1.0, 2.0, 3.0, 4.0, 5.0], &[6.0, 7.0, 8.0, 9.0, 10.0]);
+/// // statistic == 0.0
+/// ```
+#[cfg(feature = "std")]
+pub fn mann_whitney_u(a: &[f64], b: &[f64]) -> TestResult {
+    let (na, nb) = (a.len(), b.len());
+    let mut all: Vec<(f64, bool)> = a
+        .iter()
+        .map(|&x| (x, true))
+        .chain(b.iter().map(|&x| (x, false)))
+        .collect();
+    all.sort_by(|x, y| x.0.partial_cmp(&y.0).unwrap_or(core::cmp::Ordering::Equal));
+
+    // Average ranks of ties and the tie correction term sum(t^3 - t)
+    let (mut rank_a, mut ties) = (0.0, 0.0);
+    let mut i = 0;
+
+    while i < all.len() {
+        let j = i + all[i..].iter().take_while(|x| x.0 == all[i].0).count();
+        let rank = (i + j + 1) as f64 / 2.0;
+        let t = (j - i) as f64;
+
+        rank_a += rank * all[i..j].iter().filter(|x| x.1).count() as f64;
+        ties += t * t * t - t;
+        i = j;
+    }
+
+    let (fa, fb) = (na as f64, nb as f64);
+    let n = fa + fb;
+    let u = rank_a - fa * (fa + 1.0) / 2.0;
+
+    let mean = fa * fb / 2.0;
+    let sd = (fa * fb / 12.0 * ((n + 1.0) - ties / (n * (n - 1.0)))).sqrt();
+    let z = ((u - mean).abs() - 0.5).max(0.0) / sd;
+
+    TestResult {
+        statistic: u,
+        p_value: erfc(z / core::f64::consts::SQRT_2).min(1.0),
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    fn near(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    #[test]
+    fn test_eigen() {
+        let m = [[4.0, 1.0, 2.0], [1.0, 3.0, 0.0], [2.0, 0.0, 5.0]];
+        let (values, vectors) = symmetric_eigen(m);
+
+        assert!(values[0] >= values[1] && values[1] >= values[2]);
+        assert!(near(values.iter().sum(), 12.0));
+
+        for (l, v) in values.iter().zip(&vectors) {
+            for i in 0..3 {
+                let mv: f64 = (0..3).map(|k| m[i][k] * v[k]).sum();
+                assert!(near(mv, l * v[i]));
+            }
+        }
+    }
+
+    #[test]
+    fn test_pca() {
+        let samples: Vec<[f64; 2]> = (0..20)
+            .map(|i| {
+                let t = i as f64 - 9.5;
+                let e = if i % 4 == 0 || i % 4 == 3 { 0.1 } else { -0.1 };
+                [1.0 + 2.0 * t - e, -3.0 + t + 2.0 * e]
+            })
+            .collect();
+        let p = pca(&samples);
+
+        assert!(near(p.mean[0], 1.0) && near(p.mean[1], -3.0));
+        let a = p.axes[0];
+        assert!(near((a[1] / a[0]).abs(), 0.5));
+        assert!(p.explained_ratio()[0] > 0.99);
+
+        let c = p.project(&samples[3]);
+        let back = p.unproject(&c);
+        assert!(near(back[0], samples[3][0]) && near(back[1], samples[3][1]));
+
+        assert_eq!(covariance::<2>(&[[1.0, 1.0]]), [[0.0; 2]; 2]);
+    }
+
+    #[test]
+    fn test_hypothesis() {
+        let close = |a: f64, b: f64| (a - b).abs() < 1e-6;
+
+        let r = t_test(&[5.1, 4.9, 5.6, 5.8, 6.0, 5.7], 5.0);
+        assert!(close(r.statistic, 2.969261484) && close(r.p_value, 0.031183652));
+
+        let (a, b) = ([1.0, 2.0, 3.0, 4.0], [2.0, 6.0, 9.0, 12.0, 15.0]);
+        let r = t_test_two_sample(&a, &b);
+        assert!(close(r.statistic, -2.393172106) && close(r.p_value, 0.047944772));
+        let r = welch_t_test(&a, &b);
+        assert!(close(r.statistic, -2.672596387) && close(r.p_value, 0.047770473));
+
+        let r = chi_squared_test(&[16.0, 18.0, 16.0, 14.0, 12.0, 12.0], &[88.0 / 6.0; 6]);
+        assert!(close(r.statistic, 2.0) && close(r.p_value, 0.849145036));
+
+        let x = [1.0, 4.0, 2.0, 8.0, 5.0, 5.0, 9.0, 3.0];
+        let y = [6.0, 7.0, 10.0, 12.0, 5.0, 11.0, 13.0, 8.0, 14.0];
+        let r = mann_whitney_u(&x, &y);
+        assert!(close(r.statistic, 8.5) && close(r.p_value, 0.0091587));
+    }
+}
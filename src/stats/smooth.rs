@@ -0,0 +1,466 @@
+use crate::geometry::Point;
+use alloc::collections::VecDeque;
+
+// -----------------------------------------------------------------------------
+// Moving averages
+// -----------------------------------------------------------------------------
+
+/// Exponentially weighted moving average, every new value moves the average by the fraction
+/// `alpha` towards it.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::stats::Ewma;
+/// let mut e = Ewma::new(0.5);
+/// e.push(4.0); // 4.0, the first value starts the average
+/// e.push(8.0); // 6.0
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Ewma {
+    alpha: f64,
+    value: Option<f64>,
+}
+
+impl Ewma {
+    /// Creates an empty average with a smoothing factor in (0, 1].
+    pub fn new(alpha: f64) -> Self {
+        assert!(alpha > 0.0 && alpha <= 1.0, "alpha must be in (0, 1]");
+        Ewma { alpha, value: None }
+    }
+
+    /// Creates an empty average whose weights have the same mean age as a simple moving
+    /// average over `span` values, i.e. `alpha = 2 / (span + 1)`.
+    pub fn with_span(span: usize) -> Self {
+        Self::new(2.0 / (span as f64 + 1.0))
+    }
+
+    /// Adds a value and returns the new average.
+    pub fn push(&mut self, x: f64) -> f64 {
+        let v = match self.value {
+            Some(v) => v + self.alpha * (x - v),
+            None => x,
+        };
+
+        self.value = Some(v);
+        v
+    }
+
+    /// Current average, `None` before the first value.
+    pub fn value(&self) -> Option<f64> {
+        self.value
+    }
+}
+
+/// Mean of the last `size` values.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::stats::MovingAverage;
+/// let mut m = MovingAverage::new(2);
+/// m.push(1.0); // 1.0
+/// m.push(3.0); // 2.0
+/// m.push(7.0); // 5.0
+/// ```
+#[derive(Debug, Clone)]
+pub struct MovingAverage {
+    window: VecDeque<f64>,
+    size: usize,
+    sum: f64,
+    pushed: usize,
+}
+
+impl MovingAverage {
+    pub fn new(size: usize) -> Self {
+        assert!(size > 0, "window must not be empty");
+
+        MovingAverage {
+            window: VecDeque::with_capacity(size),
+            size,
+            sum: 0.0,
+            pushed: 0,
+        }
+    }
+
+    /// Adds a value, dropping the oldest one from a full window, and returns the new average.
+    pub fn push(&mut self, x: f64) -> f64 {
+        if self.window.len() == self.size {
+            self.sum -= self.window.pop_front().unwrap();
+        }
+
+        self.window.push_back(x);
+        self.sum += x;
+        self.pushed += 1;
+
+        // Resum once per window so rounding errors of the running sum don't pile up
+        if self.pushed == self.size {
+            self.sum = self.window.iter().sum();
+            self.pushed = 0;
+        }
+
+        self.sum / self.window.len() as f64
+    }
+
+    /// Current average, `None` before the first value.
+    pub fn value(&self) -> Option<f64> {
+        if self.window.is_empty() {
+            None
+        } else {
+            Some(self.sum / self.window.len() as f64)
+        }
+    }
+
+    /// Checks whether the window holds `size` values.
+    pub fn is_full(&self) -> bool {
+        self.window.len() == self.size
+    }
+}
+
+/// Linearly weighted mean of the last `size` values, the newest one weighted `size`, the one
+/// before `size - 1` and so on.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::stats::WeightedMovingAverage;
+/// let mut m = WeightedMovingAverage::new(3);
+/// m.push(3.0);
+/// m.push(6.0); // (3 + 2 * 6) / 3 = 5.0
+/// ```
+#[derive(Debug, Clone)]
+pub struct WeightedMovingAverage {
+    window: VecDeque<f64>,
+    size: usize,
+}
+
+impl WeightedMovingAverage {
+    pub fn new(size: usize) -> Self {
+        assert!(size > 0, "window must not be empty");
+
+        WeightedMovingAverage {
+            window: VecDeque::with_capacity(size),
+            size,
+        }
+    }
+
+    /// Adds a value, dropping the oldest one from a full window, and returns the new average.
+    pub fn push(&mut self, x: f64) -> f64 {
+        if self.window.len() == self.size {
+            self.window.pop_front();
+        }
+
+        self.window.push_back(x);
+        self.value().unwrap()
+    }
+
+    /// Current average in O(size), `None` before the first value.
+    pub fn value(&self) -> Option<f64> {
+        let n = self.window.len();
+        if n == 0 {
+            return None;
+        }
+
+        let sum: f64 = self
+            .window
+            .iter()
+            .enumerate()
+            .map(|(i, &x)| (i + 1) as f64 * x)
+            .sum();
+
+        Some(sum / (n * (n + 1) / 2) as f64)
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Kalman filter
+// -----------------------------------------------------------------------------
+
+/// Kalman filter tracking a scalar that drifts randomly between noisy measurements.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::stats::Kalman1D;
+/// let mut k = Kalman1D::new(0.0, 1.0, 1e-4, 0.25);
+/// for &z in &[1.1, 0.9, 1.05, 0.95] {
+///     k.update(z);
+/// }
+/// let x = k.estimate; // close to 1
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Kalman1D {
+    /// Current estimate of the value
+    pub estimate: f64,
+    /// Variance of the estimate
+    pub variance: f64,
+    /// Variance added by the drift of every step
+    pub process_noise: f64,
+    /// Variance of the measurements
+    pub measurement_noise: f64,
+}
+
+impl Kalman1D {
+    /// # Arguments
+    ///
+    /// * `estimate` - Initial guess
+    /// * `variance` - Uncertainty of the initial guess
+    /// * `process_noise` - Variance of the drift per step
+    /// * `measurement_noise` - Variance of the measurements
+    pub fn new(estimate: f64, variance: f64, process_noise: f64, measurement_noise: f64) -> Self {
+        Kalman1D {
+            estimate,
+            variance,
+            process_noise,
+            measurement_noise,
+        }
+    }
+
+    /// Advances by a step without a measurement, growing the uncertainty.
+    pub fn predict(&mut self) {
+        self.variance += self.process_noise;
+    }
+
+    /// Advances by a step and folds in a measurement, returning the new estimate.
+    pub fn update(&mut self, z: f64) -> f64 {
+        self.predict();
+
+        let gain = self.variance / (self.variance + self.measurement_noise);
+        self.estimate += gain * (z - self.estimate);
+        self.variance *= 1.0 - gain;
+
+        self.estimate
+    }
+}
+
+/// Kalman filter tracking a point moving with roughly constant velocity, e.g. to smooth noisy
+/// positions. The state is the position and velocity, changes of the velocity are modeled as
+/// random accelerations.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::geometry::Point;
+/// use ctl::stats::Kalman2D;
+/// let mut k = Kalman2D::new(Point { x: 0.0, y: 0.0 }, 10.0, 0.01, 0.5);
+/// for i in 1..=20 {
+///     k.predict(1.0);
+///     k.update(Point { x: i as f64, y: 2.0 * i as f64 });
+/// }
+/// let v = k.velocity; // close to (1, 2)
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Kalman2D {
+    pub position: Point<f64>,
+    pub velocity: Point<f64>,
+    /// Covariance of the state (x, y, vx, vy)
+    pub covariance: [[f64; 4]; 4],
+    /// Variance of the random acceleration per unit of time
+    pub process_noise: f64,
+    /// Variance of the measured coordinates
+    pub measurement_noise: f64,
+}
+
+impl Kalman2D {
+    /// Creates a filter at rest.
+    ///
+    /// # Arguments
+    ///
+    /// * `position` - Initial guess of the position
+    /// * `variance` - Uncertainty of the initial position and velocity
+    /// * `process_noise` - Variance of the random acceleration
+    /// * `measurement_noise` - Variance of the measured coordinates
+    pub fn new(
+        position: Point<f64>,
+        variance: f64,
+        process_noise: f64,
+        measurement_noise: f64,
+    ) -> Self {
+        let mut covariance = [[0.0; 4]; 4];
+        for i in 0..4 {
+            covariance[i][i] = variance;
+        }
+
+        Kalman2D {
+            position,
+            velocity: Point { x: 0.0, y: 0.0 },
+            covariance,
+            process_noise,
+            measurement_noise,
+        }
+    }
+
+    /// Moves the state `dt` time units ahead, growing the uncertainty.
+    pub fn predict(&mut self, dt: f64) {
+        self.position = Point {
+            x: self.position.x + dt * self.velocity.x,
+            y: self.position.y + dt * self.velocity.y,
+        };
+
+        // P = F P F^T with F adding dt times the velocity to the position
+        let p = &mut self.covariance;
+        for i in 0..4 {
+            p[i][0] += dt * p[i][2];
+            p[i][1] += dt * p[i][3];
+        }
+        for j in 0..4 {
+            p[0][j] += dt * p[2][j];
+            p[1][j] += dt * p[3][j];
+        }
+
+        // Random acceleration spread over the step
+        let q = self.process_noise;
+        let (dt2, dt3, dt4) = (dt * dt, dt * dt * dt, dt * dt * dt * dt);
+        for k in 0..2 {
+            p[k][k] += q * dt4 / 4.0;
+            p[k][k + 2] += q * dt3 / 2.0;
+            p[k + 2][k] += q * dt3 / 2.0;
+            p[k + 2][k + 2] += q * dt2;
+        }
+    }
+
+    /// Folds in a measured position and returns the new position estimate.
+    pub fn update(&mut self, z: Point<f64>) -> Point<f64> {
+        let p = self.covariance;
+        let r = self.measurement_noise;
+
+        // Inverse of the 2x2 innovation covariance S = H P H^T + R
+        let (a, b, c, d) = (p[0][0] + r, p[0][1], p[1][0], p[1][1] + r);
+        let det = a * d - b * c;
+        let s_inv = [[d / det, -b / det], [-c / det, a / det]];
+
+        // Gain K = P H^T S^-1, with H picking the position
+        let mut k = [[0.0; 2]; 4];
+        for i in 0..4 {
+            for j in 0..2 {
+                k[i][j] = p[i][0] * s_inv[0][j] + p[i][1] * s_inv[1][j];
+            }
+        }
+
+        let y = [z.x - self.position.x, z.y - self.position.y];
+        let dx: [f64; 4] = core::array::from_fn(|i| k[i][0] * y[0] + k[i][1] * y[1]);
+
+        self.position = Point {
+            x: self.position.x + dx[0],
+            y: self.position.y + dx[1],
+        };
+        self.velocity = Point {
+            x: self.velocity.x + dx[2],
+            y: self.velocity.y + dx[3],
+        };
+
+        // P = (I - K H) P
+        for i in 0..4 {
+            for j in 0..4 {
+                self.covariance[i][j] = p[i][j] - k[i][0] * p[0][j] - k[i][1] * p[1][j];
+            }
+        }
+
+        self.position
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::random::Rng;
+
+    #[test]
+    fn test_averages() {
+        let mut e = Ewma::with_span(3);
+        assert_eq!(e.value(), None);
+        assert_eq!(e.push(4.0), 4.0);
+        assert_eq!(e.push(8.0), 6.0);
+        assert_eq!(e.push(2.0), 4.0);
+
+        let mut m = MovingAverage::new(3);
+        let mut w = WeightedMovingAverage::new(3);
+        let xs = [1.0, 2.0, 6.0, 4.0, -1.0];
+
+        for (i, &x) in xs.iter().enumerate() {
+            let last = &xs[i.saturating_sub(2)..=i];
+            let mean = last.iter().sum::<f64>() / last.len() as f64;
+            assert!((m.push(x) - mean).abs() < 1e-12);
+            w.push(x);
+        }
+        assert!(m.is_full());
+        assert_eq!(w.value(), Some((6.0 + 2.0 * 4.0 - 3.0) / 6.0));
+
+        // The running sum recovers a small value next to a huge one that left the window
+        let mut m = MovingAverage::new(2);
+        for &x in &[1e17, 1.0, 1.0, 1.0] {
+            m.push(x);
+        }
+        assert_eq!(m.value(), Some(1.0));
+    }
+
+    #[test]
+    fn test_kalman() {
+        let mut rng = Rng::new(9);
+        let mut k = Kalman1D::new(0.0, 1e6, 0.0, 0.5);
+
+        let mut sum = 0.0;
+        for i in 1..=200 {
+            let z = 3.0 + rng.range_f64(-1.0, 1.0);
+            sum += z;
+            k.update(z);
+
+            // Without drift and a vague start the filter is the running mean
+            assert!((k.estimate - sum / i as f64).abs() < 1e-5);
+        }
+        assert!((k.estimate - 3.0).abs() < 0.1);
+        assert!((k.variance - 0.5 / 200.0).abs() < 1e-8);
+
+        let before = k;
+        k.predict();
+        assert_eq!(k.estimate, before.estimate);
+        k.process_noise = 0.1;
+        k.predict();
+        assert!(k.variance > before.variance);
+    }
+
+    #[test]
+    fn test_kalman_2d() {
+        let mut rng = Rng::new(5);
+        let mut k = Kalman2D::new(Point { x: 0.0, y: 0.0 }, 100.0, 1e-4, 0.25);
+
+        let truth = |t: f64| Point {
+            x: 1.0 + 0.5 * t,
+            y: -2.0 * t,
+        };
+
+        let (mut raw, mut smooth) = (0.0, 0.0);
+        for i in 1..=200 {
+            let t = i as f64 * 0.5;
+            let z = truth(t)
+                + Point {
+                    x: rng.range_f64(-0.8, 0.8),
+                    y: rng.range_f64(-0.8, 0.8),
+                };
+
+            k.predict(0.5);
+            let est = k.update(z);
+
+            if i > 100 {
+                let (e, m) = (est - truth(t), z - truth(t));
+                smooth += e.x * e.x + e.y * e.y;
+                raw += m.x * m.x + m.y * m.y;
+            }
+        }
+
+        assert!(smooth < raw / 10.0);
+        assert!((k.velocity.x - 0.5).abs() < 0.02);
+        assert!((k.velocity.y + 2.0).abs() < 0.02);
+
+        // The covariance stays symmetric
+        for i in 0..4 {
+            for j in 0..4 {
+                assert!((k.covariance[i][j] - k.covariance[j][i]).abs() < 1e-12);
+            }
+        }
+    }
+}
@@ -0,0 +1,343 @@
+use core::cmp::Ordering;
+use core::fmt;
+use core::marker::PhantomData;
+use core::ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign};
+
+// -----------------------------------------------------------------------------
+// Units
+// -----------------------------------------------------------------------------
+
+/// Marker of the dimension of a [`Quantity`].
+pub trait Unit {}
+
+/// Result dimension of multiplying two dimensions.
+pub trait UnitMul<R> {
+    type Output;
+}
+
+/// Result dimension of dividing two dimensions.
+pub trait UnitDiv<R> {
+    type Output;
+}
+
+macro_rules! units {
+    ($($(#[$doc:meta])* $U:ident),*) => {
+        $(
+            $(#[$doc])*
+            #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+            pub enum $U {}
+
+            impl Unit for $U {}
+
+            impl UnitMul<Scalar> for $U {
+                type Output = $U;
+            }
+
+            impl UnitDiv<Scalar> for $U {
+                type Output = $U;
+            }
+
+            impl UnitDiv<$U> for $U {
+                type Output = Scalar;
+            }
+
+            impl UnitMul<$U> for Scalar {
+                type Output = $U;
+            }
+        )*
+    };
+}
+
+macro_rules! compose {
+    ($($A:ident * $B:ident = $C:ident),* $(,)?) => {
+        $(
+            impl UnitMul<$B> for $A {
+                type Output = $C;
+            }
+
+            impl UnitDiv<$B> for $C {
+                type Output = $A;
+            }
+        )*
+    };
+}
+
+/// Dimensionless number.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Scalar {}
+
+impl Unit for Scalar {}
+
+impl UnitMul<Scalar> for Scalar {
+    type Output = Scalar;
+}
+
+impl UnitDiv<Scalar> for Scalar {
+    type Output = Scalar;
+}
+
+units!(
+    /// Meters
+    Length,
+    /// Square meters
+    Area,
+    /// Seconds
+    Time,
+    /// Meters per second
+    Velocity,
+    /// Meters per second squared
+    Acceleration,
+    /// Radians
+    Angle,
+    /// Radians per second
+    AngularVelocity,
+    /// Per second
+    Frequency
+);
+
+compose!(
+    Length * Length = Area,
+    Velocity * Time = Length,
+    Time * Velocity = Length,
+    Acceleration * Time = Velocity,
+    Time * Acceleration = Velocity,
+    AngularVelocity * Time = Angle,
+    Time * AngularVelocity = Angle,
+    Frequency * Time = Scalar,
+    Time * Frequency = Scalar,
+    Length * Frequency = Velocity,
+    Velocity * Frequency = Acceleration,
+    Angle * Frequency = AngularVelocity,
+);
+
+// -----------------------------------------------------------------------------
+// Quantity
+// -----------------------------------------------------------------------------
+
+/// Number tagged with its dimension. Only quantities of the same dimension can be added or
+/// compared, while products and quotients compose the dimension at compile time. The wrapper
+/// has the same layout as `T`.
+///
+/// Quantities work as coordinates of [`Point`](crate::geometry::Point), which keeps
+/// positions and offsets in the same unit.
+///
+/// # Examples
+///
+/// ```
+/// use ctl::units::{Length, Quantity, Time, Velocity};
+/// let d = Quantity::<f64, Length>::new(100.0);
+/// let t = Quantity::<f64, Time>::new(8.0);
+/// let v: Quantity<f64, Velocity> = d / t; // 12.5 m/s
+/// let back: Quantity<f64, Length> = v * t + d; // 200 m
+/// // d + t doesn't compile
+/// ```
+#[repr(transparent)]
+pub struct Quantity<T, U> {
+    pub value: T,
+    unit: PhantomData<U>,
+}
+
+impl<T, U> Quantity<T, U> {
+    pub const fn new(value: T) -> Self {
+        Quantity {
+            value,
+            unit: PhantomData,
+        }
+    }
+}
+
+impl<T: Copy, U> Copy for Quantity<T, U> {}
+
+impl<T: Clone, U> Clone for Quantity<T, U> {
+    fn clone(&self) -> Self {
+        Quantity::new(self.value.clone())
+    }
+}
+
+impl<T: Default, U> Default for Quantity<T, U> {
+    fn default() -> Self {
+        Quantity::new(T::default())
+    }
+}
+
+impl<T: fmt::Debug, U> fmt::Debug for Quantity<T, U> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Quantity")
+            .field(&self.value)
+            .field(&core::any::type_name::<U>())
+            .finish()
+    }
+}
+
+impl<T: PartialEq, U> PartialEq for Quantity<T, U> {
+    fn eq(&self, o: &Self) -> bool {
+        self.value == o.value
+    }
+}
+
+impl<T: PartialOrd, U> PartialOrd for Quantity<T, U> {
+    fn partial_cmp(&self, o: &Self) -> Option<Ordering> {
+        self.value.partial_cmp(&o.value)
+    }
+}
+
+impl<T: Add<Output = T>, U> Add for Quantity<T, U> {
+    type Output = Self;
+
+    fn add(self, o: Self) -> Self {
+        Quantity::new(self.value + o.value)
+    }
+}
+
+impl<T: Sub<Output = T>, U> Sub for Quantity<T, U> {
+    type Output = Self;
+
+    fn sub(self, o: Self) -> Self {
+        Quantity::new(self.value - o.value)
+    }
+}
+
+impl<T: AddAssign, U> AddAssign for Quantity<T, U> {
+    fn add_assign(&mut self, o: Self) {
+        self.value += o.value;
+    }
+}
+
+impl<T: SubAssign, U> SubAssign for Quantity<T, U> {
+    fn sub_assign(&mut self, o: Self) {
+        self.value -= o.value;
+    }
+}
+
+impl<T: Neg<Output = T>, U> Neg for Quantity<T, U> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Quantity::new(-self.value)
+    }
+}
+
+impl<T: Mul<Output = T>, U: UnitMul<R>, R> Mul<Quantity<T, R>> for Quantity<T, U> {
+    type Output = Quantity<T, U::Output>;
+
+    fn mul(self, o: Quantity<T, R>) -> Self::Output {
+        Quantity::new(self.value * o.value)
+    }
+}
+
+impl<T: Div<Output = T>, U: UnitDiv<R>, R> Div<Quantity<T, R>> for Quantity<T, U> {
+    type Output = Quantity<T, U::Output>;
+
+    fn div(self, o: Quantity<T, R>) -> Self::Output {
+        Quantity::new(self.value / o.value)
+    }
+}
+
+macro_rules! gen_scale {
+    ($($T:ty),*) => {
+        $(
+            impl<U> Mul<$T> for Quantity<$T, U> {
+                type Output = Self;
+
+                fn mul(self, k: $T) -> Self {
+                    Quantity::new(self.value * k)
+                }
+            }
+
+            impl<U> Mul<Quantity<$T, U>> for $T {
+                type Output = Quantity<$T, U>;
+
+                fn mul(self, q: Quantity<$T, U>) -> Quantity<$T, U> {
+                    Quantity::new(self * q.value)
+                }
+            }
+
+            impl<U> Div<$T> for Quantity<$T, U> {
+                type Output = Self;
+
+                fn div(self, k: $T) -> Self {
+                    Quantity::new(self.value / k)
+                }
+            }
+        )*
+    };
+}
+
+gen_scale!(f32, f64, i32, i64);
+
+macro_rules! gen_angle {
+    ($($T:ty),*) => {
+        $(
+            impl Quantity<$T, Angle> {
+                /// Angle from degrees.
+                pub fn from_degrees(deg: $T) -> Self {
+                    Quantity::new(deg.to_radians())
+                }
+
+                /// Angle in degrees.
+                pub fn to_degrees(self) -> $T {
+                    self.value.to_degrees()
+                }
+            }
+        )*
+    };
+}
+
+gen_angle!(f32, f64);
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::Point;
+
+    type Meters = Quantity<f64, Length>;
+    type Seconds = Quantity<f64, Time>;
+
+    #[test]
+    fn test_quantities() {
+        let d = Meters::new(100.0);
+        let t = Seconds::new(8.0);
+
+        let v = d / t;
+        assert_eq!(v, Quantity::<f64, Velocity>::new(12.5));
+        assert_eq!(v * t + d, Meters::new(200.0));
+        assert_eq!((v / t).value, 1.5625);
+
+        let area: Quantity<f64, Area> = d * Meters::new(2.0);
+        assert_eq!(area.value, 200.0);
+        assert_eq!(area / d, Meters::new(2.0));
+
+        let ratio: Quantity<f64, Scalar> = d / Meters::new(50.0);
+        assert_eq!(ratio.value, 2.0);
+        assert_eq!(d * ratio, Meters::new(200.0));
+        assert_eq!(2.0 * d / 4.0, Meters::new(50.0));
+
+        let mut x = Quantity::<i32, Time>::new(3);
+        x -= Quantity::new(5);
+        assert!(x < Quantity::new(0));
+
+        let p = Point {
+            x: Meters::new(1.0),
+            y: Meters::new(2.0),
+        } + Point {
+            x: Meters::new(3.0),
+            y: Meters::new(4.0),
+        };
+        assert_eq!(p.y, Meters::new(6.0));
+    }
+
+    #[test]
+    fn test_angles() {
+        let a = Quantity::<f64, Angle>::from_degrees(90.0);
+        assert_eq!(a.value, core::f64::consts::FRAC_PI_2);
+        assert_eq!(a.to_degrees(), 90.0);
+
+        let w: Quantity<f64, AngularVelocity> = a / Seconds::new(2.0);
+        let back: Quantity<f64, Angle> = w * Seconds::new(2.0);
+        assert_eq!(back, a);
+    }
+}